@@ -0,0 +1,134 @@
+// Prevents additional console window on Windows in release, DO NOT REMOVE!!
+#![cfg_attr(not(debug_assertions), windows_subsystem = "windows")]
+
+//! Desktop block explorer for the proof-of-work simulator
+//!
+//! The backend embeds the `proof-of-work-sim` library, so blocks are mined
+//! and validated by exactly the same code as the terminal simulator.
+
+// Module declarations
+mod miner;
+mod views;
+
+use std::sync::{Arc, Mutex};
+use proof_of_work_sim::blockchain::Blockchain;
+use proof_of_work_sim::config::Config;
+use proof_of_work_sim::node::Node;
+use proof_of_work_sim::transaction::Transaction;
+use tauri::{AppHandle, Manager, State};
+use miner::Miner;
+use sandbox_schema::explorer::{BlockView, ChainSummary, BLOCK_MINED_EVENT};
+use views::{block_view, chain_summary};
+
+/// Shared application state
+struct AppState {
+    /// The chain, shared with the mining thread
+    chain: Arc<Mutex<Blockchain>>,
+    /// Background miner
+    miner: Miner,
+    /// ID this app mines under (e.g. "node-a3f2")
+    node_id: String,
+}
+
+impl AppState {
+    fn summary(&self) -> ChainSummary {
+        let chain = self.chain.lock().unwrap();
+        chain_summary(&chain, self.miner.is_running())
+    }
+}
+
+/// Overview of the chain, mempool, and miner
+#[tauri::command]
+fn get_summary(state: State<AppState>) -> ChainSummary {
+    state.summary()
+}
+
+/// Blocks `start..start + count` (fewer if the chain is shorter)
+#[tauri::command]
+fn get_blocks(start: usize, count: usize, state: State<AppState>) -> Vec<BlockView> {
+    let chain = state.chain.lock().unwrap();
+    chain
+        .blocks
+        .iter()
+        .skip(start)
+        .take(count)
+        .map(block_view)
+        .collect()
+}
+
+/// A single block by index
+#[tauri::command]
+fn get_block(index: usize, state: State<AppState>) -> Result<BlockView, String> {
+    let chain = state.chain.lock().unwrap();
+    chain
+        .blocks
+        .get(index)
+        .map(block_view)
+        .ok_or_else(|| format!("No block at index {}", index))
+}
+
+/// Add a transfer to the mempool; it's included in the next mined block
+#[tauri::command]
+fn submit_transaction(
+    sender: String,
+    recipient: String,
+    amount: u64,
+    state: State<AppState>,
+) -> Result<ChainSummary, String> {
+    let (sender, recipient) = (sender.trim(), recipient.trim());
+    if sender.is_empty() || recipient.is_empty() {
+        return Err("Sender and recipient are required".to_string());
+    }
+    if amount == 0 {
+        return Err("Amount must be greater than zero".to_string());
+    }
+
+    let tx = Transaction::new_transfer(sender.to_string(), recipient.to_string(), amount);
+    state.chain.lock().unwrap().submit_transaction(tx);
+    Ok(state.summary())
+}
+
+/// Start mining in the background, emitting `block_mined` for every new block
+#[tauri::command]
+fn start_mining(app: AppHandle, state: State<AppState>) -> ChainSummary {
+    let chain = Arc::clone(&state.chain);
+    state.miner.start(chain, state.node_id.clone(), move |block| {
+        if let Err(err) = app.emit_all(BLOCK_MINED_EVENT, block_view(block)) {
+            eprintln!("Failed to emit {}: {}", BLOCK_MINED_EVENT, err);
+        }
+    });
+    state.summary()
+}
+
+/// Stop the background miner
+#[tauri::command]
+fn stop_mining(state: State<AppState>) -> ChainSummary {
+    state.miner.stop();
+    state.summary()
+}
+
+fn main() {
+    // Same defaults as the terminal simulator
+    let config = Config::default();
+
+    // SANDBOX_SEED makes the node id and nonces the same on every run
+    let mut chain = Blockchain::new(config);
+    let node_id = Node::generate_id(&mut chain.rng);
+
+    tauri::Builder::default()
+        .manage(AppState {
+            chain: Arc::new(Mutex::new(chain)),
+            miner: Miner::new(),
+            node_id,
+        })
+        .invoke_handler(tauri::generate_handler![
+            get_summary,
+            get_blocks,
+            get_block,
+            submit_transaction,
+            start_mining,
+            stop_mining
+        ])
+        .run(tauri::generate_context!())
+        .expect("error while running tauri application");
+}
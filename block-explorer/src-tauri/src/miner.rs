@@ -0,0 +1,98 @@
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+use std::thread;
+use std::time::{Duration, Instant};
+use proof_of_work_sim::blockchain::Blockchain;
+use proof_of_work_sim::block::Block;
+
+/// Nonces tried per lock of the chain, so commands stay responsive while mining
+const ATTEMPTS_PER_ROUND: u64 = 10_000;
+
+/// Background miner that extends a shared chain on its own thread
+pub struct Miner {
+    running: Arc<AtomicBool>,
+}
+
+impl Miner {
+    pub fn new() -> Self {
+        Miner {
+            running: Arc::new(AtomicBool::new(false)),
+        }
+    }
+
+    /// Whether the mining thread is running
+    pub fn is_running(&self) -> bool {
+        self.running.load(Ordering::SeqCst)
+    }
+
+    /// Start mining on a background thread
+    /// `on_block` is called with every newly mined block
+    /// Returns false if the miner was already running
+    pub fn start<F>(&self, chain: Arc<Mutex<Blockchain>>, miner_id: String, on_block: F) -> bool
+    where
+        F: Fn(&Block) + Send + 'static,
+    {
+        if self.running.swap(true, Ordering::SeqCst) {
+            return false;
+        }
+
+        let running = Arc::clone(&self.running);
+        thread::spawn(move || {
+            while running.load(Ordering::SeqCst) {
+                let start_time = Instant::now();
+
+                // Mine in rounds so the chain lock is released between them
+                let (mined, delay) = {
+                    let mut chain = chain.lock().unwrap();
+                    let mined = chain
+                        .mine_block_with_budget(&miner_id, ATTEMPTS_PER_ROUND)
+                        .map(|_| chain.latest_block().clone());
+                    (mined, Duration::from_secs(chain.config.delay_seconds))
+                };
+
+                if let Some(block) = mined {
+                    on_block(&block);
+
+                    // Same pacing as the terminal node
+                    let elapsed = start_time.elapsed();
+                    if elapsed < delay {
+                        thread::sleep(delay - elapsed);
+                    }
+                }
+            }
+        });
+        true
+    }
+
+    /// Ask the mining thread to stop after its current round
+    pub fn stop(&self) {
+        self.running.store(false, Ordering::SeqCst);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use proof_of_work_sim::config::Config;
+    use std::sync::mpsc;
+
+    #[test]
+    fn test_miner_mines_and_stops() {
+        let chain = Arc::new(Mutex::new(Blockchain::new(Config::new(i32::MAX, 0))));
+        let miner = Miner::new();
+        let (tx, rx) = mpsc::channel();
+
+        assert!(miner.start(Arc::clone(&chain), "miner1".to_string(), move |block| {
+            let _ = tx.send(block.index);
+        }));
+        assert!(miner.is_running());
+        assert!(!miner.start(Arc::clone(&chain), "miner2".to_string(), |_| {}));
+
+        let index = rx.recv_timeout(Duration::from_secs(5)).unwrap();
+        assert!(index >= 1);
+
+        miner.stop();
+        assert!(!miner.is_running());
+        assert!(chain.lock().unwrap().len() >= 2);
+    }
+}
@@ -0,0 +1,78 @@
+//! Conversions from simulator types to the explorer messages
+//!
+//! The message shapes live in `sandbox_schema::explorer` so the frontend gets
+//! matching TypeScript definitions. Both sides of each conversion are foreign
+//! types here, so these are plain functions rather than `From` impls.
+
+use proof_of_work_sim::block::Block;
+use proof_of_work_sim::blockchain::Blockchain;
+use proof_of_work_sim::traits::{Hashable, Validatable};
+use proof_of_work_sim::transaction::Transaction;
+use sandbox_schema::explorer::{BlockView, ChainSummary, TransactionView};
+use sandbox_schema::SCHEMA_VERSION;
+
+pub fn transaction_view(tx: &Transaction) -> TransactionView {
+    TransactionView {
+        hash: tx.hash(),
+        sender: tx.sender.clone(),
+        recipient: tx.recipient.clone(),
+        amount: tx.amount,
+        memo: tx.memo.clone(),
+        is_coinbase: tx.is_coinbase(),
+    }
+}
+
+pub fn block_view(block: &Block) -> BlockView {
+    BlockView {
+        schema_version: SCHEMA_VERSION,
+        index: block.index,
+        hash: block.hash(),
+        prev_hash: block.prev_hash.clone(),
+        nonce: format!("{:016x}", block.nonce),
+        is_valid: block.is_valid(),
+        transactions: block.transactions.iter().map(transaction_view).collect(),
+    }
+}
+
+pub fn chain_summary(chain: &Blockchain, mining: bool) -> ChainSummary {
+    ChainSummary {
+        schema_version: SCHEMA_VERSION,
+        length: chain.len(),
+        latest_hash: chain.latest_block().hash(),
+        ceiling: chain.config.ceiling,
+        chain_valid: chain.is_valid(),
+        mempool: chain.mempool.iter().map(transaction_view).collect(),
+        mining,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use proof_of_work_sim::config::Config;
+
+    #[test]
+    fn test_block_view() {
+        let genesis = Block::genesis();
+        let view = block_view(&genesis);
+        assert_eq!(view.schema_version, SCHEMA_VERSION);
+        assert_eq!(view.index, 0);
+        assert_eq!(view.hash, genesis.hash());
+        assert_eq!(view.nonce, "0000000000000000");
+        assert!(view.transactions[0].is_coinbase);
+    }
+
+    #[test]
+    fn test_chain_summary() {
+        let mut chain = Blockchain::new(Config::default());
+        chain.submit_transaction(Transaction::new_transfer(
+            "alice".to_string(),
+            "bob".to_string(),
+            5,
+        ));
+        let summary = chain_summary(&chain, false);
+        assert_eq!(summary.length, 1);
+        assert_eq!(summary.mempool.len(), 1);
+        assert!(summary.chain_valid);
+    }
+}
@@ -0,0 +1,138 @@
+// Blocks shown per page in the block list
+const PAGE_SIZE = 10;
+
+let summary = null;
+// Index of the newest block on the current page (null = follow the chain tip)
+let pageTop = null;
+
+// Helper function to invoke Tauri commands
+async function invoke(cmd, args = {}) {
+    return window.__TAURI__.invoke(cmd, args);
+}
+
+// Initialize the explorer
+async function initExplorer() {
+    // Refresh whenever the backend mines a block
+    await window.__TAURI__.event.listen('block_mined', () => refresh());
+    await refresh();
+}
+
+// Reload the summary and the current page of blocks
+async function refresh() {
+    try {
+        summary = await invoke('get_summary');
+        updateSummary();
+        await loadBlocks();
+    } catch (error) {
+        console.error('Error refreshing explorer:', error);
+    }
+}
+
+// Update the summary bar
+function updateSummary() {
+    document.getElementById('chain-length').textContent = summary.length;
+    document.getElementById('latest-hash').textContent = shortHash(summary.latest_hash);
+    document.getElementById('chain-valid').textContent = summary.chain_valid ? '✅' : '❌';
+    document.getElementById('mempool-size').textContent = summary.mempool.length;
+    document.getElementById('mining-btn').textContent = summary.mining ? 'Stop Mining' : 'Start Mining';
+}
+
+// Load the current page of blocks, newest first
+async function loadBlocks() {
+    const top = pageTop === null ? summary.length - 1 : pageTop;
+    const start = Math.max(0, top - PAGE_SIZE + 1);
+    const blocks = await invoke('get_blocks', { start, count: top - start + 1 });
+
+    const list = document.getElementById('block-list');
+    list.innerHTML = '';
+    blocks.reverse().forEach(block => {
+        const row = document.createElement('tr');
+        row.innerHTML = `
+            <td>${block.index}</td>
+            <td class="hash">${shortHash(block.hash)}</td>
+            <td>${block.transactions.length}</td>
+            <td>${block.is_valid ? '✅' : '❌'}</td>`;
+        row.onclick = () => showBlock(block.index);
+        list.appendChild(row);
+    });
+
+    document.getElementById('newer-btn').disabled = pageTop === null;
+    document.getElementById('older-btn').disabled = start === 0;
+}
+
+// Show one block's details
+async function showBlock(index) {
+    try {
+        const block = await invoke('get_block', { index });
+        const txs = block.transactions.map(tx => `
+            <li>${tx.is_coinbase ? '🪙 coinbase' : tx.sender} → ${tx.recipient}: ${tx.amount}${tx.memo ? ` <code>${escapeHtml(tx.memo)}</code>` : ''}</li>`).join('');
+        document.getElementById('block-details').innerHTML = `
+            <p><strong>Block #${block.index}</strong></p>
+            <p>Hash: <span class="hash">${block.hash}</span></p>
+            <p>Previous: <span class="hash">${block.prev_hash}</span></p>
+            <p>Nonce: <span class="hash">${block.nonce}</span></p>
+            <ul>${txs}</ul>`;
+    } catch (error) {
+        console.error('Error loading block:', error);
+    }
+}
+
+// Start or stop the background miner
+async function toggleMining() {
+    try {
+        summary = await invoke(summary.mining ? 'stop_mining' : 'start_mining');
+        updateSummary();
+    } catch (error) {
+        console.error('Error toggling mining:', error);
+    }
+}
+
+// Submit a transaction to the mempool
+async function submitTransaction(event) {
+    event.preventDefault();
+    const status = document.getElementById('tx-status');
+
+    try {
+        summary = await invoke('submit_transaction', {
+            sender: document.getElementById('tx-sender').value,
+            recipient: document.getElementById('tx-recipient').value,
+            amount: Number(document.getElementById('tx-amount').value),
+        });
+        updateSummary();
+        status.textContent = 'Transaction added to the mempool';
+        status.className = 'tx-status ok';
+        document.getElementById('tx-form').reset();
+    } catch (error) {
+        status.textContent = error;
+        status.className = 'tx-status error';
+    }
+}
+
+// Last 8 characters of a hash, like the terminal simulator
+function shortHash(hash) {
+    return `...${hash.slice(-8)}`;
+}
+
+// Memos are free-form text, so escape them before putting them in the page
+function escapeHtml(text) {
+    const div = document.createElement('div');
+    div.textContent = text;
+    return div.innerHTML;
+}
+
+// Event listeners
+document.getElementById('mining-btn').addEventListener('click', toggleMining);
+document.getElementById('tx-form').addEventListener('submit', submitTransaction);
+document.getElementById('newer-btn').addEventListener('click', () => {
+    const next = pageTop + PAGE_SIZE;
+    pageTop = next >= summary.length - 1 ? null : next;
+    loadBlocks();
+});
+document.getElementById('older-btn').addEventListener('click', () => {
+    const top = pageTop === null ? summary.length - 1 : pageTop;
+    pageTop = Math.max(0, top - PAGE_SIZE);
+    loadBlocks();
+});
+
+// Initialize when page loads
+window.addEventListener('DOMContentLoaded', initExplorer);
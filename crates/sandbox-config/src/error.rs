@@ -0,0 +1,48 @@
+use std::fmt;
+use std::io;
+use std::path::PathBuf;
+
+/// Errors returned while loading configuration
+#[derive(Debug)]
+pub enum ConfigError {
+    /// The config file could not be read
+    Io { path: PathBuf, source: io::Error },
+    /// The config file could not be written
+    Write { path: PathBuf, source: io::Error },
+    /// The config file is not valid TOML
+    Parse { path: PathBuf, message: String },
+    /// A command-line argument could not be understood
+    InvalidArg(String),
+    /// The merged values don't fit the config type (wrong type, unknown key, ...)
+    InvalidValue(String),
+    /// The config type's own validation rejected the values
+    Validation(String),
+}
+
+impl fmt::Display for ConfigError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ConfigError::Io { path, source } => {
+                write!(f, "could not read config file {}: {}", path.display(), source)
+            }
+            ConfigError::Write { path, source } => {
+                write!(f, "could not write config file {}: {}", path.display(), source)
+            }
+            ConfigError::Parse { path, message } => {
+                write!(f, "invalid config file {}: {}", path.display(), message)
+            }
+            ConfigError::InvalidArg(message) => write!(f, "invalid argument: {}", message),
+            ConfigError::InvalidValue(message) => write!(f, "invalid config value: {}", message),
+            ConfigError::Validation(message) => write!(f, "invalid config: {}", message),
+        }
+    }
+}
+
+impl std::error::Error for ConfigError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            ConfigError::Io { source, .. } | ConfigError::Write { source, .. } => Some(source),
+            _ => None,
+        }
+    }
+}
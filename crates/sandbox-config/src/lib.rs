@@ -0,0 +1,39 @@
+//! Layered configuration loading shared by the sandbox apps
+//!
+//! Values are merged from four layers, each one overriding the last:
+//!
+//! 1. The type's `Default` implementation
+//! 2. A TOML file (`<app>.toml` in the working directory, or `--config <path>`)
+//! 3. Environment variables (`<APP>_<KEY>`, e.g. `POW_SIM_CEILING=1000`)
+//! 4. Command-line flags (`--key value` or `--key=value`)
+//!
+//! The merged result is deserialized into the config type and then checked
+//! with its [`Validate`] implementation. [`save_file`] writes a config back
+//! as TOML, for apps that let the user change it, atomically and keeping the
+//! file it replaces to fall back to.
+//!
+//! ```no_run
+//! use sandbox_config::{ConfigLoader, Validate};
+//! use serde::{Deserialize, Serialize};
+//!
+//! #[derive(Default, Serialize, Deserialize)]
+//! #[serde(default)]
+//! struct Config {
+//!     delay_seconds: u64,
+//! }
+//!
+//! impl Validate for Config {}
+//!
+//! let config: Config = ConfigLoader::new("pow-sim")
+//!     .args(std::env::args().skip(1))
+//!     .load()
+//!     .unwrap();
+//! ```
+
+// Module declarations
+pub mod error;
+pub mod loader;
+
+// Re-exports for convenience
+pub use error::ConfigError;
+pub use loader::{save_file, ConfigLoader, Validate};
@@ -0,0 +1,433 @@
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use std::fs;
+use std::path::{Path, PathBuf};
+use sandbox_store::{backup_path, restore_backup, write_atomic_with_backups};
+use toml::{Table, Value};
+use crate::error::ConfigError;
+
+/// Command-line flag that points at the config file
+const CONFIG_FLAG: &str = "--config";
+
+/// Previous versions of a config file save_file keeps (as `<file>.1`)
+pub const BACKUPS: usize = 1;
+
+/// Checks run on a config after all layers are merged
+pub trait Validate {
+    /// Return a description of the problem if the config is unusable
+    fn validate(&self) -> Result<(), String> {
+        Ok(())
+    }
+}
+
+/// Builds a config value from defaults, a TOML file, env vars, and CLI flags
+pub struct ConfigLoader {
+    /// Config file to read (from `--config` or `file()`), must exist
+    file: Option<PathBuf>,
+    /// Config file read only if it exists (`<app>.toml`)
+    default_file: PathBuf,
+    /// Env var prefix including the trailing underscore (e.g. "POW_SIM_")
+    env_prefix: String,
+    /// Env vars to read, None means the process environment
+    env: Option<Vec<(String, String)>>,
+    /// Raw command-line arguments
+    args: Vec<String>,
+}
+
+impl ConfigLoader {
+    /// Create a loader for `app`
+    /// The default file is "<app>.toml" and env vars start with "<APP>_"
+    /// ("pow-sim" reads "pow-sim.toml" and "POW_SIM_*")
+    pub fn new(app: &str) -> Self {
+        ConfigLoader {
+            file: None,
+            default_file: PathBuf::from(format!("{}.toml", app)),
+            env_prefix: format!("{}_", app.to_uppercase().replace('-', "_")),
+            env: None,
+            args: Vec::new(),
+        }
+    }
+
+    /// Read this config file instead of the default one (it must exist)
+    pub fn file(mut self, path: impl AsRef<Path>) -> Self {
+        self.file = Some(path.as_ref().to_path_buf());
+        self
+    }
+
+    /// Use these env vars instead of the process environment
+    pub fn env_vars<I, K, V>(mut self, vars: I) -> Self
+    where
+        I: IntoIterator<Item = (K, V)>,
+        K: Into<String>,
+        V: Into<String>,
+    {
+        self.env = Some(vars.into_iter().map(|(k, v)| (k.into(), v.into())).collect());
+        self
+    }
+
+    /// Command-line arguments (without the program name)
+    /// A `--config <path>` flag selects the config file
+    pub fn args<I, S>(mut self, args: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.args = args.into_iter().map(Into::into).collect();
+        self
+    }
+
+    /// Merge all layers and build the config
+    pub fn load<T>(self) -> Result<T, ConfigError>
+    where
+        T: Default + Serialize + DeserializeOwned + Validate,
+    {
+        let overrides = parse_args(&self.args)?;
+        let mut merged = Table::try_from(T::default())
+            .map_err(|err| ConfigError::InvalidValue(err.to_string()))?;
+
+        // Layer 2: config file (`--config` wins over `file()` and the default file)
+        let cli_file = overrides
+            .iter()
+            .rev()
+            .find(|(key, _)| key == "config")
+            .map(|(_, value)| PathBuf::from(value));
+        let file = cli_file.or(self.file);
+        match &file {
+            Some(path) => merge(&mut merged, read_file_or_backup(path)?),
+            None if self.default_file.exists() => merge(&mut merged, read_file_or_backup(&self.default_file)?),
+            None => {}
+        }
+
+        // Layer 3: environment variables
+        let env = self.env.unwrap_or_else(|| std::env::vars().collect());
+        for (name, value) in env {
+            if let Some(key) = name.strip_prefix(&self.env_prefix) {
+                let key = key.to_lowercase().replace("__", ".");
+                set_path(&mut merged, &key, parse_value(&value));
+            }
+        }
+
+        // Layer 4: command-line flags
+        for (key, value) in overrides.into_iter().filter(|(key, _)| key != "config") {
+            set_path(&mut merged, &key, parse_value(&value));
+        }
+
+        let config: T = Value::Table(merged)
+            .try_into()
+            .map_err(|err: toml::de::Error| ConfigError::InvalidValue(err.message().to_string()))?;
+        config.validate().map_err(ConfigError::Validation)?;
+        Ok(config)
+    }
+}
+
+/// Validate `config` and write it to `path` as TOML, so the next load reads it back
+/// The file is written next to `path`, flushed to disk, and renamed over it, so a crash
+/// never leaves half a file, and the one it replaces is kept as `<file>.1`
+/// (loading falls back to it if the file is damaged)
+pub fn save_file<T>(path: impl AsRef<Path>, config: &T) -> Result<(), ConfigError>
+where
+    T: Serialize + Validate,
+{
+    let path = path.as_ref();
+    config.validate().map_err(ConfigError::Validation)?;
+    let contents = toml::to_string_pretty(config).map_err(|err| ConfigError::InvalidValue(err.to_string()))?;
+    let write_error = |source| ConfigError::Write {
+        path: path.to_path_buf(),
+        source,
+    };
+    write_atomic_with_backups(path, contents.as_bytes(), BACKUPS).map_err(write_error)
+}
+
+/// Read and parse a TOML config file
+fn read_file(path: &Path) -> Result<Table, ConfigError> {
+    let contents = fs::read_to_string(path).map_err(|source| ConfigError::Io {
+        path: path.to_path_buf(),
+        source,
+    })?;
+    contents.parse::<Table>().map_err(|err| ConfigError::Parse {
+        path: path.to_path_buf(),
+        message: err.to_string(),
+    })
+}
+
+/// Read a config file, or the copy save_file kept (`<file>.1`) if the file is damaged
+/// (not TOML), the file's own error is returned when the copy doesn't read either
+/// The copy is put back as the file (the damaged one kept as `<file>.damaged`), so the
+/// next save keeps it as the backup rather than the damaged file
+/// Config files are meant to be edited by hand, so they carry no checksum: a file
+/// that parses is taken as it is
+fn read_file_or_backup(path: &Path) -> Result<Table, ConfigError> {
+    match read_file(path) {
+        Err(err @ ConfigError::Parse { .. }) => {
+            let table = read_file(&backup_path(path, 1)).map_err(|_| err)?;
+            restore_backup(path, 1).map_err(|source| ConfigError::Io {
+                path: path.to_path_buf(),
+                source,
+            })?;
+            Ok(table)
+        }
+        result => result,
+    }
+}
+
+/// Turn `--key value`, `--key=value`, and bare `--flag` (= true) into pairs
+/// Dashes in keys become underscores (`--delay-seconds` -> `delay_seconds`)
+fn parse_args(args: &[String]) -> Result<Vec<(String, String)>, ConfigError> {
+    let mut pairs = Vec::new();
+    let mut iter = args.iter().peekable();
+
+    while let Some(arg) = iter.next() {
+        let flag = arg
+            .strip_prefix("--")
+            .filter(|flag| !flag.is_empty())
+            .ok_or_else(|| ConfigError::InvalidArg(format!("unexpected argument {:?}", arg)))?;
+
+        let (key, value) = match flag.split_once('=') {
+            Some((key, value)) => (key.to_string(), value.to_string()),
+            None => match iter.peek() {
+                Some(next) if !next.starts_with("--") => (flag.to_string(), iter.next().unwrap().clone()),
+                _ if arg == CONFIG_FLAG => {
+                    return Err(ConfigError::InvalidArg(format!("{} needs a path", CONFIG_FLAG)))
+                }
+                _ => (flag.to_string(), "true".to_string()),
+            },
+        };
+        pairs.push((key.replace('-', "_"), value));
+    }
+
+    Ok(pairs)
+}
+
+/// Interpret a raw env/CLI string as a TOML value (number, bool, array, ...),
+/// falling back to a plain string
+fn parse_value(raw: &str) -> Value {
+    format!("value = {}", raw)
+        .parse::<Table>()
+        .ok()
+        .and_then(|mut table| table.remove("value"))
+        .unwrap_or_else(|| Value::String(raw.to_string()))
+}
+
+/// Set a dotted key path ("mining.ceiling"), creating tables as needed
+fn set_path(table: &mut Table, path: &str, value: Value) {
+    match path.split_once('.') {
+        Some((head, rest)) => {
+            let entry = table
+                .entry(head.to_string())
+                .or_insert_with(|| Value::Table(Table::new()));
+            if !entry.is_table() {
+                *entry = Value::Table(Table::new());
+            }
+            if let Value::Table(inner) = entry {
+                set_path(inner, rest, value);
+            }
+        }
+        None => {
+            table.insert(path.to_string(), value);
+        }
+    }
+}
+
+/// Deep-merge `overlay` into `base`, overlay values win
+fn merge(base: &mut Table, overlay: Table) {
+    for (key, value) in overlay {
+        match (base.get_mut(&key), value) {
+            (Some(Value::Table(base_inner)), Value::Table(overlay_inner)) => merge(base_inner, overlay_inner),
+            (_, value) => {
+                base.insert(key, value);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde::Deserialize;
+
+    #[derive(Debug, PartialEq, Serialize, Deserialize)]
+    #[serde(default, deny_unknown_fields)]
+    struct TestConfig {
+        ceiling: i32,
+        name: String,
+        verbose: bool,
+        mining: Mining,
+    }
+
+    #[derive(Debug, PartialEq, Serialize, Deserialize)]
+    #[serde(default, deny_unknown_fields)]
+    struct Mining {
+        threads: u32,
+    }
+
+    impl Default for TestConfig {
+        fn default() -> Self {
+            TestConfig {
+                ceiling: 100,
+                name: "node".to_string(),
+                verbose: false,
+                mining: Mining::default(),
+            }
+        }
+    }
+
+    impl Default for Mining {
+        fn default() -> Self {
+            Mining { threads: 1 }
+        }
+    }
+
+    impl Validate for TestConfig {
+        fn validate(&self) -> Result<(), String> {
+            if self.ceiling <= 0 {
+                return Err("ceiling must be positive".to_string());
+            }
+            Ok(())
+        }
+    }
+
+    /// Loader that ignores the process environment and any real config file
+    fn loader() -> ConfigLoader {
+        ConfigLoader::new("test-app-that-has-no-file").env_vars(Vec::<(String, String)>::new())
+    }
+
+    fn write_file(contents: &str) -> (tempfile::TempDir, PathBuf) {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("config.toml");
+        fs::write(&path, contents).unwrap();
+        (dir, path)
+    }
+
+    #[test]
+    fn test_defaults() {
+        let config: TestConfig = loader().load().unwrap();
+        assert_eq!(config, TestConfig::default());
+    }
+
+    #[test]
+    fn test_layers_override_in_order() {
+        let (_dir, path) = write_file("ceiling = 5\nname = \"from-file\"\n[mining]\nthreads = 2\n");
+
+        let config: TestConfig = loader()
+            .file(&path)
+            .env_vars([("TEST_APP_THAT_HAS_NO_FILE_CEILING", "6")])
+            .args(["--name", "from-cli"])
+            .load()
+            .unwrap();
+
+        assert_eq!(config.ceiling, 6); // env beats file
+        assert_eq!(config.name, "from-cli"); // CLI beats file
+        assert_eq!(config.mining.threads, 2); // file beats default
+    }
+
+    #[test]
+    fn test_nested_env_and_cli_keys() {
+        let config: TestConfig = loader()
+            .env_vars([("TEST_APP_THAT_HAS_NO_FILE_MINING__THREADS", "3")])
+            .load()
+            .unwrap();
+        assert_eq!(config.mining.threads, 3);
+
+        let config: TestConfig = loader().args(["--mining.threads=4"]).load().unwrap();
+        assert_eq!(config.mining.threads, 4);
+    }
+
+    #[test]
+    fn test_bare_flag_is_true() {
+        let config: TestConfig = loader().args(["--verbose"]).load().unwrap();
+        assert!(config.verbose);
+    }
+
+    #[test]
+    fn test_config_flag_selects_file() {
+        let (_dir, path) = write_file("ceiling = 42\n");
+        let config: TestConfig = loader()
+            .args(["--config".to_string(), path.display().to_string()])
+            .load()
+            .unwrap();
+        assert_eq!(config.ceiling, 42);
+    }
+
+    #[test]
+    fn test_save_file_round_trip() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("nested").join("config.toml");
+        let config = TestConfig {
+            ceiling: 7,
+            mining: Mining { threads: 8 },
+            ..TestConfig::default()
+        };
+        save_file(&path, &config).unwrap();
+        let loaded: TestConfig = loader().file(&path).load().unwrap();
+        assert_eq!(loaded, config);
+
+        let invalid = TestConfig {
+            ceiling: 0,
+            ..TestConfig::default()
+        };
+        assert!(matches!(save_file(&path, &invalid), Err(ConfigError::Validation(_))));
+    }
+
+    #[test]
+    fn test_damaged_file_falls_back_to_the_last_saved() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("config.toml");
+        save_file(&path, &TestConfig { ceiling: 1, ..TestConfig::default() }).unwrap();
+        save_file(&path, &TestConfig { ceiling: 2, ..TestConfig::default() }).unwrap();
+        assert!(fs::read_to_string(backup_path(&path, 1)).unwrap().starts_with("ceiling = 1\n"));
+
+        // A crash can't leave half a file any more, but a disk can damage one
+        fs::write(&path, "ceiling = 2\nname = \"no").unwrap();
+        let loaded: TestConfig = loader().file(&path).load().unwrap();
+        assert_eq!(loaded.ceiling, 1);
+        assert!(fs::read_to_string(&path).unwrap().starts_with("ceiling = 1\n"));
+
+        // Saved again and damaged again, the copy put back is still there
+        save_file(&path, &TestConfig { ceiling: 3, ..TestConfig::default() }).unwrap();
+        fs::write(&path, "ceiling = 3\nname = \"no").unwrap();
+        let loaded: TestConfig = loader().file(&path).load().unwrap();
+        assert_eq!(loaded.ceiling, 1);
+
+        fs::write(&path, "ceiling = 3\nname = \"no").unwrap();
+        fs::write(backup_path(&path, 1), "ceil").unwrap();
+        let result: Result<TestConfig, _> = loader().file(&path).load();
+        assert!(matches!(result, Err(ConfigError::Parse { path: failed, .. }) if failed == path));
+    }
+
+    #[test]
+    fn test_missing_explicit_file() {
+        let result: Result<TestConfig, _> = loader().file("/does/not/exist.toml").load();
+        assert!(matches!(result, Err(ConfigError::Io { .. })));
+    }
+
+    #[test]
+    fn test_invalid_toml() {
+        let (_dir, path) = write_file("ceiling = = 1");
+        let result: Result<TestConfig, _> = loader().file(&path).load();
+        assert!(matches!(result, Err(ConfigError::Parse { .. })));
+    }
+
+    #[test]
+    fn test_wrong_type_and_unknown_key() {
+        let result: Result<TestConfig, _> = loader().args(["--ceiling", "lots"]).load();
+        assert!(matches!(result, Err(ConfigError::InvalidValue(_))));
+
+        let result: Result<TestConfig, _> = loader().args(["--bogus", "1"]).load();
+        assert!(matches!(result, Err(ConfigError::InvalidValue(_))));
+    }
+
+    #[test]
+    fn test_validation() {
+        let result: Result<TestConfig, _> = loader().args(["--ceiling", "0"]).load();
+        assert!(matches!(result, Err(ConfigError::Validation(_))));
+    }
+
+    #[test]
+    fn test_invalid_args() {
+        let result: Result<TestConfig, _> = loader().args(["positional"]).load();
+        assert!(matches!(result, Err(ConfigError::InvalidArg(_))));
+
+        let result: Result<TestConfig, _> = loader().args(["--config"]).load();
+        assert!(matches!(result, Err(ConfigError::InvalidArg(_))));
+    }
+}
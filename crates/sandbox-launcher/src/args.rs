@@ -0,0 +1,103 @@
+//! Command-line arguments
+//!
+//! Hand-rolled like the other sandbox CLIs: everything after the project name
+//! is passed to the project untouched, so its own flags never clash with ours.
+
+pub const USAGE: &str = "\
+Usage: sandbox <COMMAND>
+
+Commands:
+  list                           List the projects that can be run
+  run [--release] <PROJECT> [ARGS]...
+                                 Build and run a project, passing ARGS to it
+                                 (e.g. sandbox run pow-sim --seed 42)
+  help                           Print this help and exit
+
+Set SANDBOX_ROOT to run the projects of another checkout.";
+
+/// What the launcher was asked to do
+#[derive(Debug, PartialEq, Eq)]
+pub enum Args {
+    List,
+    Run {
+        release: bool,
+        project: String,
+        args: Vec<String>,
+    },
+    Help,
+}
+
+impl Args {
+    /// Parse arguments (without the program name)
+    pub fn parse<I>(args: I) -> Result<Self, String>
+    where
+        I: IntoIterator<Item = String>,
+    {
+        let mut args = args.into_iter();
+        let command = match args.next() {
+            Some(command) => command,
+            None => return Ok(Args::Help),
+        };
+
+        match command.as_str() {
+            "list" => match args.next() {
+                Some(extra) => Err(format!("unexpected argument to list: {}", extra)),
+                None => Ok(Args::List),
+            },
+            "run" => {
+                let mut release = false;
+                let project = loop {
+                    match args.next() {
+                        Some(arg) if arg == "--release" => release = true,
+                        Some(arg) if arg.starts_with('-') => return Err(format!("unknown run option: {}", arg)),
+                        Some(project) => break project,
+                        None => return Err("run needs a project name (see `sandbox list`)".to_string()),
+                    }
+                };
+                Ok(Args::Run {
+                    release,
+                    project,
+                    args: args.collect(),
+                })
+            }
+            "help" | "-h" | "--help" => Ok(Args::Help),
+            other => Err(format!("unknown command: {}", other)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(args: &[&str]) -> Result<Args, String> {
+        Args::parse(args.iter().map(|a| a.to_string()))
+    }
+
+    #[test]
+    fn test_commands() {
+        assert_eq!(parse(&[]).unwrap(), Args::Help);
+        assert_eq!(parse(&["--help"]).unwrap(), Args::Help);
+        assert_eq!(parse(&["list"]).unwrap(), Args::List);
+    }
+
+    #[test]
+    fn test_run_passes_args_through() {
+        assert_eq!(
+            parse(&["run", "--release", "hangman-cli", "--seed", "42", "--help"]).unwrap(),
+            Args::Run {
+                release: true,
+                project: "hangman-cli".to_string(),
+                args: vec!["--seed".to_string(), "42".to_string(), "--help".to_string()],
+            }
+        );
+    }
+
+    #[test]
+    fn test_errors() {
+        assert!(parse(&["run"]).is_err());
+        assert!(parse(&["run", "--fast", "pow-sim"]).is_err());
+        assert!(parse(&["list", "pow-sim"]).is_err());
+        assert!(parse(&["launch"]).is_err());
+    }
+}
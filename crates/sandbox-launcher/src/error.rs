@@ -0,0 +1,46 @@
+use std::fmt;
+use std::io;
+
+/// Errors returned by the launcher
+#[derive(Debug)]
+pub enum LaunchError {
+    /// cargo couldn't be started
+    Cargo(io::Error),
+    /// `cargo metadata` failed or printed something unexpected
+    Metadata(String),
+    /// No project has this name (see `sandbox list`)
+    UnknownProject(String),
+}
+
+impl fmt::Display for LaunchError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            LaunchError::Cargo(err) => write!(f, "failed to run cargo: {}", err),
+            LaunchError::Metadata(message) => write!(f, "failed to read the workspace: {}", message),
+            LaunchError::UnknownProject(name) => {
+                write!(f, "unknown project: {} (see `sandbox list`)", name)
+            }
+        }
+    }
+}
+
+impl std::error::Error for LaunchError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            LaunchError::Cargo(err) => Some(err),
+            _ => None,
+        }
+    }
+}
+
+impl From<io::Error> for LaunchError {
+    fn from(err: io::Error) -> Self {
+        LaunchError::Cargo(err)
+    }
+}
+
+impl From<serde_json::Error> for LaunchError {
+    fn from(err: serde_json::Error) -> Self {
+        LaunchError::Metadata(err.to_string())
+    }
+}
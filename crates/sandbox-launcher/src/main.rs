@@ -0,0 +1,66 @@
+//! `sandbox`: one entry point for the workspace's experiments
+//!
+//! ```text
+//! sandbox list
+//! sandbox run pow-sim --seed 42
+//! sandbox run hangman-cli --difficulty hard
+//! ```
+//!
+//! Projects are found with `cargo metadata` (see [`projects`]) and started
+//! with `cargo run`, so they're rebuilt first when their code has changed.
+
+// Module declarations
+mod args;
+mod error;
+mod projects;
+
+use args::{Args, USAGE};
+use error::LaunchError;
+use std::process;
+
+fn main() {
+    let args = match Args::parse(std::env::args().skip(1)) {
+        Ok(args) => args,
+        Err(err) => {
+            eprintln!("error: {}\n\n{}", err, USAGE);
+            process::exit(2);
+        }
+    };
+
+    match run(args) {
+        Ok(code) => process::exit(code),
+        Err(err) => {
+            eprintln!("error: {}", err);
+            process::exit(1);
+        }
+    }
+}
+
+/// Carry out a command, returns the exit code
+fn run(args: Args) -> Result<i32, LaunchError> {
+    let root = projects::workspace_root();
+
+    match args {
+        Args::Help => println!("{}", USAGE),
+        Args::List => {
+            let projects = projects::discover(&root)?;
+            let width = projects.iter().map(|p| p.name.len()).max().unwrap_or(0);
+            for project in &projects {
+                let description = project.description.as_deref().unwrap_or("");
+                println!("{:width$}  {}", project.name, description, width = width);
+            }
+        }
+        Args::Run {
+            release,
+            project,
+            args,
+        } => {
+            let projects = projects::discover(&root)?;
+            let project = projects::find(&projects, &project)?;
+            let status = project.command(&root, release, &args).status()?;
+            // A project killed by a signal has no exit code
+            return Ok(status.code().unwrap_or(1));
+        }
+    }
+    Ok(0)
+}
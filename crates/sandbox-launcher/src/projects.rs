@@ -0,0 +1,217 @@
+//! Finding the workspace's runnable projects
+//!
+//! Every binary target of a workspace member is a project, found with
+//! `cargo metadata` so new experiments show up without touching the launcher.
+//! A package can give its main binary (the one named after the package) a
+//! shorter name in its Cargo.toml:
+//!
+//! ```toml
+//! [package.metadata.sandbox]
+//! name = "pow-sim"
+//! ```
+
+use serde::Deserialize;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use crate::error::LaunchError;
+
+/// One binary that can be started with `sandbox run <name>`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Project {
+    /// Name used on the command line (the short name, or the binary's name)
+    pub name: String,
+    pub package: String,
+    pub bin: String,
+    /// The package's `description`, if it has one
+    pub description: Option<String>,
+}
+
+impl Project {
+    /// The `cargo run` command that builds and starts the project with `args`
+    pub fn command(&self, root: &Path, release: bool, args: &[String]) -> Command {
+        let mut command = cargo();
+        command
+            .arg("run")
+            .arg("--quiet")
+            .arg("--manifest-path")
+            .arg(root.join("Cargo.toml"))
+            .args(["-p", &self.package, "--bin", &self.bin]);
+        if release {
+            command.arg("--release");
+        }
+        command.arg("--").args(args);
+        command
+    }
+}
+
+// The parts of `cargo metadata --no-deps` output the launcher uses
+#[derive(Deserialize)]
+struct Metadata {
+    packages: Vec<Package>,
+}
+
+#[derive(Deserialize)]
+struct Package {
+    name: String,
+    description: Option<String>,
+    targets: Vec<Target>,
+    #[serde(default)]
+    metadata: Option<PackageMetadata>,
+}
+
+#[derive(Deserialize)]
+struct Target {
+    name: String,
+    kind: Vec<String>,
+}
+
+#[derive(Deserialize)]
+struct PackageMetadata {
+    sandbox: Option<SandboxMetadata>,
+}
+
+#[derive(Deserialize)]
+struct SandboxMetadata {
+    name: Option<String>,
+}
+
+/// The cargo that built the launcher when run through `cargo run`, otherwise the one on PATH
+fn cargo() -> Command {
+    Command::new(std::env::var_os("CARGO").unwrap_or_else(|| "cargo".into()))
+}
+
+/// The workspace the launcher was built in, unless SANDBOX_ROOT points elsewhere
+pub fn workspace_root() -> PathBuf {
+    match std::env::var_os("SANDBOX_ROOT") {
+        Some(root) => PathBuf::from(root),
+        None => Path::new(env!("CARGO_MANIFEST_DIR")).join("../.."),
+    }
+}
+
+/// Every project in the workspace at `root`, sorted by name
+pub fn discover(root: &Path) -> Result<Vec<Project>, LaunchError> {
+    let output = cargo()
+        .args(["metadata", "--no-deps", "--format-version", "1", "--manifest-path"])
+        .arg(root.join("Cargo.toml"))
+        .output()?;
+    if !output.status.success() {
+        return Err(LaunchError::Metadata(String::from_utf8_lossy(&output.stderr).trim().to_string()));
+    }
+    parse_metadata(&output.stdout)
+}
+
+/// The projects described by `cargo metadata` JSON (the launcher itself is left out)
+pub fn parse_metadata(json: &[u8]) -> Result<Vec<Project>, LaunchError> {
+    let metadata: Metadata = serde_json::from_slice(json)?;
+    let mut projects = Vec::new();
+
+    for package in metadata.packages {
+        if package.name == env!("CARGO_PKG_NAME") {
+            continue;
+        }
+        let short_name = package.metadata.and_then(|m| m.sandbox).and_then(|s| s.name);
+        for target in package.targets.iter().filter(|t| t.kind.iter().any(|k| k == "bin")) {
+            let name = match &short_name {
+                Some(short) if target.name == package.name => short.clone(),
+                _ => target.name.clone(),
+            };
+            projects.push(Project {
+                name,
+                package: package.name.clone(),
+                bin: target.name.clone(),
+                description: package.description.clone(),
+            });
+        }
+    }
+
+    projects.sort_by(|a, b| a.name.cmp(&b.name));
+    Ok(projects)
+}
+
+/// The project called `name` (its short name or its binary's name)
+pub fn find<'a>(projects: &'a [Project], name: &str) -> Result<&'a Project, LaunchError> {
+    projects
+        .iter()
+        .find(|p| p.name == name)
+        .or_else(|| projects.iter().find(|p| p.bin == name))
+        .ok_or_else(|| LaunchError::UnknownProject(name.to_string()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const METADATA: &str = r#"{
+        "packages": [
+            {
+                "name": "proof-of-work-sim",
+                "description": "Proof-of-work blockchain simulator",
+                "targets": [
+                    { "name": "proof_of_work_sim", "kind": ["rlib", "cdylib"] },
+                    { "name": "proof-of-work-sim", "kind": ["bin"] },
+                    { "name": "mining", "kind": ["bench"] }
+                ],
+                "metadata": { "sandbox": { "name": "pow-sim" } }
+            },
+            {
+                "name": "hangman-cli",
+                "description": null,
+                "targets": [{ "name": "hangman-cli", "kind": ["bin"] }],
+                "metadata": null
+            },
+            {
+                "name": "sandbox-store",
+                "description": null,
+                "targets": [{ "name": "sandbox_store", "kind": ["lib"] }],
+                "metadata": null
+            },
+            {
+                "name": "sandbox-launcher",
+                "description": null,
+                "targets": [{ "name": "sandbox", "kind": ["bin"] }],
+                "metadata": null
+            }
+        ]
+    }"#;
+
+    #[test]
+    fn test_parse_metadata() {
+        let projects = parse_metadata(METADATA.as_bytes()).unwrap();
+        let names: Vec<&str> = projects.iter().map(|p| p.name.as_str()).collect();
+        assert_eq!(names, ["hangman-cli", "pow-sim"]);
+        assert_eq!(projects[1].bin, "proof-of-work-sim");
+        assert_eq!(projects[1].description.as_deref(), Some("Proof-of-work blockchain simulator"));
+    }
+
+    #[test]
+    fn test_find() {
+        let projects = parse_metadata(METADATA.as_bytes()).unwrap();
+        assert_eq!(find(&projects, "pow-sim").unwrap().package, "proof-of-work-sim");
+        assert_eq!(find(&projects, "proof-of-work-sim").unwrap().name, "pow-sim");
+        assert!(matches!(find(&projects, "tetris"), Err(LaunchError::UnknownProject(_))));
+    }
+
+    #[test]
+    fn test_command() {
+        let projects = parse_metadata(METADATA.as_bytes()).unwrap();
+        let command = find(&projects, "pow-sim")
+            .unwrap()
+            .command(Path::new("/ws"), true, &["--seed".to_string(), "42".to_string()]);
+        let args: Vec<_> = command.get_args().map(|a| a.to_string_lossy().into_owned()).collect();
+        assert_eq!(
+            args,
+            [
+                "run", "--quiet", "--manifest-path", "/ws/Cargo.toml", "-p", "proof-of-work-sim", "--bin",
+                "proof-of-work-sim", "--release", "--", "--seed", "42"
+            ]
+        );
+    }
+
+    #[test]
+    fn test_discovers_this_workspace() {
+        let projects = discover(&workspace_root()).unwrap();
+        assert!(find(&projects, "pow-sim").is_ok());
+        assert!(find(&projects, "hangman-cli").is_ok());
+        assert!(find(&projects, "sandbox").is_err());
+    }
+}
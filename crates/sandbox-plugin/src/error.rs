@@ -0,0 +1,23 @@
+use std::fmt;
+
+/// Errors returned while registering a plugin
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PluginError {
+    /// A plugin with the same name is already registered
+    Duplicate(String),
+    /// The plugin's `init` failed, so it wasn't registered
+    Init { plugin: String, message: String },
+}
+
+impl fmt::Display for PluginError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            PluginError::Duplicate(name) => write!(f, "a plugin named {:?} is already registered", name),
+            PluginError::Init { plugin, message } => {
+                write!(f, "plugin {:?} failed to start: {}", plugin, message)
+            }
+        }
+    }
+}
+
+impl std::error::Error for PluginError {}
@@ -0,0 +1,180 @@
+//! Where plugins are registered and events are sent from
+
+use std::fmt;
+use crate::error::PluginError;
+use crate::plugin::Plugin;
+
+/// The plugins listening to one app's events, in the order they were registered
+///
+/// Dropping the host shuts every plugin down.
+pub struct PluginHost<E> {
+    plugins: Vec<Box<dyn Plugin<E>>>,
+}
+
+impl<E> PluginHost<E> {
+    pub fn new() -> Self {
+        PluginHost { plugins: Vec::new() }
+    }
+
+    /// Start `plugin` (its `init`) and send it every event from now on
+    pub fn register(&mut self, plugin: impl Plugin<E> + 'static) -> Result<(), PluginError> {
+        self.register_boxed(Box::new(plugin))
+    }
+
+    /// Like register, for plugins picked at runtime (e.g. from a config file)
+    pub fn register_boxed(&mut self, mut plugin: Box<dyn Plugin<E>>) -> Result<(), PluginError> {
+        let name = plugin.name().to_string();
+        if self.plugins.iter().any(|p| p.name() == name) {
+            return Err(PluginError::Duplicate(name));
+        }
+        plugin
+            .init()
+            .map_err(|message| PluginError::Init { plugin: name.clone(), message })?;
+        tracing::info!(plugin = %name, "plugin registered");
+        self.plugins.push(plugin);
+        Ok(())
+    }
+
+    /// Shut down and remove the plugin called `name`
+    /// Returns whether there was one
+    pub fn unregister(&mut self, name: &str) -> bool {
+        match self.plugins.iter().position(|p| p.name() == name) {
+            Some(index) => {
+                self.plugins.remove(index).shutdown();
+                tracing::info!(plugin = %name, "plugin unregistered");
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Send `event` to every plugin
+    pub fn emit(&mut self, event: &E) {
+        for plugin in &mut self.plugins {
+            plugin.on_event(event);
+        }
+    }
+
+    /// Names of the registered plugins
+    pub fn names(&self) -> Vec<&str> {
+        self.plugins.iter().map(|p| p.name()).collect()
+    }
+
+    pub fn len(&self) -> usize {
+        self.plugins.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.plugins.is_empty()
+    }
+
+    /// Shut down and remove every plugin, last registered first
+    pub fn shutdown(&mut self) {
+        while let Some(mut plugin) = self.plugins.pop() {
+            plugin.shutdown();
+        }
+    }
+}
+
+impl<E> Default for PluginHost<E> {
+    fn default() -> Self {
+        PluginHost::new()
+    }
+}
+
+impl<E> fmt::Debug for PluginHost<E> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("PluginHost").field("plugins", &self.names()).finish()
+    }
+}
+
+impl<E> Drop for PluginHost<E> {
+    fn drop(&mut self) {
+        self.shutdown();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::plugin::TracingPlugin;
+    use std::sync::{Arc, Mutex};
+
+    /// Writes down everything that happens to it
+    struct Recorder {
+        name: &'static str,
+        log: Arc<Mutex<Vec<String>>>,
+        fail_init: bool,
+    }
+
+    impl Recorder {
+        fn new(name: &'static str, log: &Arc<Mutex<Vec<String>>>) -> Self {
+            Recorder { name, log: log.clone(), fail_init: false }
+        }
+    }
+
+    impl Plugin<u32> for Recorder {
+        fn name(&self) -> &str {
+            self.name
+        }
+
+        fn init(&mut self) -> Result<(), String> {
+            if self.fail_init {
+                return Err("no database".to_string());
+            }
+            self.log.lock().unwrap().push(format!("{} init", self.name));
+            Ok(())
+        }
+
+        fn on_event(&mut self, event: &u32) {
+            self.log.lock().unwrap().push(format!("{} {}", self.name, event));
+        }
+
+        fn shutdown(&mut self) {
+            self.log.lock().unwrap().push(format!("{} shutdown", self.name));
+        }
+    }
+
+    #[test]
+    fn test_events_reach_plugins_in_order() {
+        let log = Arc::new(Mutex::new(Vec::new()));
+        let mut host = PluginHost::new();
+        host.register(Recorder::new("a", &log)).unwrap();
+        host.register(Recorder::new("b", &log)).unwrap();
+        host.emit(&1);
+        assert!(host.unregister("a"));
+        assert!(!host.unregister("a"));
+        host.emit(&2);
+        drop(host);
+
+        assert_eq!(
+            *log.lock().unwrap(),
+            ["a init", "b init", "a 1", "b 1", "a shutdown", "b 2", "b shutdown"]
+        );
+    }
+
+    #[test]
+    fn test_register_errors() {
+        let log = Arc::new(Mutex::new(Vec::new()));
+        let mut host = PluginHost::new();
+        host.register(Recorder::new("a", &log)).unwrap();
+        assert_eq!(
+            host.register(Recorder::new("a", &log)),
+            Err(PluginError::Duplicate("a".to_string()))
+        );
+
+        let broken = Recorder { fail_init: true, ..Recorder::new("broken", &log) };
+        assert!(matches!(host.register(broken), Err(PluginError::Init { .. })));
+        assert_eq!(host.names(), ["a"]);
+    }
+
+    #[test]
+    fn test_boxed_and_tracing_plugins() {
+        let mut host: PluginHost<u32> = PluginHost::default();
+        host.register_boxed(Box::new(TracingPlugin::new("log"))).unwrap();
+        host.emit(&7);
+        assert_eq!(host.len(), 1);
+        host.shutdown();
+        assert!(host.is_empty());
+    }
+}
@@ -0,0 +1,39 @@
+//! Plugins for the sandbox apps' events
+//!
+//! An app emits its events (pow-sim's mined blocks, the hangman backend's
+//! guesses and finished games) through a [`PluginHost`], which passes each
+//! one to every registered [`Plugin`]. Extensions like metrics sinks, extra
+//! storage, or game add-ons live in their own crates: they implement
+//! `Plugin<TheAppsEvent>` and the app registers them at startup (or later,
+//! plugins can come and go while the app runs).
+//!
+//! ```
+//! use sandbox_plugin::{Plugin, PluginHost};
+//!
+//! struct Counter(u32);
+//!
+//! impl Plugin<String> for Counter {
+//!     fn name(&self) -> &str {
+//!         "counter"
+//!     }
+//!
+//!     fn on_event(&mut self, _event: &String) {
+//!         self.0 += 1;
+//!     }
+//! }
+//!
+//! let mut plugins = PluginHost::new();
+//! plugins.register(Counter(0)).unwrap();
+//! plugins.emit(&"block mined".to_string());
+//! assert_eq!(plugins.names(), ["counter"]);
+//! ```
+
+// Module declarations
+pub mod error;
+pub mod host;
+pub mod plugin;
+
+// Re-exports for convenience
+pub use error::PluginError;
+pub use host::PluginHost;
+pub use plugin::{Plugin, TracingPlugin};
@@ -0,0 +1,48 @@
+//! The trait extensions implement
+
+use std::fmt::Debug;
+
+/// An extension that is told about an app's events of type `E`
+///
+/// Events are handed out by reference on the thread that emitted them
+/// (pow-sim's mining loop, a hangman command), so `on_event` should be quick
+/// and do slow work (network, disk) elsewhere. Plugins are `Send` so the apps
+/// can keep them in shared state.
+pub trait Plugin<E>: Send {
+    /// Unique name, used in logs and to unregister the plugin
+    fn name(&self) -> &str;
+
+    /// Called once when the plugin is registered, an error keeps it out
+    fn init(&mut self) -> Result<(), String> {
+        Ok(())
+    }
+
+    /// Called for every event emitted while the plugin is registered
+    fn on_event(&mut self, event: &E);
+
+    /// Called once when the plugin is unregistered or the host is dropped
+    fn shutdown(&mut self) {}
+}
+
+/// Logs every event at `debug` level (see `RUST_LOG` in sandbox-telemetry)
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TracingPlugin {
+    name: String,
+}
+
+impl TracingPlugin {
+    /// A plugin logging under `name` (e.g. "pow-sim-events")
+    pub fn new(name: impl Into<String>) -> Self {
+        TracingPlugin { name: name.into() }
+    }
+}
+
+impl<E: Debug> Plugin<E> for TracingPlugin {
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn on_event(&mut self, event: &E) {
+        tracing::debug!(plugin = %self.name, ?event, "event");
+    }
+}
@@ -0,0 +1,143 @@
+use rand::rngs::StdRng;
+use rand::{Rng, RngCore, SeedableRng};
+use std::fmt;
+use std::sync::{Arc, Mutex};
+use crate::seed::seed_from_env;
+
+/// A shareable random number generator
+///
+/// Clones share one sequence (drawing from a clone advances the original), so
+/// a handle can be passed around freely and still replay exactly for a seed.
+/// Use [`fork`](RngHandle::fork) for an independent sequence.
+#[derive(Clone)]
+pub struct RngHandle {
+    rng: Arc<Mutex<StdRng>>,
+    // The seed it was created with (None for entropy)
+    seed: Option<u64>,
+}
+
+impl RngHandle {
+    /// Seeded from `SANDBOX_SEED` if it's set, random otherwise
+    pub fn from_env() -> Self {
+        match seed_from_env() {
+            Some(seed) => RngHandle::seeded(seed),
+            None => RngHandle::from_entropy(),
+        }
+    }
+
+    /// The same sequence every time for the same seed
+    pub fn seeded(seed: u64) -> Self {
+        RngHandle::new(StdRng::seed_from_u64(seed), Some(seed))
+    }
+
+    /// Always random, whatever `SANDBOX_SEED` says
+    pub fn from_entropy() -> Self {
+        RngHandle::new(StdRng::from_entropy(), None)
+    }
+
+    /// The seed this handle was created with (None if it's random)
+    pub fn seed(&self) -> Option<u64> {
+        self.seed
+    }
+
+    /// A new handle with its own sequence, seeded from this one
+    /// Forks of a seeded handle are seeded (and deterministic) too
+    pub fn fork(&self) -> Self {
+        let seed = self.lock().gen();
+        match self.seed {
+            Some(_) => RngHandle::seeded(seed),
+            None => RngHandle::new(StdRng::seed_from_u64(seed), None),
+        }
+    }
+
+    fn new(rng: StdRng, seed: Option<u64>) -> Self {
+        RngHandle {
+            rng: Arc::new(Mutex::new(rng)),
+            seed,
+        }
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, StdRng> {
+        // The generator has no invariants a panic could break
+        self.rng.lock().unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+}
+
+impl Default for RngHandle {
+    fn default() -> Self {
+        RngHandle::from_env()
+    }
+}
+
+impl fmt::Debug for RngHandle {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("RngHandle").field("seed", &self.seed).finish()
+    }
+}
+
+impl RngCore for RngHandle {
+    fn next_u32(&mut self) -> u32 {
+        self.lock().next_u32()
+    }
+
+    fn next_u64(&mut self) -> u64 {
+        self.lock().next_u64()
+    }
+
+    fn fill_bytes(&mut self, dest: &mut [u8]) {
+        self.lock().fill_bytes(dest)
+    }
+
+    fn try_fill_bytes(&mut self, dest: &mut [u8]) -> Result<(), rand::Error> {
+        self.lock().try_fill_bytes(dest)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::seq::SliceRandom;
+
+    fn draw(rng: &mut RngHandle) -> Vec<u64> {
+        (0..5).map(|_| rng.gen()).collect()
+    }
+
+    #[test]
+    fn test_seeded_replays() {
+        let mut a = RngHandle::seeded(42);
+        let mut b = RngHandle::seeded(42);
+        assert_eq!(draw(&mut a), draw(&mut b));
+        assert_ne!(draw(&mut RngHandle::seeded(42)), draw(&mut RngHandle::seeded(43)));
+        assert_eq!(a.seed(), Some(42));
+        assert_eq!(RngHandle::from_entropy().seed(), None);
+    }
+
+    #[test]
+    fn test_clones_share_a_sequence() {
+        let mut original = RngHandle::seeded(7);
+        let mut clone = original.clone();
+        let first = clone.gen::<u64>();
+        let second = original.gen::<u64>();
+
+        let mut replay = RngHandle::seeded(7);
+        assert_eq!([first, second], [replay.gen::<u64>(), replay.gen::<u64>()]);
+    }
+
+    #[test]
+    fn test_forks_are_deterministic() {
+        let mut fork_a = RngHandle::seeded(1).fork();
+        let mut fork_b = RngHandle::seeded(1).fork();
+        assert!(fork_a.seed().is_some());
+        assert_eq!(draw(&mut fork_a), draw(&mut fork_b));
+        assert!(RngHandle::from_entropy().fork().seed().is_none());
+    }
+
+    #[test]
+    fn test_works_with_rand_helpers() {
+        let words = ["RUST", "TAURI", "CARGO"];
+        let mut a = RngHandle::seeded(3);
+        let mut b = RngHandle::seeded(3);
+        assert_eq!(words.choose(&mut a), words.choose(&mut b));
+        assert!((0..16).contains(&a.gen_range(0..16)));
+    }
+}
@@ -0,0 +1,34 @@
+//! Seedable randomness shared by the sandbox apps
+//!
+//! Code that needs random numbers takes an [`RngHandle`] instead of calling
+//! `rand::thread_rng()`, so a whole app can be made deterministic by seeding
+//! the handle it starts with:
+//!
+//! - [`RngHandle::from_env`] is seeded from the `SANDBOX_SEED` environment
+//!   variable when it's set, and random otherwise. This is the default, so
+//!   `SANDBOX_SEED=42` makes every app replay the same nonces, ids, and words.
+//! - [`RngHandle::seeded`] always gives the same sequence for the same seed.
+//! - [`RngHandle::from_entropy`] never follows `SANDBOX_SEED`, for values that
+//!   must stay unpredictable or unique (session ids, commitment salts).
+//!
+//! [`daily_seed`] turns a date into a seed, for "same word for everyone today".
+//!
+//! ```
+//! use sandbox_rng::{Rng, RngHandle};
+//!
+//! let mut a = RngHandle::seeded(42);
+//! let mut b = RngHandle::seeded(42);
+//! assert_eq!(a.gen::<u64>(), b.gen::<u64>());
+//! ```
+
+// Module declarations
+pub mod handle;
+pub mod seed;
+
+// Re-exports for convenience
+pub use handle::RngHandle;
+pub use seed::{daily_seed, day_number, seed_from_env, SEED_ENV_VAR};
+
+// The rand traits, so callers don't need their own rand dependency
+pub use rand::seq::SliceRandom;
+pub use rand::{Rng, RngCore};
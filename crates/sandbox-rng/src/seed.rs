@@ -0,0 +1,60 @@
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// Environment variable that seeds every [`RngHandle::from_env`](crate::RngHandle::from_env)
+pub const SEED_ENV_VAR: &str = "SANDBOX_SEED";
+
+const SECONDS_PER_DAY: u64 = 24 * 60 * 60;
+
+/// The seed in `SANDBOX_SEED`, if it's set to a number
+pub fn seed_from_env() -> Option<u64> {
+    std::env::var(SEED_ENV_VAR).ok().as_deref().and_then(parse_seed)
+}
+
+/// The same seed for every moment of a (UTC) day, and a different one the next day
+pub fn daily_seed(time: SystemTime) -> u64 {
+    mix(day_number(time))
+}
+
+/// Days from 1970-01-01 to the (UTC) day of `time`, what daily_seed is made from
+pub fn day_number(time: SystemTime) -> u64 {
+    time.duration_since(UNIX_EPOCH)
+        .map(|elapsed| elapsed.as_secs() / SECONDS_PER_DAY)
+        .unwrap_or(0)
+}
+
+fn parse_seed(value: &str) -> Option<u64> {
+    value.trim().parse().ok()
+}
+
+/// Spread consecutive numbers (days) over the whole u64 range (splitmix64 finalizer)
+fn mix(mut x: u64) -> u64 {
+    x = x.wrapping_add(0x9e37_79b9_7f4a_7c15);
+    x = (x ^ (x >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+    x = (x ^ (x >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+    x ^ (x >> 31)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+
+    #[test]
+    fn test_parse_seed() {
+        assert_eq!(parse_seed("42"), Some(42));
+        assert_eq!(parse_seed(" 7\n"), Some(7));
+        assert_eq!(parse_seed("forty-two"), None);
+        assert_eq!(parse_seed(""), None);
+    }
+
+    #[test]
+    fn test_daily_seed() {
+        let morning = UNIX_EPOCH + Duration::from_secs(20_000 * SECONDS_PER_DAY + 60);
+        let evening = morning + Duration::from_secs(20 * 60 * 60);
+        let next_day = morning + Duration::from_secs(SECONDS_PER_DAY);
+        assert_eq!(daily_seed(morning), daily_seed(evening));
+        assert_ne!(daily_seed(morning), daily_seed(next_day));
+        assert_eq!(day_number(evening), 20_000);
+        assert_eq!(day_number(next_day), 20_001);
+    }
+}
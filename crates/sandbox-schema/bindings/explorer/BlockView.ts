@@ -0,0 +1,11 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { TransactionView } from "./TransactionView";
+
+/**
+ * A block as shown in the explorer (with its hash precomputed)
+ */
+export type BlockView = { schema_version: number, index: number, hash: string, prev_hash: string, 
+/**
+ * Nonce as a 16-digit hex string (u64 doesn't fit in a JS number)
+ */
+nonce: string, is_valid: boolean, transactions: Array<TransactionView>, };
@@ -0,0 +1,7 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { TransactionView } from "./TransactionView";
+
+/**
+ * Overview of the chain for the explorer header
+ */
+export type ChainSummary = { schema_version: number, length: number, latest_hash: string, ceiling: number, chain_valid: boolean, mempool: Array<TransactionView>, mining: boolean, };
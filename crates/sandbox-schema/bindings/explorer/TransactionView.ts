@@ -0,0 +1,10 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * A transaction as shown in the explorer
+ */
+export type TransactionView = { hash: string, sender: string | null, recipient: string, amount: number, 
+/**
+ * Free-form data attached to a transfer (e.g. a hangman result)
+ */
+memo: string | null, is_coinbase: boolean, };
@@ -0,0 +1,6 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * An achievement and whether a player has unlocked it, from `get_achievements`
+ */
+export type AchievementView = { schema_version: number, id: string, title: string, description: string, player: string | null, unlocked: boolean, unlocked_at_ms: number | null, word: string | null, };
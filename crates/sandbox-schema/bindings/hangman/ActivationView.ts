@@ -0,0 +1,8 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { GameView } from "./GameView";
+import type { PowerUp } from "./PowerUp";
+
+/**
+ * A power-up used with `activate_powerup`, and the game after it
+ */
+export type ActivationView = { schema_version: number, power_up: PowerUp, letter: string | null, held: number, game: GameView, };
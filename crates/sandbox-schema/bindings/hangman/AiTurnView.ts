@@ -0,0 +1,7 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { GameView } from "./GameView";
+
+/**
+ * The AI player's move and the game after it
+ */
+export type AiTurnView = { schema_version: number, guess: string, word_guess: boolean, game: GameView, };
@@ -0,0 +1,6 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * A command the frontend invoked, from `get_audit_log`
+ */
+export type AuditEntryView = { at_ms: number, window: string, command: string, args: string, ok: boolean, error: string | null, duration_ms: number, };
@@ -0,0 +1,7 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { AuditEntryView } from "./AuditEntryView";
+
+/**
+ * The command audit log, from `get_audit_log` (and `clear_audit_log`, empty)
+ */
+export type AuditLogView = { schema_version: number, enabled: boolean, file: string, entries: Array<AuditEntryView>, };
@@ -0,0 +1,8 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { GameView } from "./GameView";
+import type { LetterOutcomeView } from "./LetterOutcomeView";
+
+/**
+ * What `guess_letters` did with each letter, and the game after all of them
+ */
+export type BatchGuessView = { schema_version: number, outcomes: Array<LetterOutcomeView>, game: GameView, };
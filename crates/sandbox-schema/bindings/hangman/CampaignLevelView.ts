@@ -0,0 +1,8 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { Difficulty } from "./Difficulty";
+import type { WordLengthsView } from "./WordLengthsView";
+
+/**
+ * One level of the campaign, and how the player did on it
+ */
+export type CampaignLevelView = { level: number, difficulty: Difficulty, lengths: WordLengthsView, min_score: number, unlocked: boolean, won: boolean, attempts: number, best_score: number, };
@@ -0,0 +1,7 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { CampaignLevelView } from "./CampaignLevelView";
+
+/**
+ * How far the selected profile got in the campaign, from `get_campaign_progress`
+ */
+export type CampaignView = { schema_version: number, levels: Array<CampaignLevelView>, score: number, next_level: number, completed: boolean, time_ms: number | null, };
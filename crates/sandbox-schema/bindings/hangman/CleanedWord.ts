@@ -0,0 +1,6 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * A word list entry that had characters stripped to be played
+ */
+export type CleanedWord = { entry: string, word: string, };
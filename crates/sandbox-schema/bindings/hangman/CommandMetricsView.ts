@@ -0,0 +1,6 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * How often a command ran and how long it took (in microseconds), from `get_metrics`
+ */
+export type CommandMetricsView = { command: string, calls: number, errors: number, mean_us: number, p50_us: number, p90_us: number, p99_us: number, max_us: number, total_us: number, };
@@ -0,0 +1,6 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * Where the app stands with online multiplayer
+ */
+export type ConnectionState = "offline" | "hosting" | "connected" | "lost";
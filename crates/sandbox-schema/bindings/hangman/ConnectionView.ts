@@ -0,0 +1,8 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { ConnectionState } from "./ConnectionState";
+
+/**
+ * The multiplayer connection, from `host_game`, `join_game`, `leave_game`, and the
+ * `connection_changed` event
+ */
+export type ConnectionView = { schema_version: number, state: ConnectionState, address: string | null, code: string | null, session_id: string | null, guests: number, reason: string | null, };
@@ -0,0 +1,6 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * A cell of a crossword that holds a letter
+ */
+export type CrosswordCellView = { row: number, col: number, letter: string | null, };
@@ -0,0 +1,6 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * Which way a word of a crossword runs
+ */
+export type CrosswordDirection = "across" | "down";
@@ -0,0 +1,7 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { CrosswordView } from "./CrosswordView";
+
+/**
+ * A newly started crossword game and its session, from `start_crossword_game`
+ */
+export type CrosswordSessionView = { schema_version: number, session_id: string, game: CrosswordView, };
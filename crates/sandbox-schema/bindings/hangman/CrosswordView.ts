@@ -0,0 +1,10 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { CrosswordCellView } from "./CrosswordCellView";
+import type { CrosswordWordView } from "./CrosswordWordView";
+import type { Difficulty } from "./Difficulty";
+
+/**
+ * A crossword game (several crossing words, letters revealed in all of them) as the
+ * frontend sees it
+ */
+export type CrosswordView = { schema_version: number, rows: number, cols: number, cells: Array<CrosswordCellView>, words: Array<CrosswordWordView>, guessed_letters: Array<string>, wrong_guesses: number, max_wrong_guesses: number, difficulty: Difficulty, game_over: boolean, won: boolean, };
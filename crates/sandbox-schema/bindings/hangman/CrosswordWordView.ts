@@ -0,0 +1,7 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { CrosswordDirection } from "./CrosswordDirection";
+
+/**
+ * A word of a crossword and where it is
+ */
+export type CrosswordWordView = { row: number, col: number, direction: CrosswordDirection, length: number, complete: boolean, word: string | null, };
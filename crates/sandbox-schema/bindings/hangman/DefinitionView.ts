@@ -0,0 +1,6 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * What a finished game's word means, from `get_word_definition`
+ */
+export type DefinitionView = { schema_version: number, word: string, part_of_speech: string | null, definition: string, };
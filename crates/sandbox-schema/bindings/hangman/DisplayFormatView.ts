@@ -0,0 +1,6 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * How the word is drawn in GameView's `display_word`
+ */
+export type DisplayFormatView = { placeholder: string, letter_spacing: boolean, word_separator: string, };
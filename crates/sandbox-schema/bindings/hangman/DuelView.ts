@@ -0,0 +1,6 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * The head-to-head score of a two-player duel, in the GameView of each of its rounds
+ */
+export type DuelView = { schema_version: number, round: number, setter: number, guesser: number, player_one_wins: number, player_two_wins: number, can_rematch: boolean, };
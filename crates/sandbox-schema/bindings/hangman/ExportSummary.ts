@@ -0,0 +1,7 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { ExportFormat } from "./ExportFormat";
+
+/**
+ * What `export_history` wrote
+ */
+export type ExportSummary = { schema_version: number, path: string, format: ExportFormat, games: number, guesses: number, };
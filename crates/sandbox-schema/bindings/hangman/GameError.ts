@@ -0,0 +1,8 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { PowerUp } from "./PowerUp";
+
+/**
+ * Why a game command was refused, e.g. `{ "kind": "game_already_over" }`
+ * or `{ "kind": "invalid_input", "message": "..." }`
+ */
+export type GameError = { "kind": "invalid_input", message: string, } | { "kind": "game_already_over" } | { "kind": "no_active_game" } | { "kind": "empty_guess" } | { "kind": "multiple_characters" } | { "kind": "digit", character: string, } | { "kind": "not_a_letter", character: string, } | { "kind": "already_guessed", letter: string, } | { "kind": "hint_unavailable", message: string, } | { "kind": "daily_completed" } | { "kind": "game_not_over" } | { "kind": "nothing_to_undo" } | { "kind": "not_enough_points", price: number, points: number, } | { "kind": "help_unavailable", message: string, } | { "kind": "game_paused" } | { "kind": "not_your_turn" } | { "kind": "level_locked", level: number, } | { "kind": "no_power_up_left", power_up: PowerUp, } | { "kind": "power_up_unavailable", message: string, };
@@ -0,0 +1,6 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * How a game picks its word: up front, or dodging guesses for as long as it can
+ */
+export type GameMode = "classic" | "evil";
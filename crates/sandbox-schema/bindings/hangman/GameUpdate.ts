@@ -0,0 +1,8 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { GameView } from "./GameView";
+
+/**
+ * A session's game after it changed: a new game, a move, or a timed game running out
+ * Sent to every window, each one only shows the update of its own session
+ */
+export type GameUpdate = { schema_version: number, session_id: string, game: GameView, };
@@ -0,0 +1,22 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { Difficulty } from "./Difficulty";
+import type { DuelView } from "./DuelView";
+import type { GameMode } from "./GameMode";
+import type { Help } from "./Help";
+import type { KeyView } from "./KeyView";
+import type { LetterCase } from "./LetterCase";
+import type { MatchView } from "./MatchView";
+import type { MoveView } from "./MoveView";
+import type { PowerUp } from "./PowerUp";
+import type { RaceView } from "./RaceView";
+import type { RevealView } from "./RevealView";
+import type { RunView } from "./RunView";
+import type { ScoreView } from "./ScoreView";
+import type { WordRatingView } from "./WordRatingView";
+
+/**
+ * The current game as the guessing player may see it
+ * While the game is running `word` only contains the revealed letters,
+ * every hidden letter is "_"
+ */
+export type GameView = { schema_version: number, word: string, mask: string, display_word: string, guessed_letters: Array<string>, guessed_words: Array<string>, wrong_guesses: number, max_wrong_guesses: number, stage: number, stages: number, gallows: string, game_over: boolean, won: boolean, category: string | null, clue: string | null, difficulty: Difficulty, mode: GameMode, custom: boolean, practice: boolean, kids_mode: boolean, ranked: boolean, hints_used: number, max_hints: number, letter_case: LetterCase, player: string | null, score: ScoreView | null, rating: WordRatingView | null, time_limit: number | null, remaining_ms: number | null, paused: boolean, paused_ms: number, timed_out: boolean, gave_up: boolean, ignore_accents: boolean, alphabet: Array<string>, keys: Array<KeyView>, daily: number | null, ai_moves: number, can_undo: boolean, history: Array<MoveView>, reveal: RevealView, match_score: MatchView | null, duel: DuelView | null, run: RunView | null, race: RaceView | null, campaign_level: number | null, seed: number | null, helps_bought: Array<Help>, removed_letters: Array<string>, power_ups_used: Array<PowerUp>, shield: boolean, frozen_ms: number, };
@@ -0,0 +1,6 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * What points buy in a game
+ */
+export type Help = "reveal_vowel" | "remove_letter" | "extra_life";
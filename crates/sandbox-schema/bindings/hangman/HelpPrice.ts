@@ -0,0 +1,7 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { Help } from "./Help";
+
+/**
+ * A help and what it costs
+ */
+export type HelpPrice = { help: Help, price: number, };
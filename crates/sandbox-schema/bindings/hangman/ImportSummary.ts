@@ -0,0 +1,7 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { RejectedWord } from "./RejectedWord";
+
+/**
+ * What `import_word_list` did with a file
+ */
+export type ImportSummary = { schema_version: number, accepted: Array<string>, duplicates: Array<string>, rejected: Array<RejectedWord>, pool_size: number, };
@@ -0,0 +1,7 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { PowerUpView } from "./PowerUpView";
+
+/**
+ * The selected profile's power-ups, from `get_power_ups`
+ */
+export type InventoryView = { schema_version: number, player: string | null, power_ups: Array<PowerUpView>, max_held: number, };
@@ -0,0 +1,6 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * Where a key of the keyboard stands: not guessed yet, or guessed and in the word or not
+ */
+export type KeyState = "unused" | "correct" | "wrong";
@@ -0,0 +1,7 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { KeyState } from "./KeyState";
+
+/**
+ * A key of the keyboard
+ */
+export type KeyView = { letter: string, state: KeyState, };
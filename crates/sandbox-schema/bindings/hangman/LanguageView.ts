@@ -0,0 +1,6 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * A word list language (`list_languages`, `set_language`)
+ */
+export type LanguageView = { schema_version: number, code: string, name: string, alphabet: Array<string>, words: number, };
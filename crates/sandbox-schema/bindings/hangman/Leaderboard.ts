@@ -0,0 +1,7 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { LeaderboardEntry } from "./LeaderboardEntry";
+
+/**
+ * Profiles ranked by best score, then wins
+ */
+export type Leaderboard = { schema_version: number, entries: Array<LeaderboardEntry>, };
@@ -0,0 +1,6 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * One row of the leaderboard
+ */
+export type LeaderboardEntry = { rank: number, name: string, best_score: number, total_score: number, wins: number, games_played: number, };
@@ -0,0 +1,6 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * What a letter of a word game guess says about the word
+ */
+export type LetterFeedback = "correct" | "present" | "absent";
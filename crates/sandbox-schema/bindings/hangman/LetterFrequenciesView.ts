@@ -0,0 +1,8 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { LetterFrequencyView } from "./LetterFrequencyView";
+
+/**
+ * The smart hint of `get_letter_frequencies`: how common each unguessed letter is in
+ * the dictionary's words that still fit the board
+ */
+export type LetterFrequenciesView = { schema_version: number, words: number, letters: Array<LetterFrequencyView>, };
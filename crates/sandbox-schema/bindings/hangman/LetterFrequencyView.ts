@@ -0,0 +1,6 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * In how many of the words that fit an unguessed letter is
+ */
+export type LetterFrequencyView = { letter: string, words: number, };
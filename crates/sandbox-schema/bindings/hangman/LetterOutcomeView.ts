@@ -0,0 +1,8 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { GameError } from "./GameError";
+import type { LetterResult } from "./LetterResult";
+
+/**
+ * One letter of `guess_letters`, in the order they were sent
+ */
+export type LetterOutcomeView = { input: string, letter: string | null, result: LetterResult, error: GameError | null, };
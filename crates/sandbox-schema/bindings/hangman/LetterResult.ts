@@ -0,0 +1,6 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * What happened to one letter of `guess_letters`
+ */
+export type LetterResult = "hit" | "miss" | "rejected" | "skipped";
@@ -0,0 +1,6 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * What changes from one round of a match to the next
+ */
+export type MatchRotation = "difficulty" | "category";
@@ -0,0 +1,8 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { MatchRotation } from "./MatchRotation";
+import type { MatchWinner } from "./MatchWinner";
+
+/**
+ * The score of a best-of-N match, in the GameView of each of its rounds
+ */
+export type MatchView = { schema_version: number, round: number, rounds: number, rotation: MatchRotation, wins: number, losses: number, to_win: number, score: number, winner: MatchWinner | null, can_continue: boolean, };
@@ -0,0 +1,7 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { CommandMetricsView } from "./CommandMetricsView";
+
+/**
+ * Every command's metrics since the app started, from `get_metrics`
+ */
+export type MetricsView = { schema_version: number, on_exit: boolean, file: string, commands: Array<CommandMetricsView>, };
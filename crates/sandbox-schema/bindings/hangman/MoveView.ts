@@ -0,0 +1,7 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { MoveKind } from "./MoveKind";
+
+/**
+ * One move of a game, for drawing its timeline
+ */
+export type MoveView = { kind: MoveKind, guess: string, hit: boolean, at_ms: number, };
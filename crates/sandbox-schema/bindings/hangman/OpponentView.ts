@@ -0,0 +1,6 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * The other player's board in a duel, without its letters
+ */
+export type OpponentView = { name: string | null, letters: number, found: number, wrong_guesses: number, max_wrong_guesses: number, game_over: boolean, won: boolean, };
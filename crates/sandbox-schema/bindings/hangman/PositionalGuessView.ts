@@ -0,0 +1,7 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { LetterFeedback } from "./LetterFeedback";
+
+/**
+ * A positional guess (a letter in a place) and its mark
+ */
+export type PositionalGuessView = { letter: string, index: number, feedback: LetterFeedback, };
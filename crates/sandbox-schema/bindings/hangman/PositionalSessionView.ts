@@ -0,0 +1,7 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { PositionalView } from "./PositionalView";
+
+/**
+ * A newly started positional game and its session, from `start_positional_game`
+ */
+export type PositionalSessionView = { schema_version: number, session_id: string, game: PositionalView, };
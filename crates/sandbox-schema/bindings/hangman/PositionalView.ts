@@ -0,0 +1,8 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { Difficulty } from "./Difficulty";
+import type { PositionalGuessView } from "./PositionalGuessView";
+
+/**
+ * A positional game (guess a letter for each place) as the frontend sees it
+ */
+export type PositionalView = { schema_version: number, board: Array<string | null>, guesses: Array<PositionalGuessView>, wrong_guesses: number, max_wrong_guesses: number, difficulty: Difficulty, game_over: boolean, won: boolean, word: string | null, };
@@ -0,0 +1,6 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * What a profile earns by winning and uses in a later game
+ */
+export type PowerUp = "freeze_timer" | "reveal_consonant" | "shield";
@@ -0,0 +1,7 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { PowerUp } from "./PowerUp";
+
+/**
+ * One kind of power-up and how many the profile holds
+ */
+export type PowerUpView = { power_up: PowerUp, name: string, description: string, held: number, };
@@ -0,0 +1,7 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { StatisticsView } from "./StatisticsView";
+
+/**
+ * A player profile with its own statistics
+ */
+export type ProfileView = { schema_version: number, name: string, current: boolean, statistics: StatisticsView, high_scores: Array<number>, total_score: number, points: number, campaign_ms: number | null, };
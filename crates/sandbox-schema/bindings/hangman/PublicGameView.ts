@@ -0,0 +1,9 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * A game as anyone may see it, from `get_public_state`: the revealed letters and the
+ * counters, for spectators and remote players
+ * Unlike [`GameView`] nothing in it is worked out from the word (no score or rating),
+ * and the word itself is only there once the game is over
+ */
+export type PublicGameView = { schema_version: number, mask: string, display_word: string, guessed_letters: Array<string>, guessed_words: Array<string>, wrong_guesses: number, max_wrong_guesses: number, stage: number, stages: number, gallows: string, hints_used: number, max_hints: number, remaining_ms: number | null, paused: boolean, game_over: boolean, won: boolean, word: string | null, };
@@ -0,0 +1,8 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { GameView } from "./GameView";
+import type { Help } from "./Help";
+
+/**
+ * A help bought with `buy_help`, and the game after it
+ */
+export type PurchaseView = { schema_version: number, help: Help, letter: string | null, price: number, points: number, game: GameView, };
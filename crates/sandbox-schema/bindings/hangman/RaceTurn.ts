@@ -0,0 +1,6 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * Whose turn it is in a race against the AI
+ */
+export type RaceTurn = "player" | "ai";
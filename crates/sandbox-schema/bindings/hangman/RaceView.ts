@@ -0,0 +1,9 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { RaceTurn } from "./RaceTurn";
+import type { RaceWinner } from "./RaceWinner";
+
+/**
+ * How the AI is doing on its own board in a race, in the race's GameView
+ * (the player's board is the rest of the GameView)
+ */
+export type RaceView = { schema_version: number, turn: RaceTurn, letters: number, ai_found: number, ai_letters: Array<string>, ai_wrong_guesses: number, ai_max_wrong_guesses: number, ai_out: boolean, winner: RaceWinner | null, };
@@ -0,0 +1,8 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { Difficulty } from "./Difficulty";
+import type { RatingPointView } from "./RatingPointView";
+
+/**
+ * A profile's rating against the AI on one difficulty, from `get_rating_history`
+ */
+export type RatingHistoryView = { schema_version: number, player: string | null, difficulty: Difficulty, rating: number, ai_rating: number, points: Array<RatingPointView>, };
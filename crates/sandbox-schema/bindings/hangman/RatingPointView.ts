@@ -0,0 +1,7 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { RaceWinner } from "./RaceWinner";
+
+/**
+ * One update of a profile's rating against the AI, after a race
+ */
+export type RatingPointView = { result: RaceWinner, rating: number, change: number, at_ms: number, };
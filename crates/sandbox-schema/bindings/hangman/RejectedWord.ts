@@ -0,0 +1,6 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * A word of an imported file that wasn't added
+ */
+export type RejectedWord = { word: string, reason: string, };
@@ -0,0 +1,8 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { GameError } from "./GameError";
+import type { GameView } from "./GameView";
+
+/**
+ * The game joined with `join_game`, from the `remote_game_updated` event
+ */
+export type RemoteGameUpdate = { schema_version: number, game: GameView, error: GameError | null, };
@@ -0,0 +1,7 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { GameView } from "./GameView";
+
+/**
+ * One step of a replay
+ */
+export type ReplayStepView = { at_ms: number, game: GameView, };
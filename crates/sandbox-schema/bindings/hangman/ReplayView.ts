@@ -0,0 +1,7 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { ReplayStepView } from "./ReplayStepView";
+
+/**
+ * A game played again from a replay by `import_replay`, a step per move
+ */
+export type ReplayView = { schema_version: number, steps: Array<ReplayStepView>, duration_ms: number, };
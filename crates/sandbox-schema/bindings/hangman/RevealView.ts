@@ -0,0 +1,7 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { RevealedPlaceView } from "./RevealedPlaceView";
+
+/**
+ * What the last move revealed, so only the new letters need animating
+ */
+export type RevealView = { places: Array<RevealedPlaceView>, revealed: number, letters: number, };
@@ -0,0 +1,6 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * A place of the word the last move revealed, to animate
+ */
+export type RevealedPlaceView = { place: number, letter: string, display_at: number, };
@@ -0,0 +1,6 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * The lives and score of an arcade run, in the GameView of each of its games
+ */
+export type RunView = { schema_version: number, word: number, lives: number, start_lives: number, streak: number, wins_per_life: number, wins: number, score: number, over: boolean, can_continue: boolean, };
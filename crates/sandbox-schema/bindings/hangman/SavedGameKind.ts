@@ -0,0 +1,6 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * A game mode whose unfinished game is saved, for `discard_saved_game`
+ */
+export type SavedGameKind = "hangman" | "wordle";
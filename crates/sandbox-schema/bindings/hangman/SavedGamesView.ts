@@ -0,0 +1,9 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { PublicGameView } from "./PublicGameView";
+import type { WordleView } from "./WordleView";
+
+/**
+ * The games left unfinished when the app last closed (or crashed), from `get_saved_games`
+ * The frontend offers to restore them with `resume_game` / `resume_wordle`
+ */
+export type SavedGamesView = { schema_version: number, game: PublicGameView | null, wordle: WordleView | null, };
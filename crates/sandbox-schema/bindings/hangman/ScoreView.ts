@@ -0,0 +1,7 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * A finished game's score and how it was made up
+ * total = (letter_points + guess_bonus + rating_bonus) * length_multiplier * difficulty_multiplier
+ */
+export type ScoreView = { letter_points: number, guess_bonus: number, rating_bonus: number, length_multiplier: number, difficulty_multiplier: number, total: number, };
@@ -0,0 +1,8 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { GameView } from "./GameView";
+
+/**
+ * A newly started game and the session it's played in
+ * Pass `session_id` to every later command about this game
+ */
+export type SessionView = { schema_version: number, session_id: string, game: GameView, };
@@ -0,0 +1,11 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { Difficulty } from "./Difficulty";
+import type { DisplayFormatView } from "./DisplayFormatView";
+import type { LetterCase } from "./LetterCase";
+import type { WordSource } from "./WordSource";
+
+/**
+ * Game settings, read by `get_settings` and sent to `set_settings` (which saves them)
+ * `null` limits follow the difficulty level
+ */
+export type SettingsView = { schema_version: number, difficulty: Difficulty, category: string | null, language: string | null, hints_enabled: boolean, show_clues: boolean, word_source: WordSource, word_file: string | null, profanity_filter: boolean, blocked_words: Array<string>, max_wrong_guesses: number | null, hints_allowed: number | null, letter_case: LetterCase, time_limit: number | null, ignore_accents: boolean, digraphs: Array<string>, display: DisplayFormatView, seed: number | null, min_word_length: number | null, max_word_length: number | null, telemetry: boolean, telemetry_endpoint: string | null, audit_log: boolean, metrics_on_exit: boolean, kids_mode: boolean, };
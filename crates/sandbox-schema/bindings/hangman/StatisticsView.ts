@@ -0,0 +1,6 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * Win/loss statistics across all finished games
+ */
+export type StatisticsView = { schema_version: number, games_played: number, wins: number, losses: number, current_streak: number, best_streak: number, average_wrong_guesses: number, win_rate: number, };
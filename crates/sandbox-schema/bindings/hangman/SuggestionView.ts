@@ -0,0 +1,6 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * A letter to guess and how much it's expected to tell about the answer
+ */
+export type SuggestionView = { letter: string, score: number, words: number, };
@@ -0,0 +1,7 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { SuggestionView } from "./SuggestionView";
+
+/**
+ * The best letters to guess next, from `suggest_letters`
+ */
+export type SuggestionsView = { schema_version: number, words: number, suggestions: Array<SuggestionView>, };
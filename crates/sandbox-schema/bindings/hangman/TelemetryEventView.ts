@@ -0,0 +1,9 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { Difficulty } from "./Difficulty";
+import type { GameMode } from "./GameMode";
+import type { TelemetryKind } from "./TelemetryKind";
+
+/**
+ * An anonymous telemetry event: no word, player, or time
+ */
+export type TelemetryEventView = { kind: TelemetryKind, word_length: number, guesses: number, difficulty: Difficulty, mode: GameMode, };
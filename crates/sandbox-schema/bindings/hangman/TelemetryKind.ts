@@ -0,0 +1,6 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * What happened to a game, in a telemetry event
+ */
+export type TelemetryKind = "started" | "won" | "lost";
@@ -0,0 +1,7 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { TelemetryEventView } from "./TelemetryEventView";
+
+/**
+ * The gameplay telemetry collected, from `get_telemetry` (and `clear_telemetry`, empty)
+ */
+export type TelemetryView = { schema_version: number, enabled: boolean, destination: string, pending: Array<TelemetryEventView>, saved: Array<TelemetryEventView>, };
@@ -0,0 +1,6 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * How a head-to-head duel ended for the player it's sent to
+ */
+export type VersusOutcome = "won" | "lost" | "draw";
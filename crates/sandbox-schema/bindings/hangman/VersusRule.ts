@@ -0,0 +1,6 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * How the winner of a head-to-head duel is decided, picked by its host
+ */
+export type VersusRule = "first_solve" | "fewest_wrong_guesses";
@@ -0,0 +1,6 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * Where this app stands with head-to-head duels
+ */
+export type VersusState = "offline" | "waiting" | "playing" | "lost";
@@ -0,0 +1,10 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { GameError } from "./GameError";
+import type { VersusState } from "./VersusState";
+import type { VersusView } from "./VersusView";
+
+/**
+ * The duel, from `host_versus`, `join_versus`, `leave_versus`, `get_versus`, and the
+ * `versus_updated` event
+ */
+export type VersusUpdate = { schema_version: number, state: VersusState, address: string | null, code: string | null, versus: VersusView | null, error: GameError | null, reason: string | null, };
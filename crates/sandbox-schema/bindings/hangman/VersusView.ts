@@ -0,0 +1,10 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { GameView } from "./GameView";
+import type { OpponentView } from "./OpponentView";
+import type { VersusOutcome } from "./VersusOutcome";
+import type { VersusRule } from "./VersusRule";
+
+/**
+ * A head-to-head duel from one player's side
+ */
+export type VersusView = { schema_version: number, board: GameView, opponent: OpponentView, rule: VersusRule, outcome: VersusOutcome | null, };
@@ -0,0 +1,7 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { HelpPrice } from "./HelpPrice";
+
+/**
+ * The selected profile's points and what they buy, from `get_wallet`
+ */
+export type WalletView = { schema_version: number, player: string | null, points: number, win_points: number, prices: Array<HelpPrice>, };
@@ -0,0 +1,7 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * Fewest and most letters of a new game's word, for `start_new_game`
+ * A bound left out (or `null`) is the settings' one
+ */
+export type WordLengthsView = { min: number | null, max: number | null, };
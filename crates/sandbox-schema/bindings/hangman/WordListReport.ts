@@ -0,0 +1,8 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { CleanedWord } from "./CleanedWord";
+import type { RejectedWord } from "./RejectedWord";
+
+/**
+ * What cleaning up the word list did when it was loaded (`get_word_list_report`)
+ */
+export type WordListReport = { schema_version: number, words: number, cleaned: Array<CleanedWord>, duplicates: Array<string>, rejected: Array<RejectedWord>, };
@@ -0,0 +1,7 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { WordSource } from "./WordSource";
+
+/**
+ * A word provider's health check
+ */
+export type WordProviderView = { source: WordSource, healthy: boolean, words: number, error: string | null, };
@@ -0,0 +1,8 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { WordProviderView } from "./WordProviderView";
+import type { WordSource } from "./WordSource";
+
+/**
+ * Where new games get their words from, from `get_word_providers`
+ */
+export type WordProvidersView = { schema_version: number, selected: WordSource, serving: WordSource, fallback: string | null, providers: Array<WordProviderView>, };
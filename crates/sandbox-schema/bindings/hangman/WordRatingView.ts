@@ -0,0 +1,7 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { Difficulty } from "./Difficulty";
+
+/**
+ * How hard a word is to guess, from `rate_word` and in a finished game's `rating`
+ */
+export type WordRatingView = { schema_version: number, score: number, level: Difficulty, };
@@ -0,0 +1,6 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * The words of a dictionary that fit a mask, found by `search_words`
+ */
+export type WordSearchView = { schema_version: number, words: Array<string>, total: number, limit: number, };
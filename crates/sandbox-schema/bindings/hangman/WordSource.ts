@@ -0,0 +1,7 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * Where new games get their words from: the word list files, a word API, the
+ * `word_file` list, or the day's words
+ */
+export type WordSource = "bundled" | "online" | "file" | "daily";
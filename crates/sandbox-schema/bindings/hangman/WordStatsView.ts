@@ -0,0 +1,7 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * How one word went over the games it was played in, from `get_word_stats` and
+ * `get_most_missed_words`
+ */
+export type WordStatsView = { schema_version: number, word: string, played: number, wins: number, losses: number, win_rate: number, average_wrong_guesses: number, };
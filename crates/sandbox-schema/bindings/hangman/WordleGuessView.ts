@@ -0,0 +1,7 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { LetterFeedback } from "./LetterFeedback";
+
+/**
+ * A word game guess with a mark per letter, in order
+ */
+export type WordleGuessView = { word: string, feedback: Array<LetterFeedback>, };
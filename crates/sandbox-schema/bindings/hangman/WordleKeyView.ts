@@ -0,0 +1,7 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { LetterFeedback } from "./LetterFeedback";
+
+/**
+ * A key of the word game's keyboard, with the best mark its letter got
+ */
+export type WordleKeyView = { letter: string, feedback: LetterFeedback, };
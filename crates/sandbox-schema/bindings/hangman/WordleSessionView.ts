@@ -0,0 +1,7 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { WordleView } from "./WordleView";
+
+/**
+ * A newly started word game and its session, from `start_wordle`
+ */
+export type WordleSessionView = { schema_version: number, session_id: string, game: WordleView, };
@@ -0,0 +1,8 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { WordleGuessView } from "./WordleGuessView";
+import type { WordleKeyView } from "./WordleKeyView";
+
+/**
+ * A word game (find the word in a few guesses) as the frontend sees it
+ */
+export type WordleView = { schema_version: number, guesses: Array<WordleGuessView>, word_len: number, max_guesses: number, game_over: boolean, won: boolean, answer: string | null, keys: Array<WordleKeyView>, };
@@ -0,0 +1,60 @@
+use serde::{Deserialize, Serialize};
+use ts_rs::TS;
+
+/// Event emitted with a [`BlockView`] whenever the background miner finds a block
+pub const BLOCK_MINED_EVENT: &str = "block_mined";
+
+/// A transaction as shown in the explorer
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, TS)]
+#[ts(export, export_to = "explorer/")]
+pub struct TransactionView {
+    pub hash: String,
+    pub sender: Option<String>,
+    pub recipient: String,
+    #[ts(type = "number")]
+    pub amount: u64,
+    /// Free-form data attached to a transfer (e.g. a hangman result)
+    #[serde(default)]
+    pub memo: Option<String>,
+    pub is_coinbase: bool,
+}
+
+/// A block as shown in the explorer (with its hash precomputed)
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, TS)]
+#[ts(export, export_to = "explorer/")]
+pub struct BlockView {
+    pub schema_version: u32,
+    #[ts(type = "number")]
+    pub index: u64,
+    pub hash: String,
+    pub prev_hash: String,
+    /// Nonce as a 16-digit hex string (u64 doesn't fit in a JS number)
+    pub nonce: String,
+    pub is_valid: bool,
+    pub transactions: Vec<TransactionView>,
+}
+
+/// Overview of the chain for the explorer header
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, TS)]
+#[ts(export, export_to = "explorer/")]
+pub struct ChainSummary {
+    pub schema_version: u32,
+    #[ts(type = "number")]
+    pub length: usize,
+    pub latest_hash: String,
+    pub ceiling: i32,
+    pub chain_valid: bool,
+    pub mempool: Vec<TransactionView>,
+    pub mining: bool,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_u64_fields_are_numbers_in_typescript() {
+        assert!(BlockView::decl().contains("index: number"));
+        assert!(TransactionView::decl().contains("amount: number"));
+    }
+}
@@ -0,0 +1,1625 @@
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use ts_rs::TS;
+use crate::{current_version, enabled, SCHEMA_VERSION};
+
+/// Event emitted with a [`GameUpdate`] whenever a session's game changes
+pub const GAME_UPDATED_EVENT: &str = "game_updated";
+
+/// Event emitted with an [`AchievementView`] when a finished game unlocks an achievement
+pub const ACHIEVEMENT_UNLOCKED_EVENT: &str = "achievement_unlocked";
+
+/// Event emitted with a [`ConnectionView`] when hosting or a joined game starts or stops,
+/// and when a guest comes or goes
+pub const CONNECTION_CHANGED_EVENT: &str = "connection_changed";
+
+/// Event emitted with a [`RemoteGameUpdate`] whenever the game joined with `join_game` changes
+pub const REMOTE_GAME_UPDATED_EVENT: &str = "remote_game_updated";
+
+/// Event emitted with a [`VersusUpdate`] whenever the duel hosted or joined changes
+pub const VERSUS_UPDATED_EVENT: &str = "versus_updated";
+
+/// How hard a game is
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize, TS)]
+#[serde(rename_all = "lowercase")]
+#[ts(export, export_to = "hangman/")]
+pub enum Difficulty {
+    Easy,
+    #[default]
+    Medium,
+    Hard,
+}
+
+/// How a game picks its word: up front, or dodging guesses for as long as it can
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize, TS)]
+#[serde(rename_all = "lowercase")]
+#[ts(export, export_to = "hangman/")]
+pub enum GameMode {
+    #[default]
+    Classic,
+    Evil,
+}
+
+/// How letters are shown to the player
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize, TS)]
+#[serde(rename_all = "lowercase")]
+#[ts(export, export_to = "hangman/")]
+pub enum LetterCase {
+    #[default]
+    Upper,
+    Lower,
+}
+
+/// Where new games get their words from: the word list files, a word API, the
+/// `word_file` list, or the day's words
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize, TS)]
+#[serde(rename_all = "lowercase")]
+#[ts(export, export_to = "hangman/")]
+pub enum WordSource {
+    #[default]
+    Bundled,
+    Online,
+    File,
+    Daily,
+}
+
+/// Where a key of the keyboard stands: not guessed yet, or guessed and in the word or not
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, TS)]
+#[serde(rename_all = "lowercase")]
+#[ts(export, export_to = "hangman/")]
+pub enum KeyState {
+    Unused,
+    Correct,
+    Wrong,
+}
+
+/// A key of the keyboard
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, TS)]
+#[ts(export, export_to = "hangman/")]
+pub struct KeyView {
+    // A letter, a digit or symbol its word list guesses, or a digraph's letters (e.g. "CH")
+    pub letter: String,
+    pub state: KeyState,
+}
+
+/// What kind of move a timeline entry is
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, TS)]
+#[serde(rename_all = "lowercase")]
+#[ts(export, export_to = "hangman/")]
+pub enum MoveKind {
+    Letter,
+    Word,
+    Hint,
+}
+
+/// One move of a game, for drawing its timeline
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, TS)]
+#[ts(export, export_to = "hangman/")]
+pub struct MoveView {
+    pub kind: MoveKind,
+    // The letter or word guessed, or the letter a hint revealed (in the game's letter case)
+    pub guess: String,
+    // Whether the letter is in the word, or the word was right
+    pub hit: bool,
+    // When it was made, in Unix milliseconds
+    #[ts(type = "number")]
+    pub at_ms: u64,
+}
+
+/// A place of the word the last move revealed, to animate
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, TS)]
+#[ts(export, export_to = "hangman/")]
+pub struct RevealedPlaceView {
+    // The place in the word, counting from 0 (a digraph is one place)
+    pub place: u32,
+    // Its letter, in the game's letter case (a digraph's letters, e.g. "CH")
+    pub letter: String,
+    // Where the letter starts in `display_word`, in characters
+    pub display_at: u32,
+}
+
+/// What the last move revealed, so only the new letters need animating
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize, TS)]
+#[ts(export, export_to = "hangman/")]
+pub struct RevealView {
+    // Empty after a miss, or before the first move
+    pub places: Vec<RevealedPlaceView>,
+    // Places with a letter shown now, out of `letters`
+    pub revealed: u32,
+    pub letters: u32,
+}
+
+/// The current game as the guessing player may see it
+/// While the game is running `word` only contains the revealed letters,
+/// every hidden letter is "_"
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, TS)]
+#[ts(export, export_to = "hangman/")]
+pub struct GameView {
+    pub schema_version: u32,
+    // The revealed characters with "_" for the rest while playing, the whole word once it's over
+    pub word: String,
+    // The revealed characters with "_" for the rest, even once the game is over
+    pub mask: String,
+    // The mask drawn in the settings' display format, ready to show (e.g. "R _ S T   _ _")
+    pub display_word: String,
+    // Letters and digraphs guessed, in order
+    pub guessed_letters: Vec<String>,
+    pub guessed_words: Vec<String>,
+    pub wrong_guesses: u32,
+    pub max_wrong_guesses: u32,
+    // Stage of the gallows to draw, 0 to `stages`: one per wrong guess allowed (the
+    // budget), all of them once the game is lost
+    pub stage: u32,
+    pub stages: u32,
+    // The gallows drawn in ASCII at `stage` (lines joined by "\n")
+    pub gallows: String,
+    pub game_over: bool,
+    pub won: bool,
+    pub category: Option<String>,
+    // A clue about the word to show while it's guessed (null without one, or with clues off)
+    pub clue: Option<String>,
+    pub difficulty: Difficulty,
+    pub mode: GameMode,
+    pub custom: bool,
+    // A practice game: wrong guesses are counted but never lose it
+    #[serde(default)]
+    pub practice: bool,
+    // A kids game: a bigger budget, the first letter shown, no clock (drawn for kids)
+    #[serde(default)]
+    pub kids_mode: bool,
+    // Whether the game counts in the statistics and profiles (not for practice games,
+    // games the AI moved in, or games with an undo)
+    #[serde(default)]
+    pub ranked: bool,
+    pub hints_used: u32,
+    pub max_hints: u32,
+    pub letter_case: LetterCase,
+    // Profile the game counts for (null if no profile is selected)
+    pub player: Option<String>,
+    // Worked out by the backend when the game ends (null while playing)
+    pub score: Option<ScoreView>,
+    // How hard the word was, once the game is over (null while playing)
+    pub rating: Option<WordRatingView>,
+    // Seconds per game in timed mode (null for untimed games)
+    pub time_limit: Option<u32>,
+    // Milliseconds left on the backend's clock, frozen once the game is over
+    // (null for untimed games)
+    #[ts(type = "number | null")]
+    pub remaining_ms: Option<u64>,
+    // Whether the clock is stopped (`pause_game`), guesses are refused until `unpause_game`
+    pub paused: bool,
+    // Milliseconds the game spent paused so far, never taken off `remaining_ms`
+    #[ts(type = "number")]
+    pub paused_ms: u64,
+    // Whether the game was lost by running out of time
+    pub timed_out: bool,
+    // Whether the game was lost by giving up (`give_up`)
+    pub gave_up: bool,
+    // Whether guesses match letters regardless of accents (E finds É)
+    pub ignore_accents: bool,
+    // Letters to show as keys, from the word list the game was picked from (and the digraphs setting)
+    pub alphabet: Vec<String>,
+    // The same keys with where each stands, to draw the keyboard from
+    pub keys: Vec<KeyView>,
+    // Day number of a daily challenge (days since 1970-01-01, null for other games)
+    #[ts(type = "number | null")]
+    pub daily: Option<u64>,
+    // Moves the AI player made in this game (games it played in aren't counted in the statistics)
+    pub ai_moves: u32,
+    // Whether undo_guess would take back a move
+    pub can_undo: bool,
+    // Every move so far, oldest first
+    pub history: Vec<MoveView>,
+    // What the last move revealed (the move before after an undo)
+    #[serde(default)]
+    pub reveal: RevealView,
+    // The match this game is a round of (null for other games)
+    pub match_score: Option<MatchView>,
+    // The duel this two-player game is a round of (null for other games)
+    #[serde(default)]
+    pub duel: Option<DuelView>,
+    // The arcade run this game is a word of (null for other games)
+    pub run: Option<RunView>,
+    // The race against the AI this game is (null for other games)
+    pub race: Option<RaceView>,
+    // The campaign level this game is (null for other games), start_campaign_level
+    // with it plays the level again
+    #[serde(default)]
+    pub campaign_level: Option<u32>,
+    // Seed of a game started with one, start_new_game with it deals the same game again
+    // (null for other games)
+    #[ts(type = "number | null")]
+    pub seed: Option<u64>,
+    // Helps bought with points this game, in order
+    pub helps_bought: Vec<Help>,
+    // Letters a help took off the board (none of them in the word)
+    pub removed_letters: Vec<String>,
+    // Power-ups used this game, in order
+    #[serde(default)]
+    pub power_ups_used: Vec<PowerUp>,
+    // Whether a shield will take the next miss
+    #[serde(default)]
+    pub shield: bool,
+    // Milliseconds until a frozen clock runs again (0 if it isn't frozen)
+    #[serde(default)]
+    #[ts(type = "number")]
+    pub frozen_ms: u64,
+}
+
+/// A game as anyone may see it, from `get_public_state`: the revealed letters and the
+/// counters, for spectators and remote players
+/// Unlike [`GameView`] nothing in it is worked out from the word (no score or rating),
+/// and the word itself is only there once the game is over
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, TS)]
+#[ts(export, export_to = "hangman/")]
+pub struct PublicGameView {
+    pub schema_version: u32,
+    // The revealed characters with "_" for the rest, e.g. "R_ST ____"
+    pub mask: String,
+    // The mask drawn in the settings' display format
+    pub display_word: String,
+    pub guessed_letters: Vec<String>,
+    pub guessed_words: Vec<String>,
+    pub wrong_guesses: u32,
+    pub max_wrong_guesses: u32,
+    // Stage of the gallows to draw, 0 to `stages`: one per wrong guess allowed (the
+    // budget), all of them once the game is lost
+    pub stage: u32,
+    pub stages: u32,
+    // The gallows drawn in ASCII at `stage` (lines joined by "\n")
+    pub gallows: String,
+    pub hints_used: u32,
+    pub max_hints: u32,
+    // Milliseconds left on the backend's clock (null for untimed games)
+    #[ts(type = "number | null")]
+    pub remaining_ms: Option<u64>,
+    pub paused: bool,
+    pub game_over: bool,
+    pub won: bool,
+    // The answer, null until the game is over
+    pub word: Option<String>,
+}
+
+/// What happened to one letter of `guess_letters`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, TS)]
+#[serde(rename_all = "lowercase")]
+#[ts(export, export_to = "hangman/")]
+pub enum LetterResult {
+    Hit,
+    Miss,
+    // Not a letter the rules allow, see `error`
+    Rejected,
+    // Not tried, the game was over by then
+    Skipped,
+}
+
+/// One letter of `guess_letters`, in the order they were sent
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, TS)]
+#[ts(export, export_to = "hangman/")]
+pub struct LetterOutcomeView {
+    // As sent
+    pub input: String,
+    // The letter guessed, in the game's letter case (null if it wasn't guessed)
+    pub letter: Option<String>,
+    pub result: LetterResult,
+    // Why it was rejected (null otherwise)
+    pub error: Option<GameError>,
+}
+
+/// What `guess_letters` did with each letter, and the game after all of them
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, TS)]
+#[ts(export, export_to = "hangman/")]
+pub struct BatchGuessView {
+    pub schema_version: u32,
+    pub outcomes: Vec<LetterOutcomeView>,
+    pub game: GameView,
+}
+
+/// The AI player's move and the game after it
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, TS)]
+#[ts(export, export_to = "hangman/")]
+pub struct AiTurnView {
+    pub schema_version: u32,
+    // The letter or word the AI guessed
+    pub guess: String,
+    // Whether it was a guess at the whole word
+    pub word_guess: bool,
+    pub game: GameView,
+}
+
+/// The smart hint of `get_letter_frequencies`: how common each unguessed letter is in
+/// the dictionary's words that still fit the board
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, TS)]
+#[ts(export, export_to = "hangman/")]
+pub struct LetterFrequenciesView {
+    pub schema_version: u32,
+    // How many words of the dictionary still fit the board
+    pub words: u32,
+    // From the letter in the most words to the one in the fewest
+    pub letters: Vec<LetterFrequencyView>,
+}
+
+/// In how many of the words that fit an unguessed letter is
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, TS)]
+#[ts(export, export_to = "hangman/")]
+pub struct LetterFrequencyView {
+    pub letter: String,
+    pub words: u32,
+}
+
+/// The best letters to guess next, from `suggest_letters`
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, TS)]
+#[ts(export, export_to = "hangman/")]
+pub struct SuggestionsView {
+    pub schema_version: u32,
+    // How many words of the dictionary still fit the board
+    pub words: u32,
+    // Best first, at most three
+    pub suggestions: Vec<SuggestionView>,
+}
+
+/// A letter to guess and how much it's expected to tell about the answer
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, TS)]
+#[ts(export, export_to = "hangman/")]
+pub struct SuggestionView {
+    pub letter: String,
+    // Expected information gain, in bits
+    pub score: f64,
+    // How many of the words that fit have the letter
+    pub words: u32,
+}
+
+/// The words of a dictionary that fit a mask, found by `search_words`
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, TS)]
+#[ts(export, export_to = "hangman/")]
+pub struct WordSearchView {
+    pub schema_version: u32,
+    // The first of them in the dictionary's order, at most `limit`
+    pub words: Vec<String>,
+    // How many words fit, `words` has only the first `limit` of a longer list
+    pub total: u32,
+    pub limit: u32,
+}
+
+/// A session's game after it changed: a new game, a move, or a timed game running out
+/// Sent to every window, each one only shows the update of its own session
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, TS)]
+#[ts(export, export_to = "hangman/")]
+pub struct GameUpdate {
+    pub schema_version: u32,
+    pub session_id: String,
+    pub game: GameView,
+}
+
+/// What a letter of a word game guess says about the word
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, TS)]
+#[serde(rename_all = "lowercase")]
+#[ts(export, export_to = "hangman/")]
+pub enum LetterFeedback {
+    // The right letter in the right place
+    Correct,
+    // In the word, somewhere else
+    Present,
+    // Not in the word (or not as many times)
+    Absent,
+}
+
+/// A word game guess with a mark per letter, in order
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, TS)]
+#[ts(export, export_to = "hangman/")]
+pub struct WordleGuessView {
+    pub word: String,
+    pub feedback: Vec<LetterFeedback>,
+}
+
+/// A key of the word game's keyboard, with the best mark its letter got
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, TS)]
+#[ts(export, export_to = "hangman/")]
+pub struct WordleKeyView {
+    pub letter: char,
+    pub feedback: LetterFeedback,
+}
+
+/// A word game (find the word in a few guesses) as the frontend sees it
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, TS)]
+#[ts(export, export_to = "hangman/")]
+pub struct WordleView {
+    pub schema_version: u32,
+    // Oldest first
+    pub guesses: Vec<WordleGuessView>,
+    // Letters in the word (and every guess)
+    pub word_len: u32,
+    pub max_guesses: u32,
+    pub game_over: bool,
+    pub won: bool,
+    // The word, null until the game is over
+    pub answer: Option<String>,
+    // Letters guessed so far, A to Z
+    pub keys: Vec<WordleKeyView>,
+}
+
+/// A newly started word game and its session, from `start_wordle`
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, TS)]
+#[ts(export, export_to = "hangman/")]
+pub struct WordleSessionView {
+    pub schema_version: u32,
+    pub session_id: String,
+    pub game: WordleView,
+}
+
+/// A positional guess (a letter in a place) and its mark
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, TS)]
+#[ts(export, export_to = "hangman/")]
+pub struct PositionalGuessView {
+    pub letter: String,
+    // The place guessed, counting from 0
+    pub index: u32,
+    pub feedback: LetterFeedback,
+}
+
+/// A positional game (guess a letter for each place) as the frontend sees it
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, TS)]
+#[ts(export, export_to = "hangman/")]
+pub struct PositionalView {
+    pub schema_version: u32,
+    // Each character of the word, null where it's still hidden
+    pub board: Vec<Option<String>>,
+    // Oldest first
+    pub guesses: Vec<PositionalGuessView>,
+    pub wrong_guesses: u32,
+    pub max_wrong_guesses: u32,
+    pub difficulty: Difficulty,
+    pub game_over: bool,
+    pub won: bool,
+    // The word, null until the game is over
+    pub word: Option<String>,
+}
+
+/// A newly started positional game and its session, from `start_positional_game`
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, TS)]
+#[ts(export, export_to = "hangman/")]
+pub struct PositionalSessionView {
+    pub schema_version: u32,
+    pub session_id: String,
+    pub game: PositionalView,
+}
+
+/// Which way a word of a crossword runs
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, TS)]
+#[serde(rename_all = "lowercase")]
+#[ts(export, export_to = "hangman/")]
+pub enum CrosswordDirection {
+    Across,
+    Down,
+}
+
+/// A cell of a crossword that holds a letter
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, TS)]
+#[ts(export, export_to = "hangman/")]
+pub struct CrosswordCellView {
+    // Counting from 0, from the top left
+    pub row: u32,
+    pub col: u32,
+    // Null while it's hidden
+    pub letter: Option<String>,
+}
+
+/// A word of a crossword and where it is
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, TS)]
+#[ts(export, export_to = "hangman/")]
+pub struct CrosswordWordView {
+    // Of its first letter
+    pub row: u32,
+    pub col: u32,
+    pub direction: CrosswordDirection,
+    pub length: u32,
+    // Whether every letter of it is found
+    pub complete: bool,
+    // The word, null until it's complete or the game is over
+    pub word: Option<String>,
+}
+
+/// A crossword game (several crossing words, letters revealed in all of them) as the
+/// frontend sees it
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, TS)]
+#[ts(export, export_to = "hangman/")]
+pub struct CrosswordView {
+    pub schema_version: u32,
+    // The grid's size, cells not listed in `cells` are empty
+    pub rows: u32,
+    pub cols: u32,
+    pub cells: Vec<CrosswordCellView>,
+    // In the order they were placed
+    pub words: Vec<CrosswordWordView>,
+    // Oldest first
+    pub guessed_letters: Vec<String>,
+    pub wrong_guesses: u32,
+    pub max_wrong_guesses: u32,
+    pub difficulty: Difficulty,
+    pub game_over: bool,
+    pub won: bool,
+}
+
+/// A newly started crossword game and its session, from `start_crossword_game`
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, TS)]
+#[ts(export, export_to = "hangman/")]
+pub struct CrosswordSessionView {
+    pub schema_version: u32,
+    pub session_id: String,
+    pub game: CrosswordView,
+}
+
+/// A game mode whose unfinished game is saved, for `discard_saved_game`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, TS)]
+#[serde(rename_all = "lowercase")]
+#[ts(export, export_to = "hangman/")]
+pub enum SavedGameKind {
+    Hangman,
+    Wordle,
+}
+
+/// The games left unfinished when the app last closed (or crashed), from `get_saved_games`
+/// The frontend offers to restore them with `resume_game` / `resume_wordle`
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, TS)]
+#[ts(export, export_to = "hangman/")]
+pub struct SavedGamesView {
+    pub schema_version: u32,
+    // The hangman game, without its word, null if there's none
+    pub game: Option<PublicGameView>,
+    // The word game, null if there's none
+    pub wordle: Option<WordleView>,
+}
+
+/// Where the app stands with online multiplayer
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize, TS)]
+#[serde(rename_all = "lowercase")]
+#[ts(export, export_to = "hangman/")]
+pub enum ConnectionState {
+    // Neither hosting nor in someone else's game
+    #[default]
+    Offline,
+    // Guests can join one of this app's sessions
+    Hosting,
+    // Playing a game another app hosts
+    Connected,
+    // The connection to the host dropped, or the host stopped (see `reason`)
+    Lost,
+}
+
+/// The multiplayer connection, from `host_game`, `join_game`, `leave_game`, and the
+/// `connection_changed` event
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, TS)]
+#[ts(export, export_to = "hangman/")]
+pub struct ConnectionView {
+    pub schema_version: u32,
+    pub state: ConnectionState,
+    // Where guests connect to, e.g. "192.168.1.20:7878" (null offline)
+    pub address: Option<String>,
+    // The code guests join with (null unless hosting)
+    pub code: Option<String>,
+    // The hosted session (null unless hosting)
+    pub session_id: Option<String>,
+    // Guests connected right now (0 unless hosting)
+    pub guests: u32,
+    // Why the connection was lost (null otherwise)
+    pub reason: Option<String>,
+}
+
+/// The game joined with `join_game`, from the `remote_game_updated` event
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, TS)]
+#[ts(export, export_to = "hangman/")]
+pub struct RemoteGameUpdate {
+    pub schema_version: u32,
+    pub game: GameView,
+    // Why the host refused this window's last guess (null if it didn't)
+    pub error: Option<GameError>,
+}
+
+/// How the winner of a head-to-head duel is decided, picked by its host
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize, TS)]
+#[serde(rename_all = "snake_case")]
+#[ts(export, export_to = "hangman/")]
+pub enum VersusRule {
+    // The first to complete the word wins
+    #[default]
+    FirstSolve,
+    // Both boards are played out, the one who completed it with fewer wrong guesses wins
+    FewestWrongGuesses,
+}
+
+/// How a head-to-head duel ended for the player it's sent to
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, TS)]
+#[serde(rename_all = "lowercase")]
+#[ts(export, export_to = "hangman/")]
+pub enum VersusOutcome {
+    Won,
+    Lost,
+    // Both were hanged
+    Draw,
+}
+
+/// The other player's board in a duel, without its letters
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, TS)]
+#[ts(export, export_to = "hangman/")]
+pub struct OpponentView {
+    // Their profile (null without one)
+    pub name: Option<String>,
+    // Letters in the word (counted once per place) and how many they found
+    pub letters: u32,
+    pub found: u32,
+    pub wrong_guesses: u32,
+    pub max_wrong_guesses: u32,
+    pub game_over: bool,
+    pub won: bool,
+}
+
+/// A head-to-head duel from one player's side
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, TS)]
+#[ts(export, export_to = "hangman/")]
+pub struct VersusView {
+    pub schema_version: u32,
+    // This player's board, the word hidden until it's over
+    pub board: GameView,
+    pub opponent: OpponentView,
+    pub rule: VersusRule,
+    // Null until the duel has a winner
+    pub outcome: Option<VersusOutcome>,
+}
+
+/// Where this app stands with head-to-head duels
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize, TS)]
+#[serde(rename_all = "lowercase")]
+#[ts(export, export_to = "hangman/")]
+pub enum VersusState {
+    // Neither hosting nor in someone else's duel
+    #[default]
+    Offline,
+    // Hosting, no one has joined yet
+    Waiting,
+    // Both players are in
+    Playing,
+    // The other player left or the connection dropped (see `reason`)
+    Lost,
+}
+
+/// The duel, from `host_versus`, `join_versus`, `leave_versus`, `get_versus`, and the
+/// `versus_updated` event
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, TS)]
+#[ts(export, export_to = "hangman/")]
+pub struct VersusUpdate {
+    pub schema_version: u32,
+    pub state: VersusState,
+    // Where the other player connects to and the code they join with (null unless hosting)
+    pub address: Option<String>,
+    pub code: Option<String>,
+    // The duel, null until both players are in
+    pub versus: Option<VersusView>,
+    // Why this window's last guess was refused (null if it wasn't)
+    pub error: Option<GameError>,
+    // Why the duel was lost (null otherwise)
+    pub reason: Option<String>,
+}
+
+/// A newly started game and the session it's played in
+/// Pass `session_id` to every later command about this game
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, TS)]
+#[ts(export, export_to = "hangman/")]
+pub struct SessionView {
+    pub schema_version: u32,
+    pub session_id: String,
+    pub game: GameView,
+}
+
+/// A finished game's score and how it was made up
+/// total = (letter_points + guess_bonus + rating_bonus) * length_multiplier * difficulty_multiplier
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, TS)]
+#[ts(export, export_to = "hangman/")]
+pub struct ScoreView {
+    pub letter_points: u32,
+    pub guess_bonus: u32,
+    // For winning with a word rated medium or hard
+    pub rating_bonus: u32,
+    pub length_multiplier: u32,
+    pub difficulty_multiplier: u32,
+    pub total: u32,
+}
+
+/// How hard a word is to guess, from `rate_word` and in a finished game's `rating`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, TS)]
+#[ts(export, export_to = "hangman/")]
+pub struct WordRatingView {
+    pub schema_version: u32,
+    // 0 (easy) to 100 (hard)
+    pub score: u32,
+    pub level: Difficulty,
+}
+
+/// What changes from one round of a match to the next
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize, TS)]
+#[serde(rename_all = "lowercase")]
+#[ts(export, export_to = "hangman/")]
+pub enum MatchRotation {
+    // Easy, medium, hard, easy, ... from the difficulty the match started with
+    #[default]
+    Difficulty,
+    // Every category in turn
+    Category,
+}
+
+/// Who won a match
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, TS)]
+#[serde(rename_all = "lowercase")]
+#[ts(export, export_to = "hangman/")]
+pub enum MatchWinner {
+    Player,
+    Hangman,
+}
+
+/// The score of a best-of-N match, in the GameView of each of its rounds
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, TS)]
+#[ts(export, export_to = "hangman/")]
+pub struct MatchView {
+    pub schema_version: u32,
+    // The round being played, from 1 ("Round 2 of 5")
+    pub round: u32,
+    pub rounds: u32,
+    pub rotation: MatchRotation,
+    // Rounds won and lost so far
+    pub wins: u32,
+    pub losses: u32,
+    // Rounds either side needs to win the match
+    pub to_win: u32,
+    // Sum of the finished rounds' scores
+    pub score: u32,
+    // Set once the match is decided (null while it's being played)
+    pub winner: Option<MatchWinner>,
+    // Whether next_round would start another round
+    pub can_continue: bool,
+}
+
+/// The head-to-head score of a two-player duel, in the GameView of each of its rounds
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, TS)]
+#[ts(export, export_to = "hangman/")]
+pub struct DuelView {
+    pub schema_version: u32,
+    // The round being played, from 1
+    pub round: u32,
+    // Who set this round's word and who guesses it, player 1 or 2
+    pub setter: u32,
+    pub guesser: u32,
+    // Rounds each player has won: the guesser's when the word was found, the setter's otherwise
+    pub player_one_wins: u32,
+    pub player_two_wins: u32,
+    // Whether rematch would start the next round (the roles swapped)
+    pub can_rematch: bool,
+}
+
+/// The lives and score of an arcade run, in the GameView of each of its games
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, TS)]
+#[ts(export, export_to = "hangman/")]
+pub struct RunView {
+    pub schema_version: u32,
+    // The word being played, from 1
+    pub word: u32,
+    // Lives left, and the lives the run started with (a lost game costs one)
+    pub lives: u32,
+    pub start_lives: u32,
+    // Wins in a row since the last loss, and how many win a life back
+    pub streak: u32,
+    pub wins_per_life: u32,
+    // Games won so far
+    pub wins: u32,
+    // Sum of the finished games' scores
+    pub score: u32,
+    // Whether the last life is lost
+    pub over: bool,
+    // Whether next_word would start another game
+    pub can_continue: bool,
+}
+
+/// How far the selected profile got in the campaign, from `get_campaign_progress`
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, TS)]
+#[ts(export, export_to = "hangman/")]
+pub struct CampaignView {
+    pub schema_version: u32,
+    // Every level, level 1 first
+    pub levels: Vec<CampaignLevelView>,
+    // Sum of the best scores of the levels won, what some levels need to unlock
+    pub score: u32,
+    // The first level not won yet (the last one once every level is)
+    pub next_level: u32,
+    // Whether the last level was won
+    pub completed: bool,
+    // How long it took from starting the first level to winning the last, in ms
+    // (null until it's completed)
+    #[ts(type = "number | null")]
+    pub time_ms: Option<u64>,
+}
+
+/// One level of the campaign, and how the player did on it
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, TS)]
+#[ts(export, export_to = "hangman/")]
+pub struct CampaignLevelView {
+    // From 1
+    pub level: u32,
+    pub difficulty: Difficulty,
+    // Letters the level's words have (no max: any number above the min)
+    pub lengths: WordLengthsView,
+    // Campaign score it needs, on top of winning the level before
+    pub min_score: u32,
+    pub unlocked: bool,
+    pub won: bool,
+    // Games of the level finished, won or lost
+    pub attempts: u32,
+    pub best_score: u32,
+}
+
+/// Whose turn it is in a race against the AI
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, TS)]
+#[serde(rename_all = "lowercase")]
+#[ts(export, export_to = "hangman/")]
+pub enum RaceTurn {
+    Player,
+    Ai,
+}
+
+/// Who won a race against the AI
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, TS)]
+#[serde(rename_all = "lowercase")]
+#[ts(export, export_to = "hangman/")]
+pub enum RaceWinner {
+    Player,
+    Ai,
+    // Both were hanged
+    Hangman,
+}
+
+/// How the AI is doing on its own board in a race, in the race's GameView
+/// (the player's board is the rest of the GameView)
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, TS)]
+#[ts(export, export_to = "hangman/")]
+pub struct RaceView {
+    pub schema_version: u32,
+    // Whose move it is, `ai` until ai_take_turn has made the AI's
+    pub turn: RaceTurn,
+    // Letters in the word (counted once per place) and how many the AI found
+    pub letters: u32,
+    pub ai_found: u32,
+    // Letters the AI guessed, in order
+    pub ai_letters: Vec<String>,
+    pub ai_wrong_guesses: u32,
+    pub ai_max_wrong_guesses: u32,
+    // Whether the AI is hanged (the player plays on alone)
+    pub ai_out: bool,
+    // Set once the race is over (null while it's on)
+    pub winner: Option<RaceWinner>,
+}
+
+/// Game settings, read by `get_settings` and sent to `set_settings` (which saves them)
+/// `null` limits follow the difficulty level
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, TS)]
+#[ts(export, export_to = "hangman/")]
+pub struct SettingsView {
+    #[serde(default = "current_version")]
+    pub schema_version: u32,
+    // Difficulty of games started without one
+    #[serde(default)]
+    pub difficulty: Difficulty,
+    // Category the game picker starts on, `null` for the main word list
+    #[serde(default)]
+    pub category: Option<String>,
+    // Word list language (e.g. "es"), used from the next start of the app
+    #[serde(default)]
+    pub language: Option<String>,
+    #[serde(default = "enabled")]
+    pub hints_enabled: bool,
+    // Whether games show the clue of their word or its category (e.g. "It's an animal")
+    #[serde(default)]
+    pub show_clues: bool,
+    // "online" fetches words right away, and plays the saved ones until they arrive
+    #[serde(default)]
+    pub word_source: WordSource,
+    // The word list file of the "file" word source (null without one)
+    #[serde(default)]
+    pub word_file: Option<String>,
+    // Whether the built-in list of offensive words is left out of games
+    #[serde(default = "enabled")]
+    pub profanity_filter: bool,
+    // More words never to play with
+    #[serde(default)]
+    pub blocked_words: Vec<String>,
+    pub max_wrong_guesses: Option<u32>,
+    pub hints_allowed: Option<u32>,
+    pub letter_case: LetterCase,
+    // Seconds per game, `null` plays without a clock
+    #[serde(default)]
+    pub time_limit: Option<u32>,
+    // Whether guesses match letters regardless of accents
+    #[serde(default)]
+    pub ignore_accents: bool,
+    // Letter groups guessed as one letter, e.g. "CH" (2 or 3 letters from A to Z)
+    #[serde(default)]
+    pub digraphs: Vec<String>,
+    #[serde(default)]
+    pub display: DisplayFormatView,
+    // Seeds the word and hint picks, so the same games are dealt again (read when the app
+    // starts, `null` follows SANDBOX_SEED or is random)
+    #[serde(default)]
+    #[ts(type = "number | null")]
+    pub seed: Option<u64>,
+    // Fewest and most letters of a new game's word (`null` for no bound)
+    #[serde(default)]
+    pub min_word_length: Option<u32>,
+    #[serde(default)]
+    pub max_word_length: Option<u32>,
+    // Anonymous gameplay telemetry, off unless the player turns it on
+    #[serde(default)]
+    pub telemetry: bool,
+    // Where telemetry is posted, `null` keeps it in a local file
+    #[serde(default)]
+    pub telemetry_endpoint: Option<String>,
+    // Whether the commands invoked are logged (see `get_audit_log`), off by default
+    #[serde(default)]
+    pub audit_log: bool,
+    // Whether the command metrics (see `get_metrics`) are written to a file as the app exits
+    #[serde(default)]
+    pub metrics_on_exit: bool,
+    // Whether new games are kids games (curated words, a bigger budget, the first
+    // letter shown, no clock)
+    #[serde(default)]
+    pub kids_mode: bool,
+}
+
+/// A word provider's health check
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, TS)]
+#[ts(export, export_to = "hangman/")]
+pub struct WordProviderView {
+    pub source: WordSource,
+    // Whether it can give words right now, and how many (0 when it can't)
+    pub healthy: bool,
+    pub words: u32,
+    // Why it can't (null when it can)
+    pub error: Option<String>,
+}
+
+/// Where new games get their words from, from `get_word_providers`
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, TS)]
+#[ts(export, export_to = "hangman/")]
+pub struct WordProvidersView {
+    pub schema_version: u32,
+    // The settings' word source, and the one the words come from (the bundled
+    // one when the selected one can't give words)
+    pub selected: WordSource,
+    pub serving: WordSource,
+    // Why the selected one isn't serving (null when it is)
+    pub fallback: Option<String>,
+    // Every registered provider
+    pub providers: Vec<WordProviderView>,
+}
+
+/// Fewest and most letters of a new game's word, for `start_new_game`
+/// A bound left out (or `null`) is the settings' one
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize, TS)]
+#[ts(export, export_to = "hangman/")]
+#[serde(default)]
+pub struct WordLengthsView {
+    pub min: Option<u32>,
+    pub max: Option<u32>,
+}
+
+/// How the word is drawn in GameView's `display_word`
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, TS)]
+#[ts(export, export_to = "hangman/")]
+#[serde(default)]
+pub struct DisplayFormatView {
+    // Shown for each letter that isn't guessed yet (not a letter or a space)
+    pub placeholder: char,
+    // Whether letters are separated by a space
+    pub letter_spacing: bool,
+    // Between the words of a phrase, 1 to 5 characters
+    pub word_separator: String,
+}
+
+impl Default for DisplayFormatView {
+    fn default() -> Self {
+        DisplayFormatView {
+            placeholder: '_',
+            letter_spacing: true,
+            word_separator: "   ".to_string(),
+        }
+    }
+}
+
+/// Win/loss statistics across all finished games
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, TS)]
+#[ts(export, export_to = "hangman/")]
+pub struct StatisticsView {
+    pub schema_version: u32,
+    pub games_played: u32,
+    pub wins: u32,
+    pub losses: u32,
+    pub current_streak: u32,
+    pub best_streak: u32,
+    pub average_wrong_guesses: f64,
+    // Share of games won, 0.0 to 1.0
+    pub win_rate: f64,
+}
+
+/// How one word went over the games it was played in, from `get_word_stats` and
+/// `get_most_missed_words`
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, TS)]
+#[ts(export, export_to = "hangman/")]
+pub struct WordStatsView {
+    pub schema_version: u32,
+    pub word: String,
+    // Games played with the word, won, and lost
+    pub played: u32,
+    pub wins: u32,
+    pub losses: u32,
+    // Share of its games won, 0.0 to 1.0
+    pub win_rate: f64,
+    pub average_wrong_guesses: f64,
+}
+
+impl Default for StatisticsView {
+    fn default() -> Self {
+        StatisticsView {
+            schema_version: SCHEMA_VERSION,
+            games_played: 0,
+            wins: 0,
+            losses: 0,
+            current_streak: 0,
+            best_streak: 0,
+            average_wrong_guesses: 0.0,
+            win_rate: 0.0,
+        }
+    }
+}
+
+/// A player profile with its own statistics
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, TS)]
+#[ts(export, export_to = "hangman/")]
+pub struct ProfileView {
+    pub schema_version: u32,
+    pub name: String,
+    // Whether new games are played by this profile
+    pub current: bool,
+    pub statistics: StatisticsView,
+    // Best first
+    pub high_scores: Vec<u32>,
+    #[ts(type = "number")]
+    pub total_score: u64,
+    // Points to spend on helps
+    #[ts(type = "number")]
+    pub points: u64,
+    // How long the campaign took, in ms (null until it's completed)
+    #[serde(default)]
+    #[ts(type = "number | null")]
+    pub campaign_ms: Option<u64>,
+}
+
+/// One row of the leaderboard
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, TS)]
+#[ts(export, export_to = "hangman/")]
+pub struct LeaderboardEntry {
+    // 1 for the best player
+    pub rank: u32,
+    pub name: String,
+    pub best_score: u32,
+    #[ts(type = "number")]
+    pub total_score: u64,
+    pub wins: u32,
+    pub games_played: u32,
+}
+
+/// Profiles ranked by best score, then wins
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, TS)]
+#[ts(export, export_to = "hangman/")]
+pub struct Leaderboard {
+    pub schema_version: u32,
+    pub entries: Vec<LeaderboardEntry>,
+}
+
+/// One update of a profile's rating against the AI, after a race
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, TS)]
+#[ts(export, export_to = "hangman/")]
+pub struct RatingPointView {
+    pub result: RaceWinner,
+    // The rating after the race, and by how much the race moved it
+    pub rating: i32,
+    pub change: i32,
+    // Milliseconds since the Unix epoch
+    #[ts(type = "number")]
+    pub at_ms: u64,
+}
+
+/// A profile's rating against the AI on one difficulty, from `get_rating_history`
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, TS)]
+#[ts(export, export_to = "hangman/")]
+pub struct RatingHistoryView {
+    pub schema_version: u32,
+    // Profile it's for (null without a profile)
+    pub player: Option<String>,
+    pub difficulty: Difficulty,
+    // The current rating, and the AI's on this difficulty
+    pub rating: i32,
+    pub ai_rating: i32,
+    // Oldest first
+    pub points: Vec<RatingPointView>,
+}
+
+/// An achievement and whether a player has unlocked it, from `get_achievements`
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, TS)]
+#[ts(export, export_to = "hangman/")]
+pub struct AchievementView {
+    pub schema_version: u32,
+    // Stable key, e.g. "flawless"
+    pub id: String,
+    pub title: String,
+    pub description: String,
+    // Profile it's for (null without a profile)
+    pub player: Option<String>,
+    pub unlocked: bool,
+    // Milliseconds since the Unix epoch (null while locked)
+    #[ts(type = "number | null")]
+    pub unlocked_at_ms: Option<u64>,
+    // Word of the game that unlocked it (null while locked)
+    pub word: Option<String>,
+}
+
+/// A word of an imported file that wasn't added
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, TS)]
+#[ts(export, export_to = "hangman/")]
+pub struct RejectedWord {
+    // As written in the file
+    pub word: String,
+    // E.g. "only letters and spaces are allowed (found '2')"
+    pub reason: String,
+}
+
+/// What `import_word_list` did with a file
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, TS)]
+#[ts(export, export_to = "hangman/")]
+pub struct ImportSummary {
+    pub schema_version: u32,
+    // Added to the player's words, uppercased
+    pub accepted: Vec<String>,
+    // Already playable, or repeated in the file
+    pub duplicates: Vec<String>,
+    pub rejected: Vec<RejectedWord>,
+    // Words the player's games now pick from
+    pub pool_size: u32,
+}
+
+/// A word list entry that had characters stripped to be played
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, TS)]
+#[ts(export, export_to = "hangman/")]
+pub struct CleanedWord {
+    // As written in the file, e.g. "don't"
+    pub entry: String,
+    // The word played, e.g. "DONT"
+    pub word: String,
+}
+
+/// What cleaning up the word list did when it was loaded (`get_word_list_report`)
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, TS)]
+#[ts(export, export_to = "hangman/")]
+pub struct WordListReport {
+    pub schema_version: u32,
+    // Words kept
+    pub words: u32,
+    pub cleaned: Vec<CleanedWord>,
+    // Repeated in the list, kept once
+    pub duplicates: Vec<String>,
+    pub rejected: Vec<RejectedWord>,
+}
+
+/// A word list language (`list_languages`, `set_language`)
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, TS)]
+#[ts(export, export_to = "hangman/")]
+pub struct LanguageView {
+    pub schema_version: u32,
+    // As in the `language` setting, e.g. "es" ("en" for the main word list)
+    pub code: String,
+    // In the language itself, e.g. "Español" (the code for one without a bundled list)
+    pub name: String,
+    // Keys a game from the list offers
+    pub alphabet: Vec<char>,
+    // Words in the list
+    pub words: u32,
+}
+
+/// File format of `export_history`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize, TS)]
+#[serde(rename_all = "lowercase")]
+#[ts(export, export_to = "hangman/")]
+pub enum ExportFormat {
+    // A row per game, then the statistics
+    #[default]
+    Csv,
+    Json,
+}
+
+/// What happened to a game, in a telemetry event
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, TS)]
+#[serde(rename_all = "lowercase")]
+#[ts(export, export_to = "hangman/")]
+pub enum TelemetryKind {
+    Started,
+    Won,
+    Lost,
+}
+
+/// An anonymous telemetry event: no word, player, or time
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, TS)]
+#[ts(export, export_to = "hangman/")]
+pub struct TelemetryEventView {
+    pub kind: TelemetryKind,
+    // Letters in the word
+    pub word_length: u32,
+    // Letter and word guesses made (0 when the game starts)
+    pub guesses: u32,
+    pub difficulty: Difficulty,
+    pub mode: GameMode,
+}
+
+/// The gameplay telemetry collected, from `get_telemetry` (and `clear_telemetry`, empty)
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, TS)]
+#[ts(export, export_to = "hangman/")]
+pub struct TelemetryView {
+    pub schema_version: u32,
+    // The `telemetry` setting
+    pub enabled: bool,
+    // The local file's path, or the endpoint's URL
+    pub destination: String,
+    // Collected, sent with the next full batch
+    pub pending: Vec<TelemetryEventView>,
+    // Already written to the local file
+    pub saved: Vec<TelemetryEventView>,
+}
+
+/// A command the frontend invoked, from `get_audit_log`
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, TS)]
+#[ts(export, export_to = "hangman/")]
+pub struct AuditEntryView {
+    // When it was invoked (ms since the Unix epoch)
+    #[ts(type = "number")]
+    pub at_ms: u64,
+    // Label of the window that invoked it
+    pub window: String,
+    pub command: String,
+    // The arguments as JSON, secrets replaced with "[redacted]"
+    pub args: String,
+    pub ok: bool,
+    // What it failed with, as JSON
+    pub error: Option<String>,
+    #[ts(type = "number")]
+    pub duration_ms: u64,
+}
+
+/// The command audit log, from `get_audit_log` (and `clear_audit_log`, empty)
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, TS)]
+#[ts(export, export_to = "hangman/")]
+pub struct AuditLogView {
+    pub schema_version: u32,
+    // The `audit_log` setting
+    pub enabled: bool,
+    // The log file's path
+    pub file: String,
+    // Newest first
+    pub entries: Vec<AuditEntryView>,
+}
+
+/// How often a command ran and how long it took (in microseconds), from `get_metrics`
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, TS)]
+#[ts(export, export_to = "hangman/")]
+pub struct CommandMetricsView {
+    pub command: String,
+    #[ts(type = "number")]
+    pub calls: u64,
+    // Calls that failed
+    #[ts(type = "number")]
+    pub errors: u64,
+    // The percentiles are of its last 1000 calls
+    #[ts(type = "number")]
+    pub mean_us: u64,
+    #[ts(type = "number")]
+    pub p50_us: u64,
+    #[ts(type = "number")]
+    pub p90_us: u64,
+    #[ts(type = "number")]
+    pub p99_us: u64,
+    #[ts(type = "number")]
+    pub max_us: u64,
+    #[ts(type = "number")]
+    pub total_us: u64,
+}
+
+/// Every command's metrics since the app started, from `get_metrics`
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, TS)]
+#[ts(export, export_to = "hangman/")]
+pub struct MetricsView {
+    pub schema_version: u32,
+    // The `metrics_on_exit` setting
+    pub on_exit: bool,
+    // Where they're written on exit
+    pub file: String,
+    // The most time spent in first
+    pub commands: Vec<CommandMetricsView>,
+}
+
+/// What points buy in a game
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, TS)]
+#[serde(rename_all = "snake_case")]
+#[ts(export, export_to = "hangman/")]
+pub enum Help {
+    // Shows a vowel of the word, without costing a guess
+    RevealVowel,
+    // Takes a letter that isn't in the word off the board
+    RemoveLetter,
+    // One more wrong guess allowed this game
+    ExtraLife,
+}
+
+/// A help and what it costs
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, TS)]
+#[ts(export, export_to = "hangman/")]
+pub struct HelpPrice {
+    pub help: Help,
+    #[ts(type = "number")]
+    pub price: u64,
+}
+
+/// The selected profile's points and what they buy, from `get_wallet`
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, TS)]
+#[ts(export, export_to = "hangman/")]
+pub struct WalletView {
+    pub schema_version: u32,
+    // Null if no profile is selected (only profiles have points)
+    pub player: Option<String>,
+    #[ts(type = "number")]
+    pub points: u64,
+    // Points an easy win earns (x2 medium, x3 hard)
+    #[ts(type = "number")]
+    pub win_points: u64,
+    pub prices: Vec<HelpPrice>,
+}
+
+/// What a profile earns by winning and uses in a later game
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, TS)]
+#[serde(rename_all = "snake_case")]
+#[ts(export, export_to = "hangman/")]
+pub enum PowerUp {
+    // Stops a timed game's clock for a few seconds
+    FreezeTimer,
+    // Shows a consonant of the word, without costing a guess
+    RevealConsonant,
+    // The next wrong guess costs nothing
+    Shield,
+}
+
+/// One kind of power-up and how many the profile holds
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, TS)]
+#[ts(export, export_to = "hangman/")]
+pub struct PowerUpView {
+    pub power_up: PowerUp,
+    pub name: String,
+    // What it does, and how it's earned
+    pub description: String,
+    pub held: u32,
+}
+
+/// The selected profile's power-ups, from `get_power_ups`
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, TS)]
+#[ts(export, export_to = "hangman/")]
+pub struct InventoryView {
+    pub schema_version: u32,
+    // Null if no profile is selected (only profiles hold power-ups)
+    pub player: Option<String>,
+    // Every power-up, held or not
+    pub power_ups: Vec<PowerUpView>,
+    // Most of each a profile holds
+    pub max_held: u32,
+}
+
+/// A power-up used with `activate_powerup`, and the game after it
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, TS)]
+#[ts(export, export_to = "hangman/")]
+pub struct ActivationView {
+    pub schema_version: u32,
+    pub power_up: PowerUp,
+    // The consonant revealed (null for the others)
+    pub letter: Option<String>,
+    // How many of it are left
+    pub held: u32,
+    pub game: GameView,
+}
+
+/// A help bought with `buy_help`, and the game after it
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, TS)]
+#[ts(export, export_to = "hangman/")]
+pub struct PurchaseView {
+    pub schema_version: u32,
+    pub help: Help,
+    // The letter revealed or removed (null for an extra life)
+    pub letter: Option<String>,
+    #[ts(type = "number")]
+    pub price: u64,
+    // Points left
+    #[ts(type = "number")]
+    pub points: u64,
+    pub game: GameView,
+}
+
+/// A game played again from a replay by `import_replay`, a step per move
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, TS)]
+#[ts(export, export_to = "hangman/")]
+pub struct ReplayView {
+    pub schema_version: u32,
+    // The game before the first move, then after each (and once more after
+    // giving up or running out of time, which aren't moves)
+    pub steps: Vec<ReplayStepView>,
+    // Milliseconds from the first move to the last
+    #[ts(type = "number")]
+    pub duration_ms: u64,
+}
+
+/// One step of a replay
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, TS)]
+#[ts(export, export_to = "hangman/")]
+pub struct ReplayStepView {
+    // When the move was made, in milliseconds since the first move
+    #[ts(type = "number")]
+    pub at_ms: u64,
+    pub game: GameView,
+}
+
+/// What `export_history` wrote
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, TS)]
+#[ts(export, export_to = "hangman/")]
+pub struct ExportSummary {
+    pub schema_version: u32,
+    pub path: String,
+    pub format: ExportFormat,
+    pub games: u32,
+    // Moves of all the games together
+    pub guesses: u32,
+}
+
+/// What a finished game's word means, from `get_word_definition`
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, TS)]
+#[ts(export, export_to = "hangman/")]
+pub struct DefinitionView {
+    pub schema_version: u32,
+    pub word: String,
+    // "noun", "verb", ... (null if the dictionary doesn't say)
+    pub part_of_speech: Option<String>,
+    pub definition: String,
+}
+
+/// Why a game command was refused, e.g. `{ "kind": "game_already_over" }`
+/// or `{ "kind": "invalid_input", "message": "..." }`
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, TS)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+#[ts(export, export_to = "hangman/")]
+pub enum GameError {
+    /// An argument made no sense (not a letter, an unknown category, a bad secret, ...)
+    InvalidInput { message: String },
+    /// The game has already been won or lost
+    GameAlreadyOver,
+    /// There's no game for the session (it was never started, or it expired)
+    NoActiveGame,
+    /// A letter guess with nothing in it
+    EmptyGuess,
+    /// A letter guess with more than one character (words go through guess_word)
+    MultipleCharacters,
+    /// A digit was guessed instead of a letter
+    Digit { character: char },
+    /// Punctuation or another symbol was guessed instead of a letter
+    NotALetter { character: char },
+    /// The letter (or digraph) was already guessed this game (uppercase)
+    AlreadyGuessed { letter: String },
+    /// The rules refused a hint (none left, or paying for it would lose the game)
+    HintUnavailable { message: String },
+    /// Today's daily challenge was already finished by this player
+    DailyCompleted,
+    /// The command needs a finished game (e.g. looking up the word's definition)
+    GameNotOver,
+    /// undo_guess found no move to take back
+    NothingToUndo,
+    /// buy_help was asked for a help the player's points don't cover
+    NotEnoughPoints {
+        #[ts(type = "number")]
+        price: u64,
+        #[ts(type = "number")]
+        points: u64,
+    },
+    /// The help has nothing left to do in this game (`message` says why)
+    HelpUnavailable { message: String },
+    /// A guess, hint, or help while the game is paused (`unpause_game` first)
+    GamePaused,
+    /// A guess, hint, or help on the AI's turn in a race (`ai_take_turn` plays it)
+    NotYourTurn,
+    /// start_campaign_level for a level that isn't unlocked yet
+    LevelLocked { level: u32 },
+    /// activate_powerup for a power-up the player has none of
+    NoPowerUpLeft { power_up: PowerUp },
+    /// The power-up has nothing to do in this game (`message` says why)
+    PowerUpUnavailable { message: String },
+}
+
+impl GameError {
+    pub fn invalid_input(message: impl Into<String>) -> Self {
+        GameError::InvalidInput { message: message.into() }
+    }
+}
+
+impl fmt::Display for GameError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            GameError::InvalidInput { message } => write!(f, "{}", message),
+            GameError::GameAlreadyOver => write!(f, "the game is already over"),
+            GameError::NoActiveGame => write!(f, "no game is running, start a new game"),
+            GameError::EmptyGuess => write!(f, "enter a letter"),
+            GameError::MultipleCharacters => write!(f, "guess one letter at a time"),
+            GameError::Digit { character } => write!(f, "{:?} is a digit, guess a letter", character),
+            GameError::NotALetter { character } => write!(f, "{:?} isn't a letter", character),
+            GameError::AlreadyGuessed { letter } => write!(f, "{} was already guessed", letter),
+            GameError::HintUnavailable { message } => write!(f, "{}", message),
+            GameError::DailyCompleted => write!(f, "today's challenge is already done, come back tomorrow"),
+            GameError::GameNotOver => write!(f, "the game isn't over yet"),
+            GameError::NothingToUndo => write!(f, "there's no move to undo"),
+            GameError::NotEnoughPoints { price, points } => {
+                write!(f, "that costs {} points and you have {}", price, points)
+            }
+            GameError::HelpUnavailable { message } => write!(f, "{}", message),
+            GameError::GamePaused => write!(f, "the game is paused, resume it first"),
+            GameError::NotYourTurn => write!(f, "it's the AI's turn, wait for its move"),
+            GameError::LevelLocked { level } => write!(f, "level {} isn't unlocked yet", level),
+            GameError::NoPowerUpLeft { .. } => write!(f, "you have none of that power-up left"),
+            GameError::PowerUpUnavailable { message } => write!(f, "{}", message),
+        }
+    }
+}
+
+impl std::error::Error for GameError {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_settings_without_version_get_current() {
+        let settings: SettingsView =
+            serde_json::from_str(r#"{"max_wrong_guesses":8,"hints_allowed":null,"letter_case":"lower"}"#)
+                .unwrap();
+        assert_eq!(settings.schema_version, SCHEMA_VERSION);
+        assert_eq!(settings.max_wrong_guesses, Some(8));
+        assert_eq!(settings.letter_case, LetterCase::Lower);
+        assert_eq!(settings.difficulty, Difficulty::Medium);
+        assert!(settings.hints_enabled);
+        assert_eq!(settings.display, DisplayFormatView::default());
+    }
+
+    #[test]
+    fn test_game_errors_are_tagged() {
+        assert_eq!(
+            serde_json::to_string(&GameError::GameAlreadyOver).unwrap(),
+            r#"{"kind":"game_already_over"}"#
+        );
+        assert_eq!(
+            serde_json::to_string(&GameError::invalid_input("not a letter")).unwrap(),
+            r#"{"kind":"invalid_input","message":"not a letter"}"#
+        );
+        assert_eq!(
+            serde_json::to_string(&GameError::AlreadyGuessed { letter: "R".to_string() }).unwrap(),
+            r#"{"kind":"already_guessed","letter":"R"}"#
+        );
+    }
+
+    #[test]
+    fn test_enums_are_lowercase() {
+        assert_eq!(serde_json::to_string(&Difficulty::Hard).unwrap(), "\"hard\"");
+        assert_eq!(Difficulty::decl(), r#"type Difficulty = "easy" | "medium" | "hard";"#);
+    }
+}
@@ -0,0 +1,29 @@
+//! Messages exchanged between the sandbox Tauri backends and their frontends
+//!
+//! Every command result and event payload is defined here once, so the Rust
+//! side and the JavaScript side can't drift apart:
+//!
+//! - [`hangman`]: game views, settings, and statistics for the hangman app
+//! - [`explorer`]: blocks, transactions, and chain summaries for the block explorer
+//!
+//! Top-level payloads carry a `schema_version` field (see [`SCHEMA_VERSION`]).
+//! TypeScript definitions are generated into `bindings/` by `cargo test`
+//! using ts-rs, so frontends can type-check against the same shapes.
+
+// Module declarations
+pub mod explorer;
+pub mod hangman;
+
+/// Version of the message shapes in this crate
+/// Bump this whenever a field is removed, renamed, or changes type
+pub const SCHEMA_VERSION: u32 = 1;
+
+/// Default for `schema_version` in payloads sent by a frontend that leaves it out
+pub(crate) fn current_version() -> u32 {
+    SCHEMA_VERSION
+}
+
+/// Default for switches that are on unless a payload turns them off
+pub(crate) fn enabled() -> bool {
+    true
+}
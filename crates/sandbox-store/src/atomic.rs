@@ -0,0 +1,219 @@
+use std::fs::{self, File};
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+use std::process;
+use std::sync::atomic::{AtomicU64, Ordering};
+
+/// Numbers the temp files of concurrent writes within a process
+static NEXT_TEMP: AtomicU64 = AtomicU64::new(0);
+
+/// Write `contents` to `path` atomically
+///
+/// The data is written to a temporary file next to `path`, flushed to disk,
+/// and then renamed over the destination. Readers see either the old file or
+/// the new one, never a partial write. The directory is flushed after the
+/// rename too, so the new file survives a power cut once this returns.
+pub fn write_atomic(path: &Path, contents: &[u8]) -> io::Result<()> {
+    write_atomic_with_backups(path, contents, 0)
+}
+
+/// Write `contents` to `path` atomically (see write_atomic), keeping the
+/// `backups` previous versions as `<file>.1` (the newest) to `<file>.N`
+///
+/// The old file is only rotated once the new data is safely on disk, and it
+/// stays in place until the rename, so there's always a complete copy.
+pub fn write_atomic_with_backups(path: &Path, contents: &[u8], backups: usize) -> io::Result<()> {
+    let parent = path.parent().filter(|parent| !parent.as_os_str().is_empty());
+    if let Some(parent) = parent {
+        fs::create_dir_all(parent)?;
+    }
+
+    let tmp_path = temp_path(path);
+    let result = (|| {
+        let mut file = File::create(&tmp_path)?;
+        file.write_all(contents)?;
+        file.sync_all()?;
+        if backups > 0 {
+            rotate_backups(path, backups)?;
+        }
+        fs::rename(&tmp_path, path)?;
+        sync_dir(parent.unwrap_or(Path::new(".")))
+    })();
+
+    if result.is_err() {
+        // Don't leave stray temp files behind on failure
+        let _ = fs::remove_file(&tmp_path);
+    }
+    result
+}
+
+/// The `generation`th backup of `path` (e.g. "stats.json.1", 1 is the newest)
+pub fn backup_path(path: &Path, generation: usize) -> PathBuf {
+    let mut name = path.file_name().unwrap_or_default().to_os_string();
+    name.push(format!(".{}", generation));
+    path.with_file_name(name)
+}
+
+/// Where a damaged `path` is copied by restore_backup (e.g. "stats.json.damaged")
+pub fn damaged_path(path: &Path) -> PathBuf {
+    let mut name = path.file_name().unwrap_or_default().to_os_string();
+    name.push(".damaged");
+    path.with_file_name(name)
+}
+
+/// Put the `generation`th backup of `path` back in its place, the damaged
+/// file copied aside to damaged_path (replacing the last one there)
+///
+/// The damaged file is copied rather than moved and the backup written over
+/// it atomically, so there's a file at `path` at every point (a crash midway
+/// leaves the damaged one, and the next load restores the backup again). The
+/// backups stay as they are, so the next write rotates the restored copy into
+/// them rather than the damaged one.
+pub fn restore_backup(path: &Path, generation: usize) -> io::Result<()> {
+    let contents = fs::read(backup_path(path, generation))?;
+    match fs::copy(path, damaged_path(path)) {
+        Err(err) if err.kind() != io::ErrorKind::NotFound => return Err(err),
+        _ => {}
+    }
+    write_atomic(path, &contents)
+}
+
+/// Shift the backups of `path` up by one (dropping the oldest past `backups`)
+/// and make the current file the newest backup
+fn rotate_backups(path: &Path, backups: usize) -> io::Result<()> {
+    if !path.exists() {
+        return Ok(());
+    }
+    for generation in (1..backups).rev() {
+        match fs::rename(backup_path(path, generation), backup_path(path, generation + 1)) {
+            Err(err) if err.kind() != io::ErrorKind::NotFound => return Err(err),
+            _ => {}
+        }
+    }
+
+    // A hard link keeps the current file where it is until the rename,
+    // copy on filesystems that don't have them
+    let newest = backup_path(path, 1);
+    match fs::remove_file(&newest) {
+        Err(err) if err.kind() != io::ErrorKind::NotFound => return Err(err),
+        _ => {}
+    }
+    if fs::hard_link(path, &newest).is_err() {
+        fs::copy(path, &newest)?;
+    }
+    Ok(())
+}
+
+/// Flush a directory's entries (the rename) to disk
+#[cfg(unix)]
+fn sync_dir(dir: &Path) -> io::Result<()> {
+    File::open(dir)?.sync_all()
+}
+
+/// Directories can't be opened for syncing here, the rename is as durable
+/// as the OS makes it
+#[cfg(not(unix))]
+fn sync_dir(_dir: &Path) -> io::Result<()> {
+    Ok(())
+}
+
+/// Temporary file used while writing `path` (e.g. "stats.json.4242-0.tmp"),
+/// unique so writers in other threads or processes don't share it
+fn temp_path(path: &Path) -> PathBuf {
+    let mut name = path.file_name().unwrap_or_default().to_os_string();
+    name.push(format!(".{}-{}.tmp", process::id(), NEXT_TEMP.fetch_add(1, Ordering::Relaxed)));
+    path.with_file_name(name)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn file_names(dir: &Path) -> Vec<String> {
+        let mut names: Vec<String> = fs::read_dir(dir)
+            .unwrap()
+            .map(|entry| entry.unwrap().file_name().to_string_lossy().into_owned())
+            .collect();
+        names.sort();
+        names
+    }
+
+    #[test]
+    fn test_write_atomic_creates_file() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("doc.json");
+
+        write_atomic(&path, b"hello").unwrap();
+        assert_eq!(fs::read(&path).unwrap(), b"hello");
+    }
+
+    #[test]
+    fn test_write_atomic_replaces_file() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("doc.json");
+
+        write_atomic(&path, b"old").unwrap();
+        write_atomic(&path, b"new").unwrap();
+        assert_eq!(fs::read(&path).unwrap(), b"new");
+    }
+
+    #[test]
+    fn test_write_atomic_leaves_no_temp_file() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("doc.json");
+
+        write_atomic(&path, b"data").unwrap();
+        write_atomic(&path, b"more").unwrap();
+        assert_eq!(file_names(dir.path()), ["doc.json"]);
+    }
+
+    #[test]
+    fn test_write_atomic_creates_parent_dirs() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("nested").join("doc.json");
+
+        write_atomic(&path, b"data").unwrap();
+        assert!(path.exists());
+    }
+
+    #[test]
+    fn test_backups_rotate() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("doc.json");
+
+        for contents in ["one", "two", "three", "four"] {
+            write_atomic_with_backups(&path, contents.as_bytes(), 2).unwrap();
+        }
+        assert_eq!(fs::read(&path).unwrap(), b"four");
+        assert_eq!(fs::read(backup_path(&path, 1)).unwrap(), b"three");
+        assert_eq!(fs::read(backup_path(&path, 2)).unwrap(), b"two");
+        assert_eq!(file_names(dir.path()), ["doc.json", "doc.json.1", "doc.json.2"]);
+    }
+
+    #[test]
+    fn test_restored_backup_survives_the_next_write() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("doc.json");
+        write_atomic_with_backups(&path, b"good", 1).unwrap();
+        write_atomic_with_backups(&path, b"newer", 1).unwrap();
+        fs::write(&path, b"damaged").unwrap();
+
+        restore_backup(&path, 1).unwrap();
+        assert_eq!(fs::read(&path).unwrap(), b"good");
+        assert_eq!(fs::read(damaged_path(&path)).unwrap(), b"damaged");
+        write_atomic_with_backups(&path, b"next", 1).unwrap();
+        assert_eq!(fs::read(backup_path(&path, 1)).unwrap(), b"good");
+        assert_eq!(file_names(dir.path()), ["doc.json", "doc.json.1", "doc.json.damaged"]);
+    }
+
+    #[test]
+    fn test_failed_write_cleans_up() {
+        let dir = tempfile::tempdir().unwrap();
+        // A directory where the file should be makes the rename fail
+        let path = dir.path().join("doc.json");
+        fs::create_dir_all(path.join("inside")).unwrap();
+
+        assert!(write_atomic(&path, b"new").is_err());
+        assert_eq!(file_names(dir.path()), ["doc.json"]);
+    }
+}
@@ -0,0 +1,25 @@
+use sha2::{Digest, Sha256};
+
+/// Length of a checksum (see checksum)
+pub const CHECKSUM_LEN: usize = 64;
+
+/// SHA-256 of `contents`, as lowercase hex
+///
+/// Written next to the data it covers, so a file that was damaged after it
+/// was written (a flipped byte that still leaves valid JSON) can be told apart
+/// from the file that was saved.
+pub fn checksum(contents: &[u8]) -> String {
+    Sha256::digest(contents).iter().map(|byte| format!("{:02x}", byte)).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_checksum() {
+        assert_eq!(checksum(b""), "e3b0c44298fc1c149afbf4c8996fb92427ae41e4649b934ca495991b7852b855");
+        assert_eq!(checksum(b"data").len(), CHECKSUM_LEN);
+        assert_ne!(checksum(b"data"), checksum(b"date"));
+    }
+}
@@ -0,0 +1,89 @@
+use std::fmt;
+use std::io;
+use crate::schema::Violation;
+
+/// Errors returned by the store
+#[derive(Debug)]
+pub enum StoreError {
+    /// Reading or writing a file failed
+    Io(io::Error),
+    /// A document could not be encoded or decoded as JSON
+    Json(serde_json::Error),
+    /// The key contains characters that are not allowed in file names
+    InvalidKey(String),
+    /// The document's data doesn't match the checksum saved with it (it was
+    /// damaged after it was written)
+    Checksum { key: String },
+    /// The document on disk was written with a schema version that can't be
+    /// upgraded to this one (a newer one, or an older one with no migration)
+    VersionMismatch {
+        key: String,
+        expected: u32,
+        found: u32,
+    },
+    /// Upgrading the document from schema version `from` failed
+    Migration {
+        key: String,
+        from: u32,
+        reason: String,
+    },
+    /// The document doesn't match its JSON Schema (only with the "validate" feature)
+    Invalid {
+        key: String,
+        violations: Vec<Violation>,
+    },
+}
+
+impl fmt::Display for StoreError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            StoreError::Io(err) => write!(f, "storage I/O error: {}", err),
+            StoreError::Json(err) => write!(f, "invalid stored document: {}", err),
+            StoreError::InvalidKey(key) => write!(f, "invalid store key: {:?}", key),
+            StoreError::Checksum { key } => write!(f, "document {:?} is damaged (its checksum doesn't match)", key),
+            StoreError::VersionMismatch {
+                key,
+                expected,
+                found,
+            } => write!(
+                f,
+                "document {:?} has schema version {}, expected {}",
+                key, found, expected
+            ),
+            StoreError::Migration { key, from, reason } => write!(
+                f,
+                "document {:?} couldn't be upgraded from schema version {}: {}",
+                key, from, reason
+            ),
+            StoreError::Invalid { key, violations } => {
+                write!(f, "document {:?} doesn't match its schema", key)?;
+                for violation in violations {
+                    write!(f, "\n  {}", violation)?;
+                }
+                Ok(())
+            }
+        }
+    }
+}
+
+impl std::error::Error for StoreError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            StoreError::Io(err) => Some(err),
+            StoreError::Json(err) => Some(err),
+            _ => None,
+        }
+    }
+}
+
+impl From<io::Error> for StoreError {
+    fn from(err: io::Error) -> Self {
+        StoreError::Io(err)
+    }
+}
+
+impl From<serde_json::Error> for StoreError {
+    fn from(err: serde_json::Error) -> Self {
+        StoreError::Json(err)
+    }
+}
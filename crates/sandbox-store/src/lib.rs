@@ -0,0 +1,56 @@
+//! Keyed JSON document store shared by the sandbox apps
+//!
+//! Every document is a JSON file named after its key inside the store
+//! directory. Documents are wrapped in an envelope that records the schema
+//! version they were written with, and every write goes through a
+//! temp-file-then-rename (flushed to disk, directory included) so a crash
+//! never leaves a half-written file. Each document carries a checksum of its
+//! data, the previous versions of a document are kept as backups, and loading
+//! falls back to them if the file is damaged, putting the backup back in its
+//! place (the damaged file is kept aside as `<key>.json.damaged`).
+//!
+//! Stored types also derive `schemars::JsonSchema`: [`document_schema`] gives
+//! the JSON Schema of a stored file, which apps export for editors and tools
+//! ([`export_schemas`]), and with the default `validate` feature every loaded
+//! document is checked against it, with the location of each mismatch.
+//!
+//! An older document is upgraded on load: each [`Schema`] lists the
+//! [`Migration`] steps from its previous versions, which run in order on the
+//! JSON before it's decoded, and the upgraded document is saved back (the old
+//! one staying as the newest backup). Documents from a newer version, or with
+//! a step missing, are refused rather than decoded into the wrong shape.
+//!
+//! ```no_run
+//! use sandbox_store::{Schema, Store};
+//! use schemars::JsonSchema;
+//! use serde::{Deserialize, Serialize};
+//!
+//! #[derive(Serialize, Deserialize, JsonSchema)]
+//! struct Stats {
+//!     games_played: u32,
+//! }
+//!
+//! impl Schema for Stats {
+//!     const VERSION: u32 = 1;
+//! }
+//!
+//! let store = Store::open("data").unwrap();
+//! store.save("stats", &Stats { games_played: 3 }).unwrap();
+//! let stats: Option<Stats> = store.load("stats").unwrap();
+//! ```
+
+// Module declarations
+pub mod atomic;
+pub mod checksum;
+pub mod error;
+pub mod migrate;
+pub mod schema;
+pub mod store;
+
+// Re-exports for convenience
+pub use atomic::{backup_path, damaged_path, restore_backup, write_atomic, write_atomic_with_backups};
+pub use checksum::checksum;
+pub use error::StoreError;
+pub use migrate::{migrate, Migration};
+pub use schema::{export_schemas, validate, Violation, SCHEMA_SUFFIX};
+pub use store::{document_schema, decode_document, Schema, Store, DEFAULT_BACKUPS};
@@ -0,0 +1,89 @@
+use serde_json::Value;
+use crate::error::StoreError;
+
+/// One step upgrading a stored document from schema version `from` to `from + 1`
+/// `upgrade` gets the document's data (the envelope's `data`) as JSON and
+/// returns it in the next version's shape, or why it can't
+#[derive(Debug, Clone, Copy)]
+pub struct Migration {
+    pub from: u32,
+    pub upgrade: fn(Value) -> Result<Value, String>,
+}
+
+impl Migration {
+    pub const fn new(from: u32, upgrade: fn(Value) -> Result<Value, String>) -> Self {
+        Migration { from, upgrade }
+    }
+}
+
+/// Upgrade `data`, stored under `key` with schema version `found`, to version
+/// `expected` one step of `migrations` at a time
+/// A document from a newer version, or an older one with a step missing, is
+/// refused with StoreError::VersionMismatch (nothing is dropped)
+pub fn migrate(
+    key: &str,
+    found: u32,
+    expected: u32,
+    migrations: &[Migration],
+    mut data: Value,
+) -> Result<Value, StoreError> {
+    let mismatch = || StoreError::VersionMismatch {
+        key: key.to_string(),
+        expected,
+        found,
+    };
+    if found > expected {
+        return Err(mismatch());
+    }
+    for version in found..expected {
+        let step = migrations.iter().find(|step| step.from == version).ok_or_else(mismatch)?;
+        data = (step.upgrade)(data).map_err(|reason| StoreError::Migration {
+            key: key.to_string(),
+            from: version,
+            reason,
+        })?;
+    }
+    Ok(data)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn add_total(mut data: Value) -> Result<Value, String> {
+        let count = data["count"].take();
+        Ok(json!({ "total": count }))
+    }
+
+    fn double(data: Value) -> Result<Value, String> {
+        let total = data["total"].as_u64().ok_or("total isn't a number")?;
+        Ok(json!({ "total": total * 2 }))
+    }
+
+    const STEPS: &[Migration] = &[Migration::new(2, double), Migration::new(1, add_total)];
+
+    #[test]
+    fn test_steps_run_in_order() {
+        let data = migrate("counter", 1, 3, STEPS, json!({ "count": 4 })).unwrap();
+        assert_eq!(data, json!({ "total": 8 }));
+        let data = migrate("counter", 3, 3, STEPS, json!({ "total": 1 })).unwrap();
+        assert_eq!(data, json!({ "total": 1 }));
+    }
+
+    #[test]
+    fn test_refused_documents() {
+        // Newer than this version, older than the steps know
+        for found in [4, 0] {
+            let result = migrate("counter", found, 3, STEPS, json!({}));
+            assert!(matches!(result, Err(StoreError::VersionMismatch { expected: 3, .. })), "{}", found);
+        }
+        let result = migrate("counter", 2, 3, STEPS, json!({ "total": "seven" }));
+        match result {
+            Err(StoreError::Migration { key, from, reason }) => {
+                assert_eq!((key.as_str(), from, reason.as_str()), ("counter", 2, "total isn't a number"));
+            }
+            other => panic!("expected a failed migration, got {:?}", other),
+        }
+    }
+}
@@ -0,0 +1,71 @@
+use schemars::Schema as JsonSchemaDoc;
+use serde_json::Value;
+use std::fmt;
+use std::path::{Path, PathBuf};
+use crate::atomic::write_atomic;
+use crate::error::StoreError;
+
+/// File name suffix of exported schemas, e.g. `chain.schema.json`
+pub const SCHEMA_SUFFIX: &str = ".schema.json";
+
+/// One place where a document doesn't match its schema
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Violation {
+    /// JSON pointer to the offending value, from the root of the file
+    /// e.g. `/data/blocks/3/nonce` ("" is the whole document)
+    pub path: String,
+    pub message: String,
+}
+
+impl fmt::Display for Violation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let path = if self.path.is_empty() { "/" } else { &self.path };
+        write!(f, "{}: {}", path, self.message)
+    }
+}
+
+/// Check `value` against `schema`, returning every violation found
+#[cfg(feature = "validate")]
+pub fn validate(schema: &JsonSchemaDoc, value: &Value) -> Result<(), Vec<Violation>> {
+    let validator = match jsonschema::validator_for(schema.as_value()) {
+        Ok(validator) => validator,
+        // Schemas come from schemars, so this would be a bug in a type's JsonSchema impl
+        Err(err) => {
+            return Err(vec![Violation {
+                path: String::new(),
+                message: format!("invalid schema: {}", err),
+            }])
+        }
+    };
+    let violations: Vec<Violation> = validator
+        .iter_errors(value)
+        .map(|err| Violation {
+            path: err.instance_path.to_string(),
+            message: err.to_string(),
+        })
+        .collect();
+    if violations.is_empty() {
+        Ok(())
+    } else {
+        Err(violations)
+    }
+}
+
+/// Without the "validate" feature every document is accepted
+#[cfg(not(feature = "validate"))]
+pub fn validate(_schema: &JsonSchemaDoc, _value: &Value) -> Result<(), Vec<Violation>> {
+    Ok(())
+}
+
+/// Write each `(name, schema)` to `<dir>/<name>.schema.json`, returns the paths written
+pub fn export_schemas(dir: &Path, schemas: &[(&str, JsonSchemaDoc)]) -> Result<Vec<PathBuf>, StoreError> {
+    let mut paths = Vec::new();
+    for (name, schema) in schemas {
+        let path = dir.join(format!("{}{}", name, SCHEMA_SUFFIX));
+        let mut json = serde_json::to_vec_pretty(schema)?;
+        json.push(b'\n');
+        write_atomic(&path, &json)?;
+        paths.push(path);
+    }
+    Ok(paths)
+}
@@ -0,0 +1,492 @@
+use schemars::JsonSchema;
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use serde_json::value::RawValue;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use crate::atomic::{backup_path, damaged_path, restore_backup, write_atomic_with_backups};
+use crate::checksum::{checksum, CHECKSUM_LEN};
+use crate::error::StoreError;
+use crate::migrate::{migrate, Migration};
+use crate::schema::validate;
+
+/// File extension used for stored documents
+const EXTENSION: &str = "json";
+
+/// Previous versions of each document kept by a new store (see Store::with_backups)
+pub const DEFAULT_BACKUPS: usize = 2;
+
+/// A type that can be stored as a versioned document
+/// Its JsonSchema describes the data, see document_schema for the whole file
+pub trait Schema: Serialize + DeserializeOwned + JsonSchema {
+    /// Schema version written alongside the data
+    /// Bump this whenever the stored shape changes, and add the step that
+    /// upgrades the previous shape to MIGRATIONS
+    const VERSION: u32;
+
+    /// Steps upgrading documents saved with earlier versions, one per version
+    /// (in any order), none by default
+    const MIGRATIONS: &'static [Migration] = &[];
+}
+
+/// On-disk wrapper that records which schema version wrote the data
+#[derive(Serialize, Deserialize, JsonSchema)]
+struct Envelope<T> {
+    version: u32,
+    /// SHA-256 of the data as written (documents saved before checksums have none)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    checksum: Option<String>,
+    data: T,
+}
+
+/// An envelope as read back, the data left as the exact text its checksum is of
+#[derive(Deserialize)]
+struct RawEnvelope<'a> {
+    version: u32,
+    #[serde(default)]
+    checksum: Option<String>,
+    #[serde(borrow)]
+    data: &'a RawValue,
+}
+
+/// The file of a document: its envelope as pretty JSON, the checksum filled in
+/// once the data's text is known
+fn encode<T: Serialize>(version: u32, data: &T) -> Result<Vec<u8>, StoreError> {
+    // The checksum comes before the data, so the placeholder found first is its
+    let placeholder = "0".repeat(CHECKSUM_LEN);
+    let envelope = Envelope {
+        version,
+        checksum: Some(placeholder.clone()),
+        data,
+    };
+    let mut json = serde_json::to_string_pretty(&envelope)?;
+    let written: RawEnvelope = serde_json::from_str(&json)?;
+    let sum = checksum(written.data.get().as_bytes());
+    let at = json.find(&placeholder).expect("the envelope has a checksum");
+    json.replace_range(at..at + CHECKSUM_LEN, &sum);
+    Ok(json.into_bytes())
+}
+
+/// JSON Schema of a stored `T` document, envelope included
+/// (the version is fixed to T::VERSION, titled and described after T)
+pub fn document_schema<T: Schema>() -> schemars::Schema {
+    let mut schema = schemars::schema_for!(Envelope<T>);
+    let description = format!("{} document saved by sandbox-store (schema version {})", T::schema_name(), T::VERSION);
+    schema.insert("title".to_string(), T::schema_name().into());
+    schema.insert("description".to_string(), description.into());
+    if let Some(version) = schema
+        .get_mut("properties")
+        .and_then(|properties| properties.get_mut("version"))
+        .and_then(|version| version.as_object_mut())
+    {
+        version.insert("const".to_string(), T::VERSION.into());
+    }
+    schema
+}
+
+/// Decode the data of a `T` document stored under `key` with schema version
+/// `version`: upgraded to T::VERSION with T::MIGRATIONS, then checked against
+/// document_schema (with the "validate" feature)
+pub fn decode_document<T: Schema>(key: &str, version: u32, data: serde_json::Value) -> Result<T, StoreError> {
+    let data = migrate(key, version, T::VERSION, T::MIGRATIONS, data)?;
+    let document = serde_json::json!({ "version": T::VERSION, "data": data });
+    validate(&document_schema::<T>(), &document).map_err(|violations| StoreError::Invalid {
+        key: key.to_string(),
+        violations,
+    })?;
+    Ok(serde_json::from_value(data)?)
+}
+
+/// A directory of keyed JSON documents
+#[derive(Debug, Clone)]
+pub struct Store {
+    /// Directory holding one file per key
+    dir: PathBuf,
+    /// How many previous versions of each document are kept
+    backups: usize,
+}
+
+impl Store {
+    /// Open (and create if needed) a store in `dir`
+    pub fn open(dir: impl AsRef<Path>) -> Result<Self, StoreError> {
+        let dir = dir.as_ref().to_path_buf();
+        fs::create_dir_all(&dir)?;
+        Ok(Store {
+            dir,
+            backups: DEFAULT_BACKUPS,
+        })
+    }
+
+    /// Keep `backups` previous versions of each document as `<key>.json.1`
+    /// (the newest) to `<key>.json.N`, 0 keeps none
+    pub fn with_backups(mut self, backups: usize) -> Self {
+        self.backups = backups;
+        self
+    }
+
+    /// Directory the store keeps its documents in
+    pub fn dir(&self) -> &Path {
+        &self.dir
+    }
+
+    /// Save `value` under `key`, replacing any existing document atomically
+    /// (the replaced one becomes the newest backup)
+    pub fn save<T: Schema>(&self, key: &str, value: &T) -> Result<(), StoreError> {
+        let path = self.path_for(key)?;
+        let json = encode(T::VERSION, value)?;
+        write_atomic_with_backups(&path, &json, self.backups)?;
+        Ok(())
+    }
+
+    /// Load the document stored under `key`
+    /// Returns `Ok(None)` if nothing has been stored yet
+    /// With the "validate" feature (the default) the file is checked against
+    /// document_schema first, and every mismatch is reported with its location
+    ///
+    /// A document saved with an earlier schema version is upgraded (see
+    /// decode_document) and saved back, the old file becoming the newest backup
+    ///
+    /// If the document is damaged (not JSON, not matching its checksum, or not
+    /// matching the schema) the newest backup that loads is returned instead,
+    /// and put back in the document's place (see restore_backup), so the next
+    /// save doesn't push it out for the damaged file. The error is only
+    /// returned when no backup loads. A missing document with backups left
+    /// (a crash while it was being replaced) is loaded from them the same way
+    pub fn load<T: Schema>(&self, key: &str) -> Result<Option<T>, StoreError> {
+        let path = self.path_for(key)?;
+        let error = match self.load_file(key, &path) {
+            Ok(Some((value, version))) => {
+                if version != T::VERSION {
+                    self.save(key, &value)?;
+                }
+                return Ok(Some(value));
+            }
+            // remove deletes the backups first, so they only outlive the
+            // document if something went wrong in between
+            Ok(None) => None,
+            Err(err @ (StoreError::Json(_) | StoreError::Checksum { .. } | StoreError::Invalid { .. })) => Some(err),
+            Err(err) => return Err(err),
+        };
+        for generation in 1..=self.backups {
+            if let Ok(Some((value, version))) = self.load_file(key, &backup_path(&path, generation)) {
+                restore_backup(&path, generation)?;
+                if version != T::VERSION {
+                    self.save(key, &value)?;
+                }
+                return Ok(Some(value));
+            }
+        }
+        error.map_or(Ok(None), Err)
+    }
+
+    /// Load the document in `path`, stored under `key`, with the schema version it was saved with
+    fn load_file<T: Schema>(&self, key: &str, path: &Path) -> Result<Option<(T, u32)>, StoreError> {
+        let bytes = match fs::read(path) {
+            Ok(bytes) => bytes,
+            Err(err) if err.kind() == io::ErrorKind::NotFound => return Ok(None),
+            Err(err) => return Err(err.into()),
+        };
+
+        // Read the version (and check the data) before trying to decode the data into T
+        let envelope: RawEnvelope = serde_json::from_slice(&bytes)?;
+        let text = envelope.data.get();
+        if envelope.checksum.is_some_and(|expected| expected != checksum(text.as_bytes())) {
+            return Err(StoreError::Checksum { key: key.to_string() });
+        }
+        let value = decode_document(key, envelope.version, serde_json::from_str(text)?)?;
+        Ok(Some((value, envelope.version)))
+    }
+
+    /// Remove the document stored under `key`, its backups, and a damaged copy
+    /// Returns true if a document was removed
+    pub fn remove(&self, key: &str) -> Result<bool, StoreError> {
+        let path = self.path_for(key)?;
+        let others = (1..=self.backups).map(|generation| backup_path(&path, generation));
+        for other in others.chain([damaged_path(&path)]) {
+            match fs::remove_file(other) {
+                Err(err) if err.kind() != io::ErrorKind::NotFound => return Err(err.into()),
+                _ => {}
+            }
+        }
+        match fs::remove_file(path) {
+            Ok(()) => Ok(true),
+            Err(err) if err.kind() == io::ErrorKind::NotFound => Ok(false),
+            Err(err) => Err(err.into()),
+        }
+    }
+
+    /// Check whether a document exists under `key`
+    pub fn contains(&self, key: &str) -> Result<bool, StoreError> {
+        Ok(self.path_for(key)?.exists())
+    }
+
+    /// List all keys in the store, sorted
+    pub fn keys(&self) -> Result<Vec<String>, StoreError> {
+        let mut keys = Vec::new();
+        for entry in fs::read_dir(&self.dir)? {
+            let path = entry?.path();
+            if path.extension().and_then(|ext| ext.to_str()) != Some(EXTENSION) {
+                continue;
+            }
+            if let Some(stem) = path.file_stem().and_then(|stem| stem.to_str()) {
+                keys.push(stem.to_string());
+            }
+        }
+        keys.sort();
+        Ok(keys)
+    }
+
+    /// File path for `key`, rejecting keys that could escape the store directory
+    fn path_for(&self, key: &str) -> Result<PathBuf, StoreError> {
+        let valid = !key.is_empty()
+            && !key.starts_with('.')
+            && key
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | '.'));
+        if !valid {
+            return Err(StoreError::InvalidKey(key.to_string()));
+        }
+        Ok(self.dir.join(format!("{}.{}", key, EXTENSION)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Debug, PartialEq, Serialize, Deserialize, JsonSchema)]
+    struct Counter {
+        count: u32,
+    }
+
+    impl Schema for Counter {
+        const VERSION: u32 = 1;
+    }
+
+    #[derive(Debug, Serialize, Deserialize, JsonSchema)]
+    struct CounterV2 {
+        count: u32,
+    }
+
+    impl Schema for CounterV2 {
+        const VERSION: u32 = 2;
+    }
+
+    /// Counter's next version, with `count` renamed
+    #[derive(Debug, PartialEq, Serialize, Deserialize, JsonSchema)]
+    struct Tally {
+        total: u32,
+    }
+
+    fn rename_count(mut data: serde_json::Value) -> Result<serde_json::Value, String> {
+        Ok(serde_json::json!({ "total": data["count"].take() }))
+    }
+
+    impl Schema for Tally {
+        const VERSION: u32 = 2;
+        const MIGRATIONS: &'static [Migration] = &[Migration::new(1, rename_count)];
+    }
+
+    fn temp_store() -> (tempfile::TempDir, Store) {
+        let dir = tempfile::tempdir().unwrap();
+        let store = Store::open(dir.path().join("store")).unwrap();
+        (dir, store)
+    }
+
+    #[test]
+    fn test_save_and_load() {
+        let (_dir, store) = temp_store();
+        store.save("counter", &Counter { count: 7 }).unwrap();
+
+        let loaded: Option<Counter> = store.load("counter").unwrap();
+        assert_eq!(loaded, Some(Counter { count: 7 }));
+    }
+
+    #[test]
+    fn test_load_missing_key() {
+        let (_dir, store) = temp_store();
+        let loaded: Option<Counter> = store.load("missing").unwrap();
+        assert!(loaded.is_none());
+    }
+
+    #[test]
+    fn test_version_mismatch() {
+        let (_dir, store) = temp_store();
+        store.save("counter", &Counter { count: 1 }).unwrap();
+
+        let result: Result<Option<CounterV2>, _> = store.load("counter");
+        assert!(matches!(
+            result,
+            Err(StoreError::VersionMismatch {
+                expected: 2,
+                found: 1,
+                ..
+            })
+        ));
+    }
+
+    #[test]
+    fn test_older_document_is_upgraded() {
+        let (_dir, store) = temp_store();
+        store.save("counter", &Counter { count: 5 }).unwrap();
+
+        let loaded: Option<Tally> = store.load("counter").unwrap();
+        assert_eq!(loaded, Some(Tally { total: 5 }));
+        // Saved back in the new shape, the old file kept as a backup
+        let mut saved: serde_json::Value = serde_json::from_slice(&fs::read(store.dir().join("counter.json")).unwrap()).unwrap();
+        assert!(saved["checksum"].is_string());
+        saved.as_object_mut().unwrap().remove("checksum");
+        assert_eq!(saved, serde_json::json!({ "version": 2, "data": { "total": 5 } }));
+        let backup = fs::read_to_string(backup_path(&store.dir().join("counter.json"), 1)).unwrap();
+        assert!(backup.contains("\"count\": 5"));
+
+        // A newer document isn't read by an older version
+        let result: Result<Option<Counter>, _> = store.load("counter");
+        assert!(matches!(result, Err(StoreError::VersionMismatch { expected: 1, found: 2, .. })));
+    }
+
+    #[test]
+    fn test_invalid_keys() {
+        let (_dir, store) = temp_store();
+        for key in ["", "../escape", "a/b", ".hidden"] {
+            let result = store.save(key, &Counter { count: 1 });
+            assert!(matches!(result, Err(StoreError::InvalidKey(_))), "{}", key);
+        }
+    }
+
+    #[test]
+    fn test_keys_and_remove() {
+        let (_dir, store) = temp_store();
+        store.save("b", &Counter { count: 1 }).unwrap();
+        store.save("a", &Counter { count: 2 }).unwrap();
+        assert_eq!(store.keys().unwrap(), ["a", "b"]);
+
+        assert!(store.remove("a").unwrap());
+        assert!(!store.remove("a").unwrap());
+        assert!(!store.contains("a").unwrap());
+        assert_eq!(store.keys().unwrap(), ["b"]);
+    }
+
+    #[test]
+    fn test_document_schema() {
+        let schema = serde_json::to_value(document_schema::<Counter>()).unwrap();
+        assert_eq!(schema["title"], "Counter");
+        assert_eq!(schema["properties"]["version"]["const"], 1);
+        assert_eq!(schema["required"], serde_json::json!(["version", "data"]));
+    }
+
+    #[cfg(feature = "validate")]
+    #[test]
+    fn test_invalid_document_reports_locations() {
+        let (_dir, store) = temp_store();
+        fs::write(store.dir().join("counter.json"), r#"{"version": 1, "data": {"count": "seven"}}"#).unwrap();
+
+        let result: Result<Option<Counter>, _> = store.load("counter");
+        match result {
+            Err(StoreError::Invalid { key, violations }) => {
+                assert_eq!(key, "counter");
+                assert_eq!(violations.len(), 1);
+                assert_eq!(violations[0].path, "/data/count");
+                assert!(violations[0].to_string().starts_with("/data/count: "));
+            }
+            other => panic!("expected a schema violation, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_corrupt_document() {
+        let (_dir, store) = temp_store();
+        fs::write(store.dir().join("counter.json"), "{not json").unwrap();
+
+        let result: Result<Option<Counter>, _> = store.load("counter");
+        assert!(matches!(result, Err(StoreError::Json(_))));
+    }
+
+    #[test]
+    fn test_corrupt_document_falls_back_to_backup() {
+        let (_dir, store) = temp_store();
+        store.save("counter", &Counter { count: 1 }).unwrap();
+        store.save("counter", &Counter { count: 2 }).unwrap();
+        fs::write(store.dir().join("counter.json"), "{not json").unwrap();
+
+        let loaded: Option<Counter> = store.load("counter").unwrap();
+        assert_eq!(loaded, Some(Counter { count: 1 }));
+
+        // Backups (and the damaged copy) are left alone by keys and go with their document
+        assert_eq!(store.keys().unwrap(), ["counter"]);
+        assert!(store.remove("counter").unwrap());
+        assert!(fs::read_dir(store.dir()).unwrap().next().is_none());
+    }
+
+    #[test]
+    fn test_damaged_document_fails_its_checksum() {
+        let (_dir, store) = temp_store();
+        store.save("counter", &Counter { count: 1 }).unwrap();
+        store.save("counter", &Counter { count: 2 }).unwrap();
+        let path = store.dir().join("counter.json");
+        let saved = fs::read_to_string(&path).unwrap();
+        assert!(saved.contains("\"checksum\": \""));
+
+        // Still JSON matching the schema, but not what was saved
+        fs::write(&path, saved.replace("\"count\": 2", "\"count\": 3")).unwrap();
+        let loaded: Option<Counter> = store.load("counter").unwrap();
+        assert_eq!(loaded, Some(Counter { count: 1 }));
+        fs::write(&path, saved.replace("\"count\": 2", "\"count\": 3")).unwrap();
+        let store = store.with_backups(0);
+        let result: Result<Option<Counter>, _> = store.load("counter");
+        assert!(matches!(result, Err(StoreError::Checksum { key }) if key == "counter"));
+
+        // Documents saved before checksums load as they are
+        fs::write(&path, r#"{"version": 1, "data": {"count": 4}}"#).unwrap();
+        let loaded: Option<Counter> = store.load("counter").unwrap();
+        assert_eq!(loaded, Some(Counter { count: 4 }));
+    }
+
+    #[test]
+    fn test_restored_backup_survives_another_damage() {
+        let (_dir, store) = temp_store();
+        let store = store.with_backups(1);
+        let path = store.dir().join("counter.json");
+        store.save("counter", &Counter { count: 1 }).unwrap();
+        store.save("counter", &Counter { count: 2 }).unwrap();
+
+        // Damaged, loaded from the backup, saved again, and damaged again
+        fs::write(&path, "{not json").unwrap();
+        let loaded: Option<Counter> = store.load("counter").unwrap();
+        assert_eq!(loaded, Some(Counter { count: 1 }));
+        assert_eq!(fs::read_to_string(damaged_path(&path)).unwrap(), "{not json");
+        store.save("counter", &Counter { count: 3 }).unwrap();
+        fs::write(&path, "{not json either").unwrap();
+
+        let loaded: Option<Counter> = store.load("counter").unwrap();
+        assert_eq!(loaded, Some(Counter { count: 1 }));
+    }
+
+    #[test]
+    fn test_missing_document_loads_from_its_backup() {
+        let (_dir, store) = temp_store();
+        let path = store.dir().join("counter.json");
+        store.save("counter", &Counter { count: 1 }).unwrap();
+        store.save("counter", &Counter { count: 2 }).unwrap();
+
+        // Only the backups left, as after a crash while the document was replaced
+        fs::remove_file(&path).unwrap();
+        let loaded: Option<Counter> = store.load("counter").unwrap();
+        assert_eq!(loaded, Some(Counter { count: 1 }));
+        assert!(path.exists());
+
+        store.remove("counter").unwrap();
+        let loaded: Option<Counter> = store.load("counter").unwrap();
+        assert_eq!(loaded, None);
+    }
+
+    #[test]
+    fn test_without_backups() {
+        let (_dir, store) = temp_store();
+        let store = store.with_backups(0);
+        store.save("counter", &Counter { count: 1 }).unwrap();
+        store.save("counter", &Counter { count: 2 }).unwrap();
+        assert_eq!(fs::read_dir(store.dir()).unwrap().count(), 1);
+    }
+}
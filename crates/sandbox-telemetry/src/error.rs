@@ -0,0 +1,37 @@
+use std::fmt;
+use std::io;
+use std::path::PathBuf;
+
+/// Errors returned while setting up logging
+#[derive(Debug)]
+pub enum TelemetryError {
+    /// The log directory could not be created or the log file opened
+    Io { path: PathBuf, message: String },
+    /// `RUST_LOG` or the configured level is not a valid filter
+    InvalidFilter(String),
+    /// Logging was already set up in this process
+    AlreadyInitialized,
+}
+
+impl fmt::Display for TelemetryError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            TelemetryError::Io { path, message } => {
+                write!(f, "could not open log directory {}: {}", path.display(), message)
+            }
+            TelemetryError::InvalidFilter(message) => write!(f, "invalid log filter: {}", message),
+            TelemetryError::AlreadyInitialized => write!(f, "logging is already initialized"),
+        }
+    }
+}
+
+impl std::error::Error for TelemetryError {}
+
+impl TelemetryError {
+    pub(crate) fn io(path: PathBuf, err: io::Error) -> Self {
+        TelemetryError::Io {
+            path,
+            message: err.to_string(),
+        }
+    }
+}
@@ -0,0 +1,28 @@
+//! Logging setup shared by the sandbox apps
+//!
+//! One call wires up `tracing` with two outputs:
+//!
+//! - the console (stderr), warnings and errors only so it doesn't drown out
+//!   the app's own output
+//! - a daily-rotating log file `<log_dir>/<app>.<date>.log`, at `info` level
+//!   unless `RUST_LOG` says otherwise
+//!
+//! It also installs a panic hook, so a crash is written to the log file
+//! before the default panic message is printed.
+//!
+//! ```no_run
+//! use sandbox_telemetry::Telemetry;
+//!
+//! // Keep the guard alive until the app exits, dropping it flushes the log file
+//! let _telemetry = Telemetry::new("pow-sim").log_dir("logs").init().unwrap();
+//! tracing::warn!("something odd happened");
+//! ```
+
+// Module declarations
+pub mod error;
+pub mod panic;
+pub mod telemetry;
+
+// Re-exports for convenience
+pub use error::TelemetryError;
+pub use telemetry::{Telemetry, TelemetryGuard};
@@ -0,0 +1,24 @@
+use std::panic;
+
+/// Log panics through `tracing` before running the previous panic hook
+/// (normally the default one that prints to stderr)
+pub fn install_panic_hook() {
+    let previous = panic::take_hook();
+    panic::set_hook(Box::new(move |info| {
+        let thread = std::thread::current();
+        let thread = thread.name().unwrap_or("<unnamed>");
+        let location = info
+            .location()
+            .map(|location| location.to_string())
+            .unwrap_or_else(|| "unknown location".to_string());
+        let message = info
+            .payload()
+            .downcast_ref::<&str>()
+            .map(|message| message.to_string())
+            .or_else(|| info.payload().downcast_ref::<String>().cloned())
+            .unwrap_or_else(|| "<non-string panic payload>".to_string());
+
+        tracing::error!(target: "panic", thread, location, "panicked: {}", message);
+        previous(info);
+    }));
+}
@@ -0,0 +1,152 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+use tracing::level_filters::LevelFilter;
+use tracing_appender::non_blocking::WorkerGuard;
+use tracing_appender::rolling::{RollingFileAppender, Rotation};
+use tracing_subscriber::layer::SubscriberExt;
+use tracing_subscriber::util::SubscriberInitExt;
+use tracing_subscriber::{EnvFilter, Layer};
+use crate::error::TelemetryError;
+use crate::panic::install_panic_hook;
+
+/// Rotated log files kept before the oldest is deleted (one per day)
+pub const MAX_LOG_FILES: usize = 7;
+
+/// Settings for setting up logging, finished with `init()`
+pub struct Telemetry {
+    /// Used as the log file name prefix
+    app: String,
+    /// Directory the log files go in
+    log_dir: PathBuf,
+    /// File log filter used when `RUST_LOG` isn't set
+    default_filter: String,
+}
+
+/// Keeps the background log writer running, dropping it flushes the log file
+pub struct TelemetryGuard {
+    /// Directory the log files are written to
+    pub log_dir: PathBuf,
+    _writer: WorkerGuard,
+}
+
+impl Telemetry {
+    /// Log as `app` into "./logs" at `info` level
+    pub fn new(app: &str) -> Self {
+        Telemetry {
+            app: app.to_string(),
+            log_dir: PathBuf::from("logs"),
+            default_filter: "info".to_string(),
+        }
+    }
+
+    /// Write log files into `dir` (created if missing)
+    pub fn log_dir(mut self, dir: impl AsRef<Path>) -> Self {
+        self.log_dir = dir.as_ref().to_path_buf();
+        self
+    }
+
+    /// File log filter when `RUST_LOG` isn't set, e.g. "debug" or "warn,my_app=trace"
+    pub fn default_filter(mut self, filter: &str) -> Self {
+        self.default_filter = filter.to_string();
+        self
+    }
+
+    /// Install the console and file loggers and the panic hook
+    /// Can only succeed once per process
+    pub fn init(self) -> Result<TelemetryGuard, TelemetryError> {
+        let filter_spec = std::env::var("RUST_LOG").unwrap_or_else(|_| self.default_filter.clone());
+        let filter = EnvFilter::try_new(&filter_spec)
+            .map_err(|err| TelemetryError::InvalidFilter(err.to_string()))?;
+
+        let appender = self.file_appender()?;
+        let (writer, guard) = tracing_appender::non_blocking(appender);
+
+        let file_layer = tracing_subscriber::fmt::layer()
+            .with_writer(writer)
+            .with_ansi(false)
+            .with_filter(filter);
+        let console_layer = tracing_subscriber::fmt::layer()
+            .with_writer(std::io::stderr)
+            .with_target(false)
+            .with_filter(LevelFilter::WARN);
+
+        tracing_subscriber::registry()
+            .with(file_layer)
+            .with(console_layer)
+            .try_init()
+            .map_err(|_| TelemetryError::AlreadyInitialized)?;
+        install_panic_hook();
+
+        tracing::info!(app = %self.app, log_dir = %self.log_dir.display(), "logging started");
+        Ok(TelemetryGuard {
+            log_dir: self.log_dir,
+            _writer: guard,
+        })
+    }
+
+    /// Daily-rotating "<app>.<date>.log" files in the log directory
+    fn file_appender(&self) -> Result<RollingFileAppender, TelemetryError> {
+        fs::create_dir_all(&self.log_dir).map_err(|err| TelemetryError::io(self.log_dir.clone(), err))?;
+        RollingFileAppender::builder()
+            .rotation(Rotation::DAILY)
+            .filename_prefix(&self.app)
+            .filename_suffix("log")
+            .max_log_files(MAX_LOG_FILES)
+            .build(&self.log_dir)
+            .map_err(|err| TelemetryError::Io {
+                path: self.log_dir.clone(),
+                message: err.to_string(),
+            })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn log_contents(dir: &Path) -> String {
+        fs::read_dir(dir)
+            .unwrap()
+            .map(|entry| fs::read_to_string(entry.unwrap().path()).unwrap())
+            .collect()
+    }
+
+    #[test]
+    fn test_file_appender_creates_log_dir() {
+        let dir = tempfile::tempdir().unwrap();
+        let log_dir = dir.path().join("nested").join("logs");
+        Telemetry::new("test").log_dir(&log_dir).file_appender().unwrap();
+        assert!(log_dir.is_dir());
+    }
+
+    #[test]
+    fn test_unusable_log_dir() {
+        let dir = tempfile::tempdir().unwrap();
+        let file = dir.path().join("not-a-dir");
+        fs::write(&file, "").unwrap();
+        let result = Telemetry::new("test").log_dir(&file).file_appender();
+        assert!(matches!(result, Err(TelemetryError::Io { .. })));
+    }
+
+    // Logging is global to the process, so everything that needs a live
+    // subscriber is checked in this one test
+    #[test]
+    fn test_init_logs_to_file_and_captures_panics() {
+        let dir = tempfile::tempdir().unwrap();
+        let guard = Telemetry::new("test").log_dir(dir.path()).init().unwrap();
+
+        tracing::warn!("disk almost full");
+        let _ = std::panic::catch_unwind(|| panic!("boom"));
+
+        assert!(matches!(
+            Telemetry::new("test").log_dir(dir.path()).init(),
+            Err(TelemetryError::AlreadyInitialized)
+        ));
+
+        drop(guard); // Flush the background writer
+        let logs = log_contents(dir.path());
+        assert!(logs.contains("logging started"));
+        assert!(logs.contains("disk almost full"));
+        assert!(logs.contains("panicked: boom"));
+    }
+}
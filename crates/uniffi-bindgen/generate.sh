@@ -0,0 +1,26 @@
+#!/usr/bin/env sh
+# Generate Kotlin and Swift bindings for hangman-core and proof-of-work-sim
+# Output: hangman/core/bindings/ and proof-of-work-sim/bindings/ (not committed)
+# Run from anywhere inside the repository.
+set -e
+
+cd "$(git rev-parse --show-toplevel)"
+
+case "$(uname)" in
+    Darwin) ext=dylib ;;
+    *) ext=so ;;
+esac
+
+cargo build -p hangman-core -p proof-of-work-sim \
+    --features hangman-core/uniffi,proof-of-work-sim/uniffi --lib
+
+for crate in hangman_core:hangman/core proof_of_work_sim:proof-of-work-sim; do
+    lib="${crate%%:*}"
+    dir="${crate#*:}"
+    for language in kotlin swift; do
+        cargo run -q -p uniffi-bindgen -- generate \
+            --library "target/debug/lib$lib.$ext" \
+            --language "$language" \
+            --out-dir "$dir/bindings/$language"
+    done
+done
@@ -0,0 +1,14 @@
+//! Kotlin/Swift binding generator for the sandbox's uniffi crates
+//!
+//! Pinned to the same uniffi version as `hangman-core` and `proof-of-work-sim`,
+//! so the generated code matches their scaffolding. `generate.sh` next to this
+//! crate builds both libraries and runs it for Kotlin and Swift, or by hand:
+//!
+//! ```bash
+//! cargo run -p uniffi-bindgen -- generate --library target/debug/libhangman_core.so \
+//!     --language kotlin --out-dir hangman/core/bindings/kotlin
+//! ```
+
+fn main() {
+    uniffi::uniffi_bindgen_main()
+}
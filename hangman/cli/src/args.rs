@@ -0,0 +1,192 @@
+// ============================================================================
+// COMMAND-LINE ARGUMENTS
+// ============================================================================
+// Hand-rolled parsing for the handful of flags the CLI supports.
+
+use hangman_core::Difficulty;
+use std::path::PathBuf;
+
+pub const USAGE: &str = "\
+Usage: hangman-cli [OPTIONS]
+
+Options:
+  -d, --difficulty <LEVEL>  easy, medium (default), or hard
+  -c, --category <NAME>     Pick the word from a category
+  -w, --words <PATH>        Load words from a .txt or .json word list
+      --evil                Evil mode: the word keeps changing to dodge your guesses
+      --practice            Practice: wrong guesses are counted but never lose the game
+      --list-categories     Print the available categories and exit
+      --daily               Play today's word (the same for everyone on the same list)
+      --seed <N>            Seed the word and hint picks, to replay a game
+      --chain <DIR>         Record the game on the pow-sim chain saved in DIR
+                            (the --data-dir of a running proof-of-work-sim)
+      --player <NAME>       Name the game is recorded under (default: player)
+      --results             Print the games recorded on --chain and exit
+      --schemas <DIR>       Write the JSON Schemas of the game's files to DIR and exit
+      --search <MASK>       Print the words that fit MASK (e.g. \"_ A N G M A N\", _ for a
+                            hidden letter) and exit
+      --exclude <LETTERS>   Letters the --search words don't have
+  -h, --help                Print this help and exit";
+
+/// Parsed command-line options
+#[derive(Debug, Default, PartialEq)]
+pub struct Args {
+    pub difficulty: Difficulty,
+    pub category: Option<String>,
+    pub words: Option<PathBuf>,
+    pub evil: bool,
+    pub practice: bool,
+    pub list_categories: bool,
+    pub daily: bool,
+    pub seed: Option<u64>,
+    pub chain: Option<PathBuf>,
+    pub player: Option<String>,
+    pub results: bool,
+    pub schemas: Option<PathBuf>,
+    pub search: Option<String>,
+    pub exclude: Option<String>,
+    pub help: bool,
+}
+
+impl Args {
+    /// Parse arguments (without the program name)
+    pub fn parse<I>(args: I) -> Result<Self, String>
+    where
+        I: IntoIterator<Item = String>,
+    {
+        let mut parsed = Args::default();
+        let mut args = args.into_iter();
+
+        while let Some(arg) = args.next() {
+            match arg.as_str() {
+                "-d" | "--difficulty" => {
+                    let value = value_for(&arg, args.next())?;
+                    parsed.difficulty = parse_difficulty(&value)?;
+                }
+                "-c" | "--category" => parsed.category = Some(value_for(&arg, args.next())?),
+                "-w" | "--words" => parsed.words = Some(PathBuf::from(value_for(&arg, args.next())?)),
+                "--evil" => parsed.evil = true,
+                "--practice" => parsed.practice = true,
+                "--list-categories" => parsed.list_categories = true,
+                "--daily" => parsed.daily = true,
+                "--seed" => {
+                    let value = value_for(&arg, args.next())?;
+                    let seed = value.parse().map_err(|_| format!("invalid seed: {} (expected a number)", value))?;
+                    parsed.seed = Some(seed);
+                }
+                "--chain" => parsed.chain = Some(PathBuf::from(value_for(&arg, args.next())?)),
+                "--player" => parsed.player = Some(value_for(&arg, args.next())?),
+                "--results" => parsed.results = true,
+                "--schemas" => parsed.schemas = Some(PathBuf::from(value_for(&arg, args.next())?)),
+                "--search" => parsed.search = Some(value_for(&arg, args.next())?),
+                "--exclude" => parsed.exclude = Some(value_for(&arg, args.next())?),
+                "-h" | "--help" => parsed.help = true,
+                other => return Err(format!("unknown argument: {}", other)),
+            }
+        }
+
+        if parsed.daily && parsed.seed.is_some() {
+            return Err("--daily and --seed can't be used together".to_string());
+        }
+        if parsed.daily && parsed.evil {
+            // Everyone gets the same daily word, an evil game would change it
+            return Err("--daily and --evil can't be used together".to_string());
+        }
+        if parsed.evil && parsed.chain.is_some() {
+            // The chain commits to the word before the first guess, evil games don't have one
+            return Err("--evil can't be used with --chain".to_string());
+        }
+        if parsed.practice && parsed.chain.is_some() {
+            // A game that can't be lost isn't a result to record
+            return Err("--practice can't be used with --chain".to_string());
+        }
+        if parsed.results && parsed.chain.is_none() {
+            return Err("--results needs --chain".to_string());
+        }
+        if parsed.exclude.is_some() && parsed.search.is_none() {
+            return Err("--exclude needs --search".to_string());
+        }
+        Ok(parsed)
+    }
+}
+
+/// The value following a flag, or an error if it's missing
+fn value_for(flag: &str, value: Option<String>) -> Result<String, String> {
+    value.ok_or_else(|| format!("{} needs a value", flag))
+}
+
+fn parse_difficulty(value: &str) -> Result<Difficulty, String> {
+    match value.to_lowercase().as_str() {
+        "easy" => Ok(Difficulty::Easy),
+        "medium" => Ok(Difficulty::Medium),
+        "hard" => Ok(Difficulty::Hard),
+        _ => Err(format!("unknown difficulty: {} (expected easy, medium, or hard)", value)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(args: &[&str]) -> Result<Args, String> {
+        Args::parse(args.iter().map(|a| a.to_string()))
+    }
+
+    #[test]
+    fn test_defaults() {
+        assert_eq!(parse(&[]).unwrap(), Args::default());
+    }
+
+    #[test]
+    fn test_all_options() {
+        let args = parse(&["-d", "HARD", "--category", "animals", "-w", "words.txt"]).unwrap();
+        assert_eq!(args.difficulty, Difficulty::Hard);
+        assert_eq!(args.category.as_deref(), Some("animals"));
+        assert_eq!(args.words, Some(PathBuf::from("words.txt")));
+        assert!(parse(&["--evil"]).unwrap().evil);
+        assert!(parse(&["--evil", "--chain", "pow-data"]).is_err());
+        assert!(parse(&["--practice"]).unwrap().practice);
+        assert!(parse(&["--practice", "--chain", "pow-data"]).is_err());
+    }
+
+    #[test]
+    fn test_chain_options() {
+        let args = parse(&["--chain", "pow-data", "--player", "ada", "--results"]).unwrap();
+        assert_eq!(args.chain, Some(PathBuf::from("pow-data")));
+        assert_eq!(args.player.as_deref(), Some("ada"));
+        assert!(args.results);
+        assert!(parse(&["--results"]).is_err());
+    }
+
+    #[test]
+    fn test_seed_options() {
+        assert_eq!(parse(&["--seed", "42"]).unwrap().seed, Some(42));
+        assert!(parse(&["--daily"]).unwrap().daily);
+        assert!(parse(&["--seed", "soon"]).is_err());
+        assert!(parse(&["--daily", "--seed", "1"]).is_err());
+        assert!(parse(&["--daily", "--evil"]).is_err());
+    }
+
+    #[test]
+    fn test_flags() {
+        let args = parse(&["--list-categories", "--help", "--schemas", "out"]).unwrap();
+        assert!(args.list_categories);
+        assert!(args.help);
+        assert_eq!(args.schemas, Some(PathBuf::from("out")));
+    }
+
+    #[test]
+    fn test_search_options() {
+        let args = parse(&["--search", "_ A N G M A N", "--exclude", "ST"]).unwrap();
+        assert_eq!(args.search.as_deref(), Some("_ A N G M A N"));
+        assert_eq!(args.exclude.as_deref(), Some("ST"));
+        assert!(parse(&["--exclude", "ST"]).is_err());
+    }
+
+    #[test]
+    fn test_errors() {
+        assert!(parse(&["--difficulty"]).is_err());
+        assert!(parse(&["--difficulty", "impossible"]).is_err());
+        assert!(parse(&["--bogus"]).is_err());
+    }
+}
@@ -0,0 +1,112 @@
+// ============================================================================
+// RECORDING GAMES ON A POW-SIM CHAIN
+// ============================================================================
+// With --chain DIR the game is committed to when it starts and revealed when
+// it ends (see onchain.rs in hangman-core). Both transactions go into the
+// inbox of the proof-of-work-sim node saving its chain in DIR, which mines
+// them into its next block. If no node is running they wait in the inbox.
+
+use hangman_core::onchain::{self, Commitment};
+use hangman_core::GameState;
+use proof_of_work_sim::blockchain::Blockchain;
+use proof_of_work_sim::config::Config;
+use proof_of_work_sim::inbox;
+use sandbox_store::{Store, StoreError};
+use std::io::{self, Write};
+use std::path::Path;
+
+/// Sender name used when --player isn't given
+pub const DEFAULT_PLAYER: &str = "player";
+
+/// A game being recorded on the chain
+pub struct ChainRecorder {
+    store: Store,
+    player: String,
+    commitment: Commitment,
+}
+
+impl ChainRecorder {
+    /// Commit to the game's word
+    pub fn start(dir: &Path, player: &str, game: &GameState) -> Result<Self, StoreError> {
+        let store = Store::open(dir)?;
+        let commitment = Commitment::new(&game.word);
+        inbox::submit(&store, &commitment.commit_transaction(player))?;
+        Ok(ChainRecorder {
+            store,
+            player: player.to_string(),
+            commitment,
+        })
+    }
+
+    pub fn commitment_hash(&self) -> &str {
+        &self.commitment.hash
+    }
+
+    /// Reveal the word and the result (only once the game is over)
+    pub fn finish(&self, game: &GameState) -> Result<(), StoreError> {
+        inbox::submit(&self.store, &self.commitment.reveal_transaction(&self.player, game))?;
+        Ok(())
+    }
+}
+
+/// Print every game recorded on the chain in `dir`
+pub fn print_results<W: Write>(dir: &Path, output: &mut W) -> io::Result<()> {
+    let chain = Store::open(dir)
+        .and_then(|store| Blockchain::load(&store, Config::default()))
+        .map_err(|err| io::Error::other(err.to_string()))?;
+
+    let records = onchain::records(&chain);
+    if records.is_empty() {
+        writeln!(output, "No games recorded on the chain in {} yet", dir.display())?;
+    }
+    for record in records {
+        writeln!(
+            output,
+            "#{:<5} {:<12} {:<4} {:>5} pts  {}  {}",
+            record.block_index,
+            record.player,
+            if record.won { "won" } else { "lost" },
+            record.score,
+            record.word,
+            if record.verified { "✓ verified" } else { "✗ does not match its commit" },
+        )?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use hangman_core::Difficulty;
+    use proof_of_work_sim::node::Node;
+
+    #[test]
+    fn test_record_and_print_results() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut game = GameState::new("RUST".to_string(), None, Difficulty::Easy);
+        let recorder = ChainRecorder::start(dir.path(), "ada", &game).unwrap();
+        game.guess_word("rust");
+        recorder.finish(&game).unwrap();
+
+        // Stand in for the running node: pick up the inbox and mine a block
+        let mut node = Node::with_store(Config::default(), Store::open(dir.path()).unwrap()).unwrap();
+        node.collect_inbox();
+        while !node.blockchain.try_mine_block(&node.id) {}
+        node.blockchain.save(node.store.as_ref().unwrap()).unwrap();
+
+        let mut output = Vec::new();
+        print_results(dir.path(), &mut output).unwrap();
+        let output = String::from_utf8(output).unwrap();
+        assert!(output.contains("ada"));
+        assert!(output.contains("RUST"));
+        assert!(output.contains("✓ verified"));
+    }
+
+    #[test]
+    fn test_no_results() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut output = Vec::new();
+        print_results(dir.path(), &mut output).unwrap();
+        assert!(String::from_utf8(output).unwrap().starts_with("No games recorded"));
+    }
+}
@@ -0,0 +1,170 @@
+// Terminal version of the hangman game, using the same rules as the desktop app
+
+// Module declarations
+mod args;
+mod chain;
+mod play;
+
+use args::{Args, USAGE};
+use chain::{ChainRecorder, DEFAULT_PLAYER};
+use hangman_core::{schemas, Categories, GameMode, GameState, Pattern, Settings, WordLengths, WordList};
+use sandbox_config::ConfigLoader;
+use sandbox_rng::RngHandle;
+use sandbox_store::export_schemas;
+use std::io;
+use std::process;
+use std::time::SystemTime;
+
+fn main() {
+    let args = match Args::parse(std::env::args().skip(1)) {
+        Ok(args) => args,
+        Err(err) => {
+            eprintln!("error: {}\n\n{}", err, USAGE);
+            process::exit(2);
+        }
+    };
+
+    if args.help {
+        println!("{}", USAGE);
+        return;
+    }
+
+    if let Some(dir) = &args.schemas {
+        match export_schemas(dir, &schemas()) {
+            Ok(paths) => paths.iter().for_each(|path| println!("{}", path.display())),
+            Err(err) => {
+                eprintln!("error: {}: {}", dir.display(), err);
+                process::exit(1);
+            }
+        }
+        return;
+    }
+
+    if let (true, Some(dir)) = (args.results, &args.chain) {
+        if let Err(err) = chain::print_results(dir, &mut io::stdout()) {
+            eprintln!("error: {}: {}", dir.display(), err);
+            process::exit(1);
+        }
+        return;
+    }
+
+    let categories = Categories::builtin();
+    if args.list_categories {
+        for name in categories.names() {
+            println!("{}", name);
+        }
+        return;
+    }
+
+    // Game settings from ./hangman.toml and HANGMAN_* env vars (same as the desktop app)
+    let settings: Settings = match ConfigLoader::new("hangman").load() {
+        Ok(settings) => settings,
+        Err(err) => {
+            eprintln!("error: {}", err);
+            process::exit(2);
+        }
+    };
+
+    // Pick the word pool: a category, a word list file, the kids' words in kids mode,
+    // or the built-in list (in the settings' language)
+    let words = match (&args.category, &args.words) {
+        (Some(name), _) => match categories.get(name) {
+            Some(words) => words.clone(),
+            None => {
+                eprintln!("error: unknown category: {} (try --list-categories)", name);
+                process::exit(2);
+            }
+        },
+        (None, Some(path)) => match WordList::from_file(path) {
+            Ok(words) => words,
+            Err(err) => {
+                eprintln!("error: {}: {}", path.display(), err);
+                process::exit(1);
+            }
+        },
+        (None, None) if settings.kids_mode => WordList::kids(),
+        (None, None) => WordList::load_language(None, settings.language.as_deref(), None),
+    };
+    // Without the blocked words (see hangman-core's blocklist.rs), with the settings'
+    // word lengths (the daily word is everyone's, whatever its length, and a search looks at every word)
+    let lengths = if args.daily || args.search.is_some() { WordLengths::default() } else { settings.word_lengths() };
+    let words = match words.playable(&settings.blocklist(), lengths) {
+        Ok(words) => words,
+        Err(err) => {
+            eprintln!("error: {}", err);
+            process::exit(1);
+        }
+    };
+
+    // The words that fit a mask (see hangman-core's pattern.rs), with the settings' accents and digraphs,
+    // and the list's digits and symbols hidden like letters
+    if let Some(mask) = &args.search {
+        let excluded = args.exclude.as_deref().unwrap_or("");
+        match Pattern::parse(mask, excluded, settings.ignore_accents, &settings.digraph_units()) {
+            Ok(pattern) => pattern.with_charset(words.charset().clone()).search(&words).into_iter().for_each(|word| println!("{}", word)),
+            Err(err) => {
+                eprintln!("error: {}", err);
+                process::exit(2);
+            }
+        }
+        return;
+    }
+
+    // Without --daily or --seed, SANDBOX_SEED (if set) makes the game repeatable
+    let category = args.category.map(|name| name.trim().to_lowercase());
+    let mut game = if args.daily {
+        // Today's word, the same one the desktop app's daily challenge has
+        GameState {
+            category,
+            ..GameState::new_daily(&words, args.difficulty, SystemTime::now())
+        }
+    } else {
+        let mode = if args.evil { GameMode::Evil } else { GameMode::Classic };
+        match args.seed {
+            Some(seed) => GameState::new_seeded(&words, category, args.difficulty, mode, seed),
+            None => GameState::new_random(&words, category, args.difficulty, mode, RngHandle::from_env()),
+        }
+    };
+
+    game.alphabet = words.alphabet_for(settings.language.as_deref());
+    settings.apply(&mut game);
+    if args.practice {
+        game.start_practice();
+    }
+
+    // Commit to the word on the pow-sim chain before the first guess
+    let recorder = args.chain.as_deref().map(|dir| {
+        let player = args.player.as_deref().unwrap_or(DEFAULT_PLAYER);
+        match ChainRecorder::start(dir, player, &game) {
+            Ok(recorder) => {
+                println!("🔒 Word committed to the chain: {}", &recorder.commitment_hash()[..16]);
+                recorder
+            }
+            Err(err) => {
+                eprintln!("error: {}: {}", dir.display(), err);
+                process::exit(1);
+            }
+        }
+    });
+
+    println!("🎮 Hangman (type \"hint\" for a hint, \"quit\" to give up)");
+    if game.practice {
+        println!("🎓 Practice game: wrong guesses are counted but don't end it");
+    }
+    let stdin = io::stdin();
+    let game = match play::play(game, stdin.lock(), &mut io::stdout()) {
+        Ok(game) => game,
+        Err(err) => {
+            eprintln!("error: {}", err);
+            process::exit(1);
+        }
+    };
+
+    // Games that were given up on aren't revealed (there's no result to record)
+    if let (Some(recorder), true) = (recorder, game.game_over) {
+        match recorder.finish(&game) {
+            Ok(()) => println!("📜 Result sent to the chain, see it with --results once it's mined"),
+            Err(err) => eprintln!("warning: failed to record the result: {}", err),
+        }
+    }
+}
@@ -0,0 +1,214 @@
+// ============================================================================
+// TERMINAL GAME LOOP
+// ============================================================================
+// Plays one game over any input/output pair, so tests can drive it with
+// in-memory buffers instead of a real terminal.
+
+use hangman_core::digraphs::{self, spell};
+use hangman_core::letters::normalize_word;
+use hangman_core::{GameState, GuessError, WRONG_WORD_PENALTY};
+use std::io::{self, BufRead, Write};
+
+/// Play `game` to the end (or until input runs out / the player quits)
+/// Returns the final game state
+pub fn play<R: BufRead, W: Write>(mut game: GameState, input: R, output: &mut W) -> io::Result<GameState> {
+    let mut lines = input.lines();
+
+    loop {
+        print_state(&game, output)?;
+
+        if game.game_over {
+            if game.won {
+                writeln!(output, "🎉 YOU WON! 🎉")?;
+            } else if game.timed_out {
+                writeln!(output, "⏰ TIME'S UP! The word was: {}", game.letter_case.apply_str(&game.word))?;
+            } else {
+                writeln!(output, "😢 YOU LOST! The word was: {}", game.letter_case.apply_str(&game.word))?;
+            }
+            if let Some(score) = game.score {
+                writeln!(output, "Score: {}", score.total)?;
+            }
+            if let Some(share) = game.share_text() {
+                writeln!(output, "\n{}", share)?;
+            }
+            return Ok(game);
+        }
+
+        write!(output, "Guess a letter, the whole word, or \"hint\": ")?;
+        output.flush()?;
+
+        let line = match lines.next() {
+            Some(line) => line?,
+            None => return Ok(game), // Input closed
+        };
+        let guess = line.trim();
+
+        if guess.eq_ignore_ascii_case("quit") || guess.eq_ignore_ascii_case("exit") {
+            writeln!(output, "The word was: {}", game.letter_case.apply_str(&game.word))?;
+            return Ok(game);
+        }
+
+        if guess.eq_ignore_ascii_case("hint") {
+            match game.use_hint() {
+                Ok(letter) => writeln!(output, "💡 Hint: {} (costs one wrong guess)", spell(letter))?,
+                Err(err) => writeln!(output, "No hint: {}.", err)?,
+            }
+            continue;
+        }
+
+        // More than one letter is a guess at the whole word
+        // ("e" typed with a combining accent is still one letter, a digraph too)
+        let word = normalize_word(guess);
+        if word.chars().count() > 1 && digraphs::find(&game.digraphs, guess).is_none() {
+            if game.has_guessed_word(&word) {
+                writeln!(output, "You already guessed {}.", word)?;
+            } else if !game.guess_word(guess) && !game.game_over {
+                writeln!(output, "❌ Not the word! That cost {} wrong guesses.", WRONG_WORD_PENALTY)?;
+            }
+            continue;
+        }
+
+        match game.check_letter(guess) {
+            Ok(letter) => {
+                game.guess_letter(letter);
+            }
+            Err(GuessError::AlreadyGuessed(letter)) => writeln!(output, "You already guessed {}.", spell(letter))?,
+            Err(err) => writeln!(output, "Please enter a letter ({}).", err)?,
+        }
+    }
+}
+
+/// Print the word mask, wrong-guess counter, guessed letters, and hints left
+fn print_state<W: Write>(game: &GameState, output: &mut W) -> io::Result<()> {
+    let guessed: String = game.guessed_letters.iter().map(|&c| game.letter_case.apply_str(&spell(c))).collect();
+    writeln!(output)?;
+    if let Some(clue) = &game.clue {
+        writeln!(output, "  Clue: {}", clue)?;
+    }
+    writeln!(output, "  {}", game.get_display_word())?;
+    writeln!(
+        output,
+        "  Wrong guesses: {} / {}   Guessed: {}   Hints left: {}",
+        game.wrong_guesses,
+        game.max_wrong_guesses,
+        guessed,
+        game.max_hints - game.hints_used
+    )?;
+    if let (Some(remaining), false) = (game.remaining_ms(), game.game_over) {
+        writeln!(output, "  Time left: {}s", remaining.div_ceil(1000))?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use hangman_core::Difficulty;
+
+    fn run(word: &str, input: &str) -> (GameState, String) {
+        let game = GameState::new(word.to_string(), None, Difficulty::Medium);
+        let mut output = Vec::new();
+        let game = play(game, input.as_bytes(), &mut output).unwrap();
+        (game, String::from_utf8(output).unwrap())
+    }
+
+    #[test]
+    fn test_win() {
+        let (game, output) = run("RUST", "r\nu\ns\nt\n");
+        assert!(game.won);
+        assert!(output.contains("YOU WON"));
+        assert!(output.contains(&format!("Score: {}", game.score.unwrap().total)));
+        // Then the result to share, a square per letter
+        assert!(output.trim_end().ends_with("🟩🟩🟩🟩"));
+    }
+
+    #[test]
+    fn test_lose() {
+        let (game, output) = run("RUST", "a\nb\nc\nd\ne\nf\n");
+        assert!(game.game_over);
+        assert!(!game.won);
+        assert!(output.contains("The word was: RUST"));
+    }
+
+    #[test]
+    fn test_word_guess() {
+        let (game, output) = run("RUST", "ruby\nruby\nrust\n");
+        assert!(game.won);
+        assert_eq!(game.wrong_guesses, WRONG_WORD_PENALTY);
+        assert!(output.contains("Not the word!"));
+        assert!(output.contains("You already guessed RUBY."));
+        assert!(output.contains("YOU WON"));
+    }
+
+    #[test]
+    fn test_invalid_and_repeat_input() {
+        let (game, output) = run("RUST", "1\n\n?\nr\nr\n");
+        assert!(output.contains("Please enter a letter ('1' is a digit)."));
+        assert!(output.contains("Please enter a letter (nothing was entered)."));
+        assert!(output.contains("Please enter a letter ('?' isn't a letter)."));
+        assert!(output.contains("You already guessed R."));
+        assert_eq!(game.guessed_letters, ['R']);
+    }
+
+    #[test]
+    fn test_accented_word() {
+        let (game, output) = run("AÑO", "a\ne\u{301}\nñ\nÑ\no\n");
+        assert!(game.won);
+        assert_eq!(game.guessed_letters, ['A', 'É', 'Ñ', 'O']);
+        assert!(output.contains("You already guessed Ñ."));
+    }
+
+    #[test]
+    fn test_digraph() {
+        let mut game = GameState::new("CHICO".to_string(), None, Difficulty::Medium);
+        game.digraphs = vec![digraphs::unit("CH").unwrap()];
+        let mut output = Vec::new();
+        let game = play(game, "ch\nch\ni\nc\no\n".as_bytes(), &mut output).unwrap();
+        let output = String::from_utf8(output).unwrap();
+        assert!(game.won);
+        assert_eq!(game.wrong_guesses, 0);
+        assert!(output.contains("CH _ _ _"));
+        assert!(output.contains("You already guessed CH."));
+    }
+
+    #[test]
+    fn test_hint() {
+        let (game, output) = run("RUST", "hint\nhint\nhint\n");
+        assert_eq!(game.hints_used, 2);
+        assert_eq!(game.wrong_guesses, 2);
+        assert!(output.contains("💡 Hint:"));
+        assert!(output.contains("No hint: no hints left for this game."));
+    }
+
+    #[test]
+    fn test_timed_game() {
+        let mut game = GameState::new("RUST".to_string(), None, Difficulty::Medium);
+        game.start_timer(90);
+        let mut output = Vec::new();
+        let game = play(game, "r\n".as_bytes(), &mut output).unwrap();
+        assert!(String::from_utf8(output).unwrap().contains("Time left: 90s"));
+
+        // The game's clock ran out while waiting for the next guess
+        let mut game = game;
+        game.deadline = Some(1);
+        let mut output = Vec::new();
+        let game = play(game, "u\n".as_bytes(), &mut output).unwrap();
+        assert!(game.timed_out);
+        assert_eq!(game.guessed_letters, ['R']);
+        assert!(String::from_utf8(output).unwrap().contains("TIME'S UP! The word was: RUST"));
+    }
+
+    #[test]
+    fn test_quit() {
+        let (game, output) = run("RUST", "quit\n");
+        assert!(!game.game_over);
+        assert!(output.contains("The word was: RUST"));
+    }
+
+    #[test]
+    fn test_input_closed() {
+        let (game, _) = run("RUST", "r\n");
+        assert!(!game.game_over);
+        assert_eq!(game.guessed_letters, ['R']);
+    }
+}
@@ -0,0 +1,100 @@
+//! Benchmarks for picking and searching words of a word list
+//!
+//! `Difficulty::pick_word` scores and sorts the whole list on every call, so
+//! its cost grows with the list. Searches go through the list's trie, each is
+//! measured next to the linear scan it replaced. Run with
+//! `cargo bench -p hangman-core`, see "Benchmarks" in the README for comparing
+//! against a saved baseline.
+
+use criterion::{black_box, criterion_group, criterion_main, BenchmarkId, Criterion};
+use hangman_core::difficulty::word_score;
+use hangman_core::{Candidates, Difficulty, Pattern, WordList};
+use sandbox_rng::{Rng, RngHandle};
+
+/// `len` random uppercase words of 4 to 12 letters (seeded, the same every run)
+fn word_list(len: usize) -> WordList {
+    let mut rng = RngHandle::seeded(1);
+    let words: Vec<String> = (0..len)
+        .map(|_| {
+            let letters = rng.gen_range(4..=12);
+            (0..letters).map(|_| rng.gen_range(b'A'..=b'Z') as char).collect()
+        })
+        .collect();
+    WordList::new(words).expect("generated words are valid")
+}
+
+fn scoring(c: &mut Criterion) {
+    c.bench_function("word_score", |b| b.iter(|| word_score(black_box("PROGRAMMING LANGUAGE"))));
+}
+
+fn picking(c: &mut Criterion) {
+    let mut group = c.benchmark_group("pick_word");
+    let mut rng = RngHandle::seeded(2);
+    for len in [100, 1_000, 10_000] {
+        let list = word_list(len);
+        group.bench_with_input(BenchmarkId::from_parameter(len), &list, |b, list| {
+            b.iter(|| Difficulty::Hard.pick_word(black_box(list), &mut rng))
+        });
+    }
+    group.finish();
+}
+
+/// Lists for the searches, the trie already built
+fn search_lists() -> Vec<(usize, WordList)> {
+    [10_000, 100_000]
+        .into_iter()
+        .map(|len| {
+            let list = word_list(len);
+            list.trie();
+            (len, list)
+        })
+        .collect()
+}
+
+fn pattern_search(c: &mut Criterion) {
+    let mut group = c.benchmark_group("pattern_search");
+    // A board a few guesses in: two letters shown, three missed
+    let pattern = Pattern::parse("_ A _ _ E _", "STR", false, &[]).unwrap();
+    for (len, list) in search_lists() {
+        group.bench_with_input(BenchmarkId::new("trie", len), &list, |b, list| b.iter(|| pattern.search(black_box(list))));
+        group.bench_with_input(BenchmarkId::new("scan", len), &list, |b, list| {
+            b.iter(|| black_box(list).words().iter().filter(|word| pattern.matches(word)).count())
+        });
+    }
+    group.finish();
+}
+
+fn prefix_search(c: &mut Criterion) {
+    let mut group = c.benchmark_group("prefix_search");
+    for (len, list) in search_lists() {
+        group.bench_with_input(BenchmarkId::new("trie", len), &list, |b, list| {
+            b.iter(|| black_box(list).with_prefix("QU", false))
+        });
+        group.bench_with_input(BenchmarkId::new("scan", len), &list, |b, list| {
+            b.iter(|| black_box(list).words().iter().filter(|word| word.starts_with("QU")).count())
+        });
+    }
+    group.finish();
+}
+
+fn evil_candidates(c: &mut Criterion) {
+    let mut group = c.benchmark_group("evil_candidates");
+    for (len, list) in search_lists() {
+        group.bench_with_input(BenchmarkId::new("trie", len), &list, |b, list| {
+            b.iter(|| Candidates::new(black_box(list), "HANGMAN").len())
+        });
+        // Every word's letters blanked out, compared with the word's
+        let shape = |word: &str| -> String { word.chars().map(|c| if c.is_alphabetic() { '_' } else { c }).collect() };
+        group.bench_with_input(BenchmarkId::new("scan", len), &list, |b, list| {
+            b.iter(|| {
+                let words: Vec<String> =
+                    black_box(list).words().iter().filter(|word| shape(word) == shape("HANGMAN")).cloned().collect();
+                words.len()
+            })
+        });
+    }
+    group.finish();
+}
+
+criterion_group!(benches, scoring, picking, pattern_search, prefix_search, evil_candidates);
+criterion_main!(benches);
@@ -0,0 +1,206 @@
+// ============================================================================
+// ACHIEVEMENTS
+// ============================================================================
+// Milestones a player unlocks once, e.g. winning without a wrong guess. The
+// list is fixed (ACHIEVEMENTS), each one has an id the frontend can key on.
+//
+// WHEN THEY'RE CHECKED:
+// The desktop app calls Achievements::record when a game ends and counts
+// (the same games the statistics count), with the player's win streak after
+// that game. record returns the achievements the game unlocked, so the app
+// can tell the windows about them.
+//
+// SAVING:
+// Unlocked achievements are a sandbox-store document (key ACHIEVEMENTS_KEY,
+// "achievements.json" next to the statistics), one entry per achievement
+// and player (None when no profile was selected). The desktop app keeps them
+// in its database's achievements table instead (see storage.rs).
+
+use sandbox_store::{Schema, Store, StoreError};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use crate::game::{is_guessable, now_ms, GameState};
+
+/// Store key the unlocked achievements are saved under
+pub const ACHIEVEMENTS_KEY: &str = "achievements";
+
+/// Wins in a row for the streak achievement
+pub const STREAK_TARGET: u32 = 10;
+
+/// Letters (not counting spaces and punctuation) a word needs for the long word achievement
+pub const LONG_WORD_LETTERS: usize = 12;
+
+/// Something a player can unlock
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Achievement {
+    pub id: &'static str,
+    pub title: &'static str,
+    pub description: &'static str,
+}
+
+/// Every achievement, in the order they're shown
+pub const ACHIEVEMENTS: [Achievement; 3] = [
+    Achievement {
+        id: "flawless",
+        title: "Flawless",
+        description: "Win a game without a wrong guess",
+    },
+    Achievement {
+        id: "streak_10",
+        title: "On a Roll",
+        description: "Win 10 games in a row",
+    },
+    Achievement {
+        id: "long_word",
+        title: "Wordsmith",
+        description: "Guess a word of 12 or more letters",
+    },
+];
+
+impl Achievement {
+    /// Look up an achievement by id
+    pub fn get(id: &str) -> Option<&'static Achievement> {
+        ACHIEVEMENTS.iter().find(|achievement| achievement.id == id)
+    }
+
+    /// Whether finishing `game` with `streak` wins in a row earns it
+    fn earned_by(&self, game: &GameState, streak: u32) -> bool {
+        match self.id {
+            "flawless" => game.won && game.flawless(),
+            "streak_10" => streak >= STREAK_TARGET,
+            "long_word" => game.won && game.word.chars().filter(|&c| is_guessable(c)).count() >= LONG_WORD_LETTERS,
+            _ => false,
+        }
+    }
+}
+
+/// An achievement a player has
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+pub struct Unlocked {
+    pub id: String,
+    // Profile that unlocked it (None if no profile was selected)
+    pub player: Option<String>,
+    // Milliseconds since the Unix epoch
+    pub at_ms: u64,
+    // Word of the game that unlocked it
+    pub word: String,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+pub struct Achievements {
+    // In the order they were unlocked
+    pub unlocked: Vec<Unlocked>,
+}
+
+impl Schema for Achievements {
+    const VERSION: u32 = 1;
+}
+
+impl Achievements {
+    /// Load unlocked achievements, starting with none if nothing was saved yet
+    pub fn load(store: &Store) -> Result<Self, StoreError> {
+        Ok(store.load(ACHIEVEMENTS_KEY)?.unwrap_or_default())
+    }
+
+    /// Save the unlocked achievements, replacing the previous copy
+    pub fn save(&self, store: &Store) -> Result<(), StoreError> {
+        store.save(ACHIEVEMENTS_KEY, self)
+    }
+
+    /// When `player` (None for no profile) unlocked achievement `id`, if they have
+    pub fn get(&self, id: &str, player: Option<&str>) -> Option<&Unlocked> {
+        self.unlocked
+            .iter()
+            .find(|unlocked| unlocked.id == id && unlocked.player.as_deref() == player)
+    }
+
+    /// Unlock what a finished game earned its player, `streak` being their wins in a row
+    /// after it. Returns the achievements unlocked just now (none for unfinished games,
+    /// or ones the player already has)
+    pub fn record(&mut self, game: &GameState, streak: u32) -> Vec<&'static Achievement> {
+        if !game.game_over {
+            return Vec::new();
+        }
+        let player = game.player.as_deref();
+        let new: Vec<&'static Achievement> = ACHIEVEMENTS
+            .iter()
+            .filter(|achievement| self.get(achievement.id, player).is_none() && achievement.earned_by(game, streak))
+            .collect();
+        let at_ms = now_ms();
+        for achievement in &new {
+            self.unlocked.push(Unlocked {
+                id: achievement.id.to_string(),
+                player: game.player.clone(),
+                at_ms,
+                word: game.word.clone(),
+            });
+        }
+        new
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::difficulty::Difficulty;
+
+    fn won(word: &str, wrong_guesses: u32) -> GameState {
+        let mut game = GameState::new(word.to_string(), None, Difficulty::Medium);
+        game.game_over = true;
+        game.won = true;
+        game.wrong_guesses = wrong_guesses;
+        game
+    }
+
+    fn ids(achievements: Vec<&Achievement>) -> Vec<&str> {
+        achievements.iter().map(|achievement| achievement.id).collect()
+    }
+
+    #[test]
+    fn test_unlocks_once_per_player() {
+        let mut achievements = Achievements::default();
+        assert_eq!(ids(achievements.record(&won("RUST", 0), 1)), ["flawless"]);
+        assert!(achievements.record(&won("RUST", 0), 2).is_empty());
+        assert_eq!(achievements.get("flawless", None).unwrap().word, "RUST");
+
+        let mut game = won("RUST", 0);
+        game.player = Some("ada".to_string());
+        assert_eq!(ids(achievements.record(&game, 1)), ["flawless"]);
+        assert_eq!(achievements.unlocked.len(), 2);
+    }
+
+    #[test]
+    fn test_conditions() {
+        let mut achievements = Achievements::default();
+        assert!(achievements.record(&won("RUST", 2), STREAK_TARGET - 1).is_empty());
+        assert_eq!(ids(achievements.record(&won("RUST", 2), STREAK_TARGET)), ["streak_10"]);
+        // Spaces don't count as letters
+        assert!(achievements.record(&won("ICE CREAM CAN", 1), 0).is_empty());
+        assert_eq!(ids(achievements.record(&won("BORROWCHECKER", 1), 0)), ["long_word"]);
+
+        // Losing or not finishing earns nothing
+        let mut lost = won("PATTERNMATCHING", 0);
+        lost.won = false;
+        assert!(achievements.record(&lost, 0).is_empty());
+        let unfinished = GameState::new("PATTERNMATCHING".to_string(), None, Difficulty::Medium);
+        assert!(achievements.record(&unfinished, 0).is_empty());
+
+        // A miss a shield took isn't flawless
+        let mut shielded = GameState::new("RUST".to_string(), None, Difficulty::Medium);
+        shielded.activate_powerup(crate::powerups::PowerUp::Shield).unwrap();
+        shielded.guess_letter('X');
+        shielded.guess_word("RUST");
+        assert_eq!(shielded.wrong_guesses, 0);
+        assert!(achievements.record(&shielded, 0).is_empty());
+    }
+
+    #[test]
+    fn test_save_and_load() {
+        let dir = tempfile::tempdir().unwrap();
+        let store = Store::open(dir.path()).unwrap();
+        let mut achievements = Achievements::default();
+        achievements.record(&won("RUST", 0), 1);
+        achievements.save(&store).unwrap();
+        assert_eq!(Achievements::load(&store).unwrap(), achievements);
+    }
+}
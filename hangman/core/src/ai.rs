@@ -0,0 +1,253 @@
+// ============================================================================
+// AI GUESSER
+// ============================================================================
+// A computer player for human-vs-AI games. It only looks at what a human
+// player could see (the masked word from player_view and the guesses so
+// far), never at the answer, so it plays by the same rules.
+//
+// HOW IT PICKS A GUESS:
+// 1. Keep the words of the dictionary that still fit the board (see
+//    pattern.rs): same length, revealed letters and punctuation in the same
+//    places, no guessed letter in a hidden place, and not one of the missed
+//    word guesses
+// 2. If only one word is left (and more than one letter is hidden), guess it
+// 3. Otherwise guess the letter expected to tell the most about which of
+//    them is the answer (see suggest.rs)
+// 4. If no word fits (the answer isn't in the dictionary), fall back to the
+//    most common English letter that hasn't been guessed
+//
+// SMART HINTS:
+// The player can see in how many of the words that still fit each unguessed
+// letter is (letter_frequencies), so they see which guesses are
+// statistically good. It's the same for every word that fits, so it never
+// tells which of them is the answer.
+//
+// Letters are compared like the game compares them (see letters.rs), so
+// with ignore_accents the AI doesn't guess "É" after "E", and the words are
+// split into the game's digraphs (see digraphs.rs) like its word is.
+
+use std::collections::BTreeMap;
+use crate::digraphs::tokenize;
+use crate::game::GameState;
+use crate::letters::{fold, to_upper};
+use crate::pattern::Pattern;
+use crate::suggest;
+use crate::words::WordList;
+
+/// Letters from most to least common in English text, for ties and fallbacks
+const ENGLISH_FREQUENCY: &str = "ETAOINSHRDLCUMWFGYPBVKJXQZ";
+
+/// A move chosen by the AI
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum AiMove {
+    Letter(char),
+    Word(String),
+}
+
+impl AiMove {
+    /// The move as the game would show it, e.g. "E" or "FERRIS"
+    pub fn text(&self) -> String {
+        match self {
+            AiMove::Letter(letter) => letter.to_string(),
+            AiMove::Word(word) => word.clone(),
+        }
+    }
+}
+
+/// What the board shows: Some(character) where it's revealed, None for a hidden letter
+fn board(game: &GameState) -> Vec<Option<char>> {
+    game.player_view()
+        .word
+        .chars()
+        .map(|c| if c == '_' { None } else { Some(c) })
+        .collect()
+}
+
+/// The words of `words` that could still be the answer of `game`
+pub fn candidates<'a>(game: &GameState, words: &'a WordList) -> Vec<&'a str> {
+    let excluded = game.guessed_letters.iter().chain(&game.removed_letters).copied().collect();
+    let pattern =
+        Pattern::new(board(game), excluded, game.ignore_accents, game.digraphs.clone()).with_charset(game.charset.clone());
+    pattern
+        .search(words)
+        .into_iter()
+        .filter(|word| !game.has_guessed_word(word))
+        .collect()
+}
+
+/// How common English `letter` is, 0 for the most common
+pub(crate) fn rank(letter: char) -> usize {
+    ENGLISH_FREQUENCY
+        .find(to_upper(fold(letter, true)))
+        .unwrap_or(ENGLISH_FREQUENCY.len())
+}
+
+/// In how many of the words that fit the board an unguessed letter is
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct LetterFrequency {
+    pub letter: char,
+    pub words: usize,
+}
+
+/// The unguessed letters of `candidates` of `game`, from the most to the least words
+/// they're in (ties go to the letter more common in English)
+fn frequencies(game: &GameState, candidates: &[&str]) -> Vec<LetterFrequency> {
+    let ignore_accents = game.ignore_accents;
+    // In how many candidates each unguessed letter appears (counted once per word),
+    // by folded letter, with the form to guess it as
+    let mut counts: BTreeMap<char, (usize, char)> = BTreeMap::new();
+    for word in candidates {
+        let mut seen = Vec::new();
+        for c in tokenize(word, &game.digraphs).into_iter().filter(|&c| game.is_guessable(c) && !game.is_guessed(c)) {
+            let key = fold(c, ignore_accents);
+            if !seen.contains(&key) {
+                seen.push(key);
+                counts.entry(key).or_insert((0, to_upper(c))).0 += 1;
+            }
+        }
+    }
+    let mut letters: Vec<LetterFrequency> = counts
+        .into_values()
+        .map(|(words, letter)| LetterFrequency { letter, words })
+        .collect();
+    letters.sort_by(|a, b| b.words.cmp(&a.words).then_with(|| rank(a.letter).cmp(&rank(b.letter))));
+    letters
+}
+
+/// The smart hint of `game` with `words` as the dictionary: how many words still fit
+/// the board, and the frequencies of the unguessed letters in them
+/// (no words and no letters once the game is over, or if no word fits)
+pub fn letter_frequencies(game: &GameState, words: &WordList) -> (usize, Vec<LetterFrequency>) {
+    if game.game_over {
+        return (0, Vec::new());
+    }
+    let candidates = candidates(game, words);
+    (candidates.len(), frequencies(game, &candidates))
+}
+
+/// The AI's next move in `game` with `words` as its dictionary
+/// (None once the game is over)
+pub fn choose_move(game: &GameState, words: &WordList) -> Option<AiMove> {
+    if game.game_over {
+        return None;
+    }
+    let candidates = candidates(game, words);
+    let hidden = board(game).iter().filter(|shown| shown.is_none()).count();
+    if let [word] = candidates[..] {
+        if hidden > 1 {
+            return Some(AiMove::Word(word.to_string()));
+        }
+    }
+    if let Some(best) = suggest::rank_letters(game, &candidates).first() {
+        return Some(AiMove::Letter(best.letter));
+    }
+
+    // Nothing in the dictionary fits: the most common letter that's left
+    let mut keys = game.keyboard();
+    keys.sort_by_key(|&c| (rank(c), c));
+    keys.into_iter()
+        .find(|&c| !game.is_guessed(c))
+        .map(AiMove::Letter)
+}
+
+/// Play the AI's next move in `game`, returns the move (None once the game is over)
+pub fn take_turn(game: &mut GameState, words: &WordList) -> Option<AiMove> {
+    let ai_move = choose_move(game, words)?;
+    match &ai_move {
+        AiMove::Letter(letter) => game.guess_letter(*letter),
+        AiMove::Word(word) => game.guess_word(word),
+    };
+    game.ai_moves += 1;
+    Some(ai_move)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::difficulty::Difficulty;
+
+    fn words() -> WordList {
+        WordList::from_text("rust\nruby\nrush\njava\nperl\nferris\ncargo\nice cream").unwrap()
+    }
+
+    fn game(word: &str) -> GameState {
+        GameState::new(word.to_string(), None, Difficulty::Medium)
+    }
+
+    #[test]
+    fn test_candidates_fit_the_board() {
+        let mut game = game("RUST");
+        assert_eq!(candidates(&game, &words()), ["RUST", "RUBY", "RUSH", "JAVA", "PERL"]);
+        game.guess_letter('R');
+        assert_eq!(candidates(&game, &words()), ["RUST", "RUBY", "RUSH"]);
+        game.guess_letter('B');
+        assert_eq!(candidates(&game, &words()), ["RUST", "RUSH"]);
+
+        let mut game = self::game("ICE CREAM");
+        game.guess_letter('E');
+        assert_eq!(candidates(&game, &words()), ["ICE CREAM"]);
+    }
+
+    #[test]
+    fn test_picks_most_telling_letter() {
+        let mut game = game("RUST");
+        // R splits the 5 four-letter words the most
+        assert_eq!(choose_move(&game, &words()), Some(AiMove::Letter('R')));
+        game.guess_letter('R');
+        // All of RUST, RUBY and RUSH have U, S tells them apart
+        assert_eq!(choose_move(&game, &words()), Some(AiMove::Letter('S')));
+    }
+
+    #[test]
+    fn test_letter_frequencies() {
+        let mut game = game("RUST");
+        game.guess_letter('R');
+        // RUST, RUBY and RUSH are left
+        let (left, letters) = letter_frequencies(&game, &words());
+        assert_eq!(left, 3);
+        let counts: Vec<(char, usize)> = letters.iter().map(|f| (f.letter, f.words)).collect();
+        assert_eq!(counts, [('U', 3), ('S', 2), ('T', 1), ('H', 1), ('Y', 1), ('B', 1)]);
+
+        game.guess_word("RUST");
+        assert_eq!(letter_frequencies(&game, &words()), (0, Vec::new()));
+    }
+
+    #[test]
+    fn test_guesses_the_word_when_only_one_fits() {
+        let mut game = game("FERRIS");
+        game.guess_letter('F');
+        assert_eq!(choose_move(&game, &words()), Some(AiMove::Word("FERRIS".to_string())));
+        assert_eq!(take_turn(&mut game, &words()), Some(AiMove::Word("FERRIS".to_string())));
+        assert!(game.won);
+        assert_eq!(game.ai_moves, 1);
+        assert_eq!(take_turn(&mut game, &words()), None);
+    }
+
+    #[test]
+    fn test_falls_back_to_english_frequency() {
+        let mut game = game("ZZZZZZZ"); // No seven-letter words
+        assert_eq!(choose_move(&game, &words()), Some(AiMove::Letter('E')));
+        game.guess_letter('E');
+        assert_eq!(choose_move(&game, &words()), Some(AiMove::Letter('T')));
+    }
+
+    #[test]
+    fn test_always_finishes_a_game() {
+        for word in words().words() {
+            let mut game = game(word);
+            game.max_wrong_guesses = 26;
+            while take_turn(&mut game, &words()).is_some() {}
+            assert!(game.won, "{}", word);
+        }
+    }
+
+    #[test]
+    fn test_ignores_accents_like_the_game() {
+        let words = WordList::from_text("été\nthé").unwrap();
+        let mut game = game("ÉTÉ");
+        game.ignore_accents = true;
+        game.guess_letter('E');
+        assert_eq!(candidates(&game, &words), ["ÉTÉ"]);
+        assert_eq!(choose_move(&game, &words), Some(AiMove::Letter('T')));
+    }
+}
@@ -0,0 +1,197 @@
+// ============================================================================
+// ARCADE RUNS
+// ============================================================================
+// An arcade run is a string of games played on a pool of lives: every lost
+// game costs a life, and WINS_PER_LIFE wins in a row win one back (never more
+// than the run started with). The run is over when the last life is lost, its
+// score is the sum of the scores of every game played in it.
+//
+// WORDS:
+// Each word is a new game, the run moves from one to the next with it (in
+// GameState's `run`), so a game's GameView can show the lives left and the
+// run's score. A game's result is recorded when it ends, and taken back if
+// its last move is undone: the lives are worked out from the results, so
+// taking one back gives back the life (or the streak) it changed. Games of a
+// run are counted in the statistics like any other game.
+
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use crate::difficulty::Difficulty;
+use crate::matches::RoundResult;
+
+/// Lives a run starts with when none are asked for
+pub const START_LIVES: u32 = 3;
+
+/// Most lives a run can start with
+pub const MAX_LIVES: u32 = 9;
+
+/// Wins in a row that win a life back
+pub const WINS_PER_LIFE: u32 = 3;
+
+/// Reasons a run can't be started
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum RunError {
+    /// Not from 1 to MAX_LIVES lives
+    InvalidLives(u32),
+}
+
+impl fmt::Display for RunError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            RunError::InvalidLives(lives) => {
+                write!(f, "a run starts with 1 to {} lives, not {}", MAX_LIVES, lives)
+            }
+        }
+    }
+}
+
+impl std::error::Error for RunError {}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+pub struct Run {
+    pub start_lives: u32,
+    // Every word's difficulty
+    pub difficulty: Difficulty,
+    // The word being played, from 1
+    pub word: u32,
+    // Finished games, in order
+    pub results: Vec<RoundResult>,
+}
+
+impl Run {
+    /// A run with `lives` lives, on its first word
+    pub fn new(lives: u32, difficulty: Difficulty) -> Result<Self, RunError> {
+        if lives == 0 || lives > MAX_LIVES {
+            return Err(RunError::InvalidLives(lives));
+        }
+        Ok(Run {
+            start_lives: lives,
+            difficulty,
+            word: 1,
+            results: Vec::new(),
+        })
+    }
+
+    /// Lives left and the current winning streak, after the finished games
+    fn tally(&self) -> (u32, u32) {
+        let (mut lives, mut streak) = (self.start_lives, 0u32);
+        for result in &self.results {
+            if result.won {
+                streak += 1;
+                if streak.is_multiple_of(WINS_PER_LIFE) {
+                    lives = (lives + 1).min(self.start_lives);
+                }
+            } else {
+                lives = lives.saturating_sub(1);
+                streak = 0;
+            }
+        }
+        (lives, streak)
+    }
+
+    pub fn lives(&self) -> u32 {
+        self.tally().0
+    }
+
+    /// Wins in a row since the last loss
+    pub fn streak(&self) -> u32 {
+        self.tally().1
+    }
+
+    pub fn wins(&self) -> u32 {
+        self.results.iter().filter(|result| result.won).count() as u32
+    }
+
+    /// Sum of the games' scores
+    pub fn score(&self) -> u32 {
+        self.results.iter().map(|result| result.score).sum()
+    }
+
+    /// Whether the last life is lost
+    pub fn is_over(&self) -> bool {
+        self.lives() == 0
+    }
+
+    /// Whether the current word's result is in
+    pub fn word_finished(&self) -> bool {
+        self.results.len() as u32 >= self.word
+    }
+
+    /// Record how the current word's game ended (once)
+    pub(crate) fn record(&mut self, result: RoundResult) {
+        if !self.word_finished() {
+            self.results.push(result);
+        }
+    }
+
+    /// Take the current word's result back (its game was reopened by undo)
+    pub(crate) fn reopen(&mut self) {
+        if self.word_finished() {
+            self.results.pop();
+        }
+    }
+
+    /// Move on to the next word, false if the run is over or the current
+    /// word isn't finished yet
+    pub fn next_word(&mut self) -> bool {
+        if self.is_over() || !self.word_finished() {
+            return false;
+        }
+        self.word += 1;
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn result(won: bool) -> RoundResult {
+        RoundResult {
+            word: "RUST".to_string(),
+            won,
+            score: if won { 100 } else { 0 },
+        }
+    }
+
+    #[test]
+    fn test_lives_must_be_in_range() {
+        for lives in [0, MAX_LIVES + 1] {
+            assert_eq!(Run::new(lives, Difficulty::Easy), Err(RunError::InvalidLives(lives)));
+        }
+        assert_eq!(Run::new(START_LIVES, Difficulty::Easy).unwrap().lives(), START_LIVES);
+    }
+
+    #[test]
+    fn test_lives() {
+        let mut run = Run::new(2, Difficulty::Medium).unwrap();
+        assert!(!run.next_word());
+        for won in [false, true, true, true, false] {
+            assert!(!run.is_over());
+            run.record(result(won));
+            run.record(result(!won));
+            run.next_word();
+        }
+        // Lost one, won it back with three wins in a row, and lost one again
+        assert_eq!((run.lives(), run.streak(), run.wins(), run.score()), (1, 0, 3, 300));
+
+        // Wins don't go over the lives the run started with
+        for _ in 0..WINS_PER_LIFE * 2 {
+            run.record(result(true));
+            run.next_word();
+        }
+        assert_eq!(run.lives(), 2);
+
+        run.record(result(false));
+        run.next_word();
+        run.record(result(false));
+        assert!(run.is_over());
+        assert!(!run.next_word());
+        assert_eq!((run.word, run.score()), (13, 900));
+
+        // Undoing the last loss gives the life back
+        run.reopen();
+        assert_eq!(run.lives(), 1);
+    }
+}
@@ -0,0 +1,275 @@
+// ============================================================================
+// COMMAND AUDIT LOG
+// ============================================================================
+// A record of every command the desktop app's frontend invoked, for tracking
+// down the frontend and the backend disagreeing about a game. It's opt-in:
+// nothing is recorded unless the `audit_log` setting is on.
+//
+// WHAT'S RECORDED:
+// One AuditEntry per command once it has answered: when it was invoked, the
+// window, the command, its arguments, whether it succeeded (with the error if
+// it didn't), and how long it took. Arguments that hold a word the other
+// player mustn't see (SECRET_ARGS: a two-player secret, a replay, a join code)
+// are replaced with REDACTED, wherever they are in the arguments. Results
+// aren't kept, only the errors: a game view is big, and its word is the answer.
+//
+// THE FILE:
+// Entries are appended to AUDIT_FILE in the app data directory, one JSON
+// entry per line. Once it would grow past MAX_AUDIT_FILE_BYTES it's moved to
+// "audit.jsonl.1" (replacing the one before) and a new one is started, so at
+// most two files' worth is kept. recent() reads both back, newest first.
+//
+// CALLS:
+// A call is begun when it's invoked and finished when it answers, matched by
+// the window and an id the frontend gave it (the desktop app's IPC hooks do
+// this, see src-tauri/src/audit.rs). Calls still running when the setting is
+// turned off are forgotten.
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::HashMap;
+use std::fs::{self, OpenOptions};
+use std::io::{self, Write};
+use std::path::PathBuf;
+use sandbox_store::backup_path;
+use crate::settings::Settings;
+
+/// Where entries are written, in the app data directory
+pub const AUDIT_FILE: &str = "audit.jsonl";
+
+/// Size the file may grow to before it's rotated
+pub const MAX_AUDIT_FILE_BYTES: u64 = 1024 * 1024;
+
+/// Most entries recent() returns at once
+pub const MAX_AUDIT_ENTRIES: usize = 500;
+
+/// Arguments never written to the log
+pub const SECRET_ARGS: &[&str] = &["word_or_phrase", "replay", "code"];
+
+/// What a secret argument is written as
+pub const REDACTED: &str = "[redacted]";
+
+/// One command call
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct AuditEntry {
+    // When the command was invoked (ms since the Unix epoch)
+    pub at_ms: u64,
+    // Label of the window that invoked it
+    pub window: String,
+    pub command: String,
+    // The arguments, redacted
+    pub args: Value,
+    pub ok: bool,
+    // What the command failed with
+    pub error: Option<Value>,
+    pub duration_ms: u64,
+}
+
+/// `args` with every secret argument (at any depth) replaced with REDACTED
+pub fn redact(args: &Value) -> Value {
+    match args {
+        Value::Object(fields) => Value::Object(
+            fields
+                .iter()
+                .map(|(key, value)| {
+                    let value = if SECRET_ARGS.contains(&key.as_str()) { Value::from(REDACTED) } else { redact(value) };
+                    (key.clone(), value)
+                })
+                .collect(),
+        ),
+        Value::Array(items) => Value::Array(items.iter().map(redact).collect()),
+        other => other.clone(),
+    }
+}
+
+/// A call that hasn't answered yet
+#[derive(Debug, Clone)]
+struct Call {
+    at_ms: u64,
+    command: String,
+    args: Value,
+}
+
+/// The calls running and the file they're written to
+#[derive(Debug, Clone)]
+pub struct AuditLog {
+    enabled: bool,
+    file: PathBuf,
+    max_bytes: u64,
+    // By window and call id
+    running: HashMap<(String, usize), Call>,
+}
+
+impl AuditLog {
+    /// An audit log writing to `file` once `settings` turn it on
+    pub fn new(file: PathBuf, settings: &Settings) -> Self {
+        AuditLog {
+            enabled: settings.audit_log,
+            file,
+            max_bytes: MAX_AUDIT_FILE_BYTES,
+            running: HashMap::new(),
+        }
+    }
+
+    /// Rotate the file once it would grow past `max_bytes`
+    pub fn with_max_bytes(mut self, max_bytes: u64) -> Self {
+        self.max_bytes = max_bytes;
+        self
+    }
+
+    /// Follow changed settings, turning the log off forgets the calls running
+    pub fn configure(&mut self, settings: &Settings) {
+        self.enabled = settings.audit_log;
+        if !self.enabled {
+            self.running.clear();
+        }
+    }
+
+    pub fn is_enabled(&self) -> bool {
+        self.enabled
+    }
+
+    pub fn file(&self) -> &PathBuf {
+        &self.file
+    }
+
+    /// A command was invoked (nothing while the log is off)
+    pub fn begin(&mut self, window: &str, id: usize, command: &str, args: &Value, at_ms: u64) {
+        if !self.enabled {
+            return;
+        }
+        let call = Call {
+            at_ms,
+            command: command.to_string(),
+            args: redact(args),
+        };
+        self.running.insert((window.to_string(), id), call);
+    }
+
+    /// The call `id` of `window` answered: write its entry, and return it
+    /// Ok(None) for a call that wasn't begun (invoked while the log was off)
+    pub fn finish(&mut self, window: &str, id: usize, result: Result<(), Value>, now_ms: u64) -> io::Result<Option<AuditEntry>> {
+        let Some(call) = self.running.remove(&(window.to_string(), id)) else {
+            return Ok(None);
+        };
+        let entry = AuditEntry {
+            at_ms: call.at_ms,
+            window: window.to_string(),
+            command: call.command,
+            args: call.args,
+            ok: result.is_ok(),
+            error: result.err(),
+            duration_ms: now_ms.saturating_sub(call.at_ms),
+        };
+        self.append(&entry)?;
+        Ok(Some(entry))
+    }
+
+    /// Write an entry, rotating the file first if it would get too big
+    fn append(&self, entry: &AuditEntry) -> io::Result<()> {
+        let mut line = serde_json::to_string(entry).expect("audit entries serialize");
+        line.push('\n');
+        if let Some(dir) = self.file.parent() {
+            fs::create_dir_all(dir)?;
+        }
+        let size = fs::metadata(&self.file).map_or(0, |metadata| metadata.len());
+        if size > 0 && size + line.len() as u64 > self.max_bytes {
+            fs::rename(&self.file, backup_path(&self.file, 1))?;
+        }
+        OpenOptions::new().create(true).append(true).open(&self.file)?.write_all(line.as_bytes())
+    }
+
+    /// The last `limit` entries written (at most MAX_AUDIT_ENTRIES), newest first
+    /// A line that can't be read is skipped
+    pub fn recent(&self, limit: usize) -> io::Result<Vec<AuditEntry>> {
+        let mut entries = Vec::new();
+        for path in [backup_path(&self.file, 1), self.file.clone()] {
+            match fs::read_to_string(&path) {
+                Ok(text) => entries.extend(text.lines().filter_map(|line| serde_json::from_str(line).ok())),
+                Err(err) if err.kind() == io::ErrorKind::NotFound => {}
+                Err(err) => return Err(err),
+            }
+        }
+        let skip = entries.len().saturating_sub(limit.min(MAX_AUDIT_ENTRIES));
+        Ok(entries.into_iter().skip(skip).rev().collect())
+    }
+
+    /// Remove the file and the rotated one
+    pub fn clear(&mut self) -> io::Result<()> {
+        for path in [self.file.clone(), backup_path(&self.file, 1)] {
+            match fs::remove_file(path) {
+                Err(err) if err.kind() != io::ErrorKind::NotFound => return Err(err),
+                _ => {}
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn audit_log(dir: &tempfile::TempDir) -> AuditLog {
+        let settings = Settings {
+            audit_log: true,
+            ..Settings::default()
+        };
+        AuditLog::new(dir.path().join(AUDIT_FILE), &settings)
+    }
+
+    #[test]
+    fn test_calls_are_written_once_they_answer() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut log = audit_log(&dir);
+        log.begin("main", 1, "start_custom_game", &json!({ "word_or_phrase": "RUST", "difficulty": "hard" }), 1000);
+        log.begin("main", 2, "guess_letter", &json!({ "session_id": "s1", "letter": "r" }), 1010);
+        assert!(log.recent(10).unwrap().is_empty());
+
+        let guessed = log.finish("main", 2, Err(json!({ "code": "game_over" })), 1015).unwrap().unwrap();
+        assert_eq!((guessed.ok, guessed.duration_ms), (false, 5));
+        let started = log.finish("main", 1, Ok(()), 1040).unwrap().unwrap();
+        assert_eq!(started.args, json!({ "word_or_phrase": REDACTED, "difficulty": "hard" }));
+        assert!(!fs::read_to_string(log.file()).unwrap().contains("RUST"));
+        assert_eq!(log.recent(10).unwrap(), [started.clone(), guessed]);
+        assert_eq!(log.recent(1).unwrap(), [started]);
+
+        // A call that wasn't begun isn't written
+        assert_eq!(log.finish("other", 1, Ok(()), 1050).unwrap(), None);
+        log.clear().unwrap();
+        assert!(log.recent(10).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_off_until_turned_on() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut log = AuditLog::new(dir.path().join(AUDIT_FILE), &Settings::default());
+        log.begin("main", 1, "get_settings", &json!({}), 0);
+        assert_eq!(log.finish("main", 1, Ok(()), 1).unwrap(), None);
+        assert!(!log.file().exists());
+
+        log.configure(&Settings { audit_log: true, ..Settings::default() });
+        log.begin("main", 2, "get_settings", &json!({}), 0);
+        log.configure(&Settings::default());
+        assert_eq!(log.finish("main", 2, Ok(()), 1).unwrap(), None);
+    }
+
+    #[test]
+    fn test_rotation_and_redaction() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut log = audit_log(&dir).with_max_bytes(400);
+        for id in 0..6 {
+            log.begin("main", id, "join_game", &json!({ "address": "10.0.0.2:7878", "code": "4821" }), id as u64);
+            log.finish("main", id, Ok(()), id as u64).unwrap();
+        }
+        assert!(backup_path(log.file(), 1).exists());
+        assert!(fs::metadata(log.file()).unwrap().len() <= 400);
+        let recent = log.recent(10).unwrap();
+        assert_eq!(recent[0].at_ms, 5);
+        assert!(recent.windows(2).all(|pair| pair[0].at_ms == pair[1].at_ms + 1));
+
+        let nested = redact(&json!({ "settings": [{ "code": 1, "seed": 2 }] }));
+        assert_eq!(nested, json!({ "settings": [{ "code": REDACTED, "seed": 2 }] }));
+    }
+}
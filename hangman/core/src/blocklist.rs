@@ -0,0 +1,95 @@
+// ============================================================================
+// WORD BLOCKLIST
+// ============================================================================
+// Words that are never picked for a game, whichever source they come from:
+// a word list file, a category, imported words, or words fetched online.
+// The frontends filter every pool they pick from with WordList::without, so
+// nothing reaches a game without going through it.
+//
+// WHAT'S BLOCKED:
+// - BUILTIN_BLOCKLIST ("resources/blocklist.txt"), unless the
+//   profanity_filter setting is off
+// - The blocked_words setting, added to it (e.g. words a family would rather
+//   not see)
+// A phrase is blocked if any of its words is, and words are compared
+// without case or accents, so "Dämn" is as blocked as "DAMN". Words that
+// only contain a blocked word ("SCRAPBOOK") are fine.
+
+use std::collections::HashSet;
+use crate::letters::fold_str;
+
+/// Words blocked by default, one per line ("#" comments ignored)
+const BUILTIN_BLOCKLIST: &str = include_str!("../resources/blocklist.txt");
+
+/// Words games can't be played with
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Blocklist {
+    // Folded (lowercase, no accents)
+    words: HashSet<String>,
+}
+
+impl Blocklist {
+    /// Block `words`
+    pub fn new<I, S>(words: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<str>,
+    {
+        let mut blocklist = Blocklist::default();
+        blocklist.extend(words);
+        blocklist
+    }
+
+    /// The words blocked by default
+    pub fn builtin() -> Self {
+        Blocklist::new(
+            BUILTIN_BLOCKLIST
+                .lines()
+                .map(str::trim)
+                .filter(|line| !line.is_empty() && !line.starts_with('#')),
+        )
+    }
+
+    /// Block more words (each word of a phrase on its own)
+    pub fn extend<I, S>(&mut self, words: I)
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<str>,
+    {
+        for entry in words {
+            self.words.extend(entry.as_ref().split_whitespace().map(|word| fold_str(word, true)));
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.words.is_empty()
+    }
+
+    /// Whether `word` (or any word of a phrase) is blocked
+    pub fn is_blocked(&self, word: &str) -> bool {
+        !self.words.is_empty() && word.split_whitespace().any(|part| self.words.contains(&fold_str(part, true)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_matches_whole_words() {
+        let blocklist = Blocklist::new(["damn"]);
+        assert!(blocklist.is_blocked("DAMN"));
+        assert!(blocklist.is_blocked("Dämn"));
+        assert!(blocklist.is_blocked("WELL DAMN IT"));
+        assert!(!blocklist.is_blocked("DAMNATION"));
+        assert!(!Blocklist::default().is_blocked("DAMN"));
+    }
+
+    #[test]
+    fn test_builtin() {
+        let blocklist = Blocklist::builtin();
+        assert!(blocklist.is_blocked("SHIT"));
+        assert!(!blocklist.is_blocked("SCRAPBOOK"));
+        assert!(!blocklist.is_blocked("#"));
+    }
+}
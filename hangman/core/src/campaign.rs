@@ -0,0 +1,342 @@
+// ============================================================================
+// CAMPAIGN
+// ============================================================================
+// The campaign is LEVELS, a fixed series of games that get harder as they go:
+// short easy words first, long hard ones last. Level 1 is open from the
+// start, every other level unlocks once the one before it is won, and a few
+// also need the player's campaign score (the best score of every level won
+// so far) to reach their `min_score`. A lost level can be played again as
+// often as the player likes, and so can a won one (a better score replaces
+// the level's best).
+//
+// LEVEL GAMES:
+// A level's game is a normal game picked with the level's difficulty and
+// word lengths, with the level in GameState's `campaign_level`. Its result
+// is recorded when it ends, like a daily game's.
+//
+// PROGRESS:
+// Every player's progress (and the one of games played without a profile)
+// is kept in one sandbox-store document (key CAMPAIGN_KEY), the desktop app
+// keeps it in its database's documents. Starting a level for the first time
+// starts the player's clock, and winning the last level for the first time
+// stops it: the time in between is how long the campaign took, which the
+// player's profile keeps as well (see profiles.rs).
+
+use sandbox_store::{Schema, Store, StoreError};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use crate::difficulty::Difficulty;
+use crate::game::GameState;
+use crate::words::WordLengths;
+
+/// Store key the campaign progress is saved under
+pub const CAMPAIGN_KEY: &str = "campaign";
+
+/// One level of the campaign
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Level {
+    pub difficulty: Difficulty,
+    // Letters the level's words have
+    pub lengths: WordLengths,
+    // Campaign score needed (on top of winning the level before) to unlock it
+    pub min_score: u32,
+}
+
+const fn level(difficulty: Difficulty, min: usize, max: Option<usize>, min_score: u32) -> Level {
+    Level {
+        difficulty,
+        lengths: WordLengths { min: Some(min), max },
+        min_score,
+    }
+}
+
+/// The campaign's levels in order, level 1 first
+pub const LEVELS: &[Level] = &[
+    level(Difficulty::Easy, 3, Some(5), 0),
+    level(Difficulty::Easy, 4, Some(6), 0),
+    level(Difficulty::Easy, 5, Some(7), 0),
+    level(Difficulty::Medium, 4, Some(6), 200),
+    level(Difficulty::Medium, 5, Some(8), 0),
+    level(Difficulty::Medium, 6, Some(9), 800),
+    level(Difficulty::Hard, 5, Some(8), 0),
+    level(Difficulty::Hard, 6, Some(10), 0),
+    level(Difficulty::Hard, 8, None, 2000),
+    level(Difficulty::Hard, 10, None, 0),
+];
+
+/// The level numbered `level` (from 1), None past the last one
+pub fn get(level: u32) -> Option<&'static Level> {
+    LEVELS.get(level.checked_sub(1)? as usize)
+}
+
+/// Reasons a level can't be played
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CampaignError {
+    /// There's no level with this number
+    UnknownLevel(u32),
+    /// The level isn't unlocked yet
+    Locked(u32),
+}
+
+impl fmt::Display for CampaignError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CampaignError::UnknownLevel(level) => {
+                write!(f, "the campaign has levels 1 to {}, not {}", LEVELS.len(), level)
+            }
+            CampaignError::Locked(level) => write!(f, "level {} isn't unlocked yet", level),
+        }
+    }
+}
+
+impl std::error::Error for CampaignError {}
+
+/// How a player did on one level
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+pub struct LevelResult {
+    pub level: u32,
+    // Finished games of the level, won or lost
+    pub attempts: u32,
+    pub won: bool,
+    // Best score of a won game (0 until one is won)
+    pub best_score: u32,
+}
+
+/// How far one player got in the campaign
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+pub struct Progress {
+    // Profile that played (None for games without a profile)
+    pub player: Option<String>,
+    // Levels played at least once, in the order they were first finished
+    pub levels: Vec<LevelResult>,
+    // When the first level was first started (Unix ms)
+    pub started_at_ms: Option<u64>,
+    // When the last level was first won (Unix ms)
+    pub completed_at_ms: Option<u64>,
+}
+
+impl Progress {
+    pub fn result(&self, level: u32) -> Option<&LevelResult> {
+        self.levels.iter().find(|result| result.level == level)
+    }
+
+    pub fn won(&self, level: u32) -> bool {
+        self.result(level).is_some_and(|result| result.won)
+    }
+
+    /// Sum of the best scores of the levels won
+    pub fn score(&self) -> u32 {
+        self.levels.iter().map(|result| result.best_score).sum()
+    }
+
+    /// Whether `level` can be played: level 1 always, any other once the
+    /// level before it is won and the score reaches its `min_score`
+    pub fn unlocked(&self, level: u32) -> bool {
+        match get(level) {
+            Some(_) if level == 1 => true,
+            Some(found) => self.won(level - 1) && self.score() >= found.min_score,
+            None => false,
+        }
+    }
+
+    /// The level to play next: the first one that isn't won yet, or the last
+    /// one once they all are
+    pub fn next_level(&self) -> u32 {
+        (1..=LEVELS.len() as u32)
+            .find(|&level| !self.won(level))
+            .unwrap_or(LEVELS.len() as u32)
+    }
+
+    pub fn is_complete(&self) -> bool {
+        self.completed_at_ms.is_some()
+    }
+
+    /// How long the campaign took, once it's complete
+    pub fn time_ms(&self) -> Option<u64> {
+        Some(self.completed_at_ms?.saturating_sub(self.started_at_ms?))
+    }
+}
+
+/// What recording a game changed in the campaign
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Recorded {
+    /// Nothing: not a level's game, or not finished
+    Nothing,
+    /// The level's result
+    Level,
+    /// The level's result, and it completed the campaign in this many ms
+    Completed(u64),
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+pub struct Campaign {
+    // One per player, in the order they started
+    pub players: Vec<Progress>,
+}
+
+impl Schema for Campaign {
+    const VERSION: u32 = 1;
+}
+
+impl Campaign {
+    /// Load saved progress, starting with none if nothing was saved yet
+    pub fn load(store: &Store) -> Result<Self, StoreError> {
+        Ok(store.load(CAMPAIGN_KEY)?.unwrap_or_default())
+    }
+
+    /// Save the progress, replacing the previous copy
+    pub fn save(&self, store: &Store) -> Result<(), StoreError> {
+        store.save(CAMPAIGN_KEY, self)
+    }
+
+    /// The progress of `player` (None for no profile), a fresh one if they never played
+    pub fn progress(&self, player: Option<&str>) -> Progress {
+        self.players
+            .iter()
+            .find(|progress| progress.player.as_deref() == player)
+            .cloned()
+            .unwrap_or_else(|| Progress {
+                player: player.map(str::to_string),
+                ..Progress::default()
+            })
+    }
+
+    fn progress_mut(&mut self, player: Option<&str>) -> &mut Progress {
+        match self.players.iter().position(|progress| progress.player.as_deref() == player) {
+            Some(index) => &mut self.players[index],
+            None => {
+                self.players.push(self.progress(player));
+                self.players.last_mut().unwrap()
+            }
+        }
+    }
+
+    /// Check `player` may play `level`, and start their clock if it's their
+    /// first level; false if nothing changed (the clock was already running)
+    pub fn start(&mut self, player: Option<&str>, level: u32, now_ms: u64) -> Result<bool, CampaignError> {
+        if get(level).is_none() {
+            return Err(CampaignError::UnknownLevel(level));
+        }
+        if !self.progress(player).unlocked(level) {
+            return Err(CampaignError::Locked(level));
+        }
+        let progress = self.progress_mut(player);
+        if progress.started_at_ms.is_some() {
+            return Ok(false);
+        }
+        progress.started_at_ms = Some(now_ms);
+        Ok(true)
+    }
+
+    /// Keep the result of a finished level's game (at `now_ms`)
+    pub fn record(&mut self, game: &GameState, now_ms: u64) -> Recorded {
+        let Some(level) = game.campaign_level.filter(|&level| get(level).is_some()) else {
+            return Recorded::Nothing;
+        };
+        if !game.game_over {
+            return Recorded::Nothing;
+        }
+        let progress = self.progress_mut(game.player.as_deref());
+        let index = match progress.levels.iter().position(|result| result.level == level) {
+            Some(index) => index,
+            None => {
+                progress.levels.push(LevelResult {
+                    level,
+                    attempts: 0,
+                    won: false,
+                    best_score: 0,
+                });
+                progress.levels.len() - 1
+            }
+        };
+        let result = &mut progress.levels[index];
+        result.attempts += 1;
+        if game.won {
+            result.won = true;
+            result.best_score = result.best_score.max(game.score.map_or(0, |score| score.total));
+        }
+        if game.won && level == LEVELS.len() as u32 && !progress.is_complete() {
+            progress.started_at_ms.get_or_insert(now_ms);
+            progress.completed_at_ms = Some(now_ms);
+            if let Some(time_ms) = progress.time_ms() {
+                return Recorded::Completed(time_ms);
+            }
+        }
+        Recorded::Level
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn level_game(level: u32, won: bool) -> GameState {
+        let mut game = GameState::new("RUST".to_string(), None, get(level).unwrap().difficulty);
+        game.campaign_level = Some(level);
+        if won {
+            game.guess_word("RUST");
+        } else {
+            game.give_up();
+        }
+        game
+    }
+
+    #[test]
+    fn test_levels_get_harder() {
+        assert_eq!(get(0), None);
+        assert_eq!(get(LEVELS.len() as u32 + 1), None);
+        assert_eq!(get(1).unwrap().difficulty, Difficulty::Easy);
+        assert_eq!(get(LEVELS.len() as u32).unwrap().difficulty, Difficulty::Hard);
+        for level in LEVELS {
+            assert!(level.lengths.validate().is_ok());
+        }
+    }
+
+    #[test]
+    fn test_unlocking() {
+        let mut campaign = Campaign::default();
+        assert_eq!(campaign.start(None, 2, 0), Err(CampaignError::Locked(2)));
+        assert_eq!(campaign.start(None, 99, 0), Err(CampaignError::UnknownLevel(99)));
+        assert_eq!(campaign.start(None, 1, 1_000), Ok(true));
+        assert_eq!(campaign.start(None, 1, 2_000), Ok(false));
+
+        // A lost level stays the next one, and can be retried
+        assert_eq!(campaign.record(&level_game(1, false), 3_000), Recorded::Level);
+        let progress = campaign.progress(None);
+        assert_eq!((progress.next_level(), progress.unlocked(2)), (1, false));
+        assert_eq!(campaign.record(&level_game(1, true), 4_000), Recorded::Level);
+        let progress = campaign.progress(None);
+        assert_eq!(progress.result(1).unwrap().attempts, 2);
+        assert_eq!((progress.next_level(), progress.unlocked(2)), (2, true));
+
+        // Level 4 also needs a campaign score
+        for level in 2..=3 {
+            campaign.record(&level_game(level, true), 5_000);
+        }
+        let progress = campaign.progress(None);
+        assert_eq!(progress.unlocked(4), progress.score() >= get(4).unwrap().min_score);
+        // Other players have progress of their own
+        assert!(!campaign.progress(Some("ada")).unlocked(2));
+        assert_eq!(campaign.record(&GameState::new("RUST".to_string(), None, Difficulty::Easy), 0), Recorded::Nothing);
+    }
+
+    #[test]
+    fn test_completion_time() {
+        let mut campaign = Campaign::default();
+        campaign.start(Some("ada"), 1, 1_000).unwrap();
+        for level in 1..LEVELS.len() as u32 {
+            let mut game = level_game(level, true);
+            game.player = Some("ada".to_string());
+            assert_eq!(campaign.record(&game, 2_000), Recorded::Level);
+        }
+        let mut last = level_game(LEVELS.len() as u32, true);
+        last.player = Some("ada".to_string());
+        assert_eq!(campaign.record(&last, 61_000), Recorded::Completed(60_000));
+        // Winning it again doesn't change the time
+        assert_eq!(campaign.record(&last, 90_000), Recorded::Level);
+        let progress = campaign.progress(Some("ada"));
+        assert!(progress.is_complete());
+        assert_eq!(progress.time_ms(), Some(60_000));
+    }
+}
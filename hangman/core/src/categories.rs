@@ -0,0 +1,186 @@
+// ============================================================================
+// WORD CATEGORIES
+// ============================================================================
+// Groups words into named categories (animals, tech, movies, ...) so the
+// frontend can offer themed games.
+//
+// WHERE CATEGORIES COME FROM (first match wins):
+// 1. "categories.json" in the app data directory (your own categories)
+// 2. The file bundled with the app ("resources/categories.json")
+// 3. The same bundled file compiled into the binary
+//
+// FILE FORMAT: a JSON object mapping category names to word arrays, e.g.
+// { "animals": ["ZEBRA", "OWL"], "tech": ["RUST"] }
+// A category can also be an object with a clue for its words, and a word an
+// object with a clue of its own (see "CLUES" in words.rs), e.g.
+// { "animals": { "clue": "It's an animal", "words": ["ZEBRA",
+//   { "word": "OWL", "clue": "It hoots at night" }] } }
+// The object can say what its games guess besides letters too, e.g.
+// { "tech": { "guessable": "digits + #", "words": ["C++", "WEB3"] } }
+// (see charset.rs)
+
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::Path;
+use crate::charset::Charset;
+use crate::words::{parse_json, EntryFile, WordEntry, WordList, WordListError};
+
+/// File name looked up in the app data directory
+pub const USER_CATEGORIES_FILE: &str = "categories.json";
+
+/// Built-in copy of the bundled categories, used if no file can be loaded
+const BUILTIN_CATEGORIES: &str = include_str!("../resources/categories.json");
+
+/// A category as it's written in the file: its words, or an object with its clue
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+#[serde(untagged)]
+pub enum CategoryFile {
+    Words(Vec<EntryFile>),
+    WithClue {
+        #[serde(default)]
+        clue: Option<String>,
+        // Characters guessed besides letters, e.g. "digits + #"
+        #[serde(default)]
+        guessable: Option<String>,
+        words: Vec<EntryFile>,
+    },
+}
+
+/// Named word lists, sorted by category name
+#[derive(Debug, Clone)]
+pub struct Categories {
+    categories: BTreeMap<String, WordList>,
+}
+
+impl Categories {
+    /// Parse a JSON object of category name -> words
+    /// Category names are lowercased, categories without words are rejected
+    pub fn from_json(contents: &str) -> Result<Self, WordListError> {
+        let raw: BTreeMap<String, CategoryFile> = parse_json(contents)?;
+
+        let mut categories = BTreeMap::new();
+        for (name, category) in raw {
+            let (clue, guessable, words) = match category {
+                CategoryFile::Words(words) => (None, None, words),
+                CategoryFile::WithClue { clue, guessable, words } => (clue, guessable, words),
+            };
+            let charset = guessable.as_deref().map(Charset::parse).transpose().map_err(WordListError::Charset)?;
+            let mut list = WordList::from_entries(words.into_iter().map(WordEntry::from).collect(), charset.unwrap_or_default())?;
+            if let Some(clue) = clue {
+                list = list.with_clue(&clue);
+            }
+            categories.insert(name.trim().to_lowercase(), list);
+        }
+
+        if categories.is_empty() {
+            Err(WordListError::Empty)
+        } else {
+            Ok(Categories { categories })
+        }
+    }
+
+    /// The categories compiled into the binary
+    pub fn builtin() -> Self {
+        Categories::from_json(BUILTIN_CATEGORIES).expect("built-in categories are valid")
+    }
+
+    /// Load categories from `user_dir` or `bundled`,
+    /// falling back to the built-in categories if neither can be loaded
+    pub fn load(user_dir: Option<&Path>, bundled: Option<&Path>) -> Self {
+        let user_file = user_dir.map(|dir| dir.join(USER_CATEGORIES_FILE));
+        let candidates = user_file
+            .into_iter()
+            .chain(bundled.map(Path::to_path_buf))
+            .filter(|path| path.exists());
+
+        for path in candidates {
+            let loaded = fs::read_to_string(&path)
+                .map_err(WordListError::Io)
+                .and_then(|contents| Categories::from_json(&contents));
+            match loaded {
+                Ok(categories) => return categories,
+                Err(err) => eprintln!("Skipping categories {}: {}", path.display(), err),
+            }
+        }
+
+        Categories::builtin()
+    }
+
+    /// Names of all categories, sorted
+    pub fn names(&self) -> Vec<String> {
+        self.categories.keys().cloned().collect()
+    }
+
+    /// Words in a category (names are matched case-insensitively)
+    pub fn get(&self, name: &str) -> Option<&WordList> {
+        self.categories.get(&name.trim().to_lowercase())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::charset::CharsetError;
+
+    #[test]
+    fn test_builtin_categories() {
+        let categories = Categories::builtin();
+        let names = categories.names();
+        assert!(names.contains(&"animals".to_string()));
+        assert!(names.contains(&"tech".to_string()));
+        assert!(names.contains(&"movies".to_string()));
+    }
+
+    #[test]
+    fn test_from_json_normalizes_names() {
+        let categories = Categories::from_json(r#"{"  Animals ": ["owl"]}"#).unwrap();
+        assert_eq!(categories.names(), ["animals"]);
+        assert_eq!(categories.get("ANIMALS").unwrap().words(), ["OWL"]);
+    }
+
+    #[test]
+    fn test_category_clues() {
+        let categories = Categories::from_json(
+            r#"{"animals": {"clue": "It's an animal", "words": ["zebra", {"word": "owl", "clue": "It hoots"}]}}"#,
+        )
+        .unwrap();
+        let animals = categories.get("animals").unwrap();
+        assert_eq!((animals.clue("ZEBRA"), animals.clue("OWL")), (Some("It's an animal"), Some("It hoots")));
+        assert_eq!(Categories::builtin().get("animals").unwrap().list_clue(), Some("It's an animal"));
+    }
+
+    #[test]
+    fn test_category_charsets() {
+        let categories = Categories::builtin();
+        let tech = categories.get("tech").unwrap();
+        assert_eq!(tech.charset(), &Charset::parse("digits + #").unwrap());
+        assert!(tech.words().contains(&"C++".to_string()) && tech.words().contains(&"WEB3".to_string()));
+        assert!(categories.get("animals").unwrap().charset().is_letters());
+
+        assert!(matches!(
+            Categories::from_json(r#"{"tech": {"guessable": "digit", "words": ["WEB3"]}}"#),
+            Err(WordListError::Charset(CharsetError::NotASymbol('d')))
+        ));
+    }
+
+    #[test]
+    fn test_empty_category_is_error() {
+        assert!(matches!(
+            Categories::from_json(r#"{"animals": []}"#),
+            Err(WordListError::Empty)
+        ));
+    }
+
+    #[test]
+    fn test_unknown_category() {
+        assert!(Categories::builtin().get("dinosaurs").is_none());
+    }
+
+    #[test]
+    fn test_load_falls_back_to_builtin() {
+        let categories = Categories::load(None, None);
+        assert_eq!(categories.names(), Categories::builtin().names());
+    }
+}
@@ -0,0 +1,147 @@
+// ============================================================================
+// GUESSABLE CHARACTERS
+// ============================================================================
+// Which characters of a word are hidden and guessed. Letters always are (of
+// any alphabet, see letters.rs, and digraphs, see digraphs.rs). By default
+// everything else is shown from the start, which is right for "DON'T" or
+// "ROCK & ROLL" but gives half of "WEB3" or "C++" away. A word list can also
+// make digits and a few symbols guessable, e.g. a tech list with "C++",
+// "C#" and "WEB3".
+//
+// WHERE IT'S SET:
+// - a text word list: a "#!" line, e.g. "#! guessable: digits + #"
+// - a category (see categories.rs): "guessable" next to its clue, e.g.
+//   { "tech": { "guessable": "digits + #", "words": ["C++", "WEB3"] } }
+// The words' cleanup keeps the list's guessable characters (see ingest.rs),
+// and a game picked from the list carries its charset, so the mask, the
+// keyboard (key_states), letter guesses, and the win check all go by it.
+//
+// THE SPEC:
+// Words separated by spaces: "letters" (always on, it can be left out),
+// "digits" for 0-9, and anything else is symbols, each of its characters
+// one of them. A symbol can't be a letter, a digit, a space, or "_" (the
+// mask's hidden letter), so a misspelled "digit" is an error rather than
+// five odd symbols.
+
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use crate::game::is_guessable;
+use crate::pattern::HIDDEN;
+
+/// The digits a charset with digits guesses, in the order of the keyboard
+pub const DIGITS: &str = "0123456789";
+
+/// Why a guessable spec can't be used
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CharsetError {
+    /// A symbol is a letter, a digit, or "_"
+    NotASymbol(char),
+}
+
+impl fmt::Display for CharsetError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CharsetError::NotASymbol(c) => {
+                write!(f, "{:?} can't be a guessable symbol (use \"letters\", \"digits\", and symbols like + or #)", c)
+            }
+        }
+    }
+}
+
+impl std::error::Error for CharsetError {}
+
+/// What's guessed besides letters
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+pub struct Charset {
+    // Whether 0-9 are guessed
+    #[serde(default)]
+    pub digits: bool,
+    // Symbols guessed like letters, in the order they were given
+    #[serde(default)]
+    pub symbols: Vec<char>,
+}
+
+impl Charset {
+    /// Read a spec like "letters digits + #"
+    pub fn parse(spec: &str) -> Result<Self, CharsetError> {
+        let mut charset = Charset::default();
+        for word in spec.split_whitespace() {
+            match word.to_lowercase().as_str() {
+                "letters" => {}
+                "digits" => charset.digits = true,
+                _ => {
+                    for c in word.chars() {
+                        if c.is_alphanumeric() || c == HIDDEN {
+                            return Err(CharsetError::NotASymbol(c));
+                        }
+                        if !charset.symbols.contains(&c) {
+                            charset.symbols.push(c);
+                        }
+                    }
+                }
+            }
+        }
+        Ok(charset)
+    }
+
+    /// Whether only letters are guessed (the default)
+    pub fn is_letters(&self) -> bool {
+        !self.digits && self.symbols.is_empty()
+    }
+
+    /// Whether `c` has to be guessed
+    pub fn is_guessable(&self, c: char) -> bool {
+        is_guessable(c) || (self.digits && c.is_ascii_digit()) || self.symbols.contains(&c)
+    }
+
+    /// The keys it adds to the letters': the digits, then the symbols
+    pub fn keys(&self) -> Vec<char> {
+        let digits = if self.digits { DIGITS } else { "" };
+        digits.chars().chain(self.symbols.iter().copied()).collect()
+    }
+}
+
+impl fmt::Display for Charset {
+    /// The spec it's read from, e.g. "letters digits + #"
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "letters")?;
+        if self.digits {
+            write!(f, " digits")?;
+        }
+        for c in &self.symbols {
+            write!(f, " {}", c)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse() {
+        let charset = Charset::parse("letters digits + #").unwrap();
+        assert_eq!(charset, Charset { digits: true, symbols: vec!['+', '#'] });
+        assert_eq!(Charset::parse(" Digits  ++# ").unwrap(), charset);
+        assert_eq!(charset.to_string(), "letters digits + #");
+        assert_eq!(Charset::parse(&charset.to_string()).unwrap(), charset);
+        assert!(Charset::parse("letters").unwrap().is_letters());
+        assert!(Charset::parse("").unwrap().is_letters());
+
+        assert_eq!(Charset::parse("digit"), Err(CharsetError::NotASymbol('d')));
+        assert_eq!(Charset::parse("+ 7"), Err(CharsetError::NotASymbol('7')));
+        assert_eq!(Charset::parse("_"), Err(CharsetError::NotASymbol('_')));
+    }
+
+    #[test]
+    fn test_guessable_and_keys() {
+        let charset = Charset::parse("digits +").unwrap();
+        assert!(charset.is_guessable('C') && charset.is_guessable('3') && charset.is_guessable('+'));
+        assert!(!charset.is_guessable('#') && !charset.is_guessable(' '));
+        assert!(!Charset::default().is_guessable('3'));
+        assert_eq!(charset.keys().iter().collect::<String>(), "0123456789+");
+        assert!(Charset::default().keys().is_empty());
+    }
+}
@@ -0,0 +1,441 @@
+// ============================================================================
+// CROSSWORD GAME
+// ============================================================================
+// Hangman on a small crossword: a few words cross each other on a grid, all
+// hidden, and a guessed letter is revealed in every word at once. The game is
+// won once every word is complete, and lost after the wrong guesses of the
+// difficulty plus one per word after the first.
+//
+// THE BOARD:
+// A Crossword is its words (Entry: the word, where it starts, across or
+// down) on a grid of `rows` x `cols` cells, a cell holding a letter or
+// nothing. Where two words cross they share the cell, so it has one letter.
+//
+// GENERATING ONE:
+// generate() draws words of CROSSWORD_WORD_LENGTHS letters (letters only, no
+// phrases) from a word list in a random order. The first goes across, then
+// each next word is tried at every place it crosses a word already on the
+// board at a letter they share, running the other way. A place is taken if
+// the word fits the grid (at most MAX_GRID cells each way), agrees with the
+// letters it crosses, and doesn't touch another word anywhere else (so no
+// two letters side by side spell something that isn't a word). It stops at
+// `count` words, a board needs at least two.
+//
+// GAMES:
+// CrosswordGame has rules of its own, no GameState: like the positional game
+// (see positional.rs) the desktop app plays it in sessions of its own, it
+// isn't counted in the statistics or saved.
+
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fmt;
+use std::ops::RangeInclusive;
+use sandbox_rng::{RngHandle, SliceRandom};
+use crate::difficulty::Difficulty;
+use crate::game::is_guessable;
+use crate::letters::{same_letter, to_upper};
+use crate::words::WordList;
+
+/// How many words a board has when it can fit them
+pub const CROSSWORD_WORDS: usize = 4;
+
+/// Most cells a board has across and down
+pub const MAX_GRID: usize = 12;
+
+/// Letters a word of a board may have
+pub const CROSSWORD_WORD_LENGTHS: RangeInclusive<usize> = 3..=8;
+
+/// Words tried as the first one before the list is given up on
+const STARTS: usize = 20;
+
+/// Words tried after the first (in the random order), per board
+const CANDIDATES: usize = 400;
+
+/// Reasons a crossword can't be made or a guess isn't taken
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CrosswordError {
+    /// No two words of the list cross on a board
+    NotEnoughWords,
+    /// A character that isn't a letter
+    NotALetter(char),
+    /// The letter was already guessed this game
+    AlreadyGuessed(char),
+    /// The game has already been won or lost
+    GameOver,
+}
+
+impl fmt::Display for CrosswordError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CrosswordError::NotEnoughWords => write!(
+                f,
+                "the word list has no two words of {} to {} letters that cross",
+                CROSSWORD_WORD_LENGTHS.start(),
+                CROSSWORD_WORD_LENGTHS.end()
+            ),
+            CrosswordError::NotALetter(c) => write!(f, "{:?} isn't a letter", c),
+            CrosswordError::AlreadyGuessed(c) => write!(f, "{} was already guessed", c),
+            CrosswordError::GameOver => write!(f, "the game is already over"),
+        }
+    }
+}
+
+impl std::error::Error for CrosswordError {}
+
+/// Which way a word of a board runs
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Direction {
+    Across,
+    Down,
+}
+
+impl Direction {
+    fn other(self) -> Self {
+        match self {
+            Direction::Across => Direction::Down,
+            Direction::Down => Direction::Across,
+        }
+    }
+
+    /// Row and column steps to the next letter
+    fn step(self) -> (i32, i32) {
+        match self {
+            Direction::Across => (0, 1),
+            Direction::Down => (1, 0),
+        }
+    }
+}
+
+/// A word of a board and where it is
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Entry {
+    // Uppercase
+    pub word: String,
+    // Of its first letter, counting from 0
+    pub row: usize,
+    pub col: usize,
+    pub direction: Direction,
+}
+
+impl Entry {
+    /// Each letter of the word with its row and column
+    pub fn cells(&self) -> impl Iterator<Item = (usize, usize, char)> + '_ {
+        let (down, across) = self.direction.step();
+        self.word
+            .chars()
+            .enumerate()
+            .map(move |(i, c)| (self.row + i * down as usize, self.col + i * across as usize, c))
+    }
+}
+
+/// Words crossing on a grid
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Crossword {
+    // In the order they were placed
+    pub entries: Vec<Entry>,
+    pub rows: usize,
+    pub cols: usize,
+}
+
+/// A board being built, on cells that can go either side of the first word
+#[derive(Default)]
+struct Layout {
+    // Each cell's letter and the directions of the words through it
+    cells: BTreeMap<(i32, i32), (char, Vec<Direction>)>,
+    entries: Vec<(String, i32, i32, Direction)>,
+}
+
+impl Layout {
+    fn letter(&self, row: i32, col: i32) -> Option<char> {
+        self.cells.get(&(row, col)).map(|(c, _)| *c)
+    }
+
+    /// The rows and columns the cells span, with a word at `extra` too
+    fn bounds(&self, extra: impl Iterator<Item = (i32, i32)>) -> (i32, i32, i32, i32) {
+        self.cells.keys().copied().chain(extra).fold(
+            (i32::MAX, i32::MIN, i32::MAX, i32::MIN),
+            |(top, bottom, left, right), (row, col)| (top.min(row), bottom.max(row), left.min(col), right.max(col)),
+        )
+    }
+
+    /// Whether `word` can start at `row`, `col` running `direction`
+    fn fits(&self, word: &[char], row: i32, col: i32, direction: Direction) -> bool {
+        let (dr, dc) = direction.step();
+        let at = |i: i32| (row + i * dr, col + i * dc);
+        let len = word.len() as i32;
+        // Nothing right before or after it
+        let (before, after) = (at(-1), at(len));
+        if self.letter(before.0, before.1).is_some() || self.letter(after.0, after.1).is_some() {
+            return false;
+        }
+        let mut crossings = 0;
+        for (i, &c) in word.iter().enumerate() {
+            let (r, cl) = at(i as i32);
+            match self.cells.get(&(r, cl)) {
+                Some((letter, directions)) => {
+                    if *letter != c || directions.contains(&direction) {
+                        return false;
+                    }
+                    crossings += 1;
+                }
+                None => {
+                    // A cell of its own touches no other word's side
+                    if self.letter(r + dc, cl + dr).is_some() || self.letter(r - dc, cl - dr).is_some() {
+                        return false;
+                    }
+                }
+            }
+        }
+        let (top, bottom, left, right) = self.bounds((0..len).map(at));
+        crossings > 0 && bottom - top < MAX_GRID as i32 && right - left < MAX_GRID as i32
+    }
+
+    fn place(&mut self, word: &[char], row: i32, col: i32, direction: Direction) {
+        let (dr, dc) = direction.step();
+        for (i, &c) in word.iter().enumerate() {
+            let cell = (row + i as i32 * dr, col + i as i32 * dc);
+            self.cells.entry(cell).or_insert((c, Vec::new())).1.push(direction);
+        }
+        self.entries.push((word.iter().collect(), row, col, direction));
+    }
+
+    /// Place `word` crossing a word of the board, at the first place it fits
+    fn cross(&mut self, word: &[char]) -> bool {
+        for e in 0..self.entries.len() {
+            let (placed, row, col, direction) = self.entries[e].clone();
+            let (dr, dc) = direction.step();
+            for (i, c) in placed.chars().enumerate() {
+                for j in (0..word.len()).filter(|&j| word[j] == c) {
+                    // Letter j of the word on letter i of the placed one
+                    let (r, cl) = (row + i as i32 * dr, col + i as i32 * dc);
+                    let (sr, sc) = direction.other().step();
+                    let (start_row, start_col) = (r - j as i32 * sr, cl - j as i32 * sc);
+                    if self.fits(word, start_row, start_col, direction.other()) {
+                        self.place(word, start_row, start_col, direction.other());
+                        return true;
+                    }
+                }
+            }
+        }
+        false
+    }
+
+    /// The board, moved so its top left cell is row 0, column 0
+    fn finish(self) -> Crossword {
+        let (top, bottom, left, right) = self.bounds(std::iter::empty());
+        let entries = self
+            .entries
+            .into_iter()
+            .map(|(word, row, col, direction)| Entry {
+                word,
+                row: (row - top) as usize,
+                col: (col - left) as usize,
+                direction,
+            })
+            .collect();
+        Crossword {
+            entries,
+            rows: (bottom - top + 1) as usize,
+            cols: (right - left + 1) as usize,
+        }
+    }
+}
+
+impl Crossword {
+    /// A board of up to `count` crossing words of `words`, drawn from `rng`
+    pub fn generate(words: &WordList, count: usize, rng: &mut RngHandle) -> Result<Self, CrosswordError> {
+        let mut pool: Vec<Vec<char>> = words
+            .words()
+            .iter()
+            .filter(|word| word.chars().all(is_guessable) && CROSSWORD_WORD_LENGTHS.contains(&word.chars().count()))
+            .map(|word| word.chars().collect())
+            .collect();
+        pool.shuffle(rng);
+
+        let mut best: Option<Layout> = None;
+        for start in 0..pool.len().min(STARTS) {
+            let mut layout = Layout::default();
+            layout.place(&pool[start], 0, 0, Direction::Across);
+            for word in pool.iter().skip(start + 1).take(CANDIDATES) {
+                if layout.entries.len() >= count {
+                    break;
+                }
+                layout.cross(word);
+            }
+            let placed = layout.entries.len();
+            if best.as_ref().is_none_or(|best| placed > best.entries.len()) {
+                best = Some(layout);
+            }
+            if placed >= count {
+                break;
+            }
+        }
+        match best {
+            Some(layout) if layout.entries.len() >= 2 => Ok(layout.finish()),
+            _ => Err(CrosswordError::NotEnoughWords),
+        }
+    }
+
+    /// Every cell with a letter, by row and column
+    pub fn cells(&self) -> BTreeMap<(usize, usize), char> {
+        self.entries.iter().flat_map(Entry::cells).map(|(row, col, c)| ((row, col), c)).collect()
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CrosswordGame {
+    pub board: Crossword,
+    // Uppercase, oldest first
+    pub guessed_letters: Vec<char>,
+    pub wrong_guesses: u32,
+    pub max_wrong_guesses: u32,
+    pub difficulty: Difficulty,
+    // Whether "E" finds "É" too (the settings' ignore_accents)
+    pub ignore_accents: bool,
+    pub won: bool,
+    pub game_over: bool,
+}
+
+impl CrosswordGame {
+    pub fn new(board: Crossword, difficulty: Difficulty) -> Self {
+        let max_wrong_guesses = difficulty.max_wrong_guesses() + board.entries.len() as u32 - 1;
+        CrosswordGame {
+            board,
+            guessed_letters: Vec::new(),
+            wrong_guesses: 0,
+            max_wrong_guesses,
+            difficulty,
+            ignore_accents: false,
+            won: false,
+            game_over: false,
+        }
+    }
+
+    /// A game on a board of CROSSWORD_WORDS words of `words`, drawn from `rng`
+    pub fn generate(words: &WordList, difficulty: Difficulty, rng: &mut RngHandle) -> Result<Self, CrosswordError> {
+        Ok(CrosswordGame::new(Crossword::generate(words, CROSSWORD_WORDS, rng)?, difficulty))
+    }
+
+    /// Guess `letter`, revealing it in every word, returns whether any word has it
+    pub fn guess(&mut self, letter: char) -> Result<bool, CrosswordError> {
+        if self.game_over {
+            return Err(CrosswordError::GameOver);
+        }
+        if !is_guessable(letter) {
+            return Err(CrosswordError::NotALetter(letter));
+        }
+        let letter = to_upper(letter);
+        if self.is_guessed(letter) {
+            return Err(CrosswordError::AlreadyGuessed(letter));
+        }
+        self.guessed_letters.push(letter);
+
+        let cells = self.board.cells();
+        let hit = cells.values().any(|&c| same_letter(c, letter, self.ignore_accents));
+        if !hit {
+            self.wrong_guesses += 1;
+        }
+        self.won = cells.values().all(|&c| self.is_guessed(c));
+        self.game_over = self.won || self.wrong_guesses >= self.max_wrong_guesses;
+        Ok(hit)
+    }
+
+    fn is_guessed(&self, c: char) -> bool {
+        self.guessed_letters.iter().any(|&guessed| same_letter(guessed, c, self.ignore_accents))
+    }
+
+    /// Whether the letter in a cell is shown (all of them once the game is over)
+    pub fn is_revealed(&self, c: char) -> bool {
+        self.game_over || self.is_guessed(c)
+    }
+
+    /// Whether every letter of `entry` is found
+    pub fn is_complete(&self, entry: &Entry) -> bool {
+        entry.word.chars().all(|c| self.is_guessed(c))
+    }
+
+    /// Wrong guesses left before the game is lost
+    pub fn remaining(&self) -> u32 {
+        self.max_wrong_guesses.saturating_sub(self.wrong_guesses)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn board() -> Crossword {
+        // R U S T
+        // . . . O
+        // . . . E
+        let mut layout = Layout::default();
+        layout.place(&['R', 'U', 'S', 'T'], 0, 0, Direction::Across);
+        assert!(layout.cross(&['T', 'O', 'E']));
+        layout.finish()
+    }
+
+    #[test]
+    fn test_words_cross_without_touching() {
+        let board = board();
+        assert_eq!((board.rows, board.cols), (3, 4));
+        assert_eq!(board.entries[1], Entry { word: "TOE".to_string(), row: 0, col: 3, direction: Direction::Down });
+        assert_eq!(board.cells().len(), 6);
+
+        let mut layout = Layout::default();
+        layout.place(&['R', 'U', 'S', 'T'], 0, 0, Direction::Across);
+        // No shared letter, or side by side with RUST
+        assert!(!layout.cross(&['Q', 'Q', 'Q']));
+        assert!(!layout.fits(&['A', 'B', 'C'], 1, 0, Direction::Across));
+        // Running into RUST's end
+        assert!(!layout.fits(&['S', 'A', 'T'], 0, 2, Direction::Across));
+    }
+
+    #[test]
+    fn test_generate() {
+        let words = WordList::from_text("rust\ncrab\ncargo\nferris\ntrait\nice cream\nox").unwrap();
+        for seed in 0..10 {
+            let board = Crossword::generate(&words, CROSSWORD_WORDS, &mut RngHandle::seeded(seed)).unwrap();
+            assert!((2..=CROSSWORD_WORDS).contains(&board.entries.len()), "{}", seed);
+            assert!(board.rows <= MAX_GRID && board.cols <= MAX_GRID);
+            assert!(board.entries.iter().all(|entry| entry.word != "ICE CREAM" && entry.word != "OX"));
+            // Every letter agrees with every word through its cell
+            let cells = board.cells();
+            assert!(board.entries.iter().flat_map(Entry::cells).all(|(row, col, c)| cells[&(row, col)] == c));
+        }
+        let words = WordList::from_text("rust\nzzz").unwrap();
+        assert_eq!(Crossword::generate(&words, 4, &mut RngHandle::seeded(1)), Err(CrosswordError::NotEnoughWords));
+    }
+
+    #[test]
+    fn test_letters_are_revealed_in_every_word() {
+        let mut game = CrosswordGame::new(board(), Difficulty::Hard);
+        assert_eq!(game.max_wrong_guesses, 5);
+        assert_eq!(game.guess('t'), Ok(true));
+        assert!(game.is_revealed('T') && !game.is_revealed('R'));
+        assert_eq!(game.guess('T'), Err(CrosswordError::AlreadyGuessed('T')));
+        assert_eq!(game.guess('1'), Err(CrosswordError::NotALetter('1')));
+        assert_eq!(game.guess('X'), Ok(false));
+        assert_eq!(game.remaining(), 4);
+
+        for letter in ['O', 'E'] {
+            game.guess(letter).unwrap();
+        }
+        assert!(game.is_complete(&game.board.entries[1]) && !game.is_complete(&game.board.entries[0]));
+        for letter in ['R', 'U', 'S'] {
+            game.guess(letter).unwrap();
+        }
+        assert!(game.won && game.game_over);
+        assert_eq!(game.guess('A'), Err(CrosswordError::GameOver));
+    }
+
+    #[test]
+    fn test_losing_reveals_the_board() {
+        let mut game = CrosswordGame::new(board(), Difficulty::Hard);
+        for letter in "ZQXJK".chars() {
+            assert_eq!(game.guess(letter), Ok(false));
+        }
+        assert!(game.game_over && !game.won);
+        assert!(game.is_revealed('R'));
+    }
+}
@@ -0,0 +1,217 @@
+// ============================================================================
+// IMPORTED WORD LISTS
+// ============================================================================
+// Players can import their own words from a file, which are then picked from
+// along with the main word list (see CustomWords::pool). Each player (and
+// playing without a profile) has their own imported words.
+//
+// FILE FORMATS (picked from the extension):
+// - ".json": a JSON array of strings, like a user word list (see words.rs)
+// - ".csv": words separated by commas and/or lines, quotes around a word are dropped
+// - anything else: one word per line, blank lines and "#" comments ignored
+//
+// WHAT'S KEPT:
+// Every entry is cleaned up like the words of any list (see ingest.rs):
+// characters other than letters and spaces are stripped, and it needs
+// between MIN_WORD_LETTERS letters and MAX_WORD_LEN characters. Entries
+// already in the pool (or earlier in the file) are dropped as duplicates. import returns what happened to each entry, so the
+// frontend can show a summary.
+//
+// SAVING:
+// Imported words are a sandbox-store document (key CUSTOM_WORDS_KEY,
+// "custom_words.json" next to the statistics), kept in the desktop app's
+// database (see storage.rs).
+
+use sandbox_store::{Schema, Store, StoreError};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+use std::fs;
+use std::path::Path;
+use crate::ingest::{clean_word, Rejection};
+use crate::words::{parse_json, WordList, WordListError};
+
+/// Store key the imported words are saved under
+pub const CUSTOM_WORDS_KEY: &str = "custom_words";
+
+/// The entries of a word file, as written (before any checks)
+pub fn read_entries(path: &Path) -> Result<Vec<String>, WordListError> {
+    let contents = fs::read_to_string(path).map_err(WordListError::Io)?;
+    let extension = path
+        .extension()
+        .and_then(|ext| ext.to_str())
+        .map(str::to_ascii_lowercase);
+    match extension.as_deref() {
+        Some("json") => parse_json(&contents),
+        Some("csv") => Ok(parse_csv(&contents)),
+        _ => Ok(contents
+            .lines()
+            .map(str::trim)
+            .filter(|line| !line.is_empty() && !line.starts_with('#'))
+            .map(str::to_string)
+            .collect()),
+    }
+}
+
+/// Every cell of a CSV file (words can't contain commas, so no quoting rules are needed)
+fn parse_csv(contents: &str) -> Vec<String> {
+    contents
+        .lines()
+        .flat_map(|line| line.split(','))
+        .map(|cell| cell.trim().trim_matches('"').trim())
+        .filter(|cell| !cell.is_empty())
+        .map(str::to_string)
+        .collect()
+}
+
+/// What import did with each entry of a file
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ImportReport {
+    // Added to the pool, cleaned up like every word
+    pub accepted: Vec<String>,
+    // Already in the pool, or earlier in the file
+    pub duplicates: Vec<String>,
+    // As written, with the reason
+    pub rejected: Vec<(String, Rejection)>,
+}
+
+/// One player's imported words
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+pub struct PlayerWords {
+    // Profile they were imported by (None if no profile was selected)
+    pub player: Option<String>,
+    pub words: Vec<String>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+pub struct CustomWords {
+    pub lists: Vec<PlayerWords>,
+}
+
+impl Schema for CustomWords {
+    const VERSION: u32 = 1;
+}
+
+impl CustomWords {
+    /// Load the imported words, starting with none if nothing was saved yet
+    pub fn load(store: &Store) -> Result<Self, StoreError> {
+        Ok(store.load(CUSTOM_WORDS_KEY)?.unwrap_or_default())
+    }
+
+    /// Save the imported words, replacing the previous copy
+    pub fn save(&self, store: &Store) -> Result<(), StoreError> {
+        store.save(CUSTOM_WORDS_KEY, self)
+    }
+
+    /// The words `player` (None for no profile) imported, oldest first
+    pub fn words(&self, player: Option<&str>) -> &[String] {
+        self.lists
+            .iter()
+            .find(|list| list.player.as_deref() == player)
+            .map_or(&[], |list| &list.words)
+    }
+
+    /// Check `entries` and add the new ones to what `player` imported,
+    /// `base` being the word list they're played with
+    pub fn import<I, S>(&mut self, player: Option<&str>, entries: I, base: &WordList) -> ImportReport
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<str>,
+    {
+        let mut seen: HashSet<String> = base.words().iter().chain(self.words(player)).cloned().collect();
+        let mut report = ImportReport::default();
+        for entry in entries {
+            let entry = entry.as_ref();
+            match clean_word(entry) {
+                Ok(word) if seen.contains(&word) => report.duplicates.push(word),
+                Ok(word) => {
+                    seen.insert(word.clone());
+                    report.accepted.push(word);
+                }
+                Err(err) => report.rejected.push((entry.to_string(), err)),
+            }
+        }
+
+        if !report.accepted.is_empty() {
+            match self.lists.iter_mut().find(|list| list.player.as_deref() == player) {
+                Some(list) => list.words.extend(report.accepted.iter().cloned()),
+                None => self.lists.push(PlayerWords {
+                    player: player.map(str::to_string),
+                    words: report.accepted.clone(),
+                }),
+            }
+        }
+        report
+    }
+
+    /// The words `player` plays with: `base` and the words they imported
+    pub fn pool(&self, player: Option<&str>, base: &WordList) -> WordList {
+        let imported = self.words(player);
+        if imported.is_empty() {
+            return base.clone();
+        }
+        base.derive(base.words().iter().chain(imported)).expect("the base word list is not empty")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn base() -> WordList {
+        WordList::from_text("rust\nferris").unwrap()
+    }
+
+    #[test]
+    fn test_import_checks_entries() {
+        let mut custom = CustomWords::default();
+        let report = custom.import(None, ["cargo", " ice  cream ", "Rust!", "x", "42", "CARGO"], &base());
+        assert_eq!(report.accepted, ["CARGO", "ICE CREAM"]);
+        assert_eq!(report.duplicates, ["RUST", "CARGO"]);
+        assert_eq!(report.rejected, [
+            ("x".to_string(), Rejection::TooShort),
+            ("42".to_string(), Rejection::NoLetters),
+        ]);
+
+        // Importing the same file again adds nothing
+        let report = custom.import(None, ["cargo"], &base());
+        assert!(report.accepted.is_empty());
+        assert_eq!(custom.words(None), ["CARGO", "ICE CREAM"]);
+    }
+
+    #[test]
+    fn test_pool_is_per_player() {
+        let mut custom = CustomWords::default();
+        custom.import(Some("ada"), ["cargo"], &base());
+        assert_eq!(custom.pool(Some("ada"), &base()).words(), ["RUST", "FERRIS", "CARGO"]);
+        assert_eq!(custom.pool(None, &base()).words(), ["RUST", "FERRIS"]);
+        assert_eq!(custom.import(Some("grace"), ["cargo"], &base()).accepted, ["CARGO"]);
+    }
+
+    #[test]
+    fn test_read_entries() {
+        let dir = tempfile::tempdir().unwrap();
+        let csv = dir.path().join("words.csv");
+        fs::write(&csv, "rust, \"cargo\"\ntrait,,\n").unwrap();
+        assert_eq!(read_entries(&csv).unwrap(), ["rust", "cargo", "trait"]);
+
+        let txt = dir.path().join("words.txt");
+        fs::write(&txt, "# mine\nrust\n\nice cream\n").unwrap();
+        assert_eq!(read_entries(&txt).unwrap(), ["rust", "ice cream"]);
+
+        let json = dir.path().join("words.JSON");
+        fs::write(&json, r#"["rust", 7]"#).unwrap();
+        assert!(matches!(read_entries(&json), Err(WordListError::Invalid(_))));
+        assert!(matches!(read_entries(&dir.path().join("missing.txt")), Err(WordListError::Io(_))));
+    }
+
+    #[test]
+    fn test_save_and_load() {
+        let dir = tempfile::tempdir().unwrap();
+        let store = Store::open(dir.path()).unwrap();
+        let mut custom = CustomWords::default();
+        custom.import(Some("ada"), ["cargo"], &base());
+        custom.save(&store).unwrap();
+        assert_eq!(CustomWords::load(&store).unwrap(), custom);
+    }
+}
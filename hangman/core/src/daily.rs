@@ -0,0 +1,171 @@
+// ============================================================================
+// DAILY CHALLENGE
+// ============================================================================
+// One puzzle a day, the same for every player: the word is picked with an rng
+// seeded from the (UTC) date, so everyone on the same word list and difficulty
+// gets the same word, and tomorrow brings a new one. The CLI's --daily and the
+// desktop app's daily game both start it with GameState::new_daily.
+//
+// DAY NUMBERS:
+// Days are counted from 1970-01-01 (see sandbox_rng::day_number), so a daily
+// game is "daily #20375" and the number only depends on the date.
+//
+// PLAYING ONCE:
+// Finished daily games are saved as a sandbox-store document (key DAILY_KEY,
+// "daily.json" next to the statistics), one result per day and player. The
+// desktop app refuses to start a day's challenge again once it's in there.
+// An abandoned daily game isn't a result, so it can be started again.
+
+use sandbox_rng::{daily_seed, day_number, RngHandle};
+use sandbox_store::{Schema, Store, StoreError};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use std::time::SystemTime;
+use crate::difficulty::Difficulty;
+use crate::game::GameState;
+use crate::words::WordList;
+
+/// Store key the daily results are saved under
+pub const DAILY_KEY: &str = "daily";
+
+impl GameState {
+    /// Start the daily challenge of the day `time` falls on
+    /// The word (and the hint letters) come from that day's seed
+    pub fn new_daily(words: &WordList, difficulty: Difficulty, time: SystemTime) -> Self {
+        let mut rng = RngHandle::seeded(daily_seed(time));
+        let word = difficulty.pick_word(words, &mut rng);
+        let mut game = GameState::new(word, None, difficulty);
+        game.clue = words.clue(&game.word).map(str::to_string);
+        game.daily = Some(day_number(time));
+        game.rng = rng;
+        game
+    }
+}
+
+/// How a player did on one day's challenge
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+pub struct DailyResult {
+    pub day: u64,
+    // Profile that played it (None if no profile was selected)
+    pub player: Option<String>,
+    pub word: String,
+    pub won: bool,
+    pub wrong_guesses: u32,
+    pub score: u32,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+pub struct Dailies {
+    // In the order they were played
+    pub results: Vec<DailyResult>,
+}
+
+impl Schema for Dailies {
+    const VERSION: u32 = 1;
+}
+
+impl Dailies {
+    /// Load saved daily results, starting with none if nothing was saved yet
+    pub fn load(store: &Store) -> Result<Self, StoreError> {
+        Ok(store.load(DAILY_KEY)?.unwrap_or_default())
+    }
+
+    /// Save the daily results, replacing the previous copy
+    pub fn save(&self, store: &Store) -> Result<(), StoreError> {
+        store.save(DAILY_KEY, self)
+    }
+
+    /// The result of `player` (None for no profile) on `day`, if they finished it
+    pub fn get(&self, day: u64, player: Option<&str>) -> Option<&DailyResult> {
+        self.results
+            .iter()
+            .find(|result| result.day == day && result.player.as_deref() == player)
+    }
+
+    /// Keep the result of a finished daily game
+    /// Returns false (and keeps nothing) for other games, unfinished ones,
+    /// and a day the player already has a result for
+    pub fn record(&mut self, game: &GameState) -> bool {
+        let Some(day) = game.daily else {
+            return false;
+        };
+        if !game.game_over || self.get(day, game.player.as_deref()).is_some() {
+            return false;
+        }
+        self.results.push(DailyResult {
+            day,
+            player: game.player.clone(),
+            word: game.word.clone(),
+            won: game.won,
+            wrong_guesses: game.wrong_guesses,
+            score: game.score.map_or(0, |score| score.total),
+        });
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::{Duration, UNIX_EPOCH};
+
+    const DAY: u64 = 24 * 60 * 60;
+
+    fn words() -> WordList {
+        WordList::from_text("rust\nferris\ncargo\ntrait\nborrow\nclosure\nmacro\nlifetime").unwrap()
+    }
+
+    fn day(n: u64) -> SystemTime {
+        UNIX_EPOCH + Duration::from_secs(n * DAY + 3600)
+    }
+
+    #[test]
+    fn test_same_word_all_day() {
+        let morning = GameState::new_daily(&words(), Difficulty::Medium, day(20_000));
+        let evening = GameState::new_daily(&words(), Difficulty::Medium, day(20_000) + Duration::from_secs(20 * 3600));
+        assert_eq!(morning.word, evening.word);
+        assert_eq!(morning.daily, Some(20_000));
+
+        // Some day in the next few picks another word
+        let words = words();
+        assert!((1..10).any(|n| GameState::new_daily(&words, Difficulty::Medium, day(20_000 + n)).word != morning.word));
+    }
+
+    #[test]
+    fn test_record_once_per_day_and_player() {
+        let mut dailies = Dailies::default();
+        let mut game = GameState::new_daily(&words(), Difficulty::Medium, day(20_000));
+        assert!(!dailies.record(&game)); // Not finished yet
+
+        let word = game.word.clone();
+        assert!(game.guess_word(&word));
+        assert!(dailies.record(&game));
+        assert!(!dailies.record(&game));
+        assert!(dailies.get(20_000, None).unwrap().won);
+        assert!(dailies.get(20_001, None).is_none());
+
+        game.player = Some("ada".to_string());
+        assert!(dailies.get(20_000, Some("ada")).is_none());
+        assert!(dailies.record(&game));
+
+        // Games that aren't dailies are never recorded
+        let mut classic = GameState::new("rust".to_string(), None, Difficulty::Medium);
+        assert!(classic.guess_word("rust"));
+        assert!(!dailies.record(&classic));
+        assert_eq!(dailies.results.len(), 2);
+    }
+
+    #[test]
+    fn test_save_and_load() {
+        let dir = tempfile::tempdir().unwrap();
+        let store = Store::open(dir.path()).unwrap();
+        let mut game = GameState::new_daily(&words(), Difficulty::Easy, day(20_000));
+        let word = game.word.clone();
+        assert!(game.guess_word(&word));
+
+        let mut dailies = Dailies::default();
+        dailies.record(&game);
+        dailies.save(&store).unwrap();
+        assert_eq!(Dailies::load(&store).unwrap(), dailies);
+    }
+}
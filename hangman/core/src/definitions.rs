@@ -0,0 +1,245 @@
+// ============================================================================
+// WORD DEFINITIONS
+// ============================================================================
+// When a game ends the desktop app shows what the answer means, e.g.
+// "RUST: a reddish or yellowish-brown flaking coating of iron oxide".
+// Definitions come from a free dictionary API (DICTIONARY_URL, see
+// https://dictionaryapi.dev), one request per word.
+//
+// OFFLINE:
+// Every definition fetched is kept in a DefinitionCache, a sandbox-store
+// document (key DEFINITIONS_KEY, "definitions.json" next to the statistics),
+// so a word is only ever fetched once and cached words work offline. A word
+// the dictionary doesn't know (or that can't be fetched) has no definition,
+// which isn't an error: the UI just doesn't show one.
+//
+// THE HTTP CLIENT:
+// Fetching needs the "dictionary" feature (a small blocking client, ureq),
+// which the desktop app turns on. Parsing and the cache don't, so they're
+// tested without a network.
+
+use sandbox_store::{Schema, Store, StoreError};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fmt;
+use crate::letters::lower_str;
+
+/// Store key the definition cache is saved under
+pub const DEFINITIONS_KEY: &str = "definitions";
+
+/// Where English words are looked up (the word is appended to it)
+pub const DICTIONARY_URL: &str = "https://api.dictionaryapi.dev/api/v2/entries/en";
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+pub struct Definition {
+    // As the game shows it (uppercase)
+    pub word: String,
+    // "noun", "verb", ... when the dictionary says
+    pub part_of_speech: Option<String>,
+    pub definition: String,
+}
+
+/// Reasons a definition couldn't be fetched
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DefinitionError {
+    /// The request failed (no connection, a timeout, a server error)
+    Http(String),
+    /// The dictionary answered with something that isn't a list of entries
+    Parse(String),
+}
+
+impl fmt::Display for DefinitionError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            DefinitionError::Http(message) => write!(f, "dictionary request failed: {}", message),
+            DefinitionError::Parse(message) => write!(f, "unexpected dictionary response: {}", message),
+        }
+    }
+}
+
+impl std::error::Error for DefinitionError {}
+
+/// Definitions fetched so far, by lowercase word
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+pub struct DefinitionCache {
+    pub definitions: BTreeMap<String, Definition>,
+}
+
+impl Schema for DefinitionCache {
+    const VERSION: u32 = 1;
+}
+
+impl DefinitionCache {
+    /// Load the saved cache, starting empty if nothing was saved yet
+    pub fn load(store: &Store) -> Result<Self, StoreError> {
+        Ok(store.load(DEFINITIONS_KEY)?.unwrap_or_default())
+    }
+
+    /// Save the cache, replacing the previous copy
+    pub fn save(&self, store: &Store) -> Result<(), StoreError> {
+        store.save(DEFINITIONS_KEY, self)
+    }
+
+    /// The cached definition of `word` (any case)
+    pub fn get(&self, word: &str) -> Option<&Definition> {
+        self.definitions.get(&lower_str(word))
+    }
+
+    pub fn insert(&mut self, definition: Definition) {
+        self.definitions.insert(lower_str(&definition.word), definition);
+    }
+}
+
+// The parts of a dictionaryapi.dev entry that are used:
+// [{ "word": "rust", "meanings": [{ "partOfSpeech": "noun", "definitions": [{ "definition": "..." }] }] }]
+#[derive(Deserialize)]
+struct Entry {
+    #[serde(default)]
+    meanings: Vec<Meaning>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct Meaning {
+    part_of_speech: Option<String>,
+    #[serde(default)]
+    definitions: Vec<Sense>,
+}
+
+#[derive(Deserialize)]
+struct Sense {
+    definition: String,
+}
+
+/// The first definition of `word` in a dictionary response
+/// (None if the entries don't have a definition)
+pub fn parse_entries(word: &str, json: &str) -> Result<Option<Definition>, DefinitionError> {
+    let entries: Vec<Entry> = serde_json::from_str(json).map_err(|err| DefinitionError::Parse(err.to_string()))?;
+    let first = entries
+        .into_iter()
+        .flat_map(|entry| entry.meanings)
+        .find_map(|meaning| {
+            let sense = meaning.definitions.into_iter().find(|sense| !sense.definition.trim().is_empty())?;
+            Some((meaning.part_of_speech, sense.definition))
+        });
+    Ok(first.map(|(part_of_speech, definition)| Definition {
+        word: word.to_string(),
+        part_of_speech,
+        definition: definition.trim().to_string(),
+    }))
+}
+
+/// `word` as a URL path segment (lowercase, anything but ASCII letters, digits, "-", and "_"
+/// percent-encoded, so it can't be read as ".." or a query)
+#[cfg(any(feature = "dictionary", test))]
+fn path_segment(word: &str) -> String {
+    let mut segment = String::new();
+    for byte in lower_str(word).bytes() {
+        if byte.is_ascii_alphanumeric() || matches!(byte, b'-' | b'_') {
+            segment.push(char::from(byte));
+        } else {
+            segment.push_str(&format!("%{:02X}", byte));
+        }
+    }
+    segment
+}
+
+/// A dictionary API client
+#[cfg(feature = "dictionary")]
+#[derive(Debug, Clone)]
+pub struct Dictionary {
+    base_url: String,
+    agent: ureq::Agent,
+}
+
+#[cfg(feature = "dictionary")]
+impl Dictionary {
+    /// Seconds a lookup may take before it's given up on
+    pub const TIMEOUT_SECS: u64 = 5;
+
+    /// A client for the API at `base_url` (e.g. DICTIONARY_URL)
+    pub fn new(base_url: impl Into<String>) -> Self {
+        Dictionary {
+            base_url: base_url.into().trim_end_matches('/').to_string(),
+            agent: ureq::AgentBuilder::new()
+                .timeout(std::time::Duration::from_secs(Self::TIMEOUT_SECS))
+                .build(),
+        }
+    }
+
+    /// Fetch the definition of `word`, None if the dictionary doesn't know it
+    /// Blocks until the request is done, call it off the main thread
+    pub fn lookup(&self, word: &str) -> Result<Option<Definition>, DefinitionError> {
+        let url = format!("{}/{}", self.base_url, path_segment(word));
+        match self.agent.get(&url).call() {
+            Ok(response) => {
+                let body = response.into_string().map_err(|err| DefinitionError::Http(err.to_string()))?;
+                parse_entries(word, &body)
+            }
+            // The API answers unknown words with a 404
+            Err(ureq::Error::Status(404, _)) => Ok(None),
+            Err(err) => Err(DefinitionError::Http(err.to_string())),
+        }
+    }
+}
+
+#[cfg(feature = "dictionary")]
+impl Default for Dictionary {
+    fn default() -> Self {
+        Dictionary::new(DICTIONARY_URL)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const RESPONSE: &str = r#"[{
+        "word": "rust",
+        "phonetics": [],
+        "meanings": [
+            { "partOfSpeech": "noun", "definitions": [
+                { "definition": "A reddish or yellowish-brown flaking coating of iron oxide.", "synonyms": [] },
+                { "definition": "A plant disease." }
+            ]},
+            { "partOfSpeech": "verb", "definitions": [{ "definition": "To oxidize." }] }
+        ]
+    }]"#;
+
+    #[test]
+    fn test_parse_entries() {
+        let definition = parse_entries("RUST", RESPONSE).unwrap().unwrap();
+        assert_eq!(definition.word, "RUST");
+        assert_eq!(definition.part_of_speech.as_deref(), Some("noun"));
+        assert_eq!(definition.definition, "A reddish or yellowish-brown flaking coating of iron oxide.");
+
+        assert_eq!(parse_entries("RUST", r#"[{"word": "rust", "meanings": []}]"#).unwrap(), None);
+        assert!(matches!(
+            parse_entries("RUST", r#"{"title": "No Definitions Found"}"#),
+            Err(DefinitionError::Parse(_))
+        ));
+    }
+
+    #[test]
+    fn test_path_segment() {
+        assert_eq!(path_segment("RUST"), "rust");
+        assert_eq!(path_segment("ICE CREAM"), "ice%20cream");
+        assert_eq!(path_segment("CAFÉ"), "caf%C3%A9");
+        assert_eq!(path_segment("../x?y"), "%2E%2E%2Fx%3Fy");
+    }
+
+    #[test]
+    fn test_cache_survives_restarts() {
+        let dir = tempfile::tempdir().unwrap();
+        let store = Store::open(dir.path()).unwrap();
+        let mut cache = DefinitionCache::default();
+        cache.insert(parse_entries("RUST", RESPONSE).unwrap().unwrap());
+        cache.save(&store).unwrap();
+
+        let cache = DefinitionCache::load(&store).unwrap();
+        assert_eq!(cache.get("rust").unwrap().part_of_speech.as_deref(), Some("noun"));
+        assert_eq!(cache.get("Rust").unwrap().word, "RUST");
+        assert!(cache.get("ferris").is_none());
+    }
+}
@@ -0,0 +1,182 @@
+// ============================================================================
+// DIFFICULTY LEVELS
+// ============================================================================
+// Easy/medium/hard games differ in two ways:
+// 1. Which words can be picked - every word gets a difficulty score and the
+//    word list is split into thirds (easiest third, middle third, hardest third)
+// 2. How many wrong guesses the player is allowed
+//
+// SCORING HEURISTIC:
+// Each distinct letter in a word adds its "rarity" (how uncommon it is in
+// English text). Words with more distinct letters take more guesses to solve,
+// and rare letters like J, Q, X, Z are rarely guessed early, so both push the
+// score up.
+
+use sandbox_rng::{Rng, RngHandle};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use crate::game::is_guessable;
+use crate::letters::fold;
+use crate::words::WordList;
+
+/// How hard a game is
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize, JsonSchema)]
+#[cfg_attr(feature = "uniffi", derive(uniffi::Enum))]
+#[serde(rename_all = "lowercase")]
+pub enum Difficulty {
+    Easy,
+    #[default]
+    Medium,
+    Hard,
+}
+
+impl Difficulty {
+    /// Number of wrong guesses allowed before the game is lost
+    pub fn max_wrong_guesses(self) -> u32 {
+        match self {
+            Difficulty::Easy => 8,
+            Difficulty::Medium => 6,
+            Difficulty::Hard => 4,
+        }
+    }
+
+    /// What a game's score is multiplied by (see score.rs)
+    pub fn score_multiplier(self) -> u32 {
+        match self {
+            Difficulty::Easy => 1,
+            Difficulty::Medium => 2,
+            Difficulty::Hard => 3,
+        }
+    }
+
+    /// Number of hints a player can use in one game
+    pub fn max_hints(self) -> u32 {
+        match self {
+            Difficulty::Easy => 3,
+            Difficulty::Medium => 2,
+            Difficulty::Hard => 1,
+        }
+    }
+
+    /// Pick a random word (drawn from `rng`) whose score falls in this difficulty's third of the list
+    pub fn pick_word(self, list: &WordList, rng: &mut RngHandle) -> String {
+        let mut scored: Vec<(f64, &String)> = list
+            .words()
+            .iter()
+            .map(|word| (word_score(word), word))
+            .collect();
+        scored.sort_by(|a, b| a.0.total_cmp(&b.0).then_with(|| a.1.cmp(b.1)));
+
+        // Split into thirds; tiny lists still get at least one word per level
+        let third = scored.len().div_ceil(3);
+        let start = match self {
+            Difficulty::Easy => 0,
+            Difficulty::Medium => third,
+            Difficulty::Hard => third * 2,
+        }
+        .min(scored.len() - 1);
+        let end = (start + third).min(scored.len());
+        let pool = &scored[start..end];
+
+        pool[rng.gen_range(0..pool.len())].1.clone()
+    }
+}
+
+/// Relative frequency of each letter A-Z in English text (percent)
+const LETTER_FREQUENCIES: [f64; 26] = [
+    8.2, 1.5, 2.8, 4.3, 12.7, 2.2, 2.0, 6.1, 7.0, 0.15, 0.77, 4.0, 2.4, // A-M
+    6.7, 7.5, 1.9, 0.095, 6.0, 6.3, 9.1, 2.8, 0.98, 2.4, 0.15, 2.0, 0.074, // N-Z
+];
+
+/// How uncommon a letter is, from 0.0 (E, the most common) to 1.0
+/// Accented letters count as their base letter (É as E), other letters
+/// outside A-Z as maximally rare
+pub fn letter_rarity(letter: char) -> f64 {
+    let max = LETTER_FREQUENCIES[4];
+    let upper = fold(letter, true).to_ascii_uppercase();
+    if upper.is_ascii_uppercase() {
+        let index = (upper as u8 - b'A') as usize;
+        1.0 - LETTER_FREQUENCIES[index] / max
+    } else {
+        1.0
+    }
+}
+
+/// Difficulty score for a word or phrase: the summed rarity of its distinct letters
+pub fn word_score(word: &str) -> f64 {
+    let mut seen = Vec::new();
+    word.chars()
+        .filter(|c| is_guessable(*c))
+        .filter(|c| {
+            if seen.contains(c) {
+                false
+            } else {
+                seen.push(*c);
+                true
+            }
+        })
+        .map(letter_rarity)
+        .sum()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_guess_budgets() {
+        assert!(Difficulty::Easy.max_wrong_guesses() > Difficulty::Medium.max_wrong_guesses());
+        assert!(Difficulty::Medium.max_wrong_guesses() > Difficulty::Hard.max_wrong_guesses());
+        assert_eq!(Difficulty::default().max_wrong_guesses(), 6);
+    }
+
+    #[test]
+    fn test_letter_rarity() {
+        assert_eq!(letter_rarity('E'), 0.0);
+        assert!(letter_rarity('z') > letter_rarity('t'));
+        assert_eq!(letter_rarity('É'), 0.0);
+        assert_eq!(letter_rarity('Ж'), 1.0);
+    }
+
+    #[test]
+    fn test_word_score_counts_distinct_letters() {
+        // Repeating a letter doesn't make a word harder
+        assert_eq!(word_score("AAAA"), word_score("A"));
+        assert!(word_score("JAZZ") > word_score("TEA"));
+    }
+
+    #[test]
+    fn test_pick_word_by_difficulty() {
+        let list = WordList::new(["TEE", "TEA", "SEAT", "RUST", "JAZZ", "QUIZ"]).unwrap();
+        let mut rng = RngHandle::from_entropy();
+        for _ in 0..20 {
+            assert!(["TEE", "TEA"].contains(&Difficulty::Easy.pick_word(&list, &mut rng).as_str()));
+            assert!(["JAZZ", "QUIZ"].contains(&Difficulty::Hard.pick_word(&list, &mut rng).as_str()));
+        }
+    }
+
+    #[test]
+    fn test_pick_word_is_seedable() {
+        let list = WordList::new(["AB", "BC", "CD", "DE", "EF", "FG", "GH", "HI", "IJ"]).unwrap();
+        let picks = |seed| -> Vec<String> {
+            let mut rng = RngHandle::seeded(seed);
+            (0..10).map(|_| Difficulty::Medium.pick_word(&list, &mut rng)).collect()
+        };
+        assert_eq!(picks(7), picks(7));
+    }
+
+    #[test]
+    fn test_pick_word_tiny_list() {
+        let list = WordList::new(["RUST"]).unwrap();
+        let mut rng = RngHandle::from_entropy();
+        assert_eq!(Difficulty::Hard.pick_word(&list, &mut rng), "RUST");
+        assert_eq!(Difficulty::Easy.pick_word(&list, &mut rng), "RUST");
+    }
+
+    #[test]
+    fn test_difficulty_serializes_lowercase() {
+        assert_eq!(serde_json::to_string(&Difficulty::Hard).unwrap(), "\"hard\"");
+        let parsed: Difficulty = serde_json::from_str("\"easy\"").unwrap();
+        assert_eq!(parsed, Difficulty::Easy);
+    }
+}
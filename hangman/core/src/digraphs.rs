@@ -0,0 +1,170 @@
+// ============================================================================
+// DIGRAPHS
+// ============================================================================
+// Some languages and word lists treat a few letter pairs as one letter,
+// e.g. "CH" and "LL" in Spanish or "QU" in Italian. The digraphs setting
+// lists them, and a game then has one key for each: guessing "CH" reveals
+// every CH of the word, guessing "C" only the Cs that aren't part of one.
+//
+// UNITS:
+// Each digraph is a unit, a single character standing for its letters (one
+// of the Unicode private use area, which no word or alphabet has), so the
+// board, the keys, the guessed letters, hints and the win check work on
+// units like on any other letter. A digraph is 2 or 3 letters from A to Z.
+//
+// THE WORD:
+// The game keeps its word spelled out (the statistics, the records and
+// what's shown once it's over have the letters as they are), and splits it
+// into units where it's matched: tokenize() takes the longest digraph that
+// fits at each place, e.g. "CHILLI" with CH and LL is CH I LL I. Units are
+// spelled out again wherever the game is shown (spell, spell_str).
+
+use crate::letters::same_letter;
+use crate::settings::LetterCase;
+
+/// Most digraphs a game can have
+pub const MAX_DIGRAPHS: usize = 16;
+
+/// First character of the units (plane 15, private use only)
+const UNIT_BASE: u32 = 0xF0000;
+
+/// Letters A to Z are 1 to 26, so a unit encodes up to 3 of them in base 27
+const RADIX: u32 = 27;
+
+/// The unit standing for `letters` (any case), None unless they're 2 or 3 letters from A to Z
+pub fn unit(letters: &str) -> Option<char> {
+    let letters = letters.trim();
+    if !(2..=3).contains(&letters.len()) || !letters.chars().all(|c| c.is_ascii_alphabetic()) {
+        return None;
+    }
+    let code = letters
+        .chars()
+        .fold(0, |code, c| code * RADIX + (c.to_ascii_uppercase() as u32 - 'A' as u32 + 1));
+    char::from_u32(UNIT_BASE + code)
+}
+
+/// The letters a unit stands for, None for any other character
+fn letters(c: char) -> Option<String> {
+    let mut code = (c as u32).checked_sub(UNIT_BASE)?;
+    if code >= RADIX.pow(3) {
+        return None;
+    }
+    let mut letters = Vec::new();
+    while code > 0 {
+        let digit = code % RADIX;
+        if digit == 0 {
+            return None;
+        }
+        letters.push((b'A' + digit as u8 - 1) as char);
+        code /= RADIX;
+    }
+    (letters.len() >= 2).then(|| letters.into_iter().rev().collect())
+}
+
+/// Whether a character is a unit
+pub fn is_unit(c: char) -> bool {
+    letters(c).is_some()
+}
+
+/// A unit's letters in uppercase, e.g. "CH", any other character as it is
+pub fn spell(c: char) -> String {
+    letters(c).unwrap_or_else(|| c.to_string())
+}
+
+/// `s` with its units spelled out in `case`, everything else left as it is
+pub fn spell_str(s: &str, case: LetterCase) -> String {
+    s.chars()
+        .map(|c| match letters(c) {
+            Some(letters) => case.apply_str(&letters),
+            None => c.to_string(),
+        })
+        .collect()
+}
+
+/// The unit of `units` typed as `input` (any case), e.g. "ch" for CH
+pub fn find(units: &[char], input: &str) -> Option<char> {
+    let unit = unit(input)?;
+    units.contains(&unit).then_some(unit)
+}
+
+/// `word` (uppercase) split into units: the longest of `units` that fits at
+/// each place, the word's character otherwise
+pub fn tokenize(word: &str, units: &[char]) -> Vec<char> {
+    let chars: Vec<char> = word.chars().collect();
+    let mut spelled: Vec<(char, Vec<char>)> = units.iter().map(|&unit| (unit, spell(unit).chars().collect())).collect();
+    spelled.sort_by_key(|(_, letters)| std::cmp::Reverse(letters.len()));
+    let mut tokens = Vec::new();
+    let mut at = 0;
+    while at < chars.len() {
+        let fits = spelled.iter().find(|(_, letters)| {
+            chars[at..].len() >= letters.len()
+                && chars[at..].iter().zip(letters).all(|(&c, &letter)| same_letter(c, letter, false))
+        });
+        match fits {
+            Some((unit, letters)) => {
+                tokens.push(*unit);
+                at += letters.len();
+            }
+            None => {
+                tokens.push(chars[at]);
+                at += 1;
+            }
+        }
+    }
+    tokens
+}
+
+/// Check the digraphs setting: each 2 or 3 letters from A to Z, no repeats
+pub fn validate(digraphs: &[String]) -> Result<(), String> {
+    if digraphs.len() > MAX_DIGRAPHS {
+        return Err(format!("digraphs can have at most {} entries (got {})", MAX_DIGRAPHS, digraphs.len()));
+    }
+    let mut seen = Vec::new();
+    for digraph in digraphs {
+        let unit = unit(digraph)
+            .ok_or_else(|| format!("digraphs must be 2 or 3 letters from A to Z, e.g. \"CH\" (got {:?})", digraph))?;
+        if seen.contains(&unit) {
+            return Err(format!("digraphs has {:?} twice", digraph));
+        }
+        seen.push(unit);
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_units_spell_their_letters() {
+        let ch = unit("ch").unwrap();
+        assert!(is_unit(ch));
+        assert_eq!(unit(" CH "), Some(ch));
+        assert_eq!(spell(ch), "CH");
+        assert_eq!(spell(unit("QUE").unwrap()), "QUE");
+        assert_eq!(spell('A'), "A");
+        assert!(!is_unit('A') && !is_unit('\u{F0001}'));
+        for input in ["C", "CHAR", "Ñ1", "ÑA", ""] {
+            assert_eq!(unit(input), None);
+        }
+        // Only the units change
+        assert_eq!(spell_str(&format!("{}A_", ch), LetterCase::Lower), "chA_");
+    }
+
+    #[test]
+    fn test_tokenize() {
+        let (ch, ll, lle) = (unit("CH").unwrap(), unit("LL").unwrap(), unit("LLE").unwrap());
+        assert_eq!(tokenize("CHILLI", &[ch, ll]), [ch, 'I', ll, 'I']);
+        // The longest digraph wins, and letters left over stay letters
+        assert_eq!(tokenize("LLEC", &[ll, lle, ch]), [lle, 'C']);
+        assert_eq!(tokenize("RUST", &[]), ['R', 'U', 'S', 'T']);
+    }
+
+    #[test]
+    fn test_validate() {
+        let digraphs = |list: &[&str]| list.iter().map(|d| d.to_string()).collect::<Vec<_>>();
+        assert!(validate(&digraphs(&["CH", "ll", "QU"])).is_ok());
+        assert!(validate(&digraphs(&["C"])).is_err());
+        assert!(validate(&digraphs(&["CH", "ch"])).is_err());
+    }
+}
@@ -0,0 +1,189 @@
+// ============================================================================
+// TWO-PLAYER DUELS
+// ============================================================================
+// A two-player game (player one types the word, player two guesses it) can
+// be followed by a rematch with the roles swapped: the guesser types the
+// next word. The rounds make up a duel with a head-to-head score, carried
+// from one game to the next in GameState's `duel` like a match's rounds.
+//
+// SCORING:
+// Each round gives a point: to the guesser when they find the word, to the
+// player who set it when they don't. A round's result is recorded when its
+// game ends, and taken back if its last move is undone.
+//
+// REMATCHES:
+// rematch() starts the next round from a finished round's game: the new
+// setter's word, the duel with the roles swapped. Only two-player games
+// have a duel, new_custom() starts one.
+//
+// The players are numbered 1 and 2, player 1 sets the first word.
+
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use crate::difficulty::Difficulty;
+use crate::game::GameState;
+use crate::secret::SecretError;
+
+/// Reasons a rematch can't be started
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DuelError {
+    /// The game isn't a two-player game
+    NotADuel,
+    /// The round is still being played
+    NotOver,
+    /// The new word can't be played
+    Secret(SecretError),
+}
+
+impl fmt::Display for DuelError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            DuelError::NotADuel => write!(f, "only a two-player game can have a rematch"),
+            DuelError::NotOver => write!(f, "the round isn't over yet"),
+            DuelError::Secret(err) => err.fmt(f),
+        }
+    }
+}
+
+impl std::error::Error for DuelError {}
+
+/// How a round of a duel ended
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+pub struct DuelRound {
+    // The player who set the word, 1 or 2
+    pub setter: u32,
+    // Whether the other one found it
+    pub found: bool,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+pub struct Duel {
+    // The round being played, from 1
+    pub round: u32,
+    // The player who set the current round's word, 1 or 2
+    pub setter: u32,
+    // Finished rounds, in order
+    pub results: Vec<DuelRound>,
+}
+
+impl Default for Duel {
+    fn default() -> Self {
+        Duel::new()
+    }
+}
+
+impl Duel {
+    /// A duel on its first round, player 1 setting the word
+    pub fn new() -> Self {
+        Duel { round: 1, setter: 1, results: Vec::new() }
+    }
+
+    /// The player guessing the current round's word
+    pub fn guesser(&self) -> u32 {
+        3 - self.setter
+    }
+
+    /// Rounds `player` (1 or 2) has won
+    pub fn wins(&self, player: u32) -> u32 {
+        self.results
+            .iter()
+            .filter(|result| (result.setter == player) != result.found)
+            .count() as u32
+    }
+
+    /// Whether the current round's result is in
+    pub fn round_finished(&self) -> bool {
+        self.results.len() as u32 >= self.round
+    }
+
+    /// Record how the current round ended (once)
+    pub(crate) fn record(&mut self, found: bool) {
+        if !self.round_finished() {
+            self.results.push(DuelRound { setter: self.setter, found });
+        }
+    }
+
+    /// Take the current round's result back (its game was reopened by undo)
+    pub(crate) fn reopen(&mut self) {
+        if self.round_finished() {
+            self.results.pop();
+        }
+    }
+
+    /// Move on to the rematch with the roles swapped, false if the current
+    /// round isn't finished yet
+    pub fn next_round(&mut self) -> bool {
+        if !self.round_finished() {
+            return false;
+        }
+        self.round += 1;
+        self.setter = self.guesser();
+        true
+    }
+}
+
+impl GameState {
+    /// The rematch of a finished two-player game: `secret` typed in by its guesser,
+    /// who now sets the word, with the duel's score carried over
+    pub fn rematch(&self, secret: &str, difficulty: Difficulty) -> Result<GameState, DuelError> {
+        let mut duel = self.duel.clone().ok_or(DuelError::NotADuel)?;
+        if !self.game_over || !duel.next_round() {
+            return Err(DuelError::NotOver);
+        }
+        let mut game = GameState::new_custom(secret, difficulty).map_err(DuelError::Secret)?;
+        game.duel = Some(duel);
+        Ok(game)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_roles_swap_and_score() {
+        let mut duel = Duel::new();
+        assert_eq!((duel.setter, duel.guesser()), (1, 2));
+        assert!(!duel.next_round());
+
+        // Player 2 finds player 1's word, then player 1 misses player 2's
+        duel.record(true);
+        duel.record(false);
+        assert!(duel.next_round());
+        assert_eq!((duel.round, duel.setter, duel.guesser()), (2, 2, 1));
+        duel.record(false);
+        assert_eq!((duel.wins(1), duel.wins(2)), (0, 2));
+
+        duel.reopen();
+        assert_eq!((duel.wins(1), duel.wins(2)), (0, 1));
+        duel.record(true);
+        assert!(duel.next_round());
+        assert_eq!((duel.round, duel.setter, duel.wins(1), duel.wins(2)), (3, 1, 1, 1));
+    }
+
+    #[test]
+    fn test_rematch() {
+        let mut game = GameState::new_custom("rust", Difficulty::Medium).unwrap();
+        assert_eq!(game.rematch("ruby", Difficulty::Medium).unwrap_err(), DuelError::NotOver);
+        game.guess_word("RUST");
+
+        let mut rematch = game.rematch("ferris", Difficulty::Hard).unwrap();
+        assert_eq!((rematch.word.as_str(), rematch.difficulty), ("FERRIS", Difficulty::Hard));
+        let duel = rematch.duel.clone().unwrap();
+        assert_eq!((duel.round, duel.setter, duel.wins(2)), (2, 2, 1));
+        assert_eq!(
+            rematch.rematch("42", Difficulty::Hard).unwrap_err(),
+            DuelError::NotOver
+        );
+        rematch.give_up();
+        assert_eq!(
+            rematch.rematch("42", Difficulty::Hard).unwrap_err(),
+            DuelError::Secret(SecretError::InvalidCharacter('4'))
+        );
+        assert_eq!(rematch.duel.unwrap().wins(2), 2);
+
+        let game = GameState::new("RUST".to_string(), None, Difficulty::Medium);
+        assert_eq!(game.rematch("ruby", Difficulty::Medium).unwrap_err(), DuelError::NotADuel);
+    }
+}
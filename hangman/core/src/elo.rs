@@ -0,0 +1,176 @@
+// ============================================================================
+// RATINGS AGAINST THE AI
+// ============================================================================
+// Every profile has an Elo-style rating per difficulty, moved by its races
+// against the AI (see race.rs). The AI plays at a fixed rating per difficulty
+// (ai_rating), so a profile's rating says how it does against that AI: a
+// player who wins half the races on medium stays at the medium AI's 1200.
+//
+// UPDATES:
+// A finished race the profile records (see profiles.rs) scores 1 for a win,
+// 0 for a loss, and 1/2 when the hangman won (both were hanged). The rating
+// moves by K_FACTOR times the score minus the expected one,
+//     expected = 1 / (1 + 10^((ai - player) / 400))
+// rounded to a whole point. A profile starts at STARTING_RATING on every
+// difficulty it hasn't raced on.
+//
+// HISTORY:
+// Each update is kept as a point (when, the result, the new rating, and by
+// how much it moved), so the frontend can graph the rating over time. The
+// latest MAX_RATING_POINTS are kept per difficulty, the rating itself is the
+// last point's.
+
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use crate::difficulty::Difficulty;
+use crate::game::GameState;
+use crate::race::RaceWinner;
+
+/// Rating of a profile on a difficulty it hasn't raced on
+pub const STARTING_RATING: i32 = 1200;
+
+/// Most a rating moves after one race
+pub const K_FACTOR: f64 = 32.0;
+
+/// Points of history kept per difficulty
+pub const MAX_RATING_POINTS: usize = 100;
+
+/// The AI's fixed rating on a difficulty
+pub fn ai_rating(difficulty: Difficulty) -> i32 {
+    match difficulty {
+        Difficulty::Easy => 1000,
+        Difficulty::Medium => 1200,
+        Difficulty::Hard => 1400,
+    }
+}
+
+/// The chance a player rated `rating` beats an opponent rated `opponent`
+pub fn expected_score(rating: i32, opponent: i32) -> f64 {
+    1.0 / (1.0 + 10f64.powf(f64::from(opponent - rating) / 400.0))
+}
+
+/// What a race scores for the player: 1 for a win, 0 for a loss, 1/2 for a draw
+fn score_of(winner: RaceWinner) -> f64 {
+    match winner {
+        RaceWinner::Player => 1.0,
+        RaceWinner::Ai => 0.0,
+        RaceWinner::Hangman => 0.5,
+    }
+}
+
+/// A rating update, after one race
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+pub struct RatingPoint {
+    pub difficulty: Difficulty,
+    pub result: RaceWinner,
+    // The rating after the race, and by how much the race moved it
+    pub rating: i32,
+    pub change: i32,
+    pub at_ms: u64,
+}
+
+/// A profile's ratings, as the history of their updates
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+pub struct Ratings {
+    // Oldest first, every difficulty mixed
+    #[serde(default)]
+    pub history: Vec<RatingPoint>,
+}
+
+impl Ratings {
+    /// The current rating on `difficulty`
+    pub fn rating(&self, difficulty: Difficulty) -> i32 {
+        self.points(difficulty).last().map_or(STARTING_RATING, |point| point.rating)
+    }
+
+    /// The updates on `difficulty`, oldest first
+    pub fn points(&self, difficulty: Difficulty) -> Vec<&RatingPoint> {
+        self.history.iter().filter(|point| point.difficulty == difficulty).collect()
+    }
+
+    /// Update the rating with a finished race, nothing for any other game
+    /// Returns the update made
+    pub fn record(&mut self, game: &GameState, at_ms: u64) -> Option<RatingPoint> {
+        let result = game.race_winner()?;
+        let difficulty = game.difficulty;
+        let rating = self.rating(difficulty);
+        let expected = expected_score(rating, ai_rating(difficulty));
+        let change = (K_FACTOR * (score_of(result) - expected)).round() as i32;
+        let point = RatingPoint {
+            difficulty,
+            result,
+            rating: rating + change,
+            change,
+            at_ms,
+        };
+        self.history.push(point);
+        if self.points(difficulty).len() > MAX_RATING_POINTS {
+            let oldest = self.history.iter().position(|point| point.difficulty == difficulty).unwrap();
+            self.history.remove(oldest);
+        }
+        Some(point)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::race::Race;
+
+    fn race(difficulty: Difficulty) -> GameState {
+        let mut game = GameState::new("RUST".to_string(), None, difficulty);
+        game.race = Some(Race::new(&game).unwrap());
+        game
+    }
+
+    /// A race the player was hanged in
+    fn lost_race(difficulty: Difficulty) -> GameState {
+        let mut game = GameState::new("RUST".to_string(), None, difficulty);
+        for word in ["JAVA", "RUBY", "PERL", "LISP"] {
+            game.guess_word(word);
+        }
+        game.race = Some(Race::new(&game).unwrap());
+        game
+    }
+
+    #[test]
+    fn test_updates() {
+        let mut ratings = Ratings::default();
+        assert_eq!(ratings.rating(Difficulty::Medium), STARTING_RATING);
+
+        // An even match moves the rating by half of K
+        let mut game = race(Difficulty::Medium);
+        game.guess_word("RUST");
+        assert_eq!(ratings.record(&game, 10).unwrap().change, 16);
+        assert_eq!(ratings.rating(Difficulty::Medium), 1216);
+
+        // Losing to the stronger AI on hard costs less than losing an even match
+        let point = ratings.record(&lost_race(Difficulty::Hard), 20).unwrap();
+        assert_eq!((point.result, point.change, point.rating), (RaceWinner::Ai, -8, 1192));
+        // Each difficulty has its own
+        assert_eq!(ratings.rating(Difficulty::Medium), 1216);
+        assert_eq!(ratings.points(Difficulty::Hard).len(), 1);
+
+        // Games that aren't races, or aren't over, change nothing
+        let mut game = GameState::new("RUST".to_string(), None, Difficulty::Easy);
+        game.guess_word("RUST");
+        assert_eq!(ratings.record(&game, 30), None);
+        assert_eq!(ratings.record(&race(Difficulty::Easy), 30), None);
+        assert_eq!(ratings.history.len(), 2);
+    }
+
+    #[test]
+    fn test_history_is_capped() {
+        let mut ratings = Ratings::default();
+        let mut game = race(Difficulty::Easy);
+        game.guess_word("RUST");
+        for at_ms in 0..MAX_RATING_POINTS as u64 + 5 {
+            ratings.record(&game, at_ms);
+        }
+        let points = ratings.points(Difficulty::Easy);
+        assert_eq!(points.len(), MAX_RATING_POINTS);
+        assert_eq!(points[0].at_ms, 5);
+        // Winning keeps paying less as the rating climbs
+        assert!(points.windows(2).all(|pair| pair[0].change >= pair[1].change));
+    }
+}
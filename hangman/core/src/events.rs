@@ -0,0 +1,100 @@
+// ============================================================================
+// GAME EVENTS
+// ============================================================================
+// What the desktop app tells its plugins (see crates/sandbox-plugin) about
+// the games played in it, so extensions like metrics sinks or extra storage
+// can follow along without touching the commands.
+//
+// ORDER:
+// A game sends Started, then one event per move, then Finished once it's won
+// or lost (a move after a timed game's deadline sends only Finished). A game
+// replaced or closed before it ends sends Abandoned instead. Undoing a move
+// sends Undone, and a game it reopened sends Finished again when it ends.
+
+use crate::difficulty::Difficulty;
+use crate::evil::GameMode;
+use crate::game::GameState;
+use crate::history::Action;
+use crate::score::Score;
+use crate::sessions::SessionId;
+
+/// Something that happened in a session's game
+#[derive(Debug, Clone, PartialEq)]
+pub enum GameEvent {
+    Started {
+        session_id: SessionId,
+        difficulty: Difficulty,
+        mode: GameMode,
+        category: Option<String>,
+        custom: bool,
+    },
+    LetterGuessed { session_id: SessionId, letter: char, hit: bool },
+    WordGuessed { session_id: SessionId, guess: String, hit: bool },
+    HintUsed { session_id: SessionId, letter: char },
+    /// The last move was taken back
+    Undone { session_id: SessionId, action: Action },
+    Finished {
+        session_id: SessionId,
+        word: String,
+        won: bool,
+        timed_out: bool,
+        gave_up: bool,
+        score: Option<Score>,
+    },
+    /// The game was replaced by a new one, or its window closed, before it ended
+    Abandoned { session_id: SessionId },
+}
+
+impl GameEvent {
+    pub fn started(session_id: &str, game: &GameState) -> Self {
+        GameEvent::Started {
+            session_id: session_id.to_string(),
+            difficulty: game.difficulty,
+            mode: game.mode(),
+            category: game.category.clone(),
+            custom: game.custom,
+        }
+    }
+
+    /// The end of a game that was just won or lost
+    pub fn finished(session_id: &str, game: &GameState) -> Self {
+        GameEvent::Finished {
+            session_id: session_id.to_string(),
+            word: game.word.clone(),
+            won: game.won,
+            timed_out: game.timed_out,
+            gave_up: game.gave_up,
+            score: game.score,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_started_and_finished() {
+        let mut game = GameState::new("RUST".to_string(), Some("tech".to_string()), Difficulty::Hard);
+        assert_eq!(
+            GameEvent::started("s1", &game),
+            GameEvent::Started {
+                session_id: "s1".to_string(),
+                difficulty: Difficulty::Hard,
+                mode: GameMode::Classic,
+                category: Some("tech".to_string()),
+                custom: false,
+            }
+        );
+
+        game.guess_word("rust");
+        match GameEvent::finished("s1", &game) {
+            GameEvent::Finished { word, won, score, .. } => {
+                assert_eq!(word, "RUST");
+                assert!(won);
+                assert_eq!(score, game.score);
+            }
+            other => panic!("expected Finished, got {:?}", other),
+        }
+    }
+}
@@ -0,0 +1,178 @@
+// ============================================================================
+// EVIL HANGMAN
+// ============================================================================
+// In evil mode the backend doesn't commit to a word when the game starts. It
+// keeps every word of the list that still fits what the player has seen, and
+// on each guess splits them into "families" by where the guessed letter
+// appears (e.g. for E: "_ _ _ _", "E _ _ _", "_ E _ E", ...). It then keeps
+// the largest family, so most guesses miss and the word is only pinned down
+// when nothing else fits.
+//
+// CHOOSING A FAMILY:
+// 1. The family with the most words
+// 2. On a tie, the one that reveals fewest letters (a miss beats a hit)
+// 3. Then the first pattern in alphabetical order, so a game replays the same
+//
+// THE WORD:
+// GameState::word is always the first remaining candidate. Every candidate
+// shows the same letters, so the board doesn't change when the word does,
+// and a lost game reveals a word that fits every guess.
+//
+// WORD GUESSES AND HINTS:
+// A word guess that matches a candidate just removes it if other words are
+// still left (a miss). A hint reveals a letter of the current word and keeps
+// only the candidates that show that letter in the same places.
+
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use crate::letters::{fold_str, same_letter};
+use crate::words::WordList;
+
+/// How a game picks its word
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum GameMode {
+    /// The word is picked when the game starts
+    #[default]
+    Classic,
+    /// The word is dodged for as long as possible (see Candidates)
+    Evil,
+}
+
+/// The words an evil game could still be about
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+pub struct Candidates {
+    words: Vec<String>,
+}
+
+impl Candidates {
+    /// Every word of `list` shaped like `word`: same length, and spaces and
+    /// punctuation in the same places (`word` itself is always included)
+    pub fn new(list: &WordList, word: &str) -> Self {
+        let shape: Vec<char> = word.chars().collect();
+        let mut words: Vec<String> = list
+            .trie()
+            .matching(shape.len(), |place, c| match shape[place] {
+                letter if list.charset().is_guessable(letter) => list.charset().is_guessable(c),
+                other => c == other,
+            })
+            .into_iter()
+            .map(|i| list.words()[i].clone())
+            .collect();
+        if !words.iter().any(|w| w == word) {
+            words.insert(0, word.to_string());
+        }
+        Candidates { words }
+    }
+
+    /// The word the game currently shows (the first candidate)
+    pub fn word(&self) -> &str {
+        &self.words[0]
+    }
+
+    /// How many words are still possible
+    pub fn len(&self) -> usize {
+        self.words.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.words.is_empty()
+    }
+
+    /// Keep the largest family for a guessed `letter`, returns the new word
+    pub fn narrow(&mut self, letter: char, ignore_accents: bool) -> &str {
+        let mut families: BTreeMap<String, Vec<String>> = BTreeMap::new();
+        for word in self.words.drain(..) {
+            let key = pattern(&word, |c| same_letter(c, letter, ignore_accents));
+            families.entry(key).or_default().push(word);
+        }
+
+        let revealed = |key: &str| key.chars().filter(|&c| c != '_').count();
+        let (_, family) = families
+            .into_iter()
+            .rev()
+            .max_by(|(a_key, a), (b_key, b)| {
+                a.len().cmp(&b.len()).then_with(|| revealed(b_key).cmp(&revealed(a_key)))
+            })
+            .expect("at least one candidate");
+        self.words = family;
+        self.word()
+    }
+
+    /// Keep the candidates showing `letter` where the current word does
+    /// (for hints, which must reveal it), returns the word
+    pub fn reveal(&mut self, letter: char, ignore_accents: bool) -> &str {
+        let key = pattern(self.word(), |c| same_letter(c, letter, ignore_accents));
+        self.words
+            .retain(|word| pattern(word, |c| same_letter(c, letter, ignore_accents)) == key);
+        self.word()
+    }
+
+    /// Whether any of the words has `letter`
+    pub fn has_letter(&self, letter: char, ignore_accents: bool) -> bool {
+        self.words
+            .iter()
+            .any(|word| word.chars().any(|c| same_letter(c, letter, ignore_accents)))
+    }
+
+    /// Drop the candidates a word guess matches, unless they're all that's left
+    /// Returns the new word if the guess was dodged
+    pub fn dodge(&mut self, guess: &str, ignore_accents: bool) -> Option<&str> {
+        let guess = fold_str(guess, ignore_accents);
+        let matches = |word: &String| fold_str(word, ignore_accents) == guess;
+        if !self.words.iter().any(matches) || self.words.iter().all(matches) {
+            return None;
+        }
+        self.words.retain(|word| !matches(word));
+        Some(self.word())
+    }
+}
+
+/// `word` with the characters `shown` picks kept, everything else "_"
+fn pattern(word: &str, shown: impl Fn(char) -> bool) -> String {
+    word.chars().map(|c| if shown(c) { c } else { '_' }).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn candidates(words: &str, word: &str) -> Candidates {
+        Candidates::new(&WordList::from_text(words).unwrap(), word)
+    }
+
+    #[test]
+    fn test_candidates_share_shape() {
+        let c = candidates("rust\ngo\nruby\nc sharp\njava\nruby on", "RUST");
+        assert_eq!(c.words, ["RUST", "RUBY", "JAVA"]);
+        let c = candidates("rust\nc sharp\nruby on", "RUBY ON");
+        assert_eq!(c.words, ["RUBY ON"]);
+    }
+
+    #[test]
+    fn test_narrow_keeps_largest_family() {
+        let mut c = candidates("rust\nruby\njava\nperl\nlisp", "RUST");
+        // R: "R___" x2, "__R_" x1, "____" x2 -> the miss wins the tie
+        assert_eq!(c.narrow('R', false), "JAVA");
+        assert_eq!(c.words, ["JAVA", "LISP"]);
+        // A: "_A_A" x1, "____" x1 -> the miss again
+        assert_eq!(c.narrow('A', false), "LISP");
+        assert_eq!(c.len(), 1);
+        // Only one word left, so now every guess is a hit or a plain miss
+        assert_eq!(c.narrow('L', false), "LISP");
+    }
+
+    #[test]
+    fn test_reveal_and_dodge() {
+        let mut c = candidates("rust\nruby\nrush", "RUST");
+        assert_eq!(c.reveal('T', false), "RUST");
+        assert_eq!(c.len(), 1);
+
+        let mut c = candidates("rust\nruby\nrush", "RUST");
+        assert_eq!(c.dodge("rust", false), Some("RUBY"));
+        assert_eq!(c.dodge("java", false), None);
+        assert_eq!(c.dodge("ruby", false), Some("RUSH"));
+        assert_eq!(c.dodge("rush", false), None);
+    }
+}
@@ -0,0 +1,296 @@
+// ============================================================================
+// EXPORTING THE GAME HISTORY
+// ============================================================================
+// Everything the database knows about the games played, written to a file of
+// the player's choosing so it can be looked at in a spreadsheet or a script:
+// every finished game (see storage.rs) with its moves, and the overall
+// statistics. A HistoryExport is a copy of it, taken in one go.
+//
+// FORMATS:
+// - ExportFormat::Json: one object, {"version", "exported_at_ms",
+//   "statistics", "games"}, each game with its "guesses" in order
+// - ExportFormat::Csv: a row per game, oldest first, its moves in the
+//   "guesses" column as kind:guess:hit|miss separated by spaces (e.g.
+//   "letter:R:hit word:RUBY:miss hint:S:hit"), then after an empty line a
+//   statistic,value row per statistic
+//
+// Cells with a comma, quote, or line break (a player named "Ada, Countess")
+// are quoted the usual CSV way.
+
+use sandbox_schema::hangman::{ExportFormat, ExportSummary, StatisticsView};
+use sandbox_schema::SCHEMA_VERSION;
+use serde::Serialize;
+use std::borrow::Cow;
+use std::fmt;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use crate::difficulty::Difficulty;
+use crate::game::now_ms;
+use crate::storage::{action_columns, difficulty_name, GameRecord, Storage, StorageError};
+
+/// Version of the JSON export's layout, raised when fields change meaning or go away
+pub const EXPORT_VERSION: u32 = 1;
+
+/// Columns of a CSV export's game rows
+const CSV_COLUMNS: &str =
+    "id,finished_at_ms,player,word,category,difficulty,evil,daily,won,wrong_guesses,hints_used,score,guesses";
+
+/// Reasons the history couldn't be exported
+#[derive(Debug)]
+pub enum ExportError {
+    /// The games or statistics couldn't be read
+    Storage(StorageError),
+    Json(serde_json::Error),
+    /// The file couldn't be written
+    Write { path: PathBuf, source: io::Error },
+}
+
+impl fmt::Display for ExportError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ExportError::Storage(err) => write!(f, "{}", err),
+            ExportError::Json(err) => write!(f, "failed to encode the history: {}", err),
+            ExportError::Write { path, source } => write!(f, "failed to write {}: {}", path.display(), source),
+        }
+    }
+}
+
+impl std::error::Error for ExportError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            ExportError::Storage(err) => Some(err),
+            ExportError::Json(err) => Some(err),
+            ExportError::Write { source, .. } => Some(source),
+        }
+    }
+}
+
+impl From<StorageError> for ExportError {
+    fn from(err: StorageError) -> Self {
+        ExportError::Storage(err)
+    }
+}
+
+/// A move of an exported game
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct ExportedGuess {
+    // "letter", "word", or "hint"
+    pub kind: &'static str,
+    pub guess: String,
+    pub hit: bool,
+    pub at_ms: u64,
+}
+
+/// An exported game
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct ExportedGame {
+    pub id: i64,
+    pub finished_at_ms: u64,
+    pub player: Option<String>,
+    pub word: String,
+    pub category: Option<String>,
+    pub difficulty: Difficulty,
+    pub evil: bool,
+    pub daily: Option<u64>,
+    pub won: bool,
+    pub wrong_guesses: u32,
+    pub hints_used: u32,
+    pub score: u32,
+    pub guesses: Vec<ExportedGuess>,
+}
+
+impl From<&GameRecord> for ExportedGame {
+    fn from(record: &GameRecord) -> Self {
+        ExportedGame {
+            id: record.id,
+            finished_at_ms: record.finished_at_ms,
+            player: record.player.clone(),
+            word: record.word.clone(),
+            category: record.category.clone(),
+            difficulty: record.difficulty,
+            evil: record.evil,
+            daily: record.daily,
+            won: record.won,
+            wrong_guesses: record.wrong_guesses,
+            hints_used: record.hints_used,
+            score: record.score,
+            guesses: record
+                .guesses
+                .iter()
+                .map(|guess| {
+                    let (kind, text) = action_columns(&guess.action);
+                    ExportedGuess {
+                        kind,
+                        guess: text,
+                        hit: guess.hit,
+                        at_ms: guess.at_ms,
+                    }
+                })
+                .collect(),
+        }
+    }
+}
+
+/// The game history, as it was when it was exported
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct HistoryExport {
+    pub version: u32,
+    pub exported_at_ms: u64,
+    pub statistics: StatisticsView,
+    // Oldest first
+    pub games: Vec<ExportedGame>,
+}
+
+impl HistoryExport {
+    /// Copy every stored game and the statistics
+    pub fn load(storage: &Storage) -> Result<Self, StorageError> {
+        Ok(HistoryExport {
+            version: EXPORT_VERSION,
+            exported_at_ms: now_ms(),
+            statistics: storage.load_statistics()?.summary(),
+            games: storage.all_games()?.iter().map(ExportedGame::from).collect(),
+        })
+    }
+
+    /// Moves of all the games together
+    pub fn guesses(&self) -> usize {
+        self.games.iter().map(|game| game.guesses.len()).sum()
+    }
+
+    pub fn to_json(&self) -> Result<String, serde_json::Error> {
+        serde_json::to_string_pretty(self)
+    }
+
+    pub fn to_csv(&self) -> String {
+        let mut csv = String::new();
+        csv.push_str(CSV_COLUMNS);
+        csv.push('\n');
+        for game in &self.games {
+            let guesses: Vec<String> = game
+                .guesses
+                .iter()
+                .map(|guess| format!("{}:{}:{}", guess.kind, guess.guess, if guess.hit { "hit" } else { "miss" }))
+                .collect();
+            let cells = [
+                game.id.to_string(),
+                game.finished_at_ms.to_string(),
+                game.player.clone().unwrap_or_default(),
+                game.word.clone(),
+                game.category.clone().unwrap_or_default(),
+                difficulty_name(game.difficulty).to_string(),
+                game.evil.to_string(),
+                game.daily.map(|day| day.to_string()).unwrap_or_default(),
+                game.won.to_string(),
+                game.wrong_guesses.to_string(),
+                game.hints_used.to_string(),
+                game.score.to_string(),
+                guesses.join(" "),
+            ];
+            let row: Vec<Cow<str>> = cells.iter().map(|cell| csv_cell(cell)).collect();
+            csv.push_str(&row.join(","));
+            csv.push('\n');
+        }
+
+        let stats = &self.statistics;
+        csv.push_str("\nstatistic,value\n");
+        for (name, value) in [
+            ("games_played", stats.games_played.to_string()),
+            ("wins", stats.wins.to_string()),
+            ("losses", stats.losses.to_string()),
+            ("current_streak", stats.current_streak.to_string()),
+            ("best_streak", stats.best_streak.to_string()),
+            ("average_wrong_guesses", stats.average_wrong_guesses.to_string()),
+            ("win_rate", stats.win_rate.to_string()),
+        ] {
+            csv.push_str(&format!("{},{}\n", name, value));
+        }
+        csv
+    }
+
+    /// Write the history to `path` (replacing the file if there is one)
+    pub fn write(&self, path: &Path, format: ExportFormat) -> Result<ExportSummary, ExportError> {
+        let contents = match format {
+            ExportFormat::Csv => self.to_csv(),
+            ExportFormat::Json => self.to_json().map_err(ExportError::Json)?,
+        };
+        fs::write(path, contents).map_err(|source| ExportError::Write {
+            path: path.to_path_buf(),
+            source,
+        })?;
+        Ok(ExportSummary {
+            schema_version: SCHEMA_VERSION,
+            path: path.display().to_string(),
+            format,
+            games: self.games.len() as u32,
+            guesses: self.guesses() as u32,
+        })
+    }
+}
+
+/// A CSV cell, quoted if it has to be
+fn csv_cell(text: &str) -> Cow<'_, str> {
+    if text.contains([',', '"', '\n', '\r']) {
+        Cow::Owned(format!("\"{}\"", text.replace('"', "\"\"")))
+    } else {
+        Cow::Borrowed(text)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::game::GameState;
+    use crate::stats::{Statistics, STATS_KEY};
+
+    fn storage_with_games() -> Storage {
+        let storage = Storage::open_in_memory().unwrap();
+        let mut game = GameState::new("GO".to_string(), None, Difficulty::Easy);
+        game.player = Some("Ada, Countess".to_string());
+        game.guess_letter('X');
+        game.guess_letter('G');
+        game.guess_letter('O');
+        storage.record_game(&game).unwrap();
+        let mut game = GameState::new("RUST".to_string(), None, Difficulty::Hard);
+        game.guess_word("RUBY");
+        game.guess_word("RUST");
+        storage.record_game(&game).unwrap();
+        storage.save(STATS_KEY, &Statistics { games_played: 2, wins: 2, ..Statistics::default() }).unwrap();
+        storage
+    }
+
+    #[test]
+    fn test_csv() {
+        let export = HistoryExport::load(&storage_with_games()).unwrap();
+        assert_eq!((export.games.len(), export.guesses()), (2, 5));
+
+        let csv = export.to_csv();
+        let lines: Vec<&str> = csv.lines().collect();
+        assert_eq!(lines[0], CSV_COLUMNS);
+        assert!(lines[1].starts_with("1,"));
+        assert!(lines[1].contains(",\"Ada, Countess\",GO,,easy,false,,true,1,0,"));
+        assert!(lines[1].ends_with(",letter:X:miss letter:G:hit letter:O:hit"));
+        assert!(lines[2].ends_with(",word:RUBY:miss word:RUST:hit"));
+        assert_eq!(&lines[3..6], ["", "statistic,value", "games_played,2"]);
+        assert!(lines.contains(&"win_rate,1"));
+    }
+
+    #[test]
+    fn test_json_file() {
+        let export = HistoryExport::load(&storage_with_games()).unwrap();
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("history.json");
+        let summary = export.write(&path, ExportFormat::Json).unwrap();
+        assert_eq!((summary.games, summary.guesses), (2, 5));
+
+        let json: serde_json::Value = serde_json::from_str(&fs::read_to_string(&path).unwrap()).unwrap();
+        assert_eq!(json["version"], EXPORT_VERSION);
+        assert_eq!(json["statistics"]["wins"], 2);
+        assert_eq!(json["games"][1]["difficulty"], "hard");
+        assert_eq!(json["games"][1]["guesses"][0]["guess"], "RUBY");
+        assert_eq!(json["games"][1]["guesses"][0]["hit"], false);
+
+        let missing = dir.path().join("missing").join("history.csv");
+        assert!(matches!(export.write(&missing, ExportFormat::Csv), Err(ExportError::Write { .. })));
+    }
+}
@@ -0,0 +1,171 @@
+// ============================================================================
+// GALLOWS ART
+// ============================================================================
+// The gallows drawn as text, for frontends that show it as text rather than
+// drawing it themselves (a terminal, a screen reader's braille line, a
+// future CLI). GameView carries it as `gallows`, in ASCII.
+//
+// PARTS:
+// The drawing has PARTS parts, the scaffold first (ground, pole, beam, rope)
+// and then the figure (head, body, arms, legs). It's drawn at the game's
+// stage() of stages() (see game.rs, a stage per wrong guess allowed), so it
+// always shows what `stage` says:
+// - FIGURE to PARTS stages draw one part each, the scaffold parts left over
+//   standing from the start (an 8-guess game has 8 distinct stages, the
+//   ground and pole drawn from the start)
+// - fewer stages spread the figure over them, the scaffold standing from the
+//   start
+// - more stages spread all the parts over them
+// The last part waits for the last stage (the game lost). The desktop app
+// draws its SVG gallows with the same parts (ui/main.js's partsDrawn).
+//
+// STYLES:
+// ASCII ("+---+", "O", "/|\") or Unicode box drawing ("┌───┐", "○", "╱│╲"),
+// every line the same width in either, trailing spaces trimmed. The drawing
+// is always ROWS lines high, so it doesn't move as it fills in.
+
+use crate::game::GameState;
+
+/// Parts of the whole drawing
+pub const PARTS: u32 = 10;
+
+/// Parts of the figure (head, body, arms, legs), the last of the drawing
+pub const FIGURE: u32 = 6;
+
+/// Lines of the drawing
+pub const ROWS: usize = 7;
+
+const COLUMNS: usize = 9;
+
+/// Characters the art is drawn with
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ArtStyle {
+    #[default]
+    Ascii,
+    Unicode,
+}
+
+/// A cell of a part: row, column, its ASCII and Unicode characters
+type Cell = (usize, usize, char, char);
+
+/// The parts in the order they're drawn, a later part drawing over an earlier one
+const DRAWING: [&[Cell]; PARTS as usize] = [
+    // Ground
+    &[
+        (6, 0, '=', '═'),
+        (6, 1, '=', '═'),
+        (6, 2, '=', '═'),
+        (6, 3, '=', '═'),
+        (6, 4, '=', '═'),
+        (6, 5, '=', '═'),
+        (6, 6, '=', '═'),
+        (6, 7, '=', '═'),
+        (6, 8, '=', '═'),
+    ],
+    // Pole
+    &[
+        (0, 2, '|', '│'),
+        (1, 2, '|', '│'),
+        (2, 2, '|', '│'),
+        (3, 2, '|', '│'),
+        (4, 2, '|', '│'),
+        (5, 2, '|', '│'),
+        (6, 2, '=', '╧'),
+    ],
+    // Beam
+    &[(0, 2, '+', '┌'), (0, 3, '-', '─'), (0, 4, '-', '─'), (0, 5, '-', '─'), (0, 6, '+', '┐')],
+    // Rope
+    &[(1, 6, '|', '│')],
+    // Head
+    &[(2, 6, 'O', '○')],
+    // Body
+    &[(3, 6, '|', '│'), (4, 6, '|', '│')],
+    // Left arm
+    &[(3, 5, '/', '╱')],
+    // Right arm
+    &[(3, 7, '\\', '╲')],
+    // Left leg
+    &[(5, 5, '/', '╱')],
+    // Right leg
+    &[(5, 7, '\\', '╲')],
+];
+
+/// How many parts to draw at `stage` of `stages`
+pub fn parts(stage: u32, stages: u32) -> u32 {
+    if stage >= stages {
+        return PARTS;
+    }
+    // The parts the stages draw, the rest standing from the start
+    let drawn = stages.clamp(FIGURE, PARTS);
+    PARTS - drawn + (stage * drawn).div_ceil(stages).min(drawn - 1)
+}
+
+/// The first `parts` parts, one line each of ROWS lines joined by "\n"
+pub fn draw(parts: u32, style: ArtStyle) -> String {
+    let mut grid = [[' '; COLUMNS]; ROWS];
+    for &(row, column, ascii, unicode) in DRAWING.iter().take(parts as usize).copied().flatten() {
+        grid[row][column] = match style {
+            ArtStyle::Ascii => ascii,
+            ArtStyle::Unicode => unicode,
+        };
+    }
+    let lines: Vec<String> = grid.iter().map(|line| line.iter().collect::<String>().trim_end().to_string()).collect();
+    lines.join("\n")
+}
+
+impl GameState {
+    /// The gallows drawn as text (see gallows.rs)
+    pub fn gallows(&self, style: ArtStyle) -> String {
+        draw(parts(self.stage(), self.stages()), style)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn stages(stages: u32) -> Vec<u32> {
+        (0..=stages).map(|stage| parts(stage, stages)).collect()
+    }
+
+    #[test]
+    fn test_every_stage_draws_a_part() {
+        assert_eq!(stages(6), [4, 5, 6, 7, 8, 9, 10]);
+        assert_eq!(stages(8), [2, 3, 4, 5, 6, 7, 8, 9, 10]);
+        assert_eq!(stages(10), [0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10]);
+        // Fewer stages spread the figure, more everything
+        assert_eq!(stages(4), [4, 6, 7, 9, 10]);
+        assert_eq!(stages(1), [4, 10]);
+        assert_eq!(stages(20)[19..], [9, 10]);
+
+        // Every stage is drawn differently
+        for count in FIGURE..=PARTS {
+            let mut drawings: Vec<String> = stages(count).into_iter().map(|n| draw(n, ArtStyle::Ascii)).collect();
+            drawings.dedup();
+            assert_eq!(drawings.len(), count as usize + 1);
+        }
+    }
+
+    #[test]
+    fn test_drawing() {
+        let ascii = ["  +---+", "  |   |", "  |   O", "  |  /|\\", "  |   |", "  |  / \\", "========="];
+        assert_eq!(draw(PARTS, ArtStyle::Ascii), ascii.join("\n"));
+        let unicode = ["  ┌───┐", "  │   │", "  │   ○", "  │  ╱│╲", "  │   │", "  │  ╱ ╲", "══╧══════"];
+        assert_eq!(draw(PARTS, ArtStyle::Unicode), unicode.join("\n"));
+        // Always as high, even empty
+        assert_eq!(draw(0, ArtStyle::Ascii), "\n".repeat(ROWS - 1));
+        assert_eq!(draw(2, ArtStyle::Ascii).lines().last(), Some("========="));
+    }
+
+    #[test]
+    fn test_drawn_at_the_games_stage() {
+        let mut game = GameState::new("RUST".to_string(), None, crate::difficulty::Difficulty::Easy);
+        assert_eq!(game.gallows(ArtStyle::Ascii), draw(2, ArtStyle::Ascii));
+        game.guess_letter('X');
+        assert_eq!((game.stage(), game.stages()), (1, 8));
+        assert_eq!(game.gallows(ArtStyle::Ascii), draw(3, ArtStyle::Ascii));
+        // A lost game has the whole drawing
+        game.give_up();
+        assert_eq!(game.gallows(ArtStyle::Ascii), draw(PARTS, ArtStyle::Ascii));
+    }
+}
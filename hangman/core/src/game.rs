@@ -0,0 +1,1500 @@
+// ============================================================================
+// GAME STATE AND RULES
+// ============================================================================
+// One game of hangman: the secret word, what has been guessed so far, and
+// whether the game is over. All guessing and win/lose rules live here so the
+// desktop app and the CLI play by exactly the same rules.
+//
+// PHRASES:
+// Answers can be phrases like "RUST PROGRAMMING LANGUAGE". Only letters have
+// to be guessed; spaces, digits, and punctuation are shown from the start.
+//
+// DIGITS AND SYMBOLS:
+// A game picked from a list that guesses digits or symbols too (its
+// `charset`, see charset.rs) hides them like letters: "C++" starts as "___"
+// and "WEB3" as "____". They're keys of the keyboard after the letters,
+// guessed like letters, and the word is only complete once they're found.
+//
+// THE WORD STAYS IN THE BACKEND:
+// GameState keeps the whole answer (it's what's saved and played), but no
+// frontend may read it while the game is running: not the guessing player
+// (through devtools), a guest of a hosted game, or the second player of a
+// game whose secret player one typed (see secret.rs). So nothing sends a
+// GameState, responses are built from player_view(), which masks the word
+// and drops everything that holds it (an evil game's remaining words, also
+// kept in the undo history) until the game is over. See wire.rs for the
+// messages built from it.
+//
+// WORD GUESSES:
+// Players can risk guessing the whole word or phrase. A match wins at once,
+// a miss costs WRONG_WORD_PENALTY wrong guesses.
+//
+// GIVING UP:
+// give_up() ends a game as a loss with `gave_up` set. It's recorded like any
+// other loss, and like running out of time it can't be undone.
+//
+// CLUES:
+// A game picked from a word list carries the clue of its word in `clue`
+// (the word's own, or its category's, see "CLUES" in words.rs), which the
+// frontends show while it's played. Evil games only get the category's, a
+// word's own clue would be about a word the game no longer has. The
+// show_clues setting leaves it out (see settings.rs).
+//
+// EVIL MODE:
+// An evil game (see evil.rs) keeps every word that still fits in `candidates`
+// and changes `word` as it dodges the player's guesses. The rules below apply
+// to whatever `word` is at the time.
+//
+// OTHER ALPHABETS:
+// Words can use any alphabet's letters (see letters.rs). Guesses match
+// regardless of case, and with `ignore_accents` regardless of accents too:
+// guessing "E" then reveals every "É" and "È" as well. `alphabet` is the
+// letters the frontends offer as keys, taken from the word list in use, and
+// key_states() says which were guessed and whether they're in the word, so
+// frontends draw the keyboard as it is.
+//
+// DIGRAPHS:
+// A classic game can have `digraphs`, letter pairs like "CH" guessed as one
+// letter (see digraphs.rs). Their keys come after their first letter's, and
+// the rules match the word split into units (tokens()), not its characters.
+//
+// SEEDS:
+// Words and hint letters are drawn from `rng`. new_random() picks a game's
+// word from the handle it's given and keeps it for the hints, so a handle
+// seeded with the same number (RngHandle::seeded, or SANDBOX_SEED through
+// RngHandle::from_env) deals the same games in the same order. new_seeded()
+// gives a game a seed of its own, kept in `seed` so the game can be started
+// again exactly as it was (e.g. to reproduce a bug report).
+//
+// DAILY CHALLENGE:
+// A daily game (see daily.rs) has its day number in `daily`, the word is the
+// same for everyone that day and the result is saved once it's finished.
+//
+// MATCHES:
+// A round of a best-of-N match (see matches.rs) carries the match in
+// `best_of`, and records its result there when it ends.
+//
+// ARCADE RUNS:
+// A game of an arcade run (see arcade.rs) carries the run in `run`, and
+// records its result there when it ends, like a match's rounds.
+//
+// RACES:
+// A race against the AI (see race.rs) carries the AI's board and whose turn
+// it is in `race`. Guesses and hints are ignored (or refused) on the AI's turn.
+//
+// DUELS:
+// A two-player game carries its duel (see duel.rs) in `duel`: the round,
+// who set the word and the head-to-head score, and records its result there
+// when it ends. rematch() starts the next round with the roles swapped.
+//
+// CAMPAIGN:
+// A game of the campaign (see campaign.rs) has its level in `campaign_level`,
+// and the level's result is saved with the player's progress once it ends.
+//
+// UNDO:
+// Every move is kept in `history` with what it changed, so undo() can take
+// the last one back (see history.rs), `undos` counts how many were.
+//
+// AI MOVES:
+// The AI player (see ai.rs) makes its moves through the same guess methods,
+// `ai_moves` counts them so a game it helped with isn't counted as the
+// player's own.
+//
+// HINTS:
+// A hint reveals one random unguessed letter but costs a wrong guess, and
+// each difficulty allows only a few per game. A hint is refused if paying
+// for it would lose the game.
+//
+// HELPS:
+// Helps bought with points (see shop.rs) are kept in `helps_bought`: a vowel
+// they revealed is in guessed_letters like a hint's letter, a letter they
+// removed is in `removed_letters` and counts as guessed (a miss that was
+// never charged).
+//
+// POWER-UPS:
+// Power-ups a profile earned (see powerups.rs) are kept in `power_ups_used`
+// once used, like helps. A raised `shield` takes the next miss for free, and
+// a frozen clock (`frozen_until`) doesn't count down.
+//
+// PRACTICE:
+// A practice game (`practice`, see start_practice()) can't be lost: wrong
+// guesses are still counted, past max_wrong_guesses too, but never end it,
+// hints are never refused for the guesses they cost, and it has no clock.
+// It ends when the word is found or the player gives up. It isn't ranked():
+// like a game the AI helped with or one with an undo, it's left out of the
+// statistics, profiles, and achievements.
+//
+// KIDS GAMES:
+// A kids game (`kids`, see kids.rs and start_kids()) has a bigger budget, its
+// first letter shown from the start and no clock, and is otherwise classic.
+//
+// GALLOWS STAGES:
+// The drawing has a stage per wrong guess the game allows (stages(), the
+// budget), stage() says which to show: 0 before the first miss, one more per
+// miss, stages() once the game is lost (out of guesses, or out of time). The
+// frontends draw the stage rather than working it out from the counts, so a
+// practice game's misses past the budget, or a clock running out, draw as
+// they should. (gallows.rs draws the stage as text.)
+//
+// SCORE:
+// When the game ends (won or lost) its score is calculated once and kept in
+// `score` (see score.rs), and the word's rating in `rating` (see rating.rs).
+//
+// TIMED GAMES:
+// A timed game has a deadline (a Unix timestamp in milliseconds) set when it
+// starts. The clock is enforced here, not by the frontend: every guess or
+// hint first checks the deadline, and a move received after it loses the
+// game instead. Frontends call check_timer() before showing the game so an
+// expired game is reported as lost even if the player stopped moving.
+// remaining_ms() is what the UI draws the clock from, and it stops when
+// the game ends.
+//
+// PAUSING:
+// pause() freezes a timed game's clock: remaining_ms() stays where it was,
+// the deadline can't pass, and guesses, hints, and helps are refused until
+// resume(). Resuming pushes the deadline back by the time spent paused, so
+// the player gets exactly the time they had left, and `paused_ms` adds up
+// every pause of the game. Giving up (or an undo) works while paused, a game
+// that ends while paused isn't paused any more.
+
+use sandbox_rng::{RngHandle, SliceRandom};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::time::{SystemTime, UNIX_EPOCH};
+use crate::arcade::Run;
+use crate::charset::Charset;
+use crate::difficulty::Difficulty;
+use crate::digraphs::{self, spell_str, tokenize};
+use crate::duel::Duel;
+use crate::evil::{Candidates, GameMode};
+use crate::history::{Action, Move};
+use crate::letters::{fold, fold_str, normalize_word, same_letter, to_upper};
+use crate::matches::{Match, RoundResult};
+use crate::online_words::WordSource;
+use crate::powerups::PowerUp;
+use crate::race::Race;
+use crate::rating::{rate_word, WordRating};
+use crate::score::Score;
+use crate::secret::{normalize_secret, SecretError};
+use crate::settings::{DisplayFormat, LetterCase};
+use crate::shop::Help;
+use crate::words::WordList;
+
+/// Wrong guesses charged for guessing the whole word incorrectly
+pub const WRONG_WORD_PENALTY: u32 = 2;
+
+/// Keys offered when no alphabet was set for the game
+pub const DEFAULT_ALPHABET: &str = "ABCDEFGHIJKLMNOPQRSTUVWXYZ";
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct GameState {
+    pub word: String,
+    pub guessed_letters: Vec<char>,
+    // Wrong whole-word guesses, normalized like the word
+    #[serde(default)]
+    pub guessed_words: Vec<String>,
+    pub wrong_guesses: u32,
+    pub max_wrong_guesses: u32,
+    pub game_over: bool,
+    pub won: bool,
+    // Category the word was picked from (None for the general word list)
+    pub category: Option<String>,
+    // Clue shown with the word (None without one, or with clues off)
+    #[serde(default)]
+    pub clue: Option<String>,
+    pub difficulty: Difficulty,
+    // Whether the word was typed in by another player
+    #[serde(default)]
+    pub custom: bool,
+    // Whether wrong guesses can't lose the game (see start_practice)
+    #[serde(default)]
+    pub practice: bool,
+    // Whether it's a kids game (see kids.rs)
+    #[serde(default)]
+    pub kids: bool,
+    // The provider the word was picked from (see providers.rs), None for words that
+    // didn't come from the main pool (categories, daily challenges, typed-in and kids' words)
+    #[serde(default)]
+    pub word_source: Option<WordSource>,
+    #[serde(default)]
+    pub hints_used: u32,
+    #[serde(default)]
+    pub max_hints: u32,
+    // How letters are shown (the word itself is always stored uppercase)
+    #[serde(default)]
+    pub letter_case: LetterCase,
+    // Whether a letter guess also matches the letter with accents (E finds É)
+    #[serde(default)]
+    pub ignore_accents: bool,
+    // How get_display_word draws the word
+    #[serde(default)]
+    pub display: DisplayFormat,
+    // Letters to offer as keys, from the word list (empty uses DEFAULT_ALPHABET)
+    #[serde(default)]
+    pub alphabet: Vec<char>,
+    // Units for the letter groups guessed as one letter (see digraphs.rs), none by default
+    #[serde(default)]
+    pub digraphs: Vec<char>,
+    // What's guessed besides letters, from the word list (see charset.rs)
+    #[serde(default)]
+    pub charset: Charset,
+    // Profile the game is played by (None if no profile is selected)
+    #[serde(default)]
+    pub player: Option<String>,
+    // Set once the game is over
+    #[serde(default)]
+    pub score: Option<Score>,
+    // How hard the word was, set once the game is over (an evil game's word is only settled then)
+    #[serde(default)]
+    pub rating: Option<WordRating>,
+    // Seconds the player has for a timed game (None for untimed games)
+    #[serde(default)]
+    pub time_limit: Option<u32>,
+    // When a timed game runs out, in Unix milliseconds
+    #[serde(default)]
+    pub deadline: Option<u64>,
+    // Time that was left when the game ended (timed games only)
+    #[serde(default)]
+    pub time_left_ms: Option<u64>,
+    // Whether the game was lost by running out of time
+    #[serde(default)]
+    pub timed_out: bool,
+    // Whether the player gave up (see give_up)
+    #[serde(default)]
+    pub gave_up: bool,
+    // When a timed game was paused, in Unix milliseconds (None while its clock runs)
+    #[serde(default)]
+    pub paused_at: Option<u64>,
+    // Milliseconds the game spent paused, every pause so far
+    #[serde(default)]
+    pub paused_ms: u64,
+    // Words an evil game could still be about (None for classic games)
+    #[serde(default)]
+    pub candidates: Option<Candidates>,
+    // Day of a daily challenge game (None for other games)
+    #[serde(default)]
+    pub daily: Option<u64>,
+    // The match this game is a round of (None for other games)
+    #[serde(default)]
+    pub best_of: Option<Match>,
+    // The arcade run this game is a word of (None for other games)
+    #[serde(default)]
+    pub run: Option<Run>,
+    // The race against the AI this game is (None for other games)
+    #[serde(default)]
+    pub race: Option<Race>,
+    // The duel this two-player game is a round of (None for other games)
+    #[serde(default)]
+    pub duel: Option<Duel>,
+    // The campaign level this game is (None for other games)
+    #[serde(default)]
+    pub campaign_level: Option<u32>,
+    // Moves made by the AI player
+    #[serde(default)]
+    pub ai_moves: u32,
+    // Every move so far, oldest first (see history.rs)
+    #[serde(default)]
+    pub history: Vec<Move>,
+    // Moves taken back with undo
+    #[serde(default)]
+    pub undos: u32,
+    // Seed of a game started with one (see new_seeded), None for the others
+    #[serde(default)]
+    pub seed: Option<u64>,
+    // Helps bought with points, in order (see shop.rs)
+    #[serde(default)]
+    pub helps_bought: Vec<Help>,
+    // Letters a help took off the board, none of them in the word
+    #[serde(default)]
+    pub removed_letters: Vec<char>,
+    // Power-ups used, in order (see powerups.rs)
+    #[serde(default)]
+    pub power_ups_used: Vec<PowerUp>,
+    // Whether a shield will take the next miss
+    #[serde(default)]
+    pub shield: bool,
+    // When a frozen clock runs again (Unix ms, None if it was never frozen)
+    #[serde(default)]
+    pub frozen_until: Option<u64>,
+    // Moves made before the last help bought (or power-up used), which undo can't take back
+    #[serde(default)]
+    pub undo_floor: usize,
+    // Where hint letters come from (the app's handle, so a seed replays hints too)
+    #[serde(skip)]
+    pub rng: RngHandle,
+}
+
+/// Reasons a hint can't be given
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HintError {
+    /// The game has already been won or lost
+    GameOver,
+    /// All hints for this game have been used
+    NoHintsLeft,
+    /// The hint's cost (one wrong guess) would lose the game
+    NotEnoughGuesses,
+    /// The game is paused (see pause)
+    Paused,
+    /// It's the AI's turn in a race (see race.rs)
+    NotYourTurn,
+}
+
+impl fmt::Display for HintError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            HintError::GameOver => write!(f, "the game is already over"),
+            HintError::NoHintsLeft => write!(f, "no hints left for this game"),
+            HintError::NotEnoughGuesses => write!(f, "a hint costs a wrong guess and you only have one left"),
+            HintError::Paused => write!(f, "the game is paused"),
+            HintError::NotYourTurn => write!(f, "it's the AI's turn"),
+        }
+    }
+}
+
+impl std::error::Error for HintError {}
+
+/// Reasons a game can't be paused or resumed
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PauseError {
+    /// The game has already been won or lost
+    GameOver,
+    /// Only timed games have a clock to stop
+    NotTimed,
+    AlreadyPaused,
+    NotPaused,
+}
+
+impl fmt::Display for PauseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            PauseError::GameOver => write!(f, "the game is already over"),
+            PauseError::NotTimed => write!(f, "only timed games can be paused"),
+            PauseError::AlreadyPaused => write!(f, "the game is already paused"),
+            PauseError::NotPaused => write!(f, "the game isn't paused"),
+        }
+    }
+}
+
+impl std::error::Error for PauseError {}
+
+/// Where a key of the keyboard stands
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum KeyState {
+    /// Not guessed yet
+    Unused,
+    /// Guessed (or revealed by a hint) and in the word
+    Correct,
+    /// Guessed and not in the word
+    Wrong,
+}
+
+impl GameState {
+    pub fn new(word: String, category: Option<String>, difficulty: Difficulty) -> Self {
+        GameState {
+            word: normalize_word(&word),
+            guessed_letters: Vec::new(),
+            guessed_words: Vec::new(),
+            wrong_guesses: 0,
+            max_wrong_guesses: difficulty.max_wrong_guesses(),
+            game_over: false,
+            won: false,
+            category,
+            clue: None,
+            difficulty,
+            custom: false,
+            practice: false,
+            kids: false,
+            word_source: None,
+            hints_used: 0,
+            max_hints: difficulty.max_hints(),
+            letter_case: LetterCase::default(),
+            display: DisplayFormat::default(),
+            ignore_accents: false,
+            alphabet: Vec::new(),
+            digraphs: Vec::new(),
+            charset: Charset::default(),
+            player: None,
+            score: None,
+            rating: None,
+            time_limit: None,
+            deadline: None,
+            time_left_ms: None,
+            timed_out: false,
+            gave_up: false,
+            paused_at: None,
+            paused_ms: 0,
+            candidates: None,
+            daily: None,
+            best_of: None,
+            run: None,
+            race: None,
+            duel: None,
+            campaign_level: None,
+            ai_moves: 0,
+            history: Vec::new(),
+            undos: 0,
+            seed: None,
+            helps_bought: Vec::new(),
+            removed_letters: Vec::new(),
+            power_ups_used: Vec::new(),
+            shield: false,
+            frozen_until: None,
+            undo_floor: 0,
+            rng: RngHandle::default(),
+        }
+    }
+
+    /// Start an evil game: `word` (picked as usual) only sets the shape of
+    /// the answer, which can be any word of `list` with that shape
+    pub fn new_evil(list: &WordList, word: String, category: Option<String>, difficulty: Difficulty) -> Self {
+        let mut game = GameState::new(word, category, difficulty);
+        game.candidates = Some(Candidates::new(list, &game.word));
+        game.charset = list.charset().clone();
+        game
+    }
+
+    /// Start a game with a word of `words` picked for the difficulty, classic or
+    /// evil, drawing the word and later the hint letters from `rng`
+    pub fn new_random(
+        words: &WordList,
+        category: Option<String>,
+        difficulty: Difficulty,
+        mode: GameMode,
+        mut rng: RngHandle,
+    ) -> Self {
+        let word = difficulty.pick_word(words, &mut rng);
+        let mut game = match mode {
+            GameMode::Classic => GameState::new(word, category, difficulty),
+            GameMode::Evil => GameState::new_evil(words, word, category, difficulty),
+        };
+        game.clue = match mode {
+            GameMode::Classic => words.clue(&game.word),
+            GameMode::Evil => words.list_clue(),
+        }
+        .map(str::to_string);
+        game.charset = words.charset().clone();
+        game.rng = rng;
+        game
+    }
+
+    /// Start a game like new_random, from its own `seed`: the same seed and words
+    /// always give the same word and hints
+    pub fn new_seeded(words: &WordList, category: Option<String>, difficulty: Difficulty, mode: GameMode, seed: u64) -> Self {
+        let mut game = GameState::new_random(words, category, difficulty, mode, RngHandle::seeded(seed));
+        game.seed = Some(seed);
+        game
+    }
+
+    pub fn mode(&self) -> GameMode {
+        match self.candidates {
+            Some(_) => GameMode::Evil,
+            None => GameMode::Classic,
+        }
+    }
+
+    /// Make this a timed game: the player has `seconds` from now to finish it
+    pub fn start_timer(&mut self, seconds: u32) {
+        self.time_limit = Some(seconds);
+        self.deadline = Some(now_ms() + u64::from(seconds) * 1000);
+    }
+
+    /// Make a freshly started game a practice game: no clock, and wrong guesses never end it
+    pub fn start_practice(&mut self) {
+        self.practice = true;
+        self.time_limit = None;
+        self.deadline = None;
+    }
+
+    /// Whether the game counts in the statistics, profiles, and achievements: not a
+    /// practice game, and played without the AI's moves or an undo
+    pub fn ranked(&self) -> bool {
+        !self.practice && self.ai_moves == 0 && self.undos == 0
+    }
+
+    /// Whether the wrong guesses have used up the budget, which loses any game but a practice one
+    fn out_of_guesses(&self) -> bool {
+        !self.practice && self.wrong_guesses >= self.max_wrong_guesses
+    }
+
+    /// Milliseconds left in a timed game (None for untimed games)
+    /// Once the game is over this is the time that was left when it ended
+    pub fn remaining_ms(&self) -> Option<u64> {
+        if self.game_over {
+            return self.time_left_ms;
+        }
+        // A frozen clock stays where it was until it runs again
+        let now = self.paused_at.unwrap_or_else(now_ms).max(self.frozen_until.unwrap_or(0));
+        self.deadline.map(|deadline| deadline.saturating_sub(now))
+    }
+
+    /// How much of the gallows to draw, 0 to stages()
+    pub fn stage(&self) -> u32 {
+        if self.game_over && !self.won {
+            return self.stages();
+        }
+        self.wrong_guesses.min(self.max_wrong_guesses)
+    }
+
+    /// Stages of the gallows drawing, one per wrong guess allowed
+    pub fn stages(&self) -> u32 {
+        self.max_wrong_guesses
+    }
+
+    /// Lose the game if its time has run out
+    /// Returns whether this call ended the game
+    pub fn check_timer(&mut self) -> bool {
+        if self.game_over || self.remaining_ms() != Some(0) {
+            return false;
+        }
+        self.timed_out = true;
+        self.end(false);
+        true
+    }
+
+    /// End the game as a loss, which shows the word like any other loss
+    /// Returns false if it was already over (or the time ran out first)
+    pub fn give_up(&mut self) -> bool {
+        if self.check_timer() || self.game_over {
+            return false;
+        }
+        self.gave_up = true;
+        self.end(false);
+        true
+    }
+
+    /// Stop a timed game's clock until resume()
+    pub fn pause(&mut self) -> Result<(), PauseError> {
+        if self.check_timer() || self.game_over {
+            return Err(PauseError::GameOver);
+        }
+        if self.deadline.is_none() {
+            return Err(PauseError::NotTimed);
+        }
+        if self.is_paused() {
+            return Err(PauseError::AlreadyPaused);
+        }
+        self.paused_at = Some(now_ms());
+        Ok(())
+    }
+
+    /// Start a paused game's clock again, with the time it had left when it was paused
+    pub fn resume(&mut self) -> Result<(), PauseError> {
+        if self.game_over {
+            return Err(PauseError::GameOver);
+        }
+        if !self.is_paused() {
+            return Err(PauseError::NotPaused);
+        }
+        self.unpause();
+        Ok(())
+    }
+
+    pub fn is_paused(&self) -> bool {
+        self.paused_at.is_some()
+    }
+
+    /// Move the deadline back by the time since pause() and count it
+    fn unpause(&mut self) {
+        if let Some(paused_at) = self.paused_at.take() {
+            let paused = now_ms().saturating_sub(paused_at);
+            self.paused_ms += paused;
+            self.deadline = self.deadline.map(|deadline| deadline + paused);
+            self.frozen_until = self.frozen_until.map(|until| until + paused);
+        }
+    }
+
+    /// Start a two-player game with a secret typed in by player one
+    pub fn new_custom(secret: &str, difficulty: Difficulty) -> Result<Self, SecretError> {
+        let word = normalize_secret(secret)?;
+        Ok(GameState {
+            custom: true,
+            duel: Some(Duel::new()),
+            ..GameState::new(word, None, difficulty)
+        })
+    }
+
+    /// The state as the guessing player may see it: while the game is running
+    /// the word only contains the revealed characters, everything else is "_"
+    /// e.g. "RUST IS" with R and S guessed -> "R_S_ _S", and an evil game's
+    /// candidates are dropped (so the view's mode() is always classic)
+    /// Letters are converted to the game's letter case
+    pub fn player_view(&self) -> GameState {
+        let mut view = self.clone();
+        if !self.game_over {
+            view.candidates = None;
+            view.history.iter_mut().for_each(Move::hide_word);
+            if let Some(race) = &mut view.race {
+                *race.board = race.board.player_view();
+                race.board.word = race.board.mask();
+            }
+        }
+        view.word = if self.game_over {
+            self.letter_case.apply_str(&self.word)
+        } else {
+            self.mask()
+        };
+        view.guessed_letters = self.guessed_letters.iter().map(|&c| self.letter_case.apply(c)).collect();
+        view.guessed_words = self.guessed_words.iter().map(|w| self.letter_case.apply_str(w)).collect();
+        view
+    }
+
+    /// The word split into what's guessed: its characters, with each of the
+    /// game's digraphs as one unit
+    pub fn tokens(&self) -> Vec<char> {
+        tokenize(&self.word, &self.digraphs)
+    }
+
+    /// The letters to offer as keys, one per letter a guess can tell apart
+    /// (with ignore_accents "É" is dropped when "E" is in the alphabet),
+    /// each digraph after the keys of its first letter, then the charset's
+    /// digits and symbols
+    pub fn keyboard(&self) -> Vec<char> {
+        let alphabet = if self.alphabet.is_empty() {
+            DEFAULT_ALPHABET.chars().collect()
+        } else {
+            self.alphabet.clone()
+        };
+        let mut keys: Vec<char> = Vec::new();
+        for c in alphabet {
+            if !keys.iter().any(|&key| same_letter(key, c, self.ignore_accents)) {
+                keys.push(c);
+            }
+        }
+        for &unit in &self.digraphs {
+            let first = digraphs::spell(unit).chars().next();
+            let at = keys
+                .iter()
+                .rposition(|&key| digraphs::spell(key).chars().next() == first)
+                .map_or(keys.len(), |at| at + 1);
+            keys.insert(at, unit);
+        }
+        keys.extend(self.charset.keys());
+        keys
+    }
+
+    /// Every key of keyboard() with whether it was guessed, and if so whether it's in the word
+    pub fn key_states(&self) -> Vec<(char, KeyState)> {
+        self.keyboard()
+            .into_iter()
+            .map(|key| {
+                let state = if !self.is_guessed(key) {
+                    KeyState::Unused
+                } else if self.tokens().into_iter().any(|c| same_letter(c, key, self.ignore_accents)) {
+                    KeyState::Correct
+                } else {
+                    KeyState::Wrong
+                };
+                (key, state)
+            })
+            .collect()
+    }
+
+    /// Guess a letter (case-insensitive, and accent-insensitive with ignore_accents)
+    /// Repeat guesses and guesses after the game is over are ignored
+    /// (a guess after a timed game's deadline loses it instead)
+    /// Returns whether the letter is in the word (false for an ignored guess, and
+    /// for a miss a shield took)
+    pub fn guess_letter(&mut self, letter: char) -> bool {
+        if self.check_timer() || self.game_over || self.is_paused() || self.is_ai_turn() {
+            return false;
+        }
+
+        let letter_upper = to_upper(letter);
+
+        if !self.is_guessed(letter_upper) {
+            self.begin_move(Action::Letter(letter_upper));
+            self.guessed_letters.push(letter_upper);
+            if let Some(candidates) = &mut self.candidates {
+                self.word = candidates.narrow(letter_upper, self.ignore_accents).to_string();
+            }
+
+            let hit = self.tokens().into_iter().any(|c| same_letter(c, letter_upper, self.ignore_accents));
+            self.finish_move(hit);
+            if !hit {
+                self.charge(1);
+            }
+
+            if self.out_of_guesses() {
+                self.end(false);
+            } else if self.check_win() {
+                self.end(true);
+            }
+            return hit;
+        }
+        false
+    }
+
+    /// Whether a word guess was already made and missed (compared like guess_word)
+    pub fn has_guessed_word(&self, guess: &str) -> bool {
+        let guess = fold_str(&normalize_word(guess), self.ignore_accents);
+        self.guessed_words.iter().any(|word| fold_str(word, self.ignore_accents) == guess)
+    }
+
+    /// Guess the whole word or phrase (case and extra spaces don't matter,
+    /// accents don't either with ignore_accents)
+    /// A match wins the game, a miss costs WRONG_WORD_PENALTY wrong guesses
+    /// Empty guesses, repeat misses, and guesses after the game is over are ignored
+    /// Returns whether the guess was right
+    pub fn guess_word(&mut self, guess: &str) -> bool {
+        let repeat = self.has_guessed_word(guess);
+        let guess = normalize_word(guess);
+        if self.check_timer() || self.game_over || self.is_paused() || self.is_ai_turn() || guess.is_empty() || repeat {
+            return false;
+        }
+        self.begin_move(Action::Word(guess.clone()));
+        if let Some(candidates) = &mut self.candidates {
+            if let Some(word) = candidates.dodge(&guess, self.ignore_accents) {
+                self.word = word.to_string();
+            }
+        }
+
+        if fold_str(&guess, self.ignore_accents) == fold_str(&self.word, self.ignore_accents) {
+            // Reveal the remaining letters so the guessed list matches the board
+            for c in self.tokens() {
+                if self.is_guessable(c) && !self.is_guessed(c) {
+                    self.guessed_letters.push(c);
+                }
+            }
+            self.finish_move(true);
+            self.end(true);
+            return true;
+        }
+
+        self.guessed_words.push(guess);
+        self.charge(WRONG_WORD_PENALTY);
+        if self.out_of_guesses() {
+            self.end(false);
+        }
+        false
+    }
+
+    /// Count `wrong` wrong guesses for the miss made last, unless a shield takes it
+    fn charge(&mut self, wrong: u32) {
+        if self.use_shield() {
+            self.shield_move();
+        } else if self.practice {
+            self.wrong_guesses += wrong;
+        } else {
+            self.wrong_guesses = (self.wrong_guesses + wrong).min(self.max_wrong_guesses);
+        }
+    }
+
+    /// Reveal a random unguessed letter, paid for with one wrong guess
+    /// Returns the revealed letter
+    pub fn use_hint(&mut self) -> Result<char, HintError> {
+        self.check_hint()?;
+
+        // One entry per letter a guess would reveal, so each is equally likely
+        let mut hidden: Vec<char> = self.tokens().into_iter().filter(|&c| !self.is_revealed(c)).collect();
+        hidden.sort_unstable_by_key(|&c| (fold(c, self.ignore_accents), c));
+        hidden.dedup_by(|a, b| same_letter(*a, *b, self.ignore_accents));
+        // Not game over, so at least one letter is still hidden
+        let letter = *hidden.choose(&mut self.rng).expect("unrevealed letter");
+        self.reveal_hint(letter);
+        Ok(letter)
+    }
+
+    /// Whether a hint can be given now
+    pub(crate) fn check_hint(&mut self) -> Result<(), HintError> {
+        if self.check_timer() || self.game_over {
+            return Err(HintError::GameOver);
+        }
+        if self.is_paused() {
+            return Err(HintError::Paused);
+        }
+        if self.is_ai_turn() {
+            return Err(HintError::NotYourTurn);
+        }
+        if self.hints_used >= self.max_hints {
+            return Err(HintError::NoHintsLeft);
+        }
+        if !self.practice && self.wrong_guesses + 1 >= self.max_wrong_guesses {
+            return Err(HintError::NotEnoughGuesses);
+        }
+        Ok(())
+    }
+
+    /// Give `letter` (a hidden letter of the word) as a hint, once check_hint() allowed one
+    pub(crate) fn reveal_hint(&mut self, letter: char) {
+        self.begin_move(Action::Hint(letter));
+        self.finish_move(true);
+        if let Some(candidates) = &mut self.candidates {
+            self.word = candidates.reveal(letter, self.ignore_accents).to_string();
+        }
+
+        self.hints_used += 1;
+        self.wrong_guesses += 1;
+        self.guessed_letters.push(letter);
+        if self.check_win() {
+            self.end(true);
+        }
+    }
+
+    /// The word with unguessed letters replaced by "_" and nothing else changed,
+    /// in the game's letter case, e.g. "R_ST ____" (even once the game is over)
+    /// A digraph stays one character (a unit, spelled out by digraphs::spell_str)
+    pub fn mask(&self) -> String {
+        self.tokens()
+            .into_iter()
+            .map(|c| if self.is_revealed(c) { self.letter_case.apply(c) } else { '_' })
+            .collect()
+    }
+
+    /// The word drawn in the game's display format (see settings.rs), by default
+    /// unguessed letters as "_", letters separated by a space and the words of
+    /// a phrase by three spaces, e.g. "R _ S T   _ _ _ _"
+    /// A digraph is drawn as one letter, e.g. "CH I L E"
+    pub fn get_display_word(&self) -> String {
+        let tokens: String = self.tokens().into_iter().collect();
+        let drawn = self
+            .display
+            .format(&tokens, |c| self.is_revealed(c).then(|| self.letter_case.apply(c)));
+        spell_str(&drawn, self.letter_case)
+    }
+
+    pub fn check_win(&self) -> bool {
+        self.tokens().into_iter().all(|c| self.is_revealed(c))
+    }
+
+    /// End the game and work out its score
+    pub(crate) fn end(&mut self, won: bool) {
+        self.time_left_ms = self.remaining_ms();
+        self.unpause();
+        self.game_over = true;
+        self.won = won;
+        self.score = Some(Score::calculate(self));
+        self.rating = Some(rate_word(&self.word));
+        let result = RoundResult {
+            word: self.word.clone(),
+            won,
+            score: self.score.map_or(0, |score| score.total),
+        };
+        if let Some(run) = &mut self.run {
+            run.record(result.clone());
+        }
+        if let Some(best_of) = &mut self.best_of {
+            best_of.record(result);
+        }
+        if let Some(duel) = &mut self.duel {
+            duel.record(won);
+        }
+    }
+
+    /// Whether a letter (in any case) has been guessed, revealed by a hint, or removed by a help
+    pub fn is_guessed(&self, c: char) -> bool {
+        self.guessed_letters
+            .iter()
+            .chain(&self.removed_letters)
+            .any(|&guessed| same_letter(guessed, c, self.ignore_accents))
+    }
+
+    /// Whether a character of the word has to be guessed in this game: a
+    /// letter, or one of its charset's digits and symbols
+    pub fn is_guessable(&self, c: char) -> bool {
+        self.charset.is_guessable(c)
+    }
+
+    /// Whether a character of the word is shown to the player
+    /// Characters that aren't guessed are always shown, the others once they've been guessed
+    pub(crate) fn is_revealed(&self, c: char) -> bool {
+        !self.is_guessable(c) || self.is_guessed(c)
+    }
+}
+
+/// The current time in Unix milliseconds
+pub fn now_ms() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|elapsed| elapsed.as_millis() as u64)
+        .unwrap_or(0)
+}
+
+/// Whether a character has to be guessed (letters and digraphs do, spaces and punctuation don't)
+/// A game's charset can add digits and symbols (GameState::is_guessable)
+pub fn is_guessable(c: char) -> bool {
+    c.is_alphabetic() || digraphs::is_unit(c)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::settings::Settings;
+
+    fn new_game(word: &str) -> GameState {
+        GameState::new(word.to_string(), None, Difficulty::Medium)
+    }
+
+    #[test]
+    fn test_new_game() {
+        let game = new_game("rust");
+        assert_eq!(game.word, "RUST");
+        assert_eq!(game.max_wrong_guesses, 6);
+        assert!(!game.game_over);
+        assert_eq!(game.get_display_word(), "_ _ _ _");
+    }
+
+    #[test]
+    fn test_correct_guess() {
+        let mut game = new_game("RUST");
+        game.guess_letter('r');
+        assert_eq!(game.guessed_letters, ['R']);
+        assert_eq!(game.wrong_guesses, 0);
+        assert_eq!(game.get_display_word(), "R _ _ _");
+    }
+
+    #[test]
+    fn test_wrong_guess() {
+        let mut game = new_game("RUST");
+        game.guess_letter('X');
+        assert_eq!(game.wrong_guesses, 1);
+    }
+
+    #[test]
+    fn test_repeat_guess_is_ignored() {
+        let mut game = new_game("RUST");
+        game.guess_letter('X');
+        game.guess_letter('x');
+        assert_eq!(game.wrong_guesses, 1);
+        assert_eq!(game.guessed_letters.len(), 1);
+    }
+
+    #[test]
+    fn test_win() {
+        let mut game = new_game("RUST");
+        for letter in "RUST".chars() {
+            game.guess_letter(letter);
+        }
+        assert!(game.game_over);
+        assert!(game.won);
+        assert!(game.check_win());
+    }
+
+    #[test]
+    fn test_lose() {
+        let mut game = GameState::new("RUST".to_string(), None, Difficulty::Hard);
+        for letter in "ABCD".chars() {
+            game.guess_letter(letter);
+        }
+        assert!(game.game_over);
+        assert!(!game.won);
+    }
+
+    #[test]
+    fn test_phrase_display_keeps_word_boundaries() {
+        let mut game = new_game("RUST IS FUN");
+        assert_eq!(game.get_display_word(), "_ _ _ _   _ _   _ _ _");
+        game.guess_letter('S');
+        assert_eq!(game.get_display_word(), "_ _ S _   _ S   _ _ _");
+    }
+
+    #[test]
+    fn test_punctuation_is_revealed() {
+        let game = new_game("DON'T PANIC!");
+        assert_eq!(game.get_display_word(), "_ _ _ ' _   _ _ _ _ _ !");
+    }
+
+    #[test]
+    fn test_phrase_win_ignores_spaces_and_punctuation() {
+        let mut game = new_game("HI, YOU");
+        for letter in "HIYOU".chars() {
+            game.guess_letter(letter);
+        }
+        assert!(game.won);
+        assert_eq!(game.get_display_word(), "H I ,   Y O U");
+    }
+
+    #[test]
+    fn test_guessable_digits_and_symbols() {
+        let words = WordList::from_text("#! guessable: digits +\nweb3\n").unwrap();
+        let mut game = GameState::new_random(&words, None, Difficulty::Medium, GameMode::Classic, RngHandle::seeded(1));
+        assert_eq!(game.mask(), "____");
+        let keys = game.keyboard();
+        assert_eq!(&keys[26..], "0123456789+".chars().collect::<Vec<_>>());
+        for letter in "WEB".chars() {
+            game.guess_letter(letter);
+        }
+        assert_eq!(game.mask(), "WEB_");
+        assert!(!game.won);
+        game.guess_letter('3');
+        assert!(game.won);
+        assert!(game.key_states().contains(&('3', KeyState::Correct)));
+
+        // The same word from a list of letters shows its digit
+        assert_eq!(new_game("WEB3").mask(), "___3");
+    }
+
+    #[test]
+    fn test_practice_game_cant_be_lost() {
+        let mut game = new_game("RUST");
+        game.start_timer(60);
+        game.start_practice();
+        assert_eq!(game.remaining_ms(), None);
+        assert!(!game.ranked());
+        for letter in "ABCDEFGHI".chars() {
+            game.guess_letter(letter);
+        }
+        game.guess_word("RUBY");
+        assert!(!game.game_over);
+        assert_eq!((game.wrong_guesses, game.max_wrong_guesses), (11, 6));
+        assert_eq!(game.stage(), game.stages());
+        assert!(game.use_hint().is_ok());
+
+        assert!(game.guess_word("RUST"));
+        assert!(game.game_over);
+        assert!(new_game("RUST").ranked());
+    }
+
+    #[test]
+    fn test_custom_game() {
+        let game = GameState::new_custom(" secret  word ", Difficulty::Easy).unwrap();
+        assert_eq!(game.word, "SECRET WORD");
+        assert!(game.custom);
+        assert_eq!(game.max_wrong_guesses, 8);
+        assert_eq!(
+            GameState::new_custom("42", Difficulty::Easy).unwrap_err(),
+            SecretError::InvalidCharacter('4')
+        );
+    }
+
+    #[test]
+    fn test_player_view_hides_word_until_game_over() {
+        let mut game = new_game("RUST IS");
+        game.guess_letter('R');
+        game.guess_letter('S');
+        let view = game.player_view();
+        assert_eq!(view.word, "R_S_ _S");
+        assert_eq!(view.get_display_word(), game.get_display_word());
+
+        for letter in "UTI".chars() {
+            game.guess_letter(letter);
+        }
+        assert_eq!(game.player_view().word, "RUST IS");
+    }
+
+    #[test]
+    fn test_player_view_drops_evil_words() {
+        let words = WordList::from_text("rust
+ruby
+java
+perl").unwrap();
+        let mut game = GameState::new_evil(&words, "RUST".to_string(), None, Difficulty::Medium);
+        game.guess_letter('A');
+        game.guess_letter('R');
+        let json = serde_json::to_string(&game.player_view()).unwrap();
+        for word in ["RUST", "RUBY", "JAVA", "PERL"] {
+            assert!(!json.contains(word), "{} in {}", word, json);
+        }
+        // The game itself still has them, to undo
+        assert!(game.candidates.is_some());
+        game.undo().unwrap();
+        assert_eq!(game.guessed_letters, ['A']);
+    }
+
+    #[test]
+    fn test_guess_word_win() {
+        let mut game = new_game("RUST IS FUN");
+        game.guess_letter('R');
+        assert!(game.guess_word("  rust   is fun "));
+        assert!(game.game_over);
+        assert!(game.won);
+        assert!(game.check_win());
+        assert_eq!(game.guessed_letters.len(), 7);
+    }
+
+    #[test]
+    fn test_guess_word_miss_costs_two() {
+        let mut game = new_game("RUST");
+        assert!(!game.guess_word("RUBY"));
+        assert_eq!(game.wrong_guesses, WRONG_WORD_PENALTY);
+        assert_eq!(game.guessed_words, ["RUBY"]);
+
+        // Repeats and empty guesses are free
+        assert!(!game.guess_word("ruby"));
+        assert!(!game.guess_word("  "));
+        assert_eq!(game.wrong_guesses, WRONG_WORD_PENALTY);
+    }
+
+    #[test]
+    fn test_guess_word_miss_can_lose() {
+        let mut game = GameState::new("RUST".to_string(), None, Difficulty::Hard);
+        for letter in "ABC".chars() {
+            game.guess_letter(letter);
+        }
+        assert!(!game.guess_word("RUBY"));
+        assert!(game.game_over);
+        assert!(!game.won);
+        assert_eq!(game.wrong_guesses, game.max_wrong_guesses);
+        assert!(!game.guess_word("RUST"));
+    }
+
+    #[test]
+    fn test_hint_reveals_letter_at_a_cost() {
+        let mut game = new_game("RUST");
+        game.guess_letter('R');
+        let letter = game.use_hint().unwrap();
+        assert!("UST".contains(letter));
+        assert!(game.guessed_letters.contains(&letter));
+        assert_eq!(game.wrong_guesses, 1);
+        assert_eq!(game.hints_used, 1);
+    }
+
+    #[test]
+    fn test_hint_limit() {
+        let mut game = new_game("ABCDEFG");
+        assert_eq!(game.max_hints, 2);
+        game.use_hint().unwrap();
+        game.use_hint().unwrap();
+        assert_eq!(game.use_hint(), Err(HintError::NoHintsLeft));
+        assert_eq!(game.hints_used, 2);
+    }
+
+    #[test]
+    fn test_hint_refused_on_last_guess() {
+        let mut game = GameState::new("RUST".to_string(), None, Difficulty::Hard);
+        for letter in "ABC".chars() {
+            game.guess_letter(letter);
+        }
+        assert_eq!(game.use_hint(), Err(HintError::NotEnoughGuesses));
+        assert_eq!(game.wrong_guesses, 3);
+    }
+
+    #[test]
+    fn test_hint_can_win_and_not_after_game_over() {
+        let mut game = new_game("RUST");
+        for letter in "RUS".chars() {
+            game.guess_letter(letter);
+        }
+        assert_eq!(game.use_hint(), Ok('T'));
+        assert!(game.won);
+        assert_eq!(game.use_hint(), Err(HintError::GameOver));
+    }
+
+    #[test]
+    fn test_player_view_letter_case() {
+        let mut game = new_game("RUST");
+        game.letter_case = LetterCase::Lower;
+        game.guess_letter('r');
+        game.guess_word("ruby");
+        let view = game.player_view();
+        assert_eq!(view.word, "r___");
+        assert_eq!(view.guessed_letters, ['r']);
+        assert_eq!(view.guessed_words, ["ruby"]);
+        assert_eq!(view.get_display_word(), "r _ _ _");
+    }
+
+    #[test]
+    fn test_timed_game_counts_down() {
+        let mut game = new_game("RUST");
+        assert_eq!(game.remaining_ms(), None);
+        game.start_timer(90);
+        assert_eq!(game.time_limit, Some(90));
+        let remaining = game.remaining_ms().unwrap();
+        assert!(remaining > 89_000 && remaining <= 90_000);
+        assert!(!game.check_timer());
+
+        // The clock stops when the game ends
+        assert!(game.guess_word("RUST"));
+        let left = game.remaining_ms().unwrap();
+        game.deadline = Some(0);
+        assert_eq!(game.remaining_ms(), Some(left));
+        assert!(!game.check_timer());
+        assert!(game.won);
+    }
+
+    #[test]
+    fn test_move_after_deadline_loses() {
+        let mut game = new_game("RUST");
+        game.start_timer(90);
+        game.guess_letter('R');
+        game.deadline = Some(now_ms() - 1);
+
+        game.guess_letter('U');
+        assert!(game.game_over);
+        assert!(!game.won);
+        assert!(game.timed_out);
+        assert_eq!(game.guessed_letters, ['R']);
+        assert_eq!(game.remaining_ms(), Some(0));
+        assert!(game.score.is_some());
+    }
+
+    #[test]
+    fn test_give_up() {
+        let mut game = new_game("RUST");
+        game.guess_letter('R');
+        assert!(game.give_up());
+        assert!(game.game_over && !game.won && game.gave_up);
+        assert_eq!(game.player_view().word, "RUST");
+        assert!(game.score.is_some());
+        // Nothing more can be played, or taken back
+        assert!(!game.give_up());
+        game.guess_letter('U');
+        assert_eq!(game.guessed_letters, ['R']);
+        assert!(!game.can_undo());
+
+        let mut game = new_game("RUST");
+        game.start_timer(90);
+        game.deadline = Some(now_ms() - 1);
+        assert!(!game.give_up());
+        assert!(game.timed_out && !game.gave_up);
+    }
+
+    #[test]
+    fn test_pause_freezes_the_clock() {
+        let mut game = new_game("RUST");
+        assert_eq!(game.pause(), Err(PauseError::NotTimed));
+        game.start_timer(90);
+        assert_eq!(game.resume(), Err(PauseError::NotPaused));
+        game.pause().unwrap();
+        assert_eq!(game.pause(), Err(PauseError::AlreadyPaused));
+
+        // Ten seconds on pause: the deadline passes, but not for the game
+        game.paused_at = Some(game.paused_at.unwrap() - 10_000);
+        game.deadline = Some(game.deadline.unwrap() - 10_000);
+        let left = game.remaining_ms().unwrap();
+        assert!(left > 80_000);
+        assert!(!game.check_timer());
+        game.guess_letter('R');
+        game.guess_word("RUST");
+        assert_eq!(game.use_hint(), Err(HintError::Paused));
+        assert!(game.guessed_letters.is_empty() && !game.game_over);
+
+        game.resume().unwrap();
+        assert!(!game.is_paused());
+        assert!(game.paused_ms >= 10_000);
+        assert!(game.remaining_ms().unwrap() <= left && game.remaining_ms().unwrap() > left - 1000);
+        game.guess_letter('R');
+        assert_eq!(game.guessed_letters, ['R']);
+    }
+
+    #[test]
+    fn test_game_ending_while_paused() {
+        let mut game = new_game("RUST");
+        game.start_timer(90);
+        game.pause().unwrap();
+        assert!(game.give_up());
+        assert!(!game.is_paused());
+        assert_eq!(game.resume(), Err(PauseError::GameOver));
+        assert_eq!(game.pause(), Err(PauseError::GameOver));
+    }
+
+    #[test]
+    fn test_expired_game_refuses_word_and_hint() {
+        let mut game = new_game("RUST");
+        game.start_timer(90);
+        game.deadline = Some(now_ms() - 1);
+        assert!(!game.guess_word("RUST"));
+        assert!(game.timed_out);
+
+        let mut game = new_game("RUST");
+        game.start_timer(90);
+        game.deadline = Some(now_ms() - 1);
+        assert_eq!(game.use_hint(), Err(HintError::GameOver));
+        assert!(game.timed_out);
+    }
+
+    #[test]
+    fn test_accented_letters() {
+        let mut game = new_game("niño");
+        assert_eq!(game.word, "NIÑO");
+        game.guess_letter('n');
+        assert_eq!(game.get_display_word(), "N _ _ _");
+        game.guess_letter('ñ');
+        assert_eq!(game.get_display_word(), "N _ Ñ _");
+        assert!(!game.is_guessed('I'));
+
+        let mut game = new_game("straße");
+        game.guess_letter('ẞ');
+        assert_eq!(game.wrong_guesses, 0);
+        assert_eq!(game.get_display_word(), "_ _ _ _ ß _");
+
+        let mut game = new_game("привет");
+        game.guess_letter('п');
+        assert_eq!(game.guessed_letters, ['П']);
+        assert_eq!(game.get_display_word(), "П _ _ _ _ _");
+    }
+
+    #[test]
+    fn test_ignore_accents() {
+        let mut game = new_game("école");
+        game.ignore_accents = true;
+        game.guess_letter('E');
+        assert_eq!(game.wrong_guesses, 0);
+        assert_eq!(game.get_display_word(), "É _ _ _ E");
+        // É is the same guess as E now
+        game.guess_letter('é');
+        assert_eq!(game.guessed_letters, ['E']);
+
+        let mut game = new_game("ÉCOLE");
+        game.ignore_accents = true;
+        assert!(game.guess_word("ecole"));
+        assert!(game.won);
+    }
+
+    #[test]
+    fn test_guess_word_accents_count_unless_ignored() {
+        let mut game = new_game("CAFÉ");
+        assert!(!game.guess_word("cafe"));
+        assert!(game.has_guessed_word("CAFE"));
+        assert!(game.guess_word("café"));
+    }
+
+    #[test]
+    fn test_keyboard() {
+        let mut game = new_game("RUST");
+        assert_eq!(game.keyboard().len(), 26);
+        game.alphabet = "AEÉNÑ".chars().collect();
+        assert_eq!(game.keyboard(), ['A', 'E', 'É', 'N', 'Ñ']);
+        game.ignore_accents = true;
+        assert_eq!(game.keyboard(), ['A', 'E', 'N']);
+    }
+
+    #[test]
+    fn test_key_states() {
+        let mut game = new_game("CAFÉ");
+        game.alphabet = "ACEÉFX".chars().collect();
+        game.guess_letter('c');
+        game.guess_letter('X');
+        game.guess_letter('E');
+        assert_eq!(
+            game.key_states(),
+            [
+                ('A', KeyState::Unused),
+                ('C', KeyState::Correct),
+                ('E', KeyState::Wrong),
+                ('É', KeyState::Unused),
+                ('F', KeyState::Unused),
+                ('X', KeyState::Wrong),
+            ]
+        );
+
+        // With ignore_accents the E key stands for É too
+        game.ignore_accents = true;
+        assert_eq!(game.key_states()[2], ('E', KeyState::Correct));
+    }
+
+    #[test]
+    fn test_clue() {
+        let words = WordList::from_json(r#"[{"word": "owl", "clue": "It hoots"}]"#).unwrap().with_clue("It's an animal");
+        let game = GameState::new_random(&words, None, Difficulty::Medium, GameMode::Classic, RngHandle::seeded(1));
+        assert_eq!(game.clue.as_deref(), Some("It hoots"));
+        // Only the list's clue fits every word an evil game could end up with
+        let game = GameState::new_random(&words, None, Difficulty::Medium, GameMode::Evil, RngHandle::seeded(1));
+        assert_eq!(game.clue.as_deref(), Some("It's an animal"));
+        // Clues are off unless the settings turn them on
+        let mut game = game;
+        Settings::default().apply(&mut game);
+        assert_eq!(game.clue, None);
+    }
+
+    #[test]
+    fn test_digraphs() {
+        let mut game = new_game("CHICO");
+        let ch = digraphs::unit("CH").unwrap();
+        game.digraphs = vec![ch];
+        game.alphabet = "ACHIO".chars().collect();
+        assert_eq!(game.keyboard(), ['A', 'C', ch, 'H', 'I', 'O']);
+        assert_eq!(game.check_letter("ch"), Ok(ch));
+        // C only finds the C that isn't part of CH
+        game.guess_letter('C');
+        assert_eq!(game.get_display_word(), "_ _ C _");
+        game.guess_letter('H');
+        assert_eq!(game.wrong_guesses, 1);
+        game.guess_letter(ch);
+        assert_eq!(game.get_display_word(), "CH _ C _");
+        game.guess_letter('I');
+        game.guess_letter('O');
+        assert!(game.won);
+        assert_eq!(game.word, "CHICO");
+    }
+
+    #[test]
+    fn test_evil_game_dodges_guesses() {
+        let list = WordList::from_text("rust\nruby\njava\nperl\nlisp\ngo").unwrap();
+        let mut game = GameState::new_evil(&list, "RUST".to_string(), None, Difficulty::Medium);
+        assert_eq!(game.mode(), GameMode::Evil);
+        assert_eq!(game.candidates.as_ref().unwrap().len(), 5);
+
+        game.guess_letter('R');
+        assert_eq!(game.wrong_guesses, 1);
+        assert_eq!(game.get_display_word(), "_ _ _ _");
+        // JAVA and LISP are left, JAVA is shown until it's dodged too
+        assert!(!game.guess_word("java"));
+        assert_eq!(game.word, "LISP");
+        assert_eq!(game.wrong_guesses, 1 + WRONG_WORD_PENALTY);
+
+        // One word left, so it can be found
+        for letter in "LISP".chars() {
+            game.guess_letter(letter);
+        }
+        assert!(game.won);
+    }
+
+    #[test]
+    fn test_evil_hint_pins_the_letter() {
+        let list = WordList::from_text("rust\nruby\nrush").unwrap();
+        let mut game = GameState::new_evil(&list, "RUST".to_string(), None, Difficulty::Easy);
+        game.rng = RngHandle::seeded(1);
+        let letter = game.use_hint().unwrap();
+        assert!(game.word.contains(letter));
+        assert!(game.get_display_word().contains(letter));
+        assert_eq!(game.mode(), GameMode::Evil);
+        assert_eq!(new_game("RUST").mode(), GameMode::Classic);
+    }
+
+    #[test]
+    fn test_seeded_games_replay() {
+        let words = WordList::from_text("rust\ncargo\ntauri\nserde\ntokio\nclippy\nrustup\nferris\nborrow").unwrap();
+        let deal = |rng: RngHandle| -> Vec<String> {
+            (0..4)
+                .map(|_| GameState::new_random(&words, None, Difficulty::Medium, GameMode::Classic, rng.clone()).word)
+                .collect()
+        };
+        // One handle deals the same games in the same order for the same seed
+        assert_eq!(deal(RngHandle::seeded(42)), deal(RngHandle::seeded(42)));
+
+        let mut first = GameState::new_seeded(&words, None, Difficulty::Hard, GameMode::Classic, 7);
+        let mut second = GameState::new_seeded(&words, None, Difficulty::Hard, GameMode::Classic, 7);
+        assert_eq!(first.word, second.word);
+        assert_eq!(first.seed, Some(7));
+        // ... and the same hints
+        assert_eq!(first.use_hint(), second.use_hint());
+
+        let evil = GameState::new_seeded(&words, None, Difficulty::Hard, GameMode::Evil, 7);
+        assert_eq!((evil.word.as_str(), evil.mode()), (first.word.as_str(), GameMode::Evil));
+    }
+
+    #[test]
+    fn test_stages() {
+        let stages = |max_wrong_guesses| -> Vec<u32> {
+            let mut game = new_game("RUST");
+            game.max_wrong_guesses = max_wrong_guesses;
+            let mut stages = vec![game.stage()];
+            for _ in 0..max_wrong_guesses {
+                game.wrong_guesses += 1;
+                stages.push(game.stage());
+            }
+            stages
+        };
+        assert_eq!(stages(6), [0, 1, 2, 3, 4, 5, 6]);
+        assert_eq!(stages(4), [0, 1, 2, 3, 4]);
+        assert_eq!(stages(10), [0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10]);
+        assert_eq!(stages(1), [0, 1]);
+
+        // Running out of time draws the whole gallows too
+        let mut game = new_game("RUST");
+        game.start_timer(10);
+        game.deadline = Some(0);
+        game.check_timer();
+        assert_eq!(game.stage(), game.stages());
+    }
+
+    #[test]
+    fn test_no_guesses_after_game_over() {
+        let mut game = GameState::new("RUST".to_string(), None, Difficulty::Hard);
+        for letter in "ABCDR".chars() {
+            game.guess_letter(letter);
+        }
+        assert_eq!(game.guessed_letters.len(), 4);
+    }
+}
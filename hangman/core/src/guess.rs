@@ -0,0 +1,218 @@
+// ============================================================================
+// LETTER GUESSES
+// ============================================================================
+// Letter guesses arrive as typed (or clicked) text from the frontends, so they
+// are checked here before the game sees them. Each way a guess can be wrong
+// has its own error, so a frontend can say exactly what was wrong with it.
+//
+// RULES:
+// - Exactly one letter of any alphabet, surrounding whitespace is ignored
+//   (a letter typed with a combining accent counts as one letter), or one of
+//   the game's digraphs typed as its letters, e.g. "ch" (see digraphs.rs)
+// - Digits and punctuation are refused (they're never hidden in the word),
+//   unless the game's word list guesses them too, e.g. "3" or "+" in a game
+//   of a tech list (see charset.rs)
+// - A letter can only be guessed once per game
+//
+// SEVERAL AT ONCE:
+// guess_letters takes a batch (e.g. a pasted word's letters) and makes them
+// one after another, saying what happened to each: a hit, a miss, rejected
+// by the rules above (a letter repeated in the batch included), or skipped
+// because the game was over (or paused, or on the AI's turn) by then.
+
+use std::fmt;
+use unicode_normalization::UnicodeNormalization;
+use crate::charset::Charset;
+use crate::digraphs::{self, spell};
+use crate::game::{is_guessable, GameState};
+use crate::letters::to_upper;
+
+/// Reasons a letter guess is rejected
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GuessError {
+    /// Nothing but whitespace was entered
+    Empty,
+    /// More than one character (whole words go through guess_word)
+    MultipleCharacters,
+    /// A digit, e.g. '7'
+    Digit(char),
+    /// Punctuation, a symbol, or anything else that isn't a letter
+    NotALetter(char),
+    /// The letter (uppercased) was already guessed this game
+    AlreadyGuessed(char),
+}
+
+impl fmt::Display for GuessError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            GuessError::Empty => write!(f, "nothing was entered"),
+            GuessError::MultipleCharacters => write!(f, "only one letter can be guessed at a time"),
+            GuessError::Digit(c) => write!(f, "{:?} is a digit", c),
+            GuessError::NotALetter(c) => write!(f, "{:?} isn't a letter", c),
+            GuessError::AlreadyGuessed(c) => write!(f, "{} was already guessed", spell(*c)),
+        }
+    }
+}
+
+impl std::error::Error for GuessError {}
+
+/// Check a typed letter guess and return the letter, uppercased
+/// (doesn't know about the game, see GameState::check_letter for repeats)
+pub fn parse_letter(input: &str) -> Result<char, GuessError> {
+    parse_guess(input, &Charset::default())
+}
+
+/// Check a typed guess of a letter, or of a digit or symbol `charset` guesses,
+/// and return it, uppercased
+pub fn parse_guess(input: &str, charset: &Charset) -> Result<char, GuessError> {
+    let input: String = input.trim().nfc().collect();
+    let mut chars = input.chars();
+    let c = match (chars.next(), chars.next()) {
+        (None, _) => return Err(GuessError::Empty),
+        (Some(_), Some(_)) => return Err(GuessError::MultipleCharacters),
+        (Some(c), None) => c,
+    };
+
+    if charset.is_guessable(c) {
+        return Ok(to_upper(c));
+    }
+    if c.is_numeric() {
+        return Err(GuessError::Digit(c));
+    }
+    if !is_guessable(c) {
+        return Err(GuessError::NotALetter(c));
+    }
+    Ok(to_upper(c))
+}
+
+/// What one guess of a batch did
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LetterOutcome {
+    /// The letter is in the word
+    Hit(char),
+    /// The letter isn't in the word, a wrong guess (or a miss a shield took)
+    Miss(char),
+    Rejected(GuessError),
+    /// Not tried, the game was over (won, lost, or out of time), paused, or on the AI's turn before it
+    Skipped,
+}
+
+impl GameState {
+    /// Make each guess of `inputs` in turn, returns what each did
+    pub fn guess_letters<I, S>(&mut self, inputs: I) -> Vec<LetterOutcome>
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<str>,
+    {
+        inputs
+            .into_iter()
+            .map(|input| {
+                if self.check_timer() || self.game_over || self.is_paused() || self.is_ai_turn() {
+                    return LetterOutcome::Skipped;
+                }
+                match self.check_letter(input.as_ref()) {
+                    Ok(letter) if self.guess_letter(letter) => LetterOutcome::Hit(letter),
+                    Ok(letter) => LetterOutcome::Miss(letter),
+                    Err(err) => LetterOutcome::Rejected(err),
+                }
+            })
+            .collect()
+    }
+
+    /// Check a typed letter guess against this game, returns the letter to guess
+    /// (a digraph's unit for its letters), fails like parse_letter, or with
+    /// AlreadyGuessed for a repeat
+    pub fn check_letter(&self, input: &str) -> Result<char, GuessError> {
+        let letter = match digraphs::find(&self.digraphs, input) {
+            Some(unit) => unit,
+            None => parse_guess(input, &self.charset)?,
+        };
+        if self.is_guessed(letter) {
+            return Err(GuessError::AlreadyGuessed(letter));
+        }
+        Ok(letter)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::difficulty::Difficulty;
+
+    #[test]
+    fn test_accepts_one_letter() {
+        assert_eq!(parse_letter("r"), Ok('R'));
+        assert_eq!(parse_letter(" Q\n"), Ok('Q'));
+        assert_eq!(parse_letter("é"), Ok('É'));
+        assert_eq!(parse_letter("e\u{301}"), Ok('É'));
+        assert_eq!(parse_letter("ß"), Ok('ß'));
+        assert_eq!(parse_letter("ж"), Ok('Ж'));
+    }
+
+    #[test]
+    fn test_rejects_non_letters() {
+        assert_eq!(parse_letter(""), Err(GuessError::Empty));
+        assert_eq!(parse_letter("   "), Err(GuessError::Empty));
+        assert_eq!(parse_letter("ab"), Err(GuessError::MultipleCharacters));
+        assert_eq!(parse_letter("7"), Err(GuessError::Digit('7')));
+        assert_eq!(parse_letter("!"), Err(GuessError::NotALetter('!')));
+        assert_eq!(parse_letter("-"), Err(GuessError::NotALetter('-')));
+    }
+
+    #[test]
+    fn test_charset_digits_and_symbols() {
+        let charset = Charset::parse("digits +").unwrap();
+        assert_eq!(parse_guess(" 3 ", &charset), Ok('3'));
+        assert_eq!(parse_guess("+", &charset), Ok('+'));
+        assert_eq!(parse_guess("c", &charset), Ok('C'));
+        assert_eq!(parse_guess("#", &charset), Err(GuessError::NotALetter('#')));
+        assert_eq!(parse_guess("٣", &charset), Err(GuessError::Digit('٣')));
+
+        let mut game = GameState::new("C++".to_string(), None, Difficulty::Medium);
+        assert_eq!(game.check_letter("+"), Err(GuessError::NotALetter('+')));
+        game.charset = charset;
+        assert_eq!(game.check_letter("+"), Ok('+'));
+        game.guess_letter('+');
+        assert_eq!(game.check_letter("+"), Err(GuessError::AlreadyGuessed('+')));
+    }
+
+    #[test]
+    fn test_rejects_repeats() {
+        let mut game = GameState::new("RUST".to_string(), None, Difficulty::Medium);
+        assert_eq!(game.check_letter("r"), Ok('R'));
+        game.guess_letter('R');
+        assert_eq!(game.check_letter("r"), Err(GuessError::AlreadyGuessed('R')));
+        assert_eq!(game.check_letter("R"), Err(GuessError::AlreadyGuessed('R')));
+    }
+
+    #[test]
+    fn test_guess_letters() {
+        let mut game = GameState::new("RUST".to_string(), None, Difficulty::Medium);
+        let outcomes = game.guess_letters(["r", "x", "7", "R", "u", "s", "t", "e"]);
+        assert_eq!(
+            outcomes,
+            [
+                LetterOutcome::Hit('R'),
+                LetterOutcome::Miss('X'),
+                LetterOutcome::Rejected(GuessError::Digit('7')),
+                LetterOutcome::Rejected(GuessError::AlreadyGuessed('R')),
+                LetterOutcome::Hit('U'),
+                LetterOutcome::Hit('S'),
+                LetterOutcome::Hit('T'),
+                LetterOutcome::Skipped,
+            ]
+        );
+        assert!(game.won);
+        assert_eq!(game.wrong_guesses, 1);
+
+        // A miss a shield took is still a miss
+        let mut game = GameState::new("RUST".to_string(), None, Difficulty::Medium);
+        game.activate_powerup(crate::powerups::PowerUp::Shield).unwrap();
+        assert_eq!(game.guess_letters(["z", "r"]), [LetterOutcome::Miss('Z'), LetterOutcome::Hit('R')]);
+        assert_eq!(game.wrong_guesses, 0);
+        // Nothing is tried while the game is paused
+        game.start_timer(60);
+        game.pause().unwrap();
+        assert_eq!(game.guess_letters(["u"]), [LetterOutcome::Skipped]);
+    }
+}
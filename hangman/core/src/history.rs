@@ -0,0 +1,314 @@
+// ============================================================================
+// MOVE HISTORY AND UNDO
+// ============================================================================
+// Every move that changes a game (a new letter, a word guess that isn't a
+// repeat, a hint) is kept in order in `history`: what it was, whether it was
+// right, when it was made (Unix milliseconds), and what the game looked like
+// before it. The frontend gets the first three to draw a timeline of the game.
+//
+// undo() takes the last move back: its letters and word guess are removed,
+// the wrong guesses (and the hint) it cost are given back, and a game it
+// ended is opened again.
+//
+// EVIL GAMES:
+// An evil game changes its word and candidates as it dodges guesses, so a
+// move in one keeps both from before the move and undo puts them back.
+//
+// WHAT CAN'T BE UNDONE:
+// Running out of time isn't a move: a timed game that was lost that way stays
+// lost (undo fails with UndoError::TimedOut), and undo doesn't stop the clock.
+// Helps bought with points aren't moves either, and the moves before the
+// last one bought (`undo_floor`) can't be taken back (see shop.rs).
+//
+// STATISTICS:
+// `undos` counts the moves taken back. Like games the AI helped with, a game
+// with an undo isn't the player's own and isn't counted when it ends (if it
+// had already ended and been counted before the undo, that result stays).
+//
+// SHIELDS:
+// A miss a shield took (see powerups.rs) is `shielded`, undoing it raises
+// the shield again. It costs no wrong guess but is still a miss: the move
+// isn't a hit, and the game isn't flawless().
+
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use crate::evil::Candidates;
+use crate::game::{now_ms, GameState};
+
+/// What a move was
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+pub enum Action {
+    /// A letter guess (uppercase)
+    Letter(char),
+    /// A guess at the whole word, normalized like the word
+    Word(String),
+    /// The letter a hint revealed
+    Hint(char),
+}
+
+/// A move and the parts of the game it changed, as they were before it
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+pub struct Move {
+    pub action: Action,
+    // Whether the letter is in the word, or the word guess was right (hints always are)
+    #[serde(default)]
+    pub hit: bool,
+    // When the move was made, in Unix milliseconds
+    #[serde(default)]
+    pub at_ms: u64,
+    wrong_guesses: u32,
+    hints_used: u32,
+    // How long guessed_letters and guessed_words were
+    letters: usize,
+    words: usize,
+    // An evil game's word and candidates (None for classic games)
+    evil: Option<(String, Candidates)>,
+    // Whether a shield took the miss
+    #[serde(default)]
+    shielded: bool,
+}
+
+/// Reasons the last move can't be taken back
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum UndoError {
+    /// No move has been made yet (or every move was already undone, or made before a bought help)
+    NothingToUndo,
+    /// The game was lost by running out of time
+    TimedOut,
+    /// The player gave up
+    GaveUp,
+    /// The game is a race against the AI (see race.rs)
+    Race,
+}
+
+impl fmt::Display for UndoError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            UndoError::NothingToUndo => write!(f, "there's no move to undo"),
+            UndoError::TimedOut => write!(f, "the time ran out, that can't be undone"),
+            UndoError::GaveUp => write!(f, "the game was given up, that can't be undone"),
+            UndoError::Race => write!(f, "moves can't be taken back in a race"),
+        }
+    }
+}
+
+impl std::error::Error for UndoError {}
+
+impl Move {
+    /// Forget the evil word and candidates kept for undo (see player_view)
+    pub(crate) fn hide_word(&mut self) {
+        self.evil = None;
+    }
+
+    /// How many letters had been guessed before the move
+    pub(crate) fn letters(&self) -> usize {
+        self.letters
+    }
+}
+
+impl GameState {
+    /// Start keeping `action` in the history, call it before the move changes anything
+    /// and finish_move() once it's known whether it was right (until then it's a miss)
+    pub(crate) fn begin_move(&mut self, action: Action) {
+        let evil = self.candidates.clone().map(|candidates| (self.word.clone(), candidates));
+        self.history.push(Move {
+            action,
+            hit: false,
+            at_ms: now_ms(),
+            wrong_guesses: self.wrong_guesses,
+            hints_used: self.hints_used,
+            letters: self.guessed_letters.len(),
+            words: self.guessed_words.len(),
+            evil,
+            shielded: false,
+        });
+        self.pass_turn();
+    }
+
+    /// Record whether the move begun last was right
+    pub(crate) fn finish_move(&mut self, hit: bool) {
+        if let Some(last) = self.history.last_mut() {
+            last.hit = hit;
+        }
+    }
+
+    /// Record that a shield took the miss begun last
+    pub(crate) fn shield_move(&mut self) {
+        if let Some(last) = self.history.last_mut() {
+            last.shielded = true;
+        }
+    }
+
+    /// Whether no guess has missed so far, counting the misses a shield took
+    pub fn flawless(&self) -> bool {
+        self.wrong_guesses == 0 && !self.history.iter().any(|past| past.shielded)
+    }
+
+    /// Whether there's a move undo() would take back
+    pub fn can_undo(&self) -> bool {
+        self.history.len() > self.undo_floor && !self.timed_out && !self.gave_up && self.race.is_none()
+    }
+
+    /// Take back the last move, reopening the game if that move ended it
+    /// Returns what the move was
+    pub fn undo(&mut self) -> Result<Action, UndoError> {
+        if self.check_timer() || self.timed_out {
+            return Err(UndoError::TimedOut);
+        }
+        if self.gave_up {
+            return Err(UndoError::GaveUp);
+        }
+        if self.race.is_some() {
+            return Err(UndoError::Race);
+        }
+        if self.history.len() <= self.undo_floor {
+            return Err(UndoError::NothingToUndo);
+        }
+        let last = self.history.pop().ok_or(UndoError::NothingToUndo)?;
+        self.guessed_letters.truncate(last.letters);
+        self.guessed_words.truncate(last.words);
+        self.wrong_guesses = last.wrong_guesses;
+        self.hints_used = last.hints_used;
+        self.shield |= last.shielded;
+        if let Some((word, candidates)) = last.evil {
+            self.word = word;
+            self.candidates = Some(candidates);
+        }
+
+        if self.game_over {
+            if let Some(best_of) = &mut self.best_of {
+                best_of.reopen();
+            }
+            if let Some(run) = &mut self.run {
+                run.reopen();
+            }
+            if let Some(duel) = &mut self.duel {
+                duel.reopen();
+            }
+        }
+        self.game_over = false;
+        self.won = false;
+        self.score = None;
+        self.rating = None;
+        self.time_left_ms = None;
+        self.undos += 1;
+        Ok(last.action)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::difficulty::Difficulty;
+    use crate::words::WordList;
+
+    fn new_game(word: &str) -> GameState {
+        GameState::new(word.to_string(), None, Difficulty::Medium)
+    }
+
+    #[test]
+    fn test_moves_are_kept_in_order() {
+        let mut game = new_game("RUST");
+        game.guess_letter('R');
+        game.guess_letter('r'); // Repeats aren't moves
+        game.guess_word("RUBY");
+        game.guess_word("ruby");
+        game.use_hint().unwrap();
+        let actions: Vec<&Action> = game.history.iter().map(|entry| &entry.action).collect();
+        assert!(matches!(actions[..], [Action::Letter('R'), Action::Word(_), Action::Hint(_)]));
+        assert_eq!(actions[1], &Action::Word("RUBY".to_string()));
+
+        let hits: Vec<bool> = game.history.iter().map(|entry| entry.hit).collect();
+        assert_eq!(hits, [true, false, true]);
+        assert!(game.history.windows(2).all(|pair| pair[0].at_ms <= pair[1].at_ms));
+        assert!(game.history[0].at_ms > 0);
+    }
+
+    #[test]
+    fn test_winning_word_guess_is_a_hit() {
+        let mut game = new_game("RUST");
+        game.guess_letter('X');
+        assert!(game.guess_word("rust"));
+        assert!(!game.history[0].hit);
+        assert!(game.history[1].hit);
+    }
+
+    #[test]
+    fn test_undo_letters_and_words() {
+        let mut game = new_game("RUST");
+        game.guess_letter('R');
+        game.guess_letter('X');
+        game.guess_word("RUBY");
+        assert_eq!(game.wrong_guesses, 3);
+
+        assert_eq!(game.undo(), Ok(Action::Word("RUBY".to_string())));
+        assert_eq!(game.wrong_guesses, 1);
+        assert!(game.guessed_words.is_empty());
+        assert_eq!(game.undo(), Ok(Action::Letter('X')));
+        assert_eq!(game.guessed_letters, ['R']);
+        assert_eq!(game.wrong_guesses, 0);
+        assert_eq!(game.undo(), Ok(Action::Letter('R')));
+        assert_eq!(game.undo(), Err(UndoError::NothingToUndo));
+        assert!(!game.can_undo());
+        assert_eq!(game.undos, 3);
+    }
+
+    #[test]
+    fn test_undo_gives_hints_back() {
+        let mut game = new_game("RUST");
+        let letter = game.use_hint().unwrap();
+        assert_eq!(game.undo(), Ok(Action::Hint(letter)));
+        assert_eq!((game.hints_used, game.wrong_guesses), (0, 0));
+        assert!(game.guessed_letters.is_empty());
+    }
+
+    #[test]
+    fn test_undo_reopens_a_finished_game() {
+        let mut game = new_game("RUST");
+        for letter in "RUS".chars() {
+            game.guess_letter(letter);
+        }
+        assert!(game.guess_word("RUST"));
+        assert!(game.score.is_some());
+
+        game.undo().unwrap();
+        assert!(!game.game_over && !game.won);
+        assert_eq!(game.score, None);
+        assert_eq!(game.guessed_letters, ['R', 'U', 'S']);
+        game.guess_letter('T');
+        assert!(game.won);
+
+        let mut lost = GameState::new("RUST".to_string(), None, Difficulty::Hard);
+        for letter in "ABCD".chars() {
+            lost.guess_letter(letter);
+        }
+        assert!(lost.game_over);
+        lost.undo().unwrap();
+        assert!(!lost.game_over);
+        assert_eq!(lost.wrong_guesses, 3);
+    }
+
+    #[test]
+    fn test_undo_restores_evil_word() {
+        let words = WordList::from_text("rust\nruby\njava\nperl").unwrap();
+        let mut game = GameState::new_evil(&words, "RUST".to_string(), None, Difficulty::Medium);
+        let (word, candidates) = (game.word.clone(), game.candidates.clone());
+        game.guess_letter('R');
+        game.guess_letter('A');
+        game.undo().unwrap();
+        game.undo().unwrap();
+        assert_eq!(game.word, word);
+        assert_eq!(game.candidates, candidates);
+    }
+
+    #[test]
+    fn test_time_running_out_cant_be_undone() {
+        let mut game = new_game("RUST");
+        game.guess_letter('R');
+        game.deadline = Some(1);
+        assert_eq!(game.undo(), Err(UndoError::TimedOut));
+        assert!(game.timed_out && game.game_over);
+        assert!(!game.can_undo());
+    }
+}
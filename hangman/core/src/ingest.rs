@@ -0,0 +1,201 @@
+// ============================================================================
+// WORD CLEANUP
+// ============================================================================
+// Every word the game can pick goes through clean() first, wherever it comes
+// from: the bundled and built-in lists, user word lists, category files,
+// imported words (see custom_words.rs), and words fetched online (see
+// online_words.rs). So a word in a pool is always one the mask, the keyboard,
+// and the win check can handle.
+//
+// STEPS (for each entry, in order):
+// 1. Trim and uppercase, repeated spaces become one (letters::normalize_word)
+// 2. Strip anything that isn't a letter or a space ("don't" -> "DONT"), or
+//    one of the list's guessable digits and symbols (see charset.rs)
+// 3. Reject it if it's out of range: fewer than MIN_WORD_LETTERS letters
+//    (guessable digits and symbols count as letters), or
+//    more than MAX_WORD_LEN characters (the same limits as a typed secret,
+//    see secret.rs)
+// 4. Drop it if an earlier entry cleaned up to the same word
+//
+// REPORT:
+// clean() says what it did in a CleanReport: how many words were kept, the
+// ones that had characters stripped, the duplicates, and the rejected
+// entries with their reason. WordList keeps the report of the entries it was
+// made from, load() logs it for a file that needed cleaning, and the desktop
+// app sends it to the frontend (get_word_list_report).
+
+use std::collections::HashSet;
+use std::fmt;
+use crate::charset::Charset;
+use crate::letters::normalize_word;
+use crate::secret::{MAX_SECRET_LEN, MIN_SECRET_LETTERS};
+
+/// Fewest letters a word can have
+pub const MIN_WORD_LETTERS: usize = MIN_SECRET_LETTERS;
+
+/// Longest word, spaces included
+pub const MAX_WORD_LEN: usize = MAX_SECRET_LEN;
+
+/// Reasons an entry isn't kept
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Rejection {
+    /// Nothing was left once invalid characters were stripped
+    NoLetters,
+    /// Fewer than MIN_WORD_LETTERS letters
+    TooShort,
+    /// More than MAX_WORD_LEN characters
+    TooLong,
+}
+
+impl fmt::Display for Rejection {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Rejection::NoLetters => write!(f, "there are no letters in it"),
+            Rejection::TooShort => write!(f, "a word needs at least {} letters", MIN_WORD_LETTERS),
+            Rejection::TooLong => write!(f, "a word can be at most {} characters", MAX_WORD_LEN),
+        }
+    }
+}
+
+impl std::error::Error for Rejection {}
+
+/// What clean() did with a list of entries
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct CleanReport {
+    // Words kept, duplicates not counted
+    pub kept: usize,
+    // Entries that had characters stripped: as written, and the word kept
+    pub stripped: Vec<(String, String)>,
+    // Words an earlier entry already was (cleaned up)
+    pub duplicates: Vec<String>,
+    // As written, with the reason
+    pub rejected: Vec<(String, Rejection)>,
+}
+
+impl CleanReport {
+    /// Whether every entry was kept as it was (apart from case and spacing)
+    pub fn is_clean(&self) -> bool {
+        self.stripped.is_empty() && self.duplicates.is_empty() && self.rejected.is_empty()
+    }
+}
+
+impl fmt::Display for CleanReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} words kept, {} cleaned up, {} duplicates dropped, {} rejected",
+            self.kept,
+            self.stripped.len(),
+            self.duplicates.len(),
+            self.rejected.len()
+        )
+    }
+}
+
+/// Clean up one entry (steps 1 to 3)
+pub fn clean_word(entry: &str) -> Result<String, Rejection> {
+    clean_word_with(entry, &Charset::default())
+}
+
+/// Clean up one entry of a list that guesses `charset`
+pub fn clean_word_with(entry: &str, charset: &Charset) -> Result<String, Rejection> {
+    let kept: String = normalize_word(entry)
+        .chars()
+        .filter(|&c| c == ' ' || charset.is_guessable(c))
+        .collect();
+    // Stripping can leave spaces next to each other ("ROCK & ROLL")
+    let word = normalize_word(&kept);
+
+    if word.is_empty() {
+        return Err(Rejection::NoLetters);
+    }
+    if word.chars().count() > MAX_WORD_LEN {
+        return Err(Rejection::TooLong);
+    }
+    if word.chars().filter(|&c| charset.is_guessable(c)).count() < MIN_WORD_LETTERS {
+        return Err(Rejection::TooShort);
+    }
+    Ok(word)
+}
+
+/// Clean up every entry, returning the words kept in order and the report
+/// Blank entries are skipped without being reported
+pub fn clean<I, S>(entries: I) -> (Vec<String>, CleanReport)
+where
+    I: IntoIterator<Item = S>,
+    S: AsRef<str>,
+{
+    clean_with(entries, &Charset::default())
+}
+
+/// Clean up every entry of a list that guesses `charset`, like clean()
+pub fn clean_with<I, S>(entries: I, charset: &Charset) -> (Vec<String>, CleanReport)
+where
+    I: IntoIterator<Item = S>,
+    S: AsRef<str>,
+{
+    let mut words = Vec::new();
+    let mut seen: HashSet<String> = HashSet::new();
+    let mut report = CleanReport::default();
+    for entry in entries {
+        let entry = entry.as_ref();
+        if entry.trim().is_empty() {
+            continue;
+        }
+        match clean_word_with(entry, charset) {
+            Ok(word) if !seen.insert(word.clone()) => report.duplicates.push(word),
+            Ok(word) => {
+                if word != normalize_word(entry) {
+                    report.stripped.push((entry.to_string(), word.clone()));
+                }
+                words.push(word);
+            }
+            Err(reason) => report.rejected.push((entry.to_string(), reason)),
+        }
+    }
+    report.kept = words.len();
+    (words, report)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_clean_word() {
+        assert_eq!(clean_word("  rust  ").unwrap(), "RUST");
+        assert_eq!(clean_word("don't").unwrap(), "DONT");
+        assert_eq!(clean_word("rock & roll").unwrap(), "ROCK ROLL");
+        assert_eq!(clean_word("año").unwrap(), "AÑO");
+        assert_eq!(clean_word("42!"), Err(Rejection::NoLetters));
+        assert_eq!(clean_word("x."), Err(Rejection::TooShort));
+        assert_eq!(clean_word(&"a".repeat(MAX_WORD_LEN + 1)), Err(Rejection::TooLong));
+        assert!(clean_word(&"a".repeat(MAX_WORD_LEN)).is_ok());
+    }
+
+    #[test]
+    fn test_guessable_digits_and_symbols_are_kept() {
+        let charset = Charset::parse("digits + #").unwrap();
+        assert_eq!(clean_word_with("c++", &charset).unwrap(), "C++");
+        assert_eq!(clean_word_with("Web3!", &charset).unwrap(), "WEB3");
+        // They count as letters
+        assert_eq!(clean_word_with("c#", &charset).unwrap(), "C#");
+        assert_eq!(clean_word("c#"), Err(Rejection::TooShort));
+        assert_eq!(clean_word_with("42", &charset).unwrap(), "42");
+    }
+
+    #[test]
+    fn test_clean_reports_what_it_did() {
+        let (words, report) = clean(["rust", "", "Ferris!", "RUST", " ferris", "7", "go"]);
+        assert_eq!(words, ["RUST", "FERRIS", "GO"]);
+        assert_eq!(report.kept, 3);
+        assert_eq!(report.stripped, [("Ferris!".to_string(), "FERRIS".to_string())]);
+        assert_eq!(report.duplicates, ["RUST", "FERRIS"]);
+        assert_eq!(report.rejected, [("7".to_string(), Rejection::NoLetters)]);
+        assert!(!report.is_clean());
+        assert_eq!(report.to_string(), "3 words kept, 1 cleaned up, 2 duplicates dropped, 1 rejected");
+
+        // Case and spacing alone aren't worth reporting
+        assert!(clean(["  ice   cream ", "Rust"]).1.is_clean());
+    }
+}
@@ -0,0 +1,87 @@
+// ============================================================================
+// KIDS MODE
+// ============================================================================
+// A preset for young players, switched on with the kids_mode setting (see
+// settings.rs). A kids game:
+// - has its word from a curated list of short, everyday words
+//   ("resources/words.kids.txt", compiled in), rather than the main list
+//   (a category game keeps its category's words)
+// - allows at least KIDS_MAX_WRONG_GUESSES wrong guesses, more if the
+//   max_wrong_guesses setting says so
+// - starts with the word's first letter shown, for free
+// - has no clock, whatever time_limit says
+// The game carries `kids` (GameView's kids_mode), so the frontends can
+// draw it for kids: bigger keys and friendlier messages. Otherwise it's a
+// classic game, counted in the statistics like any other.
+
+use crate::game::GameState;
+use crate::words::WordList;
+
+/// Wrong guesses a kids game allows at least
+pub const KIDS_MAX_WRONG_GUESSES: u32 = 10;
+
+/// The curated word list of kids games
+const KIDS_WORDS: &str = include_str!("../resources/words.kids.txt");
+
+impl WordList {
+    /// The curated words of kids games
+    pub fn kids() -> Self {
+        WordList::from_text(KIDS_WORDS).expect("built-in kids word list is not empty")
+    }
+}
+
+impl GameState {
+    /// Make a freshly started game a kids game: a bigger budget, the first letter
+    /// shown, and no clock (see kids.rs)
+    pub fn start_kids(&mut self) {
+        self.kids = true;
+        self.max_wrong_guesses = self.max_wrong_guesses.max(KIDS_MAX_WRONG_GUESSES);
+        self.time_limit = None;
+        self.deadline = None;
+        let Some(first) = self.tokens().into_iter().find(|&c| self.is_guessable(c)) else {
+            return;
+        };
+        if let Some(candidates) = &mut self.candidates {
+            self.word = candidates.reveal(first, self.ignore_accents).to_string();
+        }
+        self.guessed_letters.push(first);
+        self.undo_floor = self.history.len();
+        if self.check_win() {
+            self.end(true);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::difficulty::Difficulty;
+
+    #[test]
+    fn test_kids_games() {
+        let mut game = GameState::new("BANANA".to_string(), None, Difficulty::Hard);
+        game.start_timer(60);
+        game.start_kids();
+        assert!(game.kids);
+        assert_eq!(game.max_wrong_guesses, KIDS_MAX_WRONG_GUESSES);
+        assert_eq!((game.time_limit, game.deadline), (None, None));
+        assert_eq!(game.guessed_letters, ['B']);
+        assert_eq!(game.mask(), "B_____");
+        // Nothing to undo, the first letter isn't a move
+        assert!(game.undo().is_err());
+
+        // A bigger budget from the settings is kept
+        let mut game = GameState::new("CAT".to_string(), None, Difficulty::Easy);
+        game.max_wrong_guesses = 15;
+        game.start_kids();
+        assert_eq!(game.max_wrong_guesses, 15);
+    }
+
+    #[test]
+    fn test_kids_words() {
+        let words = WordList::kids();
+        assert!(words.words().len() >= 50);
+        assert!(words.words().iter().all(|word| word.len() <= 6));
+        assert!(words.report().is_clean());
+    }
+}
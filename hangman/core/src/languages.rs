@@ -0,0 +1,165 @@
+// ============================================================================
+// LANGUAGES
+// ============================================================================
+// The languages the game ships a word list for, keyed by the code used in the
+// `language` setting ("es", "fr", ...). English is the main list (words.txt),
+// the others are bundled next to it as "words.<code>.txt" and compiled in too.
+//
+// WORD LISTS:
+// With a language set, WordList::load_language() looks for the player's own
+// list first ("words.<code>.json" / "words.<code>.txt" in the app data
+// directory), then the bundled one, then the compiled-in copy (see words.rs).
+// A code without a bundled list (e.g. "pt-br") can still be used with a list
+// of the player's, and falls back to the main list otherwise.
+//
+// KEYBOARDS:
+// Each language has its alphabet, the keys a game offers: A-Z for English,
+// with the accented vowels and Ñ for Spanish, ÄÖÜß for German, Cyrillic for
+// Russian. Letters a list uses that aren't in it (an "É" in an imported word)
+// get a key as well, so every letter can still be guessed. With ignore_accents the keyboard drops
+// the accented keys a base letter already covers (see GameState::keyboard).
+
+use crate::game::{is_guessable, DEFAULT_ALPHABET};
+use crate::letters::fold;
+use crate::words::WordList;
+
+/// Language of the main word list (words.txt)
+pub const DEFAULT_LANGUAGE: &str = "en";
+
+/// A language with a bundled word list
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Language {
+    // As in the `language` setting, e.g. "es"
+    pub code: &'static str,
+    // In the language itself, e.g. "Español"
+    pub name: &'static str,
+    // Keys offered, in alphabetical order
+    pub alphabet: &'static str,
+    // Name of the bundled list, e.g. "words.es.txt"
+    pub file: &'static str,
+    // Compiled-in copy of the bundled list
+    words: &'static str,
+}
+
+/// Every language with a bundled word list, the main one first
+pub const LANGUAGES: [Language; 5] = [
+    Language {
+        code: DEFAULT_LANGUAGE,
+        name: "English",
+        alphabet: DEFAULT_ALPHABET,
+        file: "words.txt",
+        words: include_str!("../resources/words.txt"),
+    },
+    Language {
+        code: "es",
+        name: "Español",
+        alphabet: "AÁBCDEÉFGHIÍJKLMNÑOÓPQRSTUÚÜVWXYZ",
+        file: "words.es.txt",
+        words: include_str!("../resources/words.es.txt"),
+    },
+    Language {
+        code: "fr",
+        name: "Français",
+        alphabet: "AÀÂÆBCÇDEÉÈÊËFGHIÎÏJKLMNOÔŒPQRSTUÙÛÜVWXYŸZ",
+        file: "words.fr.txt",
+        words: include_str!("../resources/words.fr.txt"),
+    },
+    Language {
+        code: "de",
+        name: "Deutsch",
+        alphabet: "AÄBCDEFGHIJKLMNOÖPQRSTUÜVWXYZß",
+        file: "words.de.txt",
+        words: include_str!("../resources/words.de.txt"),
+    },
+    Language {
+        code: "ru",
+        name: "Русский",
+        alphabet: "АБВГДЕЁЖЗИЙКЛМНОПРСТУФХЦЧШЩЪЫЬЭЮЯ",
+        file: "words.ru.txt",
+        words: include_str!("../resources/words.ru.txt"),
+    },
+];
+
+impl Language {
+    /// The bundled language for `code` (ignoring case), a regional code like
+    /// "es-mx" falls back to "es"
+    pub fn find(code: &str) -> Option<&'static Language> {
+        let code = code.trim().to_ascii_lowercase();
+        let primary = code.split('-').next().unwrap_or_default();
+        LANGUAGES
+            .iter()
+            .find(|language| language.code == code)
+            .or_else(|| LANGUAGES.iter().find(|language| language.code == primary))
+    }
+
+    /// Whether this is the language of the main word list
+    pub fn is_default(&self) -> bool {
+        self.code == DEFAULT_LANGUAGE
+    }
+
+    /// The compiled-in copy of the language's word list
+    pub fn builtin_words(&self) -> WordList {
+        WordList::from_text(self.words).expect("built-in word lists are not empty")
+    }
+}
+
+impl WordList {
+    /// The keys for a game picked from this list: `language`'s alphabet in
+    /// its own order, and any other letter the list uses after its base
+    /// letter (or at the end). Without a bundled language it's the letters
+    /// of the list (see alphabet())
+    pub fn alphabet_for(&self, language: Option<&str>) -> Vec<char> {
+        let Some(language) = language.and_then(Language::find) else {
+            return self.alphabet();
+        };
+        let mut letters: Vec<char> = language.alphabet.chars().collect();
+        for c in self.words().iter().flat_map(|w| w.chars()).filter(|&c| is_guessable(c)) {
+            if letters.contains(&c) {
+                continue;
+            }
+            match letters.iter().rposition(|&key| fold(key, true) == fold(c, true)) {
+                Some(base) => letters.insert(base + 1, c),
+                None => letters.push(c),
+            }
+        }
+        letters
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_find() {
+        assert_eq!(Language::find("es").unwrap().name, "Español");
+        assert_eq!(Language::find(" ES-mx ").unwrap().code, "es");
+        assert!(Language::find("pt-br").is_none());
+        assert!(Language::find("en").unwrap().is_default());
+    }
+
+    #[test]
+    fn test_builtin_lists() {
+        for language in &LANGUAGES {
+            let words = language.builtin_words();
+            assert!(words.report().is_clean(), "{}: {}", language.code, words.report());
+            // Every letter of the list has a key
+            let keys: Vec<char> = language.alphabet.chars().collect();
+            for letter in words.words().iter().flat_map(|w| w.chars()).filter(|&c| is_guessable(c)) {
+                assert!(keys.contains(&letter), "{} has no {} key", language.code, letter);
+            }
+            assert_eq!(words.alphabet_for(Some(language.code)), keys, "{}", language.code);
+        }
+    }
+
+    #[test]
+    fn test_alphabet_for() {
+        let words = WordList::from_text("café\nrust").unwrap();
+        // English keys, and É for the letter that isn't one of them
+        let alphabet = words.alphabet_for(Some("en"));
+        assert_eq!(alphabet.len(), 27);
+        assert_eq!(&alphabet[4..6], ['E', 'É']);
+        assert!(words.alphabet_for(Some("es")).contains(&'Ñ'));
+        assert_eq!(words.alphabet_for(Some("pt-br")), words.alphabet());
+    }
+}
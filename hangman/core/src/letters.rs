@@ -0,0 +1,134 @@
+// ============================================================================
+// LETTERS IN ANY ALPHABET
+// ============================================================================
+// Word lists can be in any language (Spanish, French, German, Russian, ...),
+// so letters are compared the Unicode way instead of with ASCII tricks.
+//
+// NORMALIZATION:
+// Text is put in NFC form first, so an "é" typed as "e" plus a combining
+// accent is the same single letter as a precomposed "é".
+//
+// CASE:
+// Words and guesses are stored uppercase, one character at a time. A letter
+// whose uppercase is more than one character keeps its own form, so "ß" stays
+// "ß" (not "SS") and a word never changes length.
+//
+// MATCHING:
+// Letters match when their case folds are equal: "é" matches "É", "ß" matches
+// "ẞ", and "ς" (final sigma) matches "Σ". With `ignore_accents` (a setting)
+// letters are also compared without their diacritics, so guessing "E" reveals
+// "É", "È", and "Ê" too, and "N" reveals "Ñ". Letters that aren't a base letter
+// plus accents ("Ø", "Æ", "ß") are always letters of their own.
+
+use unicode_normalization::UnicodeNormalization;
+
+/// Uppercase of a single letter, or the letter itself if that would take
+/// more than one character (e.g. 'ß')
+pub fn to_upper(c: char) -> char {
+    single(c.to_uppercase()).unwrap_or(c)
+}
+
+/// Lowercase of a single letter, or the letter itself if that would take
+/// more than one character (e.g. 'İ')
+pub fn to_lower(c: char) -> char {
+    single(c.to_lowercase()).unwrap_or(c)
+}
+
+/// Clean up a word, phrase, or guess the way words are stored:
+/// NFC form, runs of whitespace collapsed to one space, uppercased
+pub fn normalize_word(s: &str) -> String {
+    let s: String = s.nfc().collect();
+    upper_str(&s.split_whitespace().collect::<Vec<&str>>().join(" "))
+}
+
+/// Uppercase a word or phrase letter by letter (see to_upper)
+pub fn upper_str(s: &str) -> String {
+    s.chars().map(to_upper).collect()
+}
+
+/// Lowercase a word or phrase letter by letter (see to_lower)
+pub fn lower_str(s: &str) -> String {
+    s.chars().map(to_lower).collect()
+}
+
+/// The form letters are compared in: lowercase, final sigma as sigma,
+/// and with `ignore_accents` the base letter without its diacritics
+pub fn fold(c: char, ignore_accents: bool) -> char {
+    let folded = match to_lower(c) {
+        'ς' => 'σ',
+        other => other,
+    };
+    if ignore_accents {
+        strip_accents(folded)
+    } else {
+        folded
+    }
+}
+
+/// Whether two letters count as the same guess
+pub fn same_letter(a: char, b: char, ignore_accents: bool) -> bool {
+    fold(a, ignore_accents) == fold(b, ignore_accents)
+}
+
+/// Fold a whole word or phrase (see fold), for comparing word guesses
+pub fn fold_str(s: &str, ignore_accents: bool) -> String {
+    s.chars().map(|c| fold(c, ignore_accents)).collect()
+}
+
+/// The base letter of an accented letter ('é' -> 'e', 'ñ' -> 'n'),
+/// other letters unchanged
+fn strip_accents(c: char) -> char {
+    match c.nfd().next() {
+        Some(base) if base.is_alphabetic() => base,
+        _ => c,
+    }
+}
+
+/// The only character of a case mapping, None if it has several
+fn single(mut chars: impl Iterator<Item = char>) -> Option<char> {
+    match (chars.next(), chars.next()) {
+        (Some(c), None) => Some(c),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_case_keeps_length() {
+        assert_eq!(upper_str("straße"), "STRAßE");
+        assert_eq!(upper_str("año"), "AÑO");
+        assert_eq!(upper_str("привет"), "ПРИВЕТ");
+        assert_eq!(lower_str("ÉTÉ"), "été");
+        assert_eq!(to_upper('ß'), 'ß');
+    }
+
+    #[test]
+    fn test_normalize_word() {
+        assert_eq!(normalize_word("  cafe\u{301}   au\tlait "), "CAFÉ AU LAIT");
+        assert_eq!(normalize_word("café").chars().count(), 4);
+    }
+
+    #[test]
+    fn test_same_letter() {
+        assert!(same_letter('é', 'É', false));
+        assert!(same_letter('ß', 'ẞ', false));
+        assert!(same_letter('ς', 'Σ', false));
+        assert!(same_letter('д', 'Д', false));
+        assert!(!same_letter('E', 'É', false));
+        assert!(!same_letter('N', 'Ñ', false));
+    }
+
+    #[test]
+    fn test_ignore_accents() {
+        assert!(same_letter('E', 'É', true));
+        assert!(same_letter('e', 'Ê', true));
+        assert!(same_letter('N', 'ñ', true));
+        assert!(same_letter('U', 'Ü', true));
+        assert!(!same_letter('O', 'Ø', true));
+        assert!(!same_letter('S', 'ß', true));
+        assert_eq!(fold_str("Über", true), "uber");
+    }
+}
@@ -0,0 +1,143 @@
+//! Hangman game logic, shared by the Tauri desktop app and the terminal CLI
+//!
+//! Nothing in here depends on Tauri, so the rules can be tested and reused
+//! without a frontend.
+
+// Module declarations
+pub mod achievements;
+pub mod ai;
+pub mod arcade;
+pub mod audit;
+pub mod blocklist;
+pub mod campaign;
+pub mod categories;
+pub mod charset;
+pub mod crossword;
+pub mod custom_words;
+pub mod daily;
+pub mod definitions;
+pub mod difficulty;
+pub mod digraphs;
+pub mod duel;
+pub mod elo;
+pub mod events;
+pub mod evil;
+pub mod gallows;
+pub mod game;
+pub mod guess;
+pub mod history;
+pub mod ingest;
+pub mod kids;
+pub mod languages;
+pub mod letters;
+pub mod matches;
+pub mod metrics;
+pub mod multiplayer;
+pub mod online_words;
+pub mod pattern;
+pub mod positional;
+pub mod powerups;
+pub mod profiles;
+pub mod providers;
+pub mod race;
+pub mod replay;
+pub mod reveal;
+pub mod rating;
+pub mod saved;
+pub mod schemas;
+pub mod score;
+pub mod secret;
+pub mod sessions;
+pub mod settings;
+pub mod share;
+pub mod shop;
+pub mod stats;
+pub mod suggest;
+pub mod telemetry;
+pub mod trie;
+pub mod versus;
+pub mod wire;
+pub mod wordle;
+pub mod words;
+
+// Game results on a proof-of-work-sim chain
+#[cfg(feature = "onchain")]
+pub mod onchain;
+
+// Games, profiles, and statistics in a SQLite database
+#[cfg(feature = "sqlite")]
+pub mod storage;
+
+// The game history written to a CSV or JSON file
+#[cfg(feature = "sqlite")]
+pub mod export;
+
+// Kotlin/Swift bindings, built with `cargo build --features uniffi`
+#[cfg(feature = "uniffi")]
+pub mod mobile;
+#[cfg(feature = "uniffi")]
+uniffi::setup_scaffolding!();
+
+// Re-exports for convenience
+pub use achievements::{Achievement, Achievements, ACHIEVEMENTS};
+pub use ai::{AiMove, LetterFrequency};
+pub use arcade::{Run, RunError, MAX_LIVES, START_LIVES, WINS_PER_LIFE};
+pub use audit::{AuditEntry, AuditLog};
+pub use blocklist::Blocklist;
+pub use campaign::{Campaign, CampaignError, Progress, LEVELS};
+pub use categories::Categories;
+pub use charset::{Charset, CharsetError};
+pub use crossword::{Crossword, CrosswordError, CrosswordGame};
+pub use custom_words::{CustomWords, ImportReport};
+pub use daily::{Dailies, DailyResult};
+pub use definitions::{Definition, DefinitionCache, DefinitionError};
+#[cfg(feature = "dictionary")]
+pub use definitions::Dictionary;
+pub use difficulty::Difficulty;
+pub use duel::{Duel, DuelError, DuelRound};
+pub use elo::{RatingPoint, Ratings};
+pub use events::GameEvent;
+pub use evil::{Candidates, GameMode};
+#[cfg(feature = "sqlite")]
+pub use export::{ExportError, HistoryExport};
+pub use gallows::ArtStyle;
+pub use game::{GameState, HintError, KeyState, PauseError, WRONG_WORD_PENALTY};
+pub use guess::{parse_guess, parse_letter, GuessError, LetterOutcome};
+pub use history::{Action, UndoError};
+pub use ingest::{CleanReport, Rejection};
+pub use kids::KIDS_MAX_WRONG_GUESSES;
+pub use languages::{Language, LANGUAGES};
+pub use matches::{Match, MatchError, MatchWinner, Rotation};
+pub use metrics::{CommandMetrics, CommandSummary};
+pub use multiplayer::{ClientMessage, HostMessage};
+pub use online_words::{OnlineWords, WordApiError, WordSource};
+pub use pattern::{Pattern, PatternError, MAX_MATCHES};
+pub use positional::{PositionalError, PositionalGame, PositionalGuess};
+#[cfg(feature = "online-words")]
+pub use online_words::WordApi;
+pub use powerups::{Inventory, PowerUp, PowerUpError, POWER_UPS};
+pub use profiles::{Profile, ProfileError, Profiles};
+pub use providers::{
+    DailyProvider, FileProvider, OnlineProvider, ProviderError, ProviderHealth, Served, StaticProvider, WordProvider,
+    WordProviders,
+};
+pub use race::{Race, RaceError, RaceWinner, Turn};
+pub use replay::{Replay, ReplayError};
+pub use reveal::{Reveal, RevealedPlace};
+pub use rating::{rate_word, WordRating};
+pub use saved::{Autosave, SavedGame, SavedWordle};
+pub use schemas::schemas;
+pub use score::Score;
+pub use secret::SecretError;
+pub use sessions::{SessionId, Sessions};
+pub use settings::{DisplayFormat, LetterCase, Settings};
+pub use shop::{Help, ShopError, Wallet};
+pub use stats::Statistics;
+pub use suggest::{Suggestion, SUGGESTIONS};
+pub use telemetry::{GameTelemetry, TelemetryBatch, TelemetryError, TelemetryEvent, TelemetryKind, TelemetrySink};
+pub use trie::Trie;
+pub use versus::{Side, Versus, VersusError, VersusMessage, VersusRule, VersusWinner, DEFAULT_VERSUS_PORT};
+#[cfg(feature = "sqlite")]
+pub use storage::{GameRecord, Storage, StorageError, WordStats};
+pub use wordle::{Feedback, WordleError, WordleGame};
+pub use words::{PlayableError, WordLengths, WordList, WordListError};
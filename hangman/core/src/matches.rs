@@ -0,0 +1,258 @@
+// ============================================================================
+// BEST-OF-N MATCHES
+// ============================================================================
+// A match is a series of games (rounds) against the hangman: the player wins
+// it by winning most of the rounds, e.g. 3 of a best of 5. It's over as soon
+// as either side can't be caught, so a best of 5 can end 3-0.
+//
+// ROUNDS:
+// Each round is a new game, the match moves from one to the next with it (in
+// GameState's `best_of`), so a round's GameView can show "Round 2 of 5" and
+// the score. A round's result is recorded when its game ends, and taken back
+// if its last move is undone. Rounds are counted in the statistics like any
+// other game.
+//
+// ROTATION:
+// Rounds change what they're played with, round by round:
+// - Rotation::Difficulty: easy, medium, hard, easy, ... from the difficulty
+//   the match started with
+// - Rotation::Category: the categories in turn, at the match's difficulty
+//
+// Matches have an odd number of rounds (up to MAX_ROUNDS), so there's always
+// a winner.
+
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use crate::difficulty::Difficulty;
+
+/// Most rounds a match can have
+pub const MAX_ROUNDS: u32 = 9;
+
+/// What changes from one round to the next
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum Rotation {
+    #[default]
+    Difficulty,
+    Category,
+}
+
+/// Who won a match
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum MatchWinner {
+    Player,
+    Hangman,
+}
+
+/// Reasons a match can't be started
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum MatchError {
+    /// Not an odd number of rounds from 1 to MAX_ROUNDS
+    InvalidRounds(u32),
+    /// Rotation::Category without any categories to rotate through
+    NoCategories,
+}
+
+impl fmt::Display for MatchError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            MatchError::InvalidRounds(rounds) => write!(
+                f,
+                "a match needs an odd number of rounds from 1 to {}, not {}",
+                MAX_ROUNDS, rounds
+            ),
+            MatchError::NoCategories => write!(f, "there are no categories to play a match through"),
+        }
+    }
+}
+
+impl std::error::Error for MatchError {}
+
+/// How a round ended
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+pub struct RoundResult {
+    pub word: String,
+    pub won: bool,
+    pub score: u32,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+pub struct Match {
+    pub rounds: u32,
+    pub rotation: Rotation,
+    // The first round's difficulty (every round's, rotating categories)
+    pub difficulty: Difficulty,
+    // Categories rotated through, in order (empty rotating difficulties)
+    pub categories: Vec<String>,
+    // The round being played, from 1
+    pub round: u32,
+    // Finished rounds, in order
+    pub results: Vec<RoundResult>,
+}
+
+impl Match {
+    /// A match of `rounds` rounds, on its first round
+    pub fn new(rounds: u32, rotation: Rotation, difficulty: Difficulty, categories: Vec<String>) -> Result<Self, MatchError> {
+        if rounds == 0 || rounds > MAX_ROUNDS || rounds.is_multiple_of(2) {
+            return Err(MatchError::InvalidRounds(rounds));
+        }
+        let categories = match rotation {
+            Rotation::Difficulty => Vec::new(),
+            Rotation::Category if categories.is_empty() => return Err(MatchError::NoCategories),
+            Rotation::Category => categories,
+        };
+        Ok(Match {
+            rounds,
+            rotation,
+            difficulty,
+            categories,
+            round: 1,
+            results: Vec::new(),
+        })
+    }
+
+    /// The category (None for the main word list) and difficulty of the current round
+    pub fn setup(&self) -> (Option<String>, Difficulty) {
+        let index = (self.round - 1) as usize;
+        match self.rotation {
+            Rotation::Difficulty => {
+                const ORDER: [Difficulty; 3] = [Difficulty::Easy, Difficulty::Medium, Difficulty::Hard];
+                let first = ORDER.iter().position(|&difficulty| difficulty == self.difficulty).unwrap_or(0);
+                (None, ORDER[(first + index) % ORDER.len()])
+            }
+            Rotation::Category => (Some(self.categories[index % self.categories.len()].clone()), self.difficulty),
+        }
+    }
+
+    /// Rounds a side has to win to win the match
+    pub fn to_win(&self) -> u32 {
+        self.rounds / 2 + 1
+    }
+
+    pub fn wins(&self) -> u32 {
+        self.results.iter().filter(|result| result.won).count() as u32
+    }
+
+    pub fn losses(&self) -> u32 {
+        self.results.len() as u32 - self.wins()
+    }
+
+    /// Sum of the rounds' scores
+    pub fn score(&self) -> u32 {
+        self.results.iter().map(|result| result.score).sum()
+    }
+
+    /// Who won, once the match is decided
+    pub fn winner(&self) -> Option<MatchWinner> {
+        if self.wins() >= self.to_win() {
+            Some(MatchWinner::Player)
+        } else if self.losses() >= self.to_win() {
+            Some(MatchWinner::Hangman)
+        } else {
+            None
+        }
+    }
+
+    pub fn is_over(&self) -> bool {
+        self.winner().is_some()
+    }
+
+    /// Whether the current round's result is in
+    pub fn round_finished(&self) -> bool {
+        self.results.len() as u32 >= self.round
+    }
+
+    /// Record how the current round ended (once)
+    pub(crate) fn record(&mut self, result: RoundResult) {
+        if !self.round_finished() {
+            self.results.push(result);
+        }
+    }
+
+    /// Take the current round's result back (its game was reopened by undo)
+    pub(crate) fn reopen(&mut self) {
+        if self.round_finished() {
+            self.results.pop();
+        }
+    }
+
+    /// Move on to the next round, false if the match is over or the current
+    /// round isn't finished yet
+    pub fn next_round(&mut self) -> bool {
+        if self.is_over() || !self.round_finished() {
+            return false;
+        }
+        self.round += 1;
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn result(won: bool) -> RoundResult {
+        RoundResult {
+            word: "RUST".to_string(),
+            won,
+            score: if won { 100 } else { 0 },
+        }
+    }
+
+    #[test]
+    fn test_rounds_must_be_odd() {
+        for rounds in [0, 2, MAX_ROUNDS + 2] {
+            assert_eq!(
+                Match::new(rounds, Rotation::Difficulty, Difficulty::Easy, Vec::new()),
+                Err(MatchError::InvalidRounds(rounds))
+            );
+        }
+        assert_eq!(
+            Match::new(3, Rotation::Category, Difficulty::Easy, Vec::new()),
+            Err(MatchError::NoCategories)
+        );
+    }
+
+    #[test]
+    fn test_decided_early() {
+        let mut best_of = Match::new(5, Rotation::Difficulty, Difficulty::Medium, Vec::new()).unwrap();
+        assert!(!best_of.next_round());
+        for won in [true, false, true, true] {
+            assert!(!best_of.is_over());
+            best_of.record(result(won));
+            best_of.record(result(!won));
+            best_of.next_round();
+        }
+        // 3-1 after four rounds, the fifth isn't played
+        assert_eq!(best_of.winner(), Some(MatchWinner::Player));
+        assert_eq!((best_of.round, best_of.wins(), best_of.losses(), best_of.score()), (4, 3, 1, 300));
+        assert!(!best_of.next_round());
+
+        best_of.reopen();
+        assert_eq!(best_of.winner(), None);
+    }
+
+    #[test]
+    fn test_rotation() {
+        let mut best_of = Match::new(5, Rotation::Difficulty, Difficulty::Medium, Vec::new()).unwrap();
+        let mut difficulties = Vec::new();
+        for _ in 0..3 {
+            difficulties.push(best_of.setup().1);
+            best_of.record(result(false));
+            best_of.next_round();
+        }
+        assert_eq!(difficulties, [Difficulty::Medium, Difficulty::Hard, Difficulty::Easy]);
+        assert_eq!(best_of.winner(), Some(MatchWinner::Hangman));
+
+        let categories = vec!["animals".to_string(), "food".to_string()];
+        let mut best_of = Match::new(3, Rotation::Category, Difficulty::Hard, categories).unwrap();
+        assert_eq!(best_of.setup(), (Some("animals".to_string()), Difficulty::Hard));
+        best_of.record(result(true));
+        best_of.next_round();
+        best_of.record(result(false));
+        best_of.next_round();
+        assert_eq!(best_of.setup().0.as_deref(), Some("animals"));
+    }
+}
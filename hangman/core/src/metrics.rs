@@ -0,0 +1,295 @@
+// ============================================================================
+// COMMAND METRICS
+// ============================================================================
+// How often each of the desktop app's commands ran and how long it took, for
+// spotting a slow save or word fetch. Unlike the audit log (see audit.rs) it's
+// always on: it's only counts and durations, kept in memory, nothing the
+// player typed.
+//
+// WHAT'S KEPT:
+// Per command: the calls and how many failed, the total and the longest
+// time, and the durations of the last MAX_SAMPLES calls, which the
+// percentiles (p50, p90, p99) are taken from. So the counts are since the app
+// started, the percentiles follow the recent calls.
+//
+// CALLS:
+// Like the audit log's, a call is begun when it's invoked and finished when
+// it answers, matched by the window and an id the frontend gave it (the
+// desktop app's IPC hooks do both, see src-tauri/src/audit.rs). A call that
+// never answers (its window closed or reloaded first) isn't counted: the
+// window's calls are forgotten when it's closed, and any call still running
+// after STALE_AFTER is dropped as the next one begins.
+//
+// ON EXIT:
+// With the `metrics_on_exit` setting on, the desktop app writes them to
+// METRICS_FILE in the app data directory as it exits (replacing the last
+// run's), so a session's numbers can be looked at after it's over.
+
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, VecDeque};
+use std::io;
+use std::path::PathBuf;
+use std::time::{Duration, Instant};
+use sandbox_store::write_atomic;
+use crate::settings::Settings;
+
+/// Where the metrics are written on exit, in the app data directory
+pub const METRICS_FILE: &str = "metrics.json";
+
+/// Durations kept per command for its percentiles
+pub const MAX_SAMPLES: usize = 1000;
+
+/// How long a call can run before it's taken as one that will never answer
+pub const STALE_AFTER: Duration = Duration::from_secs(10 * 60);
+
+/// One command's numbers
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CommandSummary {
+    pub command: String,
+    pub calls: u64,
+    // Calls that answered with an error
+    pub errors: u64,
+    // In microseconds
+    pub mean_us: u64,
+    pub p50_us: u64,
+    pub p90_us: u64,
+    pub p99_us: u64,
+    pub max_us: u64,
+    // All its calls together
+    pub total_us: u64,
+}
+
+/// The times of one command's calls
+#[derive(Debug, Clone, Default)]
+struct CommandStats {
+    calls: u64,
+    errors: u64,
+    total: Duration,
+    max: Duration,
+    // The last MAX_SAMPLES, oldest first
+    samples: VecDeque<Duration>,
+}
+
+impl CommandStats {
+    fn record(&mut self, took: Duration, ok: bool) {
+        self.calls += 1;
+        if !ok {
+            self.errors += 1;
+        }
+        self.total += took;
+        self.max = self.max.max(took);
+        if self.samples.len() == MAX_SAMPLES {
+            self.samples.pop_front();
+        }
+        self.samples.push_back(took);
+    }
+
+    fn summary(&self, command: &str) -> CommandSummary {
+        let mut sorted: Vec<Duration> = self.samples.iter().copied().collect();
+        sorted.sort_unstable();
+        CommandSummary {
+            command: command.to_string(),
+            calls: self.calls,
+            errors: self.errors,
+            mean_us: (self.total.as_micros() / u128::from(self.calls.max(1))).min(u64::MAX as u128) as u64,
+            p50_us: micros(percentile(&sorted, 50)),
+            p90_us: micros(percentile(&sorted, 90)),
+            p99_us: micros(percentile(&sorted, 99)),
+            max_us: micros(self.max),
+            total_us: micros(self.total),
+        }
+    }
+}
+
+/// The calls running and every command's times so far
+#[derive(Debug, Clone)]
+pub struct CommandMetrics {
+    on_exit: bool,
+    file: PathBuf,
+    // When each call running was invoked, by window and call id
+    running: HashMap<(String, usize), (String, Instant)>,
+    commands: HashMap<String, CommandStats>,
+}
+
+impl CommandMetrics {
+    /// Metrics written to `file` on exit if `settings` ask for it
+    pub fn new(file: PathBuf, settings: &Settings) -> Self {
+        CommandMetrics {
+            on_exit: settings.metrics_on_exit,
+            file,
+            running: HashMap::new(),
+            commands: HashMap::new(),
+        }
+    }
+
+    /// Follow changed settings
+    pub fn configure(&mut self, settings: &Settings) {
+        self.on_exit = settings.metrics_on_exit;
+    }
+
+    /// Whether they're written on exit
+    pub fn on_exit(&self) -> bool {
+        self.on_exit
+    }
+
+    pub fn file(&self) -> &PathBuf {
+        &self.file
+    }
+
+    /// A command was invoked
+    pub fn begin(&mut self, window: &str, id: usize, command: &str, at: Instant) {
+        self.running.retain(|_, (_, started)| at.saturating_duration_since(*started) < STALE_AFTER);
+        self.running.insert((window.to_string(), id), (command.to_string(), at));
+    }
+
+    /// `window` was closed, its calls won't answer
+    pub fn forget_window(&mut self, window: &str) {
+        self.running.retain(|(label, _), _| label != window);
+    }
+
+    /// Calls begun that haven't answered yet
+    pub fn running(&self) -> usize {
+        self.running.len()
+    }
+
+    /// The call `id` of `window` answered, returns how long it took
+    /// None for a call that wasn't begun
+    pub fn finish(&mut self, window: &str, id: usize, ok: bool, now: Instant) -> Option<Duration> {
+        let (command, at) = self.running.remove(&(window.to_string(), id))?;
+        let took = now.saturating_duration_since(at);
+        self.commands.entry(command).or_default().record(took, ok);
+        Some(took)
+    }
+
+    /// Every command called so far, the most time spent in first
+    pub fn summaries(&self) -> Vec<CommandSummary> {
+        let mut summaries: Vec<_> = self.commands.iter().map(|(command, stats)| stats.summary(command)).collect();
+        summaries.sort_by(|a, b| b.total_us.cmp(&a.total_us).then_with(|| a.command.cmp(&b.command)));
+        summaries
+    }
+
+    /// Write the summaries to the file if the setting asks for it, returns whether it did
+    pub fn write_on_exit(&self) -> io::Result<bool> {
+        if !self.on_exit || self.commands.is_empty() {
+            return Ok(false);
+        }
+        let json = serde_json::to_vec_pretty(&self.summaries()).expect("command summaries serialize");
+        if let Some(dir) = self.file.parent() {
+            std::fs::create_dir_all(dir)?;
+        }
+        write_atomic(&self.file, &json)?;
+        Ok(true)
+    }
+}
+
+/// The duration `percent`% of `sorted` are at or under (nearest rank), zero for none
+fn percentile(sorted: &[Duration], percent: usize) -> Duration {
+    if sorted.is_empty() {
+        return Duration::ZERO;
+    }
+    let rank = (sorted.len() * percent).div_ceil(100).max(1);
+    sorted[rank - 1]
+}
+
+fn micros(duration: Duration) -> u64 {
+    duration.as_micros().min(u64::MAX as u128) as u64
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn metrics(dir: &tempfile::TempDir, on_exit: bool) -> CommandMetrics {
+        let settings = Settings {
+            metrics_on_exit: on_exit,
+            ..Settings::default()
+        };
+        CommandMetrics::new(dir.path().join(METRICS_FILE), &settings)
+    }
+
+    #[test]
+    fn test_calls_are_timed_once_they_answer() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut metrics = metrics(&dir, false);
+        let start = Instant::now();
+        let ms = Duration::from_millis;
+        for id in 0..100 {
+            metrics.begin("main", id, "guess_letter", start);
+            // 1 to 100 ms, the last one failing
+            assert_eq!(metrics.finish("main", id, id < 99, start + ms(id as u64 + 1)), Some(ms(id as u64 + 1)));
+        }
+        metrics.begin("main", 100, "save_game", start);
+        metrics.begin("other", 100, "get_settings", start);
+        metrics.finish("main", 100, true, start + ms(900));
+        // A call that wasn't begun isn't counted
+        assert_eq!(metrics.finish("main", 100, true, start), None);
+
+        let summaries = metrics.summaries();
+        let commands: Vec<_> = summaries.iter().map(|summary| summary.command.as_str()).collect();
+        assert_eq!(commands, ["guess_letter", "save_game"]);
+        let guesses = &summaries[0];
+        assert_eq!((guesses.calls, guesses.errors), (100, 1));
+        assert_eq!((guesses.p50_us, guesses.p90_us, guesses.p99_us), (50_000, 90_000, 99_000));
+        assert_eq!((guesses.mean_us, guesses.max_us), (50_500, 100_000));
+        assert_eq!(summaries[1].p50_us, 900_000);
+    }
+
+    #[test]
+    fn test_calls_that_never_answer_are_dropped() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut metrics = metrics(&dir, false);
+        let start = Instant::now();
+        metrics.begin("main", 1, "guess_letter", start);
+        metrics.begin("other", 1, "guess_letter", start);
+        metrics.begin("other", 2, "get_stats", start);
+        metrics.forget_window("other");
+        assert_eq!(metrics.running(), 1);
+
+        // The one left never answers either, and is dropped once it's stale
+        metrics.begin("main", 2, "get_stats", start + STALE_AFTER);
+        assert_eq!(metrics.running(), 1);
+        assert_eq!(metrics.finish("main", 1, true, start + STALE_AFTER), None);
+        assert!(metrics.summaries().is_empty());
+    }
+
+    #[test]
+    fn test_mean_of_many_calls() {
+        let stats = CommandStats {
+            calls: 1 << 32,
+            total: Duration::from_micros(3 << 32),
+            ..CommandStats::default()
+        };
+        assert_eq!(stats.summary("get_game_state").mean_us, 3);
+    }
+
+    #[test]
+    fn test_percentiles_follow_the_last_samples() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut metrics = metrics(&dir, false);
+        let start = Instant::now();
+        for id in 0..MAX_SAMPLES + 10 {
+            let took = if id < 10 { Duration::from_secs(5) } else { Duration::from_millis(1) };
+            metrics.begin("main", id, "get_game_state", start);
+            metrics.finish("main", id, true, start + took);
+        }
+        let summary = &metrics.summaries()[0];
+        assert_eq!(summary.calls, (MAX_SAMPLES + 10) as u64);
+        assert_eq!((summary.p99_us, summary.max_us), (1_000, 5_000_000));
+    }
+
+    #[test]
+    fn test_written_on_exit_only_if_asked() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut metrics = metrics(&dir, false);
+        let start = Instant::now();
+        metrics.begin("main", 1, "get_stats", start);
+        metrics.finish("main", 1, true, start + Duration::from_millis(3));
+        assert!(!metrics.write_on_exit().unwrap());
+        assert!(!metrics.file().exists());
+
+        metrics.configure(&Settings { metrics_on_exit: true, ..Settings::default() });
+        assert!(metrics.write_on_exit().unwrap());
+        let written: Vec<CommandSummary> = serde_json::from_slice(&std::fs::read(metrics.file()).unwrap()).unwrap();
+        assert_eq!(written, metrics.summaries());
+    }
+}
@@ -0,0 +1,186 @@
+// ============================================================================
+// MOBILE BINDINGS (uniffi)
+// ============================================================================
+// Kotlin and Swift wrappers around the game rules, so a future Android or iOS
+// frontend plays by exactly the same rules as the desktop app and the CLI.
+//
+// Only compiled with the "uniffi" feature. The bindings are generated from the
+// compiled library (see README, "Mobile Bindings"):
+//   cargo build -p hangman-core --features uniffi
+//   cargo run -p uniffi-bindgen -- generate --library target/debug/libhangman_core.so \
+//       --language kotlin --out-dir hangman/core/bindings/kotlin
+//
+// Like the desktop app, the game only hands out player views, so a two-player
+// secret can't be read from the mobile UI before the game is over.
+
+use std::fmt;
+use std::sync::{Arc, Mutex, MutexGuard, PoisonError};
+use crate::difficulty::Difficulty;
+use crate::digraphs::spell_str;
+use crate::game::GameState;
+use crate::settings::LetterCase;
+
+/// Errors surfaced to Kotlin/Swift as exceptions
+#[derive(Debug, uniffi::Error)]
+#[uniffi(flat_error)]
+pub enum HangmanError {
+    /// The two-player secret was rejected (see secret.rs)
+    InvalidSecret(String),
+    /// A hint couldn't be given
+    Hint(String),
+}
+
+impl fmt::Display for HangmanError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            HangmanError::InvalidSecret(message) | HangmanError::Hint(message) => {
+                write!(f, "{}", message)
+            }
+        }
+    }
+}
+
+impl std::error::Error for HangmanError {}
+
+/// What the player may see of a game (the word is masked until it's over)
+#[derive(Debug, Clone, uniffi::Record)]
+pub struct GameSnapshot {
+    pub word: String,
+    // The word drawn as text, e.g. "R _ S T"
+    pub display_word: String,
+    pub guessed_letters: Vec<String>,
+    pub guessed_words: Vec<String>,
+    pub wrong_guesses: u32,
+    pub max_wrong_guesses: u32,
+    // Stage of the gallows to draw, 0 to `stages` (one per wrong guess allowed)
+    pub stage: u32,
+    pub stages: u32,
+    pub game_over: bool,
+    pub won: bool,
+    pub hints_used: u32,
+    pub max_hints: u32,
+    pub difficulty: Difficulty,
+    pub letter_case: LetterCase,
+    // Set once the game is over
+    pub score: Option<u32>,
+}
+
+/// One game of hangman, shared with Kotlin/Swift as an object
+#[derive(uniffi::Object)]
+pub struct HangmanGame {
+    // Foreign code can call from any thread
+    game: Mutex<GameState>,
+}
+
+#[uniffi::export]
+impl HangmanGame {
+    /// Start a game with a word picked by the app
+    #[uniffi::constructor]
+    pub fn new(word: String, difficulty: Difficulty) -> Arc<Self> {
+        Arc::new(HangmanGame {
+            game: Mutex::new(GameState::new(word, None, difficulty)),
+        })
+    }
+
+    /// Start a two-player game with a secret typed in by player one
+    #[uniffi::constructor]
+    pub fn new_custom(secret: String, difficulty: Difficulty) -> Result<Arc<Self>, HangmanError> {
+        let game = GameState::new_custom(&secret, difficulty)
+            .map_err(|err| HangmanError::InvalidSecret(err.to_string()))?;
+        Ok(Arc::new(HangmanGame { game: Mutex::new(game) }))
+    }
+
+    /// Guess a letter (only the first character is used)
+    pub fn guess_letter(&self, letter: String) -> GameSnapshot {
+        let mut game = self.game();
+        if let Some(letter) = letter.chars().next() {
+            game.guess_letter(letter);
+        }
+        snapshot(&game)
+    }
+
+    /// Guess the whole word, returns whether it was right
+    pub fn guess_word(&self, word: String) -> bool {
+        self.game().guess_word(&word)
+    }
+
+    /// End the game as a loss, returns false if it was already over
+    pub fn give_up(&self) -> bool {
+        self.game().give_up()
+    }
+
+    /// Reveal a letter for the price of a wrong guess, returns the letter
+    pub fn use_hint(&self) -> Result<String, HangmanError> {
+        let mut game = self.game();
+        let letter = game.use_hint().map_err(|err| HangmanError::Hint(err.to_string()))?;
+        Ok(game.letter_case.apply(letter).to_string())
+    }
+
+    pub fn set_letter_case(&self, letter_case: LetterCase) {
+        self.game().letter_case = letter_case;
+    }
+
+    pub fn snapshot(&self) -> GameSnapshot {
+        snapshot(&self.game())
+    }
+}
+
+impl HangmanGame {
+    /// Lock the game, carrying on if a thread panicked while holding it
+    /// (an app's panic in one call shouldn't make every later call panic too)
+    fn game(&self) -> MutexGuard<'_, GameState> {
+        self.game.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+}
+
+fn snapshot(game: &GameState) -> GameSnapshot {
+    let view = game.player_view();
+    GameSnapshot {
+        word: spell_str(&view.word, game.letter_case),
+        display_word: game.get_display_word(),
+        guessed_letters: view.guessed_letters.iter().map(|&c| spell_str(&c.to_string(), game.letter_case)).collect(),
+        guessed_words: view.guessed_words,
+        wrong_guesses: view.wrong_guesses,
+        max_wrong_guesses: view.max_wrong_guesses,
+        stage: game.stage(),
+        stages: game.stages(),
+        game_over: view.game_over,
+        won: view.won,
+        hints_used: view.hints_used,
+        max_hints: view.max_hints,
+        difficulty: view.difficulty,
+        letter_case: view.letter_case,
+        score: view.score.map(|score| score.total),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_mobile_game() {
+        let game = HangmanGame::new("rust".to_string(), Difficulty::Medium);
+        let snapshot = game.guess_letter("r".to_string());
+        assert_eq!(snapshot.word, "R___");
+        assert_eq!(snapshot.guessed_letters, ["R"]);
+
+        assert!(game.guess_word("RUST".to_string()));
+        let snapshot = game.snapshot();
+        assert!(snapshot.won);
+        assert!(snapshot.score.unwrap() > 0);
+    }
+
+    #[test]
+    fn test_mobile_errors() {
+        assert!(matches!(
+            HangmanGame::new_custom("R2D2".to_string(), Difficulty::Easy),
+            Err(HangmanError::InvalidSecret(_))
+        ));
+
+        let game = HangmanGame::new("GO".to_string(), Difficulty::Hard);
+        game.guess_word("NO".to_string());
+        game.guess_word("SO".to_string());
+        assert!(matches!(game.use_hint(), Err(HangmanError::Hint(_))));
+    }
+}
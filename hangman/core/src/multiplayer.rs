@@ -0,0 +1,149 @@
+// ============================================================================
+// ONLINE MULTIPLAYER
+// ============================================================================
+// One app hosts a game and others join it over the network: the host's
+// session keeps the only GameState, guests send it guesses and are sent the
+// GameView back whenever it changes. So there's one set of rules, one clock,
+// and one result, the host's, and guests can't see the word any more than
+// the host's window does.
+//
+// JOINING:
+// The host listens on a WebSocket port (DEFAULT_PORT unless another is
+// picked) and gets a join code, JOIN_CODE_LEN letters and digits that are
+// hard to mix up. Guests connect to the host's address and send the code in
+// their first message, a guest with the wrong code is refused.
+//
+// MESSAGES:
+// Every message is a WebSocket text frame with a JSON object tagged by
+// "type":
+// - from guests (ClientMessage): join, letter, word
+// - from the host (HostMessage): welcome (the game, once the code was
+//   right), game (the game after a move, with the error if the guest's move
+//   was refused), refused, closed
+// The WebSocket connections themselves are the desktop app's (see
+// src-tauri/src/net.rs), nothing in here does any networking.
+
+use sandbox_rng::Rng;
+use sandbox_schema::hangman::{GameError, GameView};
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+/// Port a game is hosted on unless another one is picked
+pub const DEFAULT_PORT: u16 = 7878;
+
+/// Characters in a join code
+pub const JOIN_CODE_LEN: usize = 6;
+
+/// What join codes are made of: no 0/O, 1/I/L
+const JOIN_CODE_CHARS: &[u8] = b"ABCDEFGHJKMNPQRSTUVWXYZ23456789";
+
+/// Longest message either side accepts, in bytes (a GameView is well under)
+pub const MAX_MESSAGE_LEN: usize = 64 * 1024;
+
+/// A new join code
+pub fn join_code(rng: &mut impl Rng) -> String {
+    (0..JOIN_CODE_LEN)
+        .map(|_| char::from(JOIN_CODE_CHARS[rng.gen_range(0..JOIN_CODE_CHARS.len())]))
+        .collect()
+}
+
+/// Whether a code a guest typed is the host's (ignoring case and spaces)
+pub fn code_matches(code: &str, typed: &str) -> bool {
+    let typed: String = typed.chars().filter(|c| !c.is_whitespace()).collect();
+    typed.eq_ignore_ascii_case(code)
+}
+
+/// A message that couldn't be read
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ProtocolError(pub String);
+
+impl fmt::Display for ProtocolError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "unexpected message: {}", self.0)
+    }
+}
+
+impl std::error::Error for ProtocolError {}
+
+/// What a guest sends the host
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum ClientMessage {
+    /// The first message: the host's join code, and who's joining (their profile)
+    Join { code: String, name: Option<String> },
+    /// A letter guess, checked by the host like its own
+    Letter { letter: String },
+    /// A guess at the whole word
+    Word { word: String },
+}
+
+/// What the host sends its guests
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum HostMessage {
+    /// The game as it is when a guest is let in
+    Welcome { game: GameView },
+    /// The game after a move (anyone's), or after a new game was started
+    /// `error` is set for the guest whose move it refused
+    Game { game: GameView, error: Option<GameError> },
+    /// The guest wasn't let in, the connection closes
+    Refused { reason: String },
+    /// The host stopped hosting
+    Closed { reason: String },
+}
+
+impl ClientMessage {
+    pub fn to_json(&self) -> String {
+        serde_json::to_string(self).expect("client messages are plain JSON")
+    }
+
+    pub fn from_json(json: &str) -> Result<Self, ProtocolError> {
+        serde_json::from_str(json).map_err(|err| ProtocolError(err.to_string()))
+    }
+}
+
+impl HostMessage {
+    pub fn to_json(&self) -> String {
+        serde_json::to_string(self).expect("host messages are plain JSON")
+    }
+
+    pub fn from_json(json: &str) -> Result<Self, ProtocolError> {
+        serde_json::from_str(json).map_err(|err| ProtocolError(err.to_string()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::difficulty::Difficulty;
+    use crate::game::GameState;
+    use sandbox_rng::RngHandle;
+
+    #[test]
+    fn test_join_codes() {
+        let code = join_code(&mut RngHandle::seeded(7));
+        assert_eq!(code.len(), JOIN_CODE_LEN);
+        assert!(code.bytes().all(|c| JOIN_CODE_CHARS.contains(&c)));
+        assert_eq!(code, join_code(&mut RngHandle::seeded(7)));
+
+        assert!(code_matches("K7QX2M", " k7q x2m"));
+        assert!(!code_matches("K7QX2M", "K7QX2"));
+    }
+
+    #[test]
+    fn test_messages() {
+        let join = ClientMessage::Join { code: "K7QX2M".to_string(), name: None };
+        assert_eq!(join.to_json(), r#"{"type":"join","code":"K7QX2M","name":null}"#);
+        assert_eq!(ClientMessage::from_json(&join.to_json()).unwrap(), join);
+        assert_eq!(
+            ClientMessage::from_json(r#"{"type": "letter", "letter": "r"}"#).unwrap(),
+            ClientMessage::Letter { letter: "r".to_string() }
+        );
+        assert!(ClientMessage::from_json(r#"{"type": "cheat"}"#).is_err());
+
+        let game = GameState::new("RUST".to_string(), None, Difficulty::Easy).view();
+        let update = HostMessage::Game { game, error: Some(GameError::GameAlreadyOver) };
+        assert_eq!(HostMessage::from_json(&update.to_json()).unwrap(), update);
+        assert!(!update.to_json().contains("RUST"));
+    }
+}
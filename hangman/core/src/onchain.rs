@@ -0,0 +1,210 @@
+// ============================================================================
+// GAME RESULTS ON THE POW-SIM CHAIN
+// ============================================================================
+// Records finished games as transactions on a proof-of-work-sim chain using a
+// commit-reveal scheme:
+// 1. COMMIT: when the game starts, a hash of the word (plus a random salt) is
+//    put on the chain. Nobody can read the word from it.
+// 2. REVEAL: when the game ends, the word, the salt, and the result are put
+//    on the chain. Anyone can hash them again and compare with the commit.
+// A matching, earlier commit proves the word was fixed before the first guess,
+// so it can't have been swapped to make the game easier or harder.
+//
+// Both are zero-amount transfers from the player to RESULTS_RECIPIENT, with
+// the data in the memo:
+//   hangman:commit:<hash>
+//   hangman:reveal:<hash>:<salt>:<won|lost>:<score>:<word>
+// (the word goes last because phrases contain spaces)
+//
+// Only compiled with the "onchain" feature.
+
+use proof_of_work_sim::blockchain::Blockchain;
+use proof_of_work_sim::transaction::Transaction;
+use sandbox_rng::{Rng, RngHandle};
+use sha2::{Digest, Sha256};
+use std::collections::HashSet;
+use crate::game::GameState;
+
+/// Recipient of every commit and reveal transaction
+pub const RESULTS_RECIPIENT: &str = "hangman";
+
+const COMMIT_PREFIX: &str = "hangman:commit:";
+const REVEAL_PREFIX: &str = "hangman:reveal:";
+
+/// The hashed word committed at the start of a game
+/// Keep it until the game ends, the salt is needed for the reveal
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Commitment {
+    pub hash: String,
+    salt: String,
+}
+
+impl Commitment {
+    /// Commit to `word` with a fresh random salt
+    /// (always from entropy: a seeded salt would let anyone recompute the word)
+    pub fn new(word: &str) -> Self {
+        let mut rng = RngHandle::from_entropy();
+        let salt: String = (0..16).map(|_| format!("{:02x}", rng.gen::<u8>())).collect();
+        Commitment {
+            hash: commitment_hash(&salt, word),
+            salt,
+        }
+    }
+
+    /// Transaction that puts the commitment on the chain (send at game start)
+    pub fn commit_transaction(&self, player: &str) -> Transaction {
+        results_transfer(player).with_memo(format!("{}{}", COMMIT_PREFIX, self.hash))
+    }
+
+    /// Transaction that reveals the word and the result (send once `game` is over)
+    pub fn reveal_transaction(&self, player: &str, game: &GameState) -> Transaction {
+        let result = if game.won { "won" } else { "lost" };
+        let score = game.score.map_or(0, |score| score.total);
+        results_transfer(player).with_memo(format!(
+            "{}{}:{}:{}:{}:{}",
+            REVEAL_PREFIX, self.hash, self.salt, result, score, game.word
+        ))
+    }
+}
+
+/// Hash committed for `word` (SHA-256 of "salt:word", hex)
+pub fn commitment_hash(salt: &str, word: &str) -> String {
+    let mut hasher = Sha256::new();
+    hasher.update(format!("{}:{}", salt, word));
+    format!("{:x}", hasher.finalize())
+}
+
+fn results_transfer(player: &str) -> Transaction {
+    Transaction::new_transfer(player.to_string(), RESULTS_RECIPIENT.to_string(), 0)
+}
+
+/// A game result found on the chain
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GameRecord {
+    pub player: String,
+    pub word: String,
+    pub won: bool,
+    pub score: u32,
+    // Block the reveal was mined in
+    pub block_index: u64,
+    // The word matches the hash the same player committed earlier on the chain
+    pub verified: bool,
+}
+
+/// Every revealed game on the chain, in chain order
+pub fn records(chain: &Blockchain) -> Vec<GameRecord> {
+    // (player, hash) of every commit seen so far
+    let mut commits: HashSet<(String, String)> = HashSet::new();
+    let mut records = Vec::new();
+
+    for block in &chain.blocks {
+        for tx in &block.transactions {
+            let (Some(player), Some(memo)) = (&tx.sender, &tx.memo) else {
+                continue;
+            };
+            if tx.recipient != RESULTS_RECIPIENT {
+                continue;
+            }
+            if let Some(hash) = memo.strip_prefix(COMMIT_PREFIX) {
+                commits.insert((player.clone(), hash.to_string()));
+            } else if let Some(reveal) = memo.strip_prefix(REVEAL_PREFIX) {
+                let Some((hash, salt, won, score, word)) = parse_reveal(reveal) else {
+                    continue;
+                };
+                records.push(GameRecord {
+                    player: player.clone(),
+                    word: word.to_string(),
+                    won,
+                    score,
+                    block_index: block.index,
+                    verified: commitment_hash(salt, word) == hash
+                        && commits.contains(&(player.clone(), hash.to_string())),
+                });
+            }
+        }
+    }
+    records
+}
+
+/// Split "<hash>:<salt>:<won|lost>:<score>:<word>"
+fn parse_reveal(reveal: &str) -> Option<(&str, &str, bool, u32, &str)> {
+    let mut parts = reveal.splitn(5, ':');
+    let hash = parts.next()?;
+    let salt = parts.next()?;
+    let won = match parts.next()? {
+        "won" => true,
+        "lost" => false,
+        _ => return None,
+    };
+    let score = parts.next()?.parse().ok()?;
+    let word = parts.next()?;
+    Some((hash, salt, won, score, word))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::difficulty::Difficulty;
+    use proof_of_work_sim::config::Config;
+
+    fn mine(chain: &mut Blockchain) {
+        while !chain.try_mine_block("test-miner") {}
+    }
+
+    #[test]
+    fn test_commit_reveal_round_trip() {
+        let mut game = GameState::new("RUST IS FUN".to_string(), None, Difficulty::Easy);
+        let commitment = Commitment::new(&game.word);
+        assert!(!commitment.commit_transaction("ada").memo.unwrap().contains("RUST"));
+
+        let mut chain = Blockchain::new(Config::default());
+        chain.submit_transaction(commitment.commit_transaction("ada"));
+        mine(&mut chain);
+        game.guess_word("rust is fun");
+        chain.submit_transaction(commitment.reveal_transaction("ada", &game));
+        mine(&mut chain);
+
+        let records = records(&chain);
+        assert_eq!(records.len(), 1);
+        assert_eq!(records[0].player, "ada");
+        assert_eq!(records[0].word, "RUST IS FUN");
+        assert!(records[0].won);
+        assert_eq!(records[0].score, game.score.unwrap().total);
+        assert_eq!(records[0].block_index, 2);
+        assert!(records[0].verified);
+    }
+
+    #[test]
+    fn test_swapped_word_is_not_verified() {
+        let commitment = Commitment::new("RUST");
+        let easier = GameState::new("GO".to_string(), None, Difficulty::Easy);
+
+        let mut chain = Blockchain::new(Config::default());
+        chain.submit_transaction(commitment.commit_transaction("ada"));
+        chain.submit_transaction(commitment.reveal_transaction("ada", &easier));
+        mine(&mut chain);
+
+        assert!(!records(&chain)[0].verified);
+    }
+
+    #[test]
+    fn test_reveal_without_commit_is_not_verified() {
+        let commitment = Commitment::new("RUST");
+        let game = GameState::new("RUST".to_string(), None, Difficulty::Easy);
+
+        let mut chain = Blockchain::new(Config::default());
+        // Committed by someone else
+        chain.submit_transaction(commitment.commit_transaction("grace"));
+        chain.submit_transaction(commitment.reveal_transaction("ada", &game));
+        mine(&mut chain);
+
+        let records = records(&chain);
+        assert!(!records[0].won);
+        assert!(!records[0].verified);
+    }
+
+    #[test]
+    fn test_salt_hides_repeated_words() {
+        assert_ne!(Commitment::new("RUST").hash, Commitment::new("RUST").hash);
+    }
+}
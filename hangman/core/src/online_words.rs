@@ -0,0 +1,243 @@
+// ============================================================================
+// ONLINE WORDS
+// ============================================================================
+// Instead of the word list files (see words.rs), the desktop app can play
+// words fetched from a public random word API (WORD_API_URL, see
+// https://random-word-api.herokuapp.com). Which one is the word_source
+// setting ("bundled" or "online", e.g. HANGMAN_WORD_SOURCE=online, or one of
+// the other providers, see providers.rs), so switching needs no rebuild.
+//
+// FETCHING:
+// With the online source the app fetches FETCH_COUNT words in the background
+// when it starts. Each one is cleaned up like every word (see ingest.rs):
+// letters and spaces only, a sensible length, no duplicates. Daily challenges keep using the bundled list, so everyone
+// still gets the same word.
+//
+// CACHING AND OFFLINE:
+// Fetched words are kept in an OnlineWords document (key ONLINE_WORDS_KEY,
+// "online_words.json" next to the statistics), the most recent MAX_CACHED
+// of them, so the next start has words before (or without) a connection.
+// A fetch that fails leaves the cache as it was, and with nothing cached
+// games are played with the bundled list.
+//
+// THE HTTP CLIENT:
+// Fetching needs the "online-words" feature (ureq, like the dictionary),
+// which the desktop app turns on. Parsing and the cache don't.
+
+use sandbox_store::{Schema, Store, StoreError};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+use std::fmt;
+use crate::ingest::clean_word;
+use crate::words::WordList;
+
+/// Store key the fetched words are saved under
+pub const ONLINE_WORDS_KEY: &str = "online_words";
+
+/// Where random English words are fetched from (`?number=N` words at a time)
+pub const WORD_API_URL: &str = "https://random-word-api.herokuapp.com/word";
+
+/// Words fetched per batch
+pub const FETCH_COUNT: usize = 100;
+
+/// Most fetched words kept, older ones are dropped first
+pub const MAX_CACHED: usize = 1000;
+
+/// Where new games get their words from
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum WordSource {
+    /// The word list files (a user list, or the one shipped with the app)
+    #[default]
+    Bundled,
+    /// Words fetched from the word API, the bundled list while there are none
+    Online,
+    /// The word_file list, read for every game
+    File,
+    /// The day's words of the bundled list, the same for everyone
+    Daily,
+}
+
+impl fmt::Display for WordSource {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            WordSource::Bundled => "bundled",
+            WordSource::Online => "online",
+            WordSource::File => "file",
+            WordSource::Daily => "daily",
+        };
+        write!(f, "{}", name)
+    }
+}
+
+/// Reasons a batch of words couldn't be fetched
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum WordApiError {
+    /// The request failed (no connection, a timeout, a server error)
+    Http(String),
+    /// The API answered with something that isn't a list of words
+    Parse(String),
+}
+
+impl fmt::Display for WordApiError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            WordApiError::Http(message) => write!(f, "word API request failed: {}", message),
+            WordApiError::Parse(message) => write!(f, "unexpected word API response: {}", message),
+        }
+    }
+}
+
+impl std::error::Error for WordApiError {}
+
+/// The words in a word API response, as sent (before any checks)
+pub fn parse_words(json: &str) -> Result<Vec<String>, WordApiError> {
+    serde_json::from_str(json).map_err(|err| WordApiError::Parse(err.to_string()))
+}
+
+/// Words fetched so far, oldest first
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+pub struct OnlineWords {
+    pub words: Vec<String>,
+}
+
+impl Schema for OnlineWords {
+    const VERSION: u32 = 1;
+}
+
+impl OnlineWords {
+    /// Load the fetched words, starting with none if nothing was saved yet
+    pub fn load(store: &Store) -> Result<Self, StoreError> {
+        Ok(store.load(ONLINE_WORDS_KEY)?.unwrap_or_default())
+    }
+
+    /// Save the fetched words, replacing the previous copy
+    pub fn save(&self, store: &Store) -> Result<(), StoreError> {
+        store.save(ONLINE_WORDS_KEY, self)
+    }
+
+    /// Keep the playable words of a fetched batch, returns how many were new
+    pub fn add<I, S>(&mut self, fetched: I) -> usize
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<str>,
+    {
+        let mut seen: HashSet<String> = self.words.iter().cloned().collect();
+        let before = self.words.len();
+        for word in fetched {
+            if let Ok(word) = clean_word(word.as_ref()) {
+                if seen.insert(word.clone()) {
+                    self.words.push(word);
+                }
+            }
+        }
+        let added = self.words.len() - before;
+        if self.words.len() > MAX_CACHED {
+            self.words.drain(..self.words.len() - MAX_CACHED);
+        }
+        added
+    }
+
+    /// The fetched words as a word list, None while there are none
+    pub fn list(&self) -> Option<WordList> {
+        WordList::new(&self.words).ok()
+    }
+}
+
+/// A word API client
+#[cfg(feature = "online-words")]
+#[derive(Debug, Clone)]
+pub struct WordApi {
+    url: String,
+    agent: ureq::Agent,
+}
+
+#[cfg(feature = "online-words")]
+impl WordApi {
+    /// Seconds a fetch may take before it's given up on
+    pub const TIMEOUT_SECS: u64 = 5;
+
+    /// A client for the API at `url` (e.g. WORD_API_URL)
+    pub fn new(url: impl Into<String>) -> Self {
+        WordApi {
+            url: url.into(),
+            agent: ureq::AgentBuilder::new()
+                .timeout(std::time::Duration::from_secs(Self::TIMEOUT_SECS))
+                .build(),
+        }
+    }
+
+    /// Fetch `count` random words
+    /// Blocks until the request is done, call it off the main thread
+    pub fn fetch(&self, count: usize) -> Result<Vec<String>, WordApiError> {
+        let response = self
+            .agent
+            .get(&self.url)
+            .query("number", &count.to_string())
+            .call()
+            .map_err(|err| WordApiError::Http(err.to_string()))?;
+        let body = response.into_string().map_err(|err| WordApiError::Http(err.to_string()))?;
+        parse_words(&body)
+    }
+}
+
+#[cfg(feature = "online-words")]
+impl Default for WordApi {
+    fn default() -> Self {
+        WordApi::new(WORD_API_URL)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_words() {
+        assert_eq!(parse_words(r#"["rust", "ferris"]"#).unwrap(), ["rust", "ferris"]);
+        assert!(matches!(parse_words(r#"{"error": "busy"}"#), Err(WordApiError::Parse(_))));
+    }
+
+    #[test]
+    fn test_add_keeps_playable_words() {
+        let mut online = OnlineWords::default();
+        assert!(online.list().is_none());
+
+        assert_eq!(online.add(["rust", "x", "42", "ferris", "RUST"]), 2);
+        assert_eq!(online.words, ["RUST", "FERRIS"]);
+        assert_eq!(online.add(["ferris", "cargo"]), 1);
+        assert_eq!(online.list().unwrap().words(), ["RUST", "FERRIS", "CARGO"]);
+    }
+
+    #[test]
+    fn test_oldest_words_dropped() {
+        let mut online = OnlineWords::default();
+        let batch: Vec<String> = (0..MAX_CACHED + 2).map(|n| format!("word {}", to_letters(n))).collect();
+        online.add(&batch);
+        assert_eq!(online.words.len(), MAX_CACHED);
+        assert_eq!(online.words[0], format!("WORD {}", to_letters(2).to_uppercase()));
+    }
+
+    /// A different letters-only string for every number
+    fn to_letters(mut n: usize) -> String {
+        let mut letters = String::new();
+        loop {
+            letters.push(char::from(b'a' + (n % 26) as u8));
+            n /= 26;
+            if n == 0 {
+                return letters;
+            }
+        }
+    }
+
+    #[test]
+    fn test_cache_survives_restarts() {
+        let dir = tempfile::tempdir().unwrap();
+        let store = Store::open(dir.path()).unwrap();
+        let mut online = OnlineWords::default();
+        online.add(["rust"]);
+        online.save(&store).unwrap();
+        assert_eq!(OnlineWords::load(&store).unwrap(), online);
+    }
+}
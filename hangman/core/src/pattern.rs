@@ -0,0 +1,230 @@
+// ============================================================================
+// PATTERN SEARCH
+// ============================================================================
+// Which words of a list fit a board: a mask like "_ A N G M A N" (the board
+// as it's shown, or "_ANGMAN") and the letters known not to be in the word.
+// It's the solver tool of the desktop app and the CLI's --search, and the
+// AI player keeps the words that fit its board with it (see ai.rs).
+//
+// THE MASK:
+//   _        a hidden letter
+//   A        a shown letter (any case, with a digraph typed as its letters)
+//   ' - !    punctuation, shown as it is
+// With a space after every character (the way the board is drawn) the
+// spaces only separate them, and a wider gap is a space between two words:
+// "_ _   _ _" is a two-word phrase. Without, every character is one, so
+// "__ __" is the same phrase.
+//
+// WHAT FITS:
+// A word fits when it has as many letters (or digraphs) and punctuation
+// as the mask, the shown ones in the same places, and only letters in the
+// hidden places that are neither excluded nor shown elsewhere: in hangman
+// a guessed letter is revealed everywhere in the word, so "_ A _" doesn't
+// fit "AAA". Letters are compared like the game compares them (see
+// letters.rs), accents ignored or not.
+//
+// SEARCHING:
+// search() walks the list's trie (see trie.rs), so only the words that fit
+// the places before are looked at. With digraphs a place can be several of a
+// word's letters, so those searches go through every word instead.
+
+use std::fmt;
+use crate::charset::Charset;
+use crate::digraphs::tokenize;
+use crate::game::is_guessable;
+use crate::letters::{same_letter, to_upper};
+use crate::words::WordList;
+
+/// The character of a hidden letter in a mask
+pub const HIDDEN: char = '_';
+
+/// Most words a search returns, the rest are only counted
+pub const MAX_MATCHES: usize = 100;
+
+/// Why a mask or the excluded letters can't be searched with
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PatternError {
+    /// The mask has no character
+    Empty,
+    /// An excluded character isn't a letter
+    NotALetter(char),
+    /// A letter is both shown and excluded
+    ShownAndExcluded(char),
+}
+
+impl fmt::Display for PatternError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            PatternError::Empty => write!(f, "the mask is empty, type _ for each hidden letter"),
+            PatternError::NotALetter(c) => write!(f, "'{}' can't be excluded, it isn't a letter", c),
+            PatternError::ShownAndExcluded(c) => write!(f, "{} is shown in the mask and excluded", c),
+        }
+    }
+}
+
+impl std::error::Error for PatternError {}
+
+/// A board to find the words of: what each place shows, and the letters that
+/// aren't in the word
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Pattern {
+    // Some(character) where it's shown, None for a hidden letter
+    slots: Vec<Option<char>>,
+    excluded: Vec<char>,
+    ignore_accents: bool,
+    // The digraph units the words are split into (see digraphs.rs)
+    digraphs: Vec<char>,
+    // What's hidden besides letters (see charset.rs)
+    charset: Charset,
+}
+
+impl Pattern {
+    /// The pattern of a board made of `slots` (None where a letter is hidden)
+    pub fn new(slots: Vec<Option<char>>, excluded: Vec<char>, ignore_accents: bool, digraphs: Vec<char>) -> Self {
+        Pattern { slots, excluded, ignore_accents, digraphs, charset: Charset::default() }
+    }
+
+    /// The same board with the digits and symbols of `charset` hidden like letters
+    pub fn with_charset(mut self, charset: Charset) -> Self {
+        self.charset = charset;
+        self
+    }
+
+    /// Read a typed `mask` and `excluded` letters (spaces and commas between them are
+    /// ignored), with the digraphs of `digraphs` typed as their letters
+    pub fn parse(mask: &str, excluded: &str, ignore_accents: bool, digraphs: &[char]) -> Result<Self, PatternError> {
+        let tokens = tokenize(&mask.trim().to_uppercase(), digraphs);
+        if tokens.is_empty() {
+            return Err(PatternError::Empty);
+        }
+        // "_ A _" as the board is drawn: every other character is a separator
+        let spaced = tokens.len() > 1 && tokens.iter().skip(1).step_by(2).all(|&c| c == ' ');
+        let slots: Vec<Option<char>> = tokens
+            .into_iter()
+            .step_by(if spaced { 2 } else { 1 })
+            .map(|c| (c != HIDDEN).then_some(c))
+            .collect();
+
+        let mut letters = Vec::new();
+        for c in tokenize(&excluded.to_uppercase(), digraphs) {
+            if c.is_whitespace() || c == ',' {
+                continue;
+            }
+            if !is_guessable(c) {
+                return Err(PatternError::NotALetter(c));
+            }
+            if slots.iter().flatten().any(|&shown| same_letter(shown, c, ignore_accents)) {
+                return Err(PatternError::ShownAndExcluded(to_upper(c)));
+            }
+            letters.push(c);
+        }
+        Ok(Pattern::new(slots, letters, ignore_accents, digraphs.to_vec()))
+    }
+
+    /// Whether a letter can be in a hidden place: not excluded, not shown elsewhere
+    fn may_hide(&self, c: char) -> bool {
+        self.charset.is_guessable(c)
+            && !self
+                .excluded
+                .iter()
+                .chain(self.slots.iter().flatten())
+                .any(|&known| same_letter(known, c, self.ignore_accents))
+    }
+
+    /// Whether a word can have `c` in a place showing `shown`
+    fn fits(&self, shown: Option<char>, c: char) -> bool {
+        match shown {
+            Some(shown) if self.charset.is_guessable(shown) => same_letter(c, shown, self.ignore_accents),
+            Some(shown) => c == shown,
+            None => self.may_hide(c),
+        }
+    }
+
+    /// Whether `word` fits the board
+    pub fn matches(&self, word: &str) -> bool {
+        let tokens = tokenize(word, &self.digraphs);
+        tokens.len() == self.slots.len() && tokens.into_iter().zip(&self.slots).all(|(c, &shown)| self.fits(shown, c))
+    }
+
+    /// The words of `words` that fit the board, in the list's order
+    pub fn search<'a>(&self, words: &'a WordList) -> Vec<&'a str> {
+        if !self.digraphs.is_empty() {
+            return words.words().iter().map(String::as_str).filter(|word| self.matches(word)).collect();
+        }
+        words
+            .trie()
+            .matching(self.slots.len(), |place, c| self.fits(self.slots[place], c))
+            .into_iter()
+            .map(|i| words.words()[i].as_str())
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::digraphs;
+
+    fn words() -> WordList {
+        WordList::from_text("hangman\nbatsman\ncaveman\nrust\nruby\nice cream").unwrap()
+    }
+
+    fn search(mask: &str, excluded: &str) -> Vec<String> {
+        let pattern = Pattern::parse(mask, excluded, false, &[]).unwrap();
+        pattern.search(&words()).into_iter().map(String::from).collect()
+    }
+
+    #[test]
+    fn test_masks_as_drawn_or_typed() {
+        assert_eq!(search("_ A _ _ M A _", ""), ["HANGMAN", "BATSMAN", "CAVEMAN"]);
+        assert_eq!(search("_a__ma_", "t"), ["HANGMAN", "CAVEMAN"]);
+        assert_eq!(search("_ A N G M A N", ""), ["HANGMAN"]);
+        // Its first N would be shown too
+        assert!(search("_ A _ G M A N", "").is_empty());
+        assert_eq!(search("_ _ _   _ _ _ _ _", ""), ["ICE CREAM"]);
+        assert_eq!(search("___ _____", ""), ["ICE CREAM"]);
+        let pattern = Pattern::parse("_ _ _ ' _", "", false, &[]).unwrap();
+        assert!(pattern.matches("DON'T") && !pattern.matches("DONUT"));
+        assert!(search("_ _", "").is_empty());
+    }
+
+    #[test]
+    fn test_hidden_letters_arent_shown_or_excluded() {
+        assert_eq!(search("R U _ _", ""), ["RUST", "RUBY"]);
+        assert_eq!(search("R U _ _", "S, T"), ["RUBY"]);
+        // A shown U would be revealed in every place
+        let words = WordList::from_text("tutu\ntuba").unwrap();
+        let pattern = Pattern::parse("_U_U", "", false, &[]).unwrap();
+        assert_eq!(pattern.search(&words), ["TUTU"]);
+        let pattern = Pattern::parse("_U__", "", false, &[]).unwrap();
+        assert_eq!(pattern.search(&words), ["TUBA"]);
+    }
+
+    #[test]
+    fn test_accents_and_digraphs() {
+        let words = WordList::from_text("été\nthé\nllama").unwrap();
+        let pattern = Pattern::parse("E _ E", "", true, &[]).unwrap();
+        assert_eq!(pattern.search(&words), ["ÉTÉ"]);
+        assert!(Pattern::parse("E _ E", "", false, &[]).unwrap().search(&words).is_empty());
+        let ll = digraphs::unit("LL").unwrap();
+        let pattern = Pattern::parse("ll _ _ _", "", false, &[ll]).unwrap();
+        assert_eq!(pattern.search(&words), ["LLAMA"]);
+    }
+
+    #[test]
+    fn test_search_finds_what_a_scan_does() {
+        let words = WordList::builtin();
+        for (mask, excluded) in [("_ _ _ _", "E"), ("_ A _ _", ""), ("___ _____", "S"), ("_ _ _ _ _ _ _", "AEIOU")] {
+            let pattern = Pattern::parse(mask, excluded, true, &[]).unwrap();
+            let scanned: Vec<&str> = words.words().iter().map(String::as_str).filter(|word| pattern.matches(word)).collect();
+            assert_eq!(pattern.search(&words), scanned, "{}", mask);
+        }
+    }
+
+    #[test]
+    fn test_invalid_patterns() {
+        assert_eq!(Pattern::parse("  ", "", false, &[]), Err(PatternError::Empty));
+        assert_eq!(Pattern::parse("_A_", "1", false, &[]), Err(PatternError::NotALetter('1')));
+        assert_eq!(Pattern::parse("_A_", "a", false, &[]), Err(PatternError::ShownAndExcluded('A')));
+    }
+}
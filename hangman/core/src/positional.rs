@@ -0,0 +1,228 @@
+// ============================================================================
+// POSITIONAL GAME
+// ============================================================================
+// A variant between hangman and mastermind: the word is hidden like in
+// hangman, but every guess is a letter *and* a place (guess_at('A', 2)), and
+// it's marked like a word game letter (see wordle.rs):
+// - Feedback::Correct: the letter is in that place, which is revealed (only
+//   that one, an A elsewhere in the word stays hidden)
+// - Feedback::Present: not there, but in a place not found yet
+// - Feedback::Absent: not in any place still hidden
+// Present and absent guesses are both wrong guesses, and the game is lost
+// after twice the difficulty's allowance of them (a guess says less than a
+// hangman one). It's won once every place is found.
+//
+// PLACES:
+// Places count from 0, one per character of the word: spaces and punctuation
+// are places too, shown from the start, so "ICE CREAM" has nine. Digraphs
+// aren't split into units here, "LLAMA" is five letters.
+//
+// GAMES:
+// The word is picked from the hangman words like a classic game's (see
+// difficulty.rs). PositionalGame has rules of its own, no GameState: the
+// desktop app plays it in sessions of its own (see sessions.rs), it isn't
+// counted in the statistics or saved.
+
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use sandbox_rng::RngHandle;
+use crate::difficulty::Difficulty;
+use crate::digraphs::spell;
+use crate::game::is_guessable;
+use crate::letters::{normalize_word, same_letter, to_upper};
+use crate::wordle::Feedback;
+use crate::words::WordList;
+
+/// Reasons a positional guess isn't taken
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PositionalError {
+    /// There's no place `index` in a word of `len` characters
+    OutOfRange { index: usize, len: usize },
+    /// A character that isn't a letter
+    NotALetter(char),
+    /// The place is already shown (found, or a space or punctuation)
+    AlreadyShown(usize),
+    /// The letter was already guessed in that place
+    AlreadyGuessed(char, usize),
+    /// The game has already been won or lost
+    GameOver,
+}
+
+impl fmt::Display for PositionalError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            PositionalError::OutOfRange { index, len } => {
+                write!(f, "there's no place {} in a word of {} (they count from 0)", index, len)
+            }
+            PositionalError::NotALetter(c) => write!(f, "{:?} isn't a letter", c),
+            PositionalError::AlreadyShown(index) => write!(f, "place {} is already shown", index),
+            PositionalError::AlreadyGuessed(c, index) => {
+                write!(f, "{} was already guessed in place {}", spell(*c), index)
+            }
+            PositionalError::GameOver => write!(f, "the game is already over"),
+        }
+    }
+}
+
+impl std::error::Error for PositionalError {}
+
+/// A guess and its mark
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PositionalGuess {
+    pub letter: char,
+    pub index: usize,
+    pub feedback: Feedback,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PositionalGame {
+    // Uppercase
+    pub word: String,
+    // Per character of the word, whether it's shown
+    pub revealed: Vec<bool>,
+    // Oldest first
+    pub guesses: Vec<PositionalGuess>,
+    pub wrong_guesses: u32,
+    pub max_wrong_guesses: u32,
+    pub difficulty: Difficulty,
+    // Whether "E" finds "É" too (the settings' ignore_accents)
+    pub ignore_accents: bool,
+    pub won: bool,
+    pub game_over: bool,
+}
+
+impl PositionalGame {
+    pub fn new(word: &str, difficulty: Difficulty) -> Self {
+        let word = normalize_word(word);
+        let revealed = word.chars().map(|c| !is_guessable(c)).collect();
+        PositionalGame {
+            word,
+            revealed,
+            guesses: Vec::new(),
+            wrong_guesses: 0,
+            max_wrong_guesses: difficulty.max_wrong_guesses() * 2,
+            difficulty,
+            ignore_accents: false,
+            won: false,
+            game_over: false,
+        }
+    }
+
+    /// A game with a word of `words` for `difficulty`, drawn from `rng`
+    pub fn pick(words: &WordList, difficulty: Difficulty, rng: &mut RngHandle) -> Self {
+        PositionalGame::new(&difficulty.pick_word(words, rng), difficulty)
+    }
+
+    /// Guess that `letter` is in place `index` (counting from 0)
+    pub fn guess_at(&mut self, letter: char, index: usize) -> Result<Feedback, PositionalError> {
+        if self.game_over {
+            return Err(PositionalError::GameOver);
+        }
+        let len = self.revealed.len();
+        if index >= len {
+            return Err(PositionalError::OutOfRange { index, len });
+        }
+        if !is_guessable(letter) {
+            return Err(PositionalError::NotALetter(letter));
+        }
+        let letter = to_upper(letter);
+        if self.revealed[index] {
+            return Err(PositionalError::AlreadyShown(index));
+        }
+        if self.guesses.iter().any(|guess| guess.index == index && guess.letter == letter) {
+            return Err(PositionalError::AlreadyGuessed(letter, index));
+        }
+
+        let same = |c: char| same_letter(c, letter, self.ignore_accents);
+        let word: Vec<char> = self.word.chars().collect();
+        let feedback = if same(word[index]) {
+            Feedback::Correct
+        } else if word.iter().zip(&self.revealed).any(|(&c, &shown)| !shown && same(c)) {
+            Feedback::Present
+        } else {
+            Feedback::Absent
+        };
+
+        self.guesses.push(PositionalGuess { letter, index, feedback });
+        if feedback == Feedback::Correct {
+            self.revealed[index] = true;
+            self.won = self.revealed.iter().all(|&shown| shown);
+        } else {
+            self.wrong_guesses += 1;
+        }
+        self.game_over = self.won || self.wrong_guesses >= self.max_wrong_guesses;
+        if self.game_over {
+            self.revealed.iter_mut().for_each(|shown| *shown = true);
+        }
+        Ok(feedback)
+    }
+
+    /// Wrong guesses left before the game is lost
+    pub fn remaining(&self) -> u32 {
+        self.max_wrong_guesses.saturating_sub(self.wrong_guesses)
+    }
+
+    /// Each character of the word, None where it's still hidden
+    pub fn board(&self) -> Vec<Option<char>> {
+        self.word.chars().zip(&self.revealed).map(|(c, &shown)| shown.then_some(c)).collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use Feedback::{Absent, Correct, Present};
+
+    #[test]
+    fn test_guesses_are_marked_by_place() {
+        let mut game = PositionalGame::new("banana", Difficulty::Medium);
+        assert_eq!(game.guess_at('a', 1), Ok(Correct));
+        // Only the guessed place is revealed
+        assert_eq!(game.board(), [None, Some('A'), None, None, None, None]);
+        assert_eq!(game.guess_at('A', 0), Ok(Present));
+        assert_eq!(game.guess_at('X', 0), Ok(Absent));
+        assert_eq!(game.wrong_guesses, 2);
+        assert_eq!(game.remaining(), 10);
+
+        assert_eq!(game.guess_at('A', 0), Err(PositionalError::AlreadyGuessed('A', 0)));
+        assert_eq!(game.guess_at('B', 1), Err(PositionalError::AlreadyShown(1)));
+        assert_eq!(game.guess_at('B', 6), Err(PositionalError::OutOfRange { index: 6, len: 6 }));
+        assert_eq!(game.guess_at('7', 0), Err(PositionalError::NotALetter('7')));
+        assert_eq!(game.guesses.len(), 3);
+
+        for (letter, index) in [('B', 0), ('N', 2), ('A', 3), ('N', 4)] {
+            assert_eq!(game.guess_at(letter, index), Ok(Correct));
+        }
+        // The A's left aren't hidden anywhere else but place 5
+        assert_eq!(game.guess_at('N', 5), Ok(Absent));
+        assert_eq!(game.guess_at('A', 5), Ok(Correct));
+        assert!(game.won && game.game_over);
+        assert_eq!(game.guess_at('A', 5), Err(PositionalError::GameOver));
+    }
+
+    #[test]
+    fn test_phrases_and_losing() {
+        let mut game = PositionalGame::new("ice cream", Difficulty::Hard);
+        assert_eq!(game.board()[3], Some(' '));
+        assert_eq!(game.guess_at('E', 3), Err(PositionalError::AlreadyShown(3)));
+
+        for letter in "ZQXJKWVY".chars() {
+            assert_eq!(game.guess_at(letter, 0), Ok(Absent));
+        }
+        assert_eq!(game.wrong_guesses, game.max_wrong_guesses);
+        assert!(game.game_over && !game.won);
+        assert!(game.board().iter().all(Option::is_some));
+    }
+
+    #[test]
+    fn test_accents_and_picking() {
+        let mut game = PositionalGame::new("été", Difficulty::Easy);
+        assert_eq!(game.guess_at('E', 0), Ok(Absent));
+        game.ignore_accents = true;
+        assert_eq!(game.guess_at('E', 2), Ok(Correct));
+
+        let words = WordList::from_text("rust").unwrap();
+        let game = PositionalGame::pick(&words, Difficulty::Hard, &mut RngHandle::seeded(1));
+        assert_eq!((game.word.as_str(), game.max_wrong_guesses), ("RUST", 8));
+    }
+}
@@ -0,0 +1,348 @@
+// ============================================================================
+// POWER-UPS
+// ============================================================================
+// Power-ups aren't bought like helps (see shop.rs), they're earned: the way a
+// profile wins a game puts one in its inventory, where it waits until the
+// player uses it in a later game:
+// - PowerUp::FreezeTimer: stops a timed game's clock for FREEZE_MS
+// - PowerUp::RevealConsonant: shows a consonant of the word, without costing a guess
+// - PowerUp::Shield: the next wrong guess (a letter or a word) costs nothing
+//
+// EARNING:
+// earned() says what a finished game earns, and the profile keeps it when it
+// records the game (see profiles.rs), so only games that count for the
+// player earn anything:
+// - a win without a wrong guess earns a RevealConsonant (a miss a shield took
+//   is still one, see GameState::flawless)
+// - a win on hard earns a Shield
+// - a win of a timed game earns a FreezeTimer
+// An inventory holds at most MAX_HELD of each, more earned are lost.
+//
+// ACTIVATING:
+// Everything is checked here and in the desktop app, never in the frontend:
+// the app checks the game's player holds one, applies it to the session's
+// game, and takes it from the inventory only if it worked (a game without a
+// clock can't be frozen, a shield can't be raised twice).
+//
+// POWER-UPS AND THE REST OF THE GAME:
+// Like helps, power-ups used are kept in `power_ups_used` and aren't moves:
+// the moves before one can't be undone. A raised shield is `shield` until a
+// miss takes it down (the move keeps that it did, and undoing the move raises
+// it again). A frozen clock runs again at `frozen_until` (Unix ms): the
+// deadline moves back by FREEZE_MS and remaining_ms() stays put until then.
+
+use sandbox_rng::SliceRandom;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use crate::difficulty::Difficulty;
+use crate::game::{now_ms, GameState};
+use crate::letters::{fold, same_letter};
+use crate::shop::is_vowel;
+
+/// How long a FreezeTimer stops the clock
+pub const FREEZE_MS: u64 = 10_000;
+
+/// Most power-ups of each kind an inventory holds
+pub const MAX_HELD: u32 = 3;
+
+/// What a profile can earn and use in a game
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum PowerUp {
+    FreezeTimer,
+    RevealConsonant,
+    Shield,
+}
+
+/// Every power-up, in the order the inventory shows them
+pub const POWER_UPS: [PowerUp; 3] = [PowerUp::FreezeTimer, PowerUp::RevealConsonant, PowerUp::Shield];
+
+impl PowerUp {
+    pub fn name(self) -> &'static str {
+        match self {
+            PowerUp::FreezeTimer => "Freeze Timer",
+            PowerUp::RevealConsonant => "Reveal a Consonant",
+            PowerUp::Shield => "Shield",
+        }
+    }
+
+    /// What it does, and how it's earned
+    pub fn description(self) -> &'static str {
+        match self {
+            PowerUp::FreezeTimer => "Stops the clock of a timed game for 10 seconds. Earned by winning a timed game.",
+            PowerUp::RevealConsonant => "Shows a consonant of the word, without costing a guess. Earned by winning without a wrong guess.",
+            PowerUp::Shield => "The next wrong guess costs nothing. Earned by winning on hard.",
+        }
+    }
+}
+
+/// Reasons a power-up can't be used
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PowerUpError {
+    /// The inventory has none of it
+    NoneHeld(PowerUp),
+    /// The game has already been won or lost
+    GameOver,
+    /// The game is paused (see GameState::pause)
+    Paused,
+    /// The power-up has nothing to do in this game (no clock to freeze or
+    /// it's frozen already, every consonant shown, a shield already up)
+    Unavailable(PowerUp),
+}
+
+impl fmt::Display for PowerUpError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            PowerUpError::NoneHeld(power_up) => write!(f, "you have no {} left", power_up.name()),
+            PowerUpError::GameOver => write!(f, "the game is already over"),
+            PowerUpError::Paused => write!(f, "the game is paused"),
+            PowerUpError::Unavailable(PowerUp::FreezeTimer) => write!(f, "only a running clock can be frozen"),
+            PowerUpError::Unavailable(PowerUp::RevealConsonant) => write!(f, "every consonant of the word is already shown"),
+            PowerUpError::Unavailable(PowerUp::Shield) => write!(f, "a shield is already up"),
+        }
+    }
+}
+
+impl std::error::Error for PowerUpError {}
+
+/// The power-ups a profile holds
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(default)]
+pub struct Inventory {
+    pub freeze_timer: u32,
+    pub reveal_consonant: u32,
+    pub shield: u32,
+}
+
+impl Inventory {
+    pub fn held(&self, power_up: PowerUp) -> u32 {
+        match power_up {
+            PowerUp::FreezeTimer => self.freeze_timer,
+            PowerUp::RevealConsonant => self.reveal_consonant,
+            PowerUp::Shield => self.shield,
+        }
+    }
+
+    fn held_mut(&mut self, power_up: PowerUp) -> &mut u32 {
+        match power_up {
+            PowerUp::FreezeTimer => &mut self.freeze_timer,
+            PowerUp::RevealConsonant => &mut self.reveal_consonant,
+            PowerUp::Shield => &mut self.shield,
+        }
+    }
+
+    /// Keep one more, false if MAX_HELD are held already
+    pub fn add(&mut self, power_up: PowerUp) -> bool {
+        let held = self.held_mut(power_up);
+        if *held >= MAX_HELD {
+            return false;
+        }
+        *held += 1;
+        true
+    }
+
+    /// Whether there's one to use
+    pub fn check(&self, power_up: PowerUp) -> Result<(), PowerUpError> {
+        if self.held(power_up) == 0 {
+            return Err(PowerUpError::NoneHeld(power_up));
+        }
+        Ok(())
+    }
+
+    /// Use one up, or fail without changing anything
+    pub fn take(&mut self, power_up: PowerUp) -> Result<(), PowerUpError> {
+        self.check(power_up)?;
+        *self.held_mut(power_up) -= 1;
+        Ok(())
+    }
+}
+
+/// The power-ups a finished game earns its player
+pub fn earned(game: &GameState) -> Vec<PowerUp> {
+    if !game.won {
+        return Vec::new();
+    }
+    let mut earned = Vec::new();
+    if game.deadline.is_some() {
+        earned.push(PowerUp::FreezeTimer);
+    }
+    if game.flawless() {
+        earned.push(PowerUp::RevealConsonant);
+    }
+    if game.difficulty == Difficulty::Hard {
+        earned.push(PowerUp::Shield);
+    }
+    earned
+}
+
+impl GameState {
+    /// Use a power-up (taking it from the inventory is the caller's job)
+    /// Returns the letter it revealed, None for the others
+    pub fn activate_powerup(&mut self, power_up: PowerUp) -> Result<Option<char>, PowerUpError> {
+        if self.check_timer() || self.game_over {
+            return Err(PowerUpError::GameOver);
+        }
+        if self.is_paused() {
+            return Err(PowerUpError::Paused);
+        }
+        let letter = match power_up {
+            PowerUp::FreezeTimer => {
+                if self.deadline.is_none() || self.is_frozen() {
+                    return Err(PowerUpError::Unavailable(power_up));
+                }
+                self.frozen_until = Some(now_ms() + FREEZE_MS);
+                self.deadline = self.deadline.map(|deadline| deadline + FREEZE_MS);
+                None
+            }
+            PowerUp::RevealConsonant => {
+                let mut consonants: Vec<char> = self
+                    .tokens()
+                    .into_iter()
+                    .filter(|&c| c.is_alphabetic() && !is_vowel(c) && !self.is_guessed(c))
+                    .collect();
+                consonants.sort_unstable_by_key(|&c| (fold(c, self.ignore_accents), c));
+                consonants.dedup_by(|a, b| same_letter(*a, *b, self.ignore_accents));
+                let letter = *consonants.choose(&mut self.rng).ok_or(PowerUpError::Unavailable(power_up))?;
+                if let Some(candidates) = &mut self.candidates {
+                    self.word = candidates.reveal(letter, self.ignore_accents).to_string();
+                }
+                self.guessed_letters.push(letter);
+                Some(letter)
+            }
+            PowerUp::Shield => {
+                if self.shield {
+                    return Err(PowerUpError::Unavailable(power_up));
+                }
+                self.shield = true;
+                None
+            }
+        };
+        self.power_ups_used.push(power_up);
+        self.undo_floor = self.history.len();
+        if self.check_win() {
+            self.end(true);
+        }
+        Ok(letter)
+    }
+
+    /// Whether a FreezeTimer is holding the clock right now
+    pub fn is_frozen(&self) -> bool {
+        self.frozen_ms() > 0
+    }
+
+    /// Milliseconds until a frozen clock runs again, 0 if it isn't frozen
+    pub fn frozen_ms(&self) -> u64 {
+        if self.game_over {
+            return 0;
+        }
+        let now = self.paused_at.unwrap_or_else(now_ms);
+        self.frozen_until.map_or(0, |until| until.saturating_sub(now))
+    }
+
+    /// Take the shield down for a miss, returns whether it was up
+    pub(crate) fn use_shield(&mut self) -> bool {
+        std::mem::take(&mut self.shield)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::history::Action;
+    use sandbox_rng::RngHandle;
+
+    fn new_game(word: &str) -> GameState {
+        let mut game = GameState::new(word.to_string(), None, Difficulty::Medium);
+        game.rng = RngHandle::seeded(1);
+        game
+    }
+
+    #[test]
+    fn test_inventory() {
+        let mut inventory = Inventory::default();
+        assert_eq!(inventory.take(PowerUp::Shield), Err(PowerUpError::NoneHeld(PowerUp::Shield)));
+        for _ in 0..MAX_HELD {
+            assert!(inventory.add(PowerUp::Shield));
+        }
+        assert!(!inventory.add(PowerUp::Shield));
+        inventory.take(PowerUp::Shield).unwrap();
+        assert_eq!((inventory.held(PowerUp::Shield), inventory.held(PowerUp::FreezeTimer)), (MAX_HELD - 1, 0));
+    }
+
+    #[test]
+    fn test_earning() {
+        let mut game = GameState::new("RUST".to_string(), None, Difficulty::Hard);
+        game.start_timer(60);
+        game.guess_word("RUST");
+        assert_eq!(earned(&game), POWER_UPS);
+
+        let mut game = new_game("RUST");
+        game.guess_letter('X');
+        game.guess_word("RUST");
+        assert!(earned(&game).is_empty());
+        let mut lost = new_game("RUST");
+        lost.give_up();
+        assert!(earned(&lost).is_empty());
+
+        // A miss a shield took still costs the flawless win
+        let mut shielded = new_game("RUST");
+        shielded.activate_powerup(PowerUp::Shield).unwrap();
+        shielded.guess_letter('X');
+        shielded.guess_word("RUST");
+        assert_eq!(shielded.wrong_guesses, 0);
+        assert!(!shielded.flawless() && earned(&shielded).is_empty());
+    }
+
+    #[test]
+    fn test_reveal_consonant() {
+        let mut game = new_game("AUTO");
+        assert_eq!(game.activate_powerup(PowerUp::RevealConsonant), Ok(Some('T')));
+        assert_eq!(game.wrong_guesses, 0);
+        assert_eq!(
+            game.activate_powerup(PowerUp::RevealConsonant),
+            Err(PowerUpError::Unavailable(PowerUp::RevealConsonant))
+        );
+        assert_eq!(game.power_ups_used, [PowerUp::RevealConsonant]);
+    }
+
+    #[test]
+    fn test_shield_takes_one_miss() {
+        let mut game = new_game("RUST");
+        game.activate_powerup(PowerUp::Shield).unwrap();
+        assert_eq!(game.activate_powerup(PowerUp::Shield), Err(PowerUpError::Unavailable(PowerUp::Shield)));
+        assert!(game.guess_letter('R'));
+        assert!(game.shield);
+        // The miss costs nothing, and is still a miss
+        assert!(!game.guess_letter('X'));
+        assert_eq!((game.wrong_guesses, game.shield), (0, false));
+        assert!(!game.history.last().unwrap().hit);
+        game.guess_word("RUBY");
+        assert_eq!(game.wrong_guesses, 2);
+
+        // Undoing the shielded miss raises the shield again
+        game.undo().unwrap();
+        assert_eq!(game.undo(), Ok(Action::Letter('X')));
+        assert!(game.shield);
+        game.guess_word("RUBY");
+        assert_eq!((game.wrong_guesses, game.shield), (0, false));
+    }
+
+    #[test]
+    fn test_freeze_timer() {
+        let mut game = new_game("RUST");
+        assert_eq!(game.activate_powerup(PowerUp::FreezeTimer), Err(PowerUpError::Unavailable(PowerUp::FreezeTimer)));
+        game.start_timer(30);
+        game.activate_powerup(PowerUp::FreezeTimer).unwrap();
+        assert!(game.is_frozen());
+        // The clock doesn't move while frozen
+        let left = game.remaining_ms().unwrap();
+        assert!(left > 29_000 && left <= 30_000);
+        assert_eq!(game.activate_powerup(PowerUp::FreezeTimer), Err(PowerUpError::Unavailable(PowerUp::FreezeTimer)));
+
+        game.frozen_until = Some(1);
+        assert!(!game.is_frozen());
+        game.give_up();
+        assert_eq!(game.activate_powerup(PowerUp::Shield), Err(PowerUpError::GameOver));
+    }
+}
@@ -0,0 +1,329 @@
+// ============================================================================
+// PLAYER PROFILES AND LEADERBOARD
+// ============================================================================
+// Several people can share one copy of the game. Each picks a named profile,
+// and finished games count towards that profile's statistics, high scores,
+// and running score total (game scores are worked out in score.rs). Wins
+// also earn points for the profile's wallet, spent on helps (see shop.rs),
+// and power-ups for its inventory (see powerups.rs). Races against the AI
+// move the profile's rating on their difficulty (see elo.rs).
+// A profile that completed the campaign keeps how long it took (see
+// campaign.rs).
+//
+// SAVING:
+// All profiles are one sandbox-store document (key PROFILES_KEY), saved next
+// to the overall statistics. The desktop app keeps them in its database's
+// profiles table instead, one row each (see storage.rs).
+
+use std::fmt;
+use sandbox_store::{Schema, Store, StoreError};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use crate::elo::Ratings;
+use crate::game::{now_ms, GameState};
+use crate::powerups::{earned, Inventory};
+use crate::shop::{points_for, Wallet};
+use crate::stats::Statistics;
+
+/// Store key the profiles are saved under
+pub const PROFILES_KEY: &str = "profiles";
+
+/// Longest profile name (so it fits on the leaderboard)
+pub const MAX_NAME_LEN: usize = 20;
+
+/// High scores kept per profile
+pub const MAX_HIGH_SCORES: usize = 5;
+
+/// Reasons a profile can't be created or selected
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ProfileError {
+    /// The name is empty (or only whitespace)
+    EmptyName,
+    /// The name has more than MAX_NAME_LEN characters
+    NameTooLong,
+    /// A profile with this name already exists (names ignore case)
+    AlreadyExists(String),
+    /// There is no profile with this name
+    NotFound(String),
+}
+
+impl fmt::Display for ProfileError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ProfileError::EmptyName => write!(f, "enter a profile name"),
+            ProfileError::NameTooLong => {
+                write!(f, "profile names can be at most {} characters", MAX_NAME_LEN)
+            }
+            ProfileError::AlreadyExists(name) => write!(f, "a profile named {} already exists", name),
+            ProfileError::NotFound(name) => write!(f, "no profile named {}", name),
+        }
+    }
+}
+
+impl std::error::Error for ProfileError {}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+pub struct Profile {
+    pub name: String,
+    pub stats: Statistics,
+    // Best scores first, at most MAX_HIGH_SCORES
+    pub high_scores: Vec<u32>,
+    // Sum of every finished game's score
+    #[serde(default)]
+    pub total_score: u64,
+    #[serde(default)]
+    pub wallet: Wallet,
+    // Power-ups earned and not used yet
+    #[serde(default)]
+    pub power_ups: Inventory,
+    // How long the campaign took, in ms (None until it's completed)
+    #[serde(default)]
+    pub campaign_ms: Option<u64>,
+    // Ratings against the AI, per difficulty
+    #[serde(default)]
+    pub ratings: Ratings,
+}
+
+impl Profile {
+    pub fn best_score(&self) -> u32 {
+        self.high_scores.first().copied().unwrap_or(0)
+    }
+
+    fn record(&mut self, game: &GameState) {
+        self.stats.record(game);
+        let score = game.score.map_or(0, |score| score.total);
+        self.total_score += u64::from(score);
+        self.wallet.earn(points_for(game));
+        for power_up in earned(game) {
+            self.power_ups.add(power_up);
+        }
+        self.ratings.record(game, now_ms());
+        if score > 0 {
+            self.high_scores.push(score);
+            self.high_scores.sort_unstable_by(|a, b| b.cmp(a));
+            self.high_scores.truncate(MAX_HIGH_SCORES);
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+pub struct Profiles {
+    // In the order they were created
+    pub profiles: Vec<Profile>,
+    // Name of the selected profile
+    pub current: Option<String>,
+}
+
+impl Schema for Profiles {
+    const VERSION: u32 = 1;
+}
+
+impl Profiles {
+    /// Load saved profiles, starting with none if nothing was saved yet
+    pub fn load(store: &Store) -> Result<Self, StoreError> {
+        Ok(store.load(PROFILES_KEY)?.unwrap_or_default())
+    }
+
+    /// Save the profiles, replacing the previous copy
+    pub fn save(&self, store: &Store) -> Result<(), StoreError> {
+        store.save(PROFILES_KEY, self)
+    }
+
+    /// Add a new profile (surrounding whitespace is trimmed from the name)
+    /// The first profile created is selected automatically
+    pub fn create(&mut self, name: &str) -> Result<&Profile, ProfileError> {
+        let name = name.trim();
+        if name.is_empty() {
+            return Err(ProfileError::EmptyName);
+        }
+        if name.chars().count() > MAX_NAME_LEN {
+            return Err(ProfileError::NameTooLong);
+        }
+        if let Some(existing) = self.get(name) {
+            return Err(ProfileError::AlreadyExists(existing.name.clone()));
+        }
+
+        self.profiles.push(Profile {
+            name: name.to_string(),
+            ..Profile::default()
+        });
+        if self.current.is_none() {
+            self.current = Some(name.to_string());
+        }
+        Ok(self.profiles.last().unwrap())
+    }
+
+    /// Make `name` the profile new games are played by
+    pub fn select(&mut self, name: &str) -> Result<&Profile, ProfileError> {
+        let index = self
+            .index_of(name)
+            .ok_or_else(|| ProfileError::NotFound(name.trim().to_string()))?;
+        self.current = Some(self.profiles[index].name.clone());
+        Ok(&self.profiles[index])
+    }
+
+    /// Look up a profile by name (ignoring case and surrounding whitespace)
+    pub fn get(&self, name: &str) -> Option<&Profile> {
+        self.index_of(name).map(|index| &self.profiles[index])
+    }
+
+    pub fn current(&self) -> Option<&Profile> {
+        self.current.as_deref().and_then(|name| self.get(name))
+    }
+
+    /// The profile with this name, to change it
+    pub fn get_mut(&mut self, name: &str) -> Option<&mut Profile> {
+        self.index_of(name).map(move |index| &mut self.profiles[index])
+    }
+
+    /// Count a finished game for the profile it was played by
+    /// Games without a player, or whose profile no longer exists, are ignored
+    pub fn record(&mut self, game: &GameState) {
+        if !game.game_over {
+            return;
+        }
+        if let Some(index) = game.player.as_deref().and_then(|name| self.index_of(name)) {
+            self.profiles[index].record(game);
+        }
+    }
+
+    /// Keep how long the campaign took `name`, if it's faster than before
+    /// Returns false (and keeps nothing) when it isn't, or there's no such profile
+    pub fn record_campaign(&mut self, name: &str, time_ms: u64) -> bool {
+        let Some(profile) = self.get_mut(name) else {
+            return false;
+        };
+        if profile.campaign_ms.is_some_and(|best| best <= time_ms) {
+            return false;
+        }
+        profile.campaign_ms = Some(time_ms);
+        true
+    }
+
+    /// Profiles ranked by best score, then wins, then name
+    pub fn leaderboard(&self) -> Vec<&Profile> {
+        let mut ranked: Vec<&Profile> = self.profiles.iter().collect();
+        ranked.sort_by(|a, b| {
+            b.best_score()
+                .cmp(&a.best_score())
+                .then(b.stats.wins.cmp(&a.stats.wins))
+                .then_with(|| a.name.to_lowercase().cmp(&b.name.to_lowercase()))
+        });
+        ranked
+    }
+
+    fn index_of(&self, name: &str) -> Option<usize> {
+        let name = name.trim().to_lowercase();
+        self.profiles.iter().position(|profile| profile.name.to_lowercase() == name)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::difficulty::Difficulty;
+    use crate::elo::STARTING_RATING;
+    use crate::race::Race;
+
+    fn won_game(player: &str, word: &str, difficulty: Difficulty) -> GameState {
+        let mut game = GameState::new(word.to_string(), None, difficulty);
+        game.player = Some(player.to_string());
+        game.guess_word(word);
+        game
+    }
+
+    #[test]
+    fn test_create_and_select() {
+        let mut profiles = Profiles::default();
+        profiles.create("  Ada ").unwrap();
+        profiles.create("Grace").unwrap();
+        // The first profile is selected automatically
+        assert_eq!(profiles.current().unwrap().name, "Ada");
+
+        assert_eq!(profiles.select("grace").unwrap().name, "Grace");
+        assert_eq!(profiles.current.as_deref(), Some("Grace"));
+        assert_eq!(profiles.select("Linus"), Err(ProfileError::NotFound("Linus".to_string())));
+    }
+
+    #[test]
+    fn test_invalid_names() {
+        let mut profiles = Profiles::default();
+        profiles.create("Ada").unwrap();
+        assert_eq!(profiles.create("   ").unwrap_err(), ProfileError::EmptyName);
+        assert_eq!(profiles.create(&"a".repeat(MAX_NAME_LEN + 1)).unwrap_err(), ProfileError::NameTooLong);
+        assert_eq!(profiles.create("ADA").unwrap_err(), ProfileError::AlreadyExists("Ada".to_string()));
+    }
+
+    #[test]
+    fn test_record_and_leaderboard() {
+        let mut profiles = Profiles::default();
+        profiles.create("Ada").unwrap();
+        profiles.create("Grace").unwrap();
+
+        profiles.record(&won_game("Ada", "RUST", Difficulty::Easy));
+        profiles.record(&won_game("grace", "RUST", Difficulty::Hard));
+        profiles.record(&won_game("Grace", "GO", Difficulty::Easy));
+        // Unknown or missing players are ignored
+        profiles.record(&won_game("Linus", "RUST", Difficulty::Hard));
+        profiles.record(&GameState::new("RUST".to_string(), None, Difficulty::Easy));
+
+        let grace = profiles.get("Grace").unwrap();
+        assert_eq!(grace.stats.wins, 2);
+        // RUST and GO are both hard words, worth a rating bonus of 20
+        assert_eq!(grace.high_scores, vec![(40 + 20 + 20) * 2 * 3, 20 + 40 + 20]);
+        assert_eq!(grace.total_score, 480 + 80);
+        assert_eq!(grace.wallet.points, 15 + 5);
+        // Two wins without a wrong guess, one of them on hard
+        assert_eq!((grace.power_ups.reveal_consonant, grace.power_ups.shield), (2, 1));
+
+        let ranked: Vec<&str> = profiles.leaderboard().iter().map(|p| p.name.as_str()).collect();
+        assert_eq!(ranked, ["Grace", "Ada"]);
+    }
+
+    #[test]
+    fn test_race_ratings() {
+        let mut profiles = Profiles::default();
+        profiles.create("Ada").unwrap();
+        let mut game = won_game("Ada", "RUST", Difficulty::Medium);
+        profiles.record(&game);
+        game.race = Some(Race::new(&game).unwrap());
+        profiles.record(&game);
+
+        let ratings = &profiles.get("Ada").unwrap().ratings;
+        assert_eq!(ratings.points(Difficulty::Medium).len(), 1);
+        assert_eq!(ratings.rating(Difficulty::Medium), STARTING_RATING + 16);
+    }
+
+    #[test]
+    fn test_high_scores_are_capped() {
+        let mut profiles = Profiles::default();
+        profiles.create("Ada").unwrap();
+        for _ in 0..MAX_HIGH_SCORES + 2 {
+            profiles.record(&won_game("Ada", "RUST", Difficulty::Easy));
+        }
+        assert_eq!(profiles.get("Ada").unwrap().high_scores.len(), MAX_HIGH_SCORES);
+    }
+
+    #[test]
+    fn test_campaign_time() {
+        let mut profiles = Profiles::default();
+        profiles.create("Ada").unwrap();
+        assert!(profiles.record_campaign("ada", 60_000));
+        assert!(!profiles.record_campaign("Ada", 90_000));
+        assert!(profiles.record_campaign("Ada", 45_000));
+        assert!(!profiles.record_campaign("Linus", 1_000));
+        assert_eq!(profiles.get("Ada").unwrap().campaign_ms, Some(45_000));
+    }
+
+    #[test]
+    fn test_save_and_load() {
+        let dir = tempfile::tempdir().unwrap();
+        let store = Store::open(dir.path()).unwrap();
+        let mut profiles = Profiles::default();
+        profiles.create("Ada").unwrap();
+        profiles.record(&won_game("Ada", "RUST", Difficulty::Medium));
+        profiles.save(&store).unwrap();
+
+        assert_eq!(Profiles::load(&store).unwrap(), profiles);
+    }
+}
@@ -0,0 +1,319 @@
+// ============================================================================
+// WORD PROVIDERS
+// ============================================================================
+// Where the words of new games come from. Every source is a WordProvider,
+// and the frontends keep them in a WordProviders registry, so a new source
+// is one more provider registered rather than another branch in every
+// command that starts a game. The word_source setting picks one of them
+// (see settings.rs):
+// - "bundled": StaticProvider, a list loaded once, the main word list files
+//   (see words.rs)
+// - "file": FileProvider, the word_file list, read again for every game so
+//   edits show up without a restart
+// - "online": OnlineProvider, the words fetched from the word API so far
+//   (see online_words.rs)
+// - "daily": DailyProvider, DAILY_POOL words of a list drawn with the day's
+//   seed (see daily.rs), the same for everyone today and new tomorrow
+//
+// HEALTH CHECKS:
+// check() says whether a provider can give words right now, and how many:
+// a file that can't be read, a word API that nothing was fetched from yet.
+// The desktop app sends every registered provider's (get_word_providers).
+//
+// FALLBACKS:
+// WordProviders::words asks the selected provider and, if it fails (or was
+// never registered), the bundled one, FALLBACK. Served says which one gave
+// the words and why the selected one didn't, so the fallback can be logged
+// and shown. Only a missing or failing bundled provider is an error.
+
+use sandbox_rng::{daily_seed, RngHandle, SliceRandom};
+use std::fmt;
+use std::path::PathBuf;
+use std::time::SystemTime;
+use crate::online_words::{OnlineWords, WordSource};
+use crate::words::WordList;
+
+/// The provider asked when the selected one fails
+pub const FALLBACK: WordSource = WordSource::Bundled;
+
+/// Words of the list a daily provider plays with each day
+pub const DAILY_POOL: usize = 20;
+
+/// Why a provider has no words to give
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ProviderError {
+    /// No provider of the source is registered (e.g. "file" without a word_file)
+    NotRegistered(WordSource),
+    /// It works but has no words yet (nothing fetched so far)
+    NoWords,
+    /// Its words can't be had (a file that can't be read, or isn't a word list)
+    Unavailable(String),
+}
+
+impl fmt::Display for ProviderError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ProviderError::NotRegistered(source) => write!(f, "no {} word provider is set up", source),
+            ProviderError::NoWords => write!(f, "it has no words yet"),
+            ProviderError::Unavailable(message) => write!(f, "{}", message),
+        }
+    }
+}
+
+impl std::error::Error for ProviderError {}
+
+/// What a provider's health check found
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ProviderHealth {
+    pub source: WordSource,
+    // How many words it has (0 when it fails)
+    pub words: usize,
+    // Why it can't give words, None if it can
+    pub error: Option<ProviderError>,
+}
+
+impl ProviderHealth {
+    /// Whether the provider can give words
+    pub fn is_healthy(&self) -> bool {
+        self.error.is_none()
+    }
+}
+
+/// A source of words for new games
+pub trait WordProvider: Send + Sync {
+    /// The word_source setting that selects it
+    fn source(&self) -> WordSource;
+
+    /// The words to pick from right now
+    fn words(&self) -> Result<WordList, ProviderError>;
+
+    /// Whether it can give words, and how many
+    fn check(&self) -> ProviderHealth {
+        let (words, error) = match self.words() {
+            Ok(list) => (list.words().len(), None),
+            Err(err) => (0, Some(err)),
+        };
+        ProviderHealth { source: self.source(), words, error }
+    }
+}
+
+/// A list that doesn't change, e.g. the word list files loaded at start
+#[derive(Debug, Clone)]
+pub struct StaticProvider {
+    source: WordSource,
+    list: WordList,
+}
+
+impl StaticProvider {
+    /// The provider of `source` giving `list`
+    pub fn new(source: WordSource, list: WordList) -> Self {
+        StaticProvider { source, list }
+    }
+
+    /// The bundled provider, giving `list`
+    pub fn bundled(list: WordList) -> Self {
+        StaticProvider::new(WordSource::Bundled, list)
+    }
+}
+
+impl WordProvider for StaticProvider {
+    fn source(&self) -> WordSource {
+        self.source
+    }
+
+    fn words(&self) -> Result<WordList, ProviderError> {
+        Ok(self.list.clone())
+    }
+}
+
+/// A word list file, read for every game (text or JSON, see WordList::from_file)
+#[derive(Debug, Clone)]
+pub struct FileProvider {
+    path: PathBuf,
+}
+
+impl FileProvider {
+    pub fn new(path: impl Into<PathBuf>) -> Self {
+        FileProvider { path: path.into() }
+    }
+}
+
+impl WordProvider for FileProvider {
+    fn source(&self) -> WordSource {
+        WordSource::File
+    }
+
+    fn words(&self) -> Result<WordList, ProviderError> {
+        WordList::from_file(&self.path)
+            .map_err(|err| ProviderError::Unavailable(format!("{}: {}", self.path.display(), err)))
+    }
+}
+
+/// The words fetched from the word API (the desktop app registers it again after each fetch)
+#[derive(Debug, Clone)]
+pub struct OnlineProvider {
+    fetched: OnlineWords,
+}
+
+impl OnlineProvider {
+    pub fn new(fetched: OnlineWords) -> Self {
+        OnlineProvider { fetched }
+    }
+}
+
+impl WordProvider for OnlineProvider {
+    fn source(&self) -> WordSource {
+        WordSource::Online
+    }
+
+    fn words(&self) -> Result<WordList, ProviderError> {
+        self.fetched.list().ok_or(ProviderError::NoWords)
+    }
+}
+
+/// DAILY_POOL words of a list, drawn with the day's seed
+#[derive(Debug, Clone)]
+pub struct DailyProvider {
+    list: WordList,
+}
+
+impl DailyProvider {
+    pub fn new(list: WordList) -> Self {
+        DailyProvider { list }
+    }
+
+    /// The words of the day `time` falls on
+    pub fn words_on(&self, time: SystemTime) -> Result<WordList, ProviderError> {
+        let mut words = self.list.words().to_vec();
+        words.shuffle(&mut RngHandle::seeded(daily_seed(time)));
+        words.truncate(DAILY_POOL);
+        self.list.derive(words).map_err(|err| ProviderError::Unavailable(err.to_string()))
+    }
+}
+
+impl WordProvider for DailyProvider {
+    fn source(&self) -> WordSource {
+        WordSource::Daily
+    }
+
+    fn words(&self) -> Result<WordList, ProviderError> {
+        self.words_on(SystemTime::now())
+    }
+}
+
+/// The words a registry gave, and where from
+#[derive(Debug, Clone)]
+pub struct Served {
+    // The provider that gave them, FALLBACK if the selected one failed
+    pub source: WordSource,
+    pub words: WordList,
+    // Why the selected provider didn't give them (None if it did)
+    pub fallback: Option<ProviderError>,
+}
+
+/// The providers new games can get their words from, one per source
+#[derive(Default)]
+pub struct WordProviders {
+    providers: Vec<Box<dyn WordProvider>>,
+}
+
+impl WordProviders {
+    /// Add `provider`, replacing the one of its source
+    pub fn register(&mut self, provider: impl WordProvider + 'static) {
+        self.remove(provider.source());
+        self.providers.push(Box::new(provider));
+    }
+
+    /// Take out the provider of `source`
+    pub fn remove(&mut self, source: WordSource) {
+        self.providers.retain(|provider| provider.source() != source);
+    }
+
+    /// The provider of `source`
+    pub fn get(&self, source: WordSource) -> Option<&dyn WordProvider> {
+        self.providers.iter().find(|provider| provider.source() == source).map(|provider| provider.as_ref())
+    }
+
+    /// The words of the provider of `source`, or FALLBACK's if it has none
+    pub fn words(&self, source: WordSource) -> Result<Served, ProviderError> {
+        let words = |source| self.get(source).ok_or(ProviderError::NotRegistered(source))?.words();
+        match words(source) {
+            Ok(words) => Ok(Served { source, words, fallback: None }),
+            Err(err) if source != FALLBACK => Ok(Served {
+                source: FALLBACK,
+                words: words(FALLBACK)?,
+                fallback: Some(err),
+            }),
+            Err(err) => Err(err),
+        }
+    }
+
+    /// Every provider's health check, in the order they were registered
+    pub fn health(&self) -> Vec<ProviderHealth> {
+        self.providers.iter().map(|provider| provider.check()).collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+
+    fn list(text: &str) -> WordList {
+        WordList::from_text(text).unwrap()
+    }
+
+    #[test]
+    fn test_selected_provider_or_fallback() {
+        let mut providers = WordProviders::default();
+        providers.register(StaticProvider::bundled(list("rust\ncargo")));
+        providers.register(OnlineProvider::new(OnlineWords::default()));
+
+        let served = providers.words(WordSource::Bundled).unwrap();
+        assert_eq!((served.source, served.words.words().len(), served.fallback), (WordSource::Bundled, 2, None));
+
+        // Nothing fetched yet, the bundled words are played
+        let served = providers.words(WordSource::Online).unwrap();
+        assert_eq!((served.source, served.fallback), (WordSource::Bundled, Some(ProviderError::NoWords)));
+        let served = providers.words(WordSource::File).unwrap();
+        assert_eq!(served.fallback, Some(ProviderError::NotRegistered(WordSource::File)));
+
+        // Registering again replaces it
+        let mut fetched = OnlineWords::default();
+        fetched.add(["ferris"]);
+        providers.register(OnlineProvider::new(fetched));
+        let served = providers.words(WordSource::Online).unwrap();
+        assert_eq!((served.source, served.words.words()), (WordSource::Online, &["FERRIS".to_string()][..]));
+        assert_eq!(providers.health().len(), 2);
+
+        providers.remove(WordSource::Bundled);
+        assert_eq!(providers.words(WordSource::Bundled).unwrap_err(), ProviderError::NotRegistered(WordSource::Bundled));
+    }
+
+    #[test]
+    fn test_file_provider_reads_every_time() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("words.txt");
+        let provider = FileProvider::new(&path);
+        let health = provider.check();
+        assert!(!health.is_healthy());
+        assert!(matches!(health.error, Some(ProviderError::Unavailable(_))));
+
+        std::fs::write(&path, "rust\n").unwrap();
+        assert_eq!(provider.words().unwrap().words(), ["RUST"]);
+        std::fs::write(&path, "rust\ncargo\n").unwrap();
+        assert_eq!(provider.check(), ProviderHealth { source: WordSource::File, words: 2, error: None });
+    }
+
+    #[test]
+    fn test_daily_provider() {
+        let words: Vec<String> = (b'a'..=b'z').map(|c| format!("{}{}ord", c as char, c as char)).collect();
+        let provider = DailyProvider::new(WordList::new(&words).unwrap());
+        let day = SystemTime::UNIX_EPOCH + Duration::from_secs(20_000 * 86_400);
+        let today = provider.words_on(day).unwrap();
+        assert_eq!(today.words().len(), DAILY_POOL);
+        // Later the same day, the same words, the next day others
+        assert_eq!(provider.words_on(day + Duration::from_secs(3_600)).unwrap().words(), today.words());
+        assert_ne!(provider.words_on(day + Duration::from_secs(86_400)).unwrap().words(), today.words());
+    }
+}
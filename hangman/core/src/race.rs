@@ -0,0 +1,233 @@
+// ============================================================================
+// RACE AGAINST THE AI
+// ============================================================================
+// In a race the player and the AI guesser (see ai.rs) each have a board with
+// the same hidden word, and take turns: every move of the player's (a letter,
+// a word guess, a hint) passes the turn to the AI, which makes one move on
+// its own board and passes it back. Whoever completes the word first wins.
+//
+// THE RACE STATE:
+// The player's game is the session's game, with the race in its `race`: the
+// AI's board (a game of its own, untimed and not counted anywhere) and whose
+// turn it is. A race ends with the player's game:
+// - the player completes the word: the player wins
+// - the AI completes it first: the player's game is lost, the AI wins
+// - the player is hanged: the AI wins
+// An AI that's hanged is out, and the player plays on alone (every turn is
+// theirs); if they're hanged too, the hangman wins.
+//
+// Moves can't be undone in a race (the AI would have moved since). The AI's
+// board is only seen through a RaceView while the race is on (how many
+// letters it found and the ones it guessed), never its word.
+
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use crate::ai::{self, AiMove};
+use crate::game::GameState;
+use crate::words::WordList;
+
+/// Whose turn it is
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum Turn {
+    #[default]
+    Player,
+    Ai,
+}
+
+/// Who won a race
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum RaceWinner {
+    Player,
+    Ai,
+    // Both were hanged
+    Hangman,
+}
+
+/// Reasons a race can't be started, or the AI can't move in it
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RaceError {
+    /// Races are played in the classic mode
+    EvilMode,
+    /// The game isn't a race
+    NotARace,
+    /// The race is over
+    GameOver,
+    /// The game is paused
+    Paused,
+    /// It's the player's turn
+    PlayerTurn,
+}
+
+impl fmt::Display for RaceError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            RaceError::EvilMode => write!(f, "races are played in the classic mode"),
+            RaceError::NotARace => write!(f, "this game isn't a race"),
+            RaceError::GameOver => write!(f, "the race is over"),
+            RaceError::Paused => write!(f, "the game is paused"),
+            RaceError::PlayerTurn => write!(f, "it's the player's turn"),
+        }
+    }
+}
+
+impl std::error::Error for RaceError {}
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct Race {
+    // The AI's board, with the same word
+    pub board: Box<GameState>,
+    pub turn: Turn,
+}
+
+impl Race {
+    /// A race on the word of `game` (a new classic game), the player moving first
+    pub fn new(game: &GameState) -> Result<Self, RaceError> {
+        if game.candidates.is_some() {
+            return Err(RaceError::EvilMode);
+        }
+        let mut board = GameState::new(game.word.clone(), game.category.clone(), game.difficulty);
+        board.max_wrong_guesses = game.max_wrong_guesses;
+        board.ignore_accents = game.ignore_accents;
+        board.alphabet = game.alphabet.clone();
+        board.digraphs = game.digraphs.clone();
+        board.charset = game.charset.clone();
+        Ok(Race {
+            board: Box::new(board),
+            turn: Turn::Player,
+        })
+    }
+
+    /// Letters of the word (counted once per place), and how many of them the AI found
+    pub fn progress(&self) -> (u32, u32) {
+        let letters = self.board.tokens().into_iter().filter(|&c| self.board.is_guessable(c)).count() as u32;
+        let hidden = self.board.mask().chars().filter(|&c| c == '_').count() as u32;
+        (letters, letters - hidden)
+    }
+
+    /// Whether the AI is hanged
+    pub fn ai_out(&self) -> bool {
+        self.board.game_over && !self.board.won
+    }
+}
+
+impl GameState {
+    /// Whether it's the AI's turn in a race that's on
+    pub fn is_ai_turn(&self) -> bool {
+        !self.game_over && self.race.as_ref().is_some_and(|race| race.turn == Turn::Ai)
+    }
+
+    /// A move of the player's was made: the AI's turn, unless it's out
+    pub(crate) fn pass_turn(&mut self) {
+        if let Some(race) = &mut self.race {
+            if !race.board.game_over {
+                race.turn = Turn::Ai;
+            }
+        }
+    }
+
+    /// The AI makes its move on its board in a race (None if it had nothing
+    /// left to guess), and passes the turn back; completing the word ends the
+    /// player's game as lost
+    pub fn race_turn(&mut self, words: &WordList) -> Result<Option<AiMove>, RaceError> {
+        if self.check_timer() || self.game_over {
+            return Err(RaceError::GameOver);
+        }
+        if self.is_paused() {
+            return Err(RaceError::Paused);
+        }
+        let race = self.race.as_mut().ok_or(RaceError::NotARace)?;
+        if race.turn != Turn::Ai {
+            return Err(RaceError::PlayerTurn);
+        }
+        let ai_move = ai::take_turn(&mut race.board, words);
+        race.turn = Turn::Player;
+        if race.board.won {
+            self.end(false);
+        }
+        Ok(ai_move)
+    }
+
+    /// Who won the race, once it's over
+    pub fn race_winner(&self) -> Option<RaceWinner> {
+        let race = self.race.as_ref()?;
+        if !self.game_over {
+            None
+        } else if self.won {
+            Some(RaceWinner::Player)
+        } else if race.ai_out() {
+            Some(RaceWinner::Hangman)
+        } else {
+            Some(RaceWinner::Ai)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::difficulty::Difficulty;
+
+    fn race(word: &str) -> GameState {
+        let mut game = GameState::new(word.to_string(), None, Difficulty::Medium);
+        game.race = Some(Race::new(&game).unwrap());
+        game
+    }
+
+    fn words() -> WordList {
+        WordList::from_text("rust\nruby\nrush\njava").unwrap()
+    }
+
+    #[test]
+    fn test_turns() {
+        let mut game = race("RUST");
+        assert_eq!(game.race_turn(&words()).unwrap_err(), RaceError::PlayerTurn);
+        game.guess_letter('Z');
+        assert!(game.is_ai_turn());
+        // S splits the 4 words in halves
+        assert_eq!(game.race_turn(&words()), Ok(Some(AiMove::Letter('S'))));
+        assert!(!game.is_ai_turn());
+        assert_eq!(game.race.as_ref().unwrap().progress(), (4, 1));
+        // The player's board doesn't get the AI's letters
+        assert!(!game.is_guessed('S'));
+        assert_eq!(game.race_winner(), None);
+
+        let mut game = GameState::new("RUST".to_string(), None, Difficulty::Medium);
+        assert_eq!(game.race_turn(&words()).unwrap_err(), RaceError::NotARace);
+    }
+
+    #[test]
+    fn test_ai_completing_the_word_wins() {
+        let mut game = race("RUST");
+        // The AI guesses S, then T, and then the word
+        for letter in ['Z', 'X', 'Q'] {
+            game.guess_letter(letter);
+            game.race_turn(&words()).unwrap();
+        }
+        assert!(game.game_over && !game.won);
+        assert_eq!(game.race_winner(), Some(RaceWinner::Ai));
+        assert_eq!(game.race_turn(&words()).unwrap_err(), RaceError::GameOver);
+        // The race is over, so its view shows the word again
+        assert_eq!(game.player_view().word, "RUST");
+    }
+
+    #[test]
+    fn test_player_plays_on_once_the_ai_is_out() {
+        let mut game = race("RUST");
+        game.race.as_mut().unwrap().board.max_wrong_guesses = 1;
+        game.guess_letter('R');
+        // No word fits the AI's dictionary, so it falls back to E and is hanged
+        game.race_turn(&WordList::from_text("cargo").unwrap()).unwrap();
+        assert!(game.race.as_ref().unwrap().ai_out());
+        game.guess_letter('U');
+        assert!(!game.is_ai_turn());
+        for letter in ['S', 'T'] {
+            game.guess_letter(letter);
+        }
+        assert_eq!(game.race_winner(), Some(RaceWinner::Player));
+        let evil = GameState::new_evil(&words(), "RUST".to_string(), None, Difficulty::Medium);
+        assert_eq!(Race::new(&evil).unwrap_err(), RaceError::EvilMode);
+    }
+}
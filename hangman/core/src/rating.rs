@@ -0,0 +1,122 @@
+// ============================================================================
+// WORD RATINGS
+// ============================================================================
+// How hard a word is to guess on its own, from 0 (easy) to 100 (hard), and
+// the difficulty level that falls in. difficulty.rs ranks the words of one
+// list against each other to pick them, a rating doesn't depend on the list,
+// so a finished game can say "this was a HARD word" and scoring can reward it.
+//
+// WHAT MAKES A WORD HARD:
+// 1. Rare letters (RARITY_WEIGHT): the average rarity of its distinct letters,
+//    J, Q, X, Z are rarely guessed before E, T, A
+// 2. Few letters (LENGTH_WEIGHT): a short word shows little to go on, from
+//    SHORT_WORD_LETTERS letters or less down to nothing at LONG_WORD_LETTERS
+// 3. No repeated letters (UNIQUE_WEIGHT): every correct guess reveals a single
+//    letter, while "BOOKKEEPER" gives away several at once
+// Spaces and punctuation don't count as letters.
+
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use crate::difficulty::{letter_rarity, Difficulty};
+use crate::game::is_guessable;
+use crate::letters::fold;
+
+/// Points of the rating from letter rarity
+pub const RARITY_WEIGHT: f64 = 50.0;
+
+/// Points of the rating from being short
+pub const LENGTH_WEIGHT: f64 = 30.0;
+
+/// Points of the rating from not repeating letters
+pub const UNIQUE_WEIGHT: f64 = 20.0;
+
+/// Words this short get every length point
+pub const SHORT_WORD_LETTERS: usize = 3;
+
+/// Words this long get no length points
+pub const LONG_WORD_LETTERS: usize = 12;
+
+/// Ratings from here on are medium
+pub const MEDIUM_FROM: u32 = 50;
+
+/// Ratings from here on are hard
+pub const HARD_FROM: u32 = 70;
+
+/// Score points for winning with a medium or hard word (see score.rs)
+pub const MEDIUM_WORD_BONUS: u32 = 10;
+pub const HARD_WORD_BONUS: u32 = 20;
+
+/// How hard a word is
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+pub struct WordRating {
+    // 0 (easy) to 100 (hard)
+    pub score: u32,
+    pub level: Difficulty,
+}
+
+impl WordRating {
+    /// Points added to a won game's score for guessing the word
+    pub fn bonus(&self) -> u32 {
+        match self.level {
+            Difficulty::Easy => 0,
+            Difficulty::Medium => MEDIUM_WORD_BONUS,
+            Difficulty::Hard => HARD_WORD_BONUS,
+        }
+    }
+}
+
+/// Rate a word or phrase (letters compare like ignore_accents, so É repeats E)
+pub fn rate_word(word: &str) -> WordRating {
+    let letters: Vec<char> = word.chars().filter(|&c| is_guessable(c)).collect();
+    let mut distinct: Vec<char> = letters.iter().map(|&c| fold(c, true)).collect();
+    distinct.sort_unstable();
+    distinct.dedup();
+    if distinct.is_empty() {
+        return WordRating { score: 0, level: Difficulty::Easy };
+    }
+
+    let rarity = distinct.iter().map(|&c| letter_rarity(c)).sum::<f64>() / distinct.len() as f64;
+    let shortness = LONG_WORD_LETTERS.saturating_sub(letters.len().max(SHORT_WORD_LETTERS)) as f64
+        / (LONG_WORD_LETTERS - SHORT_WORD_LETTERS) as f64;
+    let unique = distinct.len() as f64 / letters.len() as f64;
+    let score = (RARITY_WEIGHT * rarity + LENGTH_WEIGHT * shortness + UNIQUE_WEIGHT * unique).round() as u32;
+
+    let level = if score >= HARD_FROM {
+        Difficulty::Hard
+    } else if score >= MEDIUM_FROM {
+        Difficulty::Medium
+    } else {
+        Difficulty::Easy
+    };
+    WordRating { score, level }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_levels() {
+        assert_eq!(rate_word("JAZZ").level, Difficulty::Hard);
+        assert_eq!(rate_word("TEA").level, Difficulty::Medium);
+        assert_eq!(rate_word("PROGRAMMING").level, Difficulty::Easy);
+        assert_eq!(rate_word("!!"), WordRating { score: 0, level: Difficulty::Easy });
+    }
+
+    #[test]
+    fn test_each_factor_counts() {
+        // Rarer letters, fewer letters, fewer repeats
+        assert!(rate_word("QUIZ").score > rate_word("TEAS").score);
+        assert!(rate_word("CAT").score > rate_word("CATAMARAN").score);
+        assert!(rate_word("TEAM").score > rate_word("TEEM").score);
+        // Spaces don't count, letters compare without accents
+        assert_eq!(rate_word("ICE CREAM"), rate_word("ICECREAM"));
+        assert_eq!(rate_word("ÉTÉ"), rate_word("ETE"));
+    }
+
+    #[test]
+    fn test_bonus() {
+        assert_eq!(rate_word("JAZZ").bonus(), HARD_WORD_BONUS);
+        assert_eq!(rate_word("PROGRAMMING").bonus(), 0);
+    }
+}
@@ -0,0 +1,379 @@
+// ============================================================================
+// REPLAYS
+// ============================================================================
+// A finished game can be exported as a replay: the word and the rules it was
+// played with, and every move with when it was made. Importing one plays the
+// game again move by move, so a frontend can animate it from the start.
+//
+// THE FORMAT:
+// A replay is one line of JSON, kept small by writing each move as a short
+// string: the letter ("R"), "=" and the word guessed ("=RUBY"), or "?" and
+// the letter a hint revealed ("?S"), then "@" and the milliseconds since the
+// first move, e.g. {"version":1,"word":"RUST","difficulty":"medium",...,
+// "moves":["R@0","Z@850","=RUBY@1900","?S@3000"],"end":"won"}. A digraph
+// is written spelled out ("CH@400"). Whether a move was right isn't written,
+// playing it again tells.
+//
+// PLAYING IT AGAIN:
+// steps() starts a new game with the replay's word and rules and makes its
+// moves in order, keeping the game as it was before the first one and after
+// each. A move that couldn't have been made (a repeat, a move after the game
+// ended, a hint that wasn't allowed) fails the import, and so does a replay
+// whose moves don't end the way it says. Giving up and running out of time
+// aren't moves, the game is ended that way after the last one.
+//
+// WHAT A REPLAY KEEPS:
+// An evil game is replayed with the word it ended on, which answers every
+// move the way the changing word did. Helps bought with points aren't moves
+// (see shop.rs), and neither are power-ups (see powerups.rs), so a game with
+// one can't be exported. The player, the clock
+// and the match, run or campaign a game was part of aren't kept.
+
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use crate::charset::Charset;
+use crate::difficulty::Difficulty;
+use crate::digraphs::{self, spell};
+use crate::game::GameState;
+use crate::history::Action;
+use crate::letters::{normalize_word, to_upper};
+
+/// Version of the replay format, raised when fields change meaning or go away
+pub const REPLAY_VERSION: u32 = 1;
+
+/// Reasons a game can't be exported, or a replay imported
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ReplayError {
+    /// The game is still running
+    NotOver,
+    /// The game had helps bought or power-ups used
+    Helps,
+    /// The replay was written by a newer version
+    Unsupported(u32),
+    /// The replay isn't one, or has a move that couldn't have been made
+    Invalid(String),
+    /// The moves don't end the game the way the replay says
+    Mismatch,
+}
+
+impl fmt::Display for ReplayError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ReplayError::NotOver => write!(f, "only finished games can be exported"),
+            ReplayError::Helps => write!(f, "games with helps bought or power-ups used can't be replayed"),
+            ReplayError::Unsupported(version) => {
+                write!(f, "the replay is version {}, this version reads up to {}", version, REPLAY_VERSION)
+            }
+            ReplayError::Invalid(message) => write!(f, "invalid replay: {}", message),
+            ReplayError::Mismatch => write!(f, "invalid replay: its moves don't end the game the way it says"),
+        }
+    }
+}
+
+impl std::error::Error for ReplayError {}
+
+/// How the game ended
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum End {
+    Won,
+    /// Out of guesses (or beaten by the AI in a race)
+    Lost,
+    GaveUp,
+    TimedOut,
+}
+
+/// A finished game's word, rules and moves, see the top of the file
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+pub struct Replay {
+    pub version: u32,
+    pub word: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub category: Option<String>,
+    pub difficulty: Difficulty,
+    // Wrong guesses and hints the game allowed
+    pub max_wrong_guesses: u32,
+    pub max_hints: u32,
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub ignore_accents: bool,
+    // Whether it was a practice game, which wrong guesses don't end
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub practice: bool,
+    // Whether it was a kids game, which starts with its first letter shown
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub kids: bool,
+    // The keys offered, empty for A to Z
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub alphabet: String,
+    // Spelled out, e.g. "CH"
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub digraphs: Vec<String>,
+    // The digits and symbols guessed too, none by default
+    #[serde(default, skip_serializing_if = "Charset::is_letters")]
+    pub charset: Charset,
+    // The game's seed and daily number, if it had them
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub seed: Option<u64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub daily: Option<u64>,
+    // When the first move was made (Unix ms)
+    #[serde(default)]
+    pub started_at_ms: u64,
+    // Each move as "R@0", "=RUBY@1900" or "?S@3000"
+    pub moves: Vec<String>,
+    pub end: End,
+}
+
+/// The game as it was after a move (or before the first one)
+#[derive(Debug, Clone)]
+pub struct Step {
+    // Milliseconds since the first move
+    pub at_ms: u64,
+    pub game: GameState,
+}
+
+impl Replay {
+    /// The replay of a finished game
+    pub fn from_game(game: &GameState) -> Result<Self, ReplayError> {
+        if !game.game_over {
+            return Err(ReplayError::NotOver);
+        }
+        if !game.helps_bought.is_empty() || !game.power_ups_used.is_empty() {
+            return Err(ReplayError::Helps);
+        }
+        let started_at_ms = game.history.first().map_or(0, |made| made.at_ms);
+        let moves = game
+            .history
+            .iter()
+            .map(|made| {
+                let at_ms = made.at_ms.saturating_sub(started_at_ms);
+                match &made.action {
+                    Action::Letter(letter) => format!("{}@{}", spell(*letter), at_ms),
+                    Action::Word(word) => format!("={}@{}", word, at_ms),
+                    Action::Hint(letter) => format!("?{}@{}", spell(*letter), at_ms),
+                }
+            })
+            .collect();
+        let end = if game.won {
+            End::Won
+        } else if game.gave_up {
+            End::GaveUp
+        } else if game.timed_out {
+            End::TimedOut
+        } else {
+            End::Lost
+        };
+        Ok(Replay {
+            version: REPLAY_VERSION,
+            word: game.word.clone(),
+            category: game.category.clone(),
+            difficulty: game.difficulty,
+            max_wrong_guesses: game.max_wrong_guesses,
+            max_hints: game.max_hints,
+            ignore_accents: game.ignore_accents,
+            practice: game.practice,
+            kids: game.kids,
+            alphabet: game.alphabet.iter().collect(),
+            digraphs: game.digraphs.iter().map(|&unit| spell(unit)).collect(),
+            charset: game.charset.clone(),
+            seed: game.seed,
+            daily: game.daily,
+            started_at_ms,
+            moves,
+            end,
+        })
+    }
+
+    pub fn to_text(&self) -> String {
+        serde_json::to_string(self).expect("a replay is always valid JSON")
+    }
+
+    /// Read a replay written by to_text (steps() checks its moves)
+    pub fn parse(text: &str) -> Result<Self, ReplayError> {
+        let replay: Replay = serde_json::from_str(text.trim()).map_err(|err| ReplayError::Invalid(err.to_string()))?;
+        if replay.version > REPLAY_VERSION {
+            return Err(ReplayError::Unsupported(replay.version));
+        }
+        Ok(replay)
+    }
+
+    /// A new game with the replay's word and rules, before any move
+    fn new_game(&self) -> Result<GameState, ReplayError> {
+        let word = normalize_word(&self.word);
+        if !word.chars().any(|c| self.charset.is_guessable(c)) {
+            return Err(ReplayError::Invalid("the word has no letters".to_string()));
+        }
+        if self.max_wrong_guesses == 0 {
+            return Err(ReplayError::Invalid("the game allows no wrong guesses".to_string()));
+        }
+        digraphs::validate(&self.digraphs).map_err(ReplayError::Invalid)?;
+        let mut game = GameState::new(word, self.category.clone(), self.difficulty);
+        game.max_wrong_guesses = self.max_wrong_guesses;
+        game.max_hints = self.max_hints;
+        game.ignore_accents = self.ignore_accents;
+        game.practice = self.practice;
+        game.alphabet = self.alphabet.chars().collect();
+        game.digraphs = self.digraphs.iter().filter_map(|digraph| digraphs::unit(digraph)).collect();
+        game.charset = self.charset.clone();
+        game.seed = self.seed;
+        game.daily = self.daily;
+        if self.kids {
+            game.start_kids();
+        }
+        Ok(game)
+    }
+
+    /// The letter (or digraph) of a move, as it's written
+    fn letter(game: &GameState, text: &str) -> Option<char> {
+        let mut chars = text.chars();
+        match (chars.next(), chars.next()) {
+            (Some(c), None) if game.is_guessable(c) => Some(to_upper(c)),
+            _ => digraphs::find(&game.digraphs, text),
+        }
+    }
+
+    /// Play the game again: the game before the first move, then after each
+    /// move, and once more after it ended if the last move didn't end it
+    pub fn steps(&self) -> Result<Vec<Step>, ReplayError> {
+        let mut game = self.new_game()?;
+        let mut steps = vec![Step { at_ms: 0, game: game.clone() }];
+        let mut at_ms = 0;
+        for (number, text) in (1..).zip(&self.moves) {
+            let invalid = |why: &str| ReplayError::Invalid(format!("move {} ({:?}) {}", number, text, why));
+            let (action, offset) = text.rsplit_once('@').ok_or_else(|| invalid("has no time"))?;
+            at_ms = offset.parse().map_err(|_| invalid("has no time"))?;
+            if game.game_over {
+                return Err(invalid("comes after the game ended"));
+            }
+            if let Some(guess) = action.strip_prefix('=') {
+                if normalize_word(guess).is_empty() || game.has_guessed_word(guess) {
+                    return Err(invalid("isn't a new word guess"));
+                }
+                game.guess_word(guess);
+            } else if let Some(letter) = action.strip_prefix('?') {
+                let letter = Replay::letter(&game, letter).ok_or_else(|| invalid("isn't a letter"))?;
+                game.check_hint().map_err(|err| invalid(&format!("isn't allowed: {}", err)))?;
+                if game.is_revealed(letter) || !game.tokens().contains(&letter) {
+                    return Err(invalid("reveals a letter that isn't hidden"));
+                }
+                game.reveal_hint(letter);
+            } else {
+                let letter = Replay::letter(&game, action).ok_or_else(|| invalid("isn't a letter"))?;
+                if game.is_guessed(letter) {
+                    return Err(invalid("guesses a letter again"));
+                }
+                game.guess_letter(letter);
+            }
+            if let Some(made) = game.history.last_mut() {
+                made.at_ms = self.started_at_ms + at_ms;
+            }
+            steps.push(Step { at_ms, game: game.clone() });
+        }
+
+        if !game.game_over {
+            match self.end {
+                End::Won => return Err(ReplayError::Mismatch),
+                End::Lost => game.end(false),
+                End::GaveUp => {
+                    game.give_up();
+                }
+                End::TimedOut => {
+                    game.timed_out = true;
+                    game.end(false);
+                }
+            }
+            steps.push(Step { at_ms, game: game.clone() });
+        } else if game.won != (self.end == End::Won) {
+            return Err(ReplayError::Mismatch);
+        }
+        Ok(steps)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::words::WordList;
+
+    fn replay_of(game: &GameState) -> Replay {
+        Replay::parse(&Replay::from_game(game).unwrap().to_text()).unwrap()
+    }
+
+    #[test]
+    fn test_replay_plays_the_game_again() {
+        let mut game = GameState::new("RUST".to_string(), Some("languages".to_string()), Difficulty::Medium);
+        assert_eq!(Replay::from_game(&game), Err(ReplayError::NotOver));
+        game.guess_letter('R');
+        game.guess_letter('Z');
+        game.guess_word("RUBY");
+        let hint = game.use_hint().unwrap();
+        game.guess_word("RUST");
+
+        let replay = replay_of(&game);
+        let at = |index: usize| game.history[index].at_ms - replay.started_at_ms;
+        assert_eq!(replay.moves, [
+            "R@0".to_string(),
+            format!("Z@{}", at(1)),
+            format!("=RUBY@{}", at(2)),
+            format!("?{}@{}", hint, at(3)),
+            format!("=RUST@{}", at(4)),
+        ]);
+
+        let steps = replay.steps().unwrap();
+        assert_eq!(steps.len(), 6);
+        assert!(steps[0].game.history.is_empty() && !steps[0].game.game_over);
+        assert_eq!(steps[2].game.wrong_guesses, 1);
+        let last = &steps[5].game;
+        assert!(last.won);
+        assert_eq!((last.wrong_guesses, last.hints_used), (game.wrong_guesses, game.hints_used));
+        assert_eq!(last.history, game.history);
+        assert_eq!(last.score, game.score);
+    }
+
+    #[test]
+    fn test_endings_that_arent_moves() {
+        let mut game = GameState::new("RUST".to_string(), None, Difficulty::Hard);
+        game.guess_letter('A');
+        game.give_up();
+        let steps = replay_of(&game).steps().unwrap();
+        assert_eq!(steps.len(), 3);
+        assert!(steps[1].game.is_guessed('A') && !steps[1].game.game_over);
+        assert!(steps[2].game.gave_up && steps[2].game.game_over);
+
+        // An evil game is played with the word it ended on
+        let words = WordList::from_text("rust\nruby\njava\nperl").unwrap();
+        let mut evil = GameState::new_evil(&words, "RUST".to_string(), None, Difficulty::Easy);
+        for letter in "RUSTBYJAVPEL".chars() {
+            evil.guess_letter(letter);
+        }
+        let steps = replay_of(&evil).steps().unwrap();
+        let last = &steps.last().unwrap().game;
+        assert_eq!((last.word.as_str(), last.won, last.wrong_guesses), (evil.word.as_str(), evil.won, evil.wrong_guesses));
+    }
+
+    #[test]
+    fn test_invalid_replays() {
+        let mut game = GameState::new("RUST".to_string(), None, Difficulty::Medium);
+        game.guess_word("RUST");
+        let mut replay = Replay::from_game(&game).unwrap();
+        assert!(replay.steps().is_ok());
+
+        replay.moves.push("R@10".to_string());
+        assert!(matches!(replay.steps(), Err(ReplayError::Invalid(_))));
+        replay.moves = vec!["R@0".to_string(), "r@5".to_string()];
+        assert!(matches!(replay.steps(), Err(ReplayError::Invalid(_))));
+        replay.moves = vec!["R".to_string()];
+        assert!(matches!(replay.steps(), Err(ReplayError::Invalid(_))));
+        // The moves have to end the game the way the replay says
+        replay.moves = vec!["R@0".to_string()];
+        assert_eq!(replay.steps().err(), Some(ReplayError::Mismatch));
+
+        assert!(matches!(Replay::parse("not json"), Err(ReplayError::Invalid(_))));
+        replay.version = REPLAY_VERSION + 1;
+        assert_eq!(Replay::parse(&replay.to_text()), Err(ReplayError::Unsupported(REPLAY_VERSION + 1)));
+
+        game.helps_bought.push(crate::shop::Help::ExtraLife);
+        assert_eq!(Replay::from_game(&game), Err(ReplayError::Helps));
+    }
+}
@@ -0,0 +1,158 @@
+// ============================================================================
+// REVEALED LETTERS
+// ============================================================================
+// What the last move showed of the word, so a frontend can animate just the
+// letters it revealed instead of diffing the board before and after. Every
+// GameView carries it (`reveal`), so the reply to a guess has it.
+//
+// WHAT COUNTS:
+// The places whose letter the last move revealed: a letter guess that hit,
+// a hint, or a right word guess (every place left). A miss, a repeated word
+// guess, and a game without moves reveal nothing, and neither does the first
+// letter of a kids game (it isn't a move). After undo_guess it's the move
+// before, which is already on the board, so frontends animate the replies
+// to guesses only.
+//
+// PLACES:
+// A place is one letter of the word, a digraph counting as one (see
+// digraphs.rs), spaces and punctuation included, so "_ A _" has three. Each
+// revealed place also says where its letter is drawn in the display word
+// (see DisplayFormat), counted in characters, since the format's spaces and
+// separators move it around.
+
+use crate::digraphs::spell;
+use crate::game::GameState;
+use crate::letters::same_letter;
+
+/// A place of the word the last move revealed
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RevealedPlace {
+    // Counting from 0, a digraph is one place
+    pub place: usize,
+    // Its letter (or digraph's unit)
+    pub letter: char,
+    // Where the letter starts in get_display_word, in characters
+    pub display_at: usize,
+}
+
+/// What the last move revealed, and how much of the word is shown
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Reveal {
+    // In the order of the word
+    pub places: Vec<RevealedPlace>,
+    // Places with a letter to guess shown now (this move's included), out of `letters`
+    pub revealed: usize,
+    pub letters: usize,
+}
+
+impl GameState {
+    /// What the last move revealed (see reveal.rs)
+    pub fn last_reveal(&self) -> Reveal {
+        let tokens = self.tokens();
+        let guessable: Vec<bool> = tokens.iter().map(|&c| self.is_guessable(c)).collect();
+        let revealed = tokens.iter().zip(&guessable).filter(|&(&c, &guessable)| guessable && self.is_guessed(c)).count();
+        let letters = guessable.iter().filter(|&&guessable| guessable).count();
+
+        // The letters guessed before the last move
+        let before = match self.history.last() {
+            Some(last) if last.hit => &self.guessed_letters[..last.letters().min(self.guessed_letters.len())],
+            _ => return Reveal { places: Vec::new(), revealed, letters },
+        };
+        let shown_before = |c: char| {
+            before
+                .iter()
+                .chain(&self.removed_letters)
+                .any(|&guessed| same_letter(guessed, c, self.ignore_accents))
+        };
+        let starts = self.display_starts();
+        let places = tokens
+            .into_iter()
+            .enumerate()
+            .filter(|&(place, c)| guessable[place] && self.is_guessed(c) && !shown_before(c))
+            .map(|(place, letter)| RevealedPlace { place, letter, display_at: starts[place] })
+            .collect();
+        Reveal { places, revealed, letters }
+    }
+
+    /// Where each place of the word starts in get_display_word, in characters
+    /// (where the separator starts for the spaces of a phrase)
+    fn display_starts(&self) -> Vec<usize> {
+        let separator = self.display.word_separator.chars().count();
+        let mut starts = Vec::new();
+        let mut at = 0;
+        let mut previous: Option<char> = None;
+        for c in self.tokens() {
+            if c == ' ' {
+                starts.push(at);
+                at += separator;
+            } else {
+                if self.display.letter_spacing && previous.is_some_and(|previous| previous != ' ') {
+                    at += 1;
+                }
+                starts.push(at);
+                at += if self.is_revealed(c) { spell(c).chars().count() } else { 1 };
+            }
+            previous = Some(c);
+        }
+        starts
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::difficulty::Difficulty;
+    use crate::digraphs;
+
+    fn new_game(word: &str) -> GameState {
+        GameState::new(word.to_string(), None, Difficulty::Medium)
+    }
+
+    fn places(reveal: &Reveal) -> Vec<(usize, usize)> {
+        reveal.places.iter().map(|place| (place.place, place.display_at)).collect()
+    }
+
+    #[test]
+    fn test_reveals_of_moves() {
+        let mut game = new_game("BANANA");
+        assert_eq!(game.last_reveal(), Reveal { places: Vec::new(), revealed: 0, letters: 6 });
+
+        game.guess_letter('A');
+        let reveal = game.last_reveal();
+        // "_ A _ A _ A"
+        assert_eq!(places(&reveal), [(1, 2), (3, 6), (5, 10)]);
+        assert!(reveal.places.iter().all(|place| place.letter == 'A'));
+        assert_eq!((reveal.revealed, reveal.letters), (3, 6));
+
+        // A miss shows nothing new
+        game.guess_letter('X');
+        assert_eq!(game.last_reveal().places, []);
+        assert_eq!(game.last_reveal().revealed, 3);
+
+        // A right word guess shows every place that was left
+        game.guess_word("BANANA");
+        assert_eq!(places(&game.last_reveal()), [(0, 0), (2, 4), (4, 8)]);
+        assert_eq!(game.last_reveal().revealed, 6);
+    }
+
+    #[test]
+    fn test_display_places() {
+        let mut game = new_game("HI YOU");
+        game.guess_letter('Y');
+        // "_ _   Y _ _"
+        assert_eq!(places(&game.last_reveal()), [(3, 6)]);
+        game.display.letter_spacing = false;
+        game.display.word_separator = " / ".to_string();
+        // "__ / Y__"
+        assert_eq!(places(&game.last_reveal()), [(3, 5)]);
+
+        // A digraph is one place, drawn as its letters
+        let ch = digraphs::unit("CH").unwrap();
+        let mut game = new_game("CHICO");
+        game.digraphs = vec![ch];
+        game.guess_letter(ch);
+        game.guess_letter('O');
+        // "CH _ _ O"
+        assert_eq!(places(&game.last_reveal()), [(3, 7)]);
+    }
+}
@@ -0,0 +1,177 @@
+// ============================================================================
+// SAVED GAMES (AUTOSAVE)
+// ============================================================================
+// Closing the desktop app mid-game, or the app crashing, doesn't lose the
+// game: after every accepted move the game is saved as a sandbox-store
+// document, and the next start offers to restore it. Every game mode saves
+// the same way, through the Autosave trait:
+// - GameState (hangman) under SAVED_GAME_KEY
+// - WordleGame (the word game) under SAVED_WORDLE_KEY
+// A game type only says which key it's kept under and when it's over,
+// Saved<G> does the rest. The desktop app keeps them in its database (see
+// storage.rs), whose writes are transactions, and the store's writes go
+// through a temporary file renamed into place, so a crash mid-save leaves
+// the game saved before and never half a file.
+//
+// ONE GAME PER MODE:
+// Only one game of each mode is kept, the one played last. Starting another
+// game or making a move in another window's game replaces it, and it's
+// removed once that game ends, so a finished game is never offered again.
+//
+// WHAT'S KEPT (HANGMAN):
+// The whole GameState, including the answer (and an evil game's remaining
+// words), a timed game's deadline and the player. The rng isn't saved, a
+// resumed game draws its hints from the app's rng. A timed game keeps its
+// deadline, so time spent with the app closed counts.
+
+use sandbox_store::{Schema, Store, StoreError};
+use schemars::JsonSchema;
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use crate::game::GameState;
+use crate::wordle::WordleGame;
+
+/// Store key the saved hangman game is kept under
+pub const SAVED_GAME_KEY: &str = "saved_game";
+
+/// Store key the saved word game is kept under
+pub const SAVED_WORDLE_KEY: &str = "saved_wordle";
+
+/// A game mode whose unfinished game is saved after every move
+pub trait Autosave: Clone + Serialize + DeserializeOwned + JsonSchema {
+    /// Store key the game is kept under
+    const KEY: &'static str;
+
+    /// Whether the game has ended (an ended game isn't kept)
+    fn is_over(&self) -> bool;
+}
+
+impl Autosave for GameState {
+    const KEY: &'static str = SAVED_GAME_KEY;
+
+    fn is_over(&self) -> bool {
+        self.game_over
+    }
+}
+
+impl Autosave for WordleGame {
+    const KEY: &'static str = SAVED_WORDLE_KEY;
+
+    fn is_over(&self) -> bool {
+        self.game_over
+    }
+}
+
+/// The saved document of a game mode
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct Saved<G> {
+    pub game: G,
+}
+
+/// The saved hangman game
+pub type SavedGame = Saved<GameState>;
+
+/// The saved word game
+pub type SavedWordle = Saved<WordleGame>;
+
+impl<G: Autosave> Schema for Saved<G> {
+    const VERSION: u32 = 1;
+}
+
+impl<G: Autosave> Saved<G> {
+    /// Load the saved game, None if there's none (or it was already finished)
+    pub fn load(store: &Store) -> Result<Option<G>, StoreError> {
+        let saved: Option<Saved<G>> = store.load(G::KEY)?;
+        Ok(saved.map(|saved| saved.game).filter(|game| !game.is_over()))
+    }
+
+    /// Keep `game` to resume later if it's still running, forget the saved
+    /// game once it's over
+    pub fn save(store: &Store, game: &G) -> Result<(), StoreError> {
+        if game.is_over() {
+            return Self::clear(store);
+        }
+        store.save(G::KEY, &Saved { game: game.clone() })
+    }
+
+    /// Forget the saved game
+    pub fn clear(store: &Store) -> Result<(), StoreError> {
+        store.remove(G::KEY).map(|_| ())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::difficulty::Difficulty;
+    use crate::words::WordList;
+
+    fn store(dir: &tempfile::TempDir) -> Store {
+        Store::open(dir.path()).unwrap()
+    }
+
+    #[test]
+    fn test_unfinished_game_survives_restarts() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut game = GameState::new("FERRIS".to_string(), Some("tech".to_string()), Difficulty::Hard);
+        game.guess_letter('R');
+        game.guess_letter('Z');
+        game.player = Some("ada".to_string());
+        SavedGame::save(&store(&dir), &game).unwrap();
+
+        let resumed = SavedGame::load(&store(&dir)).unwrap().unwrap();
+        assert_eq!(resumed.word, "FERRIS");
+        assert_eq!(resumed.guessed_letters, ['R', 'Z']);
+        assert_eq!(resumed.wrong_guesses, 1);
+        assert_eq!(resumed.category.as_deref(), Some("tech"));
+        assert_eq!(resumed.player.as_deref(), Some("ada"));
+        assert_eq!(resumed.player_view().word, game.player_view().word);
+    }
+
+    #[test]
+    fn test_finished_game_is_forgotten() {
+        let dir = tempfile::tempdir().unwrap();
+        let store = store(&dir);
+        assert!(SavedGame::load(&store).unwrap().is_none());
+
+        let mut game = GameState::new("RUST".to_string(), None, Difficulty::Medium);
+        SavedGame::save(&store, &game).unwrap();
+        assert!(SavedGame::load(&store).unwrap().is_some());
+        assert!(game.guess_word("RUST"));
+        SavedGame::save(&store, &game).unwrap();
+        assert!(SavedGame::load(&store).unwrap().is_none());
+        assert!(store.keys().unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_evil_game_keeps_its_candidates() {
+        let dir = tempfile::tempdir().unwrap();
+        let words = WordList::from_text("rust\nruby\njava\nperl").unwrap();
+        let mut game = GameState::new_evil(&words, "RUST".to_string(), None, Difficulty::Medium);
+        game.guess_letter('A');
+        SavedGame::save(&store(&dir), &game).unwrap();
+
+        let resumed = SavedGame::load(&store(&dir)).unwrap().unwrap();
+        assert_eq!(resumed.candidates, game.candidates);
+        assert_eq!(resumed.word, game.word);
+    }
+
+    #[test]
+    fn test_game_modes_are_saved_apart() {
+        let dir = tempfile::tempdir().unwrap();
+        let store = store(&dir);
+        let dictionary = WordList::from_text("crane\nplant").unwrap();
+        let mut wordle = WordleGame::new("PLANT");
+        wordle.guess("crane", &[&dictionary]).unwrap();
+        SavedWordle::save(&store, &wordle).unwrap();
+        SavedGame::save(&store, &GameState::new("RUST".to_string(), None, Difficulty::Medium)).unwrap();
+
+        assert_eq!(SavedWordle::load(&store).unwrap().unwrap(), wordle);
+        assert_eq!(SavedGame::load(&store).unwrap().unwrap().word, "RUST");
+
+        wordle.guess("plant", &[&dictionary]).unwrap();
+        SavedWordle::save(&store, &wordle).unwrap();
+        assert!(SavedWordle::load(&store).unwrap().is_none());
+        assert_eq!(store.keys().unwrap(), [SAVED_GAME_KEY]);
+    }
+}
@@ -0,0 +1,120 @@
+// ============================================================================
+// FILE SCHEMAS
+// ============================================================================
+// JSON Schemas of every file the game reads or writes, generated from the
+// Rust types so they always match what the game actually accepts.
+//
+// WHERE THEY'RE USED:
+// - The CLI writes them out with --schemas <DIR>, the desktop app sends them
+//   to the frontend with get_schemas (for editors and other tools)
+// - Saved files (stats, profiles, daily results, campaign progress, achievements, imported words, definitions, fetched words, the saved game) are checked against them by sandbox-store
+//   whenever they're loaded, JSON word lists and categories by words.rs (and imported ones by custom_words.rs)
+// - hangman.toml is TOML, but its keys and values follow the settings schema
+
+use schemars::{schema_for, Schema};
+use sandbox_store::document_schema;
+use std::collections::BTreeMap;
+use crate::achievements::{Achievements, ACHIEVEMENTS_KEY};
+use crate::campaign::{Campaign, CAMPAIGN_KEY};
+use crate::categories::CategoryFile;
+use crate::custom_words::{CustomWords, CUSTOM_WORDS_KEY};
+use crate::daily::{Dailies, DAILY_KEY};
+use crate::definitions::{DefinitionCache, DEFINITIONS_KEY};
+use crate::online_words::{OnlineWords, ONLINE_WORDS_KEY};
+use crate::profiles::{Profiles, PROFILES_KEY};
+use crate::replay::Replay;
+use crate::saved::{SavedGame, SavedWordle, SAVED_GAME_KEY, SAVED_WORDLE_KEY};
+use crate::settings::Settings;
+use crate::stats::{Statistics, STATS_KEY};
+use crate::words::EntryFile;
+
+/// Every file format with its schema name:
+/// - "stats", "profiles": the saved statistics and profiles ("stats.json", "profiles.json")
+/// - "daily": the finished daily challenges ("daily.json")
+/// - "campaign": each player's campaign progress ("campaign.json")
+/// - "achievements": the unlocked achievements ("achievements.json")
+/// - "custom_words": each player's imported words ("custom_words.json")
+/// - "definitions": the word definitions fetched so far ("definitions.json")
+/// - "online_words": the words fetched from the word API ("online_words.json")
+/// - "saved_game", "saved_wordle": the unfinished hangman and word games to
+///   resume ("saved_game.json", "saved_wordle.json")
+/// - "replay": a finished game exported with export_replay
+/// - "settings": "hangman.toml"
+/// - "words", "categories": user word lists ("words.json", "categories.json")
+pub fn schemas() -> Vec<(&'static str, Schema)> {
+    vec![
+        (STATS_KEY, document_schema::<Statistics>()),
+        (PROFILES_KEY, document_schema::<Profiles>()),
+        (DAILY_KEY, document_schema::<Dailies>()),
+        (CAMPAIGN_KEY, document_schema::<Campaign>()),
+        (ACHIEVEMENTS_KEY, document_schema::<Achievements>()),
+        (CUSTOM_WORDS_KEY, document_schema::<CustomWords>()),
+        (DEFINITIONS_KEY, document_schema::<DefinitionCache>()),
+        (ONLINE_WORDS_KEY, document_schema::<OnlineWords>()),
+        (SAVED_GAME_KEY, document_schema::<SavedGame>()),
+        (SAVED_WORDLE_KEY, document_schema::<SavedWordle>()),
+        ("replay", schema_for!(Replay)),
+        ("settings", schema_for!(Settings)),
+        ("words", schema_for!(Vec<EntryFile>)),
+        ("categories", schema_for!(BTreeMap<String, CategoryFile>)),
+    ]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::categories::Categories;
+    use crate::words::{WordList, WordListError};
+    use sandbox_store::{Store, StoreError};
+    use std::fs;
+
+    #[test]
+    fn test_schemas() {
+        let names: Vec<&str> = schemas().iter().map(|(name, _)| *name).collect();
+        assert_eq!(
+            names,
+            [
+                "stats", "profiles", "daily", "campaign", "achievements", "custom_words", "definitions", "online_words", "saved_game",
+                "saved_wordle",
+                "replay",
+                "settings",
+                "words", "categories",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_edited_profiles_are_rejected_with_location() {
+        let dir = tempfile::tempdir().unwrap();
+        let store = Store::open(dir.path()).unwrap();
+        let mut profiles = Profiles::default();
+        profiles.create("ada").unwrap();
+        profiles.save(&store).unwrap();
+
+        let path = dir.path().join("profiles.json");
+        let mut file: serde_json::Value = serde_json::from_slice(&fs::read(&path).unwrap()).unwrap();
+        file["data"]["profiles"][0]["stats"]["wins"] = (-1).into();
+        // (edited by hand, so without the checksum, or it would be taken as damaged)
+        file.as_object_mut().unwrap().remove("checksum");
+        fs::write(&path, file.to_string()).unwrap();
+
+        match Profiles::load(&store) {
+            Err(StoreError::Invalid { violations, .. }) => {
+                assert_eq!(violations[0].path, "/data/profiles/0/stats/wins");
+            }
+            other => panic!("expected a schema violation, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_word_files_are_validated() {
+        match WordList::from_json(r#"["RUST", 7]"#) {
+            Err(WordListError::Invalid(violations)) => assert_eq!(violations[0].path, "/1"),
+            other => panic!("expected a schema violation, got {:?}", other),
+        }
+        match Categories::from_json(r#"{"animals": ["CAT"], "food": "PIE"}"#) {
+            Err(WordListError::Invalid(violations)) => assert_eq!(violations[0].path, "/food"),
+            other => panic!("expected a schema violation, got {:?}", other.map(|c| c.names())),
+        }
+    }
+}
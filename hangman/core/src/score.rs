@@ -0,0 +1,131 @@
+// ============================================================================
+// SCORING
+// ============================================================================
+// Every game gets a score when it ends. It's calculated here in the backend
+// and stored in the GameState, so the frontend only ever displays it.
+//
+// FORMULA:
+// 1. LETTER_POINTS for every distinct letter of the word the player revealed
+//    (all of them on a win, the ones found so far on a loss)
+// 2. GUESS_BONUS for every wrong guess left over, on a win only
+// 3. The word's rating bonus on a win: MEDIUM_WORD_BONUS or HARD_WORD_BONUS
+//    for a word rated medium or hard (see rating.rs)
+// 4. The sum is multiplied by the word length multiplier: 1 + letters / 4,
+//    at most MAX_LENGTH_MULTIPLIER (so "GO" x1, "RUST" x2, "LANGUAGE" x3)
+// 5. ... and by the difficulty: x1 easy, x2 medium, x3 hard
+// Hints cost a wrong guess, so they lower the bonus. Extra lives bought with
+// points (see shop.rs) don't raise it.
+
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeSet;
+use crate::game::GameState;
+use crate::rating::rate_word;
+
+/// Points per distinct letter revealed
+pub const LETTER_POINTS: u32 = 10;
+
+/// Points per wrong guess left when the game is won
+pub const GUESS_BONUS: u32 = 5;
+
+/// Cap on the word length multiplier (long phrases would dwarf everything else)
+pub const MAX_LENGTH_MULTIPLIER: u32 = 4;
+
+/// A finished game's score and how it was made up
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+pub struct Score {
+    pub letter_points: u32,
+    pub guess_bonus: u32,
+    #[serde(default)]
+    pub rating_bonus: u32,
+    pub length_multiplier: u32,
+    pub difficulty_multiplier: u32,
+    pub total: u32,
+}
+
+impl Score {
+    /// Score a game as it stands (the game sets its own score when it ends)
+    pub fn calculate(game: &GameState) -> Self {
+        let letters: Vec<char> = game.tokens().into_iter().filter(|&c| game.is_guessable(c)).collect();
+        let revealed = letters
+            .iter()
+            .filter(|&&c| game.is_guessed(c))
+            .collect::<BTreeSet<_>>()
+            .len() as u32;
+
+        let letter_points = revealed * LETTER_POINTS;
+        let (guess_bonus, rating_bonus) = if game.won {
+            (
+                (game.max_wrong_guesses - game.extra_lives()).saturating_sub(game.wrong_guesses) * GUESS_BONUS,
+                rate_word(&game.word).bonus(),
+            )
+        } else {
+            (0, 0)
+        };
+        let length_multiplier = (1 + letters.len() as u32 / 4).min(MAX_LENGTH_MULTIPLIER);
+        let difficulty_multiplier = game.difficulty.score_multiplier();
+
+        Score {
+            letter_points,
+            guess_bonus,
+            rating_bonus,
+            length_multiplier,
+            difficulty_multiplier,
+            total: (letter_points + guess_bonus + rating_bonus) * length_multiplier * difficulty_multiplier,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::difficulty::Difficulty;
+
+    #[test]
+    fn test_won_game() {
+        let mut game = GameState::new("RUST".to_string(), None, Difficulty::Medium);
+        game.guess_letter('X');
+        game.guess_word("rust");
+
+        let score = game.score.unwrap();
+        assert_eq!(score.letter_points, 40);
+        assert_eq!(score.guess_bonus, 25);
+        assert_eq!(score.rating_bonus, rate_word("RUST").bonus());
+        assert_eq!(score.length_multiplier, 2);
+        assert_eq!(score.difficulty_multiplier, 2);
+        assert_eq!(score.total, (65 + score.rating_bonus) * 4);
+    }
+
+    #[test]
+    fn test_lost_game_scores_revealed_letters_only() {
+        let mut game = GameState::new("GOOSE".to_string(), None, Difficulty::Hard);
+        game.guess_letter('O');
+        for letter in ['A', 'B', 'C', 'D'] {
+            game.guess_letter(letter);
+        }
+        assert!(game.game_over && !game.won);
+
+        let score = game.score.unwrap();
+        assert_eq!(score.letter_points, LETTER_POINTS);
+        assert_eq!(score.guess_bonus, 0);
+        assert_eq!(score.rating_bonus, 0);
+        assert_eq!(score.total, 10 * 2 * 3);
+    }
+
+    #[test]
+    fn test_length_multiplier() {
+        let multiplier = |word: &str| Score::calculate(&GameState::new(word.to_string(), None, Difficulty::Easy)).length_multiplier;
+        assert_eq!(multiplier("GO"), 1);
+        assert_eq!(multiplier("LANGUAGE"), 3);
+        // Spaces don't count, and long phrases are capped
+        assert_eq!(multiplier("RUST IS FUN"), 3);
+        assert_eq!(multiplier("RUST PROGRAMMING LANGUAGE"), MAX_LENGTH_MULTIPLIER);
+    }
+
+    #[test]
+    fn test_no_score_while_playing() {
+        let mut game = GameState::new("RUST".to_string(), None, Difficulty::Easy);
+        game.guess_letter('R');
+        assert_eq!(game.score, None);
+    }
+}
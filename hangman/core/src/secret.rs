@@ -0,0 +1,103 @@
+// ============================================================================
+// CUSTOM SECRET WORDS
+// ============================================================================
+// In two-player mode player one types the word (or phrase) for player two to
+// guess. Typed input can be anything, so it's checked here before a game is
+// started with it.
+//
+// RULES:
+// - Letters and spaces only (no digits or punctuation to give the game away)
+// - At least MIN_SECRET_LETTERS letters, at most MAX_SECRET_LEN characters
+// - Surrounding and repeated spaces are dropped, letters are uppercased
+
+use std::fmt;
+use crate::game::is_guessable;
+use crate::letters::normalize_word;
+
+/// Fewest letters a custom secret can have
+pub const MIN_SECRET_LETTERS: usize = 2;
+
+/// Longest custom secret, spaces included (so it still fits on screen)
+pub const MAX_SECRET_LEN: usize = 40;
+
+/// Reasons a custom secret word is rejected
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SecretError {
+    /// Nothing but whitespace was entered
+    Empty,
+    /// Fewer than MIN_SECRET_LETTERS letters
+    TooShort,
+    /// More than MAX_SECRET_LEN characters
+    TooLong,
+    /// Something other than a letter or space
+    InvalidCharacter(char),
+}
+
+impl fmt::Display for SecretError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SecretError::Empty => write!(f, "enter a word or phrase"),
+            SecretError::TooShort => {
+                write!(f, "the secret needs at least {} letters", MIN_SECRET_LETTERS)
+            }
+            SecretError::TooLong => {
+                write!(f, "the secret can be at most {} characters", MAX_SECRET_LEN)
+            }
+            SecretError::InvalidCharacter(c) => {
+                write!(f, "only letters and spaces are allowed (found {:?})", c)
+            }
+        }
+    }
+}
+
+impl std::error::Error for SecretError {}
+
+/// Check a typed secret and return it cleaned up (uppercased, single spaces)
+pub fn normalize_secret(input: &str) -> Result<String, SecretError> {
+    let secret = normalize_word(input);
+
+    if secret.is_empty() {
+        return Err(SecretError::Empty);
+    }
+    if let Some(c) = secret.chars().find(|&c| c != ' ' && !is_guessable(c)) {
+        return Err(SecretError::InvalidCharacter(c));
+    }
+    if secret.chars().count() > MAX_SECRET_LEN {
+        return Err(SecretError::TooLong);
+    }
+    if secret.chars().filter(|&c| is_guessable(c)).count() < MIN_SECRET_LETTERS {
+        return Err(SecretError::TooShort);
+    }
+
+    Ok(secret)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_normalizes_case_and_spaces() {
+        assert_eq!(normalize_secret("  rust   is fun ").unwrap(), "RUST IS FUN");
+        assert_eq!(normalize_secret("straße").unwrap(), "STRAßE");
+    }
+
+    #[test]
+    fn test_rejects_empty() {
+        assert_eq!(normalize_secret("   "), Err(SecretError::Empty));
+    }
+
+    #[test]
+    fn test_rejects_non_letters() {
+        assert_eq!(normalize_secret("R2D2"), Err(SecretError::InvalidCharacter('2')));
+        assert_eq!(normalize_secret("don't"), Err(SecretError::InvalidCharacter('\'')));
+    }
+
+    #[test]
+    fn test_length_limits() {
+        assert_eq!(normalize_secret("a"), Err(SecretError::TooShort));
+        assert!(normalize_secret("ab").is_ok());
+        assert_eq!(normalize_secret(&"a".repeat(MAX_SECRET_LEN + 1)), Err(SecretError::TooLong));
+        assert!(normalize_secret(&"a".repeat(MAX_SECRET_LEN)).is_ok());
+    }
+}
@@ -0,0 +1,217 @@
+// ============================================================================
+// GAME SESSIONS
+// ============================================================================
+// Every window of the desktop app plays its own game. A session is one
+// window's game, looked up by the random id handed out when the game starts,
+// so two windows guessing at the same time can't touch each other's game.
+//
+// EXPIRY:
+// Windows can be closed without telling the backend, so sessions nobody has
+// used for SESSION_IDLE_TIMEOUT are dropped. Expired sessions are cleaned up
+// whenever a new one is created. Games dropped this way are abandoned (like
+// games replaced with New Game) and don't count in the statistics.
+//
+// SHARING:
+// get finds a game through a shared reference (a session's last use is kept
+// in an atomic), so the desktop app holds its Sessions in a RwLock and every
+// game in a lock of its own, Sessions<Arc<Mutex<GameState>>>: finding a
+// session takes the read lock for a moment, and commands for two windows
+// never wait on each other. Only creating and removing sessions writes.
+//
+// OTHER GAMES:
+// Sessions holds hangman games (GameState) unless told otherwise, the
+// desktop app keeps its word game sessions (see wordle.rs) in a
+// Sessions<WordleGame> of their own.
+
+use sandbox_rng::{Rng, RngHandle};
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::{Duration, Instant};
+use crate::game::GameState;
+
+/// How long a session is kept without any command using it
+pub const SESSION_IDLE_TIMEOUT: Duration = Duration::from_secs(30 * 60);
+
+/// Opaque session id sent to the frontend (32 hex characters)
+pub type SessionId = String;
+
+#[derive(Debug)]
+struct Session<G> {
+    game: G,
+    // In nanoseconds since the sessions' epoch, atomic so get can mark it used
+    last_used: AtomicU64,
+}
+
+/// The games of all open sessions
+#[derive(Debug)]
+pub struct Sessions<G = GameState> {
+    sessions: HashMap<SessionId, Session<G>>,
+    // What the sessions' last uses are counted from
+    epoch: Instant,
+}
+
+impl<G> Default for Sessions<G> {
+    fn default() -> Self {
+        Sessions { sessions: HashMap::new(), epoch: Instant::now() }
+    }
+}
+
+impl<G> Sessions<G> {
+    /// Start a session playing `game`, returns its id
+    /// Also drops every session that has expired
+    pub fn create(&mut self, game: G) -> SessionId {
+        let now = Instant::now();
+        self.remove_idle(now);
+
+        let id = loop {
+            // From entropy so ids can't be guessed, even when SANDBOX_SEED is set
+            let id = format!("{:032x}", RngHandle::from_entropy().gen::<u128>());
+            if !self.sessions.contains_key(&id) {
+                break id;
+            }
+        };
+        let last_used = AtomicU64::new(self.nanos(now));
+        self.sessions.insert(id.clone(), Session { game, last_used });
+        id
+    }
+
+    /// The game of a session, marking the session as used
+    /// None if there's no such session (or it has expired, it's dropped on the next create)
+    pub fn get(&self, id: &str) -> Option<&G> {
+        let now = self.nanos(Instant::now());
+        let session = self.sessions.get(id)?;
+        if is_idle(session, now) {
+            return None;
+        }
+        session.last_used.fetch_max(now, Ordering::Relaxed);
+        Some(&session.game)
+    }
+
+    /// The game of a session, marking the session as used
+    /// None if there's no such session (or it has expired)
+    pub fn get_mut(&mut self, id: &str) -> Option<&mut G> {
+        let now = self.nanos(Instant::now());
+        if is_idle(self.sessions.get(id)?, now) {
+            self.sessions.remove(id);
+            return None;
+        }
+        let session = self.sessions.get_mut(id)?;
+        *session.last_used.get_mut() = now;
+        Some(&mut session.game)
+    }
+
+    /// End a session (e.g. when its window closes)
+    /// Returns its game, if the session existed
+    pub fn remove(&mut self, id: &str) -> Option<G> {
+        self.sessions.remove(id).map(|session| session.game)
+    }
+
+    /// Drop every session unused for SESSION_IDLE_TIMEOUT as of `now`
+    /// Returns how many were dropped
+    pub fn remove_idle(&mut self, now: Instant) -> usize {
+        let before = self.sessions.len();
+        let now = self.nanos(now);
+        self.sessions.retain(|_, session| !is_idle(session, now));
+        before - self.sessions.len()
+    }
+
+    /// Every session's id and game, without marking the sessions as used
+    /// (for the app's own checks, like the clock of timed games)
+    pub fn games(&self) -> impl Iterator<Item = (&SessionId, &G)> {
+        self.sessions.iter().map(|(id, session)| (id, &session.game))
+    }
+
+    /// Every session's id and game, without marking the sessions as used
+    pub fn games_mut(&mut self) -> impl Iterator<Item = (&SessionId, &mut G)> {
+        self.sessions.iter_mut().map(|(id, session)| (id, &mut session.game))
+    }
+
+    pub fn len(&self) -> usize {
+        self.sessions.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.sessions.is_empty()
+    }
+
+    /// `time` in nanoseconds since the epoch
+    fn nanos(&self, time: Instant) -> u64 {
+        time.saturating_duration_since(self.epoch).as_nanos() as u64
+    }
+}
+
+/// Whether `session` is unused for SESSION_IDLE_TIMEOUT as of `now` (in nanoseconds since the epoch)
+fn is_idle<G>(session: &Session<G>, now: u64) -> bool {
+    now.saturating_sub(session.last_used.load(Ordering::Relaxed)) >= SESSION_IDLE_TIMEOUT.as_nanos() as u64
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Mutex;
+    use crate::difficulty::Difficulty;
+
+    fn new_game(word: &str) -> GameState {
+        GameState::new(word.to_string(), None, Difficulty::Medium)
+    }
+
+    #[test]
+    fn test_sessions_are_independent() {
+        let mut sessions = Sessions::default();
+        let first = sessions.create(new_game("RUST"));
+        let second = sessions.create(new_game("TAURI"));
+        assert_ne!(first, second);
+        assert_eq!(first.len(), 32);
+
+        sessions.get_mut(&first).unwrap().guess_letter('R');
+        assert_eq!(sessions.get_mut(&first).unwrap().guessed_letters, ['R']);
+        assert!(sessions.get_mut(&second).unwrap().guessed_letters.is_empty());
+        assert!(sessions.get_mut("no-such-session").is_none());
+    }
+
+    #[test]
+    fn test_idle_sessions_expire() {
+        let mut sessions = Sessions::default();
+        let id = sessions.create(new_game("RUST"));
+        assert_eq!(sessions.remove_idle(Instant::now()), 0);
+
+        let later = Instant::now() + SESSION_IDLE_TIMEOUT;
+        assert_eq!(sessions.remove_idle(later), 1);
+        assert!(sessions.get_mut(&id).is_none());
+        assert!(sessions.is_empty());
+    }
+
+    #[test]
+    fn test_remove() {
+        let mut sessions = Sessions::default();
+        let id = sessions.create(new_game("RUST"));
+        assert_eq!(sessions.remove(&id).unwrap().word, "RUST");
+        assert!(sessions.remove(&id).is_none());
+        assert_eq!(sessions.len(), 0);
+    }
+
+    #[test]
+    fn test_games_mut_leaves_sessions_idle() {
+        let mut sessions = Sessions::default();
+        let id = sessions.create(new_game("RUST"));
+        let last_used = |sessions: &Sessions| sessions.sessions[&id].last_used.load(Ordering::Relaxed);
+        let created = last_used(&sessions);
+        for (_, game) in sessions.games_mut() {
+            game.guess_letter('R');
+        }
+        assert_eq!(sessions.games().count(), 1);
+        assert_eq!(last_used(&sessions), created);
+        assert_eq!(sessions.get_mut(&id).unwrap().guessed_letters, ['R']);
+    }
+
+    #[test]
+    fn test_get_through_a_shared_reference() {
+        let mut sessions = Sessions::default();
+        let id = sessions.create(Mutex::new(new_game("RUST")));
+        // Two lookups at once, like two commands under a read lock
+        let (first, second) = (&sessions, &sessions);
+        first.get(&id).unwrap().lock().unwrap().guess_letter('R');
+        assert_eq!(second.get(&id).unwrap().lock().unwrap().guessed_letters, ['R']);
+        assert!(first.get("no-such-session").is_none());
+    }
+}
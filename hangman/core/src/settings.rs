@@ -0,0 +1,624 @@
+// ============================================================================
+// GAME SETTINGS
+// ============================================================================
+// Player preferences that every new game picks up. Anything left unset
+// falls back to what the game's difficulty level says.
+//
+// DEFAULTS FOR NEW GAMES:
+// - difficulty: used when a frontend starts a game without choosing one
+// - category: the category the frontends offer first (None for the main list)
+// - language: which word list to load, "words.<language>.json" or
+//   "words.<language>.txt" before "words.json", or the bundled list of
+//   the language (see words.rs and languages.rs)
+// - hints_enabled: false turns hints off whatever hints_allowed says
+// - word_source: the word list files, words fetched online (see
+//   online_words.rs), the word_file list, or the day's words (see
+//   providers.rs); with "file", word_file is the list's path
+// - seed: seeds the word and hint picks of every game (see "SEEDS" in
+//   game.rs), read when the app starts; None follows SANDBOX_SEED
+// - min_word_length / max_word_length: the letters a picked word may have
+//   (see word_lengths() and "WORD LENGTHS" in words.rs), e.g. 4 and 6 for
+//   kids; a start_new_game call can ask for other bounds
+// - kids_mode: the kids preset (see kids.rs), its curated words, a bigger
+//   budget, the first letter shown, and no clock
+//
+// TELEMETRY:
+// telemetry (off unless the player turns it on) collects anonymous numbers
+// about the games, written to a local file or posted to telemetry_endpoint
+// (see telemetry.rs). audit_log (off too) records every command the desktop
+// app's frontend invokes, for debugging (see audit.rs), and metrics_on_exit
+// writes how long the commands took when the app exits (see metrics.rs).
+//
+// CLUES:
+// show_clues (off by default) lets games carry their word's clue, e.g.
+// "It's an animal" for a word of that category (see "CLUES" in game.rs).
+//
+// BLOCKED WORDS:
+// blocklist() is what new games' words are filtered with (see blocklist.rs):
+// the built-in list of offensive words while profanity_filter is on, and
+// the blocked_words.
+//
+// WHERE SETTINGS COME FROM:
+// Settings load through sandbox-config, so both frontends read them the
+// same way (each layer overrides the last):
+// 1. Defaults (everything follows the difficulty, uppercase letters)
+// 2. A "hangman.toml" file
+// 3. HANGMAN_* environment variables, e.g. HANGMAN_MAX_WRONG_GUESSES=10
+// The desktop app saves changes made in its options panel back to its
+// hangman.toml (set_settings), so they're there on the next start.
+//
+// VERSIONS:
+// version is the settings version the file was written with (a file without
+// one is from before versions, the same shape as version 1). New settings
+// always come with a default, so older files load as they are; a file from a
+// newer version of the game is refused rather than half read.
+//
+// TIMED MODE:
+// Setting time_limit makes every new game timed (see "TIMED GAMES" in
+// game.rs). The clock starts when apply() is called, so frontends apply
+// settings right as the game starts.
+//
+// DISPLAY FORMAT:
+// display says how the word is drawn as text (GameState::get_display_word):
+// what stands in for an unguessed letter, whether letters are spaced out,
+// and what goes between the words of a phrase. In hangman.toml it's a table:
+//   [display]
+//   placeholder = "•"
+//   letter_spacing = false
+//
+// ACCENTS:
+// ignore_accents makes letter and word guesses match accented letters too
+// (guessing "E" reveals "É"), handy for word lists in other languages.
+//
+// DIGRAPHS:
+// digraphs lists letter groups guessed as one letter, e.g. ["CH", "LL"] for
+// a Spanish list (see digraphs.rs). Evil games don't have them: their words
+// change under the player's guesses, and the keys would have to as well.
+
+use sandbox_config::Validate;
+use sandbox_rng::RngHandle;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use crate::difficulty::Difficulty;
+use crate::digraphs;
+use crate::blocklist::Blocklist;
+use crate::game::{is_guessable, GameState};
+use crate::letters::{lower_str, to_lower, to_upper, upper_str};
+use crate::online_words::WordSource;
+use crate::words::WordLengths;
+
+/// Settings version written to hangman.toml
+pub const SETTINGS_VERSION: u32 = 1;
+
+/// Most wrong guesses a player can allow themselves
+pub const MAX_GUESS_BUDGET: u32 = 20;
+
+/// Most hints a player can allow themselves per game
+pub const MAX_HINTS_ALLOWED: u32 = 10;
+
+/// Longest text put between the words of a phrase
+pub const MAX_WORD_SEPARATOR_LEN: usize = 5;
+
+/// Longest language tag, e.g. "pt-br"
+pub const MAX_LANGUAGE_LEN: usize = 16;
+
+/// Longest telemetry endpoint URL
+pub const MAX_ENDPOINT_LEN: usize = 512;
+
+/// Shortest and longest time limit for a timed game, in seconds
+pub const MIN_TIME_LIMIT: u32 = 10;
+pub const MAX_TIME_LIMIT: u32 = 3600;
+
+/// How letters are shown to the player (guessing is always case-insensitive)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize, JsonSchema)]
+#[cfg_attr(feature = "uniffi", derive(uniffi::Enum))]
+#[serde(rename_all = "lowercase")]
+pub enum LetterCase {
+    #[default]
+    Upper,
+    Lower,
+}
+
+impl LetterCase {
+    /// Convert a single letter to this case
+    pub fn apply(self, c: char) -> char {
+        match self {
+            LetterCase::Upper => to_upper(c),
+            LetterCase::Lower => to_lower(c),
+        }
+    }
+
+    /// Convert a whole string to this case
+    pub fn apply_str(self, s: &str) -> String {
+        match self {
+            LetterCase::Upper => upper_str(s),
+            LetterCase::Lower => lower_str(s),
+        }
+    }
+}
+
+/// How the word is drawn as text, e.g. "R _ S T   _ _"
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(default, deny_unknown_fields)]
+pub struct DisplayFormat {
+    // Shown for each letter that isn't guessed yet
+    pub placeholder: char,
+    // Whether letters are separated by a space ("R _ S T" or "R_ST")
+    pub letter_spacing: bool,
+    // Between the words of a phrase
+    pub word_separator: String,
+}
+
+impl Default for DisplayFormat {
+    fn default() -> Self {
+        DisplayFormat {
+            placeholder: '_',
+            letter_spacing: true,
+            word_separator: "   ".to_string(),
+        }
+    }
+}
+
+impl DisplayFormat {
+    /// Draw `word` with `shown` deciding which characters are shown as they are
+    /// (the others become the placeholder)
+    pub fn format(&self, word: &str, shown: impl Fn(char) -> Option<char>) -> String {
+        let letter_gap = if self.letter_spacing { " " } else { "" };
+        word.split(' ')
+            .map(|part| {
+                part.chars()
+                    .map(|c| shown(c).unwrap_or(self.placeholder).to_string())
+                    .collect::<Vec<String>>()
+                    .join(letter_gap)
+            })
+            .collect::<Vec<String>>()
+            .join(&self.word_separator)
+    }
+}
+
+impl Validate for DisplayFormat {
+    fn validate(&self) -> Result<(), String> {
+        if is_guessable(self.placeholder) || self.placeholder.is_whitespace() {
+            return Err(format!(
+                "display.placeholder can't be a letter or a space (got {:?})",
+                self.placeholder
+            ));
+        }
+        let length = self.word_separator.chars().count();
+        if length == 0 || length > MAX_WORD_SEPARATOR_LEN {
+            return Err(format!(
+                "display.word_separator must be 1 to {} characters (got {:?})",
+                MAX_WORD_SEPARATOR_LEN, self.word_separator
+            ));
+        }
+        Ok(())
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(default, deny_unknown_fields)]
+pub struct Settings {
+    // Settings version the file was written with (SETTINGS_VERSION when saved)
+    pub version: u32,
+    // Difficulty of games started without one
+    pub difficulty: Difficulty,
+    // Category offered first (None for the main word list)
+    pub category: Option<String>,
+    // Word list language, e.g. "es" (None for the plain words.json / words.txt)
+    pub language: Option<String>,
+    // Whether hints can be asked for at all
+    pub hints_enabled: bool,
+    // Whether games show the clue of their word (or of its category)
+    pub show_clues: bool,
+    // Where new games get their words from
+    pub word_source: WordSource,
+    // The word list file of the "file" word source (a .txt or .json list)
+    pub word_file: Option<String>,
+    // Whether the built-in list of offensive words is blocked
+    pub profanity_filter: bool,
+    // More words never to play with
+    pub blocked_words: Vec<String>,
+    // Wrong guesses allowed per game (None uses the difficulty's budget)
+    pub max_wrong_guesses: Option<u32>,
+    // Hints allowed per game (None uses the difficulty's allowance)
+    pub hints_allowed: Option<u32>,
+    // How the word and guessed letters are shown
+    pub letter_case: LetterCase,
+    // Seconds per game in timed mode (None plays without a clock)
+    pub time_limit: Option<u32>,
+    // Whether guesses match letters regardless of accents
+    pub ignore_accents: bool,
+    // Letter groups guessed as one letter, e.g. "CH" (2 or 3 letters from A to Z)
+    pub digraphs: Vec<String>,
+    // How the word is drawn as text
+    pub display: DisplayFormat,
+    // Seed for the word and hint picks (None follows SANDBOX_SEED, or is random)
+    pub seed: Option<u64>,
+    // Fewest and most letters of a picked word (None for no bound)
+    pub min_word_length: Option<u32>,
+    pub max_word_length: Option<u32>,
+    // Whether anonymous gameplay telemetry is collected (opt-in)
+    pub telemetry: bool,
+    // Where telemetry is posted (None writes it to the local file)
+    pub telemetry_endpoint: Option<String>,
+    // Whether the desktop app keeps a log of the commands invoked (opt-in)
+    pub audit_log: bool,
+    // Whether the desktop app writes its command metrics to a file as it exits
+    pub metrics_on_exit: bool,
+    // Whether new games are kids games (see kids.rs)
+    pub kids_mode: bool,
+}
+
+impl Default for Settings {
+    fn default() -> Self {
+        Settings {
+            version: SETTINGS_VERSION,
+            difficulty: Difficulty::default(),
+            category: None,
+            language: None,
+            hints_enabled: true,
+            show_clues: false,
+            word_source: WordSource::default(),
+            word_file: None,
+            profanity_filter: true,
+            blocked_words: Vec::new(),
+            max_wrong_guesses: None,
+            hints_allowed: None,
+            letter_case: LetterCase::default(),
+            time_limit: None,
+            ignore_accents: false,
+            digraphs: Vec::new(),
+            display: DisplayFormat::default(),
+            seed: None,
+            min_word_length: None,
+            max_word_length: None,
+            telemetry: false,
+            telemetry_endpoint: None,
+            audit_log: false,
+            metrics_on_exit: false,
+            kids_mode: false,
+        }
+    }
+}
+
+impl Settings {
+    /// Apply these settings to a freshly started game
+    pub fn apply(&self, game: &mut GameState) {
+        if let Some(max_wrong_guesses) = self.max_wrong_guesses {
+            game.max_wrong_guesses = max_wrong_guesses;
+        }
+        if let Some(hints_allowed) = self.hints_allowed {
+            game.max_hints = hints_allowed;
+        }
+        if !self.hints_enabled {
+            game.max_hints = 0;
+        }
+        if !self.show_clues {
+            game.clue = None;
+        }
+        game.letter_case = self.letter_case;
+        game.ignore_accents = self.ignore_accents;
+        if game.candidates.is_none() {
+            game.digraphs = self.digraph_units();
+        }
+        game.display = self.display.clone();
+        if self.kids_mode {
+            game.start_kids();
+        } else if let Some(seconds) = self.time_limit {
+            game.start_timer(seconds);
+        }
+    }
+
+    /// The units of the digraphs setting (see digraphs.rs)
+    pub fn digraph_units(&self) -> Vec<char> {
+        self.digraphs.iter().filter_map(|digraph| digraphs::unit(digraph)).collect()
+    }
+
+    /// Where the words and hints of the games come from: the seed if one is set,
+    /// SANDBOX_SEED otherwise
+    pub fn rng(&self) -> RngHandle {
+        self.seed.map_or_else(RngHandle::from_env, RngHandle::seeded)
+    }
+
+    /// The letters the words of new games may have
+    pub fn word_lengths(&self) -> WordLengths {
+        WordLengths {
+            min: self.min_word_length.map(|min| min as usize),
+            max: self.max_word_length.map(|max| max as usize),
+        }
+    }
+
+    /// The words new games can't be played with
+    pub fn blocklist(&self) -> Blocklist {
+        let mut blocklist = if self.profanity_filter {
+            Blocklist::builtin()
+        } else {
+            Blocklist::default()
+        };
+        blocklist.extend(&self.blocked_words);
+        blocklist
+    }
+}
+
+impl Validate for Settings {
+    fn validate(&self) -> Result<(), String> {
+        if self.version > SETTINGS_VERSION {
+            return Err(format!(
+                "the settings are version {}, this version of the game reads up to {}",
+                self.version, SETTINGS_VERSION
+            ));
+        }
+        if let Some(budget) = self.max_wrong_guesses {
+            if !(1..=MAX_GUESS_BUDGET).contains(&budget) {
+                return Err(format!(
+                    "max_wrong_guesses must be between 1 and {} (got {})",
+                    MAX_GUESS_BUDGET, budget
+                ));
+            }
+        }
+        if let Some(hints) = self.hints_allowed {
+            if hints > MAX_HINTS_ALLOWED {
+                return Err(format!(
+                    "hints_allowed must be at most {} (got {})",
+                    MAX_HINTS_ALLOWED, hints
+                ));
+            }
+        }
+        if let Some(language) = &self.language {
+            let valid = !language.is_empty()
+                && language.len() <= MAX_LANGUAGE_LEN
+                && language.chars().all(|c| c.is_ascii_alphanumeric() || c == '-');
+            if !valid {
+                return Err(format!(
+                    "language must be up to {} letters, digits, or dashes, e.g. \"es\" (got {:?})",
+                    MAX_LANGUAGE_LEN, language
+                ));
+            }
+        }
+        if matches!(&self.word_file, Some(path) if path.trim().is_empty()) {
+            return Err("word_file can't be blank".to_string());
+        }
+        if self.word_source == WordSource::File && self.word_file.is_none() {
+            return Err("word_source \"file\" needs word_file, the path of a word list".to_string());
+        }
+        if matches!(&self.category, Some(category) if category.trim().is_empty()) {
+            return Err("category can't be blank".to_string());
+        }
+        self.display.validate()?;
+        digraphs::validate(&self.digraphs)?;
+        self.word_lengths()
+            .validate()
+            .map_err(|err| format!("min_word_length / max_word_length: {}", err))?;
+        if let Some(url) = &self.telemetry_endpoint {
+            let valid = (url.starts_with("https://") || url.starts_with("http://"))
+                && url.len() <= MAX_ENDPOINT_LEN
+                && !url.contains(char::is_whitespace);
+            if !valid {
+                return Err(format!(
+                    "telemetry_endpoint must be an http:// or https:// URL of up to {} characters (got {:?})",
+                    MAX_ENDPOINT_LEN, url
+                ));
+            }
+        }
+        if let Some(seconds) = self.time_limit {
+            if !(MIN_TIME_LIMIT..=MAX_TIME_LIMIT).contains(&seconds) {
+                return Err(format!(
+                    "time_limit must be between {} and {} seconds (got {})",
+                    MIN_TIME_LIMIT, MAX_TIME_LIMIT, seconds
+                ));
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use sandbox_config::{ConfigError, ConfigLoader};
+
+    fn new_game() -> GameState {
+        GameState::new("RUST".to_string(), None, Difficulty::Medium)
+    }
+
+    #[test]
+    fn test_defaults_follow_difficulty() {
+        let mut game = new_game();
+        Settings::default().apply(&mut game);
+        assert_eq!(game.max_wrong_guesses, 6);
+        assert_eq!(game.max_hints, 2);
+        assert_eq!(game.letter_case, LetterCase::Upper);
+        assert_eq!(game.remaining_ms(), None);
+        assert_eq!(Settings::default().difficulty, Difficulty::Medium);
+        assert!(Settings::default().hints_enabled);
+    }
+
+    #[test]
+    fn test_kids_mode() {
+        let settings = Settings {
+            kids_mode: true,
+            time_limit: Some(90),
+            ..Settings::default()
+        };
+        let mut game = new_game();
+        settings.apply(&mut game);
+        assert!(game.kids);
+        assert_eq!(game.max_wrong_guesses, crate::kids::KIDS_MAX_WRONG_GUESSES);
+        assert_eq!(game.remaining_ms(), None);
+        assert_eq!(game.get_display_word(), "R _ _ _");
+    }
+
+    #[test]
+    fn test_overrides() {
+        let settings = Settings {
+            max_wrong_guesses: Some(10),
+            hints_allowed: Some(0),
+            letter_case: LetterCase::Lower,
+            time_limit: Some(90),
+            ignore_accents: true,
+            ..Settings::default()
+        };
+        let mut game = new_game();
+        settings.apply(&mut game);
+        assert_eq!(game.max_wrong_guesses, 10);
+        assert_eq!(game.max_hints, 0);
+        assert_eq!(game.time_limit, Some(90));
+        assert!(game.ignore_accents);
+        assert_eq!(game.get_display_word(), "_ _ _ _");
+        game.guess_letter('R');
+        assert_eq!(game.get_display_word(), "r _ _ _");
+    }
+
+    #[test]
+    fn test_display_format() {
+        let mut game = GameState::new("RUST IS FUN".to_string(), None, Difficulty::Medium);
+        let settings = Settings {
+            display: DisplayFormat {
+                placeholder: '•',
+                letter_spacing: false,
+                word_separator: " / ".to_string(),
+            },
+            ..Settings::default()
+        };
+        settings.apply(&mut game);
+        game.guess_letter('S');
+        assert_eq!(game.get_display_word(), "••S• / •S / •••");
+        assert_eq!(game.mask(), "__S_ _S ___");
+
+        let letter = Settings {
+            display: DisplayFormat {
+                placeholder: 'x',
+                ..DisplayFormat::default()
+            },
+            ..Settings::default()
+        };
+        assert!(letter.validate().is_err());
+        let long = Settings {
+            display: DisplayFormat {
+                word_separator: " -- / -- ".to_string(),
+                ..DisplayFormat::default()
+            },
+            ..Settings::default()
+        };
+        assert!(long.validate().is_err());
+    }
+
+    #[test]
+    fn test_blocklist() {
+        let settings = Settings {
+            blocked_words: vec!["ferris".to_string()],
+            ..Settings::default()
+        };
+        assert!(settings.blocklist().is_blocked("FERRIS"));
+        assert!(settings.blocklist().is_blocked("SHIT"));
+
+        let unfiltered = Settings {
+            profanity_filter: false,
+            ..settings
+        };
+        assert!(unfiltered.blocklist().is_blocked("FERRIS"));
+        assert!(!unfiltered.blocklist().is_blocked("SHIT"));
+    }
+
+    #[test]
+    fn test_validation() {
+        let too_many = Settings {
+            max_wrong_guesses: Some(MAX_GUESS_BUDGET + 1),
+            ..Settings::default()
+        };
+        assert!(too_many.validate().is_err());
+        let zero = Settings {
+            max_wrong_guesses: Some(0),
+            ..Settings::default()
+        };
+        assert!(zero.validate().is_err());
+        let hints = Settings {
+            hints_allowed: Some(MAX_HINTS_ALLOWED + 1),
+            ..Settings::default()
+        };
+        assert!(hints.validate().is_err());
+        let too_short = Settings {
+            time_limit: Some(MIN_TIME_LIMIT - 1),
+            ..Settings::default()
+        };
+        assert!(too_short.validate().is_err());
+        let language = Settings {
+            language: Some("../en".to_string()),
+            ..Settings::default()
+        };
+        assert!(language.validate().is_err());
+        let lengths = Settings {
+            min_word_length: Some(10),
+            max_word_length: Some(4),
+            ..Settings::default()
+        };
+        assert!(lengths.validate().is_err());
+        let endpoint = Settings {
+            telemetry_endpoint: Some("file:///etc/passwd".to_string()),
+            ..Settings::default()
+        };
+        assert!(endpoint.validate().is_err());
+        let digraphs = Settings {
+            digraphs: vec!["CH".to_string(), "Ñ".to_string()],
+            ..Settings::default()
+        };
+        assert!(digraphs.validate().is_err());
+        let newer = Settings {
+            version: SETTINGS_VERSION + 1,
+            ..Settings::default()
+        };
+        assert!(newer.validate().is_err());
+        let no_file = Settings {
+            word_source: WordSource::File,
+            ..Settings::default()
+        };
+        assert!(no_file.validate().is_err());
+        assert!(Settings { word_file: Some("words.txt".to_string()), ..no_file }.validate().is_ok());
+        assert!(Settings::default().validate().is_ok());
+        assert!(!Settings::default().telemetry);
+        assert!(!Settings::default().audit_log);
+        assert!(!Settings::default().metrics_on_exit);
+    }
+
+    #[test]
+    fn test_load_from_env() {
+        let settings: Settings = ConfigLoader::new("hangman")
+            .file("/dev/null")
+            .env_vars([
+                ("HANGMAN_MAX_WRONG_GUESSES", "9"),
+                ("HANGMAN_LETTER_CASE", "lower"),
+                ("HANGMAN_TIME_LIMIT", "90"),
+                ("HANGMAN_IGNORE_ACCENTS", "true"),
+                ("HANGMAN_DIFFICULTY", "hard"),
+                ("HANGMAN_HINTS_ENABLED", "false"),
+                ("HANGMAN_LANGUAGE", "es"),
+                ("HANGMAN_WORD_SOURCE", "online"),
+                ("HANGMAN_SEED", "42"),
+                ("HANGMAN_MIN_WORD_LENGTH", "10"),
+            ])
+            .load()
+            .unwrap();
+        assert_eq!(settings.max_wrong_guesses, Some(9));
+        assert_eq!(settings.hints_allowed, None);
+        // A file without a version is read as the first one
+        assert_eq!(settings.version, SETTINGS_VERSION);
+        assert_eq!(settings.letter_case, LetterCase::Lower);
+        assert_eq!(settings.time_limit, Some(90));
+        assert!(settings.ignore_accents);
+        assert_eq!(settings.difficulty, Difficulty::Hard);
+        assert!(!settings.hints_enabled);
+        assert_eq!(settings.language.as_deref(), Some("es"));
+        assert_eq!(settings.word_source, WordSource::Online);
+        assert_eq!(settings.rng().seed(), Some(42));
+        assert_eq!(settings.word_lengths(), WordLengths { min: Some(10), max: None });
+        let mut game = new_game();
+        settings.apply(&mut game);
+        assert_eq!(game.max_hints, 0);
+
+        let result: Result<Settings, _> = ConfigLoader::new("hangman")
+            .file("/dev/null")
+            .env_vars([("HANGMAN_HINTS_ALLOWED", "99")])
+            .load();
+        assert!(matches!(result, Err(ConfigError::Validation(_))));
+    }
+}
@@ -0,0 +1,140 @@
+// ============================================================================
+// SHARING RESULTS
+// ============================================================================
+// A finished game can be shared as a few lines of text with emoji, like the
+// word game results people paste into chats. It says how the game went
+// without giving the word away: no letters, only a mark for each move.
+//
+// THE TEXT:
+//   Hangman · Daily #20375 · Medium
+//   ✅ Solved in 7 guesses, 2/6 wrong
+//   🟩🟥🟩🟩💡🟥🟢
+// The first line is the daily number of a daily game, the day the game ended
+// (UTC) for any other, then the difficulty (and 😈 for an evil game). The
+// second is the result, the third the moves in order, a mark each (a letter
+// in the word, one that isn't, a right or wrong word guess, a hint).
+//
+// It's built here, so the desktop app and the CLI share the same text.
+
+use crate::difficulty::Difficulty;
+use crate::game::{now_ms, GameState};
+use crate::history::Action;
+
+/// Marks of the moves
+pub const LETTER_HIT: &str = "🟩";
+pub const LETTER_MISS: &str = "🟥";
+pub const WORD_HIT: &str = "🟢";
+pub const WORD_MISS: &str = "🔴";
+pub const HINT: &str = "💡";
+
+const MS_PER_DAY: u64 = 24 * 60 * 60 * 1000;
+
+/// The mark of a move
+fn mark(action: &Action, hit: bool) -> &'static str {
+    match (action, hit) {
+        (Action::Letter(_), true) => LETTER_HIT,
+        (Action::Letter(_), false) => LETTER_MISS,
+        (Action::Word(_), true) => WORD_HIT,
+        (Action::Word(_), false) => WORD_MISS,
+        (Action::Hint(_), _) => HINT,
+    }
+}
+
+/// The (UTC) date of a Unix time in milliseconds, as YYYY-MM-DD
+fn date(ms: u64) -> String {
+    // Days since 1970-01-01 to a civil date (Howard Hinnant's algorithm)
+    let days = (ms / MS_PER_DAY) as i64 + 719_468;
+    let era = days.div_euclid(146_097);
+    let day_of_era = days.rem_euclid(146_097);
+    let year_of_era = (day_of_era - day_of_era / 1460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let month_index = (5 * day_of_year + 2) / 153;
+    let day = day_of_year - (153 * month_index + 2) / 5 + 1;
+    let month = if month_index < 10 { month_index + 3 } else { month_index - 9 };
+    let year = year_of_era + era * 400 + i64::from(month <= 2);
+    format!("{:04}-{:02}-{:02}", year, month, day)
+}
+
+fn difficulty_name(difficulty: Difficulty) -> &'static str {
+    match difficulty {
+        Difficulty::Easy => "Easy",
+        Difficulty::Medium => "Medium",
+        Difficulty::Hard => "Hard",
+    }
+}
+
+impl GameState {
+    /// The game's result to share, None until it's over
+    pub fn share_text(&self) -> Option<String> {
+        if !self.game_over {
+            return None;
+        }
+        let when = match self.daily {
+            Some(day) => format!("Daily #{}", day),
+            None => date(self.history.last().map_or_else(now_ms, |made| made.at_ms)),
+        };
+        let mut title = format!("Hangman · {} · {}", when, difficulty_name(self.difficulty));
+        if self.candidates.is_some() {
+            title.push_str(" · 😈");
+        }
+
+        let guesses = self
+            .history
+            .iter()
+            .filter(|made| !matches!(made.action, Action::Hint(_)))
+            .count();
+        let plural = if guesses == 1 { "" } else { "es" };
+        let result = if self.won {
+            format!(
+                "✅ Solved in {} guess{}, {}/{} wrong",
+                guesses, plural, self.wrong_guesses, self.max_wrong_guesses
+            )
+        } else if self.gave_up {
+            format!("🏳️ Gave up after {} guess{}", guesses, plural)
+        } else if self.timed_out {
+            format!("⏰ Out of time after {} guess{}", guesses, plural)
+        } else {
+            format!("❌ Hanged after {} guess{}", guesses, plural)
+        };
+
+        let marks: String = self.history.iter().map(|made| mark(&made.action, made.hit)).collect();
+        Some([title, result, marks].join("\n").trim_end().to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_dates() {
+        assert_eq!(date(0), "1970-01-01");
+        assert_eq!(date(20_375 * MS_PER_DAY + 1), "2025-10-14");
+        assert_eq!(date(11_016 * MS_PER_DAY), "2000-02-29");
+    }
+
+    #[test]
+    fn test_share_text_keeps_the_word_out() {
+        let mut game = GameState::new("RUST".to_string(), None, Difficulty::Medium);
+        assert_eq!(game.share_text(), None);
+        game.daily = Some(20_375);
+        for letter in ['R', 'Z'] {
+            game.guess_letter(letter);
+        }
+        game.guess_word("RUBY");
+        game.guess_word("RUST");
+
+        let text = game.share_text().unwrap();
+        assert_eq!(text, "Hangman · Daily #20375 · Medium\n✅ Solved in 4 guesses, 3/6 wrong\n🟩🟥🔴🟢");
+        assert!(!text.contains("RUST") && !text.contains('R'));
+    }
+
+    #[test]
+    fn test_share_text_of_a_lost_game() {
+        let mut game = GameState::new("RUST".to_string(), None, Difficulty::Hard);
+        game.give_up();
+        let text = game.share_text().unwrap();
+        assert!(text.starts_with("Hangman · 2"));
+        assert!(text.ends_with("· Hard\n🏳️ Gave up after 0 guesses"));
+    }
+}
@@ -0,0 +1,278 @@
+// ============================================================================
+// POINTS AND HELPS
+// ============================================================================
+// Every profile has a wallet of points, earned by winning and spent on helps
+// during a game:
+// - Help::RevealVowel: shows a vowel of the word, without costing a guess
+// - Help::RemoveLetter: takes a letter that isn't in the word off the board
+// - Help::ExtraLife: one more wrong guess allowed this game
+//
+// EARNING:
+// A win earns WIN_POINTS times the difficulty's score multiplier (x1 easy,
+// x2 medium, x3 hard), added to the wallet when the profile records the game
+// (see profiles.rs), so only games that count for the player earn anything.
+//
+// SPENDING:
+// Prices are fixed here and the wallet is only ever changed by the backend:
+// the frontend asks for a help, the desktop app checks the player can pay,
+// applies the help to the session's game, and takes the points only if it
+// worked (a word without hidden vowels can't sell one).
+//
+// HELPS AND THE REST OF THE GAME:
+// Bought helps are kept in `helps_bought`. They aren't moves: undo can't take
+// one back, and can't take back the moves made before one either (so points
+// can't be spent twice on the same situation). Extra lives don't add to the
+// score's guess bonus, and in an evil game a removed letter is one no
+// remaining word has, a revealed vowel pins the word like a hint does.
+
+use sandbox_rng::SliceRandom;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use crate::game::GameState;
+use crate::letters::{fold, same_letter};
+
+/// Points a win is worth, times the difficulty's score multiplier
+pub const WIN_POINTS: u64 = 5;
+
+/// What points buy in a game
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum Help {
+    RevealVowel,
+    RemoveLetter,
+    ExtraLife,
+}
+
+/// Every help, in the order the shop shows them
+pub const HELPS: [Help; 3] = [Help::RevealVowel, Help::RemoveLetter, Help::ExtraLife];
+
+impl Help {
+    /// Points the help costs
+    pub fn price(self) -> u64 {
+        match self {
+            Help::RevealVowel => 15,
+            Help::RemoveLetter => 10,
+            Help::ExtraLife => 25,
+        }
+    }
+}
+
+/// Reasons a help can't be bought
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ShopError {
+    /// The wallet has fewer points than the help costs
+    NotEnoughPoints { price: u64, points: u64 },
+    /// The game has already been won or lost
+    GameOver,
+    /// The game is paused (see GameState::pause)
+    Paused,
+    /// The help has nothing left to do (every vowel shown, or every letter
+    /// that isn't in the word already guessed or removed)
+    Unavailable(Help),
+}
+
+impl fmt::Display for ShopError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ShopError::NotEnoughPoints { price, points } => {
+                write!(f, "that costs {} points and you have {}", price, points)
+            }
+            ShopError::GameOver => write!(f, "the game is already over"),
+            ShopError::Paused => write!(f, "the game is paused"),
+            ShopError::Unavailable(Help::RevealVowel) => write!(f, "every vowel of the word is already shown"),
+            ShopError::Unavailable(Help::RemoveLetter) => write!(f, "every letter left is in the word"),
+            ShopError::Unavailable(Help::ExtraLife) => write!(f, "no extra life can be added"),
+        }
+    }
+}
+
+impl std::error::Error for ShopError {}
+
+/// A profile's points
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+pub struct Wallet {
+    pub points: u64,
+}
+
+impl Wallet {
+    pub fn earn(&mut self, points: u64) {
+        self.points = self.points.saturating_add(points);
+    }
+
+    /// Take the price of `help`, or fail without taking anything
+    pub fn spend(&mut self, help: Help) -> Result<(), ShopError> {
+        self.check(help)?;
+        self.points -= help.price();
+        Ok(())
+    }
+
+    /// Whether the wallet can pay for `help`
+    pub fn check(&self, help: Help) -> Result<(), ShopError> {
+        if self.points < help.price() {
+            return Err(ShopError::NotEnoughPoints {
+                price: help.price(),
+                points: self.points,
+            });
+        }
+        Ok(())
+    }
+}
+
+/// Points a finished game earns its player
+pub fn points_for(game: &GameState) -> u64 {
+    if game.won {
+        WIN_POINTS * u64::from(game.difficulty.score_multiplier())
+    } else {
+        0
+    }
+}
+
+/// Whether a letter is a vowel (accented ones too)
+pub(crate) fn is_vowel(c: char) -> bool {
+    matches!(fold(c, true), 'a' | 'e' | 'i' | 'o' | 'u')
+}
+
+impl GameState {
+    /// Apply a bought help (the points are the caller's to take)
+    /// Returns the letter it revealed or removed, None for an extra life
+    pub fn apply_help(&mut self, help: Help) -> Result<Option<char>, ShopError> {
+        if self.check_timer() || self.game_over {
+            return Err(ShopError::GameOver);
+        }
+        if self.is_paused() {
+            return Err(ShopError::Paused);
+        }
+        let letter = match help {
+            Help::RevealVowel => {
+                let mut vowels: Vec<char> = self
+                    .tokens()
+                    .into_iter()
+                    .filter(|&c| is_vowel(c) && !self.is_guessed(c))
+                    .collect();
+                vowels.sort_unstable_by_key(|&c| (fold(c, self.ignore_accents), c));
+                vowels.dedup_by(|a, b| same_letter(*a, *b, self.ignore_accents));
+                let letter = *vowels.choose(&mut self.rng).ok_or(ShopError::Unavailable(help))?;
+                if let Some(candidates) = &mut self.candidates {
+                    self.word = candidates.reveal(letter, self.ignore_accents).to_string();
+                }
+                self.guessed_letters.push(letter);
+                Some(letter)
+            }
+            Help::RemoveLetter => {
+                let missing: Vec<char> = self
+                    .keyboard()
+                    .into_iter()
+                    .filter(|&key| !self.is_guessed(key) && !self.could_have(key))
+                    .collect();
+                let letter = *missing.choose(&mut self.rng).ok_or(ShopError::Unavailable(help))?;
+                self.removed_letters.push(letter);
+                Some(letter)
+            }
+            Help::ExtraLife => {
+                self.max_wrong_guesses += 1;
+                None
+            }
+        };
+        self.helps_bought.push(help);
+        self.undo_floor = self.history.len();
+        if self.check_win() {
+            self.end(true);
+        }
+        Ok(letter)
+    }
+
+    /// Wrong guesses added by bought extra lives
+    pub fn extra_lives(&self) -> u32 {
+        self.helps_bought.iter().filter(|&&help| help == Help::ExtraLife).count() as u32
+    }
+
+    /// Whether `letter` is in the word, or in an evil game in any word it could still become
+    fn could_have(&self, letter: char) -> bool {
+        match &self.candidates {
+            Some(candidates) => candidates.has_letter(letter, self.ignore_accents),
+            None => self.tokens().into_iter().any(|c| same_letter(c, letter, self.ignore_accents)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::difficulty::Difficulty;
+    use crate::history::UndoError;
+    use crate::words::WordList;
+    use sandbox_rng::RngHandle;
+
+    fn new_game(word: &str) -> GameState {
+        let mut game = GameState::new(word.to_string(), None, Difficulty::Medium);
+        game.rng = RngHandle::seeded(1);
+        game
+    }
+
+    #[test]
+    fn test_wallet() {
+        let mut wallet = Wallet { points: 20 };
+        assert_eq!(
+            wallet.spend(Help::ExtraLife),
+            Err(ShopError::NotEnoughPoints { price: 25, points: 20 })
+        );
+        assert_eq!(wallet.points, 20);
+        wallet.spend(Help::RevealVowel).unwrap();
+        assert_eq!(wallet.points, 5);
+        wallet.earn(points_for(&{
+            let mut game = new_game("GO");
+            game.guess_word("GO");
+            game
+        }));
+        assert_eq!(wallet.points, 5 + WIN_POINTS * 2);
+    }
+
+    #[test]
+    fn test_helps() {
+        let mut game = new_game("RUST");
+        assert_eq!(game.apply_help(Help::RevealVowel), Ok(Some('U')));
+        assert_eq!(game.wrong_guesses, 0);
+        assert_eq!(game.apply_help(Help::RevealVowel), Err(ShopError::Unavailable(Help::RevealVowel)));
+
+        let removed = game.apply_help(Help::RemoveLetter).unwrap().unwrap();
+        assert!(!"RUST".contains(removed));
+        // A removed letter can't be guessed any more
+        game.guess_letter(removed);
+        assert_eq!(game.wrong_guesses, 0);
+
+        game.apply_help(Help::ExtraLife).unwrap();
+        assert_eq!((game.max_wrong_guesses, game.extra_lives()), (7, 1));
+        assert_eq!(game.helps_bought, [Help::RevealVowel, Help::RemoveLetter, Help::ExtraLife]);
+
+        game.guess_word("RUST");
+        // Extra lives don't count for the guess bonus (6 wrong guesses left, not 7)
+        assert_eq!(game.score.unwrap().guess_bonus, 6 * 5);
+        assert_eq!(game.apply_help(Help::ExtraLife), Err(ShopError::GameOver));
+    }
+
+    #[test]
+    fn test_moves_before_a_help_stay() {
+        let mut game = new_game("RUST");
+        game.guess_letter('X');
+        game.apply_help(Help::RevealVowel).unwrap();
+        assert!(!game.can_undo());
+        assert_eq!(game.undo(), Err(UndoError::NothingToUndo));
+        game.guess_letter('R');
+        game.undo().unwrap();
+        assert_eq!(game.guessed_letters, ['X', 'U']);
+    }
+
+    #[test]
+    fn test_evil_game_removes_letters_no_word_has() {
+        let list = WordList::from_text("rust\nruby\njava").unwrap();
+        let mut game = GameState::new_evil(&list, "RUST".to_string(), None, Difficulty::Medium);
+        for _ in 0..20 {
+            if let Ok(Some(letter)) = game.apply_help(Help::RemoveLetter) {
+                assert!(!"RUSTBYJAV".contains(letter));
+            }
+        }
+        assert_eq!(game.removed_letters.len(), 26 - 9);
+        assert_eq!(game.apply_help(Help::RemoveLetter), Err(ShopError::Unavailable(Help::RemoveLetter)));
+    }
+}
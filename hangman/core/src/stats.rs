@@ -0,0 +1,148 @@
+// ============================================================================
+// WIN/LOSS STATISTICS
+// ============================================================================
+// Running totals across every finished game: wins, losses, streaks, and how
+// many wrong guesses games took on average.
+//
+// SAVING:
+// Statistics are a sandbox-store document (key STATS_KEY), which the desktop
+// app keeps in its database (see storage.rs) so they survive restarts.
+// Games that are abandoned before they end are not counted.
+
+use sandbox_schema::hangman::StatisticsView;
+use sandbox_schema::SCHEMA_VERSION;
+use sandbox_store::{Schema, Store, StoreError};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use crate::game::GameState;
+
+/// Store key the statistics are saved under
+pub const STATS_KEY: &str = "stats";
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+pub struct Statistics {
+    pub games_played: u32,
+    pub wins: u32,
+    pub losses: u32,
+    // Wins in a row, reset by a loss
+    pub current_streak: u32,
+    pub best_streak: u32,
+    // Sum over all finished games, for the average
+    pub total_wrong_guesses: u32,
+}
+
+impl Schema for Statistics {
+    const VERSION: u32 = 1;
+}
+
+impl Statistics {
+    /// Load saved statistics, starting from zero if none were saved yet
+    pub fn load(store: &Store) -> Result<Self, StoreError> {
+        Ok(store.load(STATS_KEY)?.unwrap_or_default())
+    }
+
+    /// Save the statistics, replacing the previous copy
+    pub fn save(&self, store: &Store) -> Result<(), StoreError> {
+        store.save(STATS_KEY, self)
+    }
+
+    /// Count a finished game (games still in progress are ignored)
+    pub fn record(&mut self, game: &GameState) {
+        if !game.game_over {
+            return;
+        }
+
+        self.games_played += 1;
+        self.total_wrong_guesses += game.wrong_guesses;
+        if game.won {
+            self.wins += 1;
+            self.current_streak += 1;
+            self.best_streak = self.best_streak.max(self.current_streak);
+        } else {
+            self.losses += 1;
+            self.current_streak = 0;
+        }
+    }
+
+    /// Average wrong guesses per finished game (0 before the first game)
+    pub fn average_wrong_guesses(&self) -> f64 {
+        if self.games_played == 0 {
+            0.0
+        } else {
+            self.total_wrong_guesses as f64 / self.games_played as f64
+        }
+    }
+
+    /// What the frontend shows: the totals plus derived averages
+    pub fn summary(&self) -> StatisticsView {
+        StatisticsView {
+            schema_version: SCHEMA_VERSION,
+            games_played: self.games_played,
+            wins: self.wins,
+            losses: self.losses,
+            current_streak: self.current_streak,
+            best_streak: self.best_streak,
+            average_wrong_guesses: self.average_wrong_guesses(),
+            win_rate: if self.games_played == 0 {
+                0.0
+            } else {
+                self.wins as f64 / self.games_played as f64
+            },
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::difficulty::Difficulty;
+
+    fn finished_game(won: bool, wrong_guesses: u32) -> GameState {
+        let mut game = GameState::new("RUST".to_string(), None, Difficulty::Medium);
+        game.game_over = true;
+        game.won = won;
+        game.wrong_guesses = wrong_guesses;
+        game
+    }
+
+    #[test]
+    fn test_record_wins_losses_and_streaks() {
+        let mut stats = Statistics::default();
+        stats.record(&finished_game(true, 1));
+        stats.record(&finished_game(true, 3));
+        stats.record(&finished_game(false, 6));
+        stats.record(&finished_game(true, 2));
+
+        assert_eq!(stats.games_played, 4);
+        assert_eq!(stats.wins, 3);
+        assert_eq!(stats.losses, 1);
+        assert_eq!(stats.current_streak, 1);
+        assert_eq!(stats.best_streak, 2);
+        assert_eq!(stats.average_wrong_guesses(), 3.0);
+        assert_eq!(stats.summary().win_rate, 0.75);
+    }
+
+    #[test]
+    fn test_unfinished_games_are_ignored() {
+        let mut stats = Statistics::default();
+        stats.record(&GameState::new("RUST".to_string(), None, Difficulty::Medium));
+        assert_eq!(stats, Statistics::default());
+        assert_eq!(stats.average_wrong_guesses(), 0.0);
+        assert_eq!(stats.summary().win_rate, 0.0);
+    }
+
+    #[test]
+    fn test_save_and_load() {
+        let dir = tempfile::tempdir().unwrap();
+        let store = Store::open(dir.path()).unwrap();
+        assert_eq!(Statistics::load(&store).unwrap(), Statistics::default());
+
+        let mut stats = Statistics::default();
+        stats.record(&finished_game(true, 2));
+        stats.save(&store).unwrap();
+
+        // A fresh store on the same directory sees the saved stats (app restart)
+        let reopened = Store::open(dir.path()).unwrap();
+        assert_eq!(Statistics::load(&reopened).unwrap(), stats);
+    }
+}
@@ -0,0 +1,937 @@
+// ============================================================================
+// SQLITE STORAGE
+// ============================================================================
+// Everything the desktop app keeps between runs, in one SQLite database
+// (STORAGE_FILE in the app data directory). sandbox-store rewrites a whole
+// JSON file on every save, which is fine for a few counters but not for the
+// history of every game played, so games are rows here.
+//
+// TABLES:
+// - games: every finished game the statistics counted (word, player, result, score),
+//   also what the per-word statistics are worked out from (word_stats)
+// - guesses: the moves of each game, in order (see history.rs)
+// - profiles: one row per profile, in the order they were created, and which
+//   one is selected
+// - achievements: one row per achievement a player unlocked
+// - documents: everything else (overall statistics, daily results, campaign
+//   progress, imported words, definitions, fetched words, the unfinished game), as JSON under the same keys and
+//   schema versions as sandbox-store documents, checked and upgraded the
+//   same way on load (an upgraded document is saved back)
+//
+// DAMAGED DOCUMENTS:
+// Every write is a transaction, so a crash never leaves half a document. Like
+// sandbox-store's files, each document is saved with the checksum of its JSON
+// and keeps the one it replaced (previous_*): a document that no longer
+// matches its checksum, isn't JSON, or doesn't match its schema is loaded from
+// the one before instead, so one bad row doesn't cost the player everything.
+// The one before is copied back over the damaged one as it's loaded, so the
+// next save keeps it as the previous_* rather than the damaged one.
+//
+// MIGRATIONS:
+// MIGRATIONS holds the SQL that builds the tables, one step per database
+// version. The database's user_version says how many steps have run, open
+// runs the rest in one transaction. Change the tables by adding a step, never
+// by editing one that has shipped.
+//
+// FROM JSON FILES:
+// import_store copies what an earlier version saved with sandbox-store into
+// the database, the desktop app does it when it creates the database so
+// players keep their statistics and profiles.
+
+use rusqlite::{params, Connection, OptionalExtension, Params, Transaction};
+use sandbox_store::{checksum, decode_document, Schema, Store, StoreError};
+use std::fmt;
+use std::path::Path;
+use std::sync::{Arc, Mutex, MutexGuard, PoisonError};
+use crate::achievements::{Achievements, Unlocked, ACHIEVEMENTS_KEY};
+use crate::campaign::{Campaign, CAMPAIGN_KEY};
+use crate::custom_words::{CustomWords, CUSTOM_WORDS_KEY};
+use crate::daily::{Dailies, DAILY_KEY};
+use crate::definitions::{DefinitionCache, DEFINITIONS_KEY};
+use crate::difficulty::Difficulty;
+use crate::game::{now_ms, GameState};
+use crate::history::Action;
+use crate::online_words::{OnlineWords, ONLINE_WORDS_KEY};
+use crate::profiles::{Profile, Profiles, PROFILES_KEY};
+use crate::saved::{Autosave, Saved, SavedGame, SAVED_GAME_KEY};
+use crate::shop::Wallet;
+use crate::stats::{Statistics, STATS_KEY};
+
+/// Database file kept in the app data directory
+pub const STORAGE_FILE: &str = "hangman.db";
+
+/// The steps that build the tables, the database's user_version counts the ones that ran
+pub const MIGRATIONS: &[&str] = &[
+    // 1: games and their guesses, profiles, achievements, and the other documents
+    "CREATE TABLE games (
+        id INTEGER PRIMARY KEY,
+        player TEXT,
+        word TEXT NOT NULL,
+        category TEXT,
+        difficulty TEXT NOT NULL,
+        evil INTEGER NOT NULL,
+        daily INTEGER,
+        won INTEGER NOT NULL,
+        wrong_guesses INTEGER NOT NULL,
+        hints_used INTEGER NOT NULL,
+        score INTEGER NOT NULL,
+        finished_at_ms INTEGER NOT NULL
+    );
+    CREATE INDEX games_by_player ON games (player, finished_at_ms);
+    CREATE TABLE guesses (
+        game_id INTEGER NOT NULL REFERENCES games (id) ON DELETE CASCADE,
+        position INTEGER NOT NULL,
+        kind TEXT NOT NULL,
+        guess TEXT NOT NULL,
+        hit INTEGER NOT NULL,
+        at_ms INTEGER NOT NULL,
+        PRIMARY KEY (game_id, position)
+    );
+    CREATE TABLE profiles (
+        position INTEGER PRIMARY KEY,
+        name TEXT NOT NULL UNIQUE COLLATE NOCASE,
+        stats TEXT NOT NULL,
+        high_scores TEXT NOT NULL,
+        total_score INTEGER NOT NULL,
+        selected INTEGER NOT NULL
+    );
+    CREATE TABLE achievements (
+        position INTEGER PRIMARY KEY,
+        id TEXT NOT NULL,
+        player TEXT,
+        at_ms INTEGER NOT NULL,
+        word TEXT NOT NULL
+    );
+    CREATE TABLE documents (
+        key TEXT PRIMARY KEY,
+        version INTEGER NOT NULL,
+        data TEXT NOT NULL
+    );",
+    // 2: each profile's points (see shop.rs)
+    "ALTER TABLE profiles ADD COLUMN points INTEGER NOT NULL DEFAULT 0;",
+    // 3: how long each profile took to complete the campaign (see campaign.rs)
+    "ALTER TABLE profiles ADD COLUMN campaign_ms INTEGER;",
+    // 4: looking games up by word, for the per-word statistics
+    "CREATE INDEX games_by_word ON games (word);",
+    // 5: each profile's power-ups (see powerups.rs), as JSON
+    "ALTER TABLE profiles ADD COLUMN power_ups TEXT NOT NULL DEFAULT '{}';",
+    // 6: each profile's ratings against the AI (see elo.rs), as JSON
+    "ALTER TABLE profiles ADD COLUMN ratings TEXT NOT NULL DEFAULT '{}';",
+    // 7: each document's checksum, and the document it replaced to fall back to
+    "ALTER TABLE documents ADD COLUMN checksum TEXT;
+    ALTER TABLE documents ADD COLUMN previous_version INTEGER;
+    ALTER TABLE documents ADD COLUMN previous_data TEXT;
+    ALTER TABLE documents ADD COLUMN previous_checksum TEXT;",
+];
+
+/// Errors returned by the storage
+#[derive(Debug)]
+pub enum StorageError {
+    /// The database couldn't be opened, read, or written
+    Sqlite(rusqlite::Error),
+    /// A stored document couldn't be encoded or decoded, or doesn't match its
+    /// schema (or, when importing, a sandbox-store document couldn't be loaded)
+    Document(StoreError),
+    /// The database was written by a newer version of the game
+    TooNew { version: usize, known: usize },
+}
+
+impl fmt::Display for StorageError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            StorageError::Sqlite(err) => write!(f, "database error: {}", err),
+            StorageError::Document(err) => write!(f, "{}", err),
+            StorageError::TooNew { version, known } => write!(
+                f,
+                "the database has version {}, this version of the game knows up to {}",
+                version, known
+            ),
+        }
+    }
+}
+
+impl std::error::Error for StorageError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            StorageError::Sqlite(err) => Some(err),
+            StorageError::Document(err) => Some(err),
+            StorageError::TooNew { .. } => None,
+        }
+    }
+}
+
+impl From<rusqlite::Error> for StorageError {
+    fn from(err: rusqlite::Error) -> Self {
+        StorageError::Sqlite(err)
+    }
+}
+
+impl From<StoreError> for StorageError {
+    fn from(err: StoreError) -> Self {
+        StorageError::Document(err)
+    }
+}
+
+impl From<serde_json::Error> for StorageError {
+    fn from(err: serde_json::Error) -> Self {
+        StorageError::Document(StoreError::Json(err))
+    }
+}
+
+/// A move of a stored game
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GuessRecord {
+    pub action: Action,
+    pub hit: bool,
+    pub at_ms: u64,
+}
+
+/// A stored game
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GameRecord {
+    pub id: i64,
+    pub player: Option<String>,
+    pub word: String,
+    pub category: Option<String>,
+    pub difficulty: Difficulty,
+    pub evil: bool,
+    // Day number of a daily challenge
+    pub daily: Option<u64>,
+    pub won: bool,
+    pub wrong_guesses: u32,
+    pub hints_used: u32,
+    pub score: u32,
+    pub finished_at_ms: u64,
+    pub guesses: Vec<GuessRecord>,
+}
+
+/// How one word went over the stored games it was played in
+#[derive(Debug, Clone, PartialEq)]
+pub struct WordStats {
+    pub word: String,
+    pub played: u32,
+    pub wins: u32,
+    pub average_wrong_guesses: f64,
+}
+
+impl WordStats {
+    pub fn losses(&self) -> u32 {
+        self.played - self.wins
+    }
+
+    /// Share of the games won, 0.0 to 1.0
+    pub fn win_rate(&self) -> f64 {
+        if self.played == 0 {
+            0.0
+        } else {
+            f64::from(self.wins) / f64::from(self.played)
+        }
+    }
+}
+
+/// The game's database, shared by every thread that saves to it
+#[derive(Debug, Clone)]
+pub struct Storage {
+    connection: Arc<Mutex<Connection>>,
+}
+
+impl Storage {
+    /// Open (and create if needed) the database in `path`, and bring its tables up to date
+    pub fn open(path: impl AsRef<Path>) -> Result<Self, StorageError> {
+        Self::migrate(Connection::open(path)?)
+    }
+
+    /// A database that lives as long as the Storage (for tests, or when there's no data directory)
+    pub fn open_in_memory() -> Result<Self, StorageError> {
+        Self::migrate(Connection::open_in_memory()?)
+    }
+
+    fn migrate(mut connection: Connection) -> Result<Self, StorageError> {
+        connection.pragma_update(None, "foreign_keys", true)?;
+        let version: usize = connection.query_row("PRAGMA user_version", [], |row| row.get(0))?;
+        if version > MIGRATIONS.len() {
+            return Err(StorageError::TooNew {
+                version,
+                known: MIGRATIONS.len(),
+            });
+        }
+        if version < MIGRATIONS.len() {
+            let transaction = connection.transaction()?;
+            for migration in &MIGRATIONS[version..] {
+                transaction.execute_batch(migration)?;
+            }
+            transaction.pragma_update(None, "user_version", MIGRATIONS.len())?;
+            transaction.commit()?;
+        }
+        Ok(Storage {
+            connection: Arc::new(Mutex::new(connection)),
+        })
+    }
+
+    /// Lock the connection, carrying on if a thread panicked while holding it
+    /// (every write is a transaction, so the database is still consistent)
+    fn connection(&self) -> MutexGuard<'_, Connection> {
+        self.connection.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    /// How many migrations the database has run
+    pub fn version(&self) -> Result<usize, StorageError> {
+        Ok(self.connection().query_row("PRAGMA user_version", [], |row| row.get(0))?)
+    }
+
+    /// Whether nothing has been saved yet (a new database)
+    pub fn is_empty(&self) -> Result<bool, StorageError> {
+        let connection = self.connection();
+        let rows: i64 = connection.query_row(
+            "SELECT (SELECT COUNT(*) FROM games) + (SELECT COUNT(*) FROM profiles)
+                + (SELECT COUNT(*) FROM achievements) + (SELECT COUNT(*) FROM documents)",
+            [],
+            |row| row.get(0),
+        )?;
+        Ok(rows == 0)
+    }
+
+    // ---- documents ----
+
+    /// Save `value` under `key`, replacing the document saved before (which is
+    /// kept to fall back to, see load)
+    /// A document saved by a newer version of the game (that this one couldn't
+    /// load) isn't replaced, so going back to an older version loses nothing
+    pub fn save<T: Schema>(&self, key: &str, value: &T) -> Result<(), StorageError> {
+        let data = serde_json::to_string(value)?;
+        let connection = self.connection();
+        let saved = connection.execute(
+            "INSERT INTO documents (key, version, data, checksum) VALUES (?1, ?2, ?3, ?4)
+                ON CONFLICT (key) DO UPDATE SET
+                    previous_version = documents.version, previous_data = documents.data,
+                    previous_checksum = documents.checksum,
+                    version = excluded.version, data = excluded.data, checksum = excluded.checksum
+                WHERE documents.version <= excluded.version",
+            params![key, T::VERSION, data, checksum(data.as_bytes())],
+        )?;
+        if saved == 0 {
+            let found = connection.query_row("SELECT version FROM documents WHERE key = ?1", [key], |row| row.get(0))?;
+            return Err(StoreError::VersionMismatch {
+                key: key.to_string(),
+                expected: T::VERSION,
+                found,
+            }
+            .into());
+        }
+        Ok(())
+    }
+
+    /// Load the document saved under `key`, Ok(None) if there's none
+    /// It's upgraded and checked against its schema like a sandbox-store document
+    /// If it's damaged (not matching its checksum, not JSON, or not matching the
+    /// schema) the document it replaced is returned instead, the error is only
+    /// returned when that one doesn't load either
+    pub fn load<T: Schema>(&self, key: &str) -> Result<Option<T>, StorageError> {
+        // (version, data, checksum) of the document and of the one it replaced
+        type Current = (u32, String, Option<String>);
+        type Previous = (Option<u32>, Option<String>, Option<String>);
+        let row: Option<(Current, Previous)> = self
+            .connection()
+            .query_row(
+                "SELECT version, data, checksum, previous_version, previous_data, previous_checksum
+                    FROM documents WHERE key = ?1",
+                [key],
+                |row| Ok(((row.get(0)?, row.get(1)?, row.get(2)?), (row.get(3)?, row.get(4)?, row.get(5)?))),
+            )
+            .optional()?;
+        let Some(((version, data, sum), previous)) = row else {
+            return Ok(None);
+        };
+        let error = match decode::<T>(key, version, &data, sum.as_deref()) {
+            Ok(value) => {
+                if version != T::VERSION {
+                    self.save(key, &value)?;
+                }
+                return Ok(Some(value));
+            }
+            Err(err @ (StoreError::Json(_) | StoreError::Checksum { .. } | StoreError::Invalid { .. })) => err,
+            Err(err) => return Err(err.into()),
+        };
+        if let (Some(version), Some(data), sum) = previous {
+            if let Ok(value) = decode::<T>(key, version, &data, sum.as_deref()) {
+                self.connection().execute(
+                    "UPDATE documents SET version = previous_version, data = previous_data, checksum = previous_checksum
+                        WHERE key = ?1",
+                    [key],
+                )?;
+                if version != T::VERSION {
+                    self.save(key, &value)?;
+                }
+                return Ok(Some(value));
+            }
+        }
+        Err(error.into())
+    }
+
+    /// Remove the document saved under `key`, true if there was one
+    pub fn remove(&self, key: &str) -> Result<bool, StorageError> {
+        Ok(self.connection().execute("DELETE FROM documents WHERE key = ?1", [key])? > 0)
+    }
+
+    /// The overall statistics, fresh ones if nothing was saved yet
+    pub fn load_statistics(&self) -> Result<Statistics, StorageError> {
+        Ok(self.load(STATS_KEY)?.unwrap_or_default())
+    }
+
+    pub fn load_dailies(&self) -> Result<Dailies, StorageError> {
+        Ok(self.load(DAILY_KEY)?.unwrap_or_default())
+    }
+
+    pub fn load_campaign(&self) -> Result<Campaign, StorageError> {
+        Ok(self.load(CAMPAIGN_KEY)?.unwrap_or_default())
+    }
+
+    pub fn load_custom_words(&self) -> Result<CustomWords, StorageError> {
+        Ok(self.load(CUSTOM_WORDS_KEY)?.unwrap_or_default())
+    }
+
+    pub fn load_definitions(&self) -> Result<DefinitionCache, StorageError> {
+        Ok(self.load(DEFINITIONS_KEY)?.unwrap_or_default())
+    }
+
+    pub fn load_online_words(&self) -> Result<OnlineWords, StorageError> {
+        Ok(self.load(ONLINE_WORDS_KEY)?.unwrap_or_default())
+    }
+
+    /// The unfinished game of a mode to resume, None if there's none (see saved.rs)
+    pub fn load_game<G: Autosave>(&self) -> Result<Option<G>, StorageError> {
+        let saved: Option<Saved<G>> = self.load(G::KEY)?;
+        Ok(saved.map(|saved| saved.game).filter(|game| !game.is_over()))
+    }
+
+    /// Keep `game` to resume if it's still running, forget its mode's saved game once it's over
+    pub fn save_game<G: Autosave>(&self, game: &G) -> Result<(), StorageError> {
+        if game.is_over() {
+            return self.forget_game::<G>();
+        }
+        self.save(G::KEY, &Saved { game: game.clone() })
+    }
+
+    /// Forget a mode's saved game
+    pub fn forget_game<G: Autosave>(&self) -> Result<(), StorageError> {
+        self.remove(G::KEY).map(|_| ())
+    }
+
+    // ---- profiles and achievements ----
+
+    /// Every profile and the selected one, no profiles if none were saved yet
+    pub fn load_profiles(&self) -> Result<Profiles, StorageError> {
+        let connection = self.connection();
+        let mut statement = connection
+            .prepare("SELECT name, stats, high_scores, total_score, selected, points, campaign_ms, power_ups, ratings FROM profiles ORDER BY position")?;
+        let rows = statement.query_map([], |row| {
+            Ok((
+                row.get::<_, String>(0)?,
+                row.get::<_, String>(1)?,
+                row.get::<_, String>(2)?,
+                row.get::<_, i64>(3)?,
+                row.get::<_, bool>(4)?,
+                row.get::<_, i64>(5)?,
+                row.get::<_, Option<i64>>(6)?,
+                row.get::<_, String>(7)?,
+                row.get::<_, String>(8)?,
+            ))
+        })?;
+        let mut profiles = Profiles::default();
+        for row in rows {
+            let (name, stats, high_scores, total_score, selected, points, campaign_ms, power_ups, ratings) = row?;
+            if selected {
+                profiles.current = Some(name.clone());
+            }
+            profiles.profiles.push(Profile {
+                name,
+                stats: serde_json::from_str(&stats)?,
+                high_scores: serde_json::from_str(&high_scores)?,
+                total_score: total_score as u64,
+                wallet: Wallet { points: points as u64 },
+                power_ups: serde_json::from_str(&power_ups)?,
+                campaign_ms: campaign_ms.map(|ms| ms as u64),
+                ratings: serde_json::from_str(&ratings)?,
+            });
+        }
+        Ok(profiles)
+    }
+
+    /// Save every profile, replacing the ones saved before
+    pub fn save_profiles(&self, profiles: &Profiles) -> Result<(), StorageError> {
+        let mut connection = self.connection();
+        let transaction = connection.transaction()?;
+        write_profiles(&transaction, profiles)?;
+        Ok(transaction.commit()?)
+    }
+
+    /// Every unlocked achievement, none if nothing was saved yet
+    pub fn load_achievements(&self) -> Result<Achievements, StorageError> {
+        let connection = self.connection();
+        let mut statement = connection.prepare("SELECT id, player, at_ms, word FROM achievements ORDER BY position")?;
+        let unlocked = statement
+            .query_map([], |row| {
+                Ok(Unlocked {
+                    id: row.get(0)?,
+                    player: row.get(1)?,
+                    at_ms: row.get::<_, i64>(2)? as u64,
+                    word: row.get(3)?,
+                })
+            })?
+            .collect::<Result<_, _>>()?;
+        Ok(Achievements { unlocked })
+    }
+
+    /// Save the unlocked achievements, replacing the ones saved before
+    pub fn save_achievements(&self, achievements: &Achievements) -> Result<(), StorageError> {
+        let mut connection = self.connection();
+        let transaction = connection.transaction()?;
+        write_achievements(&transaction, achievements)?;
+        Ok(transaction.commit()?)
+    }
+
+    // ---- games ----
+
+    /// Keep a finished game and its moves, returns its id
+    pub fn record_game(&self, game: &GameState) -> Result<i64, StorageError> {
+        let mut connection = self.connection();
+        let transaction = connection.transaction()?;
+        transaction.execute(
+            "INSERT INTO games (player, word, category, difficulty, evil, daily, won, wrong_guesses, hints_used, score, finished_at_ms)
+                VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11)",
+            params![
+                game.player,
+                game.word,
+                game.category,
+                difficulty_name(game.difficulty),
+                game.candidates.is_some(),
+                game.daily.map(|day| day as i64),
+                game.won,
+                game.wrong_guesses,
+                game.hints_used,
+                game.score.map_or(0, |score| score.total),
+                now_ms() as i64,
+            ],
+        )?;
+        let id = transaction.last_insert_rowid();
+        for (position, made) in game.history.iter().enumerate() {
+            let (kind, guess) = action_columns(&made.action);
+            transaction.execute(
+                "INSERT INTO guesses (game_id, position, kind, guess, hit, at_ms) VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
+                params![id, position as i64, kind, guess, made.hit, made.at_ms as i64],
+            )?;
+        }
+        transaction.commit()?;
+        Ok(id)
+    }
+
+    /// The last `limit` games `player` (None for every player) finished, newest first
+    pub fn recent_games(&self, player: Option<&str>, limit: usize) -> Result<Vec<GameRecord>, StorageError> {
+        self.select_games(
+            "SELECT id, player, word, category, difficulty, evil, daily, won, wrong_guesses, hints_used, score, finished_at_ms
+                FROM games WHERE ?1 IS NULL OR player = ?1 COLLATE NOCASE
+                ORDER BY finished_at_ms DESC, id DESC LIMIT ?2",
+            params![player, limit as i64],
+        )
+    }
+
+    /// Every game finished so far, by any player, oldest first
+    pub fn all_games(&self) -> Result<Vec<GameRecord>, StorageError> {
+        self.select_games(
+            "SELECT id, player, word, category, difficulty, evil, daily, won, wrong_guesses, hints_used, score, finished_at_ms
+                FROM games ORDER BY finished_at_ms, id",
+            [],
+        )
+    }
+
+    /// How every word `player` (None for every player) played went, most played first
+    pub fn word_stats(&self, player: Option<&str>) -> Result<Vec<WordStats>, StorageError> {
+        self.select_word_stats(
+            "SELECT word, COUNT(*), SUM(won), AVG(wrong_guesses)
+                FROM games WHERE ?1 IS NULL OR player = ?1 COLLATE NOCASE
+                GROUP BY word ORDER BY COUNT(*) DESC, word",
+            params![player],
+        )
+    }
+
+    /// The `limit` words `player` (None for every player) lost most often, the
+    /// lowest win rate first when they lost them as often; words never lost
+    /// aren't in it
+    pub fn most_missed_words(&self, player: Option<&str>, limit: usize) -> Result<Vec<WordStats>, StorageError> {
+        self.select_word_stats(
+            "SELECT word, COUNT(*), SUM(won), AVG(wrong_guesses)
+                FROM games WHERE ?1 IS NULL OR player = ?1 COLLATE NOCASE
+                GROUP BY word HAVING SUM(won) < COUNT(*)
+                ORDER BY COUNT(*) - SUM(won) DESC, 1.0 * SUM(won) / COUNT(*), word LIMIT ?2",
+            params![player, limit as i64],
+        )
+    }
+
+    /// The word statistics a query selects (word, games, wins, average wrong guesses)
+    fn select_word_stats(&self, query: &str, parameters: impl Params) -> Result<Vec<WordStats>, StorageError> {
+        let connection = self.connection();
+        let mut statement = connection.prepare(query)?;
+        let stats = statement
+            .query_map(parameters, |row| {
+                Ok(WordStats {
+                    word: row.get(0)?,
+                    played: row.get(1)?,
+                    wins: row.get(2)?,
+                    average_wrong_guesses: row.get(3)?,
+                })
+            })?
+            .collect::<Result<_, _>>()?;
+        Ok(stats)
+    }
+
+    /// The games a query selects (the games table's columns, in order) with their moves
+    fn select_games(&self, query: &str, parameters: impl Params) -> Result<Vec<GameRecord>, StorageError> {
+        let connection = self.connection();
+        let mut statement = connection.prepare(query)?;
+        let mut games = statement
+            .query_map(parameters, |row| {
+                Ok(GameRecord {
+                    id: row.get(0)?,
+                    player: row.get(1)?,
+                    word: row.get(2)?,
+                    category: row.get(3)?,
+                    difficulty: parse_difficulty(&row.get::<_, String>(4)?),
+                    evil: row.get(5)?,
+                    daily: row.get::<_, Option<i64>>(6)?.map(|day| day as u64),
+                    won: row.get(7)?,
+                    wrong_guesses: row.get(8)?,
+                    hints_used: row.get(9)?,
+                    score: row.get(10)?,
+                    finished_at_ms: row.get::<_, i64>(11)? as u64,
+                    guesses: Vec::new(),
+                })
+            })?
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let mut statement =
+            connection.prepare("SELECT kind, guess, hit, at_ms FROM guesses WHERE game_id = ?1 ORDER BY position")?;
+        for game in &mut games {
+            game.guesses = statement
+                .query_map([game.id], |row| {
+                    let guess: String = row.get(1)?;
+                    let letter = guess.chars().next().unwrap_or(' ');
+                    let action = match row.get::<_, String>(0)?.as_str() {
+                        "letter" => Action::Letter(letter),
+                        "hint" => Action::Hint(letter),
+                        _ => Action::Word(guess),
+                    };
+                    Ok(GuessRecord {
+                        action,
+                        hit: row.get(2)?,
+                        at_ms: row.get::<_, i64>(3)? as u64,
+                    })
+                })?
+                .collect::<Result<_, _>>()?;
+        }
+        Ok(games)
+    }
+
+    // ---- from JSON files ----
+
+    /// Copy the documents an earlier version saved with sandbox-store into the
+    /// database (replacing what's there), returns the keys that were copied
+    pub fn import_store(&self, store: &Store) -> Result<Vec<&'static str>, StorageError> {
+        let mut imported = Vec::new();
+        if let Some(stats) = store.load::<Statistics>(STATS_KEY)? {
+            self.save(STATS_KEY, &stats)?;
+            imported.push(STATS_KEY);
+        }
+        if let Some(profiles) = store.load::<Profiles>(PROFILES_KEY)? {
+            self.save_profiles(&profiles)?;
+            imported.push(PROFILES_KEY);
+        }
+        if let Some(achievements) = store.load::<Achievements>(ACHIEVEMENTS_KEY)? {
+            self.save_achievements(&achievements)?;
+            imported.push(ACHIEVEMENTS_KEY);
+        }
+        if let Some(dailies) = store.load::<Dailies>(DAILY_KEY)? {
+            self.save(DAILY_KEY, &dailies)?;
+            imported.push(DAILY_KEY);
+        }
+        if let Some(campaign) = store.load::<Campaign>(CAMPAIGN_KEY)? {
+            self.save(CAMPAIGN_KEY, &campaign)?;
+            imported.push(CAMPAIGN_KEY);
+        }
+        if let Some(custom_words) = store.load::<CustomWords>(CUSTOM_WORDS_KEY)? {
+            self.save(CUSTOM_WORDS_KEY, &custom_words)?;
+            imported.push(CUSTOM_WORDS_KEY);
+        }
+        if let Some(definitions) = store.load::<DefinitionCache>(DEFINITIONS_KEY)? {
+            self.save(DEFINITIONS_KEY, &definitions)?;
+            imported.push(DEFINITIONS_KEY);
+        }
+        if let Some(game) = SavedGame::load(store)? {
+            self.save_game(&game)?;
+            imported.push(SAVED_GAME_KEY);
+        }
+        Ok(imported)
+    }
+}
+
+/// The `data` of a document saved under `key` with schema version `version`,
+/// checked against its checksum `sum` (documents saved before checksums have none)
+fn decode<T: Schema>(key: &str, version: u32, data: &str, sum: Option<&str>) -> Result<T, StoreError> {
+    if sum.is_some_and(|sum| sum != checksum(data.as_bytes())) {
+        return Err(StoreError::Checksum { key: key.to_string() });
+    }
+    decode_document(key, version, serde_json::from_str(data)?)
+}
+
+fn write_profiles(transaction: &Transaction, profiles: &Profiles) -> Result<(), StorageError> {
+    transaction.execute("DELETE FROM profiles", [])?;
+    for (position, profile) in profiles.profiles.iter().enumerate() {
+        let selected = profiles.current.as_deref() == Some(profile.name.as_str());
+        transaction.execute(
+            "INSERT INTO profiles (position, name, stats, high_scores, total_score, selected, points, campaign_ms, power_ups, ratings) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10)",
+            params![
+                position as i64,
+                profile.name,
+                serde_json::to_string(&profile.stats)?,
+                serde_json::to_string(&profile.high_scores)?,
+                profile.total_score as i64,
+                selected,
+                profile.wallet.points as i64,
+                profile.campaign_ms.map(|ms| ms as i64),
+                serde_json::to_string(&profile.power_ups)?,
+                serde_json::to_string(&profile.ratings)?,
+            ],
+        )?;
+    }
+    Ok(())
+}
+
+fn write_achievements(transaction: &Transaction, achievements: &Achievements) -> Result<(), StorageError> {
+    transaction.execute("DELETE FROM achievements", [])?;
+    for (position, unlocked) in achievements.unlocked.iter().enumerate() {
+        transaction.execute(
+            "INSERT INTO achievements (position, id, player, at_ms, word) VALUES (?1, ?2, ?3, ?4, ?5)",
+            params![position as i64, unlocked.id, unlocked.player, unlocked.at_ms as i64, unlocked.word],
+        )?;
+    }
+    Ok(())
+}
+
+/// How a move is written in the guesses table: its kind ("letter", "word",
+/// or "hint") and what was guessed
+pub(crate) fn action_columns(action: &Action) -> (&'static str, String) {
+    match action {
+        Action::Letter(letter) => ("letter", letter.to_string()),
+        Action::Word(word) => ("word", word.clone()),
+        Action::Hint(letter) => ("hint", letter.to_string()),
+    }
+}
+
+/// How a difficulty is written in the games table (the same as in JSON)
+pub(crate) fn difficulty_name(difficulty: Difficulty) -> &'static str {
+    match difficulty {
+        Difficulty::Easy => "easy",
+        Difficulty::Medium => "medium",
+        Difficulty::Hard => "hard",
+    }
+}
+
+fn parse_difficulty(name: &str) -> Difficulty {
+    match name {
+        "easy" => Difficulty::Easy,
+        "hard" => Difficulty::Hard,
+        _ => Difficulty::Medium,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::race::Race;
+    use crate::words::WordList;
+    use crate::wordle::WordleGame;
+
+    fn finished(word: &str, player: Option<&str>) -> GameState {
+        let mut game = GameState::new(word.to_string(), None, Difficulty::Hard);
+        game.player = player.map(str::to_string);
+        for letter in word.chars() {
+            game.guess_letter(letter);
+        }
+        game
+    }
+
+    #[test]
+    fn test_migrations_run_once() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join(STORAGE_FILE);
+        let storage = Storage::open(&path).unwrap();
+        assert_eq!(storage.version().unwrap(), MIGRATIONS.len());
+        assert!(storage.is_empty().unwrap());
+        storage.save(STATS_KEY, &Statistics { wins: 2, ..Statistics::default() }).unwrap();
+        drop(storage);
+
+        // Reopening keeps the data, a database from a newer version is refused
+        let storage = Storage::open(&path).unwrap();
+        assert_eq!(storage.load_statistics().unwrap().wins, 2);
+        storage.connection().pragma_update(None, "user_version", MIGRATIONS.len() + 1).unwrap();
+        drop(storage);
+        assert!(matches!(Storage::open(&path), Err(StorageError::TooNew { .. })));
+    }
+
+    #[test]
+    fn test_documents() {
+        let storage = Storage::open_in_memory().unwrap();
+        assert!(storage.load::<Statistics>(STATS_KEY).unwrap().is_none());
+        let stats = Statistics { games_played: 3, ..Statistics::default() };
+        storage.save(STATS_KEY, &stats).unwrap();
+        assert_eq!(storage.load_statistics().unwrap(), stats);
+        assert!(storage.remove(STATS_KEY).unwrap());
+        assert!(!storage.remove(STATS_KEY).unwrap());
+
+        // Documents are checked like sandbox-store's
+        storage
+            .connection()
+            .execute("INSERT INTO documents (key, version, data) VALUES ('stats', 1, '{\"wins\": \"two\"}')", [])
+            .unwrap();
+        assert!(matches!(storage.load_statistics(), Err(StorageError::Document(StoreError::Invalid { .. }))));
+
+        // A newer version's document is neither read nor replaced
+        storage.connection().execute("UPDATE documents SET version = 99, data = '{}'", []).unwrap();
+        let newer = |result| matches!(result, Err(StorageError::Document(StoreError::VersionMismatch { expected: 1, found: 99, .. })));
+        assert!(newer(storage.load_statistics().map(|_| ())));
+        assert!(newer(storage.save(STATS_KEY, &stats)));
+        storage.remove(STATS_KEY).unwrap();
+        storage.save(STATS_KEY, &stats).unwrap();
+    }
+
+    #[test]
+    fn test_damaged_document_falls_back_to_the_one_before() {
+        let storage = Storage::open_in_memory().unwrap();
+        let stats = |games_played| Statistics { games_played, ..Statistics::default() };
+        storage.save(STATS_KEY, &stats(1)).unwrap();
+        storage.save(STATS_KEY, &stats(2)).unwrap();
+        assert_eq!(storage.load_statistics().unwrap(), stats(2));
+
+        // Still JSON matching the schema, but not what was saved
+        let damage = "UPDATE documents SET data = replace(data, '\"games_played\":', '\"games_played\":7')";
+        assert_eq!(storage.connection().execute(damage, []).unwrap(), 1);
+        assert_eq!(storage.load_statistics().unwrap(), stats(1));
+
+        // Put back, so saved again and damaged again, it's still there
+        storage.save(STATS_KEY, &stats(3)).unwrap();
+        storage.connection().execute(damage, []).unwrap();
+        assert_eq!(storage.load_statistics().unwrap(), stats(1));
+
+        storage.connection().execute(damage, []).unwrap();
+        storage.connection().execute("UPDATE documents SET previous_data = 'not json'", []).unwrap();
+        assert!(matches!(storage.load_statistics(), Err(StorageError::Document(StoreError::Checksum { .. }))));
+
+        // Saving again replaces the damaged one
+        storage.save(STATS_KEY, &stats(3)).unwrap();
+        assert_eq!(storage.load_statistics().unwrap(), stats(3));
+    }
+
+    #[test]
+    fn test_saved_game() {
+        let storage = Storage::open_in_memory().unwrap();
+        let list = WordList::from_text("rust").unwrap();
+        let mut game = GameState::new_evil(&list, "RUST".to_string(), None, Difficulty::Medium);
+        game.guess_letter('R');
+        storage.save_game(&game).unwrap();
+        assert_eq!(storage.load_game::<GameState>().unwrap().unwrap().guessed_letters, ['R']);
+
+        storage.save_game(&finished("RUST", None)).unwrap();
+        assert!(storage.load_game::<GameState>().unwrap().is_none());
+
+        storage.save_game(&WordleGame::new("CRANE")).unwrap();
+        storage.forget_game::<WordleGame>().unwrap();
+        assert!(storage.load_game::<WordleGame>().unwrap().is_none());
+    }
+
+    #[test]
+    fn test_profiles_and_achievements() {
+        let storage = Storage::open_in_memory().unwrap();
+        let mut profiles = Profiles::default();
+        profiles.create("ada").unwrap();
+        profiles.create("grace").unwrap();
+        profiles.select("grace").unwrap();
+        profiles.record(&finished("RUST", Some("ada")));
+        let mut race = finished("RUST", Some("ada"));
+        race.race = Some(Race::new(&race).unwrap());
+        profiles.record(&race);
+        profiles.record_campaign("ada", 60_000);
+        storage.save_profiles(&profiles).unwrap();
+        assert_eq!(storage.load_profiles().unwrap(), profiles);
+
+        let mut achievements = Achievements::default();
+        achievements.record(&finished("RUST", Some("ada")), 1);
+        achievements.record(&finished("RUST", None), 1);
+        storage.save_achievements(&achievements).unwrap();
+        assert_eq!(storage.load_achievements().unwrap(), achievements);
+    }
+
+    #[test]
+    fn test_games_and_guesses() {
+        let storage = Storage::open_in_memory().unwrap();
+        storage.record_game(&finished("GO", Some("ada"))).unwrap();
+        let game = finished("RUST", Some("grace"));
+        storage.record_game(&game).unwrap();
+
+        let games = storage.recent_games(None, 10).unwrap();
+        assert_eq!(games.iter().map(|game| game.word.as_str()).collect::<Vec<_>>(), ["RUST", "GO"]);
+        assert_eq!(games[0].difficulty, Difficulty::Hard);
+        assert!(games[0].won);
+        assert_eq!(games[0].score, game.score.unwrap().total);
+        let letters: Vec<Action> = games[0].guesses.iter().map(|guess| guess.action.clone()).collect();
+        assert_eq!(letters, "RUST".chars().map(Action::Letter).collect::<Vec<_>>());
+        assert!(games[0].guesses.iter().all(|guess| guess.hit));
+
+        let ada = storage.recent_games(Some("ADA"), 10).unwrap();
+        assert_eq!(ada.len(), 1);
+        assert_eq!(ada[0].word, "GO");
+        assert_eq!(storage.recent_games(None, 1).unwrap().len(), 1);
+
+        let all = storage.all_games().unwrap();
+        assert_eq!(all.iter().map(|game| game.word.as_str()).collect::<Vec<_>>(), ["GO", "RUST"]);
+    }
+
+    #[test]
+    fn test_word_stats() {
+        let storage = Storage::open_in_memory().unwrap();
+        storage.record_game(&finished("RUST", Some("ada"))).unwrap();
+        for player in [Some("ada"), Some("grace"), None] {
+            let mut lost = GameState::new("GO".to_string(), None, Difficulty::Hard);
+            lost.player = player.map(str::to_string);
+            lost.give_up();
+            storage.record_game(&lost).unwrap();
+        }
+        storage.record_game(&finished("GO", Some("ada"))).unwrap();
+
+        let stats = storage.word_stats(None).unwrap();
+        assert_eq!(stats.iter().map(|word| word.word.as_str()).collect::<Vec<_>>(), ["GO", "RUST"]);
+        assert_eq!((stats[0].played, stats[0].wins, stats[0].losses()), (4, 1, 3));
+        assert_eq!(stats[0].win_rate(), 0.25);
+
+        // Only the words lost, for one player or every one
+        let missed = storage.most_missed_words(Some("ADA"), 10).unwrap();
+        assert_eq!(missed.len(), 1);
+        assert_eq!((missed[0].word.as_str(), missed[0].win_rate()), ("GO", 0.5));
+        assert!(storage.most_missed_words(None, 0).unwrap().is_empty());
+        assert!(storage.word_stats(Some("linus")).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_import_store() {
+        let dir = tempfile::tempdir().unwrap();
+        let store = Store::open(dir.path()).unwrap();
+        let mut profiles = Profiles::default();
+        profiles.create("ada").unwrap();
+        profiles.save(&store).unwrap();
+        Statistics { wins: 4, ..Statistics::default() }.save(&store).unwrap();
+
+        let storage = Storage::open_in_memory().unwrap();
+        assert_eq!(storage.import_store(&store).unwrap(), [STATS_KEY, PROFILES_KEY]);
+        assert_eq!(storage.load_profiles().unwrap(), profiles);
+        assert_eq!(storage.load_statistics().unwrap().wins, 4);
+        assert!(!storage.is_empty().unwrap());
+    }
+}
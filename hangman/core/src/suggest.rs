@@ -0,0 +1,147 @@
+// ============================================================================
+// LETTER SUGGESTIONS
+// ============================================================================
+// Which letter to guess next: the one that tells the most about the answer,
+// over the words of the dictionary that still fit the board (ai.rs's
+// candidates). The AI guesser plays the best of them, and the player can ask
+// for the best SUGGESTIONS.
+//
+// INFORMATION GAIN:
+// Guessing a letter splits the candidates by what the board would show: the
+// places the letter would be revealed in, or a miss. With every candidate as
+// likely as the others, the bits the guess is expected to give are the
+// entropy of that split,
+//     gain = -sum(p * log2(p)), p = the share of the candidates in each part
+// A letter every candidate has in the same places gains nothing even though
+// it can't miss, one that splits them in halves gains a bit. Ties go to the
+// letter in more candidates (a guess less likely to cost a wrong one), then
+// to the one more common in English.
+//
+// Like the smart hint, it's the same for every word that fits, so it never
+// tells which of them is the answer. Letters are compared like the game
+// compares them, digraphs included (see ai.rs).
+
+use std::collections::BTreeMap;
+use crate::ai::{self, rank};
+use crate::digraphs::tokenize;
+use crate::game::GameState;
+use crate::letters::{fold, to_upper};
+use crate::words::WordList;
+
+/// How many suggestions the player gets
+pub const SUGGESTIONS: usize = 3;
+
+/// A letter to guess, and how much it's expected to tell
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Suggestion {
+    pub letter: char,
+    // Expected information gain, in bits
+    pub score: f64,
+    // How many of the candidates have the letter
+    pub words: usize,
+}
+
+/// Every unguessed letter of `candidates` of `game`, best first
+pub fn rank_letters(game: &GameState, candidates: &[&str]) -> Vec<Suggestion> {
+    let ignore_accents = game.ignore_accents;
+    // Each candidate's places of each unguessed letter, by folded letter,
+    // with the form to guess it as
+    let mut splits: BTreeMap<char, (char, BTreeMap<Vec<usize>, usize>)> = BTreeMap::new();
+    for word in candidates {
+        let mut places: BTreeMap<char, (char, Vec<usize>)> = BTreeMap::new();
+        for (place, c) in tokenize(word, &game.digraphs).into_iter().enumerate() {
+            if game.is_guessable(c) && !game.is_guessed(c) {
+                places.entry(fold(c, ignore_accents)).or_insert((to_upper(c), Vec::new())).1.push(place);
+            }
+        }
+        for (key, (letter, places)) in places {
+            *splits.entry(key).or_insert((letter, BTreeMap::new())).1.entry(places).or_insert(0) += 1;
+        }
+    }
+
+    let total = candidates.len() as f64;
+    let mut suggestions: Vec<Suggestion> = splits
+        .into_values()
+        .map(|(letter, parts)| {
+            let words: usize = parts.values().sum();
+            let misses = candidates.len() - words;
+            let score = parts
+                .into_values()
+                .chain(Some(misses))
+                .filter(|&count| count > 0)
+                .map(|count| {
+                    let p = count as f64 / total;
+                    p * (1.0 / p).log2()
+                })
+                .sum::<f64>();
+            Suggestion { letter, score, words }
+        })
+        .collect();
+    suggestions.sort_by(|a, b| {
+        b.score
+            .total_cmp(&a.score)
+            .then(b.words.cmp(&a.words))
+            .then_with(|| rank(a.letter).cmp(&rank(b.letter)))
+    });
+    suggestions
+}
+
+/// The best SUGGESTIONS letters to guess in `game` with `words` as the dictionary,
+/// and how many words still fit the board (none once the game is over, or if no word fits)
+pub fn suggest(game: &GameState, words: &WordList) -> (usize, Vec<Suggestion>) {
+    if game.game_over {
+        return (0, Vec::new());
+    }
+    let candidates = ai::candidates(game, words);
+    let mut suggestions = rank_letters(game, &candidates);
+    suggestions.truncate(SUGGESTIONS);
+    (candidates.len(), suggestions)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::difficulty::Difficulty;
+
+    fn words() -> WordList {
+        WordList::from_text("rust\nruby\nrush\njava\nperl").unwrap()
+    }
+
+    fn letters(suggestions: &[Suggestion]) -> Vec<char> {
+        suggestions.iter().map(|suggestion| suggestion.letter).collect()
+    }
+
+    #[test]
+    fn test_best_split_first() {
+        let mut game = GameState::new("RUST".to_string(), None, Difficulty::Medium);
+        // R splits the five words three ways: R___ (3), __R_ (PERL), a miss (JAVA)
+        let (left, suggestions) = suggest(&game, &words());
+        assert_eq!(left, 5);
+        // U and S split the rest as well, U is in more of them
+        assert_eq!(letters(&suggestions), ['R', 'U', 'S']);
+        assert!((suggestions[0].score - 1.371).abs() < 0.001);
+        assert_eq!(suggestions[0].words, 4);
+
+        // Every word left has U in the same place, it can't tell them apart
+        game.guess_letter('R');
+        let ranked = rank_letters(&game, &ai::candidates(&game, &words()));
+        assert_eq!(ranked.last().unwrap().letter, 'U');
+        assert_eq!(ranked.last().unwrap().score, 0.0);
+        // S is in two of the three, better than the letters of one
+        assert_eq!(ranked[0].letter, 'S');
+        assert!((ranked[0].score - 0.918).abs() < 0.001);
+
+        game.guess_word("RUST");
+        assert_eq!(suggest(&game, &words()), (0, Vec::new()));
+    }
+
+    #[test]
+    fn test_places_matter() {
+        // A is in both, but where tells them apart
+        let words = WordList::from_text("java\naaxx").unwrap();
+        let game = GameState::new("JAVA".to_string(), None, Difficulty::Medium);
+        let (_, suggestions) = suggest(&game, &words);
+        assert_eq!(suggestions[0].letter, 'A');
+        assert_eq!(suggestions[0].score, 1.0);
+    }
+}
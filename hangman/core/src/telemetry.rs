@@ -0,0 +1,373 @@
+// ============================================================================
+// GAMEPLAY TELEMETRY
+// ============================================================================
+// Anonymous numbers about the games played (how long the words are, how many
+// guesses they take), strictly opt-in: nothing is collected unless the
+// player turns the `telemetry` setting on, and turning it off again forgets
+// whatever hadn't gone out yet.
+//
+// WHAT'S COLLECTED:
+// A TelemetryEvent when a game starts, and one when it's won or lost: the
+// letters in the word, the guesses made (letters and words, hints not
+// counted), the difficulty, and the mode. Never the word, the guesses
+// themselves, the profile, the session, or when it was played, so an event
+// can't be tied back to a player or a game.
+//
+// BATCHES:
+// Events wait in GameTelemetry until BATCH_SIZE of them are there (or the app
+// closes), then go out together as a TelemetryBatch:
+// - to the local file (TELEMETRY_FILE in the app data directory), one JSON
+//   event per line, unless an endpoint is set
+// - with the telemetry_endpoint setting, POSTed to it as a JSON array
+// A batch that can't be sent is dropped, it's not worth retrying.
+//
+// SEEING AND CLEARING IT:
+// The desktop app shows the events waiting and the ones in the local file
+// (get_telemetry), and forgets both on clear_telemetry. Events posted to an
+// endpoint are out of the app's hands.
+//
+// THE HTTP CLIENT:
+// Posting needs the "telemetry" feature (ureq, like the dictionary), which
+// the desktop app turns on. Collecting and the local file don't.
+
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::fs::{self, OpenOptions};
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+use crate::difficulty::Difficulty;
+use crate::evil::GameMode;
+use crate::game::GameState;
+use crate::history::Action;
+use crate::settings::Settings;
+use crate::words::letter_count;
+
+/// Where batches are written without an endpoint, in the app data directory
+pub const TELEMETRY_FILE: &str = "telemetry.jsonl";
+
+/// Events collected before they're sent
+pub const BATCH_SIZE: usize = 10;
+
+/// What happened to a game
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum TelemetryKind {
+    Started,
+    Won,
+    Lost,
+}
+
+/// One anonymous event, everything that's sent about a game
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TelemetryEvent {
+    pub kind: TelemetryKind,
+    // Letters in the word, spaces not counted
+    pub word_length: usize,
+    // Letter and word guesses made (0 when the game starts)
+    pub guesses: usize,
+    pub difficulty: Difficulty,
+    pub mode: GameMode,
+}
+
+impl TelemetryEvent {
+    /// A game that was just started
+    pub fn started(game: &GameState) -> Self {
+        TelemetryEvent {
+            kind: TelemetryKind::Started,
+            ..Self::finished(game)
+        }
+    }
+
+    /// A game that was just won or lost
+    pub fn finished(game: &GameState) -> Self {
+        TelemetryEvent {
+            kind: if game.won { TelemetryKind::Won } else { TelemetryKind::Lost },
+            word_length: letter_count(&game.word),
+            guesses: game
+                .history
+                .iter()
+                .filter(|made| !matches!(made.action, Action::Hint(_)))
+                .count(),
+            difficulty: game.difficulty,
+            mode: game.mode(),
+        }
+    }
+}
+
+/// Reasons a batch couldn't be sent, or the local file read or cleared
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TelemetryError {
+    /// The local file couldn't be written, read, or removed
+    Io(String),
+    /// Posting to the endpoint failed (no connection, a server error, or a
+    /// build without the "telemetry" feature)
+    Http(String),
+}
+
+impl fmt::Display for TelemetryError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            TelemetryError::Io(message) => write!(f, "telemetry file: {}", message),
+            TelemetryError::Http(message) => write!(f, "telemetry endpoint: {}", message),
+        }
+    }
+}
+
+impl std::error::Error for TelemetryError {}
+
+impl From<io::Error> for TelemetryError {
+    fn from(err: io::Error) -> Self {
+        TelemetryError::Io(err.to_string())
+    }
+}
+
+/// Where a batch goes
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TelemetrySink {
+    File(PathBuf),
+    Endpoint(String),
+}
+
+impl fmt::Display for TelemetrySink {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            TelemetrySink::File(path) => write!(f, "{}", path.display()),
+            TelemetrySink::Endpoint(url) => write!(f, "{}", url),
+        }
+    }
+}
+
+/// Events on their way out
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TelemetryBatch {
+    pub events: Vec<TelemetryEvent>,
+    pub sink: TelemetrySink,
+}
+
+impl TelemetryBatch {
+    /// Write the batch to its file or post it to its endpoint
+    /// Blocks until it's done, call it off the main thread
+    pub fn send(&self) -> Result<(), TelemetryError> {
+        match &self.sink {
+            TelemetrySink::File(path) => {
+                let mut lines = String::new();
+                for event in &self.events {
+                    lines.push_str(&serde_json::to_string(event).expect("telemetry events serialize"));
+                    lines.push('\n');
+                }
+                if let Some(dir) = path.parent() {
+                    fs::create_dir_all(dir)?;
+                }
+                OpenOptions::new().create(true).append(true).open(path)?.write_all(lines.as_bytes())?;
+                Ok(())
+            }
+            TelemetrySink::Endpoint(url) => post(url, &self.events),
+        }
+    }
+}
+
+#[cfg(feature = "telemetry")]
+fn post(url: &str, events: &[TelemetryEvent]) -> Result<(), TelemetryError> {
+    /// Seconds a post may take before it's given up on
+    const TIMEOUT_SECS: u64 = 5;
+
+    let body = serde_json::to_string(events).expect("telemetry events serialize");
+    ureq::post(url)
+        .timeout(std::time::Duration::from_secs(TIMEOUT_SECS))
+        .set("Content-Type", "application/json")
+        .send_string(&body)
+        .map_err(|err| TelemetryError::Http(err.to_string()))?;
+    Ok(())
+}
+
+#[cfg(not(feature = "telemetry"))]
+fn post(_url: &str, _events: &[TelemetryEvent]) -> Result<(), TelemetryError> {
+    Err(TelemetryError::Http("built without the telemetry feature".to_string()))
+}
+
+/// The events collected and not sent yet, and where they'll go
+#[derive(Debug, Clone)]
+pub struct GameTelemetry {
+    enabled: bool,
+    // The local file, written without an endpoint and read back by saved()
+    file: PathBuf,
+    endpoint: Option<String>,
+    pending: Vec<TelemetryEvent>,
+}
+
+impl GameTelemetry {
+    /// Telemetry writing to `file` once `settings` turn it on
+    pub fn new(file: PathBuf, settings: &Settings) -> Self {
+        let mut telemetry = GameTelemetry {
+            enabled: false,
+            file,
+            endpoint: None,
+            pending: Vec::new(),
+        };
+        telemetry.configure(settings);
+        telemetry
+    }
+
+    /// Follow changed settings, turning telemetry off forgets the events waiting
+    pub fn configure(&mut self, settings: &Settings) {
+        self.enabled = settings.telemetry;
+        self.endpoint = settings.telemetry_endpoint.clone();
+        if !self.enabled {
+            self.pending.clear();
+        }
+    }
+
+    pub fn is_enabled(&self) -> bool {
+        self.enabled
+    }
+
+    /// Where the batches go now
+    pub fn sink(&self) -> TelemetrySink {
+        match &self.endpoint {
+            Some(url) => TelemetrySink::Endpoint(url.clone()),
+            None => TelemetrySink::File(self.file.clone()),
+        }
+    }
+
+    /// Collect an event (nothing while telemetry is off)
+    /// Returns the batch to send once it's full
+    pub fn record(&mut self, event: TelemetryEvent) -> Option<TelemetryBatch> {
+        if !self.enabled {
+            return None;
+        }
+        self.pending.push(event);
+        if self.pending.len() < BATCH_SIZE {
+            return None;
+        }
+        self.take()
+    }
+
+    /// The events waiting as a batch to send now, e.g. when the app closes
+    pub fn take(&mut self) -> Option<TelemetryBatch> {
+        if self.pending.is_empty() {
+            return None;
+        }
+        Some(TelemetryBatch {
+            events: std::mem::take(&mut self.pending),
+            sink: self.sink(),
+        })
+    }
+
+    /// Events collected and not sent yet
+    pub fn pending(&self) -> &[TelemetryEvent] {
+        &self.pending
+    }
+
+    /// Events in the local file (a line that can't be read is skipped)
+    pub fn saved(&self) -> Result<Vec<TelemetryEvent>, TelemetryError> {
+        read_file(&self.file)
+    }
+
+    /// Forget the events waiting and remove the local file
+    pub fn clear(&mut self) -> Result<(), TelemetryError> {
+        self.pending.clear();
+        match fs::remove_file(&self.file) {
+            Err(err) if err.kind() != io::ErrorKind::NotFound => Err(err.into()),
+            _ => Ok(()),
+        }
+    }
+}
+
+/// The events written to a telemetry file, none if there isn't one
+fn read_file(path: &Path) -> Result<Vec<TelemetryEvent>, TelemetryError> {
+    let text = match fs::read_to_string(path) {
+        Ok(text) => text,
+        Err(err) if err.kind() == io::ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(err) => return Err(err.into()),
+    };
+    Ok(text.lines().filter_map(|line| serde_json::from_str(line).ok()).collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn opted_in() -> Settings {
+        Settings {
+            telemetry: true,
+            ..Settings::default()
+        }
+    }
+
+    fn won_game() -> GameState {
+        let mut game = GameState::new("ICE CREAM".to_string(), None, Difficulty::Hard);
+        game.guess_letter('X');
+        game.use_hint().unwrap();
+        game.guess_word("ice cream");
+        game
+    }
+
+    #[test]
+    fn test_events_are_anonymous() {
+        let game = won_game();
+        let started = TelemetryEvent::started(&game);
+        assert_eq!((started.kind, started.word_length), (TelemetryKind::Started, 8));
+        assert_eq!(
+            TelemetryEvent::finished(&game),
+            TelemetryEvent {
+                kind: TelemetryKind::Won,
+                word_length: 8,
+                guesses: 2,
+                difficulty: Difficulty::Hard,
+                mode: GameMode::Classic,
+            }
+        );
+        let json = serde_json::to_string(&TelemetryEvent::finished(&game)).unwrap();
+        assert!(!json.to_uppercase().contains("ICE"));
+    }
+
+    #[test]
+    fn test_nothing_collected_while_off() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut telemetry = GameTelemetry::new(dir.path().join(TELEMETRY_FILE), &Settings::default());
+        assert!(telemetry.record(TelemetryEvent::started(&won_game())).is_none());
+        assert!(telemetry.pending().is_empty());
+
+        telemetry.configure(&opted_in());
+        telemetry.record(TelemetryEvent::started(&won_game()));
+        assert_eq!(telemetry.pending().len(), 1);
+        // Opting out forgets what wasn't sent
+        telemetry.configure(&Settings::default());
+        assert!(telemetry.pending().is_empty());
+    }
+
+    #[test]
+    fn test_batches_go_to_the_file() {
+        let dir = tempfile::tempdir().unwrap();
+        let file = dir.path().join(TELEMETRY_FILE);
+        let mut telemetry = GameTelemetry::new(file.clone(), &opted_in());
+        let event = TelemetryEvent::finished(&won_game());
+        for _ in 1..BATCH_SIZE {
+            assert!(telemetry.record(event.clone()).is_none());
+        }
+        let batch = telemetry.record(event.clone()).unwrap();
+        assert_eq!(batch.events.len(), BATCH_SIZE);
+        assert_eq!(batch.sink, TelemetrySink::File(file));
+        assert!(telemetry.pending().is_empty());
+
+        batch.send().unwrap();
+        telemetry.record(event.clone());
+        telemetry.take().unwrap().send().unwrap();
+        assert_eq!(telemetry.saved().unwrap(), vec![event; BATCH_SIZE + 1]);
+
+        telemetry.clear().unwrap();
+        assert!(telemetry.saved().unwrap().is_empty());
+        telemetry.clear().unwrap();
+    }
+
+    #[test]
+    fn test_endpoint() {
+        let settings = Settings {
+            telemetry_endpoint: Some("https://example.com/events".to_string()),
+            ..opted_in()
+        };
+        let telemetry = GameTelemetry::new(PathBuf::from(TELEMETRY_FILE), &settings);
+        assert_eq!(telemetry.sink(), TelemetrySink::Endpoint("https://example.com/events".to_string()));
+        assert_eq!(telemetry.sink().to_string(), "https://example.com/events");
+    }
+}
@@ -0,0 +1,165 @@
+// ============================================================================
+// WORD TRIE
+// ============================================================================
+// An index of a word list's words by their characters, so the queries that
+// look at every word (a pattern search, the words starting with something,
+// the words of an evil game) only go down the branches that can still fit,
+// instead of through the whole list. With a 100,000 word list a board with a
+// few letters shown is answered from a few hundred nodes.
+//
+// THE NODES:
+// One node per prefix of the words, the root for the empty one. A node keeps
+// its children by the next character (sorted, a few dozen at most), the
+// positions in the list of the words that end there, and a mask of how many
+// characters the words below it still have, so a query for words of one
+// length skips the branches that have none.
+//
+// CHARACTERS:
+// The words are indexed as they're spelled (uppercase, accented letters as
+// they are), and the queries decide what fits: an accent-insensitive one just
+// lets "E" through as both E and É. Digraphs aren't units here, a pattern
+// with digraphs is searched without the trie (see pattern.rs).
+//
+// ORDER:
+// Queries return the positions sorted, so the words come back in the list's
+// order like a scan through it would give them.
+
+/// A node of the trie
+#[derive(Debug, Clone, Default)]
+struct Node {
+    // By character, sorted
+    children: Vec<(char, usize)>,
+    // Positions of the words that end here
+    ends: Vec<usize>,
+    // Bit n is set if a word below has n more characters (bit 0: one ends here)
+    lengths: u64,
+}
+
+/// An index of words by their characters
+#[derive(Debug, Clone)]
+pub struct Trie {
+    // The root is the first
+    nodes: Vec<Node>,
+}
+
+/// Bit of a word `len` characters long, the longest ones share the last
+fn length_bit(len: usize) -> u64 {
+    1 << len.min(63)
+}
+
+impl Trie {
+    /// The trie of `words`, each under its position
+    pub fn new<S: AsRef<str>>(words: &[S]) -> Self {
+        let mut trie = Trie { nodes: vec![Node::default()] };
+        for (position, word) in words.iter().enumerate() {
+            trie.insert(word.as_ref(), position);
+        }
+        trie
+    }
+
+    fn insert(&mut self, word: &str, position: usize) {
+        let chars: Vec<char> = word.chars().collect();
+        let mut node = 0;
+        for (depth, &c) in chars.iter().enumerate() {
+            self.nodes[node].lengths |= length_bit(chars.len() - depth);
+            node = match self.nodes[node].children.binary_search_by_key(&c, |&(key, _)| key) {
+                Ok(i) => self.nodes[node].children[i].1,
+                Err(i) => {
+                    let child = self.nodes.len();
+                    self.nodes.push(Node::default());
+                    self.nodes[node].children.insert(i, (c, child));
+                    child
+                }
+            };
+        }
+        self.nodes[node].lengths |= length_bit(0);
+        self.nodes[node].ends.push(position);
+    }
+
+    /// Positions of the words of `len` characters whose character at each
+    /// place fits (`fits(place, character)`)
+    pub fn matching(&self, len: usize, fits: impl Fn(usize, char) -> bool) -> Vec<usize> {
+        let mut positions = Vec::new();
+        self.walk(0, 0, len, &fits, &mut positions);
+        positions.sort_unstable();
+        positions
+    }
+
+    /// Walk `node`, at `depth` characters, to the words `len` long under it
+    fn walk(&self, node: usize, depth: usize, len: usize, fits: &impl Fn(usize, char) -> bool, positions: &mut Vec<usize>) {
+        let node = &self.nodes[node];
+        if node.lengths & length_bit(len - depth) == 0 {
+            return;
+        }
+        if depth == len {
+            positions.extend(&node.ends);
+            return;
+        }
+        for &(c, child) in &node.children {
+            if fits(depth, c) {
+                self.walk(child, depth + 1, len, fits, positions);
+            }
+        }
+    }
+
+    /// Positions of the words starting with `prefix` characters that fit
+    /// (`fits(place, character)` for each of the first `prefix` places)
+    pub fn starting(&self, prefix: usize, fits: impl Fn(usize, char) -> bool) -> Vec<usize> {
+        let mut nodes = vec![0];
+        for depth in 0..prefix {
+            nodes = nodes
+                .into_iter()
+                .flat_map(|node| self.nodes[node].children.iter())
+                .filter(|&&(c, _)| fits(depth, c))
+                .map(|&(_, child)| child)
+                .collect();
+        }
+        let mut positions = Vec::new();
+        while let Some(node) = nodes.pop() {
+            positions.extend(&self.nodes[node].ends);
+            nodes.extend(self.nodes[node].children.iter().map(|&(_, child)| child));
+        }
+        positions.sort_unstable();
+        positions
+    }
+
+    /// How many nodes the trie has (the root included)
+    pub fn len(&self) -> usize {
+        self.nodes.len()
+    }
+
+    /// Whether no word is indexed
+    pub fn is_empty(&self) -> bool {
+        self.nodes[0].children.is_empty() && self.nodes[0].ends.is_empty()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const WORDS: [&str; 6] = ["RUST", "RUSTY", "RUN", "CAFÉ", "CAFE", "ICE CREAM"];
+
+    #[test]
+    fn test_matching_by_place() {
+        let trie = Trie::new(&WORDS);
+        assert_eq!(trie.len(), 21);
+        assert_eq!(trie.matching(4, |_, _| true), [0, 3, 4]);
+        assert_eq!(trie.matching(4, |place, c| place != 0 || c == 'R'), [0]);
+        assert_eq!(trie.matching(3, |place, c| place != 2 || c == 'N'), [2]);
+        assert_eq!(trie.matching(9, |place, c| (place == 3) == (c == ' ')), [5]);
+        assert!(trie.matching(7, |_, _| true).is_empty());
+        assert!(trie.matching(0, |_, _| true).is_empty());
+    }
+
+    #[test]
+    fn test_starting() {
+        let trie = Trie::new(&WORDS);
+        assert_eq!(trie.starting(3, |place, c| c == ['R', 'U', 'S'][place]), [0, 1]);
+        assert_eq!(trie.starting(2, |place, c| c == ['R', 'U'][place]), [0, 1, 2]);
+        assert_eq!(trie.starting(4, |place, c| c == ['C', 'A', 'F', 'É'][place]), [3]);
+        assert_eq!(trie.starting(0, |_, _| false).len(), WORDS.len());
+        assert!(trie.starting(1, |_, c| c == 'X').is_empty());
+        assert!(Trie::new::<&str>(&[]).is_empty());
+    }
+}
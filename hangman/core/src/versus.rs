@@ -0,0 +1,317 @@
+// ============================================================================
+// HEAD-TO-HEAD ON A NETWORK
+// ============================================================================
+// Two apps on the same network race each other on the same word at the same
+// time: one hosts the duel and the other joins it with the join code, the
+// way online multiplayer games are joined (see multiplayer.rs). Unlike an
+// online multiplayer game, each player has a board of their own, and both
+// boards are the host's: the guest sends its guesses and is sent its board
+// back, with how the host is doing on theirs (never the host's letters).
+//
+// WHO WINS:
+// The host picks the rule (VersusRule):
+// - first solve: the first to complete the word wins, and the other board is
+//   given up (so its player sees the word too)
+// - fewest wrong guesses: both boards are played to the end, and of the
+//   players who completed the word the one with fewer wrong guesses wins
+//   (with as many, the first to complete it)
+// If both are hanged it's a draw.
+// The boards are classic, untimed games with the host's settings (an evil
+// word would change to dodge one player's guesses and not the other's).
+//
+// MESSAGES:
+// The guest sends ClientMessages like a multiplayer guest (join, letter,
+// word). The host answers with VersusMessages: welcome (once the code was
+// right), update (after either player's move, with the error if the guest's
+// move was refused), refused, closed. The connections themselves are the
+// desktop app's (see src-tauri/src/versus.rs).
+
+use sandbox_schema::hangman::{GameError, VersusView};
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use crate::game::GameState;
+use crate::guess::GuessError;
+use crate::multiplayer::ProtocolError;
+
+/// Port a duel is hosted on unless another one is picked (next to multiplayer's)
+pub const DEFAULT_VERSUS_PORT: u16 = 7879;
+
+/// One of the two players
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Side {
+    Host,
+    Guest,
+}
+
+impl Side {
+    pub fn other(self) -> Side {
+        match self {
+            Side::Host => Side::Guest,
+            Side::Guest => Side::Host,
+        }
+    }
+}
+
+/// How the winner of a duel is decided
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum VersusRule {
+    #[default]
+    FirstSolve,
+    FewestWrongGuesses,
+}
+
+/// How a duel ended
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum VersusWinner {
+    Host,
+    Guest,
+    // Both hanged
+    Draw,
+}
+
+/// Reasons a duel can't be started, or a move in it isn't made
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum VersusError {
+    /// Duels are played in the classic mode
+    EvilMode,
+    /// The duel has a winner
+    Decided,
+    /// The player's board is over (they're hanged), the other one plays on
+    BoardOver,
+    /// The letter can't be guessed
+    Guess(GuessError),
+}
+
+impl fmt::Display for VersusError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            VersusError::EvilMode => write!(f, "duels are played in the classic mode"),
+            VersusError::Decided => write!(f, "the duel is over"),
+            VersusError::BoardOver => write!(f, "your board is over, wait for the other player"),
+            VersusError::Guess(err) => write!(f, "{}", err),
+        }
+    }
+}
+
+impl std::error::Error for VersusError {}
+
+impl From<GuessError> for VersusError {
+    fn from(err: GuessError) -> Self {
+        VersusError::Guess(err)
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Versus {
+    pub host: GameState,
+    pub guest: GameState,
+    pub rule: VersusRule,
+    // Who completed the word first, if anyone did
+    pub first_solver: Option<Side>,
+    pub winner: Option<VersusWinner>,
+}
+
+impl Versus {
+    /// A duel on the word of `game` (a new classic game with the host's settings),
+    /// the host's board being `game` and the guest's a copy of it
+    pub fn new(mut game: GameState, rule: VersusRule) -> Result<Self, VersusError> {
+        if game.candidates.is_some() {
+            return Err(VersusError::EvilMode);
+        }
+        game.time_limit = None;
+        game.deadline = None;
+        let mut guest = GameState::new(game.word.clone(), game.category.clone(), game.difficulty);
+        guest.max_wrong_guesses = game.max_wrong_guesses;
+        guest.ignore_accents = game.ignore_accents;
+        guest.letter_case = game.letter_case;
+        guest.display = game.display.clone();
+        guest.alphabet = game.alphabet.clone();
+        guest.digraphs = game.digraphs.clone();
+        guest.charset = game.charset.clone();
+        Ok(Versus {
+            host: game,
+            guest,
+            rule,
+            first_solver: None,
+            winner: None,
+        })
+    }
+
+    pub fn board(&self, side: Side) -> &GameState {
+        match side {
+            Side::Host => &self.host,
+            Side::Guest => &self.guest,
+        }
+    }
+
+    /// The board of a player who can still move
+    fn playable_board(&mut self, side: Side) -> Result<&mut GameState, VersusError> {
+        if self.winner.is_some() {
+            return Err(VersusError::Decided);
+        }
+        let board = match side {
+            Side::Host => &mut self.host,
+            Side::Guest => &mut self.guest,
+        };
+        if board.game_over {
+            return Err(VersusError::BoardOver);
+        }
+        Ok(board)
+    }
+
+    /// A letter guess (as typed) on a player's board
+    pub fn guess_letter(&mut self, side: Side, input: &str) -> Result<(), VersusError> {
+        let board = self.playable_board(side)?;
+        let letter = board.check_letter(input)?;
+        board.guess_letter(letter);
+        self.settle();
+        Ok(())
+    }
+
+    /// A guess at the whole word on a player's board, whether it was right
+    pub fn guess_word(&mut self, side: Side, guess: &str) -> Result<bool, VersusError> {
+        let hit = self.playable_board(side)?.guess_word(guess);
+        self.settle();
+        Ok(hit)
+    }
+
+    /// Declare the winner once there is one
+    fn settle(&mut self) {
+        if self.first_solver.is_none() {
+            self.first_solver = [Side::Host, Side::Guest].into_iter().find(|&side| self.board(side).won);
+        }
+        let Some(first) = self.first_solver else {
+            if self.host.game_over && self.guest.game_over {
+                self.winner = Some(VersusWinner::Draw);
+            }
+            return;
+        };
+        let winner = match self.rule {
+            VersusRule::FirstSolve => {
+                self.host.give_up();
+                self.guest.give_up();
+                first
+            }
+            VersusRule::FewestWrongGuesses if self.host.game_over && self.guest.game_over => {
+                let (host, guest) = (&self.host, &self.guest);
+                match (host.won, guest.won) {
+                    (true, true) if host.wrong_guesses != guest.wrong_guesses => {
+                        if host.wrong_guesses < guest.wrong_guesses { Side::Host } else { Side::Guest }
+                    }
+                    (true, false) => Side::Host,
+                    (false, true) => Side::Guest,
+                    _ => first,
+                }
+            }
+            VersusRule::FewestWrongGuesses => return,
+        };
+        self.winner = Some(match winner {
+            Side::Host => VersusWinner::Host,
+            Side::Guest => VersusWinner::Guest,
+        });
+    }
+
+    /// Letters of the word (counted once per place), and how many of them a player found
+    pub fn progress(&self, side: Side) -> (u32, u32) {
+        let board = self.board(side);
+        let letters = board.tokens().into_iter().filter(|&c| board.is_guessable(c)).count() as u32;
+        let hidden = board.mask().chars().filter(|&c| c == '_').count() as u32;
+        (letters, letters - hidden)
+    }
+}
+
+/// What the host of a duel sends the guest
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum VersusMessage {
+    /// The duel as it is when the guest is let in
+    Welcome { versus: VersusView },
+    /// The duel after a move (either player's), `error` set if it refused the guest's
+    Update { versus: VersusView, error: Option<GameError> },
+    /// The guest wasn't let in, the connection closes
+    Refused { reason: String },
+    /// The host stopped the duel
+    Closed { reason: String },
+}
+
+impl VersusMessage {
+    pub fn to_json(&self) -> String {
+        serde_json::to_string(self).expect("duel messages are plain JSON")
+    }
+
+    pub fn from_json(json: &str) -> Result<Self, ProtocolError> {
+        serde_json::from_str(json).map_err(|err| ProtocolError(err.to_string()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::difficulty::Difficulty;
+    use crate::game::WRONG_WORD_PENALTY;
+
+    fn duel(word: &str, rule: VersusRule) -> Versus {
+        Versus::new(GameState::new(word.to_string(), None, Difficulty::Medium), rule).unwrap()
+    }
+
+    #[test]
+    fn test_first_to_complete_the_word_wins() {
+        let mut versus = duel("RUST", VersusRule::FirstSolve);
+        versus.guess_letter(Side::Host, "r").unwrap();
+        versus.guess_letter(Side::Guest, "u").unwrap();
+        // Each board has only its own player's letters
+        assert!(!versus.guest.is_guessed('R') && !versus.host.is_guessed('U'));
+        assert_eq!(versus.progress(Side::Host), (4, 1));
+        let update = VersusMessage::Update { versus: versus.view(Side::Guest), error: None };
+        assert_eq!(VersusMessage::from_json(&update.to_json()).unwrap(), update);
+        assert!(!update.to_json().contains("RUST"));
+        assert_eq!(versus.guess_letter(Side::Host, "r"), Err(VersusError::Guess(GuessError::AlreadyGuessed('R'))));
+
+        assert_eq!(versus.guess_word(Side::Guest, "rust"), Ok(true));
+        assert_eq!(versus.winner, Some(VersusWinner::Guest));
+        assert!(versus.host.game_over && versus.host.gave_up);
+        assert_eq!(versus.guess_letter(Side::Host, "s"), Err(VersusError::Decided));
+    }
+
+    #[test]
+    fn test_fewest_wrong_guesses() {
+        let mut versus = duel("RUST", VersusRule::FewestWrongGuesses);
+        versus.guess_word(Side::Host, "rust").unwrap();
+        // The guest plays on, and can still win with fewer wrong guesses
+        assert_eq!((versus.first_solver, versus.winner), (Some(Side::Host), None));
+        assert_eq!(versus.guess_letter(Side::Host, "a"), Err(VersusError::BoardOver));
+        versus.guess_word(Side::Guest, "rust").unwrap();
+        // As many wrong guesses: the first to complete the word wins
+        assert_eq!(versus.winner, Some(VersusWinner::Host));
+
+        let mut versus = duel("RUST", VersusRule::FewestWrongGuesses);
+        versus.guess_word(Side::Guest, "ruby").unwrap();
+        versus.guess_word(Side::Guest, "rust").unwrap();
+        assert_eq!(versus.guest.wrong_guesses, WRONG_WORD_PENALTY);
+        versus.guess_letter(Side::Host, "x").unwrap();
+        versus.guess_word(Side::Host, "rust").unwrap();
+        assert_eq!(versus.winner, Some(VersusWinner::Host));
+    }
+
+    #[test]
+    fn test_both_hanged() {
+        let mut versus = duel("RUST", VersusRule::FirstSolve);
+        for letter in ["a", "b", "c", "d", "e", "f"] {
+            versus.guess_letter(Side::Host, letter).unwrap();
+        }
+        assert!(versus.host.game_over && versus.winner.is_none());
+        assert_eq!(versus.guess_letter(Side::Host, "g"), Err(VersusError::BoardOver));
+        for letter in ["a", "b", "c", "d", "e", "f"] {
+            versus.guess_letter(Side::Guest, letter).unwrap();
+        }
+        assert_eq!(versus.winner, Some(VersusWinner::Draw));
+
+        let evil = GameState::new_evil(
+            &crate::words::WordList::from_text("rust\nruby").unwrap(),
+            "RUST".to_string(),
+            None,
+            Difficulty::Medium,
+        );
+        assert_eq!(Versus::new(evil, VersusRule::FirstSolve).unwrap_err(), VersusError::EvilMode);
+    }
+}
@@ -0,0 +1,1850 @@
+// ============================================================================
+// MESSAGES FOR THE FRONTEND
+// ============================================================================
+// The desktop app never sends GameState, Settings, or Statistics to the
+// webview directly. It sends the sandbox-schema types instead, which have a
+// schema_version field and generated TypeScript definitions, so the Rust and
+// JavaScript sides can't disagree about field names.
+//
+// This file converts between the two. Adding a field to a core type means
+// adding it to the schema type too (the compiler points here when it's missing).
+
+use sandbox_schema::hangman as schema;
+use sandbox_schema::SCHEMA_VERSION;
+use crate::achievements::{Achievement, Achievements, Unlocked, ACHIEVEMENTS};
+use crate::ai::{self, AiMove};
+use crate::arcade::{Run, RunError, WINS_PER_LIFE};
+use crate::audit::{AuditEntry, AuditLog};
+use crate::campaign::{CampaignError, Progress, LEVELS};
+use crate::crossword::{CrosswordError, CrosswordGame, Direction};
+use crate::custom_words::ImportReport;
+use crate::ingest::CleanReport;
+use crate::languages::{Language, DEFAULT_LANGUAGE};
+use crate::definitions::Definition;
+use crate::difficulty::Difficulty;
+use crate::digraphs::{spell, spell_str};
+use crate::duel::{Duel, DuelError};
+use crate::elo::{ai_rating, RatingPoint, Ratings};
+use crate::evil::GameMode;
+use crate::gallows::ArtStyle;
+use crate::game::{GameState, HintError, KeyState, PauseError};
+use crate::guess::{GuessError, LetterOutcome};
+use crate::history::{Action, Move, UndoError};
+use crate::matches::{Match, MatchError, MatchWinner, Rotation};
+use crate::metrics::{CommandMetrics, CommandSummary};
+use crate::powerups::{Inventory, PowerUp, PowerUpError, MAX_HELD, POWER_UPS};
+use crate::profiles::{Profile, Profiles};
+use crate::race::{RaceError, RaceWinner, Turn};
+use crate::rating::WordRating;
+use crate::replay::{Replay, ReplayError};
+use crate::score::Score;
+use crate::secret::SecretError;
+use crate::online_words::WordSource;
+use crate::pattern::{Pattern, PatternError, MAX_MATCHES};
+use crate::positional::{PositionalError, PositionalGame};
+use crate::providers::{ProviderHealth, WordProviders};
+use crate::settings::{DisplayFormat, LetterCase, Settings, SETTINGS_VERSION};
+use crate::shop::{Help, ShopError, Wallet, HELPS, WIN_POINTS};
+use crate::suggest;
+#[cfg(feature = "sqlite")]
+use crate::storage::WordStats;
+use crate::telemetry::{GameTelemetry, TelemetryEvent, TelemetryKind};
+use crate::versus::{Side, Versus, VersusError, VersusRule, VersusWinner};
+use crate::wordle::{Feedback, WordleError, WordleGame, WORD_LEN};
+use crate::words::PlayableError;
+use crate::words::WordList;
+
+impl From<Difficulty> for schema::Difficulty {
+    fn from(difficulty: Difficulty) -> Self {
+        match difficulty {
+            Difficulty::Easy => schema::Difficulty::Easy,
+            Difficulty::Medium => schema::Difficulty::Medium,
+            Difficulty::Hard => schema::Difficulty::Hard,
+        }
+    }
+}
+
+impl From<schema::Difficulty> for Difficulty {
+    fn from(difficulty: schema::Difficulty) -> Self {
+        match difficulty {
+            schema::Difficulty::Easy => Difficulty::Easy,
+            schema::Difficulty::Medium => Difficulty::Medium,
+            schema::Difficulty::Hard => Difficulty::Hard,
+        }
+    }
+}
+
+impl From<GameMode> for schema::GameMode {
+    fn from(mode: GameMode) -> Self {
+        match mode {
+            GameMode::Classic => schema::GameMode::Classic,
+            GameMode::Evil => schema::GameMode::Evil,
+        }
+    }
+}
+
+impl From<Rotation> for schema::MatchRotation {
+    fn from(rotation: Rotation) -> Self {
+        match rotation {
+            Rotation::Difficulty => schema::MatchRotation::Difficulty,
+            Rotation::Category => schema::MatchRotation::Category,
+        }
+    }
+}
+
+impl From<schema::MatchRotation> for Rotation {
+    fn from(rotation: schema::MatchRotation) -> Self {
+        match rotation {
+            schema::MatchRotation::Difficulty => Rotation::Difficulty,
+            schema::MatchRotation::Category => Rotation::Category,
+        }
+    }
+}
+
+impl From<MatchWinner> for schema::MatchWinner {
+    fn from(winner: MatchWinner) -> Self {
+        match winner {
+            MatchWinner::Player => schema::MatchWinner::Player,
+            MatchWinner::Hangman => schema::MatchWinner::Hangman,
+        }
+    }
+}
+
+impl Match {
+    /// The match's score, as the rounds' GameViews show it
+    pub fn view(&self) -> schema::MatchView {
+        schema::MatchView {
+            schema_version: SCHEMA_VERSION,
+            round: self.round,
+            rounds: self.rounds,
+            rotation: self.rotation.into(),
+            wins: self.wins(),
+            losses: self.losses(),
+            to_win: self.to_win(),
+            score: self.score(),
+            winner: self.winner().map(schema::MatchWinner::from),
+            can_continue: self.round_finished() && !self.is_over(),
+        }
+    }
+}
+
+impl Duel {
+    /// The duel's score, as the rounds' GameViews show it
+    pub fn view(&self) -> schema::DuelView {
+        schema::DuelView {
+            schema_version: SCHEMA_VERSION,
+            round: self.round,
+            setter: self.setter,
+            guesser: self.guesser(),
+            player_one_wins: self.wins(1),
+            player_two_wins: self.wins(2),
+            can_rematch: self.round_finished(),
+        }
+    }
+}
+
+impl From<DuelError> for schema::GameError {
+    fn from(err: DuelError) -> Self {
+        match err {
+            DuelError::NotOver => schema::GameError::GameNotOver,
+            DuelError::Secret(err) => err.into(),
+            DuelError::NotADuel => schema::GameError::invalid_input(err.to_string()),
+        }
+    }
+}
+
+impl From<MatchError> for schema::GameError {
+    fn from(err: MatchError) -> Self {
+        schema::GameError::invalid_input(err.to_string())
+    }
+}
+
+impl Run {
+    /// The run's lives and score, as its games' GameViews show them
+    pub fn view(&self) -> schema::RunView {
+        schema::RunView {
+            schema_version: SCHEMA_VERSION,
+            word: self.word,
+            lives: self.lives(),
+            start_lives: self.start_lives,
+            streak: self.streak(),
+            wins_per_life: WINS_PER_LIFE,
+            wins: self.wins(),
+            score: self.score(),
+            over: self.is_over(),
+            can_continue: self.word_finished() && !self.is_over(),
+        }
+    }
+}
+
+impl From<RunError> for schema::GameError {
+    fn from(err: RunError) -> Self {
+        schema::GameError::invalid_input(err.to_string())
+    }
+}
+
+impl Progress {
+    /// Every level and how the player did on it, for get_campaign_progress
+    pub fn view(&self) -> schema::CampaignView {
+        schema::CampaignView {
+            schema_version: SCHEMA_VERSION,
+            levels: LEVELS
+                .iter()
+                .zip(1..)
+                .map(|(found, level)| {
+                    let result = self.result(level);
+                    schema::CampaignLevelView {
+                        level,
+                        difficulty: found.difficulty.into(),
+                        lengths: schema::WordLengthsView {
+                            min: found.lengths.min.map(|min| min as u32),
+                            max: found.lengths.max.map(|max| max as u32),
+                        },
+                        min_score: found.min_score,
+                        unlocked: self.unlocked(level),
+                        won: self.won(level),
+                        attempts: result.map_or(0, |result| result.attempts),
+                        best_score: result.map_or(0, |result| result.best_score),
+                    }
+                })
+                .collect(),
+            score: self.score(),
+            next_level: self.next_level(),
+            completed: self.is_complete(),
+            time_ms: self.time_ms(),
+        }
+    }
+}
+
+impl Pattern {
+    /// The words of `words` that fit, for search_words (at most MAX_MATCHES of them)
+    pub fn search_view(&self, words: &WordList) -> schema::WordSearchView {
+        let found = self.search(words);
+        schema::WordSearchView {
+            schema_version: SCHEMA_VERSION,
+            total: found.len() as u32,
+            words: found.into_iter().take(MAX_MATCHES).map(String::from).collect(),
+            limit: MAX_MATCHES as u32,
+        }
+    }
+}
+
+impl From<PatternError> for schema::GameError {
+    fn from(err: PatternError) -> Self {
+        schema::GameError::invalid_input(err.to_string())
+    }
+}
+
+impl From<CampaignError> for schema::GameError {
+    fn from(err: CampaignError) -> Self {
+        match err {
+            CampaignError::Locked(level) => schema::GameError::LevelLocked { level },
+            CampaignError::UnknownLevel(_) => schema::GameError::invalid_input(err.to_string()),
+        }
+    }
+}
+
+impl From<Turn> for schema::RaceTurn {
+    fn from(turn: Turn) -> Self {
+        match turn {
+            Turn::Player => schema::RaceTurn::Player,
+            Turn::Ai => schema::RaceTurn::Ai,
+        }
+    }
+}
+
+impl From<RaceWinner> for schema::RaceWinner {
+    fn from(winner: RaceWinner) -> Self {
+        match winner {
+            RaceWinner::Player => schema::RaceWinner::Player,
+            RaceWinner::Ai => schema::RaceWinner::Ai,
+            RaceWinner::Hangman => schema::RaceWinner::Hangman,
+        }
+    }
+}
+
+impl From<schema::VersusRule> for VersusRule {
+    fn from(rule: schema::VersusRule) -> Self {
+        match rule {
+            schema::VersusRule::FirstSolve => VersusRule::FirstSolve,
+            schema::VersusRule::FewestWrongGuesses => VersusRule::FewestWrongGuesses,
+        }
+    }
+}
+
+impl From<VersusRule> for schema::VersusRule {
+    fn from(rule: VersusRule) -> Self {
+        match rule {
+            VersusRule::FirstSolve => schema::VersusRule::FirstSolve,
+            VersusRule::FewestWrongGuesses => schema::VersusRule::FewestWrongGuesses,
+        }
+    }
+}
+
+impl From<VersusError> for schema::GameError {
+    fn from(err: VersusError) -> Self {
+        match err {
+            VersusError::Guess(err) => err.into(),
+            VersusError::Decided | VersusError::BoardOver => schema::GameError::GameAlreadyOver,
+            VersusError::EvilMode => schema::GameError::invalid_input(err.to_string()),
+        }
+    }
+}
+
+impl Versus {
+    /// The duel as `side`'s player should see it: their board (the word hidden until
+    /// it's over) and how far the other player got
+    pub fn view(&self, side: Side) -> schema::VersusView {
+        let opponent = self.board(side.other());
+        let (letters, found) = self.progress(side.other());
+        let outcome = self.winner.map(|winner| match (winner, side) {
+            (VersusWinner::Draw, _) => schema::VersusOutcome::Draw,
+            (VersusWinner::Host, Side::Host) | (VersusWinner::Guest, Side::Guest) => schema::VersusOutcome::Won,
+            _ => schema::VersusOutcome::Lost,
+        });
+        schema::VersusView {
+            schema_version: SCHEMA_VERSION,
+            board: self.board(side).view(),
+            opponent: schema::OpponentView {
+                name: opponent.player.clone(),
+                letters,
+                found,
+                wrong_guesses: opponent.wrong_guesses,
+                max_wrong_guesses: opponent.max_wrong_guesses,
+                game_over: opponent.game_over,
+                won: opponent.won,
+            },
+            rule: self.rule.into(),
+            outcome,
+        }
+    }
+}
+
+impl Replay {
+    /// The game played again step by step, for import_replay
+    pub fn view(&self) -> Result<schema::ReplayView, ReplayError> {
+        let steps = self.steps()?;
+        Ok(schema::ReplayView {
+            schema_version: SCHEMA_VERSION,
+            duration_ms: steps.last().map_or(0, |step| step.at_ms),
+            steps: steps
+                .iter()
+                .map(|step| schema::ReplayStepView { at_ms: step.at_ms, game: step.game.view() })
+                .collect(),
+        })
+    }
+}
+
+impl From<ReplayError> for schema::GameError {
+    fn from(err: ReplayError) -> Self {
+        match err {
+            ReplayError::NotOver => schema::GameError::GameNotOver,
+            err => schema::GameError::invalid_input(err.to_string()),
+        }
+    }
+}
+
+impl From<PlayableError> for schema::GameError {
+    fn from(err: PlayableError) -> Self {
+        match err {
+            PlayableError::AllBlocked => {
+                schema::GameError::invalid_input("Every word of this list is blocked, see the blocked words in the options")
+            }
+            PlayableError::NoLength { lengths, shortest, longest } => schema::GameError::invalid_input(format!(
+                "No word has {}, the words here have {} to {} letters",
+                lengths, shortest, longest
+            )),
+        }
+    }
+}
+
+impl From<RaceError> for schema::GameError {
+    fn from(err: RaceError) -> Self {
+        match err {
+            RaceError::GameOver => schema::GameError::GameAlreadyOver,
+            RaceError::Paused => schema::GameError::GamePaused,
+            RaceError::EvilMode | RaceError::NotARace | RaceError::PlayerTurn => {
+                schema::GameError::invalid_input(err.to_string())
+            }
+        }
+    }
+}
+
+impl From<schema::GameMode> for GameMode {
+    fn from(mode: schema::GameMode) -> Self {
+        match mode {
+            schema::GameMode::Classic => GameMode::Classic,
+            schema::GameMode::Evil => GameMode::Evil,
+        }
+    }
+}
+
+impl From<LetterCase> for schema::LetterCase {
+    fn from(letter_case: LetterCase) -> Self {
+        match letter_case {
+            LetterCase::Upper => schema::LetterCase::Upper,
+            LetterCase::Lower => schema::LetterCase::Lower,
+        }
+    }
+}
+
+impl From<WordSource> for schema::WordSource {
+    fn from(source: WordSource) -> Self {
+        match source {
+            WordSource::Bundled => schema::WordSource::Bundled,
+            WordSource::Online => schema::WordSource::Online,
+            WordSource::File => schema::WordSource::File,
+            WordSource::Daily => schema::WordSource::Daily,
+        }
+    }
+}
+
+impl From<schema::WordSource> for WordSource {
+    fn from(source: schema::WordSource) -> Self {
+        match source {
+            schema::WordSource::Bundled => WordSource::Bundled,
+            schema::WordSource::Online => WordSource::Online,
+            schema::WordSource::File => WordSource::File,
+            schema::WordSource::Daily => WordSource::Daily,
+        }
+    }
+}
+
+impl From<schema::LetterCase> for LetterCase {
+    fn from(letter_case: schema::LetterCase) -> Self {
+        match letter_case {
+            schema::LetterCase::Upper => LetterCase::Upper,
+            schema::LetterCase::Lower => LetterCase::Lower,
+        }
+    }
+}
+
+impl From<Feedback> for schema::LetterFeedback {
+    fn from(feedback: Feedback) -> Self {
+        match feedback {
+            Feedback::Correct => schema::LetterFeedback::Correct,
+            Feedback::Present => schema::LetterFeedback::Present,
+            Feedback::Absent => schema::LetterFeedback::Absent,
+        }
+    }
+}
+
+impl From<Help> for schema::Help {
+    fn from(help: Help) -> Self {
+        match help {
+            Help::RevealVowel => schema::Help::RevealVowel,
+            Help::RemoveLetter => schema::Help::RemoveLetter,
+            Help::ExtraLife => schema::Help::ExtraLife,
+        }
+    }
+}
+
+impl From<schema::Help> for Help {
+    fn from(help: schema::Help) -> Self {
+        match help {
+            schema::Help::RevealVowel => Help::RevealVowel,
+            schema::Help::RemoveLetter => Help::RemoveLetter,
+            schema::Help::ExtraLife => Help::ExtraLife,
+        }
+    }
+}
+
+impl From<ShopError> for schema::GameError {
+    fn from(err: ShopError) -> Self {
+        match err {
+            ShopError::NotEnoughPoints { price, points } => schema::GameError::NotEnoughPoints { price, points },
+            ShopError::GameOver => schema::GameError::GameAlreadyOver,
+            ShopError::Paused => schema::GameError::GamePaused,
+            ShopError::Unavailable(_) => schema::GameError::HelpUnavailable { message: err.to_string() },
+        }
+    }
+}
+
+impl From<PowerUp> for schema::PowerUp {
+    fn from(power_up: PowerUp) -> Self {
+        match power_up {
+            PowerUp::FreezeTimer => schema::PowerUp::FreezeTimer,
+            PowerUp::RevealConsonant => schema::PowerUp::RevealConsonant,
+            PowerUp::Shield => schema::PowerUp::Shield,
+        }
+    }
+}
+
+impl From<schema::PowerUp> for PowerUp {
+    fn from(power_up: schema::PowerUp) -> Self {
+        match power_up {
+            schema::PowerUp::FreezeTimer => PowerUp::FreezeTimer,
+            schema::PowerUp::RevealConsonant => PowerUp::RevealConsonant,
+            schema::PowerUp::Shield => PowerUp::Shield,
+        }
+    }
+}
+
+impl From<PowerUpError> for schema::GameError {
+    fn from(err: PowerUpError) -> Self {
+        match err {
+            PowerUpError::NoneHeld(power_up) => schema::GameError::NoPowerUpLeft { power_up: power_up.into() },
+            PowerUpError::GameOver => schema::GameError::GameAlreadyOver,
+            PowerUpError::Paused => schema::GameError::GamePaused,
+            PowerUpError::Unavailable(_) => schema::GameError::PowerUpUnavailable { message: err.to_string() },
+        }
+    }
+}
+
+impl Inventory {
+    /// The power-ups of `player` (None without a profile), every kind with what it does
+    pub fn view(&self, player: Option<String>) -> schema::InventoryView {
+        schema::InventoryView {
+            schema_version: SCHEMA_VERSION,
+            player,
+            power_ups: POWER_UPS
+                .iter()
+                .map(|&power_up| schema::PowerUpView {
+                    power_up: power_up.into(),
+                    name: power_up.name().to_string(),
+                    description: power_up.description().to_string(),
+                    held: self.held(power_up),
+                })
+                .collect(),
+            max_held: MAX_HELD,
+        }
+    }
+}
+
+impl Wallet {
+    /// The wallet of `player` (None without a profile) with the prices of the helps
+    pub fn view(&self, player: Option<String>) -> schema::WalletView {
+        schema::WalletView {
+            schema_version: SCHEMA_VERSION,
+            player,
+            points: self.points,
+            win_points: WIN_POINTS,
+            prices: HELPS
+                .iter()
+                .map(|&help| schema::HelpPrice { help: help.into(), price: help.price() })
+                .collect(),
+        }
+    }
+}
+
+impl From<WordleError> for schema::GameError {
+    fn from(err: WordleError) -> Self {
+        match err {
+            WordleError::NotALetter(character) if character.is_ascii_digit() => schema::GameError::Digit { character },
+            WordleError::NotALetter(character) => schema::GameError::NotALetter { character },
+            WordleError::GameOver => schema::GameError::GameAlreadyOver,
+            WordleError::WrongLength(_) | WordleError::NotInWordList(_) => schema::GameError::invalid_input(err.to_string()),
+        }
+    }
+}
+
+impl WordleGame {
+    /// The game as the frontend should see it, the answer only once it's over
+    pub fn view(&self) -> schema::WordleView {
+        schema::WordleView {
+            schema_version: SCHEMA_VERSION,
+            guesses: self
+                .guesses
+                .iter()
+                .map(|guess| schema::WordleGuessView {
+                    word: guess.word.clone(),
+                    feedback: guess.feedback.iter().map(|&mark| mark.into()).collect(),
+                })
+                .collect(),
+            word_len: WORD_LEN as u32,
+            max_guesses: self.max_guesses,
+            game_over: self.game_over,
+            won: self.won,
+            answer: self.game_over.then(|| self.answer.clone()),
+            keys: self
+                .key_states()
+                .into_iter()
+                .map(|(letter, mark)| schema::WordleKeyView { letter, feedback: mark.into() })
+                .collect(),
+        }
+    }
+
+    /// The game with the id of the session it's played in
+    pub fn session_view(&self, session_id: &str) -> schema::WordleSessionView {
+        schema::WordleSessionView {
+            schema_version: SCHEMA_VERSION,
+            session_id: session_id.to_string(),
+            game: self.view(),
+        }
+    }
+}
+
+impl From<PositionalError> for schema::GameError {
+    fn from(err: PositionalError) -> Self {
+        match err {
+            PositionalError::NotALetter(character) if character.is_ascii_digit() => schema::GameError::Digit { character },
+            PositionalError::NotALetter(character) => schema::GameError::NotALetter { character },
+            PositionalError::GameOver => schema::GameError::GameAlreadyOver,
+            PositionalError::OutOfRange { .. } | PositionalError::AlreadyShown(_) | PositionalError::AlreadyGuessed(..) => {
+                schema::GameError::invalid_input(err.to_string())
+            }
+        }
+    }
+}
+
+impl From<CrosswordError> for schema::GameError {
+    fn from(err: CrosswordError) -> Self {
+        match err {
+            CrosswordError::NotALetter(character) if character.is_ascii_digit() => schema::GameError::Digit { character },
+            CrosswordError::NotALetter(character) => schema::GameError::NotALetter { character },
+            CrosswordError::AlreadyGuessed(letter) => schema::GameError::AlreadyGuessed { letter: spell(letter) },
+            CrosswordError::GameOver => schema::GameError::GameAlreadyOver,
+            CrosswordError::NotEnoughWords => schema::GameError::invalid_input(err.to_string()),
+        }
+    }
+}
+
+impl From<Direction> for schema::CrosswordDirection {
+    fn from(direction: Direction) -> Self {
+        match direction {
+            Direction::Across => schema::CrosswordDirection::Across,
+            Direction::Down => schema::CrosswordDirection::Down,
+        }
+    }
+}
+
+impl CrosswordGame {
+    /// The game as the frontend should see it, a word only once it's complete
+    /// (every word once the game is over)
+    pub fn view(&self) -> schema::CrosswordView {
+        schema::CrosswordView {
+            schema_version: SCHEMA_VERSION,
+            rows: self.board.rows as u32,
+            cols: self.board.cols as u32,
+            cells: self
+                .board
+                .cells()
+                .into_iter()
+                .map(|((row, col), c)| schema::CrosswordCellView {
+                    row: row as u32,
+                    col: col as u32,
+                    letter: self.is_revealed(c).then(|| spell(c)),
+                })
+                .collect(),
+            words: self
+                .board
+                .entries
+                .iter()
+                .map(|entry| {
+                    let complete = self.is_complete(entry);
+                    schema::CrosswordWordView {
+                        row: entry.row as u32,
+                        col: entry.col as u32,
+                        direction: entry.direction.into(),
+                        length: entry.word.chars().count() as u32,
+                        complete,
+                        word: (complete || self.game_over).then(|| entry.word.clone()),
+                    }
+                })
+                .collect(),
+            guessed_letters: self.guessed_letters.iter().map(|&c| spell(c)).collect(),
+            wrong_guesses: self.wrong_guesses,
+            max_wrong_guesses: self.max_wrong_guesses,
+            difficulty: self.difficulty.into(),
+            game_over: self.game_over,
+            won: self.won,
+        }
+    }
+
+    /// The game with the id of the session it's played in
+    pub fn session_view(&self, session_id: &str) -> schema::CrosswordSessionView {
+        schema::CrosswordSessionView {
+            schema_version: SCHEMA_VERSION,
+            session_id: session_id.to_string(),
+            game: self.view(),
+        }
+    }
+}
+
+impl PositionalGame {
+    /// The game as the frontend should see it, the word only once it's over
+    pub fn view(&self) -> schema::PositionalView {
+        schema::PositionalView {
+            schema_version: SCHEMA_VERSION,
+            board: self.board().into_iter().map(|c| c.map(spell)).collect(),
+            guesses: self
+                .guesses
+                .iter()
+                .map(|guess| schema::PositionalGuessView {
+                    letter: spell(guess.letter),
+                    index: guess.index as u32,
+                    feedback: guess.feedback.into(),
+                })
+                .collect(),
+            wrong_guesses: self.wrong_guesses,
+            max_wrong_guesses: self.max_wrong_guesses,
+            difficulty: self.difficulty.into(),
+            game_over: self.game_over,
+            won: self.won,
+            word: self.game_over.then(|| self.word.clone()),
+        }
+    }
+
+    /// The game with the id of the session it's played in
+    pub fn session_view(&self, session_id: &str) -> schema::PositionalSessionView {
+        schema::PositionalSessionView {
+            schema_version: SCHEMA_VERSION,
+            session_id: session_id.to_string(),
+            game: self.view(),
+        }
+    }
+}
+
+impl GameState {
+    /// The game as the frontend should see it (built from `player_view`,
+    /// so the word stays hidden until the game is over)
+    pub fn view(&self) -> schema::GameView {
+        let view = self.player_view();
+        schema::GameView {
+            schema_version: SCHEMA_VERSION,
+            word: spell_str(&view.word, self.letter_case),
+            mask: spell_str(&self.mask(), self.letter_case),
+            display_word: self.get_display_word(),
+            guessed_letters: self.guessed_letters.iter().map(|&c| self.letter_text(c)).collect(),
+            guessed_words: view.guessed_words,
+            wrong_guesses: view.wrong_guesses,
+            max_wrong_guesses: view.max_wrong_guesses,
+            stage: self.stage(),
+            stages: self.stages(),
+            gallows: self.gallows(ArtStyle::Ascii),
+            game_over: view.game_over,
+            won: view.won,
+            category: view.category,
+            clue: view.clue,
+            difficulty: view.difficulty.into(),
+            mode: self.mode().into(),
+            custom: view.custom,
+            practice: view.practice,
+            kids_mode: view.kids,
+            ranked: self.ranked(),
+            hints_used: view.hints_used,
+            max_hints: view.max_hints,
+            letter_case: view.letter_case.into(),
+            player: view.player,
+            score: view.score.map(schema::ScoreView::from),
+            rating: view.rating.map(schema::WordRatingView::from),
+            time_limit: view.time_limit,
+            remaining_ms: self.remaining_ms(),
+            paused: self.is_paused(),
+            paused_ms: view.paused_ms,
+            timed_out: view.timed_out,
+            gave_up: view.gave_up,
+            ignore_accents: view.ignore_accents,
+            alphabet: self.keyboard().into_iter().map(spell).collect(),
+            keys: self
+                .key_states()
+                .into_iter()
+                .map(|(letter, state)| schema::KeyView { letter: spell(letter), state: state.into() })
+                .collect(),
+            daily: view.daily,
+            ai_moves: view.ai_moves,
+            can_undo: self.can_undo(),
+            history: self.history.iter().map(|entry| self.move_view(entry)).collect(),
+            reveal: self.reveal_view(),
+            match_score: self.best_of.as_ref().map(Match::view),
+            duel: self.duel.as_ref().map(Duel::view),
+            run: self.run.as_ref().map(Run::view),
+            race: self.race_view(),
+            campaign_level: self.campaign_level,
+            seed: self.seed,
+            helps_bought: self.helps_bought.iter().map(|&help| help.into()).collect(),
+            removed_letters: self.removed_letters.iter().map(|&c| self.letter_text(c)).collect(),
+            power_ups_used: self.power_ups_used.iter().map(|&power_up| power_up.into()).collect(),
+            shield: self.shield,
+            frozen_ms: self.frozen_ms(),
+        }
+    }
+
+    /// A letter (or a digraph's unit) as it's shown, in the game's letter case
+    fn letter_text(&self, c: char) -> String {
+        self.letter_case.apply_str(&spell(c))
+    }
+
+    /// A help bought for this game, with the points left
+    pub fn purchase_view(&self, help: Help, letter: Option<char>, points: u64) -> schema::PurchaseView {
+        schema::PurchaseView {
+            schema_version: SCHEMA_VERSION,
+            help: help.into(),
+            letter: letter.map(|c| self.letter_text(c)),
+            price: help.price(),
+            points,
+            game: self.view(),
+        }
+    }
+
+    /// A power-up used in this game, with how many are left
+    pub fn activation_view(&self, power_up: PowerUp, letter: Option<char>, held: u32) -> schema::ActivationView {
+        schema::ActivationView {
+            schema_version: SCHEMA_VERSION,
+            power_up: power_up.into(),
+            letter: letter.map(|c| self.letter_text(c)),
+            held,
+            game: self.view(),
+        }
+    }
+
+    /// The game without anything that gives the word away while it's running
+    /// (see PublicGameView), safe to send to spectators
+    pub fn public_view(&self) -> schema::PublicGameView {
+        let view = self.player_view();
+        schema::PublicGameView {
+            schema_version: SCHEMA_VERSION,
+            mask: spell_str(&self.mask(), self.letter_case),
+            display_word: self.get_display_word(),
+            guessed_letters: self.guessed_letters.iter().map(|&c| self.letter_text(c)).collect(),
+            guessed_words: view.guessed_words,
+            wrong_guesses: view.wrong_guesses,
+            max_wrong_guesses: view.max_wrong_guesses,
+            stage: self.stage(),
+            stages: self.stages(),
+            gallows: self.gallows(ArtStyle::Ascii),
+            hints_used: view.hints_used,
+            max_hints: view.max_hints,
+            remaining_ms: self.remaining_ms(),
+            paused: self.is_paused(),
+            game_over: view.game_over,
+            won: view.won,
+            word: view.game_over.then_some(view.word),
+        }
+    }
+
+    /// How the AI is doing in the race this game is (None for other games)
+    fn race_view(&self) -> Option<schema::RaceView> {
+        let race = self.race.as_ref()?;
+        let (letters, ai_found) = race.progress();
+        let board = &race.board;
+        Some(schema::RaceView {
+            schema_version: SCHEMA_VERSION,
+            turn: race.turn.into(),
+            letters,
+            ai_found,
+            ai_letters: board.guessed_letters.iter().map(|&c| self.letter_text(c)).collect(),
+            ai_wrong_guesses: board.wrong_guesses,
+            ai_max_wrong_guesses: board.max_wrong_guesses,
+            ai_out: race.ai_out(),
+            winner: self.race_winner().map(schema::RaceWinner::from),
+        })
+    }
+
+    /// A move of this game as the timeline shows it
+    /// What the last move revealed
+    fn reveal_view(&self) -> schema::RevealView {
+        let reveal = self.last_reveal();
+        schema::RevealView {
+            places: reveal
+                .places
+                .into_iter()
+                .map(|place| schema::RevealedPlaceView {
+                    place: place.place as u32,
+                    letter: self.letter_text(place.letter),
+                    display_at: place.display_at as u32,
+                })
+                .collect(),
+            revealed: reveal.revealed as u32,
+            letters: reveal.letters as u32,
+        }
+    }
+
+    fn move_view(&self, entry: &Move) -> schema::MoveView {
+        let (kind, guess) = match &entry.action {
+            Action::Letter(letter) => (schema::MoveKind::Letter, spell(*letter)),
+            Action::Word(word) => (schema::MoveKind::Word, word.clone()),
+            Action::Hint(letter) => (schema::MoveKind::Hint, spell(*letter)),
+        };
+        schema::MoveView {
+            kind,
+            guess: self.letter_case.apply_str(&guess),
+            hit: entry.hit,
+            at_ms: entry.at_ms,
+        }
+    }
+
+    /// What a batch of letter guesses did (`inputs` as sent, with their outcomes), and the game after it
+    pub fn batch_guess_view(&self, inputs: &[String], outcomes: &[LetterOutcome]) -> schema::BatchGuessView {
+        let outcomes = inputs
+            .iter()
+            .zip(outcomes)
+            .map(|(input, outcome)| {
+                let (letter, result, error) = match *outcome {
+                    LetterOutcome::Hit(letter) => (Some(letter), schema::LetterResult::Hit, None),
+                    LetterOutcome::Miss(letter) => (Some(letter), schema::LetterResult::Miss, None),
+                    LetterOutcome::Rejected(err) => (None, schema::LetterResult::Rejected, Some(err.into())),
+                    LetterOutcome::Skipped => (None, schema::LetterResult::Skipped, None),
+                };
+                schema::LetterOutcomeView {
+                    input: input.clone(),
+                    letter: letter.map(|letter| self.letter_text(letter)),
+                    result,
+                    error,
+                }
+            })
+            .collect();
+        schema::BatchGuessView {
+            schema_version: SCHEMA_VERSION,
+            outcomes,
+            game: self.view(),
+        }
+    }
+
+    /// The game after the AI's move (an empty guess if it had none to make)
+    pub fn ai_turn_view(&self, ai_move: Option<&AiMove>) -> schema::AiTurnView {
+        schema::AiTurnView {
+            schema_version: SCHEMA_VERSION,
+            guess: ai_move.map(AiMove::text).unwrap_or_default(),
+            word_guess: matches!(ai_move, Some(AiMove::Word(_))),
+            game: self.view(),
+        }
+    }
+
+    /// The smart hint with `words` as the dictionary (see ai.rs), letters spelled like the keyboard's
+    pub fn letter_frequencies_view(&self, words: &WordList) -> schema::LetterFrequenciesView {
+        let (left, letters) = ai::letter_frequencies(self, words);
+        schema::LetterFrequenciesView {
+            schema_version: SCHEMA_VERSION,
+            words: left as u32,
+            letters: letters
+                .into_iter()
+                .map(|frequency| schema::LetterFrequencyView {
+                    letter: spell(frequency.letter),
+                    words: frequency.words as u32,
+                })
+                .collect(),
+        }
+    }
+
+    /// The best letters to guess with `words` as the dictionary (see suggest.rs),
+    /// spelled like the keyboard's
+    pub fn suggestions_view(&self, words: &WordList) -> schema::SuggestionsView {
+        let (left, suggestions) = suggest::suggest(self, words);
+        schema::SuggestionsView {
+            schema_version: SCHEMA_VERSION,
+            words: left as u32,
+            suggestions: suggestions
+                .into_iter()
+                .map(|suggestion| schema::SuggestionView {
+                    letter: spell(suggestion.letter),
+                    score: suggestion.score,
+                    words: suggestion.words as u32,
+                })
+                .collect(),
+        }
+    }
+
+    /// The `game_updated` event payload for the game of a session
+    pub fn game_update(&self, session_id: &str) -> schema::GameUpdate {
+        schema::GameUpdate {
+            schema_version: SCHEMA_VERSION,
+            session_id: session_id.to_string(),
+            game: self.view(),
+        }
+    }
+
+    /// The game with the id of the session it's played in
+    pub fn session_view(&self, session_id: &str) -> schema::SessionView {
+        schema::SessionView {
+            schema_version: SCHEMA_VERSION,
+            session_id: session_id.to_string(),
+            game: self.view(),
+        }
+    }
+}
+
+impl From<HintError> for schema::GameError {
+    fn from(err: HintError) -> Self {
+        match err {
+            HintError::GameOver => schema::GameError::GameAlreadyOver,
+            HintError::Paused => schema::GameError::GamePaused,
+            HintError::NotYourTurn => schema::GameError::NotYourTurn,
+            HintError::NoHintsLeft | HintError::NotEnoughGuesses => schema::GameError::HintUnavailable {
+                message: err.to_string(),
+            },
+        }
+    }
+}
+
+impl From<PauseError> for schema::GameError {
+    fn from(err: PauseError) -> Self {
+        match err {
+            PauseError::GameOver => schema::GameError::GameAlreadyOver,
+            PauseError::NotTimed | PauseError::AlreadyPaused | PauseError::NotPaused => {
+                schema::GameError::invalid_input(err.to_string())
+            }
+        }
+    }
+}
+
+impl From<KeyState> for schema::KeyState {
+    fn from(state: KeyState) -> Self {
+        match state {
+            KeyState::Unused => schema::KeyState::Unused,
+            KeyState::Correct => schema::KeyState::Correct,
+            KeyState::Wrong => schema::KeyState::Wrong,
+        }
+    }
+}
+
+impl From<UndoError> for schema::GameError {
+    fn from(err: UndoError) -> Self {
+        match err {
+            UndoError::NothingToUndo => schema::GameError::NothingToUndo,
+            // A game lost by running out of time, or given up, stays over
+            UndoError::TimedOut | UndoError::GaveUp => schema::GameError::GameAlreadyOver,
+            UndoError::Race => schema::GameError::invalid_input(err.to_string()),
+        }
+    }
+}
+
+impl From<GuessError> for schema::GameError {
+    fn from(err: GuessError) -> Self {
+        match err {
+            GuessError::Empty => schema::GameError::EmptyGuess,
+            GuessError::MultipleCharacters => schema::GameError::MultipleCharacters,
+            GuessError::Digit(character) => schema::GameError::Digit { character },
+            GuessError::NotALetter(character) => schema::GameError::NotALetter { character },
+            GuessError::AlreadyGuessed(letter) => schema::GameError::AlreadyGuessed { letter: spell(letter) },
+        }
+    }
+}
+
+impl From<SecretError> for schema::GameError {
+    fn from(err: SecretError) -> Self {
+        schema::GameError::invalid_input(err.to_string())
+    }
+}
+
+impl From<Definition> for schema::DefinitionView {
+    fn from(definition: Definition) -> Self {
+        schema::DefinitionView {
+            schema_version: SCHEMA_VERSION,
+            word: definition.word,
+            part_of_speech: definition.part_of_speech,
+            definition: definition.definition,
+        }
+    }
+}
+
+impl From<Score> for schema::ScoreView {
+    fn from(score: Score) -> Self {
+        schema::ScoreView {
+            letter_points: score.letter_points,
+            guess_bonus: score.guess_bonus,
+            rating_bonus: score.rating_bonus,
+            length_multiplier: score.length_multiplier,
+            difficulty_multiplier: score.difficulty_multiplier,
+            total: score.total,
+        }
+    }
+}
+
+impl From<WordRating> for schema::WordRatingView {
+    fn from(rating: WordRating) -> Self {
+        schema::WordRatingView {
+            schema_version: SCHEMA_VERSION,
+            score: rating.score,
+            level: rating.level.into(),
+        }
+    }
+}
+
+impl From<&Settings> for schema::SettingsView {
+    fn from(settings: &Settings) -> Self {
+        schema::SettingsView {
+            schema_version: SCHEMA_VERSION,
+            difficulty: settings.difficulty.into(),
+            category: settings.category.clone(),
+            language: settings.language.clone(),
+            hints_enabled: settings.hints_enabled,
+            show_clues: settings.show_clues,
+            word_source: settings.word_source.into(),
+            word_file: settings.word_file.clone(),
+            profanity_filter: settings.profanity_filter,
+            blocked_words: settings.blocked_words.clone(),
+            max_wrong_guesses: settings.max_wrong_guesses,
+            hints_allowed: settings.hints_allowed,
+            letter_case: settings.letter_case.into(),
+            time_limit: settings.time_limit,
+            ignore_accents: settings.ignore_accents,
+            digraphs: settings.digraphs.clone(),
+            display: schema::DisplayFormatView {
+                placeholder: settings.display.placeholder,
+                letter_spacing: settings.display.letter_spacing,
+                word_separator: settings.display.word_separator.clone(),
+            },
+            seed: settings.seed,
+            min_word_length: settings.min_word_length,
+            max_word_length: settings.max_word_length,
+            telemetry: settings.telemetry,
+            telemetry_endpoint: settings.telemetry_endpoint.clone(),
+            audit_log: settings.audit_log,
+            metrics_on_exit: settings.metrics_on_exit,
+            kids_mode: settings.kids_mode,
+        }
+    }
+}
+
+impl From<schema::SettingsView> for Settings {
+    fn from(view: schema::SettingsView) -> Self {
+        Settings {
+            version: SETTINGS_VERSION,
+            difficulty: view.difficulty.into(),
+            category: view.category,
+            language: view.language,
+            hints_enabled: view.hints_enabled,
+            show_clues: view.show_clues,
+            word_source: view.word_source.into(),
+            word_file: view.word_file,
+            profanity_filter: view.profanity_filter,
+            blocked_words: view.blocked_words,
+            max_wrong_guesses: view.max_wrong_guesses,
+            hints_allowed: view.hints_allowed,
+            letter_case: view.letter_case.into(),
+            time_limit: view.time_limit,
+            ignore_accents: view.ignore_accents,
+            digraphs: view.digraphs,
+            display: DisplayFormat {
+                placeholder: view.display.placeholder,
+                letter_spacing: view.display.letter_spacing,
+                word_separator: view.display.word_separator,
+            },
+            seed: view.seed,
+            min_word_length: view.min_word_length,
+            max_word_length: view.max_word_length,
+            telemetry: view.telemetry,
+            telemetry_endpoint: view.telemetry_endpoint,
+            audit_log: view.audit_log,
+            metrics_on_exit: view.metrics_on_exit,
+            kids_mode: view.kids_mode,
+        }
+    }
+}
+
+impl From<ProviderHealth> for schema::WordProviderView {
+    fn from(health: ProviderHealth) -> Self {
+        schema::WordProviderView {
+            source: health.source.into(),
+            healthy: health.is_healthy(),
+            words: health.words as u32,
+            error: health.error.map(|err| err.to_string()),
+        }
+    }
+}
+
+impl WordProviders {
+    /// Every provider's health, and which one new games with the `selected` source get their words from
+    pub fn view(&self, selected: WordSource) -> schema::WordProvidersView {
+        let (serving, fallback) = match self.words(selected) {
+            Ok(served) => (served.source, served.fallback.map(|err| err.to_string())),
+            Err(err) => (selected, Some(err.to_string())),
+        };
+        schema::WordProvidersView {
+            schema_version: SCHEMA_VERSION,
+            selected: selected.into(),
+            serving: serving.into(),
+            fallback,
+            providers: self.health().into_iter().map(schema::WordProviderView::from).collect(),
+        }
+    }
+}
+
+impl From<TelemetryKind> for schema::TelemetryKind {
+    fn from(kind: TelemetryKind) -> Self {
+        match kind {
+            TelemetryKind::Started => schema::TelemetryKind::Started,
+            TelemetryKind::Won => schema::TelemetryKind::Won,
+            TelemetryKind::Lost => schema::TelemetryKind::Lost,
+        }
+    }
+}
+
+impl From<&TelemetryEvent> for schema::TelemetryEventView {
+    fn from(event: &TelemetryEvent) -> Self {
+        schema::TelemetryEventView {
+            kind: event.kind.into(),
+            word_length: event.word_length as u32,
+            guesses: event.guesses as u32,
+            difficulty: event.difficulty.into(),
+            mode: event.mode.into(),
+        }
+    }
+}
+
+impl GameTelemetry {
+    /// What's been collected, with the events `saved` in the local file
+    pub fn view(&self, saved: &[TelemetryEvent]) -> schema::TelemetryView {
+        schema::TelemetryView {
+            schema_version: SCHEMA_VERSION,
+            enabled: self.is_enabled(),
+            destination: self.sink().to_string(),
+            pending: self.pending().iter().map(Into::into).collect(),
+            saved: saved.iter().map(Into::into).collect(),
+        }
+    }
+}
+
+impl From<&AuditEntry> for schema::AuditEntryView {
+    fn from(entry: &AuditEntry) -> Self {
+        schema::AuditEntryView {
+            at_ms: entry.at_ms,
+            window: entry.window.clone(),
+            command: entry.command.clone(),
+            args: entry.args.to_string(),
+            ok: entry.ok,
+            error: entry.error.as_ref().map(ToString::to_string),
+            duration_ms: entry.duration_ms,
+        }
+    }
+}
+
+impl AuditLog {
+    /// The log with its `entries` read back from the file
+    pub fn view(&self, entries: &[AuditEntry]) -> schema::AuditLogView {
+        schema::AuditLogView {
+            schema_version: SCHEMA_VERSION,
+            enabled: self.is_enabled(),
+            file: self.file().display().to_string(),
+            entries: entries.iter().map(Into::into).collect(),
+        }
+    }
+}
+
+impl From<&CommandSummary> for schema::CommandMetricsView {
+    fn from(summary: &CommandSummary) -> Self {
+        schema::CommandMetricsView {
+            command: summary.command.clone(),
+            calls: summary.calls,
+            errors: summary.errors,
+            mean_us: summary.mean_us,
+            p50_us: summary.p50_us,
+            p90_us: summary.p90_us,
+            p99_us: summary.p99_us,
+            max_us: summary.max_us,
+            total_us: summary.total_us,
+        }
+    }
+}
+
+impl CommandMetrics {
+    /// Every command's metrics so far
+    pub fn view(&self) -> schema::MetricsView {
+        schema::MetricsView {
+            schema_version: SCHEMA_VERSION,
+            on_exit: self.on_exit(),
+            file: self.file().display().to_string(),
+            commands: self.summaries().iter().map(Into::into).collect(),
+        }
+    }
+}
+
+impl Achievement {
+    /// The achievement for `player`, unlocked if `unlocked` is Some
+    pub fn view(&self, player: Option<&str>, unlocked: Option<&Unlocked>) -> schema::AchievementView {
+        schema::AchievementView {
+            schema_version: SCHEMA_VERSION,
+            id: self.id.to_string(),
+            title: self.title.to_string(),
+            description: self.description.to_string(),
+            player: player.map(str::to_string),
+            unlocked: unlocked.is_some(),
+            unlocked_at_ms: unlocked.map(|unlocked| unlocked.at_ms),
+            word: unlocked.map(|unlocked| unlocked.word.clone()),
+        }
+    }
+}
+
+impl Achievements {
+    /// Every achievement, locked or not, for `player` (None for no profile)
+    pub fn views(&self, player: Option<&str>) -> Vec<schema::AchievementView> {
+        ACHIEVEMENTS
+            .iter()
+            .map(|achievement| achievement.view(player, self.get(achievement.id, player)))
+            .collect()
+    }
+}
+
+impl ImportReport {
+    /// The report for the frontend, with the size of the pool it was imported into
+    pub fn summary(&self, pool_size: usize) -> schema::ImportSummary {
+        schema::ImportSummary {
+            schema_version: SCHEMA_VERSION,
+            accepted: self.accepted.clone(),
+            duplicates: self.duplicates.clone(),
+            rejected: self
+                .rejected
+                .iter()
+                .map(|(word, err)| schema::RejectedWord {
+                    word: word.clone(),
+                    reason: err.to_string(),
+                })
+                .collect(),
+            pool_size: u32::try_from(pool_size).unwrap_or(u32::MAX),
+        }
+    }
+}
+
+impl CleanReport {
+    pub fn view(&self) -> schema::WordListReport {
+        schema::WordListReport {
+            schema_version: SCHEMA_VERSION,
+            words: u32::try_from(self.kept).unwrap_or(u32::MAX),
+            cleaned: self
+                .stripped
+                .iter()
+                .map(|(entry, word)| schema::CleanedWord {
+                    entry: entry.clone(),
+                    word: word.clone(),
+                })
+                .collect(),
+            duplicates: self.duplicates.clone(),
+            rejected: self
+                .rejected
+                .iter()
+                .map(|(word, reason)| schema::RejectedWord {
+                    word: word.clone(),
+                    reason: reason.to_string(),
+                })
+                .collect(),
+        }
+    }
+}
+
+impl WordList {
+    /// The list as the word list of `language` (None for the main list's)
+    pub fn language_view(&self, language: Option<&str>) -> schema::LanguageView {
+        let code = language.unwrap_or(DEFAULT_LANGUAGE);
+        schema::LanguageView {
+            schema_version: SCHEMA_VERSION,
+            code: code.to_string(),
+            name: Language::find(code).map_or_else(|| code.to_string(), |language| language.name.to_string()),
+            alphabet: self.alphabet_for(Some(code)),
+            words: u32::try_from(self.words().len()).unwrap_or(u32::MAX),
+        }
+    }
+}
+
+#[cfg(feature = "sqlite")]
+impl WordStats {
+    pub fn view(&self) -> schema::WordStatsView {
+        schema::WordStatsView {
+            schema_version: SCHEMA_VERSION,
+            word: self.word.clone(),
+            played: self.played,
+            wins: self.wins,
+            losses: self.losses(),
+            win_rate: self.win_rate(),
+            average_wrong_guesses: self.average_wrong_guesses,
+        }
+    }
+}
+
+impl Profile {
+    pub fn view(&self, current: bool) -> schema::ProfileView {
+        schema::ProfileView {
+            schema_version: SCHEMA_VERSION,
+            name: self.name.clone(),
+            current,
+            statistics: self.stats.summary(),
+            high_scores: self.high_scores.clone(),
+            total_score: self.total_score,
+            points: self.wallet.points,
+            campaign_ms: self.campaign_ms,
+        }
+    }
+}
+
+impl RatingPoint {
+    pub fn view(&self) -> schema::RatingPointView {
+        schema::RatingPointView {
+            result: self.result.into(),
+            rating: self.rating,
+            change: self.change,
+            at_ms: self.at_ms,
+        }
+    }
+}
+
+impl Ratings {
+    /// The rating on `difficulty` and its history, of `player`'s profile
+    pub fn history_view(&self, player: Option<String>, difficulty: Difficulty) -> schema::RatingHistoryView {
+        schema::RatingHistoryView {
+            schema_version: SCHEMA_VERSION,
+            player,
+            difficulty: difficulty.into(),
+            rating: self.rating(difficulty),
+            ai_rating: ai_rating(difficulty),
+            points: self.points(difficulty).into_iter().map(RatingPoint::view).collect(),
+        }
+    }
+}
+
+impl Profiles {
+    /// Every profile, in the order they were created
+    pub fn views(&self) -> Vec<schema::ProfileView> {
+        let current = self.current().map(|profile| profile.name.as_str());
+        self.profiles
+            .iter()
+            .map(|profile| profile.view(Some(profile.name.as_str()) == current))
+            .collect()
+    }
+
+    pub fn leaderboard_view(&self) -> schema::Leaderboard {
+        schema::Leaderboard {
+            schema_version: SCHEMA_VERSION,
+            entries: self
+                .leaderboard()
+                .into_iter()
+                .zip(1..)
+                .map(|(profile, rank)| schema::LeaderboardEntry {
+                    rank,
+                    name: profile.name.clone(),
+                    best_score: profile.best_score(),
+                    total_score: profile.total_score,
+                    wins: profile.stats.wins,
+                    games_played: profile.stats.games_played,
+                })
+                .collect(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::online_words::OnlineWords;
+    use crate::providers::{OnlineProvider, StaticProvider};
+    use crate::race::Race;
+
+    #[test]
+    fn test_view_hides_word_until_over() {
+        let mut game = GameState::new("RUST".to_string(), None, Difficulty::Hard);
+        game.guess_letter('r');
+        let view = game.view();
+        assert_eq!(view.schema_version, SCHEMA_VERSION);
+        assert_eq!(view.word, "R___");
+        assert_eq!(view.mask, "R___");
+        assert_eq!(view.display_word, "R _ _ _");
+        assert_eq!(view.difficulty, schema::Difficulty::Hard);
+
+        assert_eq!(view.score, None);
+        assert_eq!(view.rating, None);
+        assert_eq!(view.remaining_ms, None);
+
+        game.guess_word("rust");
+        let view = game.view();
+        assert_eq!(view.word, "RUST");
+        assert_eq!(view.display_word, "R U S T");
+        assert_eq!(view.score.unwrap().total, game.score.unwrap().total);
+        assert_eq!(view.rating.unwrap().score, game.rating.unwrap().score);
+    }
+
+    #[test]
+    fn test_public_view() {
+        let mut game = GameState::new("RUST".to_string(), None, Difficulty::Hard);
+        game.guess_letter('r');
+        game.guess_letter('x');
+        let view = game.public_view();
+        assert_eq!((view.mask.as_str(), view.word.as_deref()), ("R___", None));
+        assert_eq!((view.guessed_letters, view.wrong_guesses), (vec!["R".to_string(), "X".to_string()], 1));
+        assert!(!serde_json::to_string(&game.public_view()).unwrap().contains("RUST"));
+
+        // Lost, the word comes out (the mask stays the mask)
+        for letter in "ABCDEF".chars() {
+            game.guess_letter(letter);
+        }
+        let view = game.public_view();
+        assert!(view.game_over && !view.won);
+        assert_eq!((view.mask.as_str(), view.word.as_deref()), ("R___", Some("RUST")));
+    }
+
+    #[test]
+    fn test_no_message_has_the_word_while_playing() {
+        let list = WordList::from_text("ferris\ngopher\nducker").unwrap();
+        for mut game in [
+            GameState::new("FERRIS".to_string(), None, Difficulty::Medium),
+            GameState::new_evil(&list, "FERRIS".to_string(), None, Difficulty::Medium),
+        ] {
+            game.guess_letter('Z');
+            let messages = [
+                serde_json::to_string(&game.view()).unwrap(),
+                serde_json::to_string(&game.public_view()).unwrap(),
+                serde_json::to_string(&game.session_view("s")).unwrap(),
+                serde_json::to_string(&game.game_update("s")).unwrap(),
+                serde_json::to_string(&game.ai_turn_view(None)).unwrap(),
+                serde_json::to_string(&game.batch_guess_view(&[], &[])).unwrap(),
+            ];
+            for message in &messages {
+                for word in ["FERRIS", "GOPHER", "DUCKER"] {
+                    assert!(!message.contains(word), "{} in {}", word, message);
+                }
+            }
+
+            for letter in "QWXYVJ".chars() {
+                game.guess_letter(letter);
+            }
+            assert!(game.game_over);
+            assert!(serde_json::to_string(&game.view()).unwrap().contains(&game.word));
+        }
+    }
+
+    #[test]
+    fn test_helps_and_wallet() {
+        let mut game = GameState::new("RUST".to_string(), None, Difficulty::Easy);
+        game.apply_help(Help::RemoveLetter).unwrap();
+        game.apply_help(Help::ExtraLife).unwrap();
+        let view = game.view();
+        assert_eq!(view.helps_bought, [schema::Help::RemoveLetter, schema::Help::ExtraLife]);
+        assert_eq!(view.removed_letters.len(), 1);
+        assert!(view.keys.iter().any(|key| key.letter == view.removed_letters[0] && key.state == schema::KeyState::Wrong));
+        assert_eq!(view.max_wrong_guesses, 9);
+
+        let wallet = Wallet { points: 12 }.view(Some("Ada".to_string()));
+        assert_eq!(wallet.prices[2], schema::HelpPrice { help: schema::Help::ExtraLife, price: 25 });
+        assert_eq!(
+            schema::GameError::from(Wallet { points: 12 }.spend(Help::ExtraLife).unwrap_err()),
+            schema::GameError::NotEnoughPoints { price: 25, points: 12 }
+        );
+    }
+
+    #[test]
+    fn test_language_view() {
+        let view = Language::find("es").unwrap().builtin_words().language_view(Some("es"));
+        assert_eq!((view.code.as_str(), view.name.as_str()), ("es", "Español"));
+        assert!(view.alphabet.contains(&'Ñ'));
+        let view = WordList::from_text("rust").unwrap().language_view(None);
+        assert_eq!((view.code.as_str(), view.alphabet.len(), view.words), ("en", 26, 1));
+    }
+
+    #[test]
+    fn test_wordle_view() {
+        let dictionary = WordList::from_text("slate\nplant").unwrap();
+        let mut game = WordleGame::new("PLANT");
+        game.guess("slate", &[&dictionary]).unwrap();
+        let view = game.view();
+        assert_eq!(view.answer, None);
+        assert_eq!(view.guesses[0].feedback[1], schema::LetterFeedback::Correct);
+        assert_eq!(view.keys[0], schema::WordleKeyView { letter: 'A', feedback: schema::LetterFeedback::Correct });
+
+        game.guess("plant", &[&dictionary]).unwrap();
+        assert_eq!(game.view().answer.as_deref(), Some("PLANT"));
+        let error: schema::GameError = game.guess("slate", &[&dictionary]).unwrap_err().into();
+        assert_eq!(error, schema::GameError::GameAlreadyOver);
+    }
+
+    #[test]
+    fn test_versus_view() {
+        let game = GameState::new("RUST".to_string(), None, Difficulty::Medium);
+        let mut versus = Versus::new(game, VersusRule::FirstSolve).unwrap();
+        versus.guest.player = Some("Ada".to_string());
+        versus.guess_letter(Side::Guest, "r").unwrap();
+        let view = versus.view(Side::Host);
+        assert_eq!((view.opponent.name.as_deref(), view.opponent.found, view.opponent.letters), (Some("Ada"), 1, 4));
+        assert!(view.board.guessed_letters.is_empty() && view.outcome.is_none());
+
+        versus.guess_word(Side::Host, "rust").unwrap();
+        assert_eq!(versus.view(Side::Host).outcome, Some(schema::VersusOutcome::Won));
+        let view = versus.view(Side::Guest);
+        assert_eq!(view.outcome, Some(schema::VersusOutcome::Lost));
+        // The race is over, so the loser sees the word too
+        assert_eq!(view.board.word, "RUST");
+        let error: schema::GameError = versus.guess_letter(Side::Guest, "u").unwrap_err().into();
+        assert_eq!(error, schema::GameError::GameAlreadyOver);
+    }
+
+    #[test]
+    fn test_positional_view() {
+        let mut game = PositionalGame::new("ROCK'N'ROLL", Difficulty::Easy);
+        game.guess_at('R', 0).unwrap();
+        game.guess_at('O', 0).unwrap_err();
+        let view = game.view();
+        assert_eq!(view.word, None);
+        assert_eq!(view.board[..5], [Some("R".to_string()), None, None, None, Some("'".to_string())]);
+        assert_eq!(view.guesses[0].feedback, schema::LetterFeedback::Correct);
+        assert_eq!(view.max_wrong_guesses, 16);
+
+        let error: schema::GameError = game.guess_at('R', 0).unwrap_err().into();
+        assert!(matches!(error, schema::GameError::InvalidInput { .. }));
+        let error: schema::GameError = game.guess_at('4', 1).unwrap_err().into();
+        assert_eq!(error, schema::GameError::Digit { character: '4' });
+    }
+
+    #[test]
+    fn test_crossword_view() {
+        let words = WordList::from_text("rust\ntoe").unwrap();
+        let mut game = CrosswordGame::generate(&words, Difficulty::Easy, &mut sandbox_rng::RngHandle::seeded(1)).unwrap();
+        for letter in ['T', 'O', 'E'] {
+            game.guess(letter).unwrap();
+        }
+        let view = game.view();
+        // RUST and TOE crossing at their T, whichever went across
+        assert_eq!((view.rows * view.cols, view.cells.len()), (12, 6));
+        let toe = view.words.iter().find(|word| word.length == 3).unwrap();
+        assert_eq!((toe.complete, toe.word.as_deref()), (true, Some("TOE")));
+        assert!(view.words.iter().any(|word| word.word.is_none()));
+        assert_eq!(view.cells.iter().filter(|cell| cell.letter.is_none()).count(), 3);
+        assert_eq!(view.max_wrong_guesses, 9);
+
+        let error: schema::GameError = game.guess('t').unwrap_err().into();
+        assert_eq!(error, schema::GameError::AlreadyGuessed { letter: "T".to_string() });
+    }
+
+    #[test]
+    fn test_batch_guess_view() {
+        let mut game = GameState::new("GO".to_string(), None, Difficulty::Easy);
+        game.letter_case = LetterCase::Lower;
+        let inputs = vec!["g".to_string(), "!".to_string(), "o".to_string(), "x".to_string()];
+        let outcomes = game.guess_letters(&inputs);
+        let view = game.batch_guess_view(&inputs, &outcomes);
+
+        let results: Vec<schema::LetterResult> = view.outcomes.iter().map(|outcome| outcome.result).collect();
+        assert_eq!(
+            results,
+            [
+                schema::LetterResult::Hit,
+                schema::LetterResult::Rejected,
+                schema::LetterResult::Hit,
+                schema::LetterResult::Skipped,
+            ]
+        );
+        assert_eq!(view.outcomes[0].letter.as_deref(), Some("g"));
+        assert_eq!(view.outcomes[1].error, Some(schema::GameError::NotALetter { character: '!' }));
+        assert_eq!(view.outcomes[3].input, "x");
+        assert!(view.game.won);
+    }
+
+    #[test]
+    fn test_view_history() {
+        let mut game = GameState::new("RUST".to_string(), None, Difficulty::Medium);
+        game.letter_case = LetterCase::Lower;
+        game.guess_letter('R');
+        game.guess_word("RUBY");
+        let history = game.view().history;
+        assert_eq!(history.len(), 2);
+        assert_eq!((history[0].kind, history[0].guess.as_str(), history[0].hit), (schema::MoveKind::Letter, "r", true));
+        assert_eq!((history[1].kind, history[1].guess.as_str(), history[1].hit), (schema::MoveKind::Word, "ruby", false));
+        assert_eq!(history[0].at_ms, game.history[0].at_ms);
+    }
+
+    #[test]
+    fn test_match_score_follows_the_round() {
+        let mut game = GameState::new("GO".to_string(), None, Difficulty::Medium);
+        game.best_of = Some(Match::new(3, Rotation::Difficulty, Difficulty::Medium, Vec::new()).unwrap());
+        assert_eq!(game.view().match_score.unwrap().round, 1);
+
+        game.guess_word("GO");
+        let score = game.view().match_score.unwrap();
+        assert_eq!((score.wins, score.losses, score.to_win, score.winner), (1, 0, 2, None));
+        assert!(score.can_continue);
+        assert_eq!(score.score, game.score.unwrap().total);
+
+        // Undoing the winning move takes the round's result back
+        game.undo().unwrap();
+        assert_eq!(game.view().match_score.unwrap().wins, 0);
+        assert_eq!(GameState::new("GO".to_string(), None, Difficulty::Medium).view().match_score, None);
+    }
+
+    #[test]
+    fn test_race_view_keeps_the_word_out() {
+        let mut game = GameState::new("RUST".to_string(), None, Difficulty::Medium);
+        game.race = Some(Race::new(&game).unwrap());
+        game.guess_letter('Z');
+        assert_eq!(game.view().race.unwrap().turn, schema::RaceTurn::Ai);
+        game.race_turn(&WordList::from_text("rust\nruby").unwrap()).unwrap();
+
+        let race = game.view().race.unwrap();
+        assert_eq!((race.turn, race.letters, race.ai_found, race.winner), (schema::RaceTurn::Player, 4, 1, None));
+        assert!(!serde_json::to_string(&game.player_view()).unwrap().contains("RUST"));
+        assert_eq!(schema::GameError::from(game.undo().unwrap_err()), schema::GameError::invalid_input("moves can't be taken back in a race"));
+    }
+
+    #[test]
+    fn test_run_loses_a_life() {
+        let mut game = GameState::new("GO".to_string(), None, Difficulty::Medium);
+        game.run = Some(Run::new(2, Difficulty::Medium).unwrap());
+        for letter in "ABCDEFHIJK".chars() {
+            game.guess_letter(letter);
+        }
+        assert!(game.game_over && !game.won);
+        let run = game.view().run.unwrap();
+        assert_eq!((run.word, run.lives, run.over, run.score), (1, 1, false, 0));
+        assert!(run.can_continue);
+
+        game.undo().unwrap();
+        assert_eq!(game.view().run.unwrap().lives, 2);
+    }
+
+    #[test]
+    fn test_errors() {
+        assert_eq!(schema::GameError::from(HintError::GameOver), schema::GameError::GameAlreadyOver);
+        assert!(matches!(
+            schema::GameError::from(HintError::NoHintsLeft),
+            schema::GameError::HintUnavailable { .. }
+        ));
+        assert_eq!(
+            schema::GameError::from(GuessError::Digit('7')),
+            schema::GameError::Digit { character: '7' }
+        );
+        assert_eq!(
+            schema::GameError::from(SecretError::TooShort),
+            schema::GameError::invalid_input("the secret needs at least 2 letters")
+        );
+        assert_eq!(schema::GameError::from(UndoError::NothingToUndo), schema::GameError::NothingToUndo);
+        assert_eq!(schema::GameError::from(UndoError::TimedOut), schema::GameError::GameAlreadyOver);
+    }
+
+    #[test]
+    fn test_digraphs_are_spelled_out() {
+        let mut game = GameState::new("LLAMA".to_string(), None, Difficulty::Medium);
+        game.letter_case = LetterCase::Lower;
+        game.digraphs = vec![crate::digraphs::unit("LL").unwrap()];
+        game.guess_letter(game.check_letter("ll").unwrap());
+        let view = game.view();
+        assert_eq!((view.word.as_str(), view.mask.as_str()), ("ll___", "ll___"));
+        assert_eq!(view.display_word, "ll _ _ _");
+        assert_eq!(view.guessed_letters, ["ll"]);
+        assert!(view.keys.iter().any(|key| key.letter == "LL" && key.state == schema::KeyState::Correct));
+        assert_eq!(view.history[0].guess, "ll");
+        assert_eq!(
+            schema::GameError::from(game.check_letter("LL").unwrap_err()),
+            schema::GameError::AlreadyGuessed { letter: "LL".to_string() }
+        );
+    }
+
+    #[test]
+    fn test_settings_round_trip() {
+        let settings = Settings {
+            version: SETTINGS_VERSION,
+            difficulty: Difficulty::Hard,
+            category: Some("animals".to_string()),
+            language: Some("es".to_string()),
+            hints_enabled: false,
+            show_clues: true,
+            word_source: WordSource::File,
+            word_file: Some("words.txt".to_string()),
+            profanity_filter: false,
+            blocked_words: vec!["FERRIS".to_string()],
+            max_wrong_guesses: Some(9),
+            hints_allowed: None,
+            letter_case: LetterCase::Lower,
+            time_limit: Some(90),
+            ignore_accents: true,
+            digraphs: vec!["CH".to_string()],
+            display: DisplayFormat {
+                placeholder: '•',
+                letter_spacing: false,
+                word_separator: " / ".to_string(),
+            },
+            seed: Some(42),
+            min_word_length: Some(4),
+            max_word_length: Some(6),
+            telemetry: true,
+            telemetry_endpoint: Some("https://example.com/events".to_string()),
+            audit_log: true,
+            metrics_on_exit: true,
+            kids_mode: true,
+        };
+        let view = schema::SettingsView::from(&settings);
+        assert_eq!(view.letter_case, schema::LetterCase::Lower);
+        assert_eq!(view.difficulty, schema::Difficulty::Hard);
+        assert_eq!(Settings::from(view), settings);
+    }
+
+    #[test]
+    fn test_profile_views() {
+        let mut profiles = Profiles::default();
+        profiles.create("Ada").unwrap();
+        profiles.create("Grace").unwrap();
+        let mut game = GameState::new("GO".to_string(), None, Difficulty::Easy);
+        game.player = Some("Grace".to_string());
+        game.guess_word("go");
+        profiles.record(&game);
+
+        let views = profiles.views();
+        assert!(views[0].current);
+        assert!(!views[1].current);
+        assert_eq!(views[1].statistics.wins, 1);
+
+        let leaderboard = profiles.leaderboard_view();
+        assert_eq!(leaderboard.entries[0].rank, 1);
+        assert_eq!(leaderboard.entries[0].name, "Grace");
+        assert_eq!(leaderboard.entries[1].best_score, 0);
+    }
+
+    #[test]
+    fn test_suggestions_view() {
+        let words = WordList::from_text("rust\nruby\nrush\njava").unwrap();
+        let game = GameState::new("RUST".to_string(), None, Difficulty::Medium);
+        let view = game.suggestions_view(&words);
+        assert_eq!(view.words, 4);
+        assert_eq!(view.suggestions.len(), 3);
+        assert_eq!(view.suggestions[0].letter, "S");
+        assert_eq!((view.suggestions[0].score, view.suggestions[0].words), (1.0, 2));
+    }
+
+    #[test]
+    fn test_reveal_view() {
+        let mut game = GameState::new("RUST RUBY".to_string(), None, Difficulty::Medium);
+        game.letter_case = LetterCase::Lower;
+        game.guess_letter('R');
+        let view = game.view();
+        assert_eq!(view.display_word, "r _ _ _   r _ _ _");
+        let places: Vec<(u32, &str, u32)> =
+            view.reveal.places.iter().map(|place| (place.place, place.letter.as_str(), place.display_at)).collect();
+        assert_eq!(places, [(0, "r", 0), (5, "r", 10)]);
+        assert_eq!((view.reveal.revealed, view.reveal.letters), (2, 8));
+
+        game.guess_letter('Z');
+        assert_eq!(game.view().reveal.places, []);
+    }
+
+    #[test]
+    fn test_word_providers_view() {
+        let mut providers = WordProviders::default();
+        providers.register(StaticProvider::bundled(WordList::from_text("rust").unwrap()));
+        providers.register(OnlineProvider::new(OnlineWords::default()));
+        let view = providers.view(WordSource::Online);
+        assert_eq!((view.selected, view.serving), (schema::WordSource::Online, schema::WordSource::Bundled));
+        assert_eq!(view.fallback.as_deref(), Some("it has no words yet"));
+        assert_eq!(view.providers.len(), 2);
+        assert!(view.providers[0].healthy && view.providers[0].words == 1);
+        assert_eq!((view.providers[1].healthy, view.providers[1].words), (false, 0));
+        assert_eq!(providers.view(WordSource::Bundled).fallback, None);
+    }
+
+    #[test]
+    fn test_rating_history_view() {
+        let mut game = GameState::new("GO".to_string(), None, Difficulty::Hard);
+        game.race = Some(Race::new(&game).unwrap());
+        game.guess_word("go");
+        let mut ratings = Ratings::default();
+        ratings.record(&game, 1_000);
+
+        let view = ratings.history_view(Some("Ada".to_string()), Difficulty::Hard);
+        assert_eq!((view.rating, view.ai_rating), (1224, 1400));
+        assert_eq!(view.points.len(), 1);
+        assert_eq!(view.points[0].result, schema::RaceWinner::Player);
+        assert_eq!(view.points[0].change, 24);
+        assert!(ratings.history_view(None, Difficulty::Easy).points.is_empty());
+    }
+
+    #[test]
+    fn test_achievement_views() {
+        let mut game = GameState::new("RUST".to_string(), None, Difficulty::Medium);
+        game.player = Some("ada".to_string());
+        game.guess_word("rust");
+        let mut achievements = Achievements::default();
+        achievements.record(&game, 1);
+
+        let views = achievements.views(Some("ada"));
+        assert_eq!(views.len(), ACHIEVEMENTS.len());
+        assert_eq!(views[0].id, "flawless");
+        assert!(views[0].unlocked);
+        assert_eq!(views[0].word.as_deref(), Some("RUST"));
+        assert!(!views[1].unlocked);
+        assert_eq!(views[1].unlocked_at_ms, None);
+        assert!(!achievements.views(None)[0].unlocked);
+    }
+}
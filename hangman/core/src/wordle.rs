@@ -0,0 +1,286 @@
+// ============================================================================
+// WORD GAME (WORDLE-STYLE)
+// ============================================================================
+// The app's second game: find a WORD_LEN-letter word in MAX_GUESSES guesses.
+// Every guess is a whole word, and each of its letters is marked:
+// - Feedback::Correct: the right letter in the right place
+// - Feedback::Present: in the word, somewhere else
+// - Feedback::Absent: not in the word (or not as many times as guessed)
+// A letter guessed twice is only marked present as often as the word has it,
+// places that are correct counting first ("SPEED" against "ABIDE" marks the
+// first E present and the second absent).
+//
+// WORDS:
+// It shares the hangman word infrastructure (see words.rs): the five-letter
+// words are a word list like any other, loaded from "wordle.json" or
+// "wordle.txt" in the app data directory, the bundled "resources/wordle.txt",
+// or the copy compiled in. The answer is picked from it (without blocked
+// words), and a guess has to be a word of it or of the hangman words, so
+// guesses like "AEIOU" don't count.
+//
+// GAMES:
+// WordleGame is a game, with no rules shared with GameState. The desktop app
+// plays them in sessions of their own (see sessions.rs), the game isn't
+// counted in the hangman statistics. An unfinished one is saved after every
+// guess like a hangman game (see saved.rs).
+
+use sandbox_rng::{Rng, RngHandle};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::path::{Path, PathBuf};
+use crate::game::is_guessable;
+use crate::letters::normalize_word;
+use crate::words::WordList;
+
+/// Letters in every word of the game
+pub const WORD_LEN: usize = 5;
+
+/// Guesses a game allows
+pub const MAX_GUESSES: u32 = 6;
+
+/// File names looked up in the app data directory, in priority order
+pub const USER_WORDLE_FILES: [&str; 2] = ["wordle.json", "wordle.txt"];
+
+/// Built-in copy of the shipped five-letter words
+const BUILTIN_WORDLE_WORDS: &str = include_str!("../resources/wordle.txt");
+
+/// What a guessed letter says about the word
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum Feedback {
+    // Ordered from least to most useful, so a key shows the best it's had
+    Absent,
+    Present,
+    Correct,
+}
+
+/// Reasons a guess isn't taken
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum WordleError {
+    /// Not WORD_LEN letters (the number of characters guessed)
+    WrongLength(usize),
+    /// A character that isn't a letter
+    NotALetter(char),
+    /// A word of the right length that isn't in the word lists
+    NotInWordList(String),
+    /// The game has already been won or lost
+    GameOver,
+}
+
+impl fmt::Display for WordleError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            WordleError::WrongLength(found) => {
+                write!(f, "guess a word of {} letters (not {})", WORD_LEN, found)
+            }
+            WordleError::NotALetter(c) => write!(f, "{:?} isn't a letter", c),
+            WordleError::NotInWordList(word) => write!(f, "{} isn't in the word list", word),
+            WordleError::GameOver => write!(f, "the game is already over"),
+        }
+    }
+}
+
+impl std::error::Error for WordleError {}
+
+/// The words of a list that can be the answer: WORD_LEN letters, nothing else
+pub fn playable_words(list: &WordList) -> Vec<&String> {
+    list.words().iter().filter(|word| is_playable(word)).collect()
+}
+
+fn is_playable(word: &str) -> bool {
+    word.chars().count() == WORD_LEN && word.chars().all(is_guessable)
+}
+
+/// The built-in five-letter word list
+pub fn builtin_words() -> WordList {
+    WordList::from_text(BUILTIN_WORDLE_WORDS).expect("built-in word game list is not empty")
+}
+
+/// The game's words from `user_dir` or `bundled` (like WordList::load), the
+/// built-in ones if none can be loaded
+pub fn load_words(user_dir: Option<&Path>, bundled: Option<&Path>) -> WordList {
+    let candidates: Vec<PathBuf> = user_dir
+        .into_iter()
+        .flat_map(|dir| USER_WORDLE_FILES.iter().map(move |name| dir.join(name)))
+        .chain(bundled.map(Path::to_path_buf))
+        .collect();
+    WordList::load_first(&candidates)
+        .filter(|list| !playable_words(list).is_empty())
+        .unwrap_or_else(builtin_words)
+}
+
+/// Mark each letter of `guess` against `answer` (both WORD_LEN letters, uppercase)
+pub fn score(answer: &str, guess: &str) -> Vec<Feedback> {
+    let answer: Vec<char> = answer.chars().collect();
+    let guess: Vec<char> = guess.chars().collect();
+    let mut feedback = vec![Feedback::Absent; guess.len()];
+    // Letters of the answer not matched in place, each usable once for Present
+    let mut unmatched: Vec<char> = Vec::new();
+    for (i, &letter) in guess.iter().enumerate() {
+        if answer.get(i) == Some(&letter) {
+            feedback[i] = Feedback::Correct;
+        } else if let Some(&other) = answer.get(i) {
+            unmatched.push(other);
+        }
+    }
+    for (i, &letter) in guess.iter().enumerate() {
+        if feedback[i] == Feedback::Correct {
+            continue;
+        }
+        if let Some(found) = unmatched.iter().position(|&other| other == letter) {
+            unmatched.swap_remove(found);
+            feedback[i] = Feedback::Present;
+        }
+    }
+    feedback
+}
+
+/// A guess and its marks
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+pub struct WordleGuess {
+    pub word: String,
+    pub feedback: Vec<Feedback>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+pub struct WordleGame {
+    // Uppercase, WORD_LEN letters
+    pub answer: String,
+    // Oldest first
+    pub guesses: Vec<WordleGuess>,
+    pub max_guesses: u32,
+    pub won: bool,
+    pub game_over: bool,
+}
+
+impl WordleGame {
+    pub fn new(answer: &str) -> Self {
+        WordleGame {
+            answer: normalize_word(answer),
+            guesses: Vec::new(),
+            max_guesses: MAX_GUESSES,
+            won: false,
+            game_over: false,
+        }
+    }
+
+    /// A game with a random answer from `words`, None if it has no playable words
+    pub fn pick(words: &WordList, rng: &mut RngHandle) -> Option<Self> {
+        let playable = playable_words(words);
+        if playable.is_empty() {
+            return None;
+        }
+        Some(WordleGame::new(playable[rng.gen_range(0..playable.len())]))
+    }
+
+    /// Guess a word, which has to be in one of `dictionaries`
+    pub fn guess(&mut self, text: &str, dictionaries: &[&WordList]) -> Result<&WordleGuess, WordleError> {
+        if self.game_over {
+            return Err(WordleError::GameOver);
+        }
+        let word = normalize_word(text);
+        if let Some(c) = word.chars().find(|&c| !is_guessable(c)) {
+            return Err(WordleError::NotALetter(c));
+        }
+        let len = word.chars().count();
+        if len != WORD_LEN {
+            return Err(WordleError::WrongLength(len));
+        }
+        let known = word == self.answer || dictionaries.iter().any(|list| list.words().contains(&word));
+        if !known {
+            return Err(WordleError::NotInWordList(word));
+        }
+
+        let feedback = score(&self.answer, &word);
+        self.won = feedback.iter().all(|&mark| mark == Feedback::Correct);
+        self.guesses.push(WordleGuess { word, feedback });
+        self.game_over = self.won || self.guesses.len() as u32 >= self.max_guesses;
+        Ok(self.guesses.last().expect("a guess was just made"))
+    }
+
+    /// Guesses left
+    pub fn remaining(&self) -> u32 {
+        self.max_guesses.saturating_sub(self.guesses.len() as u32)
+    }
+
+    /// Every letter guessed so far with the best mark it got, in letter order
+    /// (for the keyboard)
+    pub fn key_states(&self) -> Vec<(char, Feedback)> {
+        let mut keys: Vec<(char, Feedback)> = Vec::new();
+        for guess in &self.guesses {
+            for (letter, &mark) in guess.word.chars().zip(&guess.feedback) {
+                match keys.iter_mut().find(|(key, _)| *key == letter) {
+                    Some((_, best)) => *best = (*best).max(mark),
+                    None => keys.push((letter, mark)),
+                }
+            }
+        }
+        keys.sort_unstable_by_key(|&(letter, _)| letter);
+        keys
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use Feedback::{Absent, Correct, Present};
+
+    #[test]
+    fn test_score() {
+        assert_eq!(score("CRANE", "CRANE"), [Correct; 5]);
+        assert_eq!(score("CRANE", "NACRE"), [Present, Present, Present, Present, Correct]);
+        // Only as many E's are marked as the word has, in-place ones first
+        assert_eq!(score("ABIDE", "SPEED"), [Absent, Absent, Present, Absent, Present]);
+        assert_eq!(score("EERIE", "THREE"), [Absent, Absent, Correct, Present, Correct]);
+        assert_eq!(score("PLANT", "LLAMA"), [Absent, Correct, Correct, Absent, Absent]);
+    }
+
+    #[test]
+    fn test_guesses() {
+        let dictionary = WordList::from_text("crane\nslate\nplant").unwrap();
+        let mut game = WordleGame::new("plant");
+        assert_eq!(game.guess("abc", &[&dictionary]), Err(WordleError::WrongLength(3)));
+        assert_eq!(game.guess("cr4ne", &[&dictionary]), Err(WordleError::NotALetter('4')));
+        assert_eq!(game.guess("aeiou", &[&dictionary]), Err(WordleError::NotInWordList("AEIOU".to_string())));
+        assert!(game.guesses.is_empty());
+
+        let guess = game.guess(" slate ", &[&dictionary]).unwrap();
+        assert_eq!(guess.feedback, [Absent, Correct, Correct, Present, Absent]);
+        assert_eq!(game.key_states()[0], ('A', Correct));
+        assert_eq!(game.remaining(), MAX_GUESSES - 1);
+
+        game.guess("PLANT", &[&dictionary]).unwrap();
+        assert!(game.won && game.game_over);
+        assert_eq!(game.guess("crane", &[&dictionary]), Err(WordleError::GameOver));
+    }
+
+    #[test]
+    fn test_lost_after_max_guesses() {
+        let dictionary = WordList::from_text("crane").unwrap();
+        let mut game = WordleGame::new("PLANT");
+        for _ in 0..MAX_GUESSES {
+            game.guess("crane", &[&dictionary]).unwrap();
+        }
+        assert!(game.game_over && !game.won);
+    }
+
+    #[test]
+    fn test_words() {
+        let words = builtin_words();
+        assert!(words.words().iter().all(|word| is_playable(word)));
+        let list = WordList::from_text("rust\ncrane\nice cream\nslate").unwrap();
+        assert_eq!(playable_words(&list), ["CRANE", "SLATE"]);
+
+        let game = WordleGame::pick(&list, &mut RngHandle::seeded(3)).unwrap();
+        assert!(["CRANE", "SLATE"].contains(&game.answer.as_str()));
+        assert!(WordleGame::pick(&WordList::from_text("rust").unwrap(), &mut RngHandle::seeded(3)).is_none());
+
+        // A user list without five-letter words falls back to the built-in one
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(dir.path().join("wordle.txt"), "rust\n").unwrap();
+        assert_eq!(load_words(Some(dir.path()), None).words().len(), words.words().len());
+        std::fs::write(dir.path().join("wordle.txt"), "crane\n").unwrap();
+        assert_eq!(load_words(Some(dir.path()), None).words(), ["CRANE"]);
+    }
+}
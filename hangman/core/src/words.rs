@@ -0,0 +1,704 @@
+// ============================================================================
+// WORD PROVIDER
+// ============================================================================
+// Loads the pool of words the game picks from.
+//
+// WHERE WORDS COME FROM (first match wins):
+// 1. A user word list in the app data directory ("words.json" or "words.txt"),
+//    with the settings' language first ("words.es.json" or "words.es.txt")
+// 2. The word list shipped alongside the app ("resources/words.txt"), or the
+//    language's one ("resources/words.es.txt", see languages.rs)
+// 3. The same word list compiled into the binary, so there's always something to play
+//
+// FILE FORMATS:
+// - ".json": a JSON array of entries, each a word or an object with the word
+//   and what's known about it, e.g. ["RUST", { "word": "OWL", "clue": "It
+//   hoots at night" }] (checked against its JSON Schema, see schemas.rs)
+// - anything else: one word per line, blank lines and "#" comments ignored,
+//   and a "#! guessable: ..." line for the characters guessed besides
+//   letters, e.g. "#! guessable: digits + #" for "C++" and "WEB3" (see
+//   charset.rs)
+//
+// CLUES:
+// A list keeps the clue of each word that has one, and can have a clue of
+// its own for all of its words (a category's, e.g. "It's an animal", see
+// categories.rs). clue() is the word's clue or else the list's, a game
+// carries it when the show_clues setting is on (see settings.rs).
+//
+// CLEANUP:
+// Every entry goes through ingest::clean() (uppercased, invalid characters
+// stripped, out-of-range lengths rejected, duplicates dropped), and the list
+// keeps the report of what that did (see ingest.rs). A list keeps its
+// charset, the lists made from it (without(), with_lengths()) too, so their
+// words keep the digits and symbols it guesses.
+//
+// BLOCKED WORDS:
+// without() drops the words of a blocklist (see blocklist.rs), the frontends
+// filter every pool with it before a word is picked.
+//
+// WORD LENGTHS:
+// A list indexes its words by their number of letters (spaces and other
+// characters of a phrase don't count), so with_lengths() can narrow a pool
+// to e.g. 4-6 letter words for kids, or 10+ for experts, without going
+// through every word. The bounds are a WordLengths, from the settings'
+// min_word_length / max_word_length or a start_new_game call.
+//
+// THE TRIE:
+// Searching the words (a pattern, a prefix, the words shaped like an evil
+// game's) goes through a trie of them (see trie.rs) rather than every word.
+// It's built the first time it's needed, so the lists only made to pick a
+// word from never pay for it.
+//
+// PLAYABLE WORDS:
+// playable() is both filters in one, the words a game may be picked from:
+// not blocked, and with the lengths asked for. Both frontends pick every
+// word from it, so they refuse the same lists for the same reasons
+// (PlayableError).
+//
+// OTHER LANGUAGES:
+// Words can be in any alphabet (see letters.rs). alphabet() lists the letters
+// a list uses, so the frontends can offer "Ñ" or "Ж" keys for it, and
+// alphabet_for() the keys of a bundled language (see languages.rs).
+
+use sandbox_store::{validate, Violation};
+use schemars::{schema_for, JsonSchema};
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fmt;
+use std::fs;
+use std::ops::Bound;
+use std::path::{Path, PathBuf};
+use std::sync::OnceLock;
+use crate::blocklist::Blocklist;
+use crate::charset::{Charset, CharsetError};
+use crate::game::{is_guessable, DEFAULT_ALPHABET};
+use crate::ingest::{clean_with, clean_word_with, CleanReport, MAX_WORD_LEN, MIN_WORD_LETTERS};
+use crate::languages::Language;
+use crate::letters::{fold, same_letter, upper_str};
+use crate::trie::Trie;
+
+/// File names looked up in the app data directory, in priority order
+pub const USER_WORD_FILES: [&str; 2] = ["words.json", "words.txt"];
+
+/// Built-in copy of the shipped word list, used if no file can be loaded
+const BUILTIN_WORDS: &str = include_str!("../resources/words.txt");
+
+/// Longest clue kept, in characters (longer ones are cut)
+pub const MAX_CLUE_LEN: usize = 120;
+
+/// A word of a word list with what's known about it
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct WordEntry {
+    pub word: String,
+    // Shown with the game when clues are on
+    pub clue: Option<String>,
+}
+
+/// An entry as it's written in a JSON file: the word alone, or an object
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+#[serde(untagged)]
+pub enum EntryFile {
+    Word(String),
+    Entry {
+        word: String,
+        #[serde(default)]
+        clue: Option<String>,
+    },
+}
+
+impl From<EntryFile> for WordEntry {
+    fn from(entry: EntryFile) -> Self {
+        match entry {
+            EntryFile::Word(word) => WordEntry { word, clue: None },
+            EntryFile::Entry { word, clue } => WordEntry { word, clue },
+        }
+    }
+}
+
+/// A clue trimmed and cut to MAX_CLUE_LEN, None if nothing's left
+fn clean_clue(clue: &str) -> Option<String> {
+    let clue: String = clue.trim().chars().take(MAX_CLUE_LEN).collect();
+    (!clue.is_empty()).then_some(clue)
+}
+
+/// Errors that can happen while loading a word list
+#[derive(Debug)]
+pub enum WordListError {
+    /// The file could not be read
+    Io(std::io::Error),
+    /// The file has a ".json" extension but is not valid JSON
+    Json(serde_json::Error),
+    /// The JSON doesn't match the file's schema (e.g. a number among the words)
+    Invalid(Vec<Violation>),
+    /// The file was read but contained no usable words
+    Empty,
+    /// The guessable characters can't be read (see charset.rs)
+    Charset(CharsetError),
+}
+
+impl fmt::Display for WordListError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            WordListError::Io(err) => write!(f, "could not read word list: {}", err),
+            WordListError::Json(err) => write!(f, "invalid JSON word list: {}", err),
+            WordListError::Invalid(violations) => {
+                write!(f, "word list doesn't match its schema")?;
+                for violation in violations {
+                    write!(f, "\n  {}", violation)?;
+                }
+                Ok(())
+            }
+            WordListError::Empty => write!(f, "word list contains no words"),
+            WordListError::Charset(err) => write!(f, "invalid guessable characters: {}", err),
+        }
+    }
+}
+
+impl std::error::Error for WordListError {}
+
+/// Reasons a list has no word a game may be played with
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PlayableError {
+    /// The blocklist blocks every word
+    AllBlocked,
+    /// No word that isn't blocked has `lengths` letters, they have `shortest` to `longest`
+    NoLength { lengths: WordLengths, shortest: usize, longest: usize },
+}
+
+impl fmt::Display for PlayableError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            PlayableError::AllBlocked => write!(f, "every word of the list is blocked"),
+            PlayableError::NoLength { lengths, shortest, longest } => {
+                write!(f, "no word has {}, the words have {} to {} letters", lengths, shortest, longest)
+            }
+        }
+    }
+}
+
+impl std::error::Error for PlayableError {}
+
+/// Parse a JSON word file into `T`, checking it against T's JSON Schema first
+/// so every mistake is reported with its location (e.g. `/animals/2`)
+pub(crate) fn parse_json<T: DeserializeOwned + JsonSchema>(contents: &str) -> Result<T, WordListError> {
+    let value: serde_json::Value = serde_json::from_str(contents).map_err(WordListError::Json)?;
+    validate(&schema_for!(T), &value).map_err(WordListError::Invalid)?;
+    serde_json::from_value(value).map_err(WordListError::Json)
+}
+
+/// Fewest and most letters the words of a game may have, None for no bound
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct WordLengths {
+    pub min: Option<usize>,
+    pub max: Option<usize>,
+}
+
+impl WordLengths {
+    /// Whether neither bound is set
+    pub fn is_any(&self) -> bool {
+        self.min.is_none() && self.max.is_none()
+    }
+
+    /// Whether a word of `letters` letters fits
+    pub fn contains(&self, letters: usize) -> bool {
+        self.min.is_none_or(|min| letters >= min) && self.max.is_none_or(|max| letters <= max)
+    }
+
+    /// Check the bounds are lengths a word can have (MIN_WORD_LETTERS to
+    /// MAX_WORD_LEN), with the minimum no more than the maximum
+    pub fn validate(&self) -> Result<(), String> {
+        for bound in [self.min, self.max].into_iter().flatten() {
+            if !(MIN_WORD_LETTERS..=MAX_WORD_LEN).contains(&bound) {
+                return Err(format!(
+                    "a word length must be between {} and {} letters (got {})",
+                    MIN_WORD_LETTERS, MAX_WORD_LEN, bound
+                ));
+            }
+        }
+        if let (Some(min), Some(max)) = (self.min, self.max) {
+            if min > max {
+                return Err(format!("the shortest word length ({}) is more than the longest ({})", min, max));
+            }
+        }
+        Ok(())
+    }
+}
+
+impl fmt::Display for WordLengths {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match (self.min, self.max) {
+            (Some(min), Some(max)) if min == max => write!(f, "{} letters", min),
+            (Some(min), Some(max)) => write!(f, "{} to {} letters", min, max),
+            (Some(min), None) => write!(f, "{} or more letters", min),
+            (None, Some(max)) => write!(f, "{} letters or fewer", max),
+            (None, None) => write!(f, "any number of letters"),
+        }
+    }
+}
+
+/// Letters in a word or phrase, what WordLengths bounds
+pub fn letter_count(word: &str) -> usize {
+    word.chars().filter(|&c| is_guessable(c)).count()
+}
+
+/// A pool of words to pick from
+#[derive(Debug, Clone)]
+pub struct WordList {
+    words: Vec<String>,
+    // What cleaning up the entries did
+    report: CleanReport,
+    // Positions in `words` by number of letters
+    by_length: BTreeMap<usize, Vec<usize>>,
+    // Clues of the words that have one
+    clues: BTreeMap<String, String>,
+    // Clue for every word without one (e.g. the category's)
+    clue: Option<String>,
+    // The words' trie, built on the first search
+    trie: OnceLock<Trie>,
+    // What's guessed besides letters
+    charset: Charset,
+}
+
+impl WordList {
+    /// Create a word list from any iterator of words or phrases
+    /// Entries are cleaned up like every word (see ingest::clean), the ones
+    /// that can't be played and the duplicates are dropped
+    pub fn new<I, S>(words: I) -> Result<Self, WordListError>
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<str>,
+    {
+        WordList::with_charset(words, Charset::default())
+    }
+
+    /// Create a word list like new() that guesses `charset`
+    pub fn with_charset<I, S>(words: I, charset: Charset) -> Result<Self, WordListError>
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<str>,
+    {
+        let (words, report) = clean_with(words, &charset);
+
+        if words.is_empty() {
+            return Err(WordListError::Empty);
+        }
+        let mut by_length: BTreeMap<usize, Vec<usize>> = BTreeMap::new();
+        for (i, word) in words.iter().enumerate() {
+            by_length.entry(word.chars().filter(|&c| charset.is_guessable(c)).count()).or_default().push(i);
+        }
+        Ok(WordList {
+            words,
+            report,
+            by_length,
+            clues: BTreeMap::new(),
+            clue: None,
+            trie: OnceLock::new(),
+            charset,
+        })
+    }
+
+    /// Create a word list from entries that guesses `charset`, keeping their clues
+    /// (cleaned up like new(), a word given twice keeps its first clue)
+    pub fn from_entries(entries: Vec<WordEntry>, charset: Charset) -> Result<Self, WordListError> {
+        let mut list = WordList::with_charset(entries.iter().map(|entry| &entry.word), charset)?;
+        for entry in entries {
+            let clue = entry.clue.as_deref().and_then(clean_clue);
+            if let (Ok(word), Some(clue)) = (clean_word_with(&entry.word, &list.charset), clue) {
+                list.clues.entry(word).or_insert(clue);
+            }
+        }
+        Ok(list)
+    }
+
+    /// The list with `clue` for the words without a clue of their own
+    pub fn with_clue(mut self, clue: &str) -> Self {
+        self.clue = clean_clue(clue);
+        self
+    }
+
+    /// The clue of `word` (one of the list's), or the list's clue
+    pub fn clue(&self, word: &str) -> Option<&str> {
+        self.clues.get(word).or(self.clue.as_ref()).map(String::as_str)
+    }
+
+    /// The clue shared by every word, None if the list has none
+    pub fn list_clue(&self) -> Option<&str> {
+        self.clue.as_deref()
+    }
+
+    /// What the list's games guess besides letters
+    pub fn charset(&self) -> &Charset {
+        &self.charset
+    }
+
+    /// Some of the list's words (or words added to them), as a list with its
+    /// clues and charset
+    pub(crate) fn derive<I, S>(&self, words: I) -> Result<WordList, WordListError>
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<str>,
+    {
+        WordList::with_charset(words, self.charset.clone()).map(|list| self.keep_clues(list))
+    }
+
+    /// `list` (made from some of this list's words) with this list's clues
+    pub(crate) fn keep_clues(&self, mut list: WordList) -> WordList {
+        list.clues = list
+            .words
+            .iter()
+            .filter_map(|word| Some((word.clone(), self.clues.get(word)?.clone())))
+            .collect();
+        list.clue = self.clue.clone();
+        list
+    }
+
+    /// The word list compiled into the binary
+    pub fn builtin() -> Self {
+        WordList::from_text(BUILTIN_WORDS).expect("built-in word list is not empty")
+    }
+
+    /// Parse newline-delimited text (blank lines and "#" comments are skipped,
+    /// but for a "#! guessable: ..." line)
+    pub fn from_text(contents: &str) -> Result<Self, WordListError> {
+        let lines = contents.lines().map(str::trim);
+        let spec = lines
+            .clone()
+            .filter_map(|line| line.strip_prefix("#!")?.trim().strip_prefix("guessable:"))
+            .next_back();
+        let charset = spec.map(Charset::parse).transpose().map_err(WordListError::Charset)?;
+        WordList::with_charset(lines.filter(|line| !line.starts_with('#')), charset.unwrap_or_default())
+    }
+
+    /// Parse a JSON array of entries (words, or objects with the word and its clue)
+    pub fn from_json(contents: &str) -> Result<Self, WordListError> {
+        let entries: Vec<EntryFile> = parse_json(contents)?;
+        WordList::from_entries(entries.into_iter().map(WordEntry::from).collect(), Charset::default())
+    }
+
+    /// Load a word list file, picking the format from the file extension
+    pub fn from_file(path: &Path) -> Result<Self, WordListError> {
+        let contents = fs::read_to_string(path).map_err(WordListError::Io)?;
+        let is_json = path
+            .extension()
+            .map(|ext| ext.eq_ignore_ascii_case("json"))
+            .unwrap_or(false);
+
+        if is_json {
+            WordList::from_json(&contents)
+        } else {
+            WordList::from_text(&contents)
+        }
+    }
+
+    /// Load the first usable word list from `user_dir` or `bundled`,
+    /// falling back to the built-in list if none can be loaded
+    pub fn load(user_dir: Option<&Path>, bundled: Option<&Path>) -> Self {
+        WordList::load_language(user_dir, None, bundled)
+    }
+
+    /// Like load(), trying `language`'s user word lists ("words.<language>.json",
+    /// "words.<language>.txt") before the others
+    /// A bundled language (see languages.rs) is played with its own list next
+    /// to `bundled`, or its compiled-in copy, rather than the main one
+    pub fn load_language(user_dir: Option<&Path>, language: Option<&str>, bundled: Option<&Path>) -> Self {
+        let user_files = |names: Vec<String>| -> Vec<PathBuf> {
+            user_dir
+                .into_iter()
+                .flat_map(|dir| names.iter().map(move |name| dir.join(name)))
+                .collect()
+        };
+        let mut candidates = user_files(
+            language
+                .into_iter()
+                .flat_map(|language| USER_WORD_FILES.iter().map(move |name| name.replacen('.', &format!(".{}.", language), 1)))
+                .collect(),
+        );
+        if let Some(language) = language.and_then(Language::find).filter(|language| !language.is_default()) {
+            candidates.extend(bundled.map(|path| path.with_file_name(language.file)));
+            return WordList::load_first(&candidates).unwrap_or_else(|| language.builtin_words());
+        }
+        candidates.extend(user_files(USER_WORD_FILES.iter().map(|name| name.to_string()).collect()));
+        candidates.extend(bundled.map(Path::to_path_buf));
+        WordList::load_first(&candidates).unwrap_or_else(WordList::builtin)
+    }
+
+    /// The first of `paths` that exists and holds a usable word list
+    pub(crate) fn load_first(paths: &[PathBuf]) -> Option<Self> {
+        for path in paths.iter().filter(|path| path.exists()) {
+            match WordList::from_file(path) {
+                Ok(list) => {
+                    if !list.report().is_clean() {
+                        eprintln!("Cleaned up word list {}: {}", path.display(), list.report());
+                    }
+                    return Some(list);
+                }
+                Err(err) => eprintln!("Skipping word list {}: {}", path.display(), err),
+            }
+        }
+        None
+    }
+
+    /// The list without the words `blocklist` blocks (see blocklist.rs)
+    /// Fails with Empty if every word is blocked
+    pub fn without(&self, blocklist: &Blocklist) -> Result<Self, WordListError> {
+        if blocklist.is_empty() {
+            return Ok(self.clone());
+        }
+        self.derive(self.words.iter().filter(|word| !blocklist.is_blocked(word)))
+    }
+
+    /// The words a game may be picked from: the ones `blocklist` doesn't block
+    /// with `lengths` letters (WordLengths::default() for any)
+    pub fn playable(&self, blocklist: &Blocklist, lengths: WordLengths) -> Result<Self, PlayableError> {
+        let allowed = self.without(blocklist).map_err(|_| PlayableError::AllBlocked)?;
+        allowed.with_lengths(lengths).map_err(|_| {
+            let (shortest, longest) = allowed.length_range();
+            PlayableError::NoLength { lengths, shortest, longest }
+        })
+    }
+
+    /// The list with only the words `lengths` allows, in the list's order
+    /// Fails with Empty if none fits
+    pub fn with_lengths(&self, lengths: WordLengths) -> Result<Self, WordListError> {
+        if lengths.is_any() {
+            return Ok(self.clone());
+        }
+        if matches!((lengths.min, lengths.max), (Some(min), Some(max)) if min > max) {
+            return Err(WordListError::Empty);
+        }
+        let range = (
+            lengths.min.map_or(Bound::Unbounded, Bound::Included),
+            lengths.max.map_or(Bound::Unbounded, Bound::Included),
+        );
+        let mut positions: Vec<usize> = self.by_length.range(range).flat_map(|(_, words)| words.iter().copied()).collect();
+        positions.sort_unstable();
+        self.derive(positions.into_iter().map(|i| &self.words[i]))
+    }
+
+    /// The fewest and most letters a word of the list has
+    pub fn length_range(&self) -> (usize, usize) {
+        let shortest = self.by_length.keys().next().copied().unwrap_or_default();
+        let longest = self.by_length.keys().next_back().copied().unwrap_or_default();
+        (shortest, longest)
+    }
+
+    /// All words in the list
+    pub fn words(&self) -> &[String] {
+        &self.words
+    }
+
+    /// The trie of the words (see trie.rs), positions in it are positions in words()
+    pub fn trie(&self) -> &Trie {
+        self.trie.get_or_init(|| Trie::new(&self.words))
+    }
+
+    /// The words starting with `prefix` (any case), in the list's order
+    pub fn with_prefix(&self, prefix: &str, ignore_accents: bool) -> Vec<&str> {
+        let prefix: Vec<char> = upper_str(prefix).chars().collect();
+        self.trie()
+            .starting(prefix.len(), |place, c| same_letter(c, prefix[place], ignore_accents))
+            .into_iter()
+            .map(|i| self.words[i].as_str())
+            .collect()
+    }
+
+    /// What cleaning up the entries the list was made from did
+    pub fn report(&self) -> &CleanReport {
+        &self.report
+    }
+
+    /// Every letter used in the list, accented letters next to their base
+    /// letter (A, Á, B, ...). Lists with any A-Z letter get all of A-Z, so
+    /// an English list still offers the whole keyboard
+    pub fn alphabet(&self) -> Vec<char> {
+        let mut letters: Vec<char> = self.words.iter().flat_map(|w| w.chars()).filter(|&c| is_guessable(c)).collect();
+        if letters.iter().any(char::is_ascii_alphabetic) {
+            letters.extend(DEFAULT_ALPHABET.chars());
+        }
+        letters.sort_unstable_by_key(|&c| (fold(c, true), c));
+        letters.dedup();
+        letters
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_from_text_skips_comments_and_blanks() {
+        let list = WordList::from_text("# header\nrust\n\n  tauri  \n").unwrap();
+        assert_eq!(list.words(), ["RUST", "TAURI"]);
+    }
+
+    #[test]
+    fn test_phrases_are_normalized() {
+        let list = WordList::from_text("  rust   programming\tlanguage \n").unwrap();
+        assert_eq!(list.words(), ["RUST PROGRAMMING LANGUAGE"]);
+    }
+
+    #[test]
+    fn test_entries_are_cleaned_up() {
+        let list = WordList::from_text("rust\nRust!\nx\nferris\n").unwrap();
+        assert_eq!(list.words(), ["RUST", "FERRIS"]);
+        assert_eq!(list.report().duplicates, ["RUST"]);
+        assert_eq!(list.report().rejected.len(), 1);
+        assert!(WordList::builtin().report().is_clean());
+    }
+
+    #[test]
+    fn test_other_alphabets() {
+        let list = WordList::from_text("año\nstraße\n").unwrap();
+        assert_eq!(list.words(), ["AÑO", "STRAßE"]);
+        let alphabet = list.alphabet();
+        assert_eq!(alphabet.len(), 28);
+        assert_eq!(&alphabet[13..16], ['N', 'Ñ', 'O']);
+        assert!(alphabet.contains(&'ß'));
+
+        let list = WordList::from_text("кот\n").unwrap();
+        assert_eq!(list.alphabet(), ['К', 'О', 'Т']);
+    }
+
+    #[test]
+    fn test_guessable_directive() {
+        let list = WordList::from_text("#! guessable: digits + #\nc++\nweb3\nc#\n42\n").unwrap();
+        assert_eq!(list.words(), ["C++", "WEB3", "C#", "42"]);
+        assert_eq!(list.charset(), &Charset::parse("digits + #").unwrap());
+        assert_eq!(list.with_lengths(WordLengths { min: Some(3), max: Some(4) }).unwrap().charset(), list.charset());
+        // Without it they're cleaned off, and "C" is too short to keep
+        let list = WordList::from_text("c++\nweb3\n").unwrap();
+        assert_eq!(list.words(), ["WEB"]);
+        assert!(list.charset().is_letters());
+        assert!(matches!(
+            WordList::from_text("#! guessable: digit\nweb3\n"),
+            Err(WordListError::Charset(_))
+        ));
+    }
+
+    #[test]
+    fn test_from_json() {
+        let list = WordList::from_json(r#"["rust", "Tauri"]"#).unwrap();
+        assert_eq!(list.words(), ["RUST", "TAURI"]);
+    }
+
+    #[test]
+    fn test_from_json_with_clues() {
+        let list = WordList::from_json(r#"["rust", {"word": "owl", "clue": "  It hoots at night "}, {"word": "cat"}]"#).unwrap();
+        assert_eq!(list.words(), ["RUST", "OWL", "CAT"]);
+        assert_eq!(list.clue("OWL"), Some("It hoots at night"));
+        assert_eq!(list.clue("RUST"), None);
+
+        // The list's clue is for the words without one, and narrowing the list keeps them
+        let list = list.with_clue("It's an animal").with_lengths(WordLengths { min: Some(3), max: Some(3) }).unwrap();
+        assert_eq!(list.words(), ["OWL", "CAT"]);
+        assert_eq!((list.clue("OWL"), list.clue("CAT")), (Some("It hoots at night"), Some("It's an animal")));
+    }
+
+    #[test]
+    fn test_from_json_invalid() {
+        assert!(matches!(WordList::from_json("[\"rust\""), Err(WordListError::Json(_))));
+        // Well-formed JSON of the wrong shape fails the schema check
+        assert!(matches!(
+            WordList::from_json("{\"words\": 1}"),
+            Err(WordListError::Invalid(_))
+        ));
+    }
+
+    #[test]
+    fn test_empty_list_is_error() {
+        assert!(matches!(
+            WordList::from_text("# nothing here\n\n"),
+            Err(WordListError::Empty)
+        ));
+    }
+
+    #[test]
+    fn test_without_blocked_words() {
+        let list = WordList::from_text("rust\ndamn\nwell damn\ndamnation").unwrap();
+        let filtered = list.without(&Blocklist::new(["damn"])).unwrap();
+        assert_eq!(filtered.words(), ["RUST", "DAMNATION"]);
+        assert!(matches!(
+            WordList::from_text("damn").unwrap().without(&Blocklist::new(["damn"])),
+            Err(WordListError::Empty)
+        ));
+    }
+
+    #[test]
+    fn test_playable_words() {
+        let list = WordList::from_text("go\nrust\ndamn\nlanguage").unwrap();
+        let blocklist = Blocklist::new(["damn"]);
+        let four = WordLengths { min: Some(4), max: Some(4) };
+        assert_eq!(list.playable(&blocklist, four).unwrap().words(), ["RUST"]);
+        assert_eq!(list.playable(&blocklist, WordLengths::default()).unwrap().words().len(), 3);
+        assert_eq!(
+            list.playable(&blocklist, WordLengths { min: Some(9), max: None }).err(),
+            Some(PlayableError::NoLength { lengths: WordLengths { min: Some(9), max: None }, shortest: 2, longest: 8 })
+        );
+        let blocked = WordList::from_text("damn").unwrap();
+        assert_eq!(blocked.playable(&blocklist, four).err(), Some(PlayableError::AllBlocked));
+    }
+
+    #[test]
+    fn test_load_falls_back_when_no_files() {
+        let list = WordList::load(None, Some(Path::new("/does/not/exist.txt")));
+        assert_eq!(list.words(), WordList::builtin().words());
+    }
+
+    #[test]
+    fn test_load_prefers_user_file() {
+        let dir = std::env::temp_dir().join(format!("hangman-words-{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        fs::write(dir.join("words.txt"), "ferris\n").unwrap();
+
+        let list = WordList::load(Some(&dir), None);
+        assert_eq!(list.words(), ["FERRIS"]);
+
+        fs::write(dir.join("words.es.json"), r#"["PIÑATA"]"#).unwrap();
+        assert_eq!(WordList::load_language(Some(&dir), Some("es"), None).words(), ["PIÑATA"]);
+        // No list for the language, the plain one is used
+        assert_eq!(WordList::load_language(Some(&dir), Some("pt-br"), None).words(), ["FERRIS"]);
+        // A bundled language has its own
+        assert_eq!(
+            WordList::load_language(Some(&dir), Some("fr"), None).words(),
+            crate::languages::Language::find("fr").unwrap().builtin_words().words()
+        );
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_with_lengths() {
+        let list = WordList::from_text("rust\nferris\ngo\nice cream\ncrab\nprogramming").unwrap();
+        assert_eq!(list.length_range(), (2, 11));
+        let kids = WordLengths { min: Some(4), max: Some(6) };
+        // In the list's order, a phrase counted by its letters
+        assert_eq!(list.with_lengths(kids).unwrap().words(), ["RUST", "FERRIS", "CRAB"]);
+        let experts = WordLengths { min: Some(8), max: None };
+        assert_eq!(list.with_lengths(experts).unwrap().words(), ["ICE CREAM", "PROGRAMMING"]);
+        assert_eq!(list.with_lengths(WordLengths::default()).unwrap().words().len(), 6);
+        assert!(matches!(list.with_lengths(WordLengths { min: Some(12), max: None }), Err(WordListError::Empty)));
+        assert!(matches!(list.with_lengths(WordLengths { min: Some(6), max: Some(4) }), Err(WordListError::Empty)));
+
+        assert_eq!(experts.to_string(), "8 or more letters");
+        assert!(kids.validate().is_ok());
+        assert!(WordLengths { min: Some(6), max: Some(4) }.validate().is_err());
+        assert!(WordLengths { min: None, max: Some(1) }.validate().is_err());
+    }
+
+    #[test]
+    fn test_with_prefix() {
+        let list = WordList::from_text("rust\nruby\ncafé\ncafeteria\nrun").unwrap();
+        assert_eq!(list.with_prefix("ru", false), ["RUST", "RUBY", "RUN"]);
+        assert_eq!(list.with_prefix("CAFE", false), ["CAFETERIA"]);
+        assert_eq!(list.with_prefix("cafe", true), ["CAFÉ", "CAFETERIA"]);
+        assert_eq!(list.with_prefix("", false).len(), 5);
+        assert!(list.with_prefix("rusty", false).is_empty());
+    }
+
+    #[test]
+    fn test_builtin_list() {
+        let list = WordList::builtin();
+        assert!(list.words().contains(&"RUST".to_string()));
+    }
+}
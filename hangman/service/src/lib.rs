@@ -0,0 +1,21 @@
+//! What the hangman desktop app's game commands do, without Tauri
+//!
+//! Starting a session's game, the moves (letters, word guesses, hints, giving
+//! up, pausing, undo), and what follows a move (the statistics and
+//! achievements of a finished game, the autosave, the game_updated event), as
+//! plain async functions. The app hands them its state as a
+//! [`ServiceState`] and its windows as a [`Frontend`]; its commands are thin
+//! wrappers around them.
+
+// Module declarations
+pub mod save;
+pub mod service;
+pub mod state;
+
+// Re-exports for convenience
+pub use save::Save;
+pub use service::{
+    check_playable, check_timer, expire_timed_games, game_changed, game_of, game_view, give_up, guess_letter, guess_letters, guess_word,
+    make_letter_guess, make_word_guess, pause, record_if_finished, start_session, switch_player, undo, unpause, use_hint,
+};
+pub use state::{Frontend, ServiceState};
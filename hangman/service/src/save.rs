@@ -0,0 +1,77 @@
+// ============================================================================
+// SAVES
+// ============================================================================
+// What a command asks to have written to the database (see hangman-core's
+// storage.rs), as it was when the save was queued. The service queues the
+// records a finished game counts in (ServiceState::save), the desktop app's
+// commands queue the rest; the app writes them one at a time in the
+// background (src-tauri's tasks.rs), so no command waits for the disk.
+
+use hangman_core::campaign::CAMPAIGN_KEY;
+use hangman_core::custom_words::CUSTOM_WORDS_KEY;
+use hangman_core::daily::DAILY_KEY;
+use hangman_core::definitions::DEFINITIONS_KEY;
+use hangman_core::online_words::ONLINE_WORDS_KEY;
+use hangman_core::stats::STATS_KEY;
+use hangman_core::{
+    Achievements, Campaign, CustomWords, Dailies, DefinitionCache, GameState, OnlineWords, Profiles, Statistics, Storage, StorageError,
+    WordleGame,
+};
+
+/// Something to write, as it was when the save was queued
+pub enum Save {
+    Statistics(Statistics),
+    Profiles(Profiles),
+    Achievements(Achievements),
+    CustomWords(CustomWords),
+    Dailies(Dailies),
+    Campaign(Campaign),
+    Definitions(DefinitionCache),
+    OnlineWords(OnlineWords),
+    // The game to resume after a restart (a finished one clears the save)
+    Game(Box<GameState>),
+    // The word game to resume after a restart, the same way
+    Wordle(Box<WordleGame>),
+    // Clear the saved game under a key (an interrupted game the player discarded)
+    Forget(&'static str),
+    // A finished game for the game history
+    Finished(Box<GameState>),
+}
+
+impl Save {
+    /// What's being saved, for warnings
+    pub fn name(&self) -> &'static str {
+        match self {
+            Save::Statistics(_) => "statistics",
+            Save::Profiles(_) => "profiles",
+            Save::Achievements(_) => "achievements",
+            Save::CustomWords(_) => "imported words",
+            Save::Dailies(_) => "daily results",
+            Save::Campaign(_) => "campaign progress",
+            Save::Definitions(_) => "definitions",
+            Save::OnlineWords(_) => "fetched words",
+            Save::Game(_) => "the game",
+            Save::Wordle(_) => "the word game",
+            Save::Forget(_) => "the discarded game",
+            Save::Finished(_) => "the finished game",
+        }
+    }
+
+    /// Write it to the database
+    pub fn write(&self, storage: &Storage) -> Result<(), StorageError> {
+        match self {
+            Save::Statistics(stats) => storage.save(STATS_KEY, stats),
+            Save::Profiles(profiles) => storage.save_profiles(profiles),
+            Save::Achievements(achievements) => storage.save_achievements(achievements),
+            Save::CustomWords(custom_words) => storage.save(CUSTOM_WORDS_KEY, custom_words),
+            Save::Dailies(dailies) => storage.save(DAILY_KEY, dailies),
+            Save::Campaign(campaign) => storage.save(CAMPAIGN_KEY, campaign),
+            Save::Definitions(definitions) => storage.save(DEFINITIONS_KEY, definitions),
+            Save::OnlineWords(online_words) => storage.save(ONLINE_WORDS_KEY, online_words),
+            Save::Game(game) => storage.save_game(&**game),
+            Save::Wordle(game) => storage.save_game(&**game),
+            Save::Forget(key) => storage.remove(key).map(|_| ()),
+            Save::Finished(game) => storage.record_game(game).map(|_| ()),
+        }
+    }
+}
@@ -0,0 +1,586 @@
+// ============================================================================
+// GAME SERVICE
+// ============================================================================
+// What the game commands do, as plain async functions over a ServiceState
+// (see state.rs): starting a session's game, the moves (letters, word
+// guesses, hints, giving up, pausing, undo), and what follows a move (the
+// statistics and achievements of a finished game, the autosave, the
+// game_updated event). The commands in the desktop app's main.rs (and the
+// guests' moves in its net.rs) are thin wrappers that hand them the app and
+// its state.
+//
+// THE FRONTEND:
+// Nothing in here needs Tauri: events for the windows go through a Frontend,
+// which the AppHandle is in the app. The tests below play games with one that
+// just keeps the events, on a state without a database.
+
+use hangman_core::campaign::Recorded;
+use hangman_core::game::now_ms;
+use hangman_core::{GameEvent, GameState, LetterOutcome, TelemetryEvent};
+use sandbox_schema::hangman::{BatchGuessView, GameError, GameView, SessionView, ACHIEVEMENT_UNLOCKED_EVENT, GAME_UPDATED_EVENT};
+use crate::save::Save;
+use crate::state::{Frontend, ServiceState};
+
+/// Lock the game of a session
+/// Fails with NoActiveGame if there's no such session (it was never started, or it expired)
+pub async fn game_of<S: ServiceState>(state: &S, session_id: &str) -> Result<S::Game, GameError> {
+    state.find_game(session_id).await.ok_or_else(|| {
+        tracing::warn!(session = %session_id, "unknown or expired session");
+        GameError::NoActiveGame
+    })
+}
+
+/// Apply the current settings to a new game, make it the game of a session,
+/// and return what the player may see of it
+/// A window starting another game passes its session id and keeps it,
+/// without one (or if it has expired) a new session is started
+pub async fn start_session<S: ServiceState>(app: &impl Frontend, state: &S, session_id: Option<String>, mut new_game: GameState) -> SessionView {
+    new_game.player = state.profiles().await.current.clone();
+    // Daily games keep the day's rng, so their hints are the same for everyone too, and
+    // seeded games keep their seed's
+    if new_game.daily.is_none() && new_game.seed.is_none() {
+        new_game.rng = state.rng().clone();
+    }
+
+    if let Some(id) = session_id {
+        if let Some(mut game) = state.find_game(&id).await {
+            // A timed game that ran out before being replaced still counts as lost
+            check_timer(app, state, &id, &mut game).await;
+            if !game.game_over {
+                state.emit(GameEvent::Abandoned { session_id: id.clone() }).await;
+            }
+            // Applied last so a timed game's clock starts now
+            state.settings().await.apply(&mut new_game);
+            *game = new_game;
+            state.emit(GameEvent::started(&id, &game)).await;
+            state.record_telemetry(TelemetryEvent::started(&game)).await;
+            game_changed(app, state, &id, &game).await;
+            return game.session_view(&id);
+        }
+    }
+
+    state.settings().await.apply(&mut new_game);
+    let (id, game) = state.create_session(new_game).await;
+    state.emit(GameEvent::started(&id, &game)).await;
+    state.record_telemetry(TelemetryEvent::started(&game)).await;
+    game_changed(app, state, &id, &game).await;
+    game.session_view(&id)
+}
+
+/// Refuse a move on a finished or paused game, or on the AI's turn in a race
+/// Checks the clock first, so a move made after time ran out gets the lost game back (not an error)
+pub async fn check_playable<S: ServiceState>(app: &impl Frontend, state: &S, session_id: &str, game: &mut GameState) -> Result<bool, GameError> {
+    if game.game_over {
+        return Err(GameError::GameAlreadyOver);
+    }
+    if game.is_paused() {
+        return Err(GameError::GamePaused);
+    }
+    if game.is_ai_turn() {
+        return Err(GameError::NotYourTurn);
+    }
+    check_timer(app, state, session_id, game).await;
+    Ok(game.game_over)
+}
+
+/// Make a letter guess in a session's game (the window's own, or a guest's in a hosted game)
+pub async fn make_letter_guess<S: ServiceState>(
+    app: &impl Frontend,
+    state: &S,
+    session_id: &str,
+    game: &mut GameState,
+    letter: &str,
+) -> Result<(), GameError> {
+    if !check_playable(app, state, session_id, game).await? {
+        let letter = game.check_letter(letter)?;
+        let was_over = game.game_over;
+        let hit = game.guess_letter(letter);
+        state.emit(GameEvent::LetterGuessed { session_id: session_id.to_string(), letter, hit }).await;
+        record_if_finished(app, state, session_id, was_over, game).await;
+        game_changed(app, state, session_id, game).await;
+    }
+    Ok(())
+}
+
+/// Make a guess at the whole word in a session's game, like make_letter_guess
+pub async fn make_word_guess<S: ServiceState>(
+    app: &impl Frontend,
+    state: &S,
+    session_id: &str,
+    game: &mut GameState,
+    word: &str,
+) -> Result<(), GameError> {
+    if word.trim().is_empty() {
+        return Err(GameError::invalid_input("Type a word or phrase to guess"));
+    }
+    if !check_playable(app, state, session_id, game).await? {
+        let (was_over, moves) = (game.game_over, game.history.len());
+        let hit = game.guess_word(word);
+        // Repeats of a missed guess are free and aren't moves (a miss a shield took is one)
+        if game.history.len() != moves {
+            let guess = word.trim().to_string();
+            state.emit(GameEvent::WordGuessed { session_id: session_id.to_string(), guess, hit }).await;
+        }
+        record_if_finished(app, state, session_id, was_over, game).await;
+        game_changed(app, state, session_id, game).await;
+    }
+    Ok(())
+}
+
+/// Count the game in the statistics (overall and for its player's profile,
+/// and the daily results for a daily game) and the game history if the last move just ended it,
+/// unlock the achievements it earned (with an `achievement_unlocked` event for each),
+/// save them, and tell the plugins and the telemetry
+/// Games that aren't ranked (practice games, and games the AI made moves in or with a move
+/// taken back, which aren't the player's own) skip the statistics and achievements (a daily one is still kept, so the day can't be played again)
+pub async fn record_if_finished<S: ServiceState>(app: &impl Frontend, state: &S, session_id: &str, was_over: bool, game: &GameState) {
+    if was_over || !game.game_over {
+        return;
+    }
+    state.emit(GameEvent::finished(session_id, game)).await;
+    state.record_telemetry(TelemetryEvent::finished(game)).await;
+    if game.ranked() {
+        let mut stats = state.stats().await;
+        stats.record(game);
+        let mut profiles = state.profiles().await;
+        profiles.record(game);
+        state.save(Save::Statistics(stats.clone()));
+        state.save(Save::Profiles(profiles.clone()));
+        state.save(Save::Finished(Box::new(game.clone())));
+
+        // The streak of the profile that played, or of every game without one
+        let player = game.player.as_deref();
+        let streak = match player.and_then(|name| profiles.get(name)) {
+            Some(profile) => profile.stats.current_streak,
+            None => stats.current_streak,
+        };
+        let mut achievements = state.achievements().await;
+        let unlocked = achievements.record(game, streak);
+        if !unlocked.is_empty() {
+            state.save(Save::Achievements(achievements.clone()));
+        }
+        for achievement in unlocked {
+            let view = achievement.view(player, achievements.get(achievement.id, player));
+            app.notify(ACHIEVEMENT_UNLOCKED_EVENT, view);
+        }
+    }
+    {
+        let mut dailies = state.dailies().await;
+        if dailies.record(game) {
+            state.save(Save::Dailies(dailies.clone()));
+        }
+    }
+
+    // A campaign level's result, and the player's time once it completes the campaign
+    // (the campaign is locked after the profiles, so it's let go first)
+    let recorded = {
+        let mut campaign = state.campaign().await;
+        let recorded = campaign.record(game, now_ms());
+        if recorded != Recorded::Nothing {
+            state.save(Save::Campaign(campaign.clone()));
+        }
+        recorded
+    };
+    if let (Recorded::Completed(time_ms), Some(player)) = (recorded, game.player.as_deref()) {
+        let mut profiles = state.profiles().await;
+        if profiles.record_campaign(player, time_ms) {
+            state.save(Save::Profiles(profiles.clone()));
+        }
+    }
+}
+
+/// Lose a game if its time has run out, and count it
+/// Every command that reads a game calls this first (moves check it themselves)
+pub async fn check_timer<S: ServiceState>(app: &impl Frontend, state: &S, session_id: &str, game: &mut GameState) {
+    let was_over = game.game_over;
+    game.check_timer();
+    record_if_finished(app, state, session_id, was_over, game).await;
+    if !was_over && game.game_over {
+        game_changed(app, state, session_id, game).await;
+    }
+}
+
+/// A session's game changed (a new game, a move, the clock running out): save it and
+/// send every window a `game_updated` event with it, so none have to ask for it
+pub async fn game_changed<S: ServiceState>(app: &impl Frontend, state: &S, session_id: &str, game: &GameState) {
+    state.game_changed(session_id, game).await;
+    app.notify(GAME_UPDATED_EVENT, game.game_update(session_id));
+}
+
+/// Lose every timed game whose time has run out, so its window hears about it
+/// (through check_timer's game_updated event) without asking
+pub async fn expire_timed_games<S: ServiceState>(app: &impl Frontend, state: &S) {
+    for id in state.session_ids().await {
+        // (a session may have expired since)
+        let Some(mut game) = state.find_game(&id).await else {
+            continue;
+        };
+        if game.deadline.is_some() && !game.game_over {
+            check_timer(app, state, &id, &mut game).await;
+        }
+    }
+}
+
+/// Hand a session's unfinished game to the newly selected profile (finished games keep their player)
+/// Call without holding the profiles lock, a game's lock is always taken first
+pub async fn switch_player<S: ServiceState>(app: &impl Frontend, state: &S, session_id: Option<&str>, player: Option<String>) {
+    let Some(id) = session_id else {
+        return;
+    };
+    if let Some(mut game) = state.find_game(id).await {
+        check_timer(app, state, id, &mut game).await;
+        if !game.game_over {
+            game.player = player;
+            game_changed(app, state, id, &game).await;
+        }
+    }
+}
+
+/// Guess a letter in a session's game, the game after it
+pub async fn guess_letter<S: ServiceState>(app: &impl Frontend, state: &S, session_id: &str, letter: &str) -> Result<GameView, GameError> {
+    let mut game = game_of(state, session_id).await?;
+    make_letter_guess(app, state, session_id, &mut game, letter).await?;
+    Ok(game.view())
+}
+
+/// Guess several letters in a session's game in order, with each one's outcome
+pub async fn guess_letters<S: ServiceState>(app: &impl Frontend, state: &S, session_id: &str, letters: &[String]) -> Result<BatchGuessView, GameError> {
+    let mut game = game_of(state, session_id).await?;
+    let outcomes = if check_playable(app, state, session_id, &mut game).await? {
+        vec![LetterOutcome::Skipped; letters.len()]
+    } else {
+        let was_over = game.game_over;
+        let outcomes = game.guess_letters(letters);
+        for outcome in &outcomes {
+            let (letter, hit) = match *outcome {
+                LetterOutcome::Hit(letter) => (letter, true),
+                LetterOutcome::Miss(letter) => (letter, false),
+                LetterOutcome::Rejected(_) | LetterOutcome::Skipped => continue,
+            };
+            state.emit(GameEvent::LetterGuessed { session_id: session_id.to_string(), letter, hit }).await;
+        }
+        record_if_finished(app, state, session_id, was_over, &game).await;
+        game_changed(app, state, session_id, &game).await;
+        outcomes
+    };
+    Ok(game.batch_guess_view(letters, &outcomes))
+}
+
+/// Guess the whole word in a session's game, the game after it
+pub async fn guess_word<S: ServiceState>(app: &impl Frontend, state: &S, session_id: &str, word: &str) -> Result<GameView, GameError> {
+    let mut game = game_of(state, session_id).await?;
+    make_word_guess(app, state, session_id, &mut game, word).await?;
+    Ok(game.view())
+}
+
+/// Reveal a letter of a session's game for a wrong guess
+pub async fn use_hint<S: ServiceState>(app: &impl Frontend, state: &S, session_id: &str) -> Result<GameView, GameError> {
+    let mut game = game_of(state, session_id).await?;
+    let was_over = game.game_over;
+    let result = game.use_hint();
+    if let Ok(letter) = result {
+        state.emit(GameEvent::HintUsed { session_id: session_id.to_string(), letter }).await;
+    }
+    // A hint can reveal the last letter and win the game (or arrive too late and lose it)
+    record_if_finished(app, state, session_id, was_over, &game).await;
+    if result.is_ok() || game.game_over != was_over {
+        game_changed(app, state, session_id, &game).await;
+    }
+    result?;
+    Ok(game.view())
+}
+
+/// End a session's game as a loss
+pub async fn give_up<S: ServiceState>(app: &impl Frontend, state: &S, session_id: &str) -> Result<GameView, GameError> {
+    let mut game = game_of(state, session_id).await?;
+    let was_over = game.game_over;
+    let gave_up = game.give_up();
+    // (the time may have run out first, which ends the game too)
+    record_if_finished(app, state, session_id, was_over, &game).await;
+    if game.game_over != was_over {
+        game_changed(app, state, session_id, &game).await;
+    }
+    if !gave_up {
+        return Err(GameError::GameAlreadyOver);
+    }
+    Ok(game.view())
+}
+
+/// Stop the clock of a session's timed game
+pub async fn pause<S: ServiceState>(app: &impl Frontend, state: &S, session_id: &str) -> Result<GameView, GameError> {
+    let mut game = game_of(state, session_id).await?;
+    let was_over = game.game_over;
+    let paused = game.pause();
+    // (the time may have run out before the pause came in)
+    record_if_finished(app, state, session_id, was_over, &game).await;
+    if paused.is_ok() || game.game_over != was_over {
+        game_changed(app, state, session_id, &game).await;
+    }
+    paused?;
+    Ok(game.view())
+}
+
+/// Start a paused game's clock again
+pub async fn unpause<S: ServiceState>(app: &impl Frontend, state: &S, session_id: &str) -> Result<GameView, GameError> {
+    let mut game = game_of(state, session_id).await?;
+    game.resume()?;
+    game_changed(app, state, session_id, &game).await;
+    Ok(game.view())
+}
+
+/// Take back the last move of a session's game
+pub async fn undo<S: ServiceState>(app: &impl Frontend, state: &S, session_id: &str) -> Result<GameView, GameError> {
+    let mut game = game_of(state, session_id).await?;
+    check_timer(app, state, session_id, &mut game).await;
+    let action = game.undo()?;
+    state.emit(GameEvent::Undone { session_id: session_id.to_string(), action }).await;
+    game_changed(app, state, session_id, &game).await;
+    Ok(game.view())
+}
+
+/// A session's game, once its clock is checked
+pub async fn game_view<S: ServiceState>(app: &impl Frontend, state: &S, session_id: &str) -> Result<GameView, GameError> {
+    let mut game = game_of(state, session_id).await?;
+    check_timer(app, state, session_id, &mut game).await;
+    Ok(game.view())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use parking_lot::lock_api::ArcMutexGuard;
+    use parking_lot::{Mutex, RawMutex};
+    use serde::Serialize;
+    use std::future::Future;
+    use std::ops::DerefMut;
+    use std::pin::pin;
+    use std::sync::Arc;
+    use std::task::{Context, Poll, Waker};
+    use hangman_core::{Achievements, Campaign, Dailies, Difficulty, PowerUp, Profiles, SessionId, Sessions, Settings, Statistics};
+    use sandbox_rng::RngHandle;
+
+    /// A Frontend that keeps the names of the events sent
+    #[derive(Default)]
+    struct Events(Mutex<Vec<String>>);
+
+    impl Frontend for Events {
+        fn notify<P: Serialize + Clone>(&self, event: &str, _payload: P) {
+            self.0.lock().push(event.to_string());
+        }
+    }
+
+    impl Events {
+        fn count(&self, event: &str) -> usize {
+            self.0.lock().iter().filter(|sent| *sent == event).count()
+        }
+    }
+
+    /// The default settings and empty records, without a database (saves are
+    /// only counted), plugins (events are only kept), or telemetry
+    #[derive(Default)]
+    struct TestState {
+        sessions: Mutex<Sessions<Arc<Mutex<GameState>>>>,
+        settings: Mutex<Settings>,
+        stats: Mutex<Statistics>,
+        profiles: Mutex<Profiles>,
+        achievements: Mutex<Achievements>,
+        dailies: Mutex<Dailies>,
+        campaign: Mutex<Campaign>,
+        rng: RngHandle,
+        saves: Mutex<Vec<&'static str>>,
+        events: Mutex<Vec<GameEvent>>,
+    }
+
+    // Nothing in here waits on a lock another task holds, so they're parking_lot's
+    impl ServiceState for TestState {
+        type Game = ArcMutexGuard<RawMutex, GameState>;
+
+        async fn create_session(&self, game: GameState) -> (SessionId, Self::Game) {
+            let game = Arc::new(Mutex::new(game));
+            let locked = game.lock_arc();
+            (self.sessions.lock().create(game), locked)
+        }
+
+        async fn find_game(&self, session_id: &str) -> Option<Self::Game> {
+            let game = self.sessions.lock().get(session_id).cloned()?;
+            Some(game.lock_arc())
+        }
+
+        async fn session_ids(&self) -> Vec<SessionId> {
+            self.sessions.lock().games().map(|(id, _)| id.clone()).collect()
+        }
+
+        async fn settings(&self) -> impl DerefMut<Target = Settings> + '_ {
+            self.settings.lock()
+        }
+
+        async fn stats(&self) -> impl DerefMut<Target = Statistics> + '_ {
+            self.stats.lock()
+        }
+
+        async fn profiles(&self) -> impl DerefMut<Target = Profiles> + '_ {
+            self.profiles.lock()
+        }
+
+        async fn achievements(&self) -> impl DerefMut<Target = Achievements> + '_ {
+            self.achievements.lock()
+        }
+
+        async fn dailies(&self) -> impl DerefMut<Target = Dailies> + '_ {
+            self.dailies.lock()
+        }
+
+        async fn campaign(&self) -> impl DerefMut<Target = Campaign> + '_ {
+            self.campaign.lock()
+        }
+
+        fn rng(&self) -> &RngHandle {
+            &self.rng
+        }
+
+        async fn emit(&self, event: GameEvent) {
+            self.events.lock().push(event);
+        }
+
+        async fn record_telemetry(&self, _event: TelemetryEvent) {}
+
+        fn save(&self, save: Save) {
+            self.saves.lock().push(save.name());
+        }
+
+        async fn game_changed(&self, _session_id: &str, _game: &GameState) {}
+    }
+
+    /// Run a test's future (nothing in it waits, so one poll finishes it)
+    fn block_on<F: Future>(future: F) -> F::Output {
+        match pin!(future).poll(&mut Context::from_waker(Waker::noop())) {
+            Poll::Ready(output) => output,
+            Poll::Pending => panic!("the test waited on something"),
+        }
+    }
+
+    /// A new session playing `word`
+    async fn play(app: &Events, state: &TestState, word: &str) -> String {
+        let game = GameState::new(word.to_string(), None, Difficulty::Medium);
+        start_session(app, state, None, game).await.session_id
+    }
+
+    #[test]
+    fn test_winning() {
+        block_on(async {
+            let (app, state) = (Events::default(), TestState::default());
+            let id = play(&app, &state, "RUST").await;
+            for letter in ["r", "u", "s"] {
+                assert!(!guess_letter(&app, &state, &id, letter).await.unwrap().game_over);
+            }
+            let view = guess_letter(&app, &state, &id, "t").await.unwrap();
+            assert!(view.game_over && view.won);
+            assert_eq!(view.word, "RUST");
+
+            let stats = state.stats.lock().clone();
+            assert_eq!((stats.games_played, stats.wins, stats.current_streak), (1, 1, 1));
+            // The new game and every move were sent to the windows
+            assert_eq!(app.count(GAME_UPDATED_EVENT), 5);
+            assert!(app.count(ACHIEVEMENT_UNLOCKED_EVENT) > 0);
+            assert!(state.saves.lock().contains(&"the finished game"));
+        });
+    }
+
+    #[test]
+    fn test_sessions_are_locked_on_their_own() {
+        block_on(async {
+            let (app, state) = (Events::default(), TestState::default());
+            let first = play(&app, &state, "RUST").await;
+            let second = play(&app, &state, "RUBY").await;
+            // A command holding the first game doesn't hold up moves in the second
+            let held = game_of(&state, &first).await.unwrap();
+            assert_eq!(guess_letter(&app, &state, &second, "r").await.unwrap().guessed_letters, ["R"]);
+            drop(held);
+            assert!(guess_letter(&app, &state, &first, "u").await.is_ok());
+
+            state.sessions.lock().remove(&first);
+            assert_eq!(game_of(&state, &first).await.err(), Some(GameError::NoActiveGame));
+        });
+    }
+
+    #[test]
+    fn test_losing() {
+        block_on(async {
+            let (app, state) = (Events::default(), TestState::default());
+            let id = play(&app, &state, "RUST").await;
+            let mut view = guess_word(&app, &state, &id, "ruby").await.unwrap();
+            assert_eq!(view.wrong_guesses, 2);
+            for letter in ["a", "b", "c", "d", "e", "f"] {
+                if view.game_over {
+                    break;
+                }
+                view = guess_letter(&app, &state, &id, letter).await.unwrap();
+            }
+            assert!(view.game_over && !view.won);
+            assert_eq!(view.word, "RUST");
+            assert_eq!(guess_letter(&app, &state, &id, "r").await.unwrap_err(), GameError::GameAlreadyOver);
+            // Undo opens it again, the loss stays counted
+            assert!(!undo(&app, &state, &id).await.unwrap().game_over);
+
+            let stats = state.stats.lock().clone();
+            assert_eq!((stats.games_played, stats.losses), (1, 1));
+        });
+    }
+
+    #[test]
+    fn test_guesses_are_checked() {
+        block_on(async {
+            let (app, state) = (Events::default(), TestState::default());
+            let id = play(&app, &state, "RUST").await;
+            assert_eq!(guess_letter(&app, &state, &id, "7").await.unwrap_err(), GameError::Digit { character: '7' });
+            guess_letter(&app, &state, &id, "r").await.unwrap();
+            assert_eq!(guess_letter(&app, &state, &id, "R").await.unwrap_err(), GameError::AlreadyGuessed { letter: "R".to_string() });
+            assert_eq!(guess_letter(&app, &state, "nope", "s").await.unwrap_err(), GameError::NoActiveGame);
+
+            let batch = guess_letters(&app, &state, &id, &["u".to_string(), "z".to_string()]).await.unwrap();
+            assert_eq!((batch.game.wrong_guesses, batch.game.game_over), (1, false));
+            assert!(give_up(&app, &state, &id).await.unwrap().game_over);
+            // Only the game that just ended is counted, once
+            assert_eq!(state.stats.lock().games_played, 1);
+        });
+    }
+
+    #[test]
+    fn test_shielded_misses_are_misses() {
+        block_on(async {
+            let (app, state) = (Events::default(), TestState::default());
+            let id = play(&app, &state, "RUST").await;
+            // A miss costs nothing behind the shield, and is still a move that missed
+            game_of(&state, &id).await.unwrap().activate_powerup(PowerUp::Shield).unwrap();
+            assert_eq!(guess_letter(&app, &state, &id, "z").await.unwrap().wrong_guesses, 0);
+            game_of(&state, &id).await.unwrap().activate_powerup(PowerUp::Shield).unwrap();
+            assert_eq!(guess_word(&app, &state, &id, "ruby").await.unwrap().wrong_guesses, 0);
+
+            let session_id = id.clone();
+            let moves: Vec<_> = state.events.lock().iter().filter(|event| !matches!(event, GameEvent::Started { .. })).cloned().collect();
+            assert_eq!(
+                moves,
+                [
+                    GameEvent::LetterGuessed { session_id: session_id.clone(), letter: 'Z', hit: false },
+                    GameEvent::WordGuessed { session_id, guess: "ruby".to_string(), hit: false },
+                ]
+            );
+        });
+    }
+
+    #[test]
+    fn test_campaign_level() {
+        block_on(async {
+            let (app, state) = (Events::default(), TestState::default());
+            let mut game = GameState::new("RUST".to_string(), None, Difficulty::Easy);
+            game.campaign_level = Some(1);
+            let id = start_session(&app, &state, None, game).await.session_id;
+            assert_eq!(give_up(&app, &state, &id).await.unwrap().campaign_level, Some(1));
+
+            // A lost level is kept, and is still the one to play
+            let progress = state.campaign.lock().progress(None);
+            assert_eq!((progress.result(1).unwrap().attempts, progress.next_level()), (1, 1));
+            assert!(!progress.unlocked(2) && progress.started_at_ms.is_none());
+        });
+    }
+}
@@ -0,0 +1,70 @@
+// ============================================================================
+// WHAT THE SERVICE NEEDS OF THE APP
+// ============================================================================
+// The service's functions (see service.rs) don't own any state: they're
+// handed the app's as a ServiceState, and the windows as a Frontend. In the
+// desktop app both are Tauri's (AppState and the AppHandle, see src-tauri's
+// main.rs), the tests have their own.
+//
+// LOCKS:
+// A ServiceState hands out its games and records locked, each on its own,
+// and the app picks the locks. The desktop app's are tokio's, so a command
+// waiting for one doesn't hold up a runtime thread, and every session's game
+// has a lock of its own: one window's slow command doesn't hold up another's
+// guesses. The guards are held across awaits, so the app's need to be Send
+// for its commands to be (the service asks nothing of them itself).
+//
+// Lock order: a session's game, then settings, stats, profiles, achievements,
+// dailies, campaign (never a game while holding another game or a record).
+
+use serde::Serialize;
+use std::future::Future;
+use std::ops::DerefMut;
+use hangman_core::{Achievements, Campaign, Dailies, GameEvent, GameState, Profiles, SessionId, Settings, Statistics, TelemetryEvent};
+use sandbox_rng::RngHandle;
+use crate::save::Save;
+
+/// Where the events for the windows go
+pub trait Frontend: Sync {
+    /// Send every window an event with its payload
+    fn notify<P: Serialize + Clone>(&self, event: &str, payload: P);
+}
+
+/// The app's sessions and records, locked when they're asked for
+pub trait ServiceState: Sync {
+    /// A session's game, locked until it's dropped
+    type Game: DerefMut<Target = GameState>;
+
+    /// Start a session playing `game`, returns its id and its locked game
+    fn create_session(&self, game: GameState) -> impl Future<Output = (SessionId, Self::Game)>;
+
+    /// Lock the game of a session, None if there's no such session (it was
+    /// never started, or it expired)
+    fn find_game(&self, session_id: &str) -> impl Future<Output = Option<Self::Game>>;
+
+    /// The sessions playing now
+    fn session_ids(&self) -> impl Future<Output = Vec<SessionId>>;
+
+    fn settings(&self) -> impl Future<Output = impl DerefMut<Target = Settings> + '_>;
+    fn stats(&self) -> impl Future<Output = impl DerefMut<Target = Statistics> + '_>;
+    fn profiles(&self) -> impl Future<Output = impl DerefMut<Target = Profiles> + '_>;
+    fn achievements(&self) -> impl Future<Output = impl DerefMut<Target = Achievements> + '_>;
+    fn dailies(&self) -> impl Future<Output = impl DerefMut<Target = Dailies> + '_>;
+    fn campaign(&self) -> impl Future<Output = impl DerefMut<Target = Campaign> + '_>;
+
+    /// Shared by every game, so a seed replays a whole session of games
+    fn rng(&self) -> &RngHandle;
+
+    /// Tell the plugins about something that happened in a game
+    fn emit(&self, event: GameEvent) -> impl Future<Output = ()>;
+
+    /// Count a game in the telemetry (nothing unless the player opted in)
+    fn record_telemetry(&self, event: TelemetryEvent) -> impl Future<Output = ()>;
+
+    /// Queue a save, returns right away
+    fn save(&self, save: Save);
+
+    /// A session's game changed: save it to resume after a restart, and send it to
+    /// anyone else playing it (the windows are told through the Frontend)
+    fn game_changed(&self, session_id: &str, game: &GameState) -> impl Future<Output = ()>;
+}
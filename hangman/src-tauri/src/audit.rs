@@ -0,0 +1,98 @@
+// ============================================================================
+// COMMAND AUDIT LOG AND METRICS (THE IPC HOOKS)
+// ============================================================================
+// Where the calls in the audit log come from (the log itself, its file and
+// its redaction, is hangman-core's audit.rs). Only while the `audit_log`
+// setting is on, nothing is kept otherwise. The same hooks time every call
+// for the command metrics (hangman-core's metrics.rs), which are always on.
+//
+// MATCHING CALLS TO ANSWERS:
+// Tauri hands a command's call and its answer to two different hooks: the
+// invoke handler sees the command and its arguments, the responder the
+// answer and the id of the JavaScript callback waiting for it, and nothing
+// ties the two together. INVOKE_SCRIPT is Tauri's own IPC script with one
+// change, it copies the callback id into the arguments (AUDIT_CALL_ARG), so
+// handler() begins each call under the id respond() finishes it under.
+// Commands ignore arguments they don't take, so none of them sees it.
+//
+// LOCKING:
+// The hooks aren't async, so the log and the metrics are behind std Mutexes
+// (AppState.audit and AppState.metrics), only held to note a call and write
+// its line, one after the other.
+
+use serde_json::Value;
+use std::sync::{Mutex, MutexGuard, PoisonError};
+use std::time::Instant;
+use tauri::api::ipc::{format_callback, format_callback_result, CallbackFn};
+use tauri::{Invoke, InvokeResponse, Manager, Window};
+use hangman_core::game::now_ms;
+use hangman_core::AuditLog;
+use crate::AppState;
+
+/// Argument INVOKE_SCRIPT adds to every call, its callback id
+pub const AUDIT_CALL_ARG: &str = "__audit_call";
+
+/// Tauri's `__TAURI_POST_MESSAGE__` (Maps sent as objects), adding AUDIT_CALL_ARG
+pub const INVOKE_SCRIPT: &str = r#"Object.defineProperty(window, '__TAURI_POST_MESSAGE__', {
+  value: (message) => window.ipc.postMessage(JSON.stringify({ ...message, __audit_call: message.callback }, (_k, val) => {
+    if (val instanceof Map) {
+      let o = {};
+      val.forEach((v, k) => o[k] = v);
+      return o;
+    } else {
+      return val;
+    }
+  }))
+})"#;
+
+/// The audit log (or the metrics), even if a hook panicked holding it
+pub fn lock<T>(mutex: &Mutex<T>) -> MutexGuard<'_, T> {
+    mutex.lock().unwrap_or_else(PoisonError::into_inner)
+}
+
+/// The invoke handler running `commands`, noting each call as it's invoked
+pub fn handler(commands: impl Fn(Invoke) + Send + Sync + 'static) -> impl Fn(Invoke) + Send + Sync + 'static {
+    move |invoke| {
+        begin(&invoke);
+        commands(invoke)
+    }
+}
+
+fn begin(invoke: &Invoke) {
+    let window = invoke.message.window_ref();
+    let Some(state) = window.try_state::<AppState>() else {
+        return;
+    };
+    let payload = invoke.message.payload();
+    let Some(id) = payload.get(AUDIT_CALL_ARG).and_then(Value::as_u64) else {
+        return;
+    };
+    lock(&state.metrics).begin(window.label(), id as usize, invoke.message.command(), Instant::now());
+    let mut audit = lock(&state.audit);
+    if !audit.is_enabled() {
+        return;
+    }
+    let mut args = payload.clone();
+    if let Some(fields) = args.as_object_mut() {
+        fields.remove(AUDIT_CALL_ARG);
+    }
+    audit.begin(window.label(), id as usize, invoke.message.command(), &args, now_ms());
+}
+
+/// The invoke responder: time and log the call the answer is for, then hand
+/// the answer to its JavaScript callback like Tauri's own responder
+pub fn respond(window: Window, response: InvokeResponse, success: CallbackFn, error: CallbackFn) {
+    let result = response.into_result();
+    if let Some(state) = window.try_state::<AppState>() {
+        lock(&state.metrics).finish(window.label(), success.0, result.is_ok(), Instant::now());
+        let outcome = result.as_ref().map(|_| ()).map_err(Clone::clone);
+        if let Err(err) = lock(&state.audit).finish(window.label(), success.0, outcome, now_ms()) {
+            tracing::warn!("couldn't write the audit log: {}", err);
+        }
+    }
+    let callback = format_callback_result(result, success, error)
+        .unwrap_or_else(|err| format_callback(error, &err.to_string()).expect("an error message serializes"));
+    if let Err(err) = window.eval(&callback) {
+        tracing::warn!("failed to answer a command: {}", err);
+    }
+}
@@ -0,0 +1,108 @@
+// ============================================================================
+// AUTOSAVE
+// ============================================================================
+// The desktop side of hangman-core's saved.rs, one Autosaver per game mode
+// (hangman games and word games) in AppState. Every command that changes a
+// game calls changed() with it once the move is accepted, nothing else
+// writes a saved game:
+// - a running game is queued with the Saver (see tasks.rs), which writes it
+//   to the database on its own thread, so a crash loses at most the move
+//   being written
+// - a finished game clears the save if it's the saved one
+//
+// AFTER A RESTART:
+// load() reads the game each mode left saved as its interrupted game. The
+// frontend asks for them (get_saved_games) before starting a game, and
+// offers to restore each: restore() hands the game to the window that
+// resumes it, discard() forgets it and its save.
+//
+// ONE GAME PER MODE:
+// The save is the last game a move was made in. A game of another session
+// ending doesn't clear it, and an interrupted game stays saved until a game
+// of its mode replaces it or it's discarded.
+
+use hangman_core::{Autosave, GameState, SessionId, Storage, WordleGame};
+use hangman_service::Save;
+use tauri::async_runtime::Mutex;
+use crate::tasks::Saver;
+
+/// A game mode the app autosaves
+pub trait Mode: Autosave + Send + 'static {
+    /// What the game is called, for warnings
+    const NAME: &'static str;
+
+    /// The save writing `game` (or clearing the save once it's over)
+    fn save(game: Self) -> Save;
+}
+
+impl Mode for GameState {
+    const NAME: &'static str = "game";
+
+    fn save(game: Self) -> Save {
+        Save::Game(Box::new(game))
+    }
+}
+
+impl Mode for WordleGame {
+    const NAME: &'static str = "word game";
+
+    fn save(game: Self) -> Save {
+        Save::Wordle(Box::new(game))
+    }
+}
+
+/// Saves a game mode's unfinished game after every move, and keeps the one
+/// left unfinished when the app last closed until it's restored or discarded
+pub struct Autosaver<G> {
+    // The game found at startup
+    interrupted: Mutex<Option<G>>,
+    // Session whose game is saved now, None until a move is made in one
+    session: Mutex<Option<SessionId>>,
+}
+
+impl<G: Mode> Autosaver<G> {
+    /// The saved game of the mode in `storage` becomes the interrupted game
+    /// (a save that can't be read is left alone and not offered)
+    pub fn load(storage: Option<&Storage>) -> Self {
+        let interrupted = storage.and_then(|storage| {
+            storage.load_game::<G>().unwrap_or_else(|err| {
+                tracing::warn!("not offering to resume the last {}: {}", G::NAME, err);
+                None
+            })
+        });
+        Autosaver {
+            interrupted: Mutex::new(interrupted),
+            session: Mutex::new(None),
+        }
+    }
+
+    /// A session's game changed: save it to resume after a restart (it replaces
+    /// the game saved before), or forget it once that game is over
+    pub async fn changed(&self, saver: &Saver, session_id: &str, game: &G) {
+        let mut session = self.session.lock().await;
+        if game.is_over() && session.as_deref() != Some(session_id) {
+            // Another session's game is the saved one
+            return;
+        }
+        *session = (!game.is_over()).then(|| session_id.to_string());
+        saver.save(G::save(game.clone()));
+    }
+
+    /// The interrupted game, left in place
+    pub async fn interrupted(&self) -> Option<G> {
+        self.interrupted.lock().await.clone()
+    }
+
+    /// Take the interrupted game to resume it, it stays saved until the
+    /// resumed session saves over it
+    pub async fn restore(&self) -> Option<G> {
+        self.interrupted.lock().await.take()
+    }
+
+    /// Drop the interrupted game, and its save unless a game played since replaced it
+    pub async fn discard(&self, saver: &Saver) {
+        if self.interrupted.lock().await.take().is_some() && self.session.lock().await.is_none() {
+            saver.save(Save::Forget(G::KEY));
+        }
+    }
+}
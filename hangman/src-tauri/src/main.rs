@@ -16,153 +16,2012 @@
 //
 // IMPORTED CRATES:
 
-// rand::Rng - Random number generation trait
-// Source: "rand = "0.8"" in Cargo.toml
-// Used for: Randomly selecting a word from our word list
-use rand::Rng;
+// tauri::async_runtime::Mutex - Tokio's async-aware lock, re-exported by Tauri
+// Source: "tauri = { version = "1.5", features = ["shell-open"] }" in Cargo.toml
+// Used for: Shared access to the game sessions and statistics across async commands,
+// waiting for a lock doesn't block a runtime thread
+use tauri::async_runtime::Mutex;
+
+// tokio::sync::OwnedMutexGuard - A lock guard that keeps its Arc, Tokio's like the Mutex above
+// Source: "tokio = { version = "1", features = ["sync", "time", "net", "macros"] }" in Cargo.toml
+// Used for: A session's game, held locked by the game service (see sessions.rs)
+use tokio::sync::OwnedMutexGuard;
+
+// std::ops::DerefMut - Standard library trait for values that hand out a mutable reference
+// Source: Rust standard library (always available)
+// Used for: The locked records AppState hands the game service
+use std::ops::DerefMut;
 
-// serde - Serialization/Deserialization framework
+// serde::Serialize - Serde's trait for values that can be turned into JSON
 // Source: "serde = { version = "1.0", features = ["derive"] }" in Cargo.toml
-// Used for: Converting Rust structs to/from JSON for communication with the frontend
-// The Deserialize and Serialize traits allow automatic JSON conversion
-use serde::{Deserialize, Serialize};
+// Used for: The payloads of the events sent to the windows
+use serde::Serialize;
 
-// std::sync::Mutex - Standard library (built-in, no Cargo.toml entry needed)
+// std::collections::BTreeMap - Standard library sorted map
 // Source: Rust standard library (always available)
-// Used for: Thread-safe access to shared game state across multiple requests
-use std::sync::Mutex;
+// Used for: The file schemas by name, in a stable order
+use std::collections::BTreeMap;
+
+// std::time::{Duration, SystemTime} - Standard library time types
+// Source: Rust standard library (always available)
+// Used for: Which day's daily challenge to start, and how often the clock of timed games ticks
+use std::time::{Duration, SystemTime};
+
+// std::path::{Path, PathBuf} - Standard library file paths
+// Source: Rust standard library (always available)
+// Used for: The settings file (read at startup, written by set_settings), and the word list file a player imports
+use std::path::{Path, PathBuf};
 
 // tauri::State - Tauri framework's state management
 // Source: "tauri = { version = "1.5", features = ["shell-open"] }" in Cargo.toml
 // Used for: Accessing shared application state in Tauri command handlers
-use tauri::State;
-
-#[derive(Debug, Clone, Serialize, Deserialize)]
-struct GameState {
-    word: String,
-    guessed_letters: Vec<char>,
-    wrong_guesses: u32,
-    max_wrong_guesses: u32,
-    game_over: bool,
-    won: bool,
-}
-
-impl GameState {
-    fn new(word: String) -> Self {
-        GameState {
-            word: word.to_uppercase(),
-            guessed_letters: Vec::new(),
-            wrong_guesses: 0,
-            max_wrong_guesses: 6,
-            game_over: false,
-            won: false,
-        }
-    }
-
-    fn get_display_word(&self) -> String {
-        self.word
-            .chars()
-            .map(|c| {
-                if self.guessed_letters.contains(&c) {
-                    c.to_string()
-                } else {
-                    "_".to_string()
-                }
-            })
-            .collect::<Vec<String>>()
-            .join(" ")
+// tauri::Manager - Gives the app access to its paths and managed state
+// tauri::AppHandle - Sends events to the windows (game_updated, achievement_unlocked), as the game service's Frontend (see Windows)
+// tauri::RunEvent - Lets the app finish writing saves before it exits (and tells it a window closed)
+use tauri::{AppHandle, Manager, RunEvent, State, WindowEvent};
+
+// hangman_core - The game rules, shared with the terminal CLI
+// Source: "hangman-core = { path = "../core" }" in Cargo.toml
+// Used for: GameState (and its shareable result and replay), the AI player (and its letter frequencies for smart hints, and its letter suggestions), the word search, game sessions, word lists (and imported words), categories, difficulty levels, game modes,
+// batches of letter guesses, best-of-N matches, two-player duels, head-to-head duels on a LAN, arcade runs, races against the AI and the ratings they move, the campaign, profiles and their points, helps, power-ups, daily results, achievements, word ratings, word definitions, words fetched online, the word providers new games get their words from,
+// game events, file schemas, the SQLite database everything is saved in, exporting the game history and per-word statistics from it, the word game, the positional game, the crossword game,
+// and the opt-in gameplay telemetry and command audit log, and the command metrics
+use hangman_core::audit::AUDIT_FILE;
+use hangman_core::game::now_ms;
+use hangman_core::metrics::METRICS_FILE;
+use hangman_core::telemetry::TELEMETRY_FILE;
+use hangman_core::{
+    ai, campaign, custom_words, multiplayer, online_words, rating, schemas, storage, wordle, DailyProvider, FileProvider, OnlineProvider, StaticProvider, WordProviders, GameTelemetry, TelemetryBatch, TelemetryEvent, Achievements, AiMove, Campaign, Categories, CustomWords, Dailies, DefinitionCache, Dictionary, Difficulty,
+    AuditLog, ClientMessage, CommandMetrics, GameEvent, GameMode, GameState, Help, HistoryExport, Inventory, Match, OnlineWords, Pattern, CrosswordGame, PositionalGame, PowerUp, Profiles, Race, Ratings, Replay, Run, SessionId, Settings, Statistics, Storage, StorageError, WordApi, WordList, WordSource,
+    VersusRule, Wallet, WordLengths, WordleGame, DEFAULT_VERSUS_PORT, LANGUAGES, START_LIVES, parse_letter,
+};
+
+// sandbox_plugin - Shared plugin interface
+// Source: "sandbox-plugin = { path = "../../crates/sandbox-plugin" }" in Cargo.toml
+// Used for: Sending game events to extensions, and logging them
+use sandbox_plugin::{PluginHost, TracingPlugin};
+
+// serde_json - JSON values
+// Source: "serde_json = "1.0"" in Cargo.toml
+// Used for: Sending the JSON Schemas of the game's files to the frontend
+use serde_json::Value;
+
+// sandbox_schema - The messages sent to and from the webview
+// Source: "sandbox-schema = { path = "../../crates/sandbox-schema" }" in Cargo.toml
+// Used for: Command results and errors with TypeScript definitions generated from the same types
+use sandbox_schema::hangman::{
+    self as schema, AchievementView, ActivationView, AiTurnView, AuditLogView, BatchGuessView, CampaignView, ConnectionView, DefinitionView, ExportFormat, ExportSummary, GameError, GameView, ImportSummary, InventoryView,
+    LanguageView, Leaderboard, LetterFrequenciesView, MetricsView, CrosswordSessionView, CrosswordView, PositionalSessionView, PositionalView, ProfileView, PublicGameView, PurchaseView, RatingHistoryView, ReplayView, SavedGameKind, SavedGamesView, WalletView,
+    SessionView, SettingsView, StatisticsView, SuggestionsView, TelemetryView, VersusUpdate, WordLengthsView, WordListReport, WordProvidersView, WordRatingView, WordSearchView, WordStatsView, WordleSessionView,
+    WordleView,
+};
+use sandbox_schema::SCHEMA_VERSION;
+
+// sandbox_rng - Seedable randomness shared by the sandbox apps
+// Source: "sandbox-rng = { path = "../../crates/sandbox-rng" }" in Cargo.toml
+// Used for: Word and hint picks (a seed makes them repeatable), and the daily challenge's day
+use sandbox_rng::{day_number, RngHandle};
+
+// sandbox_config - Layered settings loading (file, env vars), same as the CLI
+// Source: "sandbox-config = { path = "../../crates/sandbox-config" }" in Cargo.toml
+// Used for: Loading, validating, and saving game settings
+use sandbox_config::{save_file, ConfigLoader};
+
+// sandbox_store - Versioned JSON documents saved in a directory
+// Source: "sandbox-store = { path = "../../crates/sandbox-store" }" in Cargo.toml
+// Used for: Reading what earlier versions saved as JSON files, to copy it into the database
+use sandbox_store::Store;
+
+// sandbox_telemetry - Console + rotating log file output and panic logging
+// Source: "sandbox-telemetry = { path = "../../crates/sandbox-telemetry" }" in Cargo.toml
+// Used for: Writing warnings and crashes to the app's log directory
+use sandbox_telemetry::Telemetry;
+
+// Background saves and timers (see tasks.rs)
+// Source: this crate, on Tauri's tokio runtime ("tokio = { version = "1", features = ["sync", "time"] }" in Cargo.toml)
+// Used for: Writing to the database without holding a command's locks, and the clock of timed games
+mod tasks;
+use tasks::{every, Saver};
+
+// Saving each game mode's unfinished game after every move (see autosave.rs)
+// Source: this crate, over hangman-core's saved.rs
+// Used for: Restoring a game the app closed or crashed in, and offering it at startup
+mod autosave;
+use autosave::Autosaver;
+
+// hangman_service - The game logic of the commands, without Tauri
+// Source: "hangman-service = { path = "../service" }" in Cargo.toml
+// Used for: Starting sessions, the moves, and what follows them (statistics, achievements, saves, events),
+// called by the commands and by net.rs for guests' moves, over AppState (its ServiceState) and Windows (its Frontend)
+use hangman_service::{
+    self as service, check_playable, check_timer, expire_timed_games, game_changed, record_if_finished, start_session, switch_player, Frontend,
+    Save, ServiceState,
+};
+
+// Every window's game of each mode, each locked on its own (see sessions.rs)
+// Source: this crate, with tokio's locks
+// Used for: Starting, finding, and locking the sessions' games, for the commands and for the game service
+mod sessions;
+use sessions::{create_session, find_game, game_of, SessionGames};
+
+// Online multiplayer over WebSocket (see net.rs)
+// Source: this crate, with "tokio-tungstenite = "0.24"" in Cargo.toml
+// Used for: Hosting a session's game for guests on other machines, and joining a game another app hosts
+mod net;
+use net::Connection;
+
+// Head-to-head duels on a LAN over WebSocket (see versus.rs)
+// Source: this crate, with "tokio-tungstenite = "0.24"" in Cargo.toml
+// Used for: Hosting a duel where two apps race on the same word, and joining one another app hosts
+mod versus;
+use versus::VersusConnection;
+
+// The opt-in command audit log, and the command metrics (see audit.rs)
+// Source: this crate, over hangman-core's audit.rs and metrics.rs
+// Used for: Logging every command the frontend invokes, its result, and how long it took,
+// and timing every command for get_metrics
+mod audit;
+
+// Word list files shipped alongside the app, relative to the Tauri resource directory
+// (they live in the hangman-core crate so the CLI can use them too)
+// Other languages' lists, words.<language>.txt, are next to BUNDLED_WORD_FILE
+const BUNDLED_WORD_FILE: &str = "../core/resources/words.txt";
+const BUNDLED_CATEGORIES_FILE: &str = "../core/resources/categories.json";
+const BUNDLED_WORDLE_FILE: &str = "../core/resources/wordle.txt";
+
+// Words get_most_missed_words returns when it isn't given a limit
+const MOST_MISSED_WORDS: u32 = 10;
+
+// Entries get_audit_log returns when it isn't given a limit
+const AUDIT_ENTRIES: u32 = 100;
+
+// Settings file looked up in the app config directory
+const SETTINGS_FILE: &str = "hangman.toml";
+
+// How often timed games are checked for running out
+const CLOCK_INTERVAL: Duration = Duration::from_millis(250);
+
+// Lock order: a session's game (each has its own lock, see sessions.rs), then settings, providers, words, stats, profiles, achievements, custom_words, dailies,
+// campaign, definitions, online_words, autosave, plugins, telemetry, connection, or versus (held on its own)
+// (never a game while holding another lock or game, a word game is locked before words and wordle_autosave;
+// the sessions themselves are only locked for a moment to find, create, or end one)
+// The locks are async-aware, so a command waiting for one doesn't hold up a runtime thread,
+// except audit's and metrics', which the IPC hooks take on their own for a moment (see audit.rs)
+struct AppState {
+    // Each window's game, by the session id returned when it started, each locked on its own
+    sessions: SessionGames,
+    // The main word list, in the settings' language (set_language loads another)
+    words: Mutex<WordList>,
+    // Where word lists are loaded from: the app data dir, and the bundled main list
+    word_dir: Option<PathBuf>,
+    bundled_words: Option<PathBuf>,
+    categories: Categories,
+    // Shared by every game, so the settings' seed (or SANDBOX_SEED) replays a whole session of games
+    rng: RngHandle,
+    settings: Mutex<Settings>,
+    // Where set_settings saves them, read again on the next start
+    settings_file: PathBuf,
+    stats: Mutex<Statistics>,
+    profiles: Mutex<Profiles>,
+    // Achievements unlocked by each player
+    achievements: Mutex<Achievements>,
+    // Words each player imported, picked from along with `words`
+    custom_words: Mutex<CustomWords>,
+    // Finished daily challenges, so a day can't be played twice
+    dailies: Mutex<Dailies>,
+    // Each player's campaign progress, which levels are unlocked
+    campaign: Mutex<Campaign>,
+    // Word definitions fetched so far, and where they're fetched from
+    definitions: Mutex<DefinitionCache>,
+    dictionary: Dictionary,
+    // Words fetched from the word API so far, played with the online word source
+    online_words: Mutex<OnlineWords>,
+    // Where new games get their words from, the settings' word_source picks one (see hangman-core's providers.rs)
+    providers: Mutex<WordProviders>,
+    word_api: WordApi,
+    // Saves every move's game, and keeps the game left unfinished when the app last closed
+    // until a window restores or discards it
+    autosave: Autosaver<GameState>,
+    // The same for word games
+    wordle_autosave: Autosaver<WordleGame>,
+    // Writes statistics, profiles, achievements, imported words, dailies, campaign progress, definitions, fetched words,
+    // the unfinished games, and finished games to the database in the background (see tasks.rs)
+    saver: Saver,
+    // The same database, read when the game history is exported (None without an app data directory)
+    storage: Option<Storage>,
+    // Extensions told about every game (see hangman-core's events.rs)
+    plugins: Mutex<PluginHost<GameEvent>>,
+    // Anonymous game numbers waiting to be sent, collected only if the player opted in (see hangman-core's telemetry.rs)
+    telemetry: Mutex<GameTelemetry>,
+    // Hosting a session's game, or playing one another app hosts (see net.rs)
+    connection: Mutex<Connection>,
+    // Hosting a head-to-head duel, or playing one another app hosts (see versus.rs)
+    versus: Mutex<VersusConnection>,
+    // Each window's word game, in sessions of their own (see hangman-core's wordle.rs)
+    wordles: SessionGames<WordleGame>,
+    // Five-letter words the word game picks from, guesses can be these or `words`
+    wordle_words: WordList,
+    // Each window's positional game (see hangman-core's positional.rs), not saved
+    positionals: SessionGames<PositionalGame>,
+    // Each window's crossword game (see hangman-core's crossword.rs), not saved
+    crosswords: SessionGames<CrosswordGame>,
+    // The commands invoked, logged only if the player turned it on (see audit.rs)
+    audit: std::sync::Mutex<AuditLog>,
+    // How often each command ran and how long it took (see hangman-core's metrics.rs)
+    metrics: std::sync::Mutex<CommandMetrics>,
+}
+
+/// The windows, as the game service's Frontend (AppHandle is Tauri's and the trait hangman-service's,
+/// so it's implemented on a wrapper of this crate's)
+struct Windows<'a>(&'a AppHandle);
+
+impl Frontend for Windows<'_> {
+    fn notify<P: Serialize + Clone>(&self, event: &str, payload: P) {
+        if let Err(err) = self.0.emit_all(event, payload) {
+            tracing::warn!("failed to emit {}: {}", event, err);
+        }
     }
+}
+
+// What the game service needs of the app (see hangman-service's state.rs), its guards are tokio's
+impl ServiceState for AppState {
+    type Game = OwnedMutexGuard<GameState>;
 
-    fn check_win(&self) -> bool {
-        self.word
-            .chars()
-            .all(|c| self.guessed_letters.contains(&c))
+    async fn create_session(&self, game: GameState) -> (SessionId, Self::Game) {
+        create_session(&self.sessions, game).await
+    }
+
+    async fn find_game(&self, session_id: &str) -> Option<Self::Game> {
+        find_game(&self.sessions, session_id).await
+    }
+
+    async fn session_ids(&self) -> Vec<SessionId> {
+        sessions::session_ids(&self.sessions).await
+    }
+
+    async fn settings(&self) -> impl DerefMut<Target = Settings> + '_ {
+        self.settings.lock().await
+    }
+
+    async fn stats(&self) -> impl DerefMut<Target = Statistics> + '_ {
+        self.stats.lock().await
+    }
+
+    async fn profiles(&self) -> impl DerefMut<Target = Profiles> + '_ {
+        self.profiles.lock().await
+    }
+
+    async fn achievements(&self) -> impl DerefMut<Target = Achievements> + '_ {
+        self.achievements.lock().await
+    }
+
+    async fn dailies(&self) -> impl DerefMut<Target = Dailies> + '_ {
+        self.dailies.lock().await
+    }
+
+    async fn campaign(&self) -> impl DerefMut<Target = Campaign> + '_ {
+        self.campaign.lock().await
+    }
+
+    fn rng(&self) -> &RngHandle {
+        &self.rng
+    }
+
+    async fn emit(&self, event: GameEvent) {
+        emit(self, event).await;
+    }
+
+    async fn record_telemetry(&self, event: TelemetryEvent) {
+        record_telemetry(self, event).await;
+    }
+
+    fn save(&self, save: Save) {
+        self.saver.save(save);
+    }
+
+    async fn game_changed(&self, session_id: &str, game: &GameState) {
+        self.autosave.changed(&self.saver, session_id, game).await;
+        net::game_changed(self, session_id, game).await;
     }
 }
 
-struct AppState {
-    game: Mutex<GameState>,
+/// Tell the plugins about something that happened in a game
+async fn emit(state: &AppState, event: GameEvent) {
+    state.plugins.lock().await.emit(&event);
+}
+
+/// Count a game in the telemetry (nothing unless the player opted in), sending the batch once it's full
+async fn record_telemetry(state: &AppState, event: TelemetryEvent) {
+    if let Some(batch) = state.telemetry.lock().await.record(event) {
+        send_telemetry(batch);
+    }
+}
+
+/// Write or post a telemetry batch on a blocking thread, nothing waits for it
+fn send_telemetry(batch: TelemetryBatch) {
+    tauri::async_runtime::spawn_blocking(move || {
+        if let Err(err) = batch.send() {
+            tracing::warn!("dropped {} telemetry events: {}", batch.events.len(), err);
+        }
+    });
+}
+
+/// Open the database in the app data dir, copying in what an earlier version saved
+/// as JSON files there the first time (None if it can't be opened, nothing is saved then)
+fn open_storage(dir: &Path) -> Option<Storage> {
+    let storage = Storage::open(dir.join(storage::STORAGE_FILE))
+        .map_err(|err| tracing::warn!("statistics won't be saved: {}", err))
+        .ok()?;
+    let is_empty = storage.is_empty().unwrap_or_else(|err| {
+        tracing::warn!("can't read the database: {}", err);
+        false
+    });
+    if is_empty {
+        match Store::open(dir).map_err(Into::into).and_then(|store| storage.import_store(&store)) {
+            Ok(imported) if !imported.is_empty() => tracing::info!(?imported, "copied the saved files into the database"),
+            Ok(_) => {}
+            Err(err) => tracing::warn!("couldn't copy the saved files into the database: {}", err),
+        }
+    }
+    Some(storage)
+}
+
+/// Load settings from the app config dir (or ./hangman.toml) and HANGMAN_* env vars
+/// A damaged file is read from the copy the last save kept (hangman.toml.1), and the
+/// defaults are used if neither can be loaded; a file that can't (e.g. one from a newer
+/// version) becomes hangman.toml.1 when it's saved over, so it isn't lost
+fn load_settings(config_dir: Option<&Path>) -> Settings {
+    let mut loader = ConfigLoader::new("hangman");
+    if let Some(path) = config_dir.map(|dir| dir.join(SETTINGS_FILE)).filter(|path| path.exists()) {
+        loader = loader.file(path);
+    }
+    loader.load().unwrap_or_else(|err| {
+        tracing::warn!("using default settings: {}", err);
+        Settings::default()
+    })
+}
+
+/// The difficulty a start_* command was given, or the settings' difficulty
+async fn difficulty_or_default(state: &AppState, difficulty: Option<schema::Difficulty>) -> Difficulty {
+    match difficulty {
+        Some(difficulty) => difficulty.into(),
+        None => state.settings.lock().await.difficulty,
+    }
+}
+
+/// The word lengths a start_* command was given, each bound the settings' one if it wasn't
+/// Fails with invalid_input if they aren't lengths a word can have
+async fn lengths_or_default(state: &AppState, lengths: Option<WordLengthsView>) -> Result<WordLengths, GameError> {
+    let defaults = state.settings.lock().await.word_lengths();
+    let given = lengths.unwrap_or_default();
+    let lengths = WordLengths {
+        min: given.min.map(|min| min as usize).or(defaults.min),
+        max: given.max.map(|max| max as usize).or(defaults.max),
+    };
+    lengths.validate().map_err(GameError::invalid_input)?;
+    Ok(lengths)
+}
+
+/// The main word list (the fetched words with the online word source, once there are any,
+/// or the kids' words in kids mode) with the words the selected profile imported, and the
+/// source that gave the main list (None for the kids' words)
+async fn word_pool(state: &AppState) -> (WordList, Option<WordSource>) {
+    let (word_source, kids_mode) = {
+        let settings = state.settings.lock().await;
+        (settings.word_source, settings.kids_mode)
+    };
+    let (base, source) = if kids_mode {
+        (WordList::kids(), None)
+    } else {
+        let (words, source) = served_words(state, word_source).await;
+        (words, Some(source))
+    };
+    let player = state.profiles.lock().await.current.clone();
+    (state.custom_words.lock().await.pool(player.as_deref(), &base), source)
+}
+
+/// The words `word_source`'s provider gives, and the source that gave them (the fallback's
+/// if it can't, the main word list's if that can't either)
+async fn served_words(state: &AppState, word_source: WordSource) -> (WordList, WordSource) {
+    let served = state.providers.lock().await.words(word_source);
+    match served {
+        Ok(served) => {
+            if let Some(err) = &served.fallback {
+                tracing::warn!(source = %word_source, "playing the {} words: {}", served.source, err);
+            }
+            (served.words, served.source)
+        }
+        Err(err) => {
+            tracing::warn!(source = %word_source, "playing the main word list: {}", err);
+            (state.words.lock().await.clone(), WordSource::Bundled)
+        }
+    }
+}
+
+/// Register the providers made from the main word list and the settings: the bundled one,
+/// the daily one, and the word_file's (a path relative to the app data dir, if it's relative)
+fn register_providers(providers: &mut WordProviders, words: &WordList, settings: &Settings, word_dir: Option<&Path>) {
+    providers.register(StaticProvider::bundled(words.clone()));
+    providers.register(DailyProvider::new(words.clone()));
+    match &settings.word_file {
+        Some(path) => providers.register(FileProvider::new(word_dir.map_or_else(|| PathBuf::from(path), |dir| dir.join(path)))),
+        None => providers.remove(WordSource::File),
+    }
+}
+
+/// The keys of a game picked from `words`, in the settings' language (see hangman-core's languages.rs)
+async fn keys(state: &AppState, words: &WordList) -> Vec<char> {
+    words.alphabet_for(state.settings.lock().await.language.as_deref())
+}
+
+/// The main word list for `language`, read on a blocking thread (see WordList::load_language)
+async fn load_words(state: &AppState, language: Option<String>) -> Result<WordList, String> {
+    let (word_dir, bundled) = (state.word_dir.clone(), state.bundled_words.clone());
+    let words = tauri::async_runtime::spawn_blocking(move || {
+        WordList::load_language(word_dir.as_deref(), language.as_deref(), bundled.as_deref())
+    })
+    .await
+    .map_err(|err| err.to_string())?;
+    tracing::info!(words = words.words().len(), "word list loaded");
+    if !words.report().is_clean() {
+        tracing::warn!("word list cleaned up: {}", words.report());
+    }
+    Ok(words)
+}
+
+/// Fetch a batch of words from the word API in the background, add them to the
+/// fetched words, and save them (offline, the words fetched before are played)
+fn fetch_online_words(app: &AppHandle) {
+    let app = app.clone();
+    tauri::async_runtime::spawn(async move {
+        let state = app.state::<AppState>();
+        let api = state.word_api.clone();
+        match tauri::async_runtime::spawn_blocking(move || api.fetch(online_words::FETCH_COUNT)).await {
+            Ok(Ok(fetched)) => {
+                let fetched = {
+                    let mut online_words = state.online_words.lock().await;
+                    let added = online_words.add(fetched);
+                    tracing::info!(added, cached = online_words.words.len(), "words fetched");
+                    state.saver.save(Save::OnlineWords(online_words.clone()));
+                    online_words.clone()
+                };
+                state.providers.lock().await.register(OnlineProvider::new(fetched));
+            }
+            Ok(Err(err)) => tracing::warn!("playing the saved or bundled words: {}", err),
+            Err(err) => tracing::warn!("fetching words stopped: {}", err),
+        }
+    });
+}
+
+/// The game of a match's current round (from its category, or the main word list
+/// with the selected profile's words), with the match in it
+async fn round_game(state: &AppState, best_of: Match, mode: GameMode) -> Result<GameState, GameError> {
+    let (category, difficulty) = best_of.setup();
+    let lengths = lengths_or_default(state, None).await?;
+    let mut game = match category.as_deref().and_then(|name| state.categories.get(name)) {
+        Some(words) => pick_game(state, words, category, difficulty, mode, None, lengths).await?,
+        None => pool_game(state, difficulty, mode, None, lengths).await?,
+    };
+    game.best_of = Some(best_of);
+    Ok(game)
+}
+
+/// The next game of an arcade run (a word from the main list with the selected
+/// profile's words, at the run's difficulty), with the run in it
+async fn run_game(state: &AppState, run: Run, mode: GameMode) -> Result<GameState, GameError> {
+    let lengths = lengths_or_default(state, None).await?;
+    let difficulty = run.difficulty;
+    let mut game = pool_game(state, difficulty, mode, None, lengths).await?;
+    game.run = Some(run);
+    Ok(game)
+}
+
+/// The words the AI player (and the smart hint, and the letter suggestions) guesses from in
+/// `game`: the ones its word was drawn from, its category's, the day's main word list,
+/// or the source's (see word_pool) with its player's words, less the blocked ones
+async fn ai_dictionary(state: &AppState, game: &GameState) -> WordList {
+    let blocklist = state.settings.lock().await.blocklist();
+    let words = match game.category.as_deref().and_then(|name| state.categories.get(name)) {
+        Some(words) => words.clone(),
+        None if game.daily.is_some() => state.words.lock().await.clone(),
+        None => {
+            // (games saved before the source was kept have the main list's)
+            let base = match game.word_source {
+                Some(source) => served_words(state, source).await.0,
+                None if game.kids => WordList::kids(),
+                None => state.words.lock().await.clone(),
+            };
+            state.custom_words.lock().await.pool(game.player.as_deref(), &base)
+        }
+    };
+    // The game's word was playable when it was picked, so only a blocklist changed since can leave none
+    words.playable(&blocklist, WordLengths::default()).unwrap_or(words)
+}
+
+/// The words of `words` a game may be played with: not blocked by the settings (see
+/// hangman-core's blocklist.rs), with `lengths` letters (WordLengths::default() for any)
+/// Every word a game is played with comes through here
+/// Fails with invalid_input if no word is left
+async fn playable(state: &AppState, words: &WordList, lengths: WordLengths) -> Result<WordList, GameError> {
+    let blocklist = state.settings.lock().await.blocklist();
+    words.playable(&blocklist, lengths).map_err(|err| {
+        tracing::warn!("no word to play: {}", err);
+        GameError::from(err)
+    })
+}
+
+/// A new game with a word from the main pool (see word_pool), keeping the source it came from
+async fn pool_game(state: &AppState, difficulty: Difficulty, mode: GameMode, seed: Option<u64>, lengths: WordLengths) -> Result<GameState, GameError> {
+    let (words, source) = word_pool(state).await;
+    let mut game = pick_game(state, &words, None, difficulty, mode, seed, lengths).await?;
+    game.word_source = source;
+    Ok(game)
+}
+
+/// A new game with a word from `words` (the ones that aren't blocked and have `lengths` letters),
+/// in the classic or evil mode
+/// The word and hints come from `seed` if there's one, the app's shared rng otherwise
+async fn pick_game(
+    state: &AppState,
+    words: &WordList,
+    category: Option<String>,
+    difficulty: Difficulty,
+    mode: GameMode,
+    seed: Option<u64>,
+    lengths: WordLengths,
+) -> Result<GameState, GameError> {
+    let words = playable(state, words, lengths).await?;
+    let mut game = match seed {
+        Some(seed) => GameState::new_seeded(&words, category, difficulty, mode, seed),
+        None => GameState::new_random(&words, category, difficulty, mode, state.rng.clone()),
+    };
+    game.alphabet = keys(state, &words).await;
+    Ok(game)
+}
+
+// Commands are async and run on Tauri's tokio runtime, so a command waiting for a lock
+// (or a dictionary lookup) doesn't hold up the others. Async commands that take State
+// have to return a Result, the ones that can't fail never return the error
+
+// Difficulty and mode are optional so the frontend can leave them out
+// (defaults to the settings' difficulty, classic)
+// With a seed the game's word and hints are drawn from it, so the same seed (with the same
+// words) starts the same game again, e.g. the `seed` of a GameView from a bug report
+// `lengths` bounds the letters of the word (each bound defaults to the settings' one),
+// invalid_input if no word fits
+// With `practice` wrong guesses never lose the game and it isn't ranked (GameView's
+// `practice` and `ranked`), see hangman-core's game.rs
+// Every start_* command takes the window's session id (if it has one yet) and
+// returns it with the game, the other game commands need it
+// (the frontend passes the arguments by name, so they aren't grouped in a struct)
+#[allow(clippy::too_many_arguments)]
+#[tauri::command]
+async fn start_new_game(
+    session_id: Option<String>,
+    difficulty: Option<schema::Difficulty>,
+    mode: Option<schema::GameMode>,
+    seed: Option<u64>,
+    lengths: Option<WordLengthsView>,
+    practice: Option<bool>,
+    app: AppHandle,
+    state: State<'_, AppState>,
+) -> Result<SessionView, GameError> {
+    let difficulty = difficulty_or_default(&state, difficulty).await;
+    let lengths = lengths_or_default(&state, lengths).await?;
+    let mut game = pool_game(&state, difficulty, mode.unwrap_or_default().into(), seed, lengths).await?;
+    if practice.unwrap_or(false) {
+        game.start_practice();
+    }
+    Ok(start_session(&Windows(&app), &*state, session_id, game).await)
+}
+
+// The same word for everyone today (see hangman-core's daily.rs)
+// Fails with DailyCompleted if the selected profile already finished today's
+#[tauri::command]
+async fn start_daily_game(
+    session_id: Option<String>,
+    difficulty: Option<schema::Difficulty>,
+    app: AppHandle,
+    state: State<'_, AppState>,
+) -> Result<SessionView, GameError> {
+    let now = SystemTime::now();
+    let player = state.profiles.lock().await.current.clone();
+    if state.dailies.lock().await.get(day_number(now), player.as_deref()).is_some() {
+        return Err(GameError::DailyCompleted);
+    }
+
+    let difficulty = difficulty_or_default(&state, difficulty).await;
+    let words = playable(&state, &state.words.lock().await.clone(), WordLengths::default()).await?;
+    let mut game = GameState::new_daily(&words, difficulty, now);
+    game.alphabet = keys(&state, &words).await;
+    Ok(start_session(&Windows(&app), &*state, session_id, game).await)
+}
+
+#[tauri::command]
+fn list_categories(state: State<AppState>) -> Vec<String> {
+    state.categories.names()
+}
+
+#[allow(clippy::too_many_arguments)]
+#[tauri::command]
+async fn start_new_game_with_category(
+    session_id: Option<String>,
+    name: String,
+    difficulty: Option<schema::Difficulty>,
+    mode: Option<schema::GameMode>,
+    seed: Option<u64>,
+    practice: Option<bool>,
+    app: AppHandle,
+    state: State<'_, AppState>,
+) -> Result<SessionView, GameError> {
+    let words = state.categories.get(&name).ok_or_else(|| {
+        tracing::warn!(category = %name, "unknown category requested");
+        GameError::invalid_input(format!("Unknown category: {}", name))
+    })?;
+    let difficulty = difficulty_or_default(&state, difficulty).await;
+    let category = name.trim().to_lowercase();
+    let lengths = lengths_or_default(&state, None).await?;
+    let mut game = pick_game(&state, words, Some(category), difficulty, mode.unwrap_or_default().into(), seed, lengths).await?;
+    if practice.unwrap_or(false) {
+        game.start_practice();
+    }
+    Ok(start_session(&Windows(&app), &*state, session_id, game).await)
+}
+
+// Two-player mode: player one types the secret, player two guesses it
+// The word is masked in every response until the game is over
+#[tauri::command]
+async fn start_custom_game(
+    session_id: Option<String>,
+    word_or_phrase: String,
+    difficulty: Option<schema::Difficulty>,
+    app: AppHandle,
+    state: State<'_, AppState>,
+) -> Result<SessionView, GameError> {
+    let game = GameState::new_custom(&word_or_phrase, difficulty_or_default(&state, difficulty).await)?;
+    let game = with_main_keys(&state, game).await;
+    Ok(start_session(&Windows(&app), &*state, session_id, game).await)
+}
+
+// The rematch of the session's finished two-player game (see hangman-core's duel.rs):
+// its guesser types the next secret and player one guesses, the head-to-head score
+// carried over in the GameView's `duel`
+// Fails with game_not_over before the round is over, and invalid_input for a game
+// that isn't a two-player one or a secret that can't be played
+#[tauri::command]
+async fn rematch(
+    session_id: String,
+    word_or_phrase: String,
+    difficulty: Option<schema::Difficulty>,
+    app: AppHandle,
+    state: State<'_, AppState>,
+) -> Result<SessionView, GameError> {
+    let difficulty = difficulty_or_default(&state, difficulty).await;
+    let game = {
+        let mut game = game_of(&state.sessions, &session_id).await?;
+        check_timer(&Windows(&app), &*state, &session_id, &mut game).await;
+        game.rematch(&word_or_phrase, difficulty)?
+    };
+    let game = with_main_keys(&state, game).await;
+    Ok(start_session(&Windows(&app), &*state, Some(session_id), game).await)
+}
+
+/// A two-player game with the main list's keys, not the secret's letters (those would give it away)
+async fn with_main_keys(state: &AppState, mut game: GameState) -> GameState {
+    let words = state.words.lock().await.clone();
+    game.alphabet = keys(state, &words).await;
+    game
+}
+
+// A best-of-N match against the hangman (see hangman-core's matches.rs), `rounds`
+// must be odd (3, 5, ...). Each round's GameView has the match score in `match_score`
+// Rounds rotate through the difficulties from `difficulty`, or through the categories
+#[tauri::command]
+async fn start_match(
+    session_id: Option<String>,
+    rounds: u32,
+    rotation: Option<schema::MatchRotation>,
+    difficulty: Option<schema::Difficulty>,
+    mode: Option<schema::GameMode>,
+    app: AppHandle,
+    state: State<'_, AppState>,
+) -> Result<SessionView, GameError> {
+    let difficulty = difficulty_or_default(&state, difficulty).await;
+    let best_of = Match::new(rounds, rotation.unwrap_or_default().into(), difficulty, state.categories.names())?;
+    let game = round_game(&state, best_of, mode.unwrap_or_default().into()).await?;
+    Ok(start_session(&Windows(&app), &*state, session_id, game).await)
+}
+
+// The next round of the session's match, once the current one is over
+// Fails with game_not_over before that, and invalid_input without a match or once it's decided
+#[tauri::command]
+async fn next_round(session_id: String, app: AppHandle, state: State<'_, AppState>) -> Result<SessionView, GameError> {
+    let (best_of, mode) = {
+        let mut game = game_of(&state.sessions, &session_id).await?;
+        check_timer(&Windows(&app), &*state, &session_id, &mut game).await;
+        let mut best_of = game
+            .best_of
+            .clone()
+            .ok_or_else(|| GameError::invalid_input("This game isn't part of a match"))?;
+        if !game.game_over {
+            return Err(GameError::GameNotOver);
+        }
+        if !best_of.next_round() {
+            return Err(GameError::invalid_input("The match is over, start a new one"));
+        }
+        (best_of, game.mode())
+    };
+    let game = round_game(&state, best_of, mode).await?;
+    Ok(start_session(&Windows(&app), &*state, Some(session_id), game).await)
+}
+
+// An arcade run (see hangman-core's arcade.rs): games played on a pool of `lives` lives
+// (3 without), each lost game costs one and a few wins in a row win one back. Each game's
+// GameView has the lives left and the run's score in `run`
+#[tauri::command]
+async fn start_run(
+    session_id: Option<String>,
+    lives: Option<u32>,
+    difficulty: Option<schema::Difficulty>,
+    mode: Option<schema::GameMode>,
+    app: AppHandle,
+    state: State<'_, AppState>,
+) -> Result<SessionView, GameError> {
+    let difficulty = difficulty_or_default(&state, difficulty).await;
+    let run = Run::new(lives.unwrap_or(START_LIVES), difficulty)?;
+    let game = run_game(&state, run, mode.unwrap_or_default().into()).await?;
+    Ok(start_session(&Windows(&app), &*state, session_id, game).await)
+}
+
+// The next word of the session's arcade run, once the current game is over
+// Fails with game_not_over before that, and invalid_input without a run or once its lives are gone
+#[tauri::command]
+async fn next_word(session_id: String, app: AppHandle, state: State<'_, AppState>) -> Result<SessionView, GameError> {
+    let (run, mode) = {
+        let mut game = game_of(&state.sessions, &session_id).await?;
+        check_timer(&Windows(&app), &*state, &session_id, &mut game).await;
+        let mut run = game
+            .run
+            .clone()
+            .ok_or_else(|| GameError::invalid_input("This game isn't part of an arcade run"))?;
+        if !game.game_over {
+            return Err(GameError::GameNotOver);
+        }
+        if !run.next_word() {
+            return Err(GameError::invalid_input("The run is over, start a new one"));
+        }
+        (run, game.mode())
+    };
+    let game = run_game(&state, run, mode).await?;
+    Ok(start_session(&Windows(&app), &*state, Some(session_id), game).await)
+}
+
+// A level of the campaign (see hangman-core's campaign.rs): the selected profile's next level
+// without `level`, a word picked with the level's difficulty and word lengths. Levels can be
+// played again, won or lost (start it with the GameView's `campaign_level` to retry)
+// Fails with level_locked for a level the profile hasn't unlocked yet
+#[tauri::command]
+async fn start_campaign_level(
+    session_id: Option<String>,
+    level: Option<u32>,
+    mode: Option<schema::GameMode>,
+    app: AppHandle,
+    state: State<'_, AppState>,
+) -> Result<SessionView, GameError> {
+    let player = state.profiles.lock().await.current.clone();
+    let level = {
+        let mut campaign = state.campaign.lock().await;
+        let level = level.unwrap_or_else(|| campaign.progress(player.as_deref()).next_level());
+        if campaign.start(player.as_deref(), level, now_ms())? {
+            state.saver.save(Save::Campaign(campaign.clone()));
+        }
+        level
+    };
+    let found = campaign::get(level).ok_or_else(|| GameError::invalid_input("Unknown campaign level"))?;
+    let mut game = pool_game(&state, found.difficulty, mode.unwrap_or_default().into(), None, found.lengths).await?;
+    game.campaign_level = Some(level);
+    Ok(start_session(&Windows(&app), &*state, session_id, game).await)
+}
+
+// Every level of the campaign and how the selected profile did on it: which are unlocked,
+// the best scores, and the completion time once the last level is won
+#[tauri::command]
+async fn get_campaign_progress(state: State<'_, AppState>) -> Result<CampaignView, String> {
+    let player = state.profiles.lock().await.current.clone();
+    Ok(state.campaign.lock().await.progress(player.as_deref()).view())
+}
+
+// A race against the AI (see hangman-core's race.rs): the AI gets a board of its own with
+// the same word, and moves on it after each of the player's moves (ai_take_turn), whoever
+// completes the word first wins. The GameView has the AI's progress and the winner in `race`
+// Races are played in the classic mode
+#[tauri::command]
+async fn start_race(
+    session_id: Option<String>,
+    difficulty: Option<schema::Difficulty>,
+    app: AppHandle,
+    state: State<'_, AppState>,
+) -> Result<SessionView, GameError> {
+    let difficulty = difficulty_or_default(&state, difficulty).await;
+    let lengths = lengths_or_default(&state, None).await?;
+    let mut game = pool_game(&state, difficulty, GameMode::Classic, None, lengths).await?;
+    game.race = Some(Race::new(&game)?);
+    Ok(start_session(&Windows(&app), &*state, session_id, game).await)
+}
+
+// Anything but a single new letter is refused, with an error saying what was wrong
+// (empty_guess, multiple_characters, digit, not_a_letter, or already_guessed)
+#[tauri::command]
+async fn guess_letter(session_id: String, letter: String, app: AppHandle, state: State<'_, AppState>) -> Result<GameView, GameError> {
+    service::guess_letter(&Windows(&app), &*state, &session_id, &letter).await
+}
+
+// Several letter guesses in one go (e.g. the letters of a pasted word, or the moves made
+// while reconnecting), made in order while holding the game, so nothing comes in between
+// Each letter gets its outcome: a hit, a miss, rejected (with the error guess_letter would
+// have returned), or skipped once the game is over. Fails only like guess_letter does on
+// a finished game
+#[tauri::command]
+async fn guess_letters(session_id: String, letters: Vec<String>, app: AppHandle, state: State<'_, AppState>) -> Result<BatchGuessView, GameError> {
+    service::guess_letters(&Windows(&app), &*state, &session_id, &letters).await
+}
+
+// Risk a guess at the whole word: wins on a match, costs two wrong guesses on a miss
+#[tauri::command]
+async fn guess_word(session_id: String, word: String, app: AppHandle, state: State<'_, AppState>) -> Result<GameView, GameError> {
+    service::guess_word(&Windows(&app), &*state, &session_id, &word).await
+}
+
+// Reveals a random unguessed letter, costs one wrong guess (limited per game)
+#[tauri::command]
+async fn use_hint(session_id: String, app: AppHandle, state: State<'_, AppState>) -> Result<GameView, GameError> {
+    service::use_hint(&Windows(&app), &*state, &session_id).await
+}
+
+// End the session's game as a loss, the response has the word
+// It's recorded in the statistics and game history like any other loss, and can't be undone
+// Fails with game_already_over once the game is over
+#[tauri::command]
+async fn give_up(session_id: String, app: AppHandle, state: State<'_, AppState>) -> Result<GameView, GameError> {
+    service::give_up(&Windows(&app), &*state, &session_id).await
+}
+
+// Stop the clock of the session's timed game, guesses, hints, and helps fail with game_paused
+// until unpause_game (the time spent paused doesn't count against the player)
+// invalid_input for an untimed or already paused game, game_already_over once it's over
+#[tauri::command]
+async fn pause_game(session_id: String, app: AppHandle, state: State<'_, AppState>) -> Result<GameView, GameError> {
+    service::pause(&Windows(&app), &*state, &session_id).await
+}
+
+// Start the paused game's clock again with the time it had left
+// (named so it isn't confused with resume_game, which restores a saved game)
+#[tauri::command]
+async fn unpause_game(session_id: String, app: AppHandle, state: State<'_, AppState>) -> Result<GameView, GameError> {
+    service::unpause(&Windows(&app), &*state, &session_id).await
+}
+
+// Take back the session's last move (a letter, a word guess, or a hint), reopening
+// the game if that move ended it
+// Fails with nothing_to_undo before the first move (or the last help bought), and game_already_over
+// once time ran out
+#[tauri::command]
+async fn undo_guess(session_id: String, app: AppHandle, state: State<'_, AppState>) -> Result<GameView, GameError> {
+    service::undo(&Windows(&app), &*state, &session_id).await
+}
+
+// The selected profile's points and the prices of the helps they buy
+#[tauri::command]
+async fn get_wallet(state: State<'_, AppState>) -> Result<WalletView, String> {
+    let profiles = state.profiles.lock().await;
+    Ok(match profiles.current() {
+        Some(profile) => profile.wallet.view(Some(profile.name.clone())),
+        None => Wallet::default().view(None),
+    })
+}
+
+// Spend the points of the game's player on a help (see hangman-core's shop.rs)
+// The price and the player's points are checked here, and the points are only taken
+// if the help could be given
+#[tauri::command]
+async fn buy_help(session_id: String, help: schema::Help, app: AppHandle, state: State<'_, AppState>) -> Result<PurchaseView, GameError> {
+    let help = Help::from(help);
+    let mut game = game_of(&state.sessions, &session_id).await?;
+    if check_playable(&Windows(&app), &*state, &session_id, &mut game).await? {
+        return Err(GameError::GameAlreadyOver);
+    }
+    let was_over = game.game_over;
+    let (letter, points) = {
+        let mut profiles = state.profiles.lock().await;
+        let wallet = match game.player.as_deref().and_then(|name| profiles.get_mut(name)) {
+            Some(profile) => &mut profile.wallet,
+            None => return Err(GameError::invalid_input("Select a player to spend points")),
+        };
+        wallet.check(help)?;
+        let letter = game.apply_help(help)?;
+        wallet.spend(help)?;
+        let points = wallet.points;
+        state.saver.save(Save::Profiles(profiles.clone()));
+        (letter, points)
+    };
+    tracing::debug!(?help, points, "help bought");
+    // A revealed vowel can be the last hidden letter
+    record_if_finished(&Windows(&app), &*state, &session_id, was_over, &game).await;
+    game_changed(&Windows(&app), &*state, &session_id, &game).await;
+    Ok(game.purchase_view(help, letter, points))
+}
+
+// The selected profile's power-ups, every kind with what it does and how it's earned
+#[tauri::command]
+async fn get_power_ups(state: State<'_, AppState>) -> Result<InventoryView, String> {
+    let profiles = state.profiles.lock().await;
+    Ok(match profiles.current() {
+        Some(profile) => profile.power_ups.view(Some(profile.name.clone())),
+        None => Inventory::default().view(None),
+    })
 }
 
+// Use one of the game's player's power-ups (see hangman-core's powerups.rs)
+// The inventory and the game are checked here, and the power-up is only taken
+// if it could be used
 #[tauri::command]
-fn start_new_game(state: State<AppState>) -> GameState {
-    let words = vec![
-        "RUST",
-        "TAURI",
-        "PROGRAMMING",
-        "COMPUTER",
-        "KEYBOARD",
-        "DEVELOPER",
-        "SOFTWARE",
-        "ALGORITHM",
-        "FUNCTION",
-        "VARIABLE",
-        "HANGMAN",
-        "CHALLENGE",
-        "VICTORY",
-        "PUZZLE",
-        "MYSTERY",
-    ];
+async fn activate_powerup(
+    session_id: String,
+    power_up: schema::PowerUp,
+    app: AppHandle,
+    state: State<'_, AppState>,
+) -> Result<ActivationView, GameError> {
+    let power_up = PowerUp::from(power_up);
+    let mut game = game_of(&state.sessions, &session_id).await?;
+    if check_playable(&Windows(&app), &*state, &session_id, &mut game).await? {
+        return Err(GameError::GameAlreadyOver);
+    }
+    let was_over = game.game_over;
+    let (letter, held) = {
+        let mut profiles = state.profiles.lock().await;
+        let inventory = match game.player.as_deref().and_then(|name| profiles.get_mut(name)) {
+            Some(profile) => &mut profile.power_ups,
+            None => return Err(GameError::invalid_input("Select a player to use power-ups")),
+        };
+        inventory.check(power_up)?;
+        let letter = game.activate_powerup(power_up)?;
+        inventory.take(power_up)?;
+        let held = inventory.held(power_up);
+        state.saver.save(Save::Profiles(profiles.clone()));
+        (letter, held)
+    };
+    tracing::debug!(?power_up, held, "power-up used");
+    // A revealed consonant can be the last hidden letter
+    record_if_finished(&Windows(&app), &*state, &session_id, was_over, &game).await;
+    game_changed(&Windows(&app), &*state, &session_id, &game).await;
+    Ok(game.activation_view(power_up, letter, held))
+}
 
-    let mut rng = rand::thread_rng();
-    let word = words[rng.gen_range(0..words.len())].to_string();
+// The AI player makes the next move in the session's game (a letter, or the word
+// once it's sure), for human-vs-AI games taking turns on the same board
+// In a race it moves on its own board instead, on its turn (invalid_input on the player's)
+// It only sees what the player sees, and uses the game's category as its dictionary
+#[tauri::command]
+async fn ai_take_turn(session_id: String, app: AppHandle, state: State<'_, AppState>) -> Result<AiTurnView, GameError> {
+    let mut game = game_of(&state.sessions, &session_id).await?;
+    let mut ai_move = None;
+    if game.race.is_some() {
+        check_timer(&Windows(&app), &*state, &session_id, &mut game).await;
+        let was_over = game.game_over;
+        let words = ai_dictionary(&state, &game).await;
+        ai_move = game.race_turn(&words)?;
+        tracing::debug!(ai_move = ?ai_move, "AI moved in a race");
+        record_if_finished(&Windows(&app), &*state, &session_id, was_over, &game).await;
+        game_changed(&Windows(&app), &*state, &session_id, &game).await;
+    } else if !check_playable(&Windows(&app), &*state, &session_id, &mut game).await? {
+        let words = ai_dictionary(&state, &game).await;
+        let was_over = game.game_over;
+        ai_move = ai::take_turn(&mut game, &words);
+        // (its move is the last one, a miss a shield took included)
+        let hit = game.history.last().is_some_and(|last| last.hit);
+        match &ai_move {
+            Some(AiMove::Letter(letter)) => {
+                emit(&state, GameEvent::LetterGuessed { session_id: session_id.clone(), letter: *letter, hit }).await;
+            }
+            Some(AiMove::Word(guess)) => {
+                emit(&state, GameEvent::WordGuessed { session_id: session_id.clone(), guess: guess.clone(), hit }).await;
+            }
+            None => {}
+        }
+        record_if_finished(&Windows(&app), &*state, &session_id, was_over, &game).await;
+        game_changed(&Windows(&app), &*state, &session_id, &game).await;
+    }
+    Ok(game.ai_turn_view(ai_move.as_ref()))
+}
+
+// The smart hint: for each unguessed letter, in how many of the words that still fit
+// the board it is, with the AI's dictionary (see hangman-core's ai.rs)
+// It suggests good guesses without giving the answer away, so it's free and
+// isn't counted as a hint; a finished game has no letters left to suggest
+#[tauri::command]
+async fn get_letter_frequencies(session_id: String, app: AppHandle, state: State<'_, AppState>) -> Result<LetterFrequenciesView, GameError> {
+    let mut game = game_of(&state.sessions, &session_id).await?;
+    check_timer(&Windows(&app), &*state, &session_id, &mut game).await;
+    let words = ai_dictionary(&state, &game).await;
+    Ok(game.letter_frequencies_view(&words))
+}
+
+// The best three letters to guess next, by how much each is expected to tell about the answer
+// over the AI's dictionary's words that still fit the board (see hangman-core's suggest.rs)
+// Free like the smart hint, the AI plays the best of them; a finished game has none
+#[tauri::command]
+async fn suggest_letters(session_id: String, app: AppHandle, state: State<'_, AppState>) -> Result<SuggestionsView, GameError> {
+    let mut game = game_of(&state.sessions, &session_id).await?;
+    check_timer(&Windows(&app), &*state, &session_id, &mut game).await;
+    let words = ai_dictionary(&state, &game).await;
+    Ok(game.suggestions_view(&words))
+}
+
+// The solver: the words that fit a mask like "_ A N G M A N" without the `excluded`
+// letters (see hangman-core's pattern.rs), from a category or the main word list with
+// the selected profile's words, without the blocked ones, with the settings' accents and digraphs
+// Fails with invalid_input for an empty mask, an excluded character that isn't a
+// letter, or an unknown category
+#[tauri::command]
+async fn search_words(
+    mask: String,
+    excluded: Option<String>,
+    category: Option<String>,
+    state: State<'_, AppState>,
+) -> Result<WordSearchView, GameError> {
+    let words = match category {
+        Some(name) => match state.categories.get(&name) {
+            Some(words) => words.clone(),
+            None => return Err(GameError::invalid_input(format!("Unknown category: {}", name))),
+        },
+        None => word_pool(&state).await.0,
+    };
+    let words = playable(&state, &words, WordLengths::default()).await?;
+    let pattern = {
+        let settings = state.settings.lock().await;
+        Pattern::parse(&mask, excluded.as_deref().unwrap_or(""), settings.ignore_accents, &settings.digraph_units())?
+    };
+    Ok(pattern.with_charset(words.charset().clone()).search_view(&words))
+}
+
+// The games left unfinished when the app last closed or crashed, to offer restoring them
+// Windows ask before starting their first game, the games stay offered until restored or discarded
+#[tauri::command]
+async fn get_saved_games(state: State<'_, AppState>) -> Result<SavedGamesView, String> {
+    Ok(SavedGamesView {
+        schema_version: SCHEMA_VERSION,
+        game: state.autosave.interrupted().await.map(|game| game.public_view()),
+        wordle: state.wordle_autosave.interrupted().await.map(|game| game.view()),
+    })
+}
+
+// Don't restore a mode's interrupted game, its save is cleared
+#[tauri::command]
+async fn discard_saved_game(kind: SavedGameKind, state: State<'_, AppState>) -> Result<(), String> {
+    match kind {
+        SavedGameKind::Hangman => state.autosave.discard(&state.saver).await,
+        SavedGameKind::Wordle => state.wordle_autosave.discard(&state.saver).await,
+    }
+    Ok(())
+}
+
+// The game left unfinished when the app last closed, in a new session for the window
+// (null if there's none, or another window already resumed or discarded it)
+#[tauri::command]
+async fn resume_game(app: AppHandle, state: State<'_, AppState>) -> Result<Option<SessionView>, GameError> {
+    let Some(mut game) = state.autosave.restore().await else {
+        return Ok(None);
+    };
+    // The rng isn't saved, hints come from the app's from here on
+    game.rng = state.rng.clone();
+    let (id, mut game) = create_session(&state.sessions, game).await;
+    tracing::debug!(session = %id, "game resumed");
+    emit(&state, GameEvent::started(&id, &game)).await;
+    // A timed game that ran out while the app was closed is lost, and comes back as lost
+    check_timer(&Windows(&app), &*state, &id, &mut game).await;
+    if !game.game_over {
+        // (check_timer already told the windows about a game that ran out)
+        game_changed(&Windows(&app), &*state, &id, &game).await;
+    }
+    Ok(Some(game.session_view(&id)))
+}
 
-    let new_game = GameState::new(word);
-    let mut game = state.game.lock().unwrap();
-    *game = new_game.clone();
+#[tauri::command]
+async fn get_statistics(state: State<'_, AppState>) -> Result<StatisticsView, String> {
+    Ok(state.stats.lock().await.summary())
+}
 
-    new_game
+#[tauri::command]
+async fn get_settings(state: State<'_, AppState>) -> Result<SettingsView, String> {
+    Ok(SettingsView::from(&*state.settings.lock().await))
 }
 
+// New settings apply from the next game on, the current game keeps its rules
+// They're saved to hangman.toml in the app config dir first, nothing changes if that fails
+// Switching to the online word source fetches words right away, and a new language loads its word list
 #[tauri::command]
-fn guess_letter(letter: String, state: State<AppState>) -> GameState {
-    let mut game = state.game.lock().unwrap();
+async fn set_settings(settings: SettingsView, app: AppHandle, state: State<'_, AppState>) -> Result<SettingsView, String> {
+    let settings = Settings::from(settings);
+    let view = SettingsView::from(&settings);
+    replace_settings(&app, &state, |current| *current = settings).await?;
+    Ok(view)
+}
 
-    if game.game_over {
-        return game.clone();
+/// Save the settings `change` makes to hangman.toml, then use them (see set_settings)
+async fn replace_settings(app: &AppHandle, state: &AppState, change: impl FnOnce(&mut Settings)) -> Result<(), String> {
+    // Held while saving, so the file always has the last settings set
+    let mut current = state.settings.lock().await;
+    let mut settings = current.clone();
+    change(&mut settings);
+    let file = state.settings_file.clone();
+    let saved = settings.clone();
+    tauri::async_runtime::spawn_blocking(move || save_file(&file, &saved))
+        .await
+        .map_err(|err| err.to_string())?
+        .map_err(|err| err.to_string())?;
+    if settings.language != current.language {
+        *state.words.lock().await = load_words(state, settings.language.clone()).await?;
+    }
+    if settings.language != current.language || settings.word_file != current.word_file {
+        let words = state.words.lock().await.clone();
+        register_providers(&mut *state.providers.lock().await, &words, &settings, state.word_dir.as_deref());
     }
+    let fetch = settings.word_source == WordSource::Online && current.word_source != WordSource::Online;
+    state.telemetry.lock().await.configure(&settings);
+    audit::lock(&state.audit).configure(&settings);
+    audit::lock(&state.metrics).configure(&settings);
+    *current = settings;
+    if fetch {
+        fetch_online_words(app);
+    }
+    Ok(())
+}
 
-    let letter_upper = letter.to_uppercase().chars().next().unwrap_or(' ');
+// The languages with a bundled word list (see hangman-core's languages.rs), for the language picker
+#[tauri::command]
+fn list_languages() -> Vec<LanguageView> {
+    LANGUAGES
+        .iter()
+        .map(|language| language.builtin_words().language_view(Some(language.code)))
+        .collect()
+}
 
-    if !game.guessed_letters.contains(&letter_upper) {
-        game.guessed_letters.push(letter_upper);
+// Switch the main word list to `language` ("es", "fr", ..., None for the main list) right away,
+// games in progress keep their words and keys. Saved in the settings like set_settings
+// Any code set_settings takes works, one without a bundled list needs the player's own
+// words.<language>.json / .txt in the app data dir (or plays the main list)
+#[tauri::command]
+async fn set_language(language: Option<String>, app: AppHandle, state: State<'_, AppState>) -> Result<LanguageView, String> {
+    let language = language.map(|code| code.trim().to_lowercase()).filter(|code| !code.is_empty());
+    replace_settings(&app, &state, |settings| settings.language = language.clone()).await?;
+    Ok(state.words.lock().await.language_view(language.as_deref()))
+}
+
+// Where new games get their words from: every word provider's health check, and the one
+// serving the settings' word_source (the bundled one, with why, when it can't)
+#[tauri::command]
+async fn get_word_providers(state: State<'_, AppState>) -> Result<WordProvidersView, String> {
+    let source = state.settings.lock().await.word_source;
+    Ok(state.providers.lock().await.view(source))
+}
+
+#[tauri::command]
+async fn list_profiles(state: State<'_, AppState>) -> Result<Vec<ProfileView>, String> {
+    Ok(state.profiles.lock().await.views())
+}
+
+// The first profile created becomes the current one (and takes over the window's game)
+#[tauri::command]
+async fn create_profile(
+    name: String,
+    session_id: Option<String>,
+    app: AppHandle,
+    state: State<'_, AppState>,
+) -> Result<ProfileView, String> {
+    let (created, current) = {
+        let mut profiles = state.profiles.lock().await;
+        let created = profiles.create(&name).map_err(|err| err.to_string())?.clone();
+        state.saver.save(Save::Profiles(profiles.clone()));
+        (created, profiles.current.clone())
+    };
+    let is_current = current.as_deref() == Some(created.name.as_str());
+    switch_player(&Windows(&app), &*state, session_id.as_deref(), current).await;
+    Ok(created.view(is_current))
+}
+
+// The window's game, if it's still running, counts for the selected profile
+#[tauri::command]
+async fn select_profile(
+    name: String,
+    session_id: Option<String>,
+    app: AppHandle,
+    state: State<'_, AppState>,
+) -> Result<ProfileView, String> {
+    let selected = {
+        let mut profiles = state.profiles.lock().await;
+        let selected = profiles.select(&name).map_err(|err| err.to_string())?.view(true);
+        state.saver.save(Save::Profiles(profiles.clone()));
+        selected
+    };
+    switch_player(&Windows(&app), &*state, session_id.as_deref(), Some(selected.name.clone())).await;
+    Ok(selected)
+}
 
-        if !game.word.contains(letter_upper) {
-            game.wrong_guesses += 1;
+// Every achievement, locked or not, for the selected profile
+#[tauri::command]
+async fn get_achievements(state: State<'_, AppState>) -> Result<Vec<AchievementView>, String> {
+    let profiles = state.profiles.lock().await;
+    Ok(state.achievements.lock().await.views(profiles.current.as_deref()))
+}
+
+// Add the words of a .json, .csv, or .txt file to the selected profile's words
+// (see hangman-core's custom_words.rs), entries that aren't playable or already are
+// are left out and listed in the summary
+// The file is read on a blocking thread, it could be large
+#[tauri::command]
+async fn import_word_list(path: String, state: State<'_, AppState>) -> Result<ImportSummary, String> {
+    let file = PathBuf::from(&path);
+    let entries = tauri::async_runtime::spawn_blocking(move || custom_words::read_entries(&file))
+        .await
+        .map_err(|err| err.to_string())?
+        .map_err(|err| err.to_string())?;
+    let words = state.words.lock().await.clone();
+    let player = state.profiles.lock().await.current.clone();
+    let mut custom_words = state.custom_words.lock().await;
+    let report = custom_words.import(player.as_deref(), entries, &words);
+    if !report.accepted.is_empty() {
+        state.saver.save(Save::CustomWords(custom_words.clone()));
+    }
+    tracing::info!(
+        path = %path,
+        accepted = report.accepted.len(),
+        duplicates = report.duplicates.len(),
+        rejected = report.rejected.len(),
+        "word list imported"
+    );
+    let pool_size = words.words().len() + custom_words.words(player.as_deref()).len();
+    Ok(report.summary(pool_size))
+}
+
+// Write every finished game with its moves, and the statistics, to a .csv or .json file
+// (see hangman-core's export.rs), after the saves still queued so the last game is in it
+#[tauri::command]
+async fn export_history(format: ExportFormat, path: String, state: State<'_, AppState>) -> Result<ExportSummary, String> {
+    let Some(storage) = state.storage.clone() else {
+        return Err("there's no game history to export: the app has nowhere to keep it".to_string());
+    };
+    state.saver.flush().await;
+    let file = PathBuf::from(&path);
+    let summary = tauri::async_runtime::spawn_blocking(move || {
+        HistoryExport::load(&storage)
+            .map_err(Into::into)
+            .and_then(|history| history.write(&file, format))
+    })
+    .await
+    .map_err(|err| err.to_string())?
+    .map_err(|err| err.to_string())?;
+    tracing::info!(path = %path, games = summary.games, "game history exported");
+    Ok(summary)
+}
+
+/// Read the stored games of the selected profile (None for every player's without one)
+/// on a blocking thread, after the saves still queued so the last game is in them
+async fn read_games<T: Send + 'static>(
+    state: &AppState,
+    read: impl FnOnce(&Storage, Option<&str>) -> Result<T, StorageError> + Send + 'static,
+) -> Result<T, String> {
+    let Some(storage) = state.storage.clone() else {
+        return Err("there are no stored games: the app has nowhere to keep them".to_string());
+    };
+    let player = state.profiles.lock().await.current.clone();
+    state.saver.flush().await;
+    tauri::async_runtime::spawn_blocking(move || read(&storage, player.as_deref()))
+        .await
+        .map_err(|err| err.to_string())?
+        .map_err(|err| err.to_string())
+}
+
+// How each word the selected profile played went (games played, wins, average wrong
+// guesses), most played first, from the stored games (see hangman-core's storage.rs)
+#[tauri::command]
+async fn get_word_stats(state: State<'_, AppState>) -> Result<Vec<WordStatsView>, String> {
+    let stats = read_games(&state, |storage, player| storage.word_stats(player)).await?;
+    Ok(stats.iter().map(|word| word.view()).collect())
+}
+
+// The words the selected profile lost most often (`limit`, 10 without), to practice them
+#[tauri::command]
+async fn get_most_missed_words(limit: Option<u32>, state: State<'_, AppState>) -> Result<Vec<WordStatsView>, String> {
+    let limit = limit.unwrap_or(MOST_MISSED_WORDS) as usize;
+    let missed = read_games(&state, move |storage, player| storage.most_missed_words(player, limit)).await?;
+    Ok(missed.iter().map(|word| word.view()).collect())
+}
+
+#[tauri::command]
+async fn get_leaderboard(state: State<'_, AppState>) -> Result<Leaderboard, String> {
+    Ok(state.profiles.lock().await.leaderboard_view())
+}
+
+// The selected profile's rating against the AI on a difficulty (the settings' by default),
+// and every update races made to it, oldest first (see hangman-core's elo.rs)
+#[tauri::command]
+async fn get_rating_history(difficulty: Option<schema::Difficulty>, state: State<'_, AppState>) -> Result<RatingHistoryView, String> {
+    let difficulty = difficulty_or_default(&state, difficulty).await;
+    let profiles = state.profiles.lock().await;
+    Ok(match profiles.current() {
+        Some(profile) => profile.ratings.history_view(Some(profile.name.clone()), difficulty),
+        None => Ratings::default().history_view(None, difficulty),
+    })
+}
+
+// Changes are pushed with the game_updated event, this is for catching up on a game
+#[tauri::command]
+async fn get_game_state(session_id: String, app: AppHandle, state: State<'_, AppState>) -> Result<GameView, GameError> {
+    service::game_view(&Windows(&app), &*state, &session_id).await
+}
+
+// The game as a spectator or remote player may see it: the mask, the guesses, and the
+// counters, with the word only once the game is over
+#[tauri::command]
+async fn get_public_state(session_id: String, app: AppHandle, state: State<'_, AppState>) -> Result<PublicGameView, GameError> {
+    let mut game = game_of(&state.sessions, &session_id).await?;
+    check_timer(&Windows(&app), &*state, &session_id, &mut game).await;
+    Ok(game.public_view())
+}
+
+// The finished game's result to paste anywhere, without the word: the day (or daily number),
+// the result, and a square per move (see hangman-core's share.rs)
+// Fails with game_not_over while the game is running
+#[tauri::command]
+async fn get_share_text(session_id: String, app: AppHandle, state: State<'_, AppState>) -> Result<String, GameError> {
+    let mut game = game_of(&state.sessions, &session_id).await?;
+    check_timer(&Windows(&app), &*state, &session_id, &mut game).await;
+    game.share_text().ok_or(GameError::GameNotOver)
+}
+
+// The finished game as a replay (its word, rules, and every move with its time,
+// see hangman-core's replay.rs), to save or send and open with import_replay
+// Fails with game_not_over while the game is running
+#[tauri::command]
+async fn export_replay(session_id: String, app: AppHandle, state: State<'_, AppState>) -> Result<String, GameError> {
+    let mut game = game_of(&state.sessions, &session_id).await?;
+    check_timer(&Windows(&app), &*state, &session_id, &mut game).await;
+    Ok(Replay::from_game(&game)?.to_text())
+}
+
+// A replay played again, the game before the first move and after each, for
+// the frontend to animate; it doesn't start a session or count as a game
+#[tauri::command]
+fn import_replay(replay: String) -> Result<ReplayView, GameError> {
+    Ok(Replay::parse(&replay)?.view()?)
+}
+
+// How hard any word or phrase is to guess (see hangman-core's rating.rs),
+// finished games carry their word's rating in `rating`
+#[tauri::command]
+fn rate_word(word: String) -> Result<WordRatingView, GameError> {
+    if !word.chars().any(char::is_alphabetic) {
+        return Err(GameError::invalid_input("Enter a word with letters to rate"));
+    }
+    Ok(rating::rate_word(&word).into())
+}
+
+// What the word of a finished game means, from the cache or the dictionary API
+// (null if the dictionary doesn't know it or can't be reached)
+// The request runs on a blocking thread, without holding any lock
+#[tauri::command]
+async fn get_word_definition(session_id: String, app: AppHandle, state: State<'_, AppState>) -> Result<Option<DefinitionView>, GameError> {
+    // The word is only looked up once the game has revealed it
+    let word = {
+        let mut game = game_of(&state.sessions, &session_id).await?;
+        check_timer(&Windows(&app), &*state, &session_id, &mut game).await;
+        if !game.game_over {
+            return Err(GameError::GameNotOver);
         }
+        game.word.clone()
+    };
+    if let Some(definition) = state.definitions.lock().await.get(&word) {
+        return Ok(Some(definition.clone().into()));
+    }
 
-        if game.wrong_guesses >= game.max_wrong_guesses {
-            game.game_over = true;
-            game.won = false;
-        } else if game.check_win() {
-            game.game_over = true;
-            game.won = true;
+    let dictionary = state.dictionary.clone();
+    let lookup = word.clone();
+    let fetched = match tauri::async_runtime::spawn_blocking(move || dictionary.lookup(&lookup)).await {
+        Ok(fetched) => fetched,
+        Err(err) => {
+            tracing::warn!("definition lookup stopped: {}", err);
+            return Ok(None);
+        }
+    };
+    match fetched {
+        Ok(Some(definition)) => {
+            let mut definitions = state.definitions.lock().await;
+            definitions.insert(definition.clone());
+            state.saver.save(Save::Definitions(definitions.clone()));
+            Ok(Some(definition.into()))
+        }
+        Ok(None) => Ok(None),
+        Err(err) => {
+            tracing::warn!(word = %word, "no definition: {}", err);
+            Ok(None)
         }
     }
+}
 
-    game.clone()
+// What cleaning up the word list did when it was loaded (stripped characters,
+// duplicates, and rejected entries), so a list maintainer can fix the file
+#[tauri::command]
+async fn get_word_list_report(state: State<'_, AppState>) -> Result<WordListReport, String> {
+    Ok(state.words.lock().await.report().view())
 }
 
+// The gameplay telemetry collected: the events waiting for a full batch, and the ones
+// already in the local file (see the `telemetry` setting)
 #[tauri::command]
-fn get_game_state(state: State<AppState>) -> GameState {
-    let game = state.game.lock().unwrap();
-    game.clone()
+async fn get_telemetry(state: State<'_, AppState>) -> Result<TelemetryView, String> {
+    let telemetry = state.telemetry.lock().await;
+    let saved = telemetry.saved().map_err(|err| err.to_string())?;
+    Ok(telemetry.view(&saved))
 }
 
-fn main() {
-    let words = vec!["RUST"];
-    let initial_word = words[0].to_string();
-    let initial_game = GameState::new(initial_word);
+// Forget the telemetry waiting to be sent and remove the local file (posted events are the endpoint's)
+#[tauri::command]
+async fn clear_telemetry(state: State<'_, AppState>) -> Result<TelemetryView, String> {
+    let mut telemetry = state.telemetry.lock().await;
+    telemetry.clear().map_err(|err| err.to_string())?;
+    tracing::info!("telemetry cleared");
+    Ok(telemetry.view(&[]))
+}
+
+// The last commands in the audit log, newest first (see the `audit_log` setting)
+#[tauri::command]
+async fn get_audit_log(limit: Option<u32>, state: State<'_, AppState>) -> Result<AuditLogView, String> {
+    let audit = audit::lock(&state.audit);
+    let entries = audit.recent(limit.unwrap_or(AUDIT_ENTRIES) as usize).map_err(|err| err.to_string())?;
+    Ok(audit.view(&entries))
+}
+
+// Remove the audit log's files
+#[tauri::command]
+async fn clear_audit_log(state: State<'_, AppState>) -> Result<AuditLogView, String> {
+    let mut audit = audit::lock(&state.audit);
+    audit.clear().map_err(|err| err.to_string())?;
+    tracing::info!("audit log cleared");
+    Ok(audit.view(&[]))
+}
+
+// How often each command ran and how long it took since the app started, the most time spent in first
+#[tauri::command]
+fn get_metrics(state: State<AppState>) -> MetricsView {
+    audit::lock(&state.metrics).view()
+}
+
+// JSON Schemas of the files the game reads and writes (stats, profiles, settings,
+// word lists), by name, e.g. to check a word list before copying it into the data dir
+#[tauri::command]
+fn get_schemas() -> BTreeMap<&'static str, Value> {
+    schemas()
+        .into_iter()
+        .map(|(name, schema)| (name, schema.to_value()))
+        .collect()
+}
+
+// Start a word game (guess a five-letter word in six tries), or a new one in a window's
+// word game session
+// The word is one of the word game's words the settings don't block
+#[tauri::command]
+async fn start_wordle(session_id: Option<String>, state: State<'_, AppState>) -> Result<WordleSessionView, GameError> {
+    let words = playable(&state, &state.wordle_words, WordLengths::default()).await?;
+    let game = WordleGame::pick(&words, &mut state.rng.clone())
+        .ok_or_else(|| GameError::invalid_input("Every five-letter word is blocked, see the blocked words in the options"))?;
+    if let Some(id) = session_id {
+        if let Some(mut existing) = find_game(&state.wordles, &id).await {
+            *existing = game;
+            state.wordle_autosave.changed(&state.saver, &id, &existing).await;
+            return Ok(existing.session_view(&id));
+        }
+    }
+    let (id, game) = create_session(&state.wordles, game).await;
+    state.wordle_autosave.changed(&state.saver, &id, &game).await;
+    Ok(game.session_view(&id))
+}
+
+// Guess a word in a word game, it has to be in the word game's words or the hangman words
+#[tauri::command]
+async fn guess_wordle(session_id: String, word: String, state: State<'_, AppState>) -> Result<WordleView, GameError> {
+    let mut game = game_of(&state.wordles, &session_id).await?;
+    game.guess(&word, &[&state.wordle_words, &*state.words.lock().await])?;
+    state.wordle_autosave.changed(&state.saver, &session_id, &game).await;
+    Ok(game.view())
+}
+
+// Carry on with the word game left unfinished when the app last closed, in a new session
+#[tauri::command]
+async fn resume_wordle(state: State<'_, AppState>) -> Result<Option<WordleSessionView>, GameError> {
+    let Some(game) = state.wordle_autosave.restore().await else {
+        return Ok(None);
+    };
+    let (id, game) = create_session(&state.wordles, game).await;
+    state.wordle_autosave.changed(&state.saver, &id, &game).await;
+    Ok(Some(game.session_view(&id)))
+}
+
+#[tauri::command]
+async fn get_wordle_state(session_id: String, state: State<'_, AppState>) -> Result<WordleView, GameError> {
+    Ok(game_of(&state.wordles, &session_id).await?.view())
+}
+
+// Start a positional game (guess a letter for a place of the word), or a new one in a
+// window's positional game session
+// The word is picked from the same words as a classic game's, accents compared like the settings say
+#[tauri::command]
+async fn start_positional_game(
+    session_id: Option<String>,
+    difficulty: Option<schema::Difficulty>,
+    state: State<'_, AppState>,
+) -> Result<PositionalSessionView, GameError> {
+    let difficulty = difficulty_or_default(&state, difficulty).await;
+    let lengths = lengths_or_default(&state, None).await?;
+    let words = playable(&state, &word_pool(&state).await.0, lengths).await?;
+    let mut game = PositionalGame::pick(&words, difficulty, &mut state.rng.clone());
+    game.ignore_accents = state.settings.lock().await.ignore_accents;
+    if let Some(id) = session_id {
+        if let Some(mut existing) = find_game(&state.positionals, &id).await {
+            *existing = game;
+            return Ok(existing.session_view(&id));
+        }
+    }
+    let (id, game) = create_session(&state.positionals, game).await;
+    Ok(game.session_view(&id))
+}
+
+// Guess that `letter` is in place `index` (counting from 0) of a positional game
+// Fails with invalid_input for a place that isn't hidden or a guess made before, like
+// guess_letter for anything but one letter
+#[tauri::command]
+async fn guess_at(session_id: String, letter: String, index: u32, state: State<'_, AppState>) -> Result<PositionalView, GameError> {
+    let letter = parse_letter(&letter)?;
+    let mut game = game_of(&state.positionals, &session_id).await?;
+    game.guess_at(letter, index as usize)?;
+    Ok(game.view())
+}
+
+#[tauri::command]
+async fn get_positional_state(session_id: String, state: State<'_, AppState>) -> Result<PositionalView, GameError> {
+    Ok(game_of(&state.positionals, &session_id).await?.view())
+}
+
+// Start a crossword game (a few crossing words, each letter revealed in all of them),
+// or a new board in a window's crossword game session
+// The words are drawn from the same words as a classic game's, fails with invalid_input
+// if no two of them cross
+#[tauri::command]
+async fn start_crossword_game(
+    session_id: Option<String>,
+    difficulty: Option<schema::Difficulty>,
+    state: State<'_, AppState>,
+) -> Result<CrosswordSessionView, GameError> {
+    let difficulty = difficulty_or_default(&state, difficulty).await;
+    let lengths = lengths_or_default(&state, None).await?;
+    let words = playable(&state, &word_pool(&state).await.0, lengths).await?;
+    let mut game = CrosswordGame::generate(&words, difficulty, &mut state.rng.clone())?;
+    game.ignore_accents = state.settings.lock().await.ignore_accents;
+    if let Some(id) = session_id {
+        if let Some(mut existing) = find_game(&state.crosswords, &id).await {
+            *existing = game;
+            return Ok(existing.session_view(&id));
+        }
+    }
+    let (id, game) = create_session(&state.crosswords, game).await;
+    Ok(game.session_view(&id))
+}
+
+// Guess a letter of a crossword game, revealed in every word that has it
+#[tauri::command]
+async fn guess_crossword(session_id: String, letter: String, state: State<'_, AppState>) -> Result<CrosswordView, GameError> {
+    let letter = parse_letter(&letter)?;
+    let mut game = game_of(&state.crosswords, &session_id).await?;
+    game.guess(letter)?;
+    Ok(game.view())
+}
+
+#[tauri::command]
+async fn get_crossword_state(session_id: String, state: State<'_, AppState>) -> Result<CrosswordView, GameError> {
+    Ok(game_of(&state.crosswords, &session_id).await?.view())
+}
+
+// Called when a window closes, its game is abandoned (idle sessions expire on their own too)
+// An unfinished game stays saved, so it can be resumed after a restart
+#[tauri::command]
+async fn end_session(session_id: String, app: AppHandle, state: State<'_, AppState>) -> Result<(), GameError> {
+    let removed = state.sessions.write().await.remove(&session_id);
+    if let Some(game) = removed {
+        // (a command still playing it finishes first)
+        if !game.lock().await.game_over {
+            emit(&state, GameEvent::Abandoned { session_id: session_id.clone() }).await;
+        }
+    }
+    state.wordles.write().await.remove(&session_id);
+    state.positionals.write().await.remove(&session_id);
+    state.crosswords.write().await.remove(&session_id);
+    // Its guests have nothing left to play
+    net::session_ended(&app, &state, &session_id).await;
+    Ok(())
+}
+
+// Let guests on other machines play the session's game too, on `port` (7878 by default)
+// Returns the address and join code to give them, guests' moves are made in the game
+// like the window's own and come back through game_updated. Stops hosting or leaves a
+// game joined before
+#[tauri::command]
+async fn host_game(session_id: String, port: Option<u16>, app: AppHandle, state: State<'_, AppState>) -> Result<ConnectionView, String> {
+    game_of(&state.sessions, &session_id).await.map_err(|err| err.to_string())?;
+    net::host(&app, session_id, port.unwrap_or(multiplayer::DEFAULT_PORT)).await
+}
+
+// Play the game hosted at `address` (host:port) with its join code, as the selected profile
+// The host's game comes in remote_game_updated events, from right away
+#[tauri::command]
+async fn join_game(address: String, code: String, app: AppHandle, state: State<'_, AppState>) -> Result<ConnectionView, String> {
+    let name = state.profiles.lock().await.current.clone();
+    // Leave first, so a window can't end up in two games
+    net::leave(&app).await;
+    net::join(&app, address.trim().to_string(), code, name).await
+}
+
+// Guess a letter (or with `whole_word`, the word) in the joined game
+// The host checks it like its own guesses, a refused one comes back with the error in
+// remote_game_updated
+#[tauri::command]
+async fn send_guess(guess: String, whole_word: bool, state: State<'_, AppState>) -> Result<(), String> {
+    let message = if whole_word {
+        ClientMessage::Word { word: guess }
+    } else {
+        ClientMessage::Letter { letter: guess }
+    };
+    net::send_guess(&state, message).await
+}
+
+// Stop hosting (the guests are told) or leave the joined game
+#[tauri::command]
+async fn leave_game(app: AppHandle) -> Result<ConnectionView, String> {
+    Ok(net::leave(&app).await)
+}
+
+#[tauri::command]
+async fn get_connection(state: State<'_, AppState>) -> Result<ConnectionView, String> {
+    Ok(state.connection.lock().await.view())
+}
+
+// Host a head-to-head duel on `port` (7879 by default): another app on the network joins it
+// with the address and join code returned, and both race on the same word, each on a board
+// of their own (see hangman-core's versus.rs)
+// The word is picked like a classic game's, `rule` (first_solve by default) decides the winner
+// Stops hosting or leaves a duel joined before
+#[tauri::command]
+async fn host_versus(
+    port: Option<u16>,
+    difficulty: Option<schema::Difficulty>,
+    rule: Option<schema::VersusRule>,
+    app: AppHandle,
+    state: State<'_, AppState>,
+) -> Result<VersusUpdate, String> {
+    let difficulty = difficulty_or_default(&state, difficulty).await;
+    let lengths = lengths_or_default(&state, None).await.map_err(|err| err.to_string())?;
+    let mut game = pool_game(&state, difficulty, GameMode::Classic, None, lengths)
+        .await
+        .map_err(|err| err.to_string())?;
+    game.player = state.profiles.lock().await.current.clone();
+    state.settings.lock().await.apply(&mut game);
+    let rule = rule.map_or(VersusRule::default(), VersusRule::from);
+    versus::host(&app, game, rule, port.unwrap_or(DEFAULT_VERSUS_PORT)).await
+}
 
+// Play the duel hosted at `address` (host:port) with its join code, as the selected profile
+// The duel comes in versus_updated events, from right away
+#[tauri::command]
+async fn join_versus(address: String, code: String, app: AppHandle, state: State<'_, AppState>) -> Result<VersusUpdate, String> {
+    let name = state.profiles.lock().await.current.clone();
+    // Leave first, so a window can't end up in two duels
+    versus::leave(&app).await;
+    versus::join(&app, address.trim().to_string(), code, name).await
+}
+
+// Guess a letter (or with `whole_word`, the word) on this app's board of the duel
+// A refused guess comes back with the error in versus_updated
+#[tauri::command]
+async fn versus_guess(guess: String, whole_word: bool, app: AppHandle) -> Result<(), String> {
+    let message = if whole_word {
+        ClientMessage::Word { word: guess }
+    } else {
+        ClientMessage::Letter { letter: guess }
+    };
+    versus::guess(&app, message).await
+}
+
+// Stop hosting the duel (the other player is told) or leave the joined one
+#[tauri::command]
+async fn leave_versus(app: AppHandle) -> Result<VersusUpdate, String> {
+    Ok(versus::leave(&app).await)
+}
+
+#[tauri::command]
+async fn get_versus(state: State<'_, AppState>) -> Result<VersusUpdate, String> {
+    Ok(state.versus.lock().await.update(None))
+}
+
+fn main() {
     tauri::Builder::default()
-        .manage(AppState {
-            game: Mutex::new(initial_game),
+        .setup(|app| {
+            let resolver = app.path_resolver();
+
+            // Logs go to the OS log directory for the app (see README, "Logs")
+            let log_dir = resolver.app_log_dir().unwrap_or_else(|| "logs".into());
+            let telemetry = Telemetry::new("hangman").log_dir(log_dir).init()?;
+
+            // Settings saved by set_settings, or ./hangman.toml without an app config dir
+            let config_dir = resolver.app_config_dir();
+            let settings = load_settings(config_dir.as_deref());
+            let settings_file = config_dir.map_or_else(|| PathBuf::from(SETTINGS_FILE), |dir| dir.join(SETTINGS_FILE));
+
+            // Word lists are looked up in the app data dir first (in the settings' language), then the bundled resource
+            let user_dir = resolver.app_data_dir();
+            let bundled = resolver.resolve_resource(BUNDLED_WORD_FILE);
+            let words = WordList::load_language(user_dir.as_deref(), settings.language.as_deref(), bundled.as_deref());
+            let bundled_categories = resolver.resolve_resource(BUNDLED_CATEGORIES_FILE);
+            let categories = Categories::load(user_dir.as_deref(), bundled_categories.as_deref());
+            let bundled_wordle = resolver.resolve_resource(BUNDLED_WORDLE_FILE);
+            let wordle_words = wordle::load_words(user_dir.as_deref(), bundled_wordle.as_deref());
+            tracing::info!(
+                words = words.words().len(),
+                categories = categories.names().len(),
+                wordle_words = wordle_words.words().len(),
+                "word lists loaded"
+            );
+            if !words.report().is_clean() {
+                tracing::warn!("word list cleaned up: {}", words.report());
+            }
+
+            // Telemetry (if the player opts in) is written next to the user word lists too
+            let telemetry_file = user_dir.as_ref().map_or_else(|| PathBuf::from(TELEMETRY_FILE), |dir| dir.join(TELEMETRY_FILE));
+            let game_telemetry = GameTelemetry::new(telemetry_file, &settings);
+            // And so is the audit log
+            let audit_file = user_dir.as_ref().map_or_else(|| PathBuf::from(AUDIT_FILE), |dir| dir.join(AUDIT_FILE));
+            let audit_log = AuditLog::new(audit_file, &settings);
+            // And the command metrics, if they're written on exit
+            let metrics_file = user_dir.as_ref().map_or_else(|| PathBuf::from(METRICS_FILE), |dir| dir.join(METRICS_FILE));
+            let metrics = CommandMetrics::new(metrics_file, &settings);
+
+            // Statistics, profiles, and games are saved next to the user word lists in the app data dir
+            let storage = user_dir.as_deref().and_then(open_storage);
+            let stats = match &storage {
+                Some(storage) => storage.load_statistics().unwrap_or_else(|err| {
+                    tracing::warn!("starting with fresh statistics: {}", err);
+                    Statistics::default()
+                }),
+                None => Statistics::default(),
+            };
+            let profiles = match &storage {
+                Some(storage) => storage.load_profiles().unwrap_or_else(|err| {
+                    tracing::warn!("starting without profiles: {}", err);
+                    Profiles::default()
+                }),
+                None => Profiles::default(),
+            };
+            let achievements = match &storage {
+                Some(storage) => storage.load_achievements().unwrap_or_else(|err| {
+                    tracing::warn!("starting without achievements: {}", err);
+                    Achievements::default()
+                }),
+                None => Achievements::default(),
+            };
+            let custom_words = match &storage {
+                Some(storage) => storage.load_custom_words().unwrap_or_else(|err| {
+                    tracing::warn!("starting without imported words: {}", err);
+                    CustomWords::default()
+                }),
+                None => CustomWords::default(),
+            };
+            let dailies = match &storage {
+                Some(storage) => storage.load_dailies().unwrap_or_else(|err| {
+                    tracing::warn!("starting without daily results: {}", err);
+                    Dailies::default()
+                }),
+                None => Dailies::default(),
+            };
+            let campaign = match &storage {
+                Some(storage) => storage.load_campaign().unwrap_or_else(|err| {
+                    tracing::warn!("starting without campaign progress: {}", err);
+                    Campaign::default()
+                }),
+                None => Campaign::default(),
+            };
+            let definitions = match &storage {
+                Some(storage) => storage.load_definitions().unwrap_or_else(|err| {
+                    tracing::warn!("starting without cached definitions: {}", err);
+                    DefinitionCache::default()
+                }),
+                None => DefinitionCache::default(),
+            };
+            let online_words = match &storage {
+                Some(storage) => storage.load_online_words().unwrap_or_else(|err| {
+                    tracing::warn!("starting without fetched words: {}", err);
+                    OnlineWords::default()
+                }),
+                None => OnlineWords::default(),
+            };
+            let fetch = settings.word_source == WordSource::Online;
+            let mut providers = WordProviders::default();
+            register_providers(&mut providers, &words, &settings, user_dir.as_deref());
+            providers.register(OnlineProvider::new(online_words.clone()));
+
+            // Game events in the log file with RUST_LOG=debug (more plugins register here)
+            let mut plugins = PluginHost::new();
+            plugins.register(TracingPlugin::new("hangman-events"))?;
+
+            // Windows start their own session with their first game
+            app.manage(AppState {
+                sessions: SessionGames::default(),
+                words: Mutex::new(words),
+                word_dir: user_dir,
+                bundled_words: bundled,
+                categories,
+                rng: settings.rng(),
+                settings: Mutex::new(settings),
+                settings_file,
+                stats: Mutex::new(stats),
+                profiles: Mutex::new(profiles),
+                achievements: Mutex::new(achievements),
+                custom_words: Mutex::new(custom_words),
+                dailies: Mutex::new(dailies),
+                campaign: Mutex::new(campaign),
+                definitions: Mutex::new(definitions),
+                dictionary: Dictionary::default(),
+                online_words: Mutex::new(online_words),
+                providers: Mutex::new(providers),
+                word_api: WordApi::default(),
+                autosave: Autosaver::load(storage.as_ref()),
+                wordle_autosave: Autosaver::load(storage.as_ref()),
+                saver: Saver::start(storage.clone()),
+                storage,
+                plugins: Mutex::new(plugins),
+                telemetry: Mutex::new(game_telemetry),
+                connection: Mutex::new(Connection::Offline),
+                versus: Mutex::new(VersusConnection::Offline),
+                wordles: SessionGames::default(),
+                wordle_words,
+                positionals: SessionGames::default(),
+                crosswords: SessionGames::default(),
+                audit: std::sync::Mutex::new(audit_log),
+                metrics: std::sync::Mutex::new(metrics),
+            });
+            // Managed so the log writer lives (and flushes) as long as the app
+            app.manage(telemetry);
+
+            // New words for the online word source, the saved ones are played until they arrive
+            if fetch {
+                fetch_online_words(&app.handle());
+            }
+
+            // The clock of timed games runs in the backend, the frontend just waits for the update
+            let handle = app.handle();
+            every(CLOCK_INTERVAL, move || {
+                let handle = handle.clone();
+                async move { expire_timed_games(&Windows(&handle), &*handle.state::<AppState>()).await }
+            });
+            Ok(())
         })
-        .invoke_handler(tauri::generate_handler![
+        // Calls and their answers go through the audit log's hooks (see audit.rs)
+        .invoke_system(audit::INVOKE_SCRIPT.to_string(), audit::respond)
+        .invoke_handler(audit::handler(tauri::generate_handler![
             start_new_game,
+            start_new_game_with_category,
+            start_custom_game,
+            rematch,
+            start_daily_game,
+            start_match,
+            next_round,
+            start_run,
+            next_word,
+            start_campaign_level,
+            get_campaign_progress,
+            start_race,
+            list_categories,
             guess_letter,
-            get_game_state
-        ])
-        .run(tauri::generate_context!())
-        .expect("error while running tauri application");
+            guess_letters,
+            guess_word,
+            use_hint,
+            undo_guess,
+            give_up,
+            pause_game,
+            unpause_game,
+            get_wallet,
+            buy_help,
+            get_power_ups,
+            activate_powerup,
+            get_statistics,
+            get_settings,
+            set_settings,
+            list_profiles,
+            create_profile,
+            select_profile,
+            get_leaderboard,
+            get_rating_history,
+            get_word_providers,
+            get_achievements,
+            import_word_list,
+            get_word_list_report,
+            get_telemetry,
+            clear_telemetry,
+            list_languages,
+            set_language,
+            export_history,
+            get_word_stats,
+            get_most_missed_words,
+            rate_word,
+            get_game_state,
+            get_public_state,
+            get_share_text,
+            export_replay,
+            import_replay,
+            get_word_definition,
+            ai_take_turn,
+            get_letter_frequencies,
+            suggest_letters,
+            search_words,
+            get_saved_games,
+            discard_saved_game,
+            resume_game,
+            get_schemas,
+            start_wordle,
+            guess_wordle,
+            resume_wordle,
+            get_wordle_state,
+            start_positional_game,
+            guess_at,
+            get_positional_state,
+            start_crossword_game,
+            guess_crossword,
+            get_crossword_state,
+            end_session,
+            host_game,
+            join_game,
+            send_guess,
+            leave_game,
+            get_connection,
+            host_versus,
+            join_versus,
+            versus_guess,
+            leave_versus,
+            get_versus,
+            get_audit_log,
+            clear_audit_log,
+            get_metrics
+        ]))
+        .build(tauri::generate_context!())
+        .expect("error while building tauri application")
+        .run(|app, event| {
+            // A closed window's calls won't answer, so the metrics stop waiting for them
+            if let RunEvent::WindowEvent { label, event: WindowEvent::Destroyed, .. } = &event {
+                audit::lock(&app.state::<AppState>().metrics).forget_window(label);
+            }
+            // Saves still queued are written before the app goes away, the telemetry waiting is sent,
+            // and the command metrics are written if the settings ask for it
+            if let RunEvent::Exit = event {
+                let state = app.state::<AppState>();
+                tauri::async_runtime::block_on(state.saver.flush());
+                let waiting = tauri::async_runtime::block_on(state.telemetry.lock()).take();
+                if let Some(batch) = waiting {
+                    if let Err(err) = batch.send() {
+                        tracing::warn!("dropped {} telemetry events: {}", batch.events.len(), err);
+                    }
+                }
+                let metrics = audit::lock(&state.metrics);
+                match metrics.write_on_exit() {
+                    Ok(true) => tracing::info!(file = %metrics.file().display(), "command metrics written"),
+                    Ok(false) => {}
+                    Err(err) => tracing::warn!("couldn't write the command metrics: {}", err),
+                }
+            }
+        });
 }
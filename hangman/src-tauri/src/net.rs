@@ -0,0 +1,440 @@
+// ============================================================================
+// ONLINE MULTIPLAYER CONNECTIONS
+// ============================================================================
+// The WebSocket side of hangman-core's multiplayer.rs, one of:
+// - hosting (host_game): a window's session is played by guests too. The app
+//   listens on a port, lets in guests with the join code, makes their guesses
+//   in the session's game like the window's own (make_letter_guess,
+//   make_word_guess), and sends every guest the game whenever game_changed
+//   runs for the session
+// - joined (join_game): the window plays a game another app hosts. Guesses
+//   are sent to the host, and the host's games come back as
+//   remote_game_updated events
+// leave_game ends either, and starting one ends the other. Whenever the
+// connection changes (a guest comes or goes, the host goes away) the windows
+// get a connection_changed event.
+//
+// TASKS:
+// The listener and each connection are tokio tasks. A guest's connection
+// ends when the host's Host goes away (dropping it closes the channels the
+// tasks wait on), a joined game's when its Guest does.
+
+use std::net::{IpAddr, Ipv4Addr, UdpSocket};
+use std::sync::atomic::{AtomicU32, AtomicU64, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+use futures_util::{SinkExt, Stream, StreamExt};
+use hangman_core::multiplayer::{self, ClientMessage, HostMessage, MAX_MESSAGE_LEN};
+use hangman_core::GameState;
+use sandbox_rng::RngHandle;
+use sandbox_schema::hangman::{
+    ConnectionState, ConnectionView, GameError, GameView, RemoteGameUpdate, CONNECTION_CHANGED_EVENT, REMOTE_GAME_UPDATED_EVENT,
+};
+use sandbox_schema::SCHEMA_VERSION;
+use tauri::{AppHandle, Manager};
+use tokio::net::{TcpListener, TcpStream};
+use tokio::sync::{broadcast, mpsc, watch};
+use tokio_tungstenite::tungstenite::protocol::WebSocketConfig;
+use tokio_tungstenite::tungstenite::{Error as WsError, Message};
+use crate::sessions::{find_game, game_of};
+use hangman_service::{make_letter_guess, make_word_guess};
+use crate::{AppState, Windows};
+
+/// How long a guest has to send the join code, and a host to answer it
+const JOIN_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Game updates a slow guest can fall behind before missing some (each one is the whole game)
+const UPDATE_BACKLOG: usize = 16;
+
+/// Joins made so far, so a joined game that ended late doesn't end a newer one
+static JOINS: AtomicU64 = AtomicU64::new(0);
+
+/// The app's part in online multiplayer
+#[derive(Default)]
+pub enum Connection {
+    #[default]
+    Offline,
+    Hosting(Host),
+    Joined(Guest),
+    // A joined game that ended without leave_game, and why
+    Lost(String),
+}
+
+pub struct Host {
+    session_id: String,
+    address: String,
+    code: String,
+    // Every guest's connection gets the session's games from here
+    updates: broadcast::Sender<HostMessage>,
+    guests: Arc<AtomicU32>,
+    // Stops the listener when the Host is dropped
+    _stop: watch::Sender<()>,
+}
+
+pub struct Guest {
+    id: u64,
+    address: String,
+    // Guesses for the connection to send
+    outgoing: mpsc::UnboundedSender<ClientMessage>,
+}
+
+impl Connection {
+    pub fn view(&self) -> ConnectionView {
+        let mut view = ConnectionView {
+            schema_version: SCHEMA_VERSION,
+            state: ConnectionState::Offline,
+            address: None,
+            code: None,
+            session_id: None,
+            guests: 0,
+            reason: None,
+        };
+        match self {
+            Connection::Offline => {}
+            Connection::Hosting(host) => {
+                view.state = ConnectionState::Hosting;
+                view.address = Some(host.address.clone());
+                view.code = Some(host.code.clone());
+                view.session_id = Some(host.session_id.clone());
+                view.guests = host.guests.load(Ordering::Relaxed);
+            }
+            Connection::Joined(guest) => {
+                view.state = ConnectionState::Connected;
+                view.address = Some(guest.address.clone());
+            }
+            Connection::Lost(reason) => {
+                view.state = ConnectionState::Lost;
+                view.reason = Some(reason.clone());
+            }
+        }
+        view
+    }
+}
+
+pub fn websocket_config() -> WebSocketConfig {
+    WebSocketConfig {
+        max_message_size: Some(MAX_MESSAGE_LEN),
+        max_frame_size: Some(MAX_MESSAGE_LEN),
+        ..WebSocketConfig::default()
+    }
+}
+
+/// This machine's address on its network, what guests on it connect to
+/// (connecting a UDP socket only picks the interface, nothing is sent)
+pub fn lan_ip() -> IpAddr {
+    UdpSocket::bind((Ipv4Addr::UNSPECIFIED, 0))
+        .and_then(|socket| {
+            socket.connect((Ipv4Addr::new(8, 8, 8, 8), 80))?;
+            socket.local_addr()
+        })
+        .map_or(IpAddr::V4(Ipv4Addr::LOCALHOST), |address| address.ip())
+}
+
+/// Tell the windows where the connection stands
+async fn connection_changed(app: &AppHandle) -> ConnectionView {
+    let view = app.state::<AppState>().connection.lock().await.view();
+    if let Err(err) = app.emit_all(CONNECTION_CHANGED_EVENT, view.clone()) {
+        tracing::warn!("failed to emit {}: {}", CONNECTION_CHANGED_EVENT, err);
+    }
+    view
+}
+
+fn remote_game_changed(app: &AppHandle, game: GameView, error: Option<GameError>) {
+    let update = RemoteGameUpdate {
+        schema_version: SCHEMA_VERSION,
+        game,
+        error,
+    };
+    if let Err(err) = app.emit_all(REMOTE_GAME_UPDATED_EVENT, update) {
+        tracing::warn!("failed to emit {}: {}", REMOTE_GAME_UPDATED_EVENT, err);
+    }
+}
+
+// ---- hosting ----
+
+/// Let guests join `session_id`'s game on `port` (0 for any free port)
+pub async fn host(app: &AppHandle, session_id: String, port: u16) -> Result<ConnectionView, String> {
+    let listener = TcpListener::bind((Ipv4Addr::UNSPECIFIED, port))
+        .await
+        .map_err(|err| format!("couldn't listen on port {}: {}", port, err))?;
+    let port = listener.local_addr().map_err(|err| err.to_string())?.port();
+    let code = multiplayer::join_code(&mut RngHandle::from_entropy());
+    let (updates, _) = broadcast::channel(UPDATE_BACKLOG);
+    let (stop, mut stopped) = watch::channel(());
+    let guests = Arc::new(AtomicU32::new(0));
+    let host = Host {
+        session_id: session_id.clone(),
+        address: format!("{}:{}", lan_ip(), port),
+        code: code.clone(),
+        updates: updates.clone(),
+        guests: guests.clone(),
+        _stop: stop,
+    };
+    tracing::info!(address = %host.address, session_id = %session_id, "hosting a game");
+    replace(app, Connection::Hosting(host)).await;
+
+    let handle = app.clone();
+    tauri::async_runtime::spawn(async move {
+        loop {
+            tokio::select! {
+                accepted = listener.accept() => match accepted {
+                    Ok((stream, peer)) => {
+                        tracing::debug!(%peer, "guest connecting");
+                        let guest = serve_guest(handle.clone(), stream, code.clone(), session_id.clone(), updates.subscribe(), guests.clone());
+                        tauri::async_runtime::spawn(guest);
+                    }
+                    Err(err) => tracing::warn!("failed to accept a guest: {}", err),
+                },
+                // The Host was dropped
+                _ = stopped.changed() => break,
+            }
+        }
+    });
+    Ok(connection_changed(app).await)
+}
+
+/// A guest's connection, from the join code to the end
+async fn serve_guest(
+    app: AppHandle,
+    stream: TcpStream,
+    code: String,
+    session_id: String,
+    mut updates: broadcast::Receiver<HostMessage>,
+    guests: Arc<AtomicU32>,
+) {
+    let mut socket = match tokio_tungstenite::accept_async_with_config(stream, Some(websocket_config())).await {
+        Ok(socket) => socket,
+        Err(err) => {
+            tracing::debug!("not a WebSocket connection: {}", err);
+            return;
+        }
+    };
+    let joined = match tokio::time::timeout(JOIN_TIMEOUT, next_text(&mut socket)).await {
+        Ok(Some(text)) => ClientMessage::from_json(&text),
+        _ => return,
+    };
+    let name = match joined {
+        Ok(ClientMessage::Join { code: typed, name }) if multiplayer::code_matches(&code, &typed) => name,
+        Ok(ClientMessage::Join { .. }) => return refuse(socket, "wrong join code").await,
+        _ => return refuse(socket, "join with a code first").await,
+    };
+    let state = app.state::<AppState>();
+    let game = find_game(&state.sessions, &session_id).await.map(|game| game.view());
+    let Some(game) = game else {
+        return refuse(socket, "the game has ended").await;
+    };
+    if socket.send(Message::Text(HostMessage::Welcome { game }.to_json())).await.is_err() {
+        return;
+    }
+    tracing::info!(guest = ?name, "guest joined");
+    guests.fetch_add(1, Ordering::Relaxed);
+    connection_changed(&app).await;
+
+    loop {
+        tokio::select! {
+            text = next_text(&mut socket) => {
+                let Some(text) = text else {
+                    break;
+                };
+                if let Some(reply) = guest_move(&app, &session_id, &text).await {
+                    if socket.send(Message::Text(reply.to_json())).await.is_err() {
+                        break;
+                    }
+                }
+            }
+            update = updates.recv() => match update {
+                Ok(update) => {
+                    let closed = matches!(update, HostMessage::Closed { .. });
+                    if socket.send(Message::Text(update.to_json())).await.is_err() || closed {
+                        break;
+                    }
+                }
+                // Only the latest game matters, and the next update has it
+                Err(broadcast::error::RecvError::Lagged(_)) => continue,
+                Err(broadcast::error::RecvError::Closed) => break,
+            },
+        }
+    }
+    let _ = socket.close(None).await;
+    tracing::info!(guest = ?name, "guest left");
+    guests.fetch_sub(1, Ordering::Relaxed);
+    connection_changed(&app).await;
+}
+
+async fn refuse<S>(mut socket: tokio_tungstenite::WebSocketStream<S>, reason: &str)
+where
+    S: tokio::io::AsyncRead + tokio::io::AsyncWrite + Unpin,
+{
+    let refused = HostMessage::Refused { reason: reason.to_string() };
+    let _ = socket.send(Message::Text(refused.to_json())).await;
+    let _ = socket.close(None).await;
+}
+
+/// Make a guest's guess in the hosted game, returns what only that guest needs to hear
+/// (a refused guess; the game after an accepted one goes to everyone through game_changed)
+async fn guest_move(app: &AppHandle, session_id: &str, text: &str) -> Option<HostMessage> {
+    let message = match ClientMessage::from_json(text) {
+        Ok(message) => message,
+        Err(err) => {
+            tracing::debug!("ignored a guest message: {}", err);
+            return None;
+        }
+    };
+    let state = app.state::<AppState>();
+    let mut game = game_of(&state.sessions, session_id).await.ok()?;
+    let made = match message {
+        ClientMessage::Letter { letter } => make_letter_guess(&Windows(app), &*state, session_id, &mut game, &letter).await,
+        ClientMessage::Word { word } => make_word_guess(&Windows(app), &*state, session_id, &mut game, &word).await,
+        ClientMessage::Join { .. } => Err(GameError::invalid_input("already joined")),
+    };
+    made.err().map(|error| HostMessage::Game {
+        game: game.view(),
+        error: Some(error),
+    })
+}
+
+/// Send the guests a hosted session's game after it changed
+pub async fn game_changed(state: &AppState, session_id: &str, game: &GameState) {
+    if let Connection::Hosting(host) = &*state.connection.lock().await {
+        if host.session_id == session_id {
+            // No receivers just means no guests yet
+            let _ = host.updates.send(HostMessage::Game { game: game.view(), error: None });
+        }
+    }
+}
+
+/// Stop hosting a session whose window closed its game
+pub async fn session_ended(app: &AppHandle, state: &AppState, session_id: &str) {
+    let hosted = matches!(&*state.connection.lock().await, Connection::Hosting(host) if host.session_id == session_id);
+    if hosted {
+        leave(app).await;
+    }
+}
+
+// ---- joining ----
+
+/// Join the game hosted at `address` (e.g. "192.168.1.20:7878") with its join code
+pub async fn join(app: &AppHandle, address: String, code: String, name: Option<String>) -> Result<ConnectionView, String> {
+    let url = if address.starts_with("ws://") || address.starts_with("wss://") {
+        address.clone()
+    } else {
+        format!("ws://{}", address)
+    };
+    let connecting = tokio_tungstenite::connect_async_with_config(url.as_str(), Some(websocket_config()), false);
+    let (mut socket, _) = tokio::time::timeout(JOIN_TIMEOUT, connecting)
+        .await
+        .map_err(|_| format!("{} didn't answer", address))?
+        .map_err(|err| format!("couldn't connect to {}: {}", address, err))?;
+    socket
+        .send(Message::Text(ClientMessage::Join { code, name }.to_json()))
+        .await
+        .map_err(|err| err.to_string())?;
+    let game = match tokio::time::timeout(JOIN_TIMEOUT, next_host_message(&mut socket)).await {
+        Ok(Some(HostMessage::Welcome { game })) => game,
+        Ok(Some(HostMessage::Refused { reason })) => return Err(format!("the host refused: {}", reason)),
+        _ => return Err(format!("{} isn't hosting a game", address)),
+    };
+
+    let id = JOINS.fetch_add(1, Ordering::Relaxed);
+    let (outgoing, mut queue) = mpsc::unbounded_channel();
+    tracing::info!(address = %address, "joined a game");
+    replace(app, Connection::Joined(Guest { id, address, outgoing })).await;
+    remote_game_changed(app, game, None);
+
+    let handle = app.clone();
+    tauri::async_runtime::spawn(async move {
+        let lost = loop {
+            tokio::select! {
+                message = next_host_message(&mut socket) => match message {
+                    Some(HostMessage::Welcome { game }) => remote_game_changed(&handle, game, None),
+                    Some(HostMessage::Game { game, error }) => remote_game_changed(&handle, game, error),
+                    Some(HostMessage::Refused { reason } | HostMessage::Closed { reason }) => break Some(reason),
+                    None => break Some("the connection to the host was lost".to_string()),
+                },
+                guess = queue.recv() => match guess {
+                    Some(guess) => {
+                        if socket.send(Message::Text(guess.to_json())).await.is_err() {
+                            break Some("the connection to the host was lost".to_string());
+                        }
+                    }
+                    // The Guest was dropped: the player left, or started hosting
+                    None => break None,
+                },
+            }
+        };
+        let _ = socket.close(None).await;
+        let Some(reason) = lost else {
+            return;
+        };
+        tracing::info!(reason = %reason, "joined game ended");
+        {
+            let state = handle.state::<AppState>();
+            let mut connection = state.connection.lock().await;
+            if !matches!(&*connection, Connection::Joined(guest) if guest.id == id) {
+                return;
+            }
+            *connection = Connection::Lost(reason);
+        }
+        connection_changed(&handle).await;
+    });
+    Ok(connection_changed(app).await)
+}
+
+/// Send a guess to the host of the joined game (its result comes as a remote_game_updated event)
+pub async fn send_guess(state: &AppState, guess: ClientMessage) -> Result<(), String> {
+    match &*state.connection.lock().await {
+        Connection::Joined(guest) => guest.outgoing.send(guess).map_err(|_| "the connection to the host was lost".to_string()),
+        _ => Err("not in an online game, join one first".to_string()),
+    }
+}
+
+// ---- leaving ----
+
+/// Stop hosting (telling the guests) or leave the joined game
+pub async fn leave(app: &AppHandle) -> ConnectionView {
+    replace(app, Connection::Offline).await;
+    connection_changed(app).await
+}
+
+/// Make `next` the connection, ending the one before
+async fn replace(app: &AppHandle, next: Connection) {
+    let state = app.state::<AppState>();
+    let previous = std::mem::replace(&mut *state.connection.lock().await, next);
+    if let Connection::Hosting(host) = previous {
+        let closed = HostMessage::Closed { reason: "the host stopped hosting".to_string() };
+        let _ = host.updates.send(closed);
+    }
+}
+
+// ---- messages ----
+
+/// The next text message, None once the connection is closed (or broken)
+pub async fn next_text<S>(socket: &mut S) -> Option<String>
+where
+    S: Stream<Item = Result<Message, WsError>> + Unpin,
+{
+    while let Some(message) = socket.next().await {
+        match message {
+            Ok(Message::Text(text)) => return Some(text),
+            Ok(Message::Close(_)) | Err(_) => return None,
+            // Pings are answered by tungstenite, binary messages aren't part of the protocol
+            Ok(_) => {}
+        }
+    }
+    None
+}
+
+/// The next message from the host (one it sent that can't be read ends the connection)
+async fn next_host_message<S>(socket: &mut S) -> Option<HostMessage>
+where
+    S: Stream<Item = Result<Message, WsError>> + Unpin,
+{
+    let text = next_text(socket).await?;
+    match HostMessage::from_json(&text) {
+        Ok(message) => Some(message),
+        Err(err) => {
+            tracing::warn!("left the game: {}", err);
+            None
+        }
+    }
+}
@@ -0,0 +1,49 @@
+// ============================================================================
+// SESSION GAMES
+// ============================================================================
+// Every window's game of a mode (hangman games, word games, positional and
+// crossword games), by its session id (see hangman-core's sessions.rs), each
+// behind a tokio lock of its own: a command read-locks the sessions just long
+// enough to find its game, then holds only the game's lock, so a slow command
+// (a save, a fetch) in one window doesn't hold up another's guesses. Tokio's
+// locks aren't poisoned by a panicking command, the next one just gets the
+// game. AppState hands the hangman games to the game service this way (see
+// hangman-service's state.rs).
+
+use std::sync::Arc;
+use tokio::sync::{Mutex, OwnedMutexGuard, RwLock};
+use hangman_core::{GameState, SessionId, Sessions};
+use sandbox_schema::hangman::GameError;
+
+/// Every session's game, each locked on its own
+pub type SessionGames<G = GameState> = RwLock<Sessions<Arc<Mutex<G>>>>;
+
+/// Start a session playing `game`, returns its id and its locked game
+pub async fn create_session<G>(sessions: &SessionGames<G>, game: G) -> (SessionId, OwnedMutexGuard<G>) {
+    let game = Arc::new(Mutex::new(game));
+    let locked = Arc::clone(&game).try_lock_owned().expect("a new game isn't locked");
+    let mut sessions = sessions.write().await;
+    let id = sessions.create(game);
+    tracing::debug!(sessions = sessions.len(), "session started");
+    (id, locked)
+}
+
+/// Lock the game of a session (the sessions are only read-locked while it's found)
+/// Fails with NoActiveGame if there's no such session (it was never started, or it expired)
+pub async fn game_of<G>(sessions: &SessionGames<G>, session_id: &str) -> Result<OwnedMutexGuard<G>, GameError> {
+    find_game(sessions, session_id).await.ok_or_else(|| {
+        tracing::warn!(session = %session_id, "unknown or expired session");
+        GameError::NoActiveGame
+    })
+}
+
+/// Lock the game of a session, None if there's no such session
+pub async fn find_game<G>(sessions: &SessionGames<G>, session_id: &str) -> Option<OwnedMutexGuard<G>> {
+    let game = sessions.read().await.get(session_id).cloned()?;
+    Some(game.lock_owned().await)
+}
+
+/// The sessions playing now
+pub async fn session_ids<G>(sessions: &SessionGames<G>) -> Vec<SessionId> {
+    sessions.read().await.games().map(|(id, _)| id.clone()).collect()
+}
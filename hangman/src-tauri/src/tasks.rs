@@ -0,0 +1,100 @@
+// ============================================================================
+// BACKGROUND TASKS
+// ============================================================================
+// Work the commands hand off to Tauri's tokio runtime, so none of it runs
+// while a command holds the game state's locks:
+// - Saver writes to the database (see hangman-core's storage.rs) one save
+//   at a time on a blocking thread: the statistics, profiles, the unfinished
+//   games (see autosave.rs), ..., and every finished game. Commands queue a
+//   copy (a Save, see hangman-service's save.rs) and carry on
+// - every() runs something on a timer, e.g. the clock of timed games
+// Dictionary lookups are run with spawn_blocking where they're made
+// (get_word_definition), since the command waits for their result anyway.
+// Fetching words for the online word source (fetch_online_words) runs in its
+// own task, nothing waits for it. Exporting the game history (export_history)
+// flushes the saver first, then reads the database on a blocking thread.
+//
+// SHUTTING DOWN:
+// Saves are written in the order they were queued. Saver::flush waits for
+// the ones queued so far, the app calls it on exit so none are lost.
+
+use std::future::Future;
+use std::time::Duration;
+use hangman_core::Storage;
+use hangman_service::Save;
+use tokio::sync::{mpsc, oneshot};
+
+enum Job {
+    Save(Save),
+    // Answered once every save queued before it is written
+    Flush(oneshot::Sender<()>),
+}
+
+/// Writes to the database in the background
+#[derive(Clone)]
+pub struct Saver {
+    jobs: mpsc::UnboundedSender<Job>,
+}
+
+impl Saver {
+    /// Start writing saves to `storage` (without one, e.g. when there's no app
+    /// data directory, saves are dropped)
+    pub fn start(storage: Option<Storage>) -> Self {
+        let (jobs, mut queue) = mpsc::unbounded_channel();
+        tauri::async_runtime::spawn(async move {
+            while let Some(job) = queue.recv().await {
+                match job {
+                    Job::Save(save) => {
+                        let Some(storage) = storage.clone() else {
+                            continue;
+                        };
+                        let name = save.name();
+                        match tauri::async_runtime::spawn_blocking(move || save.write(&storage)).await {
+                            Ok(Ok(())) => {}
+                            Ok(Err(err)) => tracing::warn!("failed to save {}: {}", name, err),
+                            Err(err) => tracing::warn!("saving {} stopped: {}", name, err),
+                        }
+                    }
+                    Job::Flush(done) => {
+                        // The flusher may have stopped waiting
+                        let _ = done.send(());
+                    }
+                }
+            }
+        });
+        Saver { jobs }
+    }
+
+    /// Queue a save, returns right away
+    pub fn save(&self, save: Save) {
+        let name = save.name();
+        if self.jobs.send(Job::Save(save)).is_err() {
+            tracing::warn!("{} not saved: the saver has stopped", name);
+        }
+    }
+
+    /// Wait until every save queued so far is written
+    pub async fn flush(&self) {
+        let (done, written) = oneshot::channel();
+        if self.jobs.send(Job::Flush(done)).is_ok() {
+            let _ = written.await;
+        }
+    }
+}
+
+/// Run `tick` every `period` (the first time right away) for as long as the app runs
+pub fn every<F, Fut>(period: Duration, mut tick: F)
+where
+    F: FnMut() -> Fut + Send + 'static,
+    Fut: Future<Output = ()> + Send,
+{
+    tauri::async_runtime::spawn(async move {
+        let mut interval = tokio::time::interval(period);
+        // A slow tick delays the next one instead of bunching them up
+        interval.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+        loop {
+            interval.tick().await;
+            tick().await;
+        }
+    });
+}
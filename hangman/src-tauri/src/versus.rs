@@ -0,0 +1,427 @@
+// ============================================================================
+// HEAD-TO-HEAD DUEL CONNECTIONS
+// ============================================================================
+// The WebSocket side of hangman-core's versus.rs, one of:
+// - hosting (host_versus): the app keeps both boards of a duel, listens on a
+//   port, and lets in one other player with the join code. The host's
+//   guesses (versus_guess) and the other player's are each made on their own
+//   board, and after every move both hear about it: the host's windows in a
+//   versus_updated event, the other player in an update message
+// - joined (join_versus): the window plays its board of a duel another app
+//   hosts. Guesses are sent to the host, and the duel comes back in
+//   versus_updated events
+// leave_versus ends either, and starting one ends the other. Duels don't use
+// online multiplayer's connection (see net.rs), an app can host a game and
+// be in a duel at the same time.
+//
+// TASKS:
+// Like net.rs's, the listener and the connection are tokio tasks. The other
+// player's connection ends when the host's VersusHost goes away, a joined
+// duel's when its VersusGuest does.
+
+use std::net::Ipv4Addr;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::Duration;
+use futures_util::{SinkExt, Stream};
+use hangman_core::multiplayer::{self, ClientMessage};
+use hangman_core::{GameState, Side, Versus, VersusMessage, VersusRule};
+use sandbox_rng::RngHandle;
+use sandbox_schema::hangman::{GameError, VersusState, VersusUpdate, VersusView, VERSUS_UPDATED_EVENT};
+use sandbox_schema::SCHEMA_VERSION;
+use tauri::{AppHandle, Manager};
+use tokio::net::{TcpListener, TcpStream};
+use tokio::sync::{mpsc, watch};
+use tokio_tungstenite::tungstenite::{Error as WsError, Message};
+use crate::net::{lan_ip, next_text, websocket_config};
+use crate::AppState;
+
+/// How long the other player has to send the join code, and a host to answer it
+const JOIN_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Duels joined so far, so a joined duel that ended late doesn't end a newer one
+static JOINS: AtomicU64 = AtomicU64::new(0);
+
+/// The app's part in a head-to-head duel
+#[derive(Default)]
+pub enum VersusConnection {
+    #[default]
+    Offline,
+    Hosting(VersusHost),
+    Joined(VersusGuest),
+    // The other player left or the connection dropped, and why
+    Lost(String),
+}
+
+pub struct VersusHost {
+    address: String,
+    code: String,
+    versus: Box<Versus>,
+    // The other player's connection gets the duel from here, once they joined
+    guest: Option<mpsc::UnboundedSender<VersusMessage>>,
+    // Stops the listener when the VersusHost is dropped
+    _stop: watch::Sender<()>,
+}
+
+pub struct VersusGuest {
+    id: u64,
+    address: String,
+    // The duel as the host last sent it
+    versus: Box<VersusView>,
+    // Guesses for the connection to send
+    outgoing: mpsc::UnboundedSender<ClientMessage>,
+}
+
+impl VersusConnection {
+    pub fn update(&self, error: Option<GameError>) -> VersusUpdate {
+        let mut update = VersusUpdate {
+            schema_version: SCHEMA_VERSION,
+            state: VersusState::Offline,
+            address: None,
+            code: None,
+            versus: None,
+            error,
+            reason: None,
+        };
+        match self {
+            VersusConnection::Offline => {}
+            VersusConnection::Hosting(host) => {
+                update.address = Some(host.address.clone());
+                update.code = Some(host.code.clone());
+                if host.guest.is_some() {
+                    update.state = VersusState::Playing;
+                    update.versus = Some(host.versus.view(Side::Host));
+                } else {
+                    update.state = VersusState::Waiting;
+                }
+            }
+            VersusConnection::Joined(guest) => {
+                update.state = VersusState::Playing;
+                update.address = Some(guest.address.clone());
+                update.versus = Some((*guest.versus).clone());
+            }
+            VersusConnection::Lost(reason) => {
+                update.state = VersusState::Lost;
+                update.reason = Some(reason.clone());
+            }
+        }
+        update
+    }
+}
+
+/// Tell the windows where the duel stands, `error` for a guess of theirs it refused
+async fn versus_changed(app: &AppHandle, error: Option<GameError>) -> VersusUpdate {
+    let update = app.state::<AppState>().versus.lock().await.update(error);
+    if let Err(err) = app.emit_all(VERSUS_UPDATED_EVENT, update.clone()) {
+        tracing::warn!("failed to emit {}: {}", VERSUS_UPDATED_EVENT, err);
+    }
+    update
+}
+
+// ---- hosting ----
+
+/// Host a duel on the word of `game` (a new classic game) on `port` (0 for any free port)
+pub async fn host(app: &AppHandle, game: GameState, rule: VersusRule, port: u16) -> Result<VersusUpdate, String> {
+    let versus = Versus::new(game, rule).map_err(|err| err.to_string())?;
+    let listener = TcpListener::bind((Ipv4Addr::UNSPECIFIED, port))
+        .await
+        .map_err(|err| format!("couldn't listen on port {}: {}", port, err))?;
+    let port = listener.local_addr().map_err(|err| err.to_string())?.port();
+    let code = multiplayer::join_code(&mut RngHandle::from_entropy());
+    let (stop, mut stopped) = watch::channel(());
+    let host = VersusHost {
+        address: format!("{}:{}", lan_ip(), port),
+        code: code.clone(),
+        versus: Box::new(versus),
+        guest: None,
+        _stop: stop,
+    };
+    tracing::info!(address = %host.address, "hosting a duel");
+    replace(app, VersusConnection::Hosting(host)).await;
+
+    let handle = app.clone();
+    tauri::async_runtime::spawn(async move {
+        loop {
+            tokio::select! {
+                accepted = listener.accept() => match accepted {
+                    Ok((stream, peer)) => {
+                        tracing::debug!(%peer, "opponent connecting");
+                        tauri::async_runtime::spawn(serve_opponent(handle.clone(), stream, code.clone()));
+                    }
+                    Err(err) => tracing::warn!("failed to accept an opponent: {}", err),
+                },
+                // The VersusHost was dropped
+                _ = stopped.changed() => break,
+            }
+        }
+    });
+    Ok(versus_changed(app, None).await)
+}
+
+/// Whether the duel being hosted is the one with join code `code`
+fn hosting<'a>(connection: &'a mut VersusConnection, code: &str) -> Option<&'a mut VersusHost> {
+    match connection {
+        VersusConnection::Hosting(host) if host.code == code => Some(host),
+        _ => None,
+    }
+}
+
+/// The other player's connection, from the join code to the end
+async fn serve_opponent(app: AppHandle, stream: TcpStream, code: String) {
+    let mut socket = match tokio_tungstenite::accept_async_with_config(stream, Some(websocket_config())).await {
+        Ok(socket) => socket,
+        Err(err) => {
+            tracing::debug!("not a WebSocket connection: {}", err);
+            return;
+        }
+    };
+    let joined = match tokio::time::timeout(JOIN_TIMEOUT, next_text(&mut socket)).await {
+        Ok(Some(text)) => ClientMessage::from_json(&text),
+        _ => return,
+    };
+    let name = match joined {
+        Ok(ClientMessage::Join { code: typed, name }) if multiplayer::code_matches(&code, &typed) => name,
+        Ok(ClientMessage::Join { .. }) => return refuse(socket, "wrong join code").await,
+        _ => return refuse(socket, "join with a code first").await,
+    };
+
+    let (outgoing, mut queue) = mpsc::unbounded_channel();
+    let welcome = {
+        let state = app.state::<AppState>();
+        let mut connection = state.versus.lock().await;
+        match hosting(&mut connection, &code) {
+            Some(host) if host.guest.is_none() => {
+                host.guest = Some(outgoing);
+                host.versus.guest.player = name.clone();
+                Some(host.versus.view(Side::Guest))
+            }
+            _ => None,
+        }
+    };
+    let Some(versus) = welcome else {
+        return refuse(socket, "the duel already has two players").await;
+    };
+    if socket.send(Message::Text(VersusMessage::Welcome { versus }.to_json())).await.is_err() {
+        return;
+    }
+    tracing::info!(opponent = ?name, "opponent joined the duel");
+    versus_changed(&app, None).await;
+
+    loop {
+        tokio::select! {
+            text = next_text(&mut socket) => match text {
+                Some(text) => opponent_move(&app, &code, &text).await,
+                None => break,
+            },
+            message = queue.recv() => match message {
+                Some(message) => {
+                    let closed = matches!(message, VersusMessage::Closed { .. });
+                    if socket.send(Message::Text(message.to_json())).await.is_err() || closed {
+                        break;
+                    }
+                }
+                // The VersusHost was dropped
+                None => break,
+            },
+        }
+    }
+    let _ = socket.close(None).await;
+    tracing::info!(opponent = ?name, "opponent left the duel");
+    {
+        let state = app.state::<AppState>();
+        let mut connection = state.versus.lock().await;
+        if hosting(&mut connection, &code).is_none() {
+            return;
+        }
+        *connection = VersusConnection::Lost("the other player left".to_string());
+    }
+    versus_changed(&app, None).await;
+}
+
+async fn refuse<S>(mut socket: tokio_tungstenite::WebSocketStream<S>, reason: &str)
+where
+    S: tokio::io::AsyncRead + tokio::io::AsyncWrite + Unpin,
+{
+    let refused = VersusMessage::Refused { reason: reason.to_string() };
+    let _ = socket.send(Message::Text(refused.to_json())).await;
+    let _ = socket.close(None).await;
+}
+
+/// Make the other player's guess on their board, and send them the duel (with the error
+/// if it was refused); the host's windows hear about accepted ones
+async fn opponent_move(app: &AppHandle, code: &str, text: &str) {
+    let message = match ClientMessage::from_json(text) {
+        Ok(message) => message,
+        Err(err) => {
+            tracing::debug!("ignored an opponent message: {}", err);
+            return;
+        }
+    };
+    let made = {
+        let state = app.state::<AppState>();
+        let mut connection = state.versus.lock().await;
+        let Some(host) = hosting(&mut connection, code) else {
+            return;
+        };
+        let made = make_move(&mut host.versus, Side::Guest, message);
+        if let Some(guest) = &host.guest {
+            let versus = host.versus.view(Side::Guest);
+            let _ = guest.send(VersusMessage::Update { versus, error: made.clone().err() });
+        }
+        made
+    };
+    if made.is_ok() {
+        versus_changed(app, None).await;
+    }
+}
+
+/// A letter or word guess on `side`'s board
+fn make_move(versus: &mut Versus, side: Side, message: ClientMessage) -> Result<(), GameError> {
+    match message {
+        ClientMessage::Letter { letter } => Ok(versus.guess_letter(side, &letter)?),
+        ClientMessage::Word { word } if word.trim().is_empty() => Err(GameError::invalid_input("Type a word or phrase to guess")),
+        ClientMessage::Word { word } => versus.guess_word(side, &word).map(|_| ()).map_err(GameError::from),
+        ClientMessage::Join { .. } => Err(GameError::invalid_input("already joined")),
+    }
+}
+
+// ---- playing ----
+
+/// Guess on this app's board of the duel: made right away when hosting, sent to the
+/// host when joined (the duel comes back in a versus_updated event either way, with
+/// the error if the guess was refused)
+pub async fn guess(app: &AppHandle, guess: ClientMessage) -> Result<(), String> {
+    let state = app.state::<AppState>();
+    let mut connection = state.versus.lock().await;
+    let made = match &mut *connection {
+        VersusConnection::Hosting(host) => {
+            let Some(guest) = &host.guest else {
+                return Err("wait for the other player to join".to_string());
+            };
+            let made = make_move(&mut host.versus, Side::Host, guess);
+            if made.is_ok() {
+                let _ = guest.send(VersusMessage::Update { versus: host.versus.view(Side::Guest), error: None });
+            }
+            made
+        }
+        VersusConnection::Joined(guest) => {
+            return guest.outgoing.send(guess).map_err(|_| "the connection to the host was lost".to_string());
+        }
+        _ => return Err("not in a duel, host or join one first".to_string()),
+    };
+    drop(connection);
+    versus_changed(app, made.err()).await;
+    Ok(())
+}
+
+// ---- joining ----
+
+/// Join the duel hosted at `address` (e.g. "192.168.1.20:7879") with its join code
+pub async fn join(app: &AppHandle, address: String, code: String, name: Option<String>) -> Result<VersusUpdate, String> {
+    let url = if address.starts_with("ws://") || address.starts_with("wss://") {
+        address.clone()
+    } else {
+        format!("ws://{}", address)
+    };
+    let connecting = tokio_tungstenite::connect_async_with_config(url.as_str(), Some(websocket_config()), false);
+    let (mut socket, _) = tokio::time::timeout(JOIN_TIMEOUT, connecting)
+        .await
+        .map_err(|_| format!("{} didn't answer", address))?
+        .map_err(|err| format!("couldn't connect to {}: {}", address, err))?;
+    socket
+        .send(Message::Text(ClientMessage::Join { code, name }.to_json()))
+        .await
+        .map_err(|err| err.to_string())?;
+    let versus = match tokio::time::timeout(JOIN_TIMEOUT, next_versus_message(&mut socket)).await {
+        Ok(Some(VersusMessage::Welcome { versus })) => versus,
+        Ok(Some(VersusMessage::Refused { reason })) => return Err(format!("the host refused: {}", reason)),
+        _ => return Err(format!("{} isn't hosting a duel", address)),
+    };
+
+    let id = JOINS.fetch_add(1, Ordering::Relaxed);
+    let (outgoing, mut queue) = mpsc::unbounded_channel();
+    tracing::info!(address = %address, "joined a duel");
+    replace(app, VersusConnection::Joined(VersusGuest { id, address, versus: Box::new(versus), outgoing })).await;
+
+    let handle = app.clone();
+    tauri::async_runtime::spawn(async move {
+        let lost = loop {
+            tokio::select! {
+                message = next_versus_message(&mut socket) => match message {
+                    Some(VersusMessage::Welcome { versus }) => joined_changed(&handle, id, versus, None).await,
+                    Some(VersusMessage::Update { versus, error }) => joined_changed(&handle, id, versus, error).await,
+                    Some(VersusMessage::Refused { reason } | VersusMessage::Closed { reason }) => break Some(reason),
+                    None => break Some("the connection to the host was lost".to_string()),
+                },
+                guess = queue.recv() => match guess {
+                    Some(guess) => {
+                        if socket.send(Message::Text(guess.to_json())).await.is_err() {
+                            break Some("the connection to the host was lost".to_string());
+                        }
+                    }
+                    // The VersusGuest was dropped: the player left, or started hosting
+                    None => break None,
+                },
+            }
+        };
+        let _ = socket.close(None).await;
+        let Some(reason) = lost else {
+            return;
+        };
+        tracing::info!(reason = %reason, "joined duel ended");
+        {
+            let state = handle.state::<AppState>();
+            let mut connection = state.versus.lock().await;
+            if !matches!(&*connection, VersusConnection::Joined(guest) if guest.id == id) {
+                return;
+            }
+            *connection = VersusConnection::Lost(reason);
+        }
+        versus_changed(&handle, None).await;
+    });
+    Ok(versus_changed(app, None).await)
+}
+
+/// Keep the duel the host sent, if it's still the one joined, and tell the windows
+async fn joined_changed(app: &AppHandle, id: u64, versus: VersusView, error: Option<GameError>) {
+    {
+        let state = app.state::<AppState>();
+        let mut connection = state.versus.lock().await;
+        match &mut *connection {
+            VersusConnection::Joined(guest) if guest.id == id => *guest.versus = versus,
+            _ => return,
+        }
+    }
+    versus_changed(app, error).await;
+}
+
+// ---- leaving ----
+
+/// Stop hosting (telling the other player) or leave the joined duel
+pub async fn leave(app: &AppHandle) -> VersusUpdate {
+    replace(app, VersusConnection::Offline).await;
+    versus_changed(app, None).await
+}
+
+/// Make `next` the duel, ending the one before
+async fn replace(app: &AppHandle, next: VersusConnection) {
+    let state = app.state::<AppState>();
+    let previous = std::mem::replace(&mut *state.versus.lock().await, next);
+    if let VersusConnection::Hosting(VersusHost { guest: Some(guest), .. }) = previous {
+        let _ = guest.send(VersusMessage::Closed { reason: "the host stopped the duel".to_string() });
+    }
+}
+
+/// The next message from the host (one it sent that can't be read ends the connection)
+async fn next_versus_message<S>(socket: &mut S) -> Option<VersusMessage>
+where
+    S: Stream<Item = Result<Message, WsError>> + Unpin,
+{
+    let text = next_text(socket).await?;
+    match VersusMessage::from_json(&text) {
+        Ok(message) => Some(message),
+        Err(err) => {
+            tracing::warn!("left the duel: {}", err);
+            None
+        }
+    }
+}
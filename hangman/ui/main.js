@@ -1,12 +1,34 @@
 let currentGameState = null;
+// This window's game session, sent with every game command (null until the first game starts)
+let sessionId = null;
+// When currentGameState.remaining_ms was received (the clock counts down from there)
+let timerSyncedAt = 0;
+// Who made the last move when taking turns with the AI ('you' or 'ai')
+let lastMover = 'you';
+// Whether the board shows a game another app hosts (guesses go to the host), and whether
+// that game has been drawn yet
+let remote = false;
+let remoteShown = false;
+// This window's word game session (null until the first word game starts)
+let wordleSessionId = null;
+let positionalSessionId = null;
+// The place of the positional game the next guess is for
+let positionalIndex = null;
+let crosswordSessionId = null;
+// How many moves the drawn board had, so only a new move's letters are animated
+let drawnMoves = 0;
 
 // Helper function to invoke Tauri commands
 async function invoke(cmd, args = {}) {
     return window.__TAURI__.invoke(cmd, args);
 }
 
-// Hangman parts in order
+// Hangman parts in order, the gallows first and then the figure
 const hangmanParts = [
+    'ground',
+    'pole',
+    'beam',
+    'rope',
     'head',
     'body',
     'left-arm',
@@ -17,70 +39,1487 @@ const hangmanParts = [
 
 // Initialize the game
 async function initGame() {
-    createKeyboard();
-    await startNewGame();
+    // The backend sends every change to a game, e.g. a timed game running out
+    await window.__TAURI__.event.listen('game_updated', event => {
+        if (currentGameState && event.payload.session_id === sessionId) {
+            showGame(event.payload.game);
+        }
+    });
+    // The joined game, after every move in it (anyone's)
+    await window.__TAURI__.event.listen('remote_game_updated', event => {
+        if (!remote) {
+            return;
+        }
+        const { game, error } = event.payload;
+        if (remoteShown) {
+            showGame(game);
+        } else {
+            currentGameState = game;
+            redrawBoard();
+            remoteShown = true;
+        }
+        if (error) {
+            showError(error);
+        }
+    });
+    // Hosting, joining or leaving, guests coming and going, and losing the host
+    await window.__TAURI__.event.listen('connection_changed', event => {
+        showConnection(event.payload);
+        if (remote && event.payload.state !== 'connected') {
+            backToLocalGame();
+        }
+    });
+    // The duel hosted or joined, after every move in it (either player's)
+    await window.__TAURI__.event.listen('versus_updated', event => {
+        showVersus(event.payload);
+    });
+    // Sent when a finished game unlocks an achievement
+    await window.__TAURI__.event.listen('achievement_unlocked', event => {
+        showUnlocked(event.payload);
+        loadAchievements();
+    });
+    await loadCategories();
+    await loadLanguages();
+    await loadSettings();
+    await loadStatistics();
+    await loadProfiles();
+    await loadAchievements();
+    await loadCampaign();
+    // Offer the games left unfinished last time (the app closed or crashed mid-game),
+    // a new game only starts once the player has chosen
+    const saved = await invoke('get_saved_games');
+    showSavedGames(saved);
+    if (!saved.game) {
+        await startNewGame();
+    }
+}
+
+// A line per interrupted game with its restore and discard buttons
+function showSavedGames(saved) {
+    const banner = document.getElementById('saved-games');
+    banner.innerHTML = '';
+    const offer = (text, restore, discard) => {
+        const line = document.createElement('div');
+        line.textContent = `💾 ${text} `;
+        const restoreBtn = document.createElement('button');
+        restoreBtn.className = 'new-game-btn';
+        restoreBtn.textContent = 'Restore';
+        const discardBtn = document.createElement('button');
+        discardBtn.className = 'new-game-btn';
+        discardBtn.textContent = 'Discard';
+        const done = async action => {
+            line.remove();
+            banner.hidden = banner.childElementCount === 0;
+            await action();
+        };
+        restoreBtn.addEventListener('click', () => done(restore));
+        discardBtn.addEventListener('click', () => done(discard));
+        line.append(restoreBtn, discardBtn);
+        banner.append(line);
+    };
+    if (saved.game) {
+        const game = saved.game;
+        offer(
+            `Unfinished game: ${game.display_word} (${game.wrong_guesses} / ${game.max_wrong_guesses} wrong)`,
+            async () => {
+                const resumed = await invoke('resume_game');
+                if (resumed) {
+                    resumeSession(resumed);
+                } else {
+                    await startNewGame();
+                }
+            },
+            async () => {
+                await invoke('discard_saved_game', { kind: 'hangman' });
+                await startNewGame();
+            },
+        );
+    }
+    if (saved.wordle) {
+        offer(
+            `Unfinished word game (${saved.wordle.guesses.length} / ${saved.wordle.max_guesses} guesses)`,
+            async () => {
+                const session = await invoke('resume_wordle');
+                if (session) {
+                    wordleSessionId = session.session_id;
+                    showWordle(session.game);
+                }
+            },
+            () => invoke('discard_saved_game', { kind: 'wordle' }),
+        );
+    }
+    banner.hidden = banner.childElementCount === 0;
+}
+
+// Refresh the statistics panel (after every finished game)
+async function loadStatistics() {
+    try {
+        const stats = await invoke('get_statistics');
+        document.getElementById('stat-played').textContent = stats.games_played;
+        document.getElementById('stat-wins').textContent = stats.wins;
+        document.getElementById('stat-losses').textContent = stats.losses;
+        document.getElementById('stat-win-rate').textContent = `${Math.round(stats.win_rate * 100)}%`;
+        document.getElementById('stat-streak').textContent = stats.current_streak;
+        document.getElementById('stat-best-streak').textContent = stats.best_streak;
+        document.getElementById('stat-avg-wrong').textContent = stats.average_wrong_guesses.toFixed(1);
+    } catch (error) {
+        console.error('Error loading statistics:', error);
+    }
+    loadMissedWords();
+}
+
+// List the words the selected player lost most often
+async function loadMissedWords() {
+    const list = document.getElementById('missed-words');
+    list.innerHTML = '';
+    try {
+        for (const word of await invoke('get_most_missed_words', { limit: 5 })) {
+            const name = document.createElement('span');
+            name.textContent = `Missed: ${word.word}`;
+            const detail = document.createElement('span');
+            detail.textContent = `lost ${word.losses} of ${word.played}, ${word.average_wrong_guesses.toFixed(1)} wrong guesses`;
+            list.append(name, detail);
+        }
+    } catch (error) {
+        // No stored games (the app has no data directory)
+        console.error('Error loading missed words:', error);
+    }
+}
+
+// Fill the achievements panel for the selected player
+async function loadAchievements() {
+    try {
+        const achievements = await invoke('get_achievements');
+        achievements.sort((a, b) => b.unlocked - a.unlocked);
+        const list = document.getElementById('achievements');
+        list.innerHTML = '';
+        for (const achievement of achievements) {
+            const title = document.createElement('span');
+            title.textContent = `${achievement.unlocked ? '🏆' : '🔒'} ${achievement.title}`;
+            title.title = achievement.description;
+            const detail = document.createElement('span');
+            detail.textContent = achievement.unlocked
+                ? `${achievement.word}, ${new Date(achievement.unlocked_at_ms).toLocaleDateString()}`
+                : achievement.description;
+            if (!achievement.unlocked) {
+                title.className = 'locked';
+                detail.className = 'locked';
+            }
+            list.append(title, detail);
+        }
+    } catch (error) {
+        console.error('Error loading achievements:', error);
+    }
+}
+
+// Announce an achievement under the game (until the next game starts)
+function showUnlocked(achievement) {
+    const unlocked = document.getElementById('unlocked');
+    const line = document.createElement('div');
+    line.textContent = `🏆 Achievement unlocked: ${achievement.title} (${achievement.description})`;
+    unlocked.append(line);
+    unlocked.hidden = false;
+}
+
+// Fill the profile dropdown and the leaderboard
+async function loadProfiles() {
+    try {
+        const profiles = await invoke('list_profiles');
+        const select = document.getElementById('profile-select');
+        select.innerHTML = '';
+        if (profiles.length === 0) {
+            select.add(new Option('Nobody (add a player below)', ''));
+        }
+        for (const profile of profiles) {
+            select.add(new Option(profile.name, profile.name, false, profile.current));
+        }
+
+        const leaderboard = await invoke('get_leaderboard');
+        const board = document.getElementById('leaderboard');
+        board.innerHTML = '';
+        for (const entry of leaderboard.entries) {
+            const name = document.createElement('span');
+            name.textContent = `${entry.rank}. ${entry.name} (${entry.wins}/${entry.games_played} won)`;
+            const score = document.createElement('span');
+            score.textContent = `${entry.best_score} best, ${entry.total_score} total`;
+            board.append(name, score);
+        }
+        await loadWallet();
+        await loadPowerUps();
+        await loadRatingHistory();
+    } catch (error) {
+        console.error('Error loading profiles:', error);
+    }
+}
+
+// The selected player's race rating on the chosen difficulty, graphed over their races
+async function loadRatingHistory() {
+    const difficulty = document.getElementById('rating-difficulty').value;
+    const history = await invoke('get_rating_history', { difficulty });
+    const summary = document.getElementById('rating-summary');
+    const graph = document.getElementById('rating-graph');
+    graph.innerHTML = '';
+    if (history.player === null) {
+        summary.textContent = 'Add a player to get a rating';
+        return;
+    }
+    const last = history.points[history.points.length - 1];
+    summary.textContent = `📈 ${history.rating} (the AI plays at ${history.ai_rating})`
+        + (last ? `, ${last.change >= 0 ? '+' : ''}${last.change} last race` : ', no races yet');
+    if (history.points.length < 2) {
+        return;
+    }
+    // The AI's rating is always on the graph, as a dashed line
+    const ratings = history.points.map((point) => point.rating).concat(history.ai_rating);
+    const low = Math.min(...ratings) - 10;
+    const high = Math.max(...ratings) + 10;
+    const y = (rating) => 60 - ((rating - low) / (high - low)) * 60;
+    const x = (index) => (index / (history.points.length - 1)) * 200;
+    const ns = 'http://www.w3.org/2000/svg';
+    const ai = document.createElementNS(ns, 'line');
+    ai.setAttribute('class', 'ai-rating');
+    ai.setAttribute('x1', 0);
+    ai.setAttribute('x2', 200);
+    ai.setAttribute('y1', y(history.ai_rating));
+    ai.setAttribute('y2', y(history.ai_rating));
+    const line = document.createElementNS(ns, 'polyline');
+    line.setAttribute('points', history.points.map((point, i) => `${x(i)},${y(point.rating)}`).join(' '));
+    graph.append(ai, line);
+}
+
+// The selected player's points, and the prices on the help buttons
+async function loadWallet() {
+    const wallet = await invoke('get_wallet');
+    for (const { help, price } of wallet.prices) {
+        const button = document.querySelector(`#shop-row [data-help="${help}"]`);
+        button.title = `${price} points`;
+        button.disabled = wallet.player === null || wallet.points < price;
+    }
+    document.getElementById('wallet-points').textContent = wallet.player === null
+        ? 'Add a player to earn points'
+        : `🪙 ${wallet.points} points (a win earns ${wallet.win_points} or more)`;
+}
+
+// Spend points on a help, the backend checks they cover it
+async function buyHelp(help) {
+    if (currentGameState.game_over || remote) {
+        return;
+    }
+    try {
+        const purchase = await invoke('buy_help', { sessionId, help });
+        showGame(purchase.game);
+        await loadWallet();
+    } catch (error) {
+        showError(error);
+    }
+}
+
+// The selected player's power-ups, how many of each on its button
+async function loadPowerUps() {
+    const inventory = await invoke('get_power_ups');
+    for (const { power_up, description, held } of inventory.power_ups) {
+        const button = document.querySelector(`#power-up-row [data-power-up="${power_up}"]`);
+        button.title = description;
+        button.querySelector('span').textContent = `×${held}`;
+        button.disabled = inventory.player === null || held === 0;
+    }
+}
+
+// Use a power-up, the backend checks the player holds one and the game can use it
+async function activatePowerUp(powerUp) {
+    if (currentGameState.game_over || remote) {
+        return;
+    }
+    try {
+        const activation = await invoke('activate_powerup', { sessionId, powerUp });
+        showGame(activation.game);
+        await loadPowerUps();
+    } catch (error) {
+        showError(error);
+    }
+}
+
+async function createProfile() {
+    const input = document.getElementById('profile-name-input');
+    const status = document.getElementById('profile-status');
+    try {
+        const profile = await invoke('create_profile', { name: input.value, sessionId });
+        input.value = '';
+        status.textContent = profile.current ? `Playing as ${profile.name}` : `Added ${profile.name}`;
+        await loadProfiles();
+        await loadAchievements();
+    } catch (error) {
+        status.textContent = `⚠️ ${error}`;
+    }
+}
+
+async function selectProfile() {
+    const status = document.getElementById('profile-status');
+    try {
+        const profile = await invoke('select_profile', {
+            name: document.getElementById('profile-select').value,
+            sessionId,
+        });
+        status.textContent = `Playing as ${profile.name}`;
+        await loadAchievements();
+        await loadCampaign();
+    } catch (error) {
+        status.textContent = `⚠️ ${error}`;
+    }
+}
+
+// Add the words of a file to the selected player's words, and say what was kept
+async function importWordList() {
+    const input = document.getElementById('import-path-input');
+    const status = document.getElementById('import-status');
+    const rejected = document.getElementById('import-rejected');
+    rejected.innerHTML = '';
+    try {
+        const summary = await invoke('import_word_list', { path: input.value.trim() });
+        status.textContent = `Added ${summary.accepted.length} words`
+            + ` (${summary.duplicates.length} already playable, ${summary.rejected.length} rejected),`
+            + ` ${summary.pool_size} words in play`;
+        for (const entry of summary.rejected) {
+            const item = document.createElement('li');
+            item.textContent = `${entry.word}: ${entry.reason}`;
+            rejected.append(item);
+        }
+    } catch (error) {
+        status.textContent = `⚠️ ${error}`;
+    }
+}
+
+// Write the game history to a file, and say how much went in
+async function exportHistory() {
+    const path = document.getElementById('export-path-input').value.trim();
+    const format = document.getElementById('export-format-select').value;
+    const status = document.getElementById('export-status');
+    try {
+        const summary = await invoke('export_history', { format, path });
+        status.textContent = `Exported ${summary.games} games (${summary.guesses} guesses) to ${summary.path}`;
+    } catch (error) {
+        status.textContent = `⚠️ ${error}`;
+    }
+}
+
+// List the telemetry collected (waiting to be sent, and in the local file)
+function showTelemetry(telemetry) {
+    const status = document.getElementById('telemetry-status');
+    const list = document.getElementById('telemetry-list');
+    status.textContent = telemetry.enabled
+        ? `${telemetry.pending.length} waiting, ${telemetry.saved.length} sent to ${telemetry.destination}`
+        : `Off, ${telemetry.saved.length} kept in ${telemetry.destination}`;
+    list.innerHTML = '';
+    [...telemetry.saved, ...telemetry.pending].forEach(event => {
+        const item = document.createElement('li');
+        item.textContent = `${event.kind}: ${event.word_length} letters, ${event.guesses} guesses (${event.difficulty}, ${event.mode})`;
+        list.appendChild(item);
+    });
+}
+
+async function viewTelemetry() {
+    try {
+        showTelemetry(await invoke('get_telemetry'));
+    } catch (error) {
+        document.getElementById('telemetry-status').textContent = `⚠️ ${error}`;
+    }
+}
+
+async function clearTelemetry() {
+    try {
+        showTelemetry(await invoke('clear_telemetry'));
+    } catch (error) {
+        document.getElementById('telemetry-status').textContent = `⚠️ ${error}`;
+    }
+}
+
+// List the last commands in the audit log, newest first
+function showAuditLog(audit) {
+    const status = document.getElementById('audit-status');
+    const list = document.getElementById('audit-list');
+    status.textContent = `${audit.enabled ? 'On' : 'Off'}, ${audit.entries.length} shown from ${audit.file}`;
+    list.innerHTML = '';
+    audit.entries.forEach(entry => {
+        const item = document.createElement('li');
+        const time = new Date(entry.at_ms).toLocaleTimeString();
+        const outcome = entry.ok ? 'ok' : `failed: ${entry.error}`;
+        item.textContent = `${time} ${entry.command} ${entry.args} → ${outcome} (${entry.duration_ms} ms)`;
+        list.appendChild(item);
+    });
+}
+
+async function viewAuditLog() {
+    try {
+        showAuditLog(await invoke('get_audit_log', { limit: 50 }));
+    } catch (error) {
+        document.getElementById('audit-status').textContent = `⚠️ ${error}`;
+    }
+}
+
+async function clearAuditLog() {
+    try {
+        showAuditLog(await invoke('clear_audit_log'));
+    } catch (error) {
+        document.getElementById('audit-status').textContent = `⚠️ ${error}`;
+    }
+}
+
+// List each command's calls and times, the most time spent in first
+function showMetrics(metrics) {
+    const status = document.getElementById('metrics-status');
+    const list = document.getElementById('metrics-list');
+    const ms = us => (us / 1000).toFixed(1);
+    status.textContent = `${metrics.commands.length} commands` + (metrics.on_exit ? `, written to ${metrics.file} on exit` : '');
+    list.innerHTML = '';
+    metrics.commands.forEach(command => {
+        const item = document.createElement('li');
+        const errors = command.errors ? `, ${command.errors} failed` : '';
+        item.textContent = `${command.command}: ${command.calls} calls${errors}, ` +
+            `p50 ${ms(command.p50_us)} ms, p90 ${ms(command.p90_us)} ms, p99 ${ms(command.p99_us)} ms, max ${ms(command.max_us)} ms`;
+        list.appendChild(item);
+    });
+}
+
+async function viewMetrics() {
+    try {
+        showMetrics(await invoke('get_metrics'));
+    } catch (error) {
+        document.getElementById('metrics-status').textContent = `⚠️ ${error}`;
+    }
+}
+
+// Start a word game, or a new word in this window's one
+async function startWordle() {
+    try {
+        const session = await invoke('start_wordle', { sessionId: wordleSessionId });
+        wordleSessionId = session.session_id;
+        showWordle(session.game);
+    } catch (error) {
+        document.getElementById('wordle-status').textContent = `⚠️ ${errorMessage(error)}`;
+    }
+}
+
+async function guessWordle() {
+    const input = document.getElementById('wordle-input');
+    if (!wordleSessionId) {
+        await startWordle();
+    }
+    try {
+        showWordle(await invoke('guess_wordle', { sessionId: wordleSessionId, word: input.value }));
+        input.value = '';
+    } catch (error) {
+        document.getElementById('wordle-status').textContent = `⚠️ ${errorMessage(error)}`;
+    }
+}
+
+// A row per guess, a letter per cell colored by its feedback, then the keys guessed so far
+function showWordle(game) {
+    const board = document.getElementById('wordle-board');
+    board.innerHTML = '';
+    for (const guess of game.guesses) {
+        const row = document.createElement('div');
+        row.className = 'wordle-row';
+        [...guess.word].forEach((letter, i) => {
+            const cell = document.createElement('span');
+            cell.className = `wordle-cell ${guess.feedback[i]}`;
+            cell.textContent = letter;
+            row.appendChild(cell);
+        });
+        board.appendChild(row);
+    }
+
+    const keys = document.getElementById('wordle-keys');
+    keys.innerHTML = '';
+    for (const key of game.keys) {
+        const cell = document.createElement('span');
+        cell.className = `wordle-cell ${key.feedback}`;
+        cell.textContent = key.letter;
+        keys.appendChild(cell);
+    }
+
+    const left = game.max_guesses - game.guesses.length;
+    document.getElementById('wordle-status').textContent = game.won
+        ? `🎉 Found in ${game.guesses.length}!`
+        : game.game_over
+            ? `The word was ${game.answer}`
+            : `${left} ${left === 1 ? 'guess' : 'guesses'} left`;
+}
+
+// Start a positional game, or a new word in this window's one
+async function startPositional() {
+    try {
+        const session = await invoke('start_positional_game', { sessionId: positionalSessionId });
+        positionalSessionId = session.session_id;
+        positionalIndex = null;
+        showPositional(session.game);
+    } catch (error) {
+        document.getElementById('positional-status').textContent = `⚠️ ${errorMessage(error)}`;
+    }
+}
+
+async function guessAt() {
+    const input = document.getElementById('positional-input');
+    const status = document.getElementById('positional-status');
+    if (positionalIndex === null) {
+        status.textContent = 'Click a hidden place first';
+        return;
+    }
+    try {
+        const game = await invoke('guess_at', { sessionId: positionalSessionId, letter: input.value, index: positionalIndex });
+        input.value = '';
+        if (game.board[positionalIndex] !== null) {
+            positionalIndex = null;
+        }
+        showPositional(game);
+    } catch (error) {
+        status.textContent = `⚠️ ${errorMessage(error)}`;
+    }
+}
+
+// The word's places (hidden ones can be picked), then every guess colored by its feedback
+function showPositional(game) {
+    const board = document.getElementById('positional-board');
+    board.innerHTML = '';
+    game.board.forEach((letter, i) => {
+        const cell = document.createElement('span');
+        if (letter === null) {
+            cell.className = `wordle-cell hidden${i === positionalIndex ? ' selected' : ''}`;
+            cell.textContent = '_';
+            cell.addEventListener('click', () => {
+                positionalIndex = i;
+                showPositional(game);
+            });
+        } else {
+            cell.className = 'wordle-cell correct';
+            cell.textContent = letter;
+        }
+        board.appendChild(cell);
+    });
+
+    const guesses = document.getElementById('positional-guesses');
+    guesses.innerHTML = '';
+    for (const guess of game.guesses) {
+        const cell = document.createElement('span');
+        cell.className = `wordle-cell ${guess.feedback}`;
+        cell.textContent = guess.letter;
+        // Places count from 1 for the player
+        cell.title = `Place ${guess.index + 1}`;
+        guesses.appendChild(cell);
+    }
+
+    const left = game.max_wrong_guesses - game.wrong_guesses;
+    document.getElementById('positional-status').textContent = game.won
+        ? `🎉 Found with ${game.wrong_guesses} wrong ${game.wrong_guesses === 1 ? 'guess' : 'guesses'}!`
+        : game.game_over
+            ? `The word was ${game.word}`
+            : `${left} wrong ${left === 1 ? 'guess' : 'guesses'} left`;
+}
+
+// Start a crossword game, or a new board in this window's one
+async function startCrossword() {
+    try {
+        const session = await invoke('start_crossword_game', { sessionId: crosswordSessionId });
+        crosswordSessionId = session.session_id;
+        showCrossword(session.game);
+    } catch (error) {
+        document.getElementById('crossword-status').textContent = `⚠️ ${errorMessage(error)}`;
+    }
+}
+
+async function guessCrossword() {
+    const input = document.getElementById('crossword-input');
+    try {
+        const game = await invoke('guess_crossword', { sessionId: crosswordSessionId, letter: input.value });
+        input.value = '';
+        showCrossword(game);
+    } catch (error) {
+        document.getElementById('crossword-status').textContent = `⚠️ ${errorMessage(error)}`;
+    }
+}
+
+// The grid (empty cells left blank), then the guessed letters, the misses marked
+function showCrossword(game) {
+    const grid = document.getElementById('crossword-grid');
+    grid.innerHTML = '';
+    grid.style.gridTemplateColumns = `repeat(${game.cols}, 1.8em)`;
+    for (const cell of game.cells) {
+        const square = document.createElement('span');
+        square.className = `wordle-cell ${cell.letter === null ? 'hidden' : 'correct'}`;
+        square.style.gridRow = cell.row + 1;
+        square.style.gridColumn = cell.col + 1;
+        square.textContent = cell.letter ?? '';
+        grid.appendChild(square);
+    }
+
+    const found = new Set(game.cells.map((cell) => cell.letter).filter((letter) => letter !== null));
+    const guesses = document.getElementById('crossword-guesses');
+    guesses.innerHTML = '';
+    for (const letter of game.guessed_letters) {
+        const key = document.createElement('span');
+        key.className = `wordle-cell ${found.has(letter) ? 'correct' : 'absent'}`;
+        key.textContent = letter;
+        guesses.appendChild(key);
+    }
+
+    const complete = game.words.filter((word) => word.complete).length;
+    const left = game.max_wrong_guesses - game.wrong_guesses;
+    document.getElementById('crossword-status').textContent = game.won
+        ? `🎉 All ${game.words.length} words found!`
+        : game.game_over
+            ? `The words were ${game.words.map((word) => word.word).join(', ')}`
+            : `${complete}/${game.words.length} words, ${left} wrong ${left === 1 ? 'guess' : 'guesses'} left`;
+}
+
+// Fill the options panel from the backend, and start the game pickers on the saved defaults
+async function loadSettings() {
+    try {
+        const settings = await invoke('get_settings');
+        showSettings(settings);
+        document.getElementById('difficulty-select').value = settings.difficulty;
+        document.getElementById('category-select').value = settings.category ?? '';
+        showLanguage(settings.language);
+        showWordProviders();
+    } catch (error) {
+        console.error('Error loading settings:', error);
+    }
+}
+
+// Say next to the word source whether it can give words, or which one new games fall back to
+async function showWordProviders() {
+    const status = document.getElementById('word-source-status');
+    try {
+        const view = await invoke('get_word_providers');
+        const serving = view.providers.find((provider) => provider.source === view.serving);
+        status.textContent = view.fallback
+            ? `⚠️ ${view.fallback}, playing the ${view.serving} words`
+            : `✅ ${serving ? serving.words : 0} words`;
+    } catch (error) {
+        status.textContent = '';
+        console.error('Error checking the word providers:', error);
+    }
+}
+
+function showSettings(settings) {
+    document.getElementById('setting-difficulty').value = settings.difficulty;
+    document.getElementById('setting-category').value = settings.category ?? '';
+    document.getElementById('setting-language').value = settings.language ?? '';
+    document.getElementById('setting-hints-enabled').checked = settings.hints_enabled;
+    document.getElementById('setting-show-clues').checked = settings.show_clues;
+    document.getElementById('setting-word-source').value = settings.word_source;
+    document.getElementById('setting-word-file').value = settings.word_file ?? '';
+    document.getElementById('setting-profanity-filter').checked = settings.profanity_filter;
+    document.getElementById('setting-blocked-words').value = settings.blocked_words.join(', ');
+    document.getElementById('setting-max-wrong').value = settings.max_wrong_guesses ?? '';
+    document.getElementById('setting-hints').value = settings.hints_allowed ?? '';
+    document.getElementById('setting-case').value = settings.letter_case;
+    document.getElementById('setting-time-limit').value = settings.time_limit ?? '';
+    document.getElementById('setting-seed').value = settings.seed ?? '';
+    document.getElementById('setting-min-word-length').value = settings.min_word_length ?? '';
+    document.getElementById('setting-max-word-length').value = settings.max_word_length ?? '';
+    document.getElementById('setting-ignore-accents').checked = settings.ignore_accents;
+    document.getElementById('setting-digraphs').value = settings.digraphs.join(', ');
+    document.getElementById('setting-placeholder').value = settings.display.placeholder;
+    document.getElementById('setting-letter-spacing').checked = settings.display.letter_spacing;
+    document.getElementById('setting-word-separator').value = settings.display.word_separator;
+    document.getElementById('setting-telemetry').checked = settings.telemetry;
+    document.getElementById('setting-telemetry-endpoint').value = settings.telemetry_endpoint ?? '';
+    document.getElementById('setting-audit-log').checked = settings.audit_log;
+    document.getElementById('setting-metrics-on-exit').checked = settings.metrics_on_exit;
+    document.getElementById('setting-kids-mode').checked = settings.kids_mode;
+}
+
+// Save the options panel, empty fields fall back to the difficulty's defaults
+// The backend keeps them in hangman.toml, a new language loads its word list right away
+async function saveSettings() {
+    const status = document.getElementById('settings-status');
+    const numberOrNull = id => {
+        const value = document.getElementById(id).value;
+        return value === '' ? null : Number(value);
+    };
+    const valueOrNull = id => document.getElementById(id).value.trim() || null;
+    const settings = {
+        difficulty: document.getElementById('setting-difficulty').value,
+        category: valueOrNull('setting-category'),
+        language: valueOrNull('setting-language'),
+        hints_enabled: document.getElementById('setting-hints-enabled').checked,
+        show_clues: document.getElementById('setting-show-clues').checked,
+        word_source: document.getElementById('setting-word-source').value,
+        word_file: valueOrNull('setting-word-file'),
+        profanity_filter: document.getElementById('setting-profanity-filter').checked,
+        blocked_words: document.getElementById('setting-blocked-words').value
+            .split(',')
+            .map(word => word.trim())
+            .filter(word => word),
+        max_wrong_guesses: numberOrNull('setting-max-wrong'),
+        hints_allowed: numberOrNull('setting-hints'),
+        letter_case: document.getElementById('setting-case').value,
+        time_limit: numberOrNull('setting-time-limit'),
+        seed: numberOrNull('setting-seed'),
+        min_word_length: numberOrNull('setting-min-word-length'),
+        max_word_length: numberOrNull('setting-max-word-length'),
+        ignore_accents: document.getElementById('setting-ignore-accents').checked,
+        digraphs: document.getElementById('setting-digraphs').value
+            .split(',')
+            .map(digraph => digraph.trim())
+            .filter(digraph => digraph),
+        display: {
+            placeholder: document.getElementById('setting-placeholder').value || '_',
+            letter_spacing: document.getElementById('setting-letter-spacing').checked,
+            word_separator: document.getElementById('setting-word-separator').value || '   ',
+        },
+        telemetry: document.getElementById('setting-telemetry').checked,
+        telemetry_endpoint: valueOrNull('setting-telemetry-endpoint'),
+        audit_log: document.getElementById('setting-audit-log').checked,
+        metrics_on_exit: document.getElementById('setting-metrics-on-exit').checked,
+        kids_mode: document.getElementById('setting-kids-mode').checked,
+    };
+
+    try {
+        const saved = await invoke('set_settings', { settings });
+        showSettings(saved);
+        showLanguage(saved.language);
+        showWordProviders();
+        status.textContent = 'Saved, applies from the next game';
+    } catch (error) {
+        status.textContent = `⚠️ ${error}`;
+    }
+}
+
+// Fill the category dropdowns (the game picker's and the options') from the backend
+async function loadCategories() {
+    try {
+        const categories = await invoke('list_categories');
+        ['category-select', 'setting-category'].forEach(id => {
+            const select = document.getElementById(id);
+            categories.forEach(name => {
+                const option = document.createElement('option');
+                option.value = name;
+                option.textContent = name.charAt(0).toUpperCase() + name.slice(1);
+                select.appendChild(option);
+            });
+        });
+    } catch (error) {
+        console.error('Error loading categories:', error);
+    }
+}
+
+// Fill the language picker and the options' suggestions with the bundled word lists
+async function loadLanguages() {
+    try {
+        const languages = await invoke('list_languages');
+        const select = document.getElementById('language-select');
+        const list = document.getElementById('language-list');
+        languages.forEach(language => {
+            const option = document.createElement('option');
+            option.value = language.code;
+            option.textContent = language.name;
+            select.appendChild(option);
+            list.appendChild(option.cloneNode(true));
+        });
+    } catch (error) {
+        console.error('Error loading languages:', error);
+    }
+}
+
+// Select the settings' language in the picker, adding a code without a bundled list
+function showLanguage(code) {
+    const select = document.getElementById('language-select');
+    code = code ?? 'en';
+    if (![...select.options].some(option => option.value === code)) {
+        const option = document.createElement('option');
+        option.value = code;
+        option.textContent = code;
+        select.appendChild(option);
+    }
+    select.value = code;
+}
+
+// Switch the word list and play a game from it (the keys follow the language)
+async function setLanguage() {
+    const code = document.getElementById('language-select').value;
+    try {
+        const language = await invoke('set_language', { language: code === 'en' ? null : code });
+        document.getElementById('setting-language').value = code === 'en' ? '' : language.code;
+        await startNewGame();
+    } catch (error) {
+        console.error('Error switching language:', error);
+        await loadSettings();
+    }
+}
+
+// Draw the keyboard from the keys the backend sends (they follow the word list,
+// so e.g. a Spanish list gets an Ñ key): guessed keys are disabled and coloured
+// by whether the letter is in the word, every key once the game is over
+function updateKeyboard() {
+    const keyboard = document.getElementById('keyboard');
+    keyboard.innerHTML = '';
+
+    for (const key of currentGameState.keys) {
+        const button = document.createElement('button');
+        button.className = key.state === 'unused' ? 'key' : `key ${key.state}`;
+        button.textContent = key.letter;
+        button.onclick = () => guessLetter(key.letter);
+        button.disabled = key.state !== 'unused' || currentGameState.game_over;
+        keyboard.appendChild(button);
+    }
+}
+
+// Whether two letters count as the same guess, like the backend decides it:
+// case never matters, accents only without the ignore_accents setting
+function sameLetter(a, b) {
+    const fold = c => {
+        const lower = c.toLowerCase() === 'ς' ? 'σ' : c.toLowerCase();
+        // (a digraph's key, e.g. "CH", keeps all its letters)
+        return currentGameState.ignore_accents ? lower.normalize('NFD').replace(/\p{M}/gu, '') : lower;
+    };
+    return fold(a) === fold(b);
+}
+
+function isGuessed(letter) {
+    return currentGameState.guessed_letters.some(guessed => sameLetter(guessed, letter));
+}
+
+// Draw the word, with the letters a new move revealed (GameView.reveal) popping in
+// Redraws of the same move, and undos, draw it as it is
+function drawWord(game) {
+    const display = document.getElementById('word-display');
+    const newMove = game.history.length > drawnMoves;
+    drawnMoves = game.history.length;
+    if (!newMove || game.reveal.places.length === 0) {
+        display.textContent = game.display_word;
+        return;
+    }
+    const chars = Array.from(game.display_word);
+    display.textContent = '';
+    let at = 0;
+    for (const place of game.reveal.places) {
+        const end = place.display_at + Array.from(place.letter).length;
+        display.append(chars.slice(at, place.display_at).join(''));
+        const letter = document.createElement('span');
+        letter.className = 'revealed-letter';
+        letter.textContent = chars.slice(place.display_at, end).join('');
+        display.appendChild(letter);
+        at = end;
+    }
+    display.append(chars.slice(at).join(''));
+}
+
+// Start a new game
+async function startNewGame() {
+    try {
+        const category = document.getElementById('category-select').value;
+        const difficulty = document.getElementById('difficulty-select').value;
+        // In evil mode the backend keeps switching words to dodge the guesses
+        const mode = document.getElementById('mode-select').value;
+        // Practice games can't be lost and don't count
+        const practice = document.getElementById('practice').checked;
+        const session = category
+            ? await invoke('start_new_game_with_category', { sessionId, name: category, difficulty, mode, practice })
+            : await invoke('start_new_game', { sessionId, difficulty, mode, practice });
+        startSession(session);
+    } catch (error) {
+        showError(error);
+    }
+}
+
+// Start today's daily challenge (the backend refuses it once it's been finished)
+async function startDailyGame() {
+    try {
+        const difficulty = document.getElementById('difficulty-select').value;
+        startSession(await invoke('start_daily_game', { sessionId, difficulty }));
+    } catch (error) {
+        showError(error);
+    }
+}
+
+// Start a best-of-N match against the hangman, from its first round
+async function startMatch() {
+    try {
+        const rounds = Number(document.getElementById('match-rounds').value);
+        const rotation = document.getElementById('match-rotation').value;
+        const difficulty = document.getElementById('difficulty-select').value;
+        const mode = document.getElementById('mode-select').value;
+        startSession(await invoke('start_match', { sessionId, rounds, rotation, difficulty, mode }));
+    } catch (error) {
+        showError(error);
+    }
+}
+
+// Play the next round of the match (the backend picks its difficulty or category)
+async function nextRound() {
+    try {
+        startSession(await invoke('next_round', { sessionId }));
+    } catch (error) {
+        showError(error);
+    }
+}
+
+// Start an arcade run on the selected number of lives, from its first word
+async function startRun() {
+    try {
+        const lives = Number(document.getElementById('run-lives').value);
+        const difficulty = document.getElementById('difficulty-select').value;
+        const mode = document.getElementById('mode-select').value;
+        startSession(await invoke('start_run', { sessionId, lives, difficulty, mode }));
+    } catch (error) {
+        showError(error);
+    }
+}
+
+// Play the next word of the run, while it has lives left
+async function nextWord() {
+    try {
+        startSession(await invoke('next_word', { sessionId }));
+    } catch (error) {
+        showError(error);
+    }
+}
+
+// Play the selected player's next campaign level, or `level` (to retry one, or move on)
+async function startCampaignLevel(level) {
+    try {
+        const mode = document.getElementById('mode-select').value;
+        startSession(await invoke('start_campaign_level', { sessionId, level, mode }));
+    } catch (error) {
+        showError(error);
+    }
+}
+
+// Show how far the selected player got in the campaign
+async function loadCampaign() {
+    try {
+        const campaign = await invoke('get_campaign_progress');
+        const won = campaign.levels.filter(level => level.won).length;
+        const time = campaign.time_ms !== null ? ` · Completed in ${Math.round(campaign.time_ms / 60000)} min` : '';
+        document.getElementById('campaign-progress').textContent =
+            `${won}/${campaign.levels.length} levels · Campaign score ${campaign.score}${time}`;
+    } catch (error) {
+        console.error('could not load the campaign:', error);
+    }
+}
+
+// Start a two-player game with the secret typed in by player one
+// The backend only ever sends back the revealed letters of the secret
+async function startCustomGame() {
+    const input = document.getElementById('secret-input');
+    const difficulty = document.getElementById('difficulty-select').value;
+    try {
+        const session = await invoke('start_custom_game', { sessionId, wordOrPhrase: input.value, difficulty });
+        input.value = '';
+        startSession(session);
+    } catch (error) {
+        // Validation errors come back as invalid_input with a message for player one
+        showError(error);
+    }
+}
+
+// Swap roles after a two-player game: the secret typed by the player who just guessed
+async function rematch() {
+    const input = document.getElementById('secret-input');
+    const difficulty = document.getElementById('difficulty-select').value;
+    try {
+        const session = await invoke('rematch', { sessionId, wordOrPhrase: input.value, difficulty });
+        input.value = '';
+        startSession(session);
+    } catch (error) {
+        showError(error);
+    }
+}
+
+// Show a freshly started game and remember its session
+// (the same one unless the old session expired)
+function startSession(session) {
+    sessionId = session.session_id;
+    currentGameState = session.game;
+    resetBoard();
+}
+
+// Show a resumed game as it was left: drawing, used keys, and the result if it's already over
+function resumeSession(session) {
+    startSession(session);
+    redrawBoard();
+}
+
+// Draw a game that's under way from scratch (after resuming it, or undoing a move)
+function redrawBoard() {
+    resetBoard();
+    updateHangman(currentGameState);
+    if (currentGameState.game_over) {
+        showGameResult();
+    }
 }
 
-// Create keyboard buttons
-function createKeyboard() {
-    const keyboard = document.getElementById('keyboard');
-    const letters = 'ABCDEFGHIJKLMNOPQRSTUVWXYZ';
-    
-    for (let letter of letters) {
-        const button = document.createElement('button');
-        button.className = 'key';
-        button.textContent = letter;
-        button.onclick = () => guessLetter(letter);
-        button.id = `key-${letter}`;
-        keyboard.appendChild(button);
+// Reset the board for a freshly started game
+function resetBoard() {
+    // (updateUI draws the gallows too, with the parts a new game starts with)
+    updateUI();
+    document.getElementById('word-guess-input').value = '';
+    document.getElementById('game-status').textContent = '';
+    document.getElementById('game-status').className = 'game-status';
+    document.getElementById('definition').hidden = true;
+    document.getElementById('unlocked').innerHTML = '';
+    document.getElementById('unlocked').hidden = true;
+}
+
+// Guess a letter
+async function guessLetter(letter) {
+    if (currentGameState.game_over) {
+        return;
+    }
+
+    if (remote) {
+        await sendRemoteGuess(letter, false);
+        return;
+    }
+
+    try {
+        lastMover = 'you';
+        const game = await invoke('guess_letter', { sessionId, letter });
+        showGame(game);
+        if (!game.game_over) {
+            queueAiTurn();
+        }
+    } catch (error) {
+        // A repeat (the board was behind, game_updated catches it up), or
+        // e.g. the session expired while the window was left alone
+        showError(error);
     }
 }
 
-// Start a new game
-async function startNewGame() {
+// Guess every letter of some pasted text in one go (repeats and non-letters are skipped
+// by the backend, which says what happened to each)
+async function guessPastedLetters(text) {
+    if (currentGameState.game_over) {
+        return;
+    }
+    const letters = [...text].filter(char => /\p{L}/u.test(char));
+    if (letters.length === 0) {
+        return;
+    }
+    if (remote) {
+        for (const letter of letters) {
+            await sendRemoteGuess(letter, false);
+        }
+        return;
+    }
+
+    try {
+        lastMover = 'you';
+        const batch = await invoke('guess_letters', { sessionId, letters });
+        showGame(batch.game);
+        const misses = batch.outcomes.filter(outcome => outcome.result === 'miss').map(outcome => outcome.letter);
+        if (misses.length > 0 && !batch.game.game_over) {
+            const statusDiv = document.getElementById('game-status');
+            statusDiv.textContent = `Not in the word: ${misses.join(', ')}`;
+            statusDiv.className = 'game-status';
+        }
+        if (!batch.game.game_over) {
+            queueAiTurn();
+        }
+    } catch (error) {
+        showError(error);
+    }
+}
+
+// Guess the whole word: a miss costs two wrong guesses
+async function guessWord() {
+    const input = document.getElementById('word-guess-input');
+    if (currentGameState.game_over || !input.value.trim()) {
+        return;
+    }
+    if (remote) {
+        await sendRemoteGuess(input.value, true);
+        input.value = '';
+        return;
+    }
+
     try {
-        currentGameState = await invoke('start_new_game');
-        updateUI();
-        resetHangman();
-        enableAllKeys();
-        document.getElementById('game-status').textContent = '';
-        document.getElementById('game-status').className = 'game-status';
+        lastMover = 'you';
+        const game = await invoke('guess_word', { sessionId, word: input.value });
+        input.value = '';
+        showGame(game);
+        if (!game.game_over) {
+            queueAiTurn();
+        }
     } catch (error) {
-        console.error('Error starting new game:', error);
+        showError(error);
     }
 }
 
-// Guess a letter
-async function guessLetter(letter) {
+// Use a hint: the backend reveals a letter and charges a wrong guess
+async function useHint() {
     if (currentGameState.game_over) {
         return;
     }
 
     try {
-        currentGameState = await invoke('guess_letter', { letter });
-        updateUI();
-        disableKey(letter);
-        
-        if (currentGameState.game_over) {
-            disableAllKeys();
-            showGameResult();
+        const game = await invoke('use_hint', { sessionId });
+        showGame(game);
+        if (!game.game_over) {
+            queueAiTurn();
+        }
+    } catch (error) {
+        // No hints left, or not enough guesses to pay for one
+        const statusDiv = document.getElementById('game-status');
+        statusDiv.textContent = error.kind === 'hint_unavailable' ? `💡 ${errorMessage(error)}` : `⚠️ ${errorMessage(error)}`;
+        statusDiv.className = 'game-status';
+    }
+}
+
+// How many letters a smart hint shows
+const SMART_HINT_LETTERS = 5;
+
+// Show the letters in the most of the dictionary's words that still fit the board,
+// the backend counts them without giving the word away
+async function showLetterFrequencies() {
+    if (currentGameState.game_over) {
+        return;
+    }
+
+    try {
+        const frequencies = await invoke('get_letter_frequencies', { sessionId });
+        const best = frequencies.letters
+            .slice(0, SMART_HINT_LETTERS)
+            .map(frequency => `${frequency.letter} ${frequency.words}/${frequencies.words}`);
+        document.getElementById('letter-frequencies').textContent = best.length > 0
+            ? `📊 ${best.join(' · ')}`
+            : '📊 No word of the dictionary fits';
+    } catch (error) {
+        showError(error);
+    }
+}
+
+// Show the three letters the backend expects to tell the most about the word,
+// with how many bits each is worth
+async function showSuggestions() {
+    if (currentGameState.game_over) {
+        return;
+    }
+
+    try {
+        const view = await invoke('suggest_letters', { sessionId });
+        const best = view.suggestions.map(suggestion => `${suggestion.letter} ${suggestion.score.toFixed(2)} bits`);
+        document.getElementById('letter-suggestions').textContent = best.length > 0
+            ? `🧭 ${best.join(' · ')}`
+            : '🧭 No word of the dictionary fits';
+    } catch (error) {
+        showError(error);
+    }
+}
+
+// End the game as a loss, the backend sends the word back and counts it
+async function giveUp() {
+    if (!currentGameState || currentGameState.game_over) {
+        return;
+    }
+
+    try {
+        showGame(await invoke('give_up', { sessionId }));
+    } catch (error) {
+        showError(error);
+    }
+}
+
+// Stop or restart the clock of a timed game, guesses wait while it's stopped
+async function togglePause() {
+    if (!currentGameState || currentGameState.game_over) {
+        return;
+    }
+
+    try {
+        const command = currentGameState.paused ? 'unpause_game' : 'pause_game';
+        showGame(await invoke(command, { sessionId }));
+    } catch (error) {
+        showError(error);
+    }
+}
+
+// Take back the last move, a game it ended is played on
+async function undoGuess() {
+    try {
+        currentGameState = await invoke('undo_guess', { sessionId });
+        redrawBoard();
+    } catch (error) {
+        showError(error);
+    }
+}
+
+// When taking turns with the AI (or racing it), let it move after a short pause
+function queueAiTurn() {
+    const race = currentGameState && currentGameState.race;
+    if (race ? race.turn === 'ai' : document.getElementById('vs-ai').checked) {
+        setTimeout(aiTakeTurn, 600);
+    }
+}
+
+// Race the AI on the same word, each on a board of their own
+async function startRace() {
+    try {
+        const difficulty = document.getElementById('difficulty-select').value;
+        startSession(await invoke('start_race', { sessionId, difficulty }));
+    } catch (error) {
+        showError(error);
+    }
+}
+
+// The AI guesses a letter (or the word) using only what's on the board
+async function aiTakeTurn() {
+    if (!currentGameState || currentGameState.game_over) {
+        return;
+    }
+
+    try {
+        lastMover = 'ai';
+        const turn = await invoke('ai_take_turn', { sessionId });
+        showGame(turn.game);
+        if (!turn.game.game_over) {
+            const statusDiv = document.getElementById('game-status');
+            statusDiv.textContent = turn.word_guess ? `🤖 The AI guessed the word ${turn.guess}` : `🤖 The AI guessed ${turn.guess}`;
+            if (turn.game.race) {
+                statusDiv.textContent += ` on its board`;
+            }
+            statusDiv.className = 'game-status';
+        }
+    } catch (error) {
+        showError(error);
+    }
+}
+
+// Let guests on other machines play this window's game
+async function hostGame() {
+    try {
+        showConnection(await invoke('host_game', { sessionId }));
+    } catch (error) {
+        document.getElementById('connection-status').textContent = `⚠️ ${error}`;
+    }
+}
+
+// Play a game another app hosts, the board shows it until leaving
+async function joinGame() {
+    const address = document.getElementById('join-address-input').value.trim();
+    const code = document.getElementById('join-code-input').value.trim();
+    remote = true;
+    remoteShown = false;
+    try {
+        showConnection(await invoke('join_game', { address, code }));
+    } catch (error) {
+        remote = false;
+        document.getElementById('connection-status').textContent = `⚠️ ${error}`;
+    }
+}
+
+async function leaveGame() {
+    try {
+        showConnection(await invoke('leave_game'));
+    } catch (error) {
+        document.getElementById('connection-status').textContent = `⚠️ ${error}`;
+    }
+}
+
+// The host makes the guess, and sends the game back with remote_game_updated
+async function sendRemoteGuess(guess, wholeWord) {
+    try {
+        await invoke('send_guess', { guess, wholeWord });
+    } catch (error) {
+        showError(error);
+    }
+}
+
+// Host a duel on a new word, another app joins it with the address and code shown
+async function hostVersus() {
+    const rule = document.getElementById('versus-rule-select').value;
+    try {
+        showVersus(await invoke('host_versus', { rule }));
+    } catch (error) {
+        document.getElementById('versus-status').textContent = `⚠️ ${error}`;
+    }
+}
+
+async function joinVersus() {
+    const address = document.getElementById('versus-address-input').value.trim();
+    const code = document.getElementById('versus-code-input').value.trim();
+    try {
+        showVersus(await invoke('join_versus', { address, code }));
+    } catch (error) {
+        document.getElementById('versus-status').textContent = `⚠️ ${error}`;
+    }
+}
+
+// One letter is a letter guess, anything longer a guess at the word
+// (the duel comes back in versus_updated)
+async function guessVersus() {
+    const input = document.getElementById('versus-input');
+    const guess = input.value.trim();
+    try {
+        await invoke('versus_guess', { guess, wholeWord: [...guess].length > 1 });
+        input.value = '';
+    } catch (error) {
+        document.getElementById('versus-status').textContent = `⚠️ ${error}`;
+    }
+}
+
+async function leaveVersus() {
+    try {
+        showVersus(await invoke('leave_versus'));
+    } catch (error) {
+        document.getElementById('versus-status').textContent = `⚠️ ${error}`;
+    }
+}
+
+// This app's board of the duel, how far the other player got, and who won
+function showVersus(update) {
+    const status = document.getElementById('versus-status');
+    const board = document.getElementById('versus-board');
+    const opponent = document.getElementById('versus-opponent');
+    const versus = update.versus;
+    board.textContent = versus ? versus.board.display_word : '';
+    opponent.textContent = versus
+        ? `${versus.opponent.name ?? 'Opponent'}: ${versus.opponent.found} / ${versus.opponent.letters} letters, `
+            + `${versus.opponent.wrong_guesses} / ${versus.opponent.max_wrong_guesses} wrong`
+        : '';
+    if (update.error) {
+        status.textContent = `⚠️ ${errorMessage(update.error)}`;
+        return;
+    }
+    switch (update.state) {
+        case 'waiting':
+            status.textContent = `Waiting at ${update.address}, code ${update.code}`;
+            break;
+        case 'playing': {
+            const outcomes = { won: '🏆 You won the duel!', lost: `💀 You lost, the word was ${versus.board.word}`, draw: '🤝 A draw' };
+            status.textContent = versus.outcome
+                ? outcomes[versus.outcome]
+                : `${versus.board.max_wrong_guesses - versus.board.wrong_guesses} wrong guesses left`;
+            break;
         }
+        case 'lost':
+            status.textContent = `⚠️ ${update.reason}`;
+            break;
+        default:
+            status.textContent = '';
+    }
+}
+
+// Show this window's own game again after a joined one ended
+async function backToLocalGame() {
+    remote = false;
+    try {
+        currentGameState = await invoke('get_game_state', { sessionId });
+        redrawBoard();
     } catch (error) {
-        console.error('Error guessing letter:', error);
+        showError(error);
+    }
+}
+
+function showConnection(connection) {
+    const status = document.getElementById('connection-status');
+    switch (connection.state) {
+        case 'hosting':
+            status.textContent = `Hosting at ${connection.address}, code ${connection.code}`
+                + ` (${connection.guests} ${connection.guests === 1 ? 'guest' : 'guests'})`;
+            break;
+        case 'connected':
+            status.textContent = `Playing the game at ${connection.address}`;
+            break;
+        case 'lost':
+            status.textContent = `⚠️ ${connection.reason}`;
+            break;
+        default:
+            status.textContent = '';
+    }
+}
+
+// Show the latest state of the window's game, from a command result or a game_updated event
+// (both arrive for a move, the result is only announced by whichever comes first)
+function showGame(game) {
+    const wasOver = currentGameState.game_over;
+    currentGameState = game;
+    updateUI();
+    if (currentGameState.game_over && !wasOver) {
+        showGameResult();
     }
 }
 
 // Update the UI
 function updateUI() {
-    // Update word display
-    const wordDisplay = document.getElementById('word-display');
-    wordDisplay.textContent = currentGameState.word
-        .split('')
-        .map(char => currentGameState.guessed_letters.includes(char) ? char : '_')
-        .join(' ');
+    // Kids games get bigger keys and kinder messages
+    document.body.classList.toggle('kids', currentGameState.kids_mode);
+    // Update category label
+    const label = currentGameState.custom
+        ? 'Two players: custom word'
+        : currentGameState.daily !== null
+            ? `📅 Daily challenge #${currentGameState.daily}`
+            : currentGameState.category
+                ? `Category: ${currentGameState.category}`
+                : '';
+    // Rounds of a match show where the match stands, games of a run the lives left
+    const match = currentGameState.match_score;
+    const run = currentGameState.run;
+    const duel = currentGameState.duel;
+    document.getElementById('category-label').textContent = [
+        duel && `🤝 Round ${duel.round} · Player 1 ${duel.player_one_wins} – ${duel.player_two_wins} Player 2`
+            + ` · Player ${duel.guesser} guesses`,
+        match && `🏁 Round ${match.round} of ${match.rounds} · You ${match.wins} – ${match.losses} Hangman`,
+        currentGameState.race && `🏎️ AI: ${currentGameState.race.ai_found}/${currentGameState.race.letters} letters`
+            + `, ${currentGameState.race.ai_wrong_guesses}/${currentGameState.race.ai_max_wrong_guesses} misses`
+            + (currentGameState.race.ai_out ? ' (out)' : ''),
+        run && `${'❤️'.repeat(run.lives)}${'🖤'.repeat(run.start_lives - run.lives)} Word ${run.word} · Run score ${run.score}`,
+        currentGameState.campaign_level !== null && `🗺️ Level ${currentGameState.campaign_level}`,
+        label,
+        currentGameState.mode === 'evil' && '😈 Evil mode',
+        currentGameState.practice && '🎓 Practice',
+        currentGameState.kids_mode && '🧸 Kids',
+        !currentGameState.ranked && 'Unranked',
+    ].filter(Boolean).join(' · ');
+    document.getElementById('next-round-btn').hidden = !(match && match.can_continue);
+    document.getElementById('rematch-btn').hidden = !(duel && duel.can_rematch);
+    document.getElementById('secret-input').placeholder = duel && duel.can_rematch
+        ? `Secret word for player ${duel.setter}`
+        : 'Secret word for player two';
+    document.getElementById('next-word-btn').hidden = !(run && run.can_continue);
+    // A lost level is offered again, a won one moves on (the backend says if it's unlocked)
+    const level = currentGameState.campaign_level;
+    const over = currentGameState.game_over;
+    document.getElementById('retry-level-btn').hidden = !(level !== null && over && !currentGameState.won);
+    document.getElementById('next-level-btn').hidden = !(level !== null && over && currentGameState.won);
+
+    // The clue of the word (or its category), when the options turn clues on
+    const clue = document.getElementById('clue-banner');
+    clue.textContent = currentGameState.clue ? `💡 ${currentGameState.clue}` : '';
+    clue.hidden = !currentGameState.clue;
+
+    // Update word display (drawn by the backend in the display format from the options)
+    drawWord(currentGameState);
     
     // Update wrong guesses count
     document.getElementById('wrong-count').textContent = currentGameState.wrong_guesses;
+    document.getElementById('max-wrong').textContent = currentGameState.max_wrong_guesses;
+
+    // Show the keyboard in the game's letter case
+    updateKeyboard();
+    document.getElementById('keyboard').classList.toggle('lowercase', currentGameState.letter_case === 'lower');
+
+    // Update hints left
+    const hintsLeft = currentGameState.max_hints - currentGameState.hints_used;
+    document.getElementById('hints-left').textContent = hintsLeft;
+    document.getElementById('hint-btn').disabled = hintsLeft === 0 || currentGameState.game_over;
+    document.getElementById('smart-hint-btn').disabled = currentGameState.game_over;
+    document.getElementById('suggest-btn').disabled = currentGameState.game_over;
+    // A smart hint's counts and the suggestions are out of date after any move
+    document.getElementById('letter-frequencies').textContent = '';
+    document.getElementById('letter-suggestions').textContent = '';
+    document.getElementById('undo-btn').disabled = !currentGameState.can_undo;
+    document.getElementById('give-up-btn').disabled = currentGameState.game_over;
+    document.getElementById('share-btn').hidden = !currentGameState.game_over;
+    document.getElementById('export-replay-btn').hidden = !currentGameState.game_over;
+    const powerUps = [];
+    if (currentGameState.shield) {
+        powerUps.push('🛡️ Shield up');
+    }
+    if (currentGameState.frozen_ms > 0) {
+        powerUps.push(`🧊 Frozen ${Math.ceil(currentGameState.frozen_ms / 1000)}s`);
+    }
+    document.getElementById('power-up-status').textContent = powerUps.join(' · ');
     
     // Update guessed letters
     const guessedList = document.getElementById('guessed-list');
@@ -90,7 +1529,7 @@ function updateUI() {
         span.className = 'guessed-letter';
         span.textContent = letter;
         
-        if (currentGameState.word.includes(letter)) {
+        if ([...currentGameState.word].some(char => sameLetter(char, letter))) {
             span.classList.add('correct');
         } else {
             span.classList.add('wrong');
@@ -99,15 +1538,77 @@ function updateUI() {
         guessedList.appendChild(span);
     });
     
+    updateTimeline();
+
     // Update hangman drawing
-    updateHangman(currentGameState.wrong_guesses);
+    updateHangman(currentGameState);
+
+    timerSyncedAt = Date.now();
+    updateTimer();
+}
+
+// List the moves of the game in order, e.g. "R ✓ 0:00", "💡 S 0:12", "RUBY ✗ 0:31"
+function updateTimeline() {
+    const timeline = document.getElementById('timeline');
+    timeline.innerHTML = '';
+    const start = currentGameState.history[0]?.at_ms;
+    currentGameState.history.forEach(move => {
+        const item = document.createElement('li');
+        item.className = move.hit ? 'hit' : 'miss';
+        const seconds = Math.floor((move.at_ms - start) / 1000);
+        const time = `${Math.floor(seconds / 60)}:${String(seconds % 60).padStart(2, '0')}`;
+        item.textContent = move.kind === 'hint'
+            ? `💡 ${move.guess} ${time}`
+            : `${move.guess} ${move.hit ? '✓' : '✗'} ${time}`;
+        timeline.appendChild(item);
+    });
+}
+
+// Draw the clock of a timed game from the backend's remaining time
+// Between responses it counts down locally, at zero the backend ends the game
+// and sends the lost game with game_updated
+function updateTimer() {
+    const timer = document.getElementById('timer');
+    const remainingMs = currentGameState?.remaining_ms;
+    timer.hidden = remainingMs == null;
+    if (remainingMs == null) {
+        return;
+    }
+
+    const stopped = currentGameState.game_over || currentGameState.paused;
+    const elapsed = stopped ? 0 : Date.now() - timerSyncedAt;
+    const pause = document.getElementById('pause-btn');
+    pause.textContent = currentGameState.paused ? '▶️ Resume' : '⏸️ Pause';
+    pause.disabled = currentGameState.game_over;
+    document.getElementById('keyboard').classList.toggle('paused', !!currentGameState.paused);
+    const seconds = Math.ceil(Math.max(0, remainingMs - elapsed) / 1000);
+    document.getElementById('timer-value').textContent =
+        `${Math.floor(seconds / 60)}:${String(seconds % 60).padStart(2, '0')}`;
+    timer.classList.toggle('low', seconds <= 10);
 }
 
-// Update hangman drawing
-function updateHangman(wrongGuesses) {
+// Parts of the figure, the last of hangmanParts
+const FIGURE_PARTS = 6;
+
+// How many parts to draw at `stage` of `stages` (the same as hangman-core's gallows.rs):
+// up to 10 stages draw one part each, the gallows parts left over standing from the
+// start, fewer than 6 spread the figure over them, more spread every part
+function partsDrawn(stage, stages) {
+    const parts = hangmanParts.length;
+    if (stage >= stages) {
+        return parts;
+    }
+    const drawn = Math.min(Math.max(stages, FIGURE_PARTS), parts);
+    return parts - drawn + Math.min(Math.ceil(stage * drawn / stages), drawn - 1);
+}
+
+// Draw the game's stage, the backend works it out from the wrong guesses and the
+// game's budget (see GameView.stage)
+function updateHangman(state) {
+    const drawn = partsDrawn(state.stage, state.stages);
     for (let i = 0; i < hangmanParts.length; i++) {
         const part = document.getElementById(hangmanParts[i]);
-        if (i < wrongGuesses) {
+        if (i < drawn) {
             part.classList.add('visible');
         } else {
             part.classList.remove('visible');
@@ -115,58 +1616,373 @@ function updateHangman(wrongGuesses) {
     }
 }
 
-// Reset hangman drawing
-function resetHangman() {
-    hangmanParts.forEach(partId => {
-        document.getElementById(partId).classList.remove('visible');
-    });
-}
-
 // Show game result
 function showGameResult() {
     const statusDiv = document.getElementById('game-status');
     
-    if (currentGameState.won) {
+    if (currentGameState.won && currentGameState.ai_moves > 0 && lastMover === 'ai') {
+        statusDiv.textContent = `🤖 THE AI GOT IT! The word was: ${currentGameState.word}`;
+        statusDiv.className = 'game-status lose';
+    } else if (currentGameState.won) {
         statusDiv.textContent = '🎉 YOU WON! 🎉';
         statusDiv.className = 'game-status win';
+    } else if (currentGameState.timed_out) {
+        statusDiv.textContent = `⏰ TIME'S UP! The word was: ${currentGameState.word}`;
+        statusDiv.className = 'game-status lose';
+    } else if (currentGameState.gave_up) {
+        statusDiv.textContent = `🏳️ You gave up. The word was: ${currentGameState.word}`;
+        statusDiv.className = 'game-status lose';
+    } else if (currentGameState.kids_mode) {
+        statusDiv.textContent = `🌈 Good try! The word was: ${currentGameState.word}`;
+        statusDiv.className = 'game-status lose';
     } else {
         statusDiv.textContent = `😢 YOU LOST! The word was: ${currentGameState.word}`;
         statusDiv.className = 'game-status lose';
     }
+    // The score comes from the backend, it's only displayed here
+    if (currentGameState.score) {
+        statusDiv.textContent += ` Score: ${currentGameState.score.total}`;
+    }
+    // Rated by the backend once the word is settled
+    if (currentGameState.rating) {
+        statusDiv.textContent += ` (this was ${currentGameState.rating.level === 'easy' ? 'an' : 'a'}`
+            + ` ${currentGameState.rating.level.toUpperCase()} word)`;
+    }
+    const match = currentGameState.match_score;
+    if (match && match.winner) {
+        statusDiv.textContent += match.winner === 'player'
+            ? ` 🏆 You won the match ${match.wins}–${match.losses}!`
+            : ` The hangman won the match ${match.losses}–${match.wins}.`;
+    }
+    const race = currentGameState.race;
+    if (race && race.winner) {
+        statusDiv.textContent += {
+            player: ' 🏎️ You beat the AI!',
+            ai: ' 🤖 The AI completed the word first.',
+            hangman: ' Nobody completed the word.',
+        }[race.winner];
+    }
+    const run = currentGameState.run;
+    if (run && run.over) {
+        statusDiv.textContent += ` 💔 Out of lives after ${run.word} words, run score ${run.score}.`;
+    }
+
+    loadStatistics();
+    loadProfiles();
+    loadCampaign();
+    showDefinition();
+}
+
+// Show what the word means (nothing if the dictionary has no definition or can't be reached)
+async function showDefinition() {
+    const shownFor = sessionId;
+    try {
+        const definition = await invoke('get_word_definition', { sessionId });
+        // A new game may have started while the definition was fetched
+        if (!definition || shownFor !== sessionId || !currentGameState.game_over) {
+            return;
+        }
+        const partOfSpeech = definition.part_of_speech ? ` (${definition.part_of_speech})` : '';
+        const div = document.getElementById('definition');
+        div.textContent = `${definition.word}${partOfSpeech}: ${definition.definition}`;
+        div.hidden = false;
+    } catch (error) {
+        console.warn('no definition:', errorMessage(error));
+    }
+}
+
+// Copy the finished game's result (the backend builds it, so it reads the same everywhere)
+async function shareResult() {
+    try {
+        const text = await invoke('get_share_text', { sessionId });
+        await navigator.clipboard.writeText(text);
+        document.getElementById('share-btn').textContent = '📋 Copied!';
+        setTimeout(() => { document.getElementById('share-btn').textContent = '📋 Share Result'; }, 2000);
+    } catch (error) {
+        showError(error);
+    }
+}
+
+// Copy the finished game's replay, to watch it again later or send it
+async function exportReplay() {
+    try {
+        const replay = await invoke('export_replay', { sessionId });
+        await navigator.clipboard.writeText(replay);
+        document.getElementById('export-replay-btn').textContent = '🎬 Copied!';
+        setTimeout(() => { document.getElementById('export-replay-btn').textContent = '🎬 Copy Replay'; }, 2000);
+    } catch (error) {
+        showError(error);
+    }
+}
+
+// Longest pause between two steps of a replay, so a long think doesn't stall it
+const REPLAY_MAX_DELAY_MS = 1500;
+
+// Play a pasted replay on the board, with the moves' own timing (the backend
+// checks it and plays every step), then show the window's game again
+async function importReplay() {
+    const status = document.getElementById('replay-status');
+    try {
+        const replay = await invoke('import_replay', { replay: document.getElementById('replay-input').value });
+        status.textContent = `Playing ${replay.steps.length - 1} steps…`;
+        let previous = 0;
+        for (const step of replay.steps) {
+            await new Promise(resolve => setTimeout(resolve, Math.min(step.at_ms - previous, REPLAY_MAX_DELAY_MS)));
+            previous = step.at_ms;
+            currentGameState = step.game;
+            updateUI();
+        }
+        status.textContent = 'Replay finished';
+        setTimeout(async () => {
+            currentGameState = await invoke('get_game_state', { sessionId });
+            updateUI();
+        }, REPLAY_MAX_DELAY_MS * 2);
+    } catch (error) {
+        status.textContent = `⚠️ ${errorMessage(error)}`;
+    }
 }
 
-// Disable a specific key
-function disableKey(letter) {
-    const key = document.getElementById(`key-${letter}`);
-    if (key) {
-        key.disabled = true;
+// Find the words that fit the typed mask, in the selected category or all words
+async function searchWords() {
+    const results = document.getElementById('search-results');
+    try {
+        const search = await invoke('search_words', {
+            mask: document.getElementById('search-mask').value,
+            excluded: document.getElementById('search-excluded').value,
+            category: document.getElementById('category-select').value || null,
+        });
+        const more = search.total > search.words.length ? ` (first ${search.limit} of ${search.total})` : '';
+        results.textContent = search.total === 0
+            ? 'No word fits'
+            : `${search.words.join(', ')}${more}`;
+    } catch (error) {
+        results.textContent = `⚠️ ${errorMessage(error)}`;
     }
 }
 
-// Disable all keys
-function disableAllKeys() {
-    const keys = document.querySelectorAll('.key');
-    keys.forEach(key => key.disabled = true);
+// Show an error from the backend in the status line
+function showError(error) {
+    const statusDiv = document.getElementById('game-status');
+    statusDiv.textContent = `⚠️ ${errorMessage(error)}`;
+    statusDiv.className = 'game-status';
 }
 
-// Enable all keys
-function enableAllKeys() {
-    const keys = document.querySelectorAll('.key');
-    keys.forEach(key => key.disabled = false);
+// Text for a GameError ({ kind, message? }), other commands still fail with a plain string
+function errorMessage(error) {
+    switch (error && error.kind) {
+        case 'invalid_input':
+        case 'hint_unavailable':
+            return error.message;
+        case 'game_already_over':
+            return 'This game is over, start a new one';
+        case 'no_active_game':
+            return 'This game has expired, start a new one';
+        case 'empty_guess':
+            return 'Enter a letter';
+        case 'multiple_characters':
+            return 'Guess one letter at a time';
+        case 'digit':
+            return `${error.character} is a digit, guess a letter`;
+        case 'not_a_letter':
+            return `${error.character} isn't a letter`;
+        case 'already_guessed':
+            return `You already guessed ${error.letter}`;
+        case 'daily_completed':
+            return "You've done today's challenge, come back tomorrow";
+        case 'game_not_over':
+            return 'Finish the game first';
+        case 'nothing_to_undo':
+            return 'Nothing to undo yet';
+        case 'not_enough_points':
+            return `That costs ${error.price} points, you have ${error.points}`;
+        case 'help_unavailable':
+            return error.message;
+        case 'no_power_up_left':
+            return 'You have none of that power-up left, win games to earn more';
+        case 'power_up_unavailable':
+            return error.message;
+        case 'level_locked':
+            return `Level ${error.level} is locked, win the level before it first`;
+        default:
+            return String(error);
+    }
 }
 
 // Event listeners
 document.getElementById('new-game-btn').addEventListener('click', startNewGame);
+document.getElementById('language-select').addEventListener('change', setLanguage);
+document.getElementById('daily-game-btn').addEventListener('click', startDailyGame);
+document.getElementById('match-btn').addEventListener('click', startMatch);
+document.getElementById('next-round-btn').addEventListener('click', nextRound);
+document.getElementById('run-btn').addEventListener('click', startRun);
+document.getElementById('race-btn').addEventListener('click', startRace);
+document.getElementById('rating-difficulty').addEventListener('change', loadRatingHistory);
+document.getElementById('next-word-btn').addEventListener('click', nextWord);
+document.getElementById('campaign-btn').addEventListener('click', () => startCampaignLevel(null));
+document.getElementById('retry-level-btn').addEventListener('click', () => startCampaignLevel(currentGameState.campaign_level));
+document.getElementById('next-level-btn').addEventListener('click', () => startCampaignLevel(currentGameState.campaign_level + 1));
+document.getElementById('custom-game-btn').addEventListener('click', startCustomGame);
+document.getElementById('rematch-btn').addEventListener('click', rematch);
+document.getElementById('hint-btn').addEventListener('click', useHint);
+document.getElementById('smart-hint-btn').addEventListener('click', showLetterFrequencies);
+document.getElementById('suggest-btn').addEventListener('click', showSuggestions);
+document.getElementById('undo-btn').addEventListener('click', undoGuess);
+document.getElementById('give-up-btn').addEventListener('click', giveUp);
+document.getElementById('share-btn').addEventListener('click', shareResult);
+document.getElementById('export-replay-btn').addEventListener('click', exportReplay);
+document.getElementById('import-replay-btn').addEventListener('click', importReplay);
+document.getElementById('search-btn').addEventListener('click', searchWords);
+document.getElementById('pause-btn').addEventListener('click', togglePause);
+for (const button of document.querySelectorAll('#shop-row [data-help]')) {
+    button.addEventListener('click', () => buyHelp(button.dataset.help));
+}
+for (const button of document.querySelectorAll('#power-up-row [data-power-up]')) {
+    button.addEventListener('click', () => activatePowerUp(button.dataset.powerUp));
+}
+document.getElementById('save-settings-btn').addEventListener('click', saveSettings);
+document.getElementById('create-profile-btn').addEventListener('click', createProfile);
+document.getElementById('import-words-btn').addEventListener('click', importWordList);
+document.getElementById('export-history-btn').addEventListener('click', exportHistory);
+document.getElementById('view-telemetry-btn').addEventListener('click', viewTelemetry);
+document.getElementById('clear-telemetry-btn').addEventListener('click', clearTelemetry);
+document.getElementById('view-audit-btn').addEventListener('click', viewAuditLog);
+document.getElementById('clear-audit-btn').addEventListener('click', clearAuditLog);
+document.getElementById('view-metrics-btn').addEventListener('click', viewMetrics);
+document.getElementById('host-game-btn').addEventListener('click', hostGame);
+document.getElementById('join-game-btn').addEventListener('click', joinGame);
+document.getElementById('leave-game-btn').addEventListener('click', leaveGame);
+document.getElementById('wordle-new-btn').addEventListener('click', startWordle);
+document.getElementById('wordle-guess-btn').addEventListener('click', guessWordle);
+document.getElementById('positional-new-btn').addEventListener('click', startPositional);
+document.getElementById('host-versus-btn').addEventListener('click', hostVersus);
+document.getElementById('join-versus-btn').addEventListener('click', joinVersus);
+document.getElementById('versus-guess-btn').addEventListener('click', guessVersus);
+document.getElementById('leave-versus-btn').addEventListener('click', leaveVersus);
+document.getElementById('positional-guess-btn').addEventListener('click', guessAt);
+document.getElementById('crossword-new-btn').addEventListener('click', startCrossword);
+document.getElementById('crossword-guess-btn').addEventListener('click', guessCrossword);
+document.getElementById('profile-select').addEventListener('change', selectProfile);
+document.getElementById('guess-word-btn').addEventListener('click', guessWord);
+document.getElementById('word-guess-input').addEventListener('keydown', (e) => {
+    // Keep typing the word from counting as letter guesses
+    e.stopPropagation();
+    if (e.key === 'Enter') {
+        guessWord();
+    }
+});
+document.getElementById('secret-input').addEventListener('keydown', (e) => {
+    // Keep typing the secret from counting as guesses
+    e.stopPropagation();
+    if (e.key === 'Enter') {
+        startCustomGame();
+    }
+});
+document.getElementById('profile-name-input').addEventListener('keydown', (e) => {
+    // Keep typing the name from counting as guesses
+    e.stopPropagation();
+    if (e.key === 'Enter') {
+        createProfile();
+    }
+});
+document.getElementById('import-path-input').addEventListener('keydown', (e) => {
+    // Keep typing the path from counting as guesses
+    e.stopPropagation();
+    if (e.key === 'Enter') {
+        importWordList();
+    }
+});
+for (const id of ['join-address-input', 'join-code-input']) {
+    document.getElementById(id).addEventListener('keydown', (e) => {
+        // Keep typing the address or code from counting as guesses
+        e.stopPropagation();
+        if (e.key === 'Enter') {
+            joinGame();
+        }
+    });
+}
+for (const id of ['versus-address-input', 'versus-code-input']) {
+    document.getElementById(id).addEventListener('keydown', (e) => {
+        // Keep typing the address or code from counting as guesses
+        e.stopPropagation();
+        if (e.key === 'Enter') {
+            joinVersus();
+        }
+    });
+}
+document.getElementById('export-path-input').addEventListener('keydown', (e) => {
+    // Keep typing the path from counting as guesses
+    e.stopPropagation();
+    if (e.key === 'Enter') {
+        exportHistory();
+    }
+});
+document.getElementById('wordle-input').addEventListener('keydown', (e) => {
+    // The word game's letters aren't hangman guesses
+    e.stopPropagation();
+    if (e.key === 'Enter') {
+        guessWordle();
+    }
+});
+document.getElementById('versus-input').addEventListener('keydown', (e) => {
+    // The duel's guesses aren't this window's hangman guesses
+    e.stopPropagation();
+    if (e.key === 'Enter') {
+        guessVersus();
+    }
+});
+document.getElementById('positional-input').addEventListener('keydown', (e) => {
+    // The positional game's letters aren't hangman guesses
+    e.stopPropagation();
+    if (e.key === 'Enter') {
+        guessAt();
+    }
+});
+document.getElementById('crossword-input').addEventListener('keydown', (e) => {
+    // The crossword's letters aren't hangman guesses
+    e.stopPropagation();
+    if (e.key === 'Enter') {
+        guessCrossword();
+    }
+});
 
 // Keyboard support
 document.addEventListener('keydown', (e) => {
     if (currentGameState && !currentGameState.game_over) {
-        const letter = e.key.toUpperCase();
-        if (/^[A-Z]$/.test(letter) && !currentGameState.guessed_letters.includes(letter)) {
-            guessLetter(letter);
+        // Any alphabet's letters, including ones without a key on the board, and the
+        // digits and symbols the word list guesses (they have keys)
+        const isKey = currentGameState.keys.some((key) => key.letter === e.key);
+        if ((/^\p{L}$/u.test(e.key) || isKey) && !isGuessed(e.key)) {
+            guessLetter(e.key);
         }
     }
 });
 
+// Pasting outside the text boxes guesses the pasted letters
+document.addEventListener('paste', (e) => {
+    if (currentGameState && !currentGameState.game_over && !(e.target instanceof HTMLInputElement)) {
+        e.preventDefault();
+        guessPastedLetters(e.clipboardData.getData('text'));
+    }
+});
+
+// Tell the backend this window's game is abandoned
+window.addEventListener('beforeunload', () => {
+    if (sessionId) {
+        invoke('end_session', { sessionId });
+    }
+    if (wordleSessionId) {
+        invoke('end_session', { sessionId: wordleSessionId });
+    }
+    if (positionalSessionId) {
+        invoke('end_session', { sessionId: positionalSessionId });
+    }
+    if (crosswordSessionId) {
+        invoke('end_session', { sessionId: crosswordSessionId });
+    }
+});
+
+// Tick the clock of a timed game
+setInterval(updateTimer, 250);
+
 // Initialize when page loads
 window.addEventListener('DOMContentLoaded', initGame);
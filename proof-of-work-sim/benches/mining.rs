@@ -0,0 +1,95 @@
+//! Benchmarks for the mining hot paths: block hashing, nonce search, and
+//! chain validation
+//!
+//! Run with `cargo bench -p proof-of-work-sim`, see "Benchmarks" in the README
+//! for comparing against a saved baseline.
+
+use criterion::{black_box, criterion_group, criterion_main, BatchSize, BenchmarkId, Criterion};
+use proof_of_work_sim::block::Block;
+use proof_of_work_sim::blockchain::Blockchain;
+use proof_of_work_sim::config::Config;
+use proof_of_work_sim::traits::{Hashable, Validatable};
+use proof_of_work_sim::transaction::Transaction;
+use sandbox_rng::RngHandle;
+
+/// Roughly one valid nonce in 128 tries (a hash passes with probability ceiling / 2^32)
+const SEARCH_CEILING: i32 = i32::MAX / 64;
+
+/// A block holding a coinbase plus `transfers` transfers
+fn block_with(transfers: usize) -> Block {
+    let mut transactions = vec![Transaction::new_coinbase("miner1".to_string())];
+    transactions.extend(
+        (0..transfers).map(|i| Transaction::new_transfer(format!("sender{}", i), "bob".to_string(), i as u64)),
+    );
+    Block::new(1, transactions, "0".repeat(64))
+}
+
+/// A seeded chain of `len` blocks (genesis included), mined with an easy ceiling
+fn chain_of(len: usize) -> Blockchain {
+    let config = Config {
+        seed: Some(1),
+        ..Config::default()
+    };
+    let mut chain = Blockchain::new(config);
+    while chain.len() < len {
+        chain.submit_transaction(Transaction::new_transfer("alice".to_string(), "bob".to_string(), 1));
+        while !chain.try_mine_block("miner1") {}
+    }
+    chain
+}
+
+fn block_hashing(c: &mut Criterion) {
+    let mut group = c.benchmark_group("block_hash");
+    for transfers in [0, 10, 100] {
+        let block = block_with(transfers);
+        group.bench_with_input(BenchmarkId::from_parameter(transfers), &block, |b, block| {
+            b.iter(|| black_box(block).hash())
+        });
+    }
+    group.finish();
+}
+
+// How long finding one valid nonce takes with each way of picking candidates
+// (the simulator uses random nonces, sequential is the usual alternative)
+fn nonce_search(c: &mut Criterion) {
+    let mut group = c.benchmark_group("nonce_search");
+    let mut rng = RngHandle::seeded(7);
+    group.bench_function("random", |b| {
+        b.iter_batched(
+            || block_with(10),
+            |mut block| while !block.try_nonce(SEARCH_CEILING, &mut rng) {},
+            BatchSize::SmallInput,
+        )
+    });
+
+    let mut start = 0u64;
+    group.bench_function("sequential", |b| {
+        b.iter_batched(
+            || block_with(10),
+            |mut block| {
+                // Carry on from where the last search stopped so every run sees new nonces
+                block.nonce = start;
+                while !block.meets_ceiling(SEARCH_CEILING) {
+                    block.nonce += 1;
+                }
+                start = block.nonce + 1;
+            },
+            BatchSize::SmallInput,
+        )
+    });
+    group.finish();
+}
+
+fn chain_validation(c: &mut Criterion) {
+    let mut group = c.benchmark_group("chain_is_valid");
+    for len in [10, 100, 1000] {
+        let chain = chain_of(len);
+        group.bench_with_input(BenchmarkId::from_parameter(len), &chain, |b, chain| {
+            b.iter(|| black_box(chain).is_valid())
+        });
+    }
+    group.finish();
+}
+
+criterion_group!(benches, block_hashing, nonce_search, chain_validation);
+criterion_main!(benches);
@@ -0,0 +1,40 @@
+// Mines a few blocks through the C API and prints them as JSON
+//
+// Build the library, then compile and run from proof-of-work-sim/:
+//   cargo build --release --features ffi
+//   cc examples/ffi_demo.c -Iinclude -L../target/release -lproof_of_work_sim -o ffi_demo
+//   LD_LIBRARY_PATH=../target/release ./ffi_demo
+
+#include <stdio.h>
+#include <stdlib.h>
+#include "pow_sim.h"
+
+int main(void) {
+    PowChain *chain = pow_chain_new(0x0FFFFFFF);
+    if (chain == NULL) {
+        fprintf(stderr, "invalid ceiling\n");
+        return 1;
+    }
+
+    pow_chain_submit_transaction(chain, "alice", "bob", 5);
+    for (int i = 0; i < 3; i++) {
+        int64_t attempts = pow_chain_mine(chain, "c-demo", 1000000);
+        if (attempts < 0) {
+            fprintf(stderr, "mining failed: %lld\n", (long long)attempts);
+            break;
+        }
+        printf("mined block %zu in %lld attempts\n", pow_chain_len(chain) - 1, (long long)attempts);
+    }
+
+    // Ask for the JSON size first, then fetch the latest block
+    size_t last = pow_chain_len(chain) - 1;
+    int64_t len = pow_chain_block_json(chain, last, NULL, 0);
+    char *json = malloc((size_t)len + 1);
+    pow_chain_block_json(chain, last, json, (size_t)len + 1);
+    printf("latest block: %s\n", json);
+    printf("chain valid: %s\n", pow_chain_validate(chain) ? "yes" : "no");
+
+    free(json);
+    pow_chain_free(chain);
+    return 0;
+}
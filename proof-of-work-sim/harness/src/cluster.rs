@@ -0,0 +1,279 @@
+use std::fs::{self, File};
+use std::path::{Path, PathBuf};
+use std::process::{Child, Command, Stdio};
+use std::thread;
+use std::time::{Duration, Instant};
+use proof_of_work_sim::blockchain::Blockchain;
+use proof_of_work_sim::config::Config;
+use proof_of_work_sim::inbox;
+use proof_of_work_sim::transaction::Transaction;
+use sandbox_store::Store;
+use tempfile::TempDir;
+use crate::error::HarnessError;
+
+/// How often `wait_until` re-reads the saved chains
+const POLL_INTERVAL: Duration = Duration::from_millis(50);
+
+/// Settings for a cluster of simulator processes
+pub struct ClusterBuilder {
+    binary: PathBuf,
+    nodes: usize,
+    ceiling: i32,
+    delay_seconds: u64,
+}
+
+impl ClusterBuilder {
+    /// Number of node processes to start (default 2)
+    pub fn nodes(mut self, nodes: usize) -> Self {
+        self.nodes = nodes;
+        self
+    }
+
+    /// Proof-of-work ceiling every node mines against (default: almost always mines)
+    pub fn ceiling(mut self, ceiling: i32) -> Self {
+        self.ceiling = ceiling;
+        self
+    }
+
+    /// Pause between blocks (default 0, mine as fast as possible)
+    pub fn delay_seconds(mut self, delay_seconds: u64) -> Self {
+        self.delay_seconds = delay_seconds;
+        self
+    }
+
+    /// Start every node in its own data directory under a temp directory
+    pub fn spawn(self) -> Result<Cluster, HarnessError> {
+        let dir = tempfile::tempdir().map_err(|source| HarnessError::Spawn {
+            binary: self.binary.clone(),
+            source,
+        })?;
+
+        let mut nodes = Vec::with_capacity(self.nodes);
+        for i in 0..self.nodes {
+            let mut node = NodeProcess {
+                name: format!("node-{}", i),
+                dir: dir.path().join(format!("node-{}", i)),
+                binary: self.binary.clone(),
+                config: Config::new(self.ceiling, self.delay_seconds),
+                child: None,
+            };
+            node.start()?;
+            nodes.push(node);
+        }
+
+        Ok(Cluster { nodes, _dir: dir })
+    }
+}
+
+/// A group of running simulator processes
+/// Every process is killed when the cluster is dropped
+pub struct Cluster {
+    nodes: Vec<NodeProcess>,
+    // Removed on drop, after the nodes (fields drop in order)
+    _dir: TempDir,
+}
+
+impl Cluster {
+    /// Start configuring a cluster that runs the simulator at `binary`
+    /// (in integration tests: `env!("CARGO_BIN_EXE_proof-of-work-sim")`)
+    pub fn builder(binary: impl Into<PathBuf>) -> ClusterBuilder {
+        ClusterBuilder {
+            binary: binary.into(),
+            nodes: 2,
+            ceiling: i32::MAX,
+            delay_seconds: 0,
+        }
+    }
+
+    pub fn nodes(&self) -> &[NodeProcess] {
+        &self.nodes
+    }
+
+    pub fn node_mut(&mut self, index: usize) -> &mut NodeProcess {
+        &mut self.nodes[index]
+    }
+
+    /// The chain each node has saved so far, in node order
+    pub fn chains(&self) -> Result<Vec<Blockchain>, HarnessError> {
+        self.nodes.iter().map(NodeProcess::chain).collect()
+    }
+
+    /// Poll the saved chains until `condition` holds for them
+    /// Fails if a running node exits or `timeout` passes first
+    pub fn wait_until<F>(&mut self, timeout: Duration, mut condition: F) -> Result<Vec<Blockchain>, HarnessError>
+    where
+        F: FnMut(&[Blockchain]) -> bool,
+    {
+        let start = Instant::now();
+        loop {
+            for node in &mut self.nodes {
+                node.check_running()?;
+            }
+            let chains = self.chains()?;
+            if condition(&chains) {
+                return Ok(chains);
+            }
+            if start.elapsed() >= timeout {
+                return Err(HarnessError::Timeout(timeout));
+            }
+            thread::sleep(POLL_INTERVAL);
+        }
+    }
+
+    /// Wait until every node has saved at least `len` blocks (genesis included)
+    pub fn wait_for_len(&mut self, len: usize, timeout: Duration) -> Result<Vec<Blockchain>, HarnessError> {
+        self.wait_until(timeout, |chains| chains.iter().all(|chain| chain.len() >= len))
+    }
+
+    /// Kill every node and return the chains they left behind
+    pub fn stop(mut self) -> Result<Vec<Blockchain>, HarnessError> {
+        for node in &mut self.nodes {
+            node.kill();
+        }
+        self.chains()
+    }
+}
+
+/// One simulator process and the data directory it saves its chain in
+pub struct NodeProcess {
+    name: String,
+    dir: PathBuf,
+    binary: PathBuf,
+    config: Config,
+    // None while the node is stopped
+    child: Option<Child>,
+}
+
+impl NodeProcess {
+    /// Name used for the node's directory and in errors (e.g. "node-0")
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn data_dir(&self) -> PathBuf {
+        self.dir.join("data")
+    }
+
+    pub fn is_running(&self) -> bool {
+        self.child.is_some()
+    }
+
+    /// Kill the process without warning (like a crash or power loss)
+    pub fn kill(&mut self) {
+        if let Some(mut child) = self.child.take() {
+            // Errors mean it already exited, which is what we want
+            let _ = child.kill();
+            let _ = child.wait();
+        }
+    }
+
+    /// Start the process again on the same data directory
+    pub fn restart(&mut self) -> Result<(), HarnessError> {
+        self.kill();
+        self.start()
+    }
+
+    /// The chain the node has saved so far (genesis only before the first save)
+    pub fn chain(&self) -> Result<Blockchain, HarnessError> {
+        let store_error = |source| HarnessError::Store {
+            node: self.name.clone(),
+            source,
+        };
+        let store = Store::open(self.data_dir()).map_err(store_error)?;
+        Blockchain::load(&store, self.config.clone()).map_err(store_error)
+    }
+
+    /// Hand a transaction to the running node through its inbox
+    pub fn submit_transaction(&self, tx: &Transaction) -> Result<(), HarnessError> {
+        let store_error = |source| HarnessError::Store {
+            node: self.name.clone(),
+            source,
+        };
+        let store = Store::open(self.data_dir()).map_err(store_error)?;
+        inbox::submit(&store, tx).map_err(store_error)?;
+        Ok(())
+    }
+
+    fn start(&mut self) -> Result<(), HarnessError> {
+        let spawn_error = |source| HarnessError::Spawn {
+            binary: self.binary.clone(),
+            source,
+        };
+        fs::create_dir_all(&self.dir).map_err(spawn_error)?;
+        let stderr = File::create(self.stderr_path()).map_err(spawn_error)?;
+
+        // Run inside the node directory so a stray pow-sim.toml isn't picked up
+        let child = Command::new(&self.binary)
+            .args(node_args(&self.config, &self.data_dir(), &self.dir.join("logs")))
+            .current_dir(&self.dir)
+            .stdin(Stdio::null())
+            .stdout(Stdio::null())
+            .stderr(stderr)
+            .spawn()
+            .map_err(spawn_error)?;
+        self.child = Some(child);
+        Ok(())
+    }
+
+    /// Fail with the node's stderr if it was running and has exited
+    fn check_running(&mut self) -> Result<(), HarnessError> {
+        let Some(child) = &mut self.child else {
+            return Ok(());
+        };
+        let status = match child.try_wait() {
+            Ok(Some(status)) => status,
+            // Still running (or can't tell, then the next poll will)
+            _ => return Ok(()),
+        };
+        self.child = None;
+        Err(HarnessError::NodeExited {
+            node: self.name.clone(),
+            status,
+            stderr: fs::read_to_string(self.stderr_path()).unwrap_or_default(),
+        })
+    }
+
+    fn stderr_path(&self) -> PathBuf {
+        self.dir.join("stderr.log")
+    }
+}
+
+impl Drop for NodeProcess {
+    fn drop(&mut self) {
+        self.kill();
+    }
+}
+
+/// Command-line flags that configure one node
+fn node_args(config: &Config, data_dir: &Path, log_dir: &Path) -> Vec<String> {
+    vec![
+        "--ceiling".to_string(),
+        config.ceiling.to_string(),
+        "--delay-seconds".to_string(),
+        config.delay_seconds.to_string(),
+        "--data-dir".to_string(),
+        data_dir.display().to_string(),
+        "--log-dir".to_string(),
+        log_dir.display().to_string(),
+    ]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_node_args() {
+        let args = node_args(&Config::new(100, 2), Path::new("/tmp/data"), Path::new("/tmp/logs"));
+        assert_eq!(
+            args,
+            ["--ceiling", "100", "--delay-seconds", "2", "--data-dir", "/tmp/data", "--log-dir", "/tmp/logs"]
+        );
+    }
+
+    #[test]
+    fn test_missing_binary() {
+        let result = Cluster::builder("/nonexistent/pow-sim").nodes(1).spawn();
+        assert!(matches!(result, Err(HarnessError::Spawn { .. })));
+    }
+}
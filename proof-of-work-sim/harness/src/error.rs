@@ -0,0 +1,52 @@
+use std::fmt;
+use std::io;
+use std::path::PathBuf;
+use std::process::ExitStatus;
+use std::time::Duration;
+use sandbox_store::StoreError;
+
+/// Errors from spawning nodes or waiting on a scenario
+#[derive(Debug)]
+pub enum HarnessError {
+    /// The node binary couldn't be started (or its directories created)
+    Spawn { binary: PathBuf, source: io::Error },
+    /// A node's saved chain couldn't be read (or its inbox written)
+    Store { node: String, source: StoreError },
+    /// A node process stopped on its own
+    NodeExited { node: String, status: ExitStatus, stderr: String },
+    /// The scenario's condition wasn't met in time
+    Timeout(Duration),
+}
+
+impl fmt::Display for HarnessError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            HarnessError::Spawn { binary, source } => {
+                write!(f, "failed to start {}: {}", binary.display(), source)
+            }
+            HarnessError::Store { node, source } => {
+                write!(f, "failed to read the chain of {}: {}", node, source)
+            }
+            HarnessError::NodeExited { node, status, stderr } => {
+                write!(f, "{} exited with {}", node, status)?;
+                if !stderr.trim().is_empty() {
+                    write!(f, ":\n{}", stderr.trim_end())?;
+                }
+                Ok(())
+            }
+            HarnessError::Timeout(waited) => {
+                write!(f, "condition not met after {:?}", waited)
+            }
+        }
+    }
+}
+
+impl std::error::Error for HarnessError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            HarnessError::Spawn { source, .. } => Some(source),
+            HarnessError::Store { source, .. } => Some(source),
+            _ => None,
+        }
+    }
+}
@@ -0,0 +1,20 @@
+//! End-to-end test harness for the proof-of-work simulator
+//!
+//! Spawns real `proof-of-work-sim` processes, each with its own data
+//! directory, and reads back the chains they save after every mined block.
+//! Scenarios drive the nodes (start, kill, restart) and then assert on the
+//! resulting chains, which unit tests inside one process can't cover:
+//! configuration from the command line, saving while being killed, and
+//! resuming a saved chain on restart.
+//!
+//! The simulator has no networking mode yet, so nodes mine independently.
+//! Once it does, connecting nodes belongs in [`ClusterBuilder::spawn`] and
+//! sync, reorg, and partition scenarios can be written against [`Cluster`].
+
+// Module declarations
+mod cluster;
+mod error;
+
+// Re-exports for convenience
+pub use cluster::{Cluster, ClusterBuilder, NodeProcess};
+pub use error::HarnessError;
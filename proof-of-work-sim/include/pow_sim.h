@@ -0,0 +1,88 @@
+#ifndef POW_SIM_H
+#define POW_SIM_H
+
+/* Generated with cbindgen from src/ffi.rs, do not edit by hand. */
+
+#include <stdarg.h>
+#include <stdbool.h>
+#include <stddef.h>
+#include <stdint.h>
+#include <stdlib.h>
+
+// Success
+#define POW_OK 0
+
+// A pointer was NULL or a string was not valid UTF-8
+#define POW_ERR_INVALID_ARG -1
+
+// No block could be mined within the attempt budget
+#define POW_ERR_BUDGET_EXHAUSTED -2
+
+// There is no block at the requested index
+#define POW_ERR_NOT_FOUND -3
+
+// Opaque chain handle for C callers
+typedef struct PowChain PowChain;
+
+#ifdef __cplusplus
+extern "C" {
+#endif // __cplusplus
+
+// Create a new chain (genesis only) with the given proof-of-work ceiling
+// Returns NULL if `ceiling` is zero or negative
+struct PowChain *pow_chain_new(int32_t ceiling);
+
+// Release a chain created by `pow_chain_new` (NULL is ignored)
+//
+// # Safety
+// `chain` must be NULL or a pointer from `pow_chain_new` that hasn't been freed
+void pow_chain_free(struct PowChain *chain);
+
+// Number of blocks in the chain (including genesis), 0 for NULL
+//
+// # Safety
+// `chain` must be NULL or a live pointer from `pow_chain_new`
+size_t pow_chain_len(const struct PowChain *chain);
+
+// Try to mine one block, trying at most `max_attempts` nonces
+// Returns the number of attempts used, or a negative `POW_ERR_*` code
+//
+// # Safety
+// `chain` must be NULL or a live pointer from `pow_chain_new`,
+// `miner_id` must be NULL or a NUL-terminated string
+int64_t pow_chain_mine(struct PowChain *chain, const char *miner_id, uint64_t max_attempts);
+
+// Queue a transfer for the next mined block
+// Returns `POW_OK` or `POW_ERR_INVALID_ARG`
+//
+// # Safety
+// `chain` must be NULL or a live pointer from `pow_chain_new`,
+// `sender` and `recipient` must be NULL or NUL-terminated strings
+int64_t pow_chain_submit_transaction(struct PowChain *chain,
+                                     const char *sender,
+                                     const char *recipient,
+                                     uint64_t amount);
+
+// Check links and proof-of-work for the whole chain (false for NULL)
+//
+// # Safety
+// `chain` must be NULL or a live pointer from `pow_chain_new`
+bool pow_chain_validate(const struct PowChain *chain);
+
+// Write the block at `index` as NUL-terminated JSON into `buf`
+//
+// Works like `snprintf`: returns the JSON length (without the NUL) even if
+// it didn't fit, so passing `buf = NULL, buf_len = 0` asks for the size.
+// Output that doesn't fit is not written at all. Returns a negative
+// `POW_ERR_*` code if there is no such block or `chain` is NULL.
+//
+// # Safety
+// `chain` must be NULL or a live pointer from `pow_chain_new`,
+// `buf` must be NULL or point to at least `buf_len` writable bytes
+int64_t pow_chain_block_json(const struct PowChain *chain, size_t index, char *buf, size_t buf_len);
+
+#ifdef __cplusplus
+}  // extern "C"
+#endif  // __cplusplus
+
+#endif  /* POW_SIM_H */
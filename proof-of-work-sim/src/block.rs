@@ -1,10 +1,12 @@
-use rand::Rng;
+use sandbox_rng::{Rng, RngHandle};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
 use sha2::{Digest, Sha256};
 use crate::traits::{Hashable, Validatable};
 use crate::transaction::Transaction;
 
 /// Represents a block in the blockchain
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct Block {
     /// Block index
     pub index: u64,
@@ -42,27 +44,28 @@ impl Block {
         }
     }
 
-    /// Try a random nonce for proof-of-work
+    /// Try a random nonce (drawn from `rng`) for proof-of-work
     /// Returns true if the nonce produces a valid hash
-    pub fn try_nonce(&mut self, ceiling: i32) -> bool {
-        // Generate random nonce
-        let mut rng = rand::thread_rng();
+    pub fn try_nonce(&mut self, ceiling: i32, rng: &mut RngHandle) -> bool {
         self.nonce = rng.gen();
 
-        // Calculate hash with this nonce
-        let hash = self.hash();
-        
-        // Convert first 8 hex chars to i32 for comparison
-        let hash_value = i32::from_str_radix(&hash[..8], 16).unwrap_or(i32::MAX);
-        
-        // Check if hash is below ceiling
-        if hash_value < ceiling {
+        // Check if the hash with this nonce is below ceiling
+        if self.meets_ceiling(ceiling) {
             self.is_valid = true;
             true
         } else {
             false
         }
     }
+
+    /// Check whether the block's current hash is below the ceiling
+    pub fn meets_ceiling(&self, ceiling: i32) -> bool {
+        let hash = self.hash();
+
+        // Convert first 8 hex chars to i32 for comparison
+        let hash_value = i32::from_str_radix(&hash[..8], 16).unwrap_or(i32::MAX);
+        hash_value < ceiling
+    }
 }
 
 impl Hashable for Block {
@@ -128,9 +131,10 @@ mod tests {
         let mut block = Block::new(1, vec![tx], "prev_hash".to_string());
         
         // With i32::MAX ceiling, should succeed within a few tries
+        let mut rng = RngHandle::seeded(1);
         let mut success = false;
         for _ in 0..100 {
-            if block.try_nonce(i32::MAX) {
+            if block.try_nonce(i32::MAX, &mut rng) {
                 success = true;
                 break;
             }
@@ -145,7 +149,7 @@ mod tests {
         let mut block = Block::new(1, vec![tx], "prev_hash".to_string());
         
         // With 0 ceiling, should always fail
-        let result = block.try_nonce(0);
+        let result = block.try_nonce(0, &mut RngHandle::seeded(1));
         assert!(!result);
         assert!(!block.is_valid);
     }
@@ -1,7 +1,26 @@
+use sandbox_rng::RngHandle;
+use sandbox_store::{Schema, Store, StoreError};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
 use crate::block::Block;
 use crate::config::Config;
 use crate::transaction::Transaction;
-use crate::traits::Hashable;
+use crate::traits::{Hashable, Validatable};
+
+/// Store key the chain is saved under
+pub const CHAIN_KEY: &str = "chain";
+
+/// Stored form of the chain (the config comes from the running app)
+#[derive(Serialize, Deserialize, JsonSchema)]
+pub(crate) struct ChainSnapshot {
+    blocks: Vec<Block>,
+    #[serde(default)]
+    mempool: Vec<Transaction>,
+}
+
+impl Schema for ChainSnapshot {
+    const VERSION: u32 = 1;
+}
 
 /// Represents a blockchain (chain of blocks)
 pub struct Blockchain {
@@ -9,6 +28,10 @@ pub struct Blockchain {
     pub blocks: Vec<Block>,
     /// Configuration
     pub config: Config,
+    /// Submitted transactions waiting to be mined
+    pub mempool: Vec<Transaction>,
+    /// Where nonces come from (seeded by the config, see `Config::seed`)
+    pub rng: RngHandle,
 }
 
 impl Blockchain {
@@ -17,10 +40,17 @@ impl Blockchain {
         let genesis = Block::genesis();
         Blockchain {
             blocks: vec![genesis],
+            rng: config.rng(),
             config,
+            mempool: Vec::new(),
         }
     }
 
+    /// Submit a transaction to be included in the next mined block
+    pub fn submit_transaction(&mut self, tx: Transaction) {
+        self.mempool.push(tx);
+    }
+
     /// Get the latest block
     pub fn latest_block(&self) -> &Block {
         self.blocks.last().unwrap()
@@ -29,23 +59,32 @@ impl Blockchain {
     /// Try to mine a new block
     /// Returns true if a block was successfully mined and added
     pub fn try_mine_block(&mut self, miner_id: &str) -> bool {
-        // Create new block with coinbase transaction
+        // Create new block with coinbase transaction plus the mempool
         let coinbase = Transaction::new_coinbase(miner_id.to_string());
         let prev_hash = self.latest_block().hash();
         let index = self.blocks.len() as u64;
+        let mut transactions = vec![coinbase];
+        transactions.extend(self.mempool.iter().cloned());
         
-        let mut new_block = Block::new(index, vec![coinbase], prev_hash);
+        let mut new_block = Block::new(index, transactions, prev_hash);
         
         // Try to find a valid nonce
-        if new_block.try_nonce(self.config.ceiling) {
-            // Valid nonce found! Add block to chain
+        if new_block.try_nonce(self.config.ceiling, &mut self.rng) {
+            // Valid nonce found! Add block to chain, its transactions are now mined
             self.blocks.push(new_block);
+            self.mempool.clear();
             true
         } else {
             false
         }
     }
 
+    /// Try to mine a new block, giving up after `max_attempts` nonces
+    /// Returns the number of attempts used if a block was mined
+    pub fn mine_block_with_budget(&mut self, miner_id: &str, max_attempts: u64) -> Option<u64> {
+        (1..=max_attempts).find(|_| self.try_mine_block(miner_id))
+    }
+
     /// Get the last N blocks
     pub fn last_n_blocks(&self, n: usize) -> Vec<&Block> {
         let start = if self.blocks.len() > n {
@@ -65,6 +104,51 @@ impl Blockchain {
     pub fn is_empty(&self) -> bool {
         self.blocks.is_empty()
     }
+
+    /// Save the chain to a store
+    pub fn save(&self, store: &Store) -> Result<(), StoreError> {
+        let snapshot = ChainSnapshot {
+            blocks: self.blocks.clone(),
+            mempool: self.mempool.clone(),
+        };
+        store.save(CHAIN_KEY, &snapshot)
+    }
+
+    /// Load the chain from a store
+    /// Starts a fresh chain (genesis only) if nothing was saved yet
+    pub fn load(store: &Store, config: Config) -> Result<Self, StoreError> {
+        match store.load::<ChainSnapshot>(CHAIN_KEY)? {
+            Some(snapshot) if !snapshot.blocks.is_empty() => Ok(Blockchain {
+                blocks: snapshot.blocks,
+                rng: config.rng(),
+                config,
+                mempool: snapshot.mempool,
+            }),
+            _ => Ok(Blockchain::new(config)),
+        }
+    }
+}
+
+impl Validatable for Blockchain {
+    /// A chain is valid when it starts with the genesis block, every block
+    /// links to the hash of the one before it, and every mined block's hash
+    /// is below the configured ceiling
+    fn is_valid(&self) -> bool {
+        let genesis = match self.blocks.first() {
+            Some(block) => block,
+            None => return false,
+        };
+        if genesis.index != 0 || genesis.prev_hash != Block::genesis().prev_hash {
+            return false;
+        }
+
+        self.blocks.windows(2).all(|pair| {
+            let (prev, block) = (&pair[0], &pair[1]);
+            block.index == prev.index + 1
+                && block.prev_hash == prev.hash()
+                && block.meets_ceiling(self.config.ceiling)
+        })
+    }
 }
 
 #[cfg(test)]
@@ -153,6 +237,102 @@ mod tests {
         assert_eq!(last_10.len(), 1); // Only genesis block
     }
 
+    #[test]
+    fn test_mempool_is_mined() {
+        let mut blockchain = Blockchain::new(Config::default());
+        blockchain.submit_transaction(Transaction::new_transfer(
+            "alice".to_string(),
+            "bob".to_string(),
+            10,
+        ));
+        assert_eq!(blockchain.mempool.len(), 1);
+
+        while !blockchain.try_mine_block("miner1") {}
+        let block = blockchain.latest_block();
+        assert_eq!(block.transactions.len(), 2); // Coinbase + transfer
+        assert!(block.transactions[0].is_coinbase());
+        assert!(blockchain.mempool.is_empty());
+    }
+
+    #[test]
+    fn test_mine_block_with_budget() {
+        let mut blockchain = Blockchain::new(Config::default());
+        let attempts = blockchain.mine_block_with_budget("miner1", 100);
+        assert!(attempts.is_some());
+        assert_eq!(blockchain.len(), 2);
+    }
+
+    #[test]
+    fn test_mine_block_with_budget_exhausted() {
+        let mut blockchain = Blockchain::new(Config::new(0, 1)); // Impossible ceiling
+        assert_eq!(blockchain.mine_block_with_budget("miner1", 10), None);
+        assert_eq!(blockchain.len(), 1);
+    }
+
+    #[test]
+    fn test_valid_chain() {
+        let mut blockchain = Blockchain::new(Config::default());
+        for _ in 0..3 {
+            while !blockchain.try_mine_block("miner1") {}
+        }
+        assert!(blockchain.is_valid());
+    }
+
+    #[test]
+    fn test_tampered_chain_is_invalid() {
+        let mut blockchain = Blockchain::new(Config::default());
+        for _ in 0..3 {
+            while !blockchain.try_mine_block("miner1") {}
+        }
+        blockchain.blocks[1].transactions[0].recipient = "attacker".to_string();
+        assert!(!blockchain.is_valid()); // Block 2 no longer links to block 1
+    }
+
+    #[test]
+    fn test_save_and_load() {
+        let dir = std::env::temp_dir().join(format!("pow-sim-chain-{}", std::process::id()));
+        let store = Store::open(&dir).unwrap();
+
+        let mut blockchain = Blockchain::new(Config::default());
+        while !blockchain.try_mine_block("miner1") {}
+        blockchain.save(&store).unwrap();
+
+        let loaded = Blockchain::load(&store, Config::default()).unwrap();
+        assert_eq!(loaded.len(), 2);
+        assert_eq!(loaded.latest_block().hash(), blockchain.latest_block().hash());
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_damaged_save_loads_previous_chain() {
+        let dir = std::env::temp_dir().join(format!("pow-sim-damaged-{}", std::process::id()));
+        let store = Store::open(&dir).unwrap();
+
+        let mut blockchain = Blockchain::new(Config::default());
+        while !blockchain.try_mine_block("miner1") {}
+        blockchain.save(&store).unwrap();
+        while !blockchain.try_mine_block("miner1") {}
+        blockchain.save(&store).unwrap();
+        std::fs::write(dir.join("chain.json"), "{\"version\": 1, \"data\": {\"blo").unwrap();
+
+        let loaded = Blockchain::load(&store, Config::default()).unwrap();
+        assert_eq!(loaded.len(), 2); // The save before the damaged one
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_load_empty_store() {
+        let dir = std::env::temp_dir().join(format!("pow-sim-empty-{}", std::process::id()));
+        let store = Store::open(&dir).unwrap();
+
+        let blockchain = Blockchain::load(&store, Config::default()).unwrap();
+        assert_eq!(blockchain.len(), 1); // Fresh chain with genesis
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
     #[test]
     fn test_mine_with_impossible_ceiling() {
         let config = Config::new(0, 1); // Impossible ceiling
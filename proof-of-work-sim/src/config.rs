@@ -1,37 +1,84 @@
+use sandbox_config::Validate;
+use sandbox_rng::RngHandle;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
+
 /// Global configuration for the blockchain simulator
-#[derive(Debug, Clone)]
+/// Loaded from `pow-sim.toml`, `POW_SIM_*` env vars, and `--key value` flags
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+#[serde(default, deny_unknown_fields)]
 pub struct Config {
     /// Ceiling value for proof-of-work (hash must be below this)
     pub ceiling: i32,
     /// Delay after mining a block (in seconds)
     pub delay_seconds: u64,
+    /// Directory to save the chain in (None keeps it in memory only)
+    pub data_dir: Option<PathBuf>,
+    /// Directory for the rotating log files
+    pub log_dir: PathBuf,
+    /// Seed for nonces and the node id, to replay a run exactly
+    /// (None follows the SANDBOX_SEED env var, or is random)
+    pub seed: Option<u64>,
 }
 
-impl Config {
-    pub fn default() -> Self {
+impl Default for Config {
+    fn default() -> Self {
         Config {
             ceiling: i32::MAX, // Default: almost always mine successfully
             delay_seconds: 1,
+            data_dir: None,
+            log_dir: PathBuf::from("logs"),
+            seed: None,
         }
     }
+}
 
+impl Config {
     pub fn new(ceiling: i32, delay_seconds: u64) -> Self {
         Config {
             ceiling,
             delay_seconds,
+            data_dir: None,
+            log_dir: PathBuf::from("logs"),
+            seed: None,
+        }
+    }
+
+    /// The random number generator this config asks for
+    pub fn rng(&self) -> RngHandle {
+        self.seed.map(RngHandle::seeded).unwrap_or_default()
+    }
+}
+
+impl Validate for Config {
+    fn validate(&self) -> Result<(), String> {
+        if self.ceiling <= 0 {
+            return Err(format!("ceiling must be positive (got {}), no block could ever be mined", self.ceiling));
         }
+        Ok(())
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use sandbox_config::{ConfigError, ConfigLoader};
+
+    /// Loader that ignores the process environment and any pow-sim.toml
+    fn loader() -> ConfigLoader {
+        ConfigLoader::new("pow-sim")
+            .file("/dev/null")
+            .env_vars(Vec::<(String, String)>::new())
+    }
 
     #[test]
     fn test_config_default() {
         let config = Config::default();
         assert_eq!(config.ceiling, i32::MAX);
         assert_eq!(config.delay_seconds, 1);
+        assert!(config.data_dir.is_none());
+        assert_eq!(config.log_dir, PathBuf::from("logs"));
     }
 
     #[test]
@@ -40,4 +87,22 @@ mod tests {
         assert_eq!(config.ceiling, 1000);
         assert_eq!(config.delay_seconds, 5);
     }
+
+    #[test]
+    fn test_config_load_overrides() {
+        let config: Config = loader()
+            .env_vars([("POW_SIM_DATA_DIR", "/tmp/chain")])
+            .args(["--ceiling", "1000", "--delay-seconds=0"])
+            .load()
+            .unwrap();
+        assert_eq!(config.ceiling, 1000);
+        assert_eq!(config.delay_seconds, 0);
+        assert_eq!(config.data_dir, Some(PathBuf::from("/tmp/chain")));
+    }
+
+    #[test]
+    fn test_config_rejects_bad_ceiling() {
+        let result: Result<Config, _> = loader().args(["--ceiling", "0"]).load();
+        assert!(matches!(result, Err(ConfigError::Validation(_))));
+    }
 }
@@ -0,0 +1,19 @@
+//! What a mining node tells its plugins
+//!
+//! A [`Node`](crate::node::Node) sends these through its `plugins` host (see
+//! sandbox-plugin), so extensions like metrics sinks or extra storage can
+//! follow the chain without touching the mining loop.
+
+use crate::block::Block;
+use crate::transaction::Transaction;
+
+/// Something that happened on a node
+#[derive(Debug, Clone)]
+pub enum NodeEvent {
+    /// The node started mining on top of a chain of `height` blocks
+    Started { node_id: String, height: usize },
+    /// A transaction arrived through the inbox and went into the mempool
+    TransactionReceived { node_id: String, transaction: Transaction },
+    /// The node mined `block` (already saved, if the node has a store)
+    BlockMined { node_id: String, block: Block },
+}
@@ -0,0 +1,220 @@
+//! C bindings for embedding the simulator in non-Rust tools
+//!
+//! The header is `include/pow_sim.h`, generated from this file with cbindgen.
+//! Every chain returned by `pow_chain_new` must be released with
+//! `pow_chain_free`. Strings passed in must be NUL-terminated UTF-8.
+
+use std::ffi::{c_char, CStr};
+use std::ptr;
+use crate::blockchain::Blockchain;
+use crate::config::Config;
+use crate::transaction::Transaction;
+use crate::traits::Validatable;
+
+/// Success
+pub const POW_OK: i64 = 0;
+/// A pointer was NULL or a string was not valid UTF-8
+pub const POW_ERR_INVALID_ARG: i64 = -1;
+/// No block could be mined within the attempt budget
+pub const POW_ERR_BUDGET_EXHAUSTED: i64 = -2;
+/// There is no block at the requested index
+pub const POW_ERR_NOT_FOUND: i64 = -3;
+
+/// Opaque chain handle for C callers
+pub struct PowChain {
+    inner: Blockchain,
+}
+
+/// Read a C string argument, None if it's NULL or not UTF-8
+///
+/// # Safety
+/// `s` must be NULL or point to a NUL-terminated string
+unsafe fn read_str<'a>(s: *const c_char) -> Option<&'a str> {
+    if s.is_null() {
+        return None;
+    }
+    CStr::from_ptr(s).to_str().ok()
+}
+
+/// Create a new chain (genesis only) with the given proof-of-work ceiling
+/// Returns NULL if `ceiling` is zero or negative
+#[no_mangle]
+pub extern "C" fn pow_chain_new(ceiling: i32) -> *mut PowChain {
+    if ceiling <= 0 {
+        return ptr::null_mut();
+    }
+    // The caller drives its own timing, so the mining delay is unused
+    Box::into_raw(Box::new(PowChain {
+        inner: Blockchain::new(Config::new(ceiling, 0)),
+    }))
+}
+
+/// Release a chain created by `pow_chain_new` (NULL is ignored)
+///
+/// # Safety
+/// `chain` must be NULL or a pointer from `pow_chain_new` that hasn't been freed
+#[no_mangle]
+pub unsafe extern "C" fn pow_chain_free(chain: *mut PowChain) {
+    if !chain.is_null() {
+        drop(Box::from_raw(chain));
+    }
+}
+
+/// Number of blocks in the chain (including genesis), 0 for NULL
+///
+/// # Safety
+/// `chain` must be NULL or a live pointer from `pow_chain_new`
+#[no_mangle]
+pub unsafe extern "C" fn pow_chain_len(chain: *const PowChain) -> usize {
+    chain.as_ref().map_or(0, |chain| chain.inner.len())
+}
+
+/// Try to mine one block, trying at most `max_attempts` nonces
+/// Returns the number of attempts used, or a negative `POW_ERR_*` code
+///
+/// # Safety
+/// `chain` must be NULL or a live pointer from `pow_chain_new`,
+/// `miner_id` must be NULL or a NUL-terminated string
+#[no_mangle]
+pub unsafe extern "C" fn pow_chain_mine(
+    chain: *mut PowChain,
+    miner_id: *const c_char,
+    max_attempts: u64,
+) -> i64 {
+    let (Some(chain), Some(miner_id)) = (chain.as_mut(), read_str(miner_id)) else {
+        return POW_ERR_INVALID_ARG;
+    };
+    match chain.inner.mine_block_with_budget(miner_id, max_attempts) {
+        Some(attempts) => attempts.min(i64::MAX as u64) as i64,
+        None => POW_ERR_BUDGET_EXHAUSTED,
+    }
+}
+
+/// Queue a transfer for the next mined block
+/// Returns `POW_OK` or `POW_ERR_INVALID_ARG`
+///
+/// # Safety
+/// `chain` must be NULL or a live pointer from `pow_chain_new`,
+/// `sender` and `recipient` must be NULL or NUL-terminated strings
+#[no_mangle]
+pub unsafe extern "C" fn pow_chain_submit_transaction(
+    chain: *mut PowChain,
+    sender: *const c_char,
+    recipient: *const c_char,
+    amount: u64,
+) -> i64 {
+    let (Some(chain), Some(sender), Some(recipient)) =
+        (chain.as_mut(), read_str(sender), read_str(recipient))
+    else {
+        return POW_ERR_INVALID_ARG;
+    };
+    chain.inner.submit_transaction(Transaction::new_transfer(
+        sender.to_string(),
+        recipient.to_string(),
+        amount,
+    ));
+    POW_OK
+}
+
+/// Check links and proof-of-work for the whole chain (false for NULL)
+///
+/// # Safety
+/// `chain` must be NULL or a live pointer from `pow_chain_new`
+#[no_mangle]
+pub unsafe extern "C" fn pow_chain_validate(chain: *const PowChain) -> bool {
+    chain.as_ref().is_some_and(|chain| chain.inner.is_valid())
+}
+
+/// Write the block at `index` as NUL-terminated JSON into `buf`
+///
+/// Works like `snprintf`: returns the JSON length (without the NUL) even if
+/// it didn't fit, so passing `buf = NULL, buf_len = 0` asks for the size.
+/// Output that doesn't fit is not written at all. Returns a negative
+/// `POW_ERR_*` code if there is no such block or `chain` is NULL.
+///
+/// # Safety
+/// `chain` must be NULL or a live pointer from `pow_chain_new`,
+/// `buf` must be NULL or point to at least `buf_len` writable bytes
+#[no_mangle]
+pub unsafe extern "C" fn pow_chain_block_json(
+    chain: *const PowChain,
+    index: usize,
+    buf: *mut c_char,
+    buf_len: usize,
+) -> i64 {
+    let Some(chain) = chain.as_ref() else {
+        return POW_ERR_INVALID_ARG;
+    };
+    let Some(block) = chain.inner.blocks.get(index) else {
+        return POW_ERR_NOT_FOUND;
+    };
+
+    let json = serde_json::to_string(block).expect("blocks serialize to JSON");
+    if !buf.is_null() && json.len() < buf_len {
+        ptr::copy_nonoverlapping(json.as_ptr(), buf.cast::<u8>(), json.len());
+        *buf.add(json.len()) = 0;
+    }
+    json.len() as i64
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::ffi::CString;
+
+    #[test]
+    fn test_ffi_chain_lifecycle() {
+        let miner = CString::new("c-tool").unwrap();
+        let alice = CString::new("alice").unwrap();
+        let bob = CString::new("bob").unwrap();
+
+        unsafe {
+            let chain = pow_chain_new(i32::MAX);
+            assert!(!chain.is_null());
+            assert_eq!(pow_chain_submit_transaction(chain, alice.as_ptr(), bob.as_ptr(), 5), POW_OK);
+            assert!(pow_chain_mine(chain, miner.as_ptr(), 100) > 0);
+            assert_eq!(pow_chain_len(chain), 2);
+            assert!(pow_chain_validate(chain));
+            pow_chain_free(chain);
+        }
+    }
+
+    #[test]
+    fn test_ffi_invalid_args() {
+        unsafe {
+            assert!(pow_chain_new(0).is_null());
+            assert_eq!(pow_chain_len(ptr::null()), 0);
+            assert!(!pow_chain_validate(ptr::null()));
+
+            let chain = pow_chain_new(1);
+            assert_eq!(pow_chain_mine(chain, ptr::null(), 10), POW_ERR_INVALID_ARG);
+            let miner = CString::new("c-tool").unwrap();
+            assert_eq!(pow_chain_mine(chain, miner.as_ptr(), 5), POW_ERR_BUDGET_EXHAUSTED);
+            pow_chain_free(chain);
+            pow_chain_free(ptr::null_mut());
+        }
+    }
+
+    #[test]
+    fn test_ffi_block_json_buffer() {
+        unsafe {
+            let chain = pow_chain_new(i32::MAX);
+
+            // Ask for the size first, then fetch
+            let len = pow_chain_block_json(chain, 0, ptr::null_mut(), 0);
+            assert!(len > 0);
+            let mut buf = vec![0 as c_char; len as usize + 1];
+            assert_eq!(pow_chain_block_json(chain, 0, buf.as_mut_ptr(), buf.len()), len);
+            let json = CStr::from_ptr(buf.as_ptr()).to_str().unwrap();
+            assert!(json.starts_with("{\"index\":0"));
+
+            // Too small: nothing written, size still reported
+            let mut small = [7 as c_char; 4];
+            assert_eq!(pow_chain_block_json(chain, 0, small.as_mut_ptr(), small.len()), len);
+            assert_eq!(small, [7; 4]);
+
+            assert_eq!(pow_chain_block_json(chain, 5, ptr::null_mut(), 0), POW_ERR_NOT_FOUND);
+            pow_chain_free(chain);
+        }
+    }
+}
@@ -0,0 +1,77 @@
+use sandbox_rng::{Rng, RngHandle};
+use sandbox_store::{Schema, Store, StoreError};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use std::time::{SystemTime, UNIX_EPOCH};
+use crate::transaction::Transaction;
+
+/// Store keys of waiting transactions start with this
+pub const INBOX_PREFIX: &str = "inbox-";
+
+/// Stored form of one submitted transaction
+#[derive(Serialize, Deserialize, JsonSchema)]
+pub(crate) struct InboxEntry {
+    transaction: Transaction,
+}
+
+impl Schema for InboxEntry {
+    const VERSION: u32 = 1;
+}
+
+/// Hand a transaction to the node that saves its chain in `store`
+///
+/// Lets other programs on the same machine (like the hangman CLI) submit
+/// transactions to a running simulator: every submission is its own document,
+/// written atomically, so the node never sees half a transaction and two
+/// submitters can't overwrite each other. The node picks them up before it
+/// starts mining its next block. Returns the key the transaction was saved under.
+pub fn submit(store: &Store, tx: &Transaction) -> Result<String, StoreError> {
+    // Time first so keys sort in submission order, random suffix against collisions
+    // (from entropy: two submitters running with the same SANDBOX_SEED mustn't collide)
+    let nanos = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |since| since.as_nanos());
+    let key = format!("{}{:020}-{:04x}", INBOX_PREFIX, nanos, RngHandle::from_entropy().gen::<u16>());
+    store.save(
+        &key,
+        &InboxEntry {
+            transaction: tx.clone(),
+        },
+    )?;
+    Ok(key)
+}
+
+/// Take every waiting transaction out of the inbox, oldest first
+pub fn drain(store: &Store) -> Result<Vec<Transaction>, StoreError> {
+    let mut transactions = Vec::new();
+    for key in store.keys()?.into_iter().filter(|key| key.starts_with(INBOX_PREFIX)) {
+        if let Some(entry) = store.load::<InboxEntry>(&key)? {
+            transactions.push(entry.transaction);
+        }
+        store.remove(&key)?;
+    }
+    Ok(transactions)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_submit_and_drain_in_order() {
+        let dir = tempfile::tempdir().unwrap();
+        let store = Store::open(dir.path()).unwrap();
+        for amount in 1..=3 {
+            submit(&store, &Transaction::new_transfer("alice".to_string(), "bob".to_string(), amount)).unwrap();
+        }
+        store.save("chain-note", &InboxEntry {
+            transaction: Transaction::new_coinbase("not-inbox".to_string()),
+        }).unwrap();
+
+        let amounts: Vec<u64> = drain(&store).unwrap().iter().map(|tx| tx.amount).collect();
+        assert_eq!(amounts, [1, 2, 3]);
+        // Drained transactions are gone, other documents are left alone
+        assert!(drain(&store).unwrap().is_empty());
+        assert_eq!(store.keys().unwrap(), ["chain-note"]);
+    }
+}
@@ -0,0 +1,33 @@
+//! Proof-of-Work Blockchain Simulator
+//!
+//! The chain/mining core lives in this library so other crates in the
+//! sandbox can drive it; `main.rs` is the terminal simulator.
+
+// Module declarations
+pub mod config;
+pub mod traits;
+pub mod transaction;
+pub mod block;
+pub mod blockchain;
+pub mod events;
+pub mod inbox;
+pub mod node;
+pub mod schemas;
+
+// JavaScript bindings, built with `wasm-pack build --features wasm`
+#[cfg(feature = "wasm")]
+pub mod wasm;
+
+// Python bindings, built with `maturin develop --features python`
+#[cfg(feature = "python")]
+pub mod python;
+
+// C bindings, built with `cargo build --release --features ffi`
+#[cfg(feature = "ffi")]
+pub mod ffi;
+
+// Kotlin/Swift bindings, built with `cargo build --features uniffi`
+#[cfg(feature = "uniffi")]
+pub mod mobile;
+#[cfg(feature = "uniffi")]
+uniffi::setup_scaffolding!();
@@ -1,27 +1,76 @@
-// Module declarations
-mod config;
-mod traits;
-mod transaction;
-mod block;
-mod blockchain;
-mod node;
-
-// Re-exports for convenience
-use config::Config;
-use node::Node;
+// The simulator core lives in the library crate (src/lib.rs)
+use proof_of_work_sim::config::Config;
+use proof_of_work_sim::node::Node;
+use proof_of_work_sim::schemas::schemas;
+use sandbox_config::ConfigLoader;
+use sandbox_plugin::TracingPlugin;
+use sandbox_store::{export_schemas, Store};
+use sandbox_telemetry::Telemetry;
+use std::path::Path;
+use std::process;
 
 fn main() {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+
+    // `schemas <DIR>` writes the JSON Schemas of the chain, inbox, and config files
+    if args.first().map(String::as_str) == Some("schemas") {
+        let dir = match args.get(1) {
+            Some(dir) => Path::new(dir),
+            None => {
+                eprintln!("error: usage: proof-of-work-sim schemas <DIR>");
+                process::exit(2);
+            }
+        };
+        match export_schemas(dir, &schemas()) {
+            Ok(paths) => paths.iter().for_each(|path| println!("{}", path.display())),
+            Err(err) => {
+                eprintln!("error: {}: {}", dir.display(), err);
+                process::exit(1);
+            }
+        }
+        return;
+    }
+
     println!("⛏️  Proof-of-Work Blockchain Simulator\n");
     
-    // Create configuration
-    let config = Config::default();
+    // Load configuration (defaults < pow-sim.toml < POW_SIM_* env < --flags)
+    let config: Config = match ConfigLoader::new("pow-sim").args(args).load() {
+        Ok(config) => config,
+        Err(err) => {
+            eprintln!("error: {}", err);
+            process::exit(2);
+        }
+    };
+    
+    // Log warnings, mined blocks, and panics to <log_dir>/pow-sim.<date>.log
+    let _telemetry = match Telemetry::new("pow-sim").log_dir(&config.log_dir).init() {
+        Ok(guard) => guard,
+        Err(err) => {
+            eprintln!("error: {}", err);
+            process::exit(1);
+        }
+    };
     
     println!("⚙️  Configuration:");
     println!("   Ceiling: {} ({})", config.ceiling, 
              if config.ceiling == i32::MAX { "almost always mines" } else { "challenging" });
-    println!("   Delay: {} second(s)\n", config.delay_seconds);
+    println!("   Delay: {} second(s)", config.delay_seconds);
+    println!("   Logs: {}\n", config.log_dir.display());
     
-    // Create and start a mining node
-    let mut node = Node::new(config);
+    // Create and start a mining node, resuming a saved chain if configured
+    let mut node = match config.data_dir.clone() {
+        Some(dir) => {
+            let store = Store::open(&dir).expect("failed to open data directory");
+            let node = Node::with_store(config, store).expect("failed to load saved chain");
+            println!("💾 Saving chain to {} ({} blocks loaded)\n", dir.display(), node.blockchain.len());
+            node
+        }
+        None => Node::new(config),
+    };
+
+    // Node events in the log file with RUST_LOG=debug (more plugins register here)
+    if let Err(err) = node.plugins.register(TracingPlugin::new("pow-sim-events")) {
+        tracing::warn!("{}", err);
+    }
     node.start_mining();
 }
@@ -0,0 +1,113 @@
+//! Kotlin/Swift bindings (uniffi) for a future mobile explorer
+//!
+//! Generated from the compiled library, see README ("Mobile Bindings").
+//! Mirrors the Python bindings: a chain object that mines with an attempt
+//! budget, so the caller decides how long one call may run.
+
+use std::fmt;
+use std::sync::{Arc, Mutex};
+use crate::blockchain::Blockchain;
+use crate::config::Config;
+use crate::transaction::Transaction;
+use crate::traits::{Hashable, Validatable};
+
+/// Errors surfaced to Kotlin/Swift as exceptions
+#[derive(Debug, uniffi::Error)]
+#[uniffi(flat_error)]
+pub enum ChainError {
+    /// The proof-of-work ceiling was zero or negative
+    InvalidCeiling(i32),
+}
+
+impl fmt::Display for ChainError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ChainError::InvalidCeiling(ceiling) => {
+                write!(f, "ceiling must be positive (got {})", ceiling)
+            }
+        }
+    }
+}
+
+impl std::error::Error for ChainError {}
+
+/// A blockchain handle shared with Kotlin/Swift
+#[derive(uniffi::Object)]
+pub struct MobileChain {
+    // Foreign code can call from any thread
+    inner: Mutex<Blockchain>,
+}
+
+#[uniffi::export]
+impl MobileChain {
+    /// Create a new chain (genesis only) with the given proof-of-work ceiling
+    #[uniffi::constructor]
+    pub fn new(ceiling: i32) -> Result<Arc<Self>, ChainError> {
+        if ceiling <= 0 {
+            return Err(ChainError::InvalidCeiling(ceiling));
+        }
+        // The app drives its own timing, so the mining delay is unused
+        Ok(Arc::new(MobileChain {
+            inner: Mutex::new(Blockchain::new(Config::new(ceiling, 0))),
+        }))
+    }
+
+    /// Try to mine one block, trying at most `max_attempts` nonces
+    /// Returns the number of attempts used, or None if the budget ran out
+    pub fn mine_block(&self, miner_id: String, max_attempts: u64) -> Option<u64> {
+        self.inner.lock().unwrap().mine_block_with_budget(&miner_id, max_attempts)
+    }
+
+    /// Queue a transfer for the next mined block
+    pub fn submit_transaction(&self, sender: String, recipient: String, amount: u64) {
+        self.inner
+            .lock()
+            .unwrap()
+            .submit_transaction(Transaction::new_transfer(sender, recipient, amount));
+    }
+
+    /// Check links and proof-of-work for the whole chain
+    pub fn validate(&self) -> bool {
+        self.inner.lock().unwrap().is_valid()
+    }
+
+    /// Number of blocks in the chain (including genesis)
+    pub fn length(&self) -> u64 {
+        self.inner.lock().unwrap().len() as u64
+    }
+
+    /// Hash of the latest block
+    pub fn latest_hash(&self) -> String {
+        self.inner.lock().unwrap().latest_block().hash()
+    }
+
+    /// The block at `index` as JSON, or None if there is none
+    pub fn block_json(&self, index: u64) -> Option<String> {
+        let chain = self.inner.lock().unwrap();
+        let block = chain.blocks.get(usize::try_from(index).ok()?)?;
+        Some(serde_json::to_string(block).expect("blocks serialize to JSON"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_mobile_chain() {
+        let chain = MobileChain::new(i32::MAX).unwrap();
+        chain.submit_transaction("alice".to_string(), "bob".to_string(), 5);
+        assert!(chain.mine_block("phone".to_string(), 100).is_some());
+        assert_eq!(chain.length(), 2);
+        assert!(chain.validate());
+        assert!(chain.block_json(1).unwrap().starts_with("{\"index\":1"));
+        assert_eq!(chain.block_json(2), None);
+    }
+
+    #[test]
+    fn test_mobile_chain_errors() {
+        assert!(matches!(MobileChain::new(0), Err(ChainError::InvalidCeiling(0))));
+        let chain = MobileChain::new(1).unwrap();
+        assert_eq!(chain.mine_block("phone".to_string(), 5), None);
+    }
+}
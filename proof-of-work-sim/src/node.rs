@@ -1,9 +1,13 @@
-use rand::Rng;
+use sandbox_plugin::PluginHost;
+use sandbox_rng::{Rng, RngHandle};
+use sandbox_store::{Store, StoreError};
 use std::thread;
 use std::time::{Duration, Instant};
 use crate::blockchain::Blockchain;
 use crate::config::Config;
-use crate::traits::Hashable;
+use crate::events::NodeEvent;
+use crate::inbox;
+use crate::traits::{Hashable, Validatable};
 
 /// Represents a mining node
 pub struct Node {
@@ -11,21 +15,38 @@ pub struct Node {
     pub id: String,
     /// The blockchain this node maintains
     pub blockchain: Blockchain,
+    /// Where the chain is saved after each mined block (if anywhere)
+    pub store: Option<Store>,
+    /// Extensions told about started mining, received transactions, and mined blocks
+    pub plugins: PluginHost<NodeEvent>,
 }
 
 impl Node {
     /// Create a new node with random ID
     pub fn new(config: Config) -> Self {
-        let id = Self::generate_id();
+        let mut blockchain = Blockchain::new(config);
         Node {
-            id,
-            blockchain: Blockchain::new(config),
+            id: Self::generate_id(&mut blockchain.rng),
+            blockchain,
+            store: None,
+            plugins: PluginHost::new(),
         }
     }
 
+    /// Create a node that resumes the chain saved in `store`
+    /// and keeps saving it as new blocks are mined
+    pub fn with_store(config: Config, store: Store) -> Result<Self, StoreError> {
+        let mut blockchain = Blockchain::load(&store, config)?;
+        Ok(Node {
+            id: Self::generate_id(&mut blockchain.rng),
+            blockchain,
+            store: Some(store),
+            plugins: PluginHost::new(),
+        })
+    }
+
     /// Generate a random node ID (e.g., "node-a3f2")
-    pub fn generate_id() -> String {
-        let mut rng = rand::thread_rng();
+    pub fn generate_id(rng: &mut RngHandle) -> String {
         let hex: String = (0..4)
             .map(|_| format!("{:x}", rng.gen_range(0..16)))
             .collect();
@@ -35,16 +56,16 @@ impl Node {
     /// Start mining blocks
     pub fn start_mining(&mut self) {
         println!("🚀 {} started mining...\n", self.id);
+        self.plugins.emit(&NodeEvent::Started {
+            node_id: self.id.clone(),
+            height: self.blockchain.len(),
+        });
         
         loop {
             let start_time = Instant::now();
-            
-            // Try to mine a block (keep trying until successful)
-            while !self.blockchain.try_mine_block(&self.id) {
-                // Keep trying different nonces
-            }
-            
-            // Block mined! Calculate remaining delay time
+            self.mine_next_block();
+
+            // Calculate remaining delay time
             let elapsed = start_time.elapsed();
             let delay = Duration::from_secs(self.blockchain.config.delay_seconds);
             
@@ -57,6 +78,49 @@ impl Node {
         }
     }
 
+    /// Collect the inbox, mine one block, save the chain, and tell the plugins
+    pub fn mine_next_block(&mut self) {
+        self.collect_inbox();
+
+        // Try to mine a block (keep trying until successful)
+        while !self.blockchain.try_mine_block(&self.id) {
+            // Keep trying different nonces
+        }
+
+        // Block mined! Persist it before anything else
+        let block = self.blockchain.latest_block();
+        tracing::info!(node = %self.id, index = block.index, nonce = block.nonce, "mined block");
+        if let Some(store) = &self.store {
+            if let Err(err) = self.blockchain.save(store) {
+                tracing::warn!(node = %self.id, "failed to save chain: {}", err);
+            }
+        }
+        self.plugins.emit(&NodeEvent::BlockMined {
+            node_id: self.id.clone(),
+            block: self.blockchain.latest_block().clone(),
+        });
+    }
+
+    /// Move transactions other programs submitted (see inbox.rs) into the mempool
+    pub fn collect_inbox(&mut self) {
+        let Some(store) = &self.store else {
+            return;
+        };
+        match inbox::drain(store) {
+            Ok(transactions) => {
+                for tx in transactions {
+                    tracing::info!(node = %self.id, sender = ?tx.sender, "transaction received");
+                    self.plugins.emit(&NodeEvent::TransactionReceived {
+                        node_id: self.id.clone(),
+                        transaction: tx.clone(),
+                    });
+                    self.blockchain.submit_transaction(tx);
+                }
+            }
+            Err(err) => tracing::warn!(node = %self.id, "failed to read inbox: {}", err),
+        }
+    }
+
     /// Print the last 3 blocks in the chain
     pub fn print_chain(&self) {
         let blocks = self.blockchain.last_n_blocks(3);
@@ -76,7 +140,7 @@ impl Node {
                 block.index,
                 hash_short,
                 nonce_display,
-                if block.is_valid { "✅" } else { "❌" }
+                if block.is_valid() { "✅" } else { "❌" }
             );
         }
         
@@ -87,6 +151,22 @@ impl Node {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::transaction::Transaction;
+    use sandbox_plugin::Plugin;
+    use std::sync::{Arc, Mutex};
+
+    /// Keeps every event it's sent
+    struct Recorder(Arc<Mutex<Vec<NodeEvent>>>);
+
+    impl Plugin<NodeEvent> for Recorder {
+        fn name(&self) -> &str {
+            "recorder"
+        }
+
+        fn on_event(&mut self, event: &NodeEvent) {
+            self.0.lock().unwrap().push(event.clone());
+        }
+    }
 
     #[test]
     fn test_node_creation() {
@@ -97,9 +177,64 @@ mod tests {
         assert_eq!(node.blockchain.len(), 1); // Genesis block
     }
 
+    #[test]
+    fn test_node_with_store_resumes_chain() {
+        let dir = std::env::temp_dir().join(format!("pow-sim-node-{}", std::process::id()));
+        let store = Store::open(&dir).unwrap();
+
+        let mut blockchain = Blockchain::new(Config::default());
+        while !blockchain.try_mine_block("miner1") {}
+        blockchain.save(&store).unwrap();
+
+        let node = Node::with_store(Config::default(), store).unwrap();
+        assert_eq!(node.blockchain.len(), 2);
+        assert!(node.store.is_some());
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_collect_inbox() {
+        let dir = tempfile::tempdir().unwrap();
+        let store = Store::open(dir.path()).unwrap();
+        let tx = Transaction::new_transfer("hangman".to_string(), "hangman".to_string(), 0);
+        inbox::submit(&store, &tx.with_memo("hello")).unwrap();
+
+        let mut node = Node::with_store(Config::default(), store).unwrap();
+        node.collect_inbox();
+        assert_eq!(node.blockchain.mempool.len(), 1);
+        assert_eq!(node.blockchain.mempool[0].memo.as_deref(), Some("hello"));
+    }
+
+    #[test]
+    fn test_plugins_see_transactions_and_blocks() {
+        let dir = tempfile::tempdir().unwrap();
+        let store = Store::open(dir.path()).unwrap();
+        let tx = Transaction::new_transfer("hangman".to_string(), "hangman".to_string(), 0);
+        inbox::submit(&store, &tx).unwrap();
+
+        let events = Arc::new(Mutex::new(Vec::new()));
+        let mut node = Node::with_store(Config::default(), store).unwrap();
+        node.plugins.register(Recorder(events.clone())).unwrap();
+        node.mine_next_block();
+
+        let events = events.lock().unwrap();
+        assert_eq!(events.len(), 2);
+        assert!(matches!(&events[0], NodeEvent::TransactionReceived { transaction, .. } if transaction.memo.is_none()));
+        match &events[1] {
+            NodeEvent::BlockMined { node_id, block } => {
+                assert_eq!(node_id, &node.id);
+                assert_eq!(block.index, 1);
+                // The coinbase plus the inbox transaction
+                assert_eq!(block.transactions.len(), 2);
+            }
+            other => panic!("expected a mined block, got {:?}", other),
+        }
+    }
+
     #[test]
     fn test_generate_id_format() {
-        let id = Node::generate_id();
+        let id = Node::generate_id(&mut RngHandle::from_entropy());
         assert!(id.starts_with("node-"));
         assert_eq!(id.len(), 9);
         
@@ -110,10 +245,26 @@ mod tests {
 
     #[test]
     fn test_generate_id_uniqueness() {
-        let id1 = Node::generate_id();
-        let id2 = Node::generate_id();
-        // Very unlikely to be the same (1 in 65536 chance)
-        // But this test could theoretically fail
+        // Seeded, so this always draws the same two (different) ids
+        let mut rng = RngHandle::seeded(5);
+        let id1 = Node::generate_id(&mut rng);
+        let id2 = Node::generate_id(&mut rng);
         assert_ne!(id1, id2);
     }
+
+    #[test]
+    fn test_seeded_nodes_replay() {
+        let config = Config {
+            seed: Some(42),
+            ..Config::default()
+        };
+        let mut a = Node::new(config.clone());
+        let mut b = Node::new(config);
+        assert_eq!(a.id, b.id);
+
+        while !a.blockchain.try_mine_block(&a.id) {}
+        while !b.blockchain.try_mine_block(&b.id) {}
+        assert_eq!(a.blockchain.latest_block().nonce, b.blockchain.latest_block().nonce);
+        assert_eq!(a.blockchain.latest_block().hash(), b.blockchain.latest_block().hash());
+    }
 }
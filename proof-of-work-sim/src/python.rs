@@ -0,0 +1,156 @@
+use pyo3::exceptions::PyValueError;
+use pyo3::prelude::*;
+use pyo3::types::PyDict;
+use crate::block::Block;
+use crate::blockchain::Blockchain;
+use crate::config::Config;
+use crate::transaction::Transaction;
+use crate::traits::{Hashable, Validatable};
+
+/// Nonces tried by `mine_block` when Python doesn't pass a budget
+const DEFAULT_MAX_ATTEMPTS: u64 = 1_000_000;
+
+/// A blockchain handle exported to Python
+///
+/// Wraps the same `Blockchain` the terminal simulator uses, so experiments
+/// scripted in Python run the exact same mining and validation rules.
+#[pyclass(name = "Chain")]
+pub struct PyChain {
+    inner: Blockchain,
+}
+
+#[pymethods]
+impl PyChain {
+    /// Create a new chain (genesis only) with the given proof-of-work ceiling
+    #[new]
+    #[pyo3(signature = (ceiling = i32::MAX))]
+    fn new(ceiling: i32) -> PyResult<Self> {
+        if ceiling <= 0 {
+            return Err(PyValueError::new_err("ceiling must be positive"));
+        }
+        // Python drives its own timing, so the mining delay is unused
+        Ok(PyChain {
+            inner: Blockchain::new(Config::new(ceiling, 0)),
+        })
+    }
+
+    /// Try to mine one block, trying at most `max_attempts` nonces
+    /// Returns the number of attempts used, or None if the budget ran out
+    #[pyo3(signature = (miner_id, max_attempts = DEFAULT_MAX_ATTEMPTS))]
+    fn mine_block(&mut self, py: Python<'_>, miner_id: &str, max_attempts: u64) -> Option<u64> {
+        // Mining doesn't touch Python objects, so let other Python threads run
+        py.allow_threads(|| self.inner.mine_block_with_budget(miner_id, max_attempts))
+    }
+
+    /// Queue a transfer for the next mined block
+    fn submit_transaction(&mut self, sender: String, recipient: String, amount: u64) {
+        self.inner
+            .submit_transaction(Transaction::new_transfer(sender, recipient, amount));
+    }
+
+    /// Check links and proof-of-work for the whole chain
+    fn validate(&self) -> bool {
+        self.inner.is_valid()
+    }
+
+    /// Number of blocks in the chain (including genesis)
+    fn __len__(&self) -> usize {
+        self.inner.len()
+    }
+
+    /// Proof-of-work ceiling the chain mines against
+    #[getter]
+    fn ceiling(&self) -> i32 {
+        self.inner.config.ceiling
+    }
+
+    /// Number of transactions waiting to be mined
+    #[getter]
+    fn pending(&self) -> usize {
+        self.inner.mempool.len()
+    }
+
+    /// Hash of the latest block
+    fn latest_hash(&self) -> String {
+        self.inner.latest_block().hash()
+    }
+
+    /// The block at `index` as a dict, or None if there is none
+    fn block<'py>(&self, py: Python<'py>, index: usize) -> PyResult<Option<Bound<'py, PyDict>>> {
+        self.inner
+            .blocks
+            .get(index)
+            .map(|block| block_to_dict(py, block))
+            .transpose()
+    }
+
+    /// Every block as a list of dicts, ready for `pandas.DataFrame(...)`
+    fn blocks<'py>(&self, py: Python<'py>) -> PyResult<Vec<Bound<'py, PyDict>>> {
+        self.inner
+            .blocks
+            .iter()
+            .map(|block| block_to_dict(py, block))
+            .collect()
+    }
+
+    fn __repr__(&self) -> String {
+        format!("Chain(ceiling={}, length={})", self.inner.config.ceiling, self.inner.len())
+    }
+}
+
+/// Convert a block to a dict via its JSON form, plus its hash
+fn block_to_dict<'py>(py: Python<'py>, block: &Block) -> PyResult<Bound<'py, PyDict>> {
+    let json = serde_json::to_string(block).expect("blocks serialize to JSON");
+    let dict = py
+        .import("json")?
+        .call_method1("loads", (json,))?
+        .downcast_into::<PyDict>()?;
+    dict.set_item("hash", block.hash())?;
+    Ok(dict)
+}
+
+/// The `proof_of_work_sim` Python module
+#[pymodule]
+fn proof_of_work_sim(m: &Bound<'_, PyModule>) -> PyResult<()> {
+    m.add_class::<PyChain>()?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_py_chain_mining_and_transactions() {
+        pyo3::prepare_freethreaded_python();
+        Python::with_gil(|py| {
+            let mut chain = PyChain::new(i32::MAX).unwrap();
+            chain.submit_transaction("alice".to_string(), "bob".to_string(), 5);
+            assert_eq!(chain.pending(), 1);
+
+            assert!(chain.mine_block(py, "python", 100).is_some());
+            assert_eq!(chain.__len__(), 2);
+            assert_eq!(chain.pending(), 0);
+            assert!(chain.validate());
+
+            let block = chain.block(py, 1).unwrap().unwrap();
+            let index: u64 = block.get_item("index").unwrap().unwrap().extract().unwrap();
+            let hash: String = block.get_item("hash").unwrap().unwrap().extract().unwrap();
+            assert_eq!(index, 1);
+            assert_eq!(hash, chain.latest_hash());
+            assert!(chain.block(py, 2).unwrap().is_none());
+            assert_eq!(chain.blocks(py).unwrap().len(), 2);
+        });
+    }
+
+    #[test]
+    fn test_py_chain_budget_and_bad_ceiling() {
+        pyo3::prepare_freethreaded_python();
+        Python::with_gil(|py| {
+            let mut chain = PyChain::new(1).unwrap();
+            assert_eq!(chain.mine_block(py, "python", 5), None);
+            assert_eq!(chain.__len__(), 1);
+            assert!(PyChain::new(0).is_err());
+        });
+    }
+}
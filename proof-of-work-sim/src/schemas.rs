@@ -0,0 +1,78 @@
+//! JSON Schemas of the simulator's files
+//!
+//! Generated from the Rust types, so they can't drift from what the simulator
+//! actually reads. `proof-of-work-sim schemas <DIR>` writes them out for
+//! editors and other tools; saved chains and inbox entries are also checked
+//! against them whenever they're loaded (see sandbox-store).
+
+use schemars::{schema_for, Schema};
+use sandbox_store::document_schema;
+use crate::blockchain::ChainSnapshot;
+use crate::config::Config;
+use crate::inbox::InboxEntry;
+
+/// Every file format with its schema name:
+/// - `chain`: the saved chain (`<data_dir>/chain.json`)
+/// - `inbox`: a submitted transaction waiting in `<data_dir>/inbox-*.json`
+/// - `config`: `pow-sim.toml` (TOML, described by the same schema)
+pub fn schemas() -> Vec<(&'static str, Schema)> {
+    vec![
+        ("chain", document_schema::<ChainSnapshot>()),
+        ("inbox", document_schema::<InboxEntry>()),
+        ("config", schema_for!(Config)),
+    ]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::blockchain::Blockchain;
+    use crate::transaction::Transaction;
+    use sandbox_store::{export_schemas, Store, StoreError};
+    use std::fs;
+
+    #[test]
+    fn test_saved_chain_matches_schema() {
+        let dir = tempfile::tempdir().unwrap();
+        let store = Store::open(dir.path()).unwrap();
+        let mut blockchain = Blockchain::new(Config::default());
+        blockchain.submit_transaction(Transaction::new_transfer("alice".to_string(), "bob".to_string(), 5).with_memo("hi"));
+        blockchain.mine_block_with_budget("miner", 1000).unwrap();
+        blockchain.save(&store).unwrap();
+
+        let schema = document_schema::<ChainSnapshot>();
+        let file: serde_json::Value = serde_json::from_slice(&fs::read(dir.path().join("chain.json")).unwrap()).unwrap();
+        assert_eq!(sandbox_store::validate(&schema, &file), Ok(()));
+    }
+
+    #[test]
+    fn test_edited_chain_is_rejected_with_location() {
+        let dir = tempfile::tempdir().unwrap();
+        let store = Store::open(dir.path()).unwrap();
+        Blockchain::new(Config::default()).save(&store).unwrap();
+
+        let path = dir.path().join("chain.json");
+        let mut file: serde_json::Value = serde_json::from_slice(&fs::read(&path).unwrap()).unwrap();
+        file["data"]["blocks"][0]["nonce"] = "lucky".into();
+        // (edited by hand, so without the checksum, or it would be taken as damaged)
+        file.as_object_mut().unwrap().remove("checksum");
+        fs::write(&path, file.to_string()).unwrap();
+
+        match Blockchain::load(&store, Config::default()) {
+            Err(StoreError::Invalid { violations, .. }) => {
+                assert_eq!(violations[0].path, "/data/blocks/0/nonce");
+            }
+            other => panic!("expected a schema violation, got {:?}", other.err()),
+        }
+    }
+
+    #[test]
+    fn test_export() {
+        let dir = tempfile::tempdir().unwrap();
+        let paths = export_schemas(dir.path(), &schemas()).unwrap();
+        assert_eq!(paths.len(), 3);
+        let config: serde_json::Value = serde_json::from_slice(&fs::read(dir.path().join("config.schema.json")).unwrap()).unwrap();
+        assert_eq!(config["title"], "Config");
+        assert_eq!(config["additionalProperties"], false);
+    }
+}
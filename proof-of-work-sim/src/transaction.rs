@@ -1,13 +1,21 @@
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
 use sha2::{Digest, Sha256};
 use crate::traits::Hashable;
 
-/// Represents a coinbase transaction (block reward)
-#[derive(Debug, Clone)]
+/// Represents a transaction: a coinbase (block reward) or a transfer
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct Transaction {
-    /// Coinbase reward amount
+    /// Amount transferred (or the reward, for coinbase)
     pub amount: u64,
-    /// Recipient (miner)
+    /// Recipient (the miner, for coinbase)
     pub recipient: String,
+    /// Sender of a transfer (None for coinbase)
+    #[serde(default)]
+    pub sender: Option<String>,
+    /// Free-form data carried by a transfer (e.g. hangman game results)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub memo: Option<String>,
 }
 
 impl Transaction {
@@ -15,14 +23,45 @@ impl Transaction {
         Transaction {
             amount: 50, // Block reward
             recipient,
+            sender: None,
+            memo: None,
         }
     }
+
+    /// Create a transfer from `sender` to `recipient`
+    pub fn new_transfer(sender: String, recipient: String, amount: u64) -> Self {
+        Transaction {
+            amount,
+            recipient,
+            sender: Some(sender),
+            memo: None,
+        }
+    }
+
+    /// Attach a memo to this transaction
+    pub fn with_memo(mut self, memo: impl Into<String>) -> Self {
+        self.memo = Some(memo.into());
+        self
+    }
+
+    /// Check if this is a coinbase (block reward) transaction
+    pub fn is_coinbase(&self) -> bool {
+        self.sender.is_none()
+    }
 }
 
 impl Hashable for Transaction {
     fn hash(&self) -> String {
         let mut hasher = Sha256::new();
-        hasher.update(format!("{}:{}", self.amount, self.recipient));
+        // Coinbase keeps the original "amount:recipient" format,
+        // and transfers without a memo keep theirs
+        match (&self.sender, &self.memo) {
+            (None, _) => hasher.update(format!("{}:{}", self.amount, self.recipient)),
+            (Some(sender), None) => hasher.update(format!("{}:{}:{}", sender, self.amount, self.recipient)),
+            (Some(sender), Some(memo)) => {
+                hasher.update(format!("{}:{}:{}:{}", sender, self.amount, self.recipient, memo))
+            }
+        }
         format!("{:x}", hasher.finalize())
     }
 }
@@ -38,6 +77,37 @@ mod tests {
         assert_eq!(tx.recipient, "miner1");
     }
 
+    #[test]
+    fn test_transfer_creation() {
+        let tx = Transaction::new_transfer("alice".to_string(), "bob".to_string(), 10);
+        assert_eq!(tx.amount, 10);
+        assert_eq!(tx.sender.as_deref(), Some("alice"));
+        assert!(!tx.is_coinbase());
+        assert!(Transaction::new_coinbase("miner1".to_string()).is_coinbase());
+    }
+
+    #[test]
+    fn test_transfer_hash_includes_sender() {
+        let tx1 = Transaction::new_transfer("alice".to_string(), "bob".to_string(), 10);
+        let tx2 = Transaction::new_transfer("carol".to_string(), "bob".to_string(), 10);
+        assert_ne!(tx1.hash(), tx2.hash());
+    }
+
+    #[test]
+    fn test_memo() {
+        let plain = Transaction::new_transfer("alice".to_string(), "bob".to_string(), 0);
+        let with_memo = plain.clone().with_memo("hello");
+        assert_eq!(with_memo.memo.as_deref(), Some("hello"));
+        assert_ne!(plain.hash(), with_memo.hash());
+        assert_ne!(with_memo.hash(), plain.clone().with_memo("bye").hash());
+
+        // No memo, no "memo" field (saved chains look the same as before)
+        let json = serde_json::to_string(&plain).unwrap();
+        assert!(!json.contains("memo"));
+        let parsed: Transaction = serde_json::from_str(&serde_json::to_string(&with_memo).unwrap()).unwrap();
+        assert_eq!(parsed.memo.as_deref(), Some("hello"));
+    }
+
     #[test]
     fn test_transaction_hash() {
         let tx = Transaction::new_coinbase("miner1".to_string());
@@ -0,0 +1,101 @@
+use wasm_bindgen::prelude::*;
+use crate::blockchain::Blockchain;
+use crate::config::Config;
+use crate::traits::{Hashable, Validatable};
+
+/// A blockchain handle exported to JavaScript
+///
+/// Wraps the same `Blockchain` the terminal simulator uses, so a browser
+/// visualizer runs the exact same mining and validation rules.
+#[wasm_bindgen]
+pub struct WasmChain {
+    inner: Blockchain,
+}
+
+#[wasm_bindgen]
+impl WasmChain {
+    /// Create a new chain (genesis only) with the given proof-of-work ceiling
+    #[wasm_bindgen(constructor)]
+    pub fn new(ceiling: i32) -> WasmChain {
+        // The browser drives its own timing, so the mining delay is unused
+        WasmChain {
+            inner: Blockchain::new(Config::new(ceiling, 0)),
+        }
+    }
+
+    /// Try to mine one block, trying at most `max_attempts` nonces
+    /// Returns the number of attempts used, or undefined if the budget ran out
+    #[wasm_bindgen(js_name = mineBlock)]
+    pub fn mine_block(&mut self, miner_id: &str, max_attempts: u32) -> Option<u32> {
+        self.inner
+            .mine_block_with_budget(miner_id, max_attempts as u64)
+            .map(|attempts| attempts as u32)
+    }
+
+    /// Check links and proof-of-work for the whole chain
+    pub fn validate(&self) -> bool {
+        self.inner.is_valid()
+    }
+
+    /// Number of blocks in the chain (including genesis)
+    #[wasm_bindgen(getter)]
+    pub fn length(&self) -> usize {
+        self.inner.len()
+    }
+
+    /// Proof-of-work ceiling the chain mines against
+    #[wasm_bindgen(getter)]
+    pub fn ceiling(&self) -> i32 {
+        self.inner.config.ceiling
+    }
+
+    /// Hash of the latest block
+    #[wasm_bindgen(js_name = latestHash)]
+    pub fn latest_hash(&self) -> String {
+        self.inner.latest_block().hash()
+    }
+
+    /// The block at `index` as JSON, or undefined if there is none
+    #[wasm_bindgen(js_name = blockJson)]
+    pub fn block_json(&self, index: usize) -> Option<String> {
+        self.inner
+            .blocks
+            .get(index)
+            .map(|block| serde_json::to_string(block).expect("blocks serialize to JSON"))
+    }
+
+    /// The last `n` blocks as a JSON array
+    #[wasm_bindgen(js_name = lastBlocksJson)]
+    pub fn last_blocks_json(&self, n: usize) -> String {
+        serde_json::to_string(&self.inner.last_n_blocks(n)).expect("blocks serialize to JSON")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_wasm_chain_mining() {
+        let mut chain = WasmChain::new(i32::MAX);
+        assert!(chain.mine_block("browser", 100).is_some());
+        assert_eq!(chain.length(), 2);
+        assert!(chain.validate());
+    }
+
+    #[test]
+    fn test_wasm_chain_budget_exhausted() {
+        let mut chain = WasmChain::new(0);
+        assert_eq!(chain.mine_block("browser", 5), None);
+        assert_eq!(chain.length(), 1);
+    }
+
+    #[test]
+    fn test_wasm_chain_block_json() {
+        let chain = WasmChain::new(i32::MAX);
+        let json = chain.block_json(0).unwrap();
+        assert!(json.contains("\"index\":0"));
+        assert!(chain.block_json(1).is_none());
+        assert!(chain.last_blocks_json(3).starts_with('['));
+    }
+}
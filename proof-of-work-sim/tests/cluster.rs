@@ -0,0 +1,77 @@
+//! End-to-end tests that run the simulator as separate processes
+//! (see harness/ for how nodes are started and their chains read back)
+
+use std::time::Duration;
+use pow_sim_harness::{Cluster, HarnessError};
+use proof_of_work_sim::traits::{Hashable, Validatable};
+use proof_of_work_sim::transaction::Transaction;
+
+const SIMULATOR: &str = env!("CARGO_BIN_EXE_proof-of-work-sim");
+const TIMEOUT: Duration = Duration::from_secs(30);
+
+#[test]
+fn test_nodes_mine_valid_chains() {
+    let mut cluster = Cluster::builder(SIMULATOR).nodes(3).spawn().unwrap();
+    cluster.wait_for_len(4, TIMEOUT).unwrap();
+    let chains = cluster.stop().unwrap();
+
+    assert_eq!(chains.len(), 3);
+    for chain in &chains {
+        assert!(chain.len() >= 4);
+        assert!(chain.is_valid());
+    }
+
+    // Without networking every node builds its own chain after genesis
+    assert_eq!(chains[0].blocks[0].hash(), chains[1].blocks[0].hash());
+    assert_ne!(chains[0].blocks[1].hash(), chains[1].blocks[1].hash());
+}
+
+#[test]
+fn test_killed_node_resumes_saved_chain() {
+    let mut cluster = Cluster::builder(SIMULATOR).nodes(1).spawn().unwrap();
+    cluster.wait_for_len(3, TIMEOUT).unwrap();
+
+    // A kill can land mid-save, the chain on disk must still be intact
+    cluster.node_mut(0).kill();
+    let before = cluster.nodes()[0].chain().unwrap();
+    assert!(before.is_valid());
+
+    cluster.node_mut(0).restart().unwrap();
+    let after = cluster
+        .wait_until(TIMEOUT, |chains| chains[0].len() > before.len())
+        .unwrap()
+        .remove(0);
+
+    assert!(after.is_valid());
+    for (old, new) in before.blocks.iter().zip(&after.blocks) {
+        assert_eq!(old.hash(), new.hash());
+    }
+}
+
+#[test]
+fn test_running_node_mines_submitted_transactions() {
+    let mut cluster = Cluster::builder(SIMULATOR).nodes(1).spawn().unwrap();
+    cluster.wait_for_len(2, TIMEOUT).unwrap();
+
+    let tx = Transaction::new_transfer("alice".to_string(), "bob".to_string(), 7).with_memo("hello");
+    cluster.nodes()[0].submit_transaction(&tx).unwrap();
+    let chain = cluster
+        .wait_until(TIMEOUT, |chains| {
+            chains[0].blocks.iter().any(|block| block.transactions.iter().any(|t| t.hash() == tx.hash()))
+        })
+        .unwrap()
+        .remove(0);
+    assert!(chain.is_valid());
+}
+
+#[test]
+fn test_invalid_config_exits() {
+    let mut cluster = Cluster::builder(SIMULATOR).nodes(1).ceiling(0).spawn().unwrap();
+    match cluster.wait_for_len(2, TIMEOUT) {
+        Err(HarnessError::NodeExited { status, stderr, .. }) => {
+            assert_eq!(status.code(), Some(2));
+            assert!(stderr.contains("ceiling must be positive"));
+        }
+        other => panic!("expected the node to exit, got {:?}", other.map(|chains| chains.len())),
+    }
+}